@@ -0,0 +1,169 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::config::load_config;
+use crate::commands::upload_system::{UploadQueue, UploadQueueState, UploadStatus};
+use crate::tray::TRAY_ID;
+
+/// 失敗があるときに件数の代わりに表示する記号
+const ALERT_LABEL: &str = "!";
+
+/// Dock・トレイに表示するバッジの状態
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BadgeState {
+    /// バッジなし（未完了0件）
+    Hidden,
+    /// 未完了のアップロード件数
+    Count(usize),
+    /// 失敗したアップロードがある
+    Alert,
+}
+
+impl BadgeState {
+    /// バッジに表示する文字列
+    pub fn label(&self) -> Option<String> {
+        match self {
+            BadgeState::Hidden => None,
+            BadgeState::Count(count) => Some(count.to_string()),
+            BadgeState::Alert => Some(ALERT_LABEL.to_string()),
+        }
+    }
+}
+
+/// 最後に反映したバッジ状態（同じ状態での再描画を避ける）
+pub struct BadgeCache {
+    enabled: AtomicBool,
+    last_state: Mutex<Option<BadgeState>>,
+}
+
+impl BadgeCache {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled: AtomicBool::new(enabled),
+            last_state: Mutex::new(None),
+        }
+    }
+}
+
+/// キューの状態からバッジ状態を算出
+pub fn compute_badge_state(queue: &UploadQueue) -> BadgeState {
+    let has_failed = queue.items.iter().any(|item| item.status == UploadStatus::Failed);
+    if has_failed {
+        return BadgeState::Alert;
+    }
+
+    let unfinished = queue.items.iter()
+        .filter(|item| matches!(
+            item.status,
+            UploadStatus::Pending | UploadStatus::InProgress | UploadStatus::Paused
+        ))
+        .count();
+    if unfinished == 0 {
+        BadgeState::Hidden
+    } else {
+        BadgeState::Count(unfinished)
+    }
+}
+
+/// キューの状態をバッジに反映（状態が変わったときのみ描画）
+pub fn update_badge(app: &AppHandle, queue_state: &UploadQueueState) {
+    let Some(cache) = app.try_state::<BadgeCache>() else {
+        return;
+    };
+
+    let state = if cache.enabled.load(Ordering::Relaxed) {
+        match queue_state.lock() {
+            Ok(queue) => compute_badge_state(&queue),
+            Err(e) => {
+                log::error!("Failed to lock upload queue for badge: {}", e);
+                return;
+            }
+        }
+    } else {
+        BadgeState::Hidden
+    };
+
+    if let Ok(mut last_state) = cache.last_state.lock() {
+        if last_state.as_ref() == Some(&state) {
+            return;
+        }
+        *last_state = Some(state.clone());
+    }
+
+    apply_badge(app, &state);
+}
+
+/// 設定を読み直してバッジを再描画（設定変更時・ウィンドウイベント時）
+pub fn refresh_badge(app: &AppHandle) {
+    let Some(cache) = app.try_state::<BadgeCache>() else {
+        return;
+    };
+    if let Ok(config) = load_config(app) {
+        cache.enabled.store(config.notification_settings.show_badge, Ordering::Relaxed);
+    }
+    if let Ok(mut last_state) = cache.last_state.lock() {
+        *last_state = None;
+    }
+    let queue_state = app.state::<UploadQueueState>();
+    update_badge(app, queue_state.inner());
+}
+
+fn apply_badge(app: &AppHandle, state: &BadgeState) {
+    let label = state.label();
+
+    // Dockのバッジ表示はmacOSのみ
+    #[cfg(target_os = "macos")]
+    if let Some(window) = app.get_webview_window("main") {
+        if let Err(e) = window.set_badge_label(label.clone()) {
+            log::error!("Failed to update dock badge: {}", e);
+        }
+    }
+
+    // メニューバーのトレイアイコン横にも件数を表示
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        if let Err(e) = tray.set_title(label) {
+            log::error!("Failed to update tray title: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::upload_system::UploadItem;
+
+    fn create_item(status: UploadStatus) -> UploadItem {
+        UploadItem::for_test("/tmp/test.mov", status)
+    }
+
+    #[test]
+    fn test_badge_hidden_when_nothing_left() {
+        let mut queue = UploadQueue::new();
+        assert_eq!(compute_badge_state(&queue), BadgeState::Hidden);
+
+        queue.items.push(create_item(UploadStatus::Completed));
+        queue.items.push(create_item(UploadStatus::Cancelled));
+        assert_eq!(compute_badge_state(&queue), BadgeState::Hidden);
+        assert_eq!(BadgeState::Hidden.label(), None);
+    }
+
+    #[test]
+    fn test_badge_counts_unfinished_items() {
+        let mut queue = UploadQueue::new();
+        queue.items.push(create_item(UploadStatus::Pending));
+        queue.items.push(create_item(UploadStatus::InProgress));
+        queue.items.push(create_item(UploadStatus::Completed));
+        assert_eq!(compute_badge_state(&queue), BadgeState::Count(2));
+        assert_eq!(BadgeState::Count(2).label(), Some("2".to_string()));
+    }
+
+    #[test]
+    fn test_badge_alert_on_failure() {
+        let mut queue = UploadQueue::new();
+        queue.items.push(create_item(UploadStatus::Pending));
+        queue.items.push(create_item(UploadStatus::Failed));
+        assert_eq!(compute_badge_state(&queue), BadgeState::Alert);
+        assert_eq!(BadgeState::Alert.label(), Some("!".to_string()));
+    }
+}
@@ -0,0 +1,606 @@
+use std::path::{Path, PathBuf};
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::commands::aws_auth::{load_aws_credentials_secure, verify_aws_credentials, AwsCredentials};
+use crate::commands::aws_operations::{create_s3_client_for_credentials, request_restore};
+use crate::commands::mock_aws::{is_mock_aws_mode, set_mock_aws_mode, MOCK_LABEL};
+use crate::commands::config::{load_config_from_path, AppConfig};
+use crate::commands::upload_system::{
+    generate_s3_key, upload_file_to_s3, S3KeyConfig, UploadConfig, UploadItem, UploadProgress, QUEUE_STATE_FILE,
+};
+use crate::internal::InternalError;
+
+/// CLIモードで扱うサブコマンド（グローバルオプションを除いた先頭の引数がこれ以外のときはGUIを起動する）
+const SUBCOMMANDS: [&str; 4] = ["upload", "status", "restore", "help"];
+
+/// サブコマンドより前に置ける値なしのグローバルオプション
+const GLOBAL_FLAGS: [&str; 3] = ["--json", "--verbose", "-v"];
+/// サブコマンドより前に置ける値付きのグローバルオプション（`--profile x`と`--profile=x`の両方）
+const GLOBAL_OPTIONS_WITH_VALUE: [&str; 2] = ["--profile", "--region"];
+
+/// アプリのデータディレクトリ名（tauri.conf.jsonのidentifier）
+pub(crate) const APP_IDENTIFIER: &str = "com.civictech.reelvault";
+
+/// Keychainプロファイルが指定・設定されていない場合のプロファイル名
+const DEFAULT_PROFILE: &str = "default";
+
+const EXIT_SUCCESS: i32 = 0;
+const EXIT_FAILURE: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+
+/// ReelVault ヘッドレスCLI
+#[derive(Debug, Parser)]
+#[command(name = "reelvault", version, about = "GUIを起動せずにReelVaultのアップロード・復元を実行します")]
+pub struct Cli {
+    /// 進捗と結果をJSON Lines形式で出力
+    #[arg(long, global = true)]
+    json: bool,
+    /// Keychainに保存した認証情報のプロファイル名（省略時は環境変数を優先）
+    #[arg(long, global = true)]
+    profile: Option<String>,
+    /// AWSリージョン（省略時は環境変数・設定ファイルの値）
+    #[arg(long, global = true)]
+    region: Option<String>,
+    /// 詳細ログを標準エラーに出力
+    #[arg(long, short, global = true)]
+    verbose: bool,
+    #[command(subcommand)]
+    command: CliCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum CliCommand {
+    /// ファイル（ディレクトリの場合は配下の全ファイル）をアップロード
+    Upload {
+        path: PathBuf,
+        /// アップロード先バケット（省略時は設定ファイルのデフォルトバケット）
+        #[arg(long)]
+        bucket: Option<String>,
+        /// S3キーのプレフィックス
+        #[arg(long)]
+        prefix: Option<String>,
+    },
+    /// 認証状態と未完了のアップロードを表示
+    Status {
+        #[arg(long)]
+        bucket: Option<String>,
+    },
+    /// Deep Archiveからファイルを復元
+    Restore {
+        key: String,
+        #[arg(long)]
+        bucket: Option<String>,
+        /// 復元ティア（Standard / Expedited / Bulk）
+        #[arg(long, default_value = "Standard")]
+        tier: String,
+        /// 復元済みコピーの保持日数（未指定は設定の既定値）
+        #[arg(long)]
+        days: Option<u32>,
+    },
+}
+
+/// 認証情報の取得元
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CredentialSource {
+    Environment,
+    Keychain { profile: String },
+}
+
+/// statusサブコマンドの結果
+#[derive(Debug, Serialize)]
+struct StatusReport {
+    credential_source: Option<CredentialSource>,
+    authenticated: bool,
+    identity_arn: Option<String>,
+    message: String,
+    bucket: Option<String>,
+    unfinished_uploads: usize,
+}
+
+/// 起動引数がCLIのサブコマンドであるか
+pub fn is_cli_invocation(args: &[String]) -> bool {
+    let mut rest = args.iter().skip(1).map(String::as_str);
+    while let Some(arg) = rest.next() {
+        if GLOBAL_FLAGS.contains(&arg) {
+            continue;
+        }
+        if GLOBAL_OPTIONS_WITH_VALUE.contains(&arg) {
+            rest.next();
+            continue;
+        }
+        let is_option_with_inline_value = GLOBAL_OPTIONS_WITH_VALUE
+            .iter()
+            .any(|option| arg.strip_prefix(option).is_some_and(|value| value.starts_with('=')));
+        if is_option_with_inline_value {
+            continue;
+        }
+        return SUBCOMMANDS.contains(&arg);
+    }
+    false
+}
+
+/// 起動引数がCLIのサブコマンドならCLIとして実行して終了コードを返す（GUI起動時はNone）
+pub fn run_from_args() -> Option<i32> {
+    let args: Vec<String> = std::env::args().collect();
+    if !is_cli_invocation(&args) {
+        return None;
+    }
+
+    let cli = match Cli::try_parse_from(&args) {
+        Ok(cli) => cli,
+        Err(e) => {
+            let _ = e.print();
+            return Some(if e.use_stderr() { EXIT_USAGE } else { EXIT_SUCCESS });
+        }
+    };
+
+    crate::logger::init_cli_logger(cli.verbose);
+    let output = Output { json: cli.json };
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            output.error(&InternalError::Other(format!("Failed to start async runtime: {}", e)));
+            return Some(EXIT_FAILURE);
+        }
+    };
+
+    Some(match runtime.block_on(run(cli, &output)) {
+        Ok(()) => EXIT_SUCCESS,
+        Err(e) => {
+            output.error(&e);
+            EXIT_FAILURE
+        }
+    })
+}
+
+async fn run(cli: Cli, output: &Output) -> Result<(), InternalError> {
+    let config = load_cli_config();
+    // GUIと同じくモックAWSモードを反映（本番と混同しないよう標準エラーへ明示する）
+    set_mock_aws_mode(config.mock_aws_mode, config.mock_aws_speed_mbps);
+    if is_mock_aws_mode() {
+        eprintln!("{} モックAWSモードで実行中: AWSへは一切アクセスしません", MOCK_LABEL);
+    }
+
+    match cli.command {
+        CliCommand::Upload { path, bucket, prefix } => {
+            let bucket = resolve_bucket(bucket, &config)?;
+            let (credentials, _) = resolve_credentials(cli.profile.as_deref(), cli.region.as_deref(), &config).await?;
+            upload(output, credentials, bucket, &path, prefix.as_deref()).await
+        }
+        CliCommand::Status { bucket } => {
+            let bucket = bucket.or_else(|| config.user_preferences.default_bucket_name.clone());
+            status(output, cli.profile.as_deref(), cli.region.as_deref(), &config, bucket).await
+        }
+        CliCommand::Restore { key, bucket, tier, days } => {
+            let bucket = resolve_bucket(bucket, &config)?;
+            let days = days.unwrap_or(config.user_preferences.default_restore_days);
+            let (credentials, _) = resolve_credentials(cli.profile.as_deref(), cli.region.as_deref(), &config).await?;
+            let s3_client = create_s3_client_for_credentials(&credentials).await.map_err(InternalError::Other)?;
+            let restore_info = request_restore(s3_client.as_ref(), &key, &bucket, &tier, days, None).await?;
+            output.result(&restore_info, || {
+                format!("復元をリクエストしました: s3://{}/{}（ティア: {}、保持: {}日）", bucket, key, restore_info.tier, days)
+            });
+            Ok(())
+        }
+    }
+}
+
+fn app_data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_IDENTIFIER))
+}
+
+/// GUIと同じ設定ファイルを読み込む（読めない場合はデフォルト設定）
+fn load_cli_config() -> AppConfig {
+    let Some(config_path) = app_data_dir().map(|dir| dir.join("config.json")) else {
+        return AppConfig::default();
+    };
+    load_config_from_path(&config_path).unwrap_or_else(|e| {
+        log::warn!("Failed to load config, using defaults: {}", e);
+        AppConfig::default()
+    })
+}
+
+fn resolve_bucket(bucket: Option<String>, config: &AppConfig) -> Result<String, InternalError> {
+    bucket
+        .or_else(|| config.user_preferences.default_bucket_name.clone())
+        .filter(|bucket| !bucket.is_empty())
+        .ok_or_else(|| InternalError::Config("バケットが指定されていません。--bucketを指定するか、アプリでデフォルトバケットを設定してください".to_string()))
+}
+
+/// リージョンを決定（--region > AWS_REGION > AWS_DEFAULT_REGION > 設定ファイル）
+fn resolve_region(flag: Option<&str>, env: impl Fn(&str) -> Option<String>, config: &AppConfig) -> String {
+    flag.map(String::from)
+        .or_else(|| env("AWS_REGION"))
+        .or_else(|| env("AWS_DEFAULT_REGION"))
+        .filter(|region| !region.is_empty())
+        .unwrap_or_else(|| config.aws_settings.default_region.clone())
+}
+
+/// 環境変数から認証情報を組み立てる（アクセスキーとシークレットの両方がある場合のみ）
+fn credentials_from_env(env: impl Fn(&str) -> Option<String>, region: &str) -> Option<AwsCredentials> {
+    let non_empty = |name: &str| env(name).filter(|value| !value.is_empty());
+    Some(AwsCredentials {
+        access_key_id: non_empty("AWS_ACCESS_KEY_ID")?,
+        secret_access_key: non_empty("AWS_SECRET_ACCESS_KEY")?,
+        region: region.to_string(),
+        session_token: non_empty("AWS_SESSION_TOKEN"),
+    })
+}
+
+/// 認証情報を取得（--profile指定時はKeychain、未指定時は環境変数 → Keychainの順）
+async fn resolve_credentials(
+    profile: Option<&str>,
+    region_flag: Option<&str>,
+    config: &AppConfig,
+) -> Result<(AwsCredentials, CredentialSource), InternalError> {
+    let env = |name: &str| std::env::var(name).ok();
+    let region = resolve_region(region_flag, env, config);
+
+    if profile.is_none() {
+        if let Some(credentials) = credentials_from_env(env, &region) {
+            credentials.register_for_masking();
+            return Ok((credentials, CredentialSource::Environment));
+        }
+    }
+
+    let profile = profile
+        .map(String::from)
+        .or_else(|| config.aws_settings.profile_name.clone())
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+    let mut credentials = load_aws_credentials_secure(profile.clone()).await.map_err(|e| {
+        InternalError::AwsConfig(format!(
+            "認証情報を取得できませんでした。環境変数AWS_ACCESS_KEY_ID/AWS_SECRET_ACCESS_KEYを設定するか、アプリでプロファイル「{}」を保存してください（{}）",
+            profile, e
+        ))
+    })?;
+    if let Some(region) = region_flag {
+        credentials.region = region.to_string();
+    }
+
+    Ok((credentials, CredentialSource::Keychain { profile }))
+}
+
+/// アップロード対象のファイルを列挙（ディレクトリは再帰的に走査し、隠しファイルは除外）
+fn collect_files(path: &Path) -> Result<Vec<PathBuf>, InternalError> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    if !path.is_dir() {
+        return Err(InternalError::File(format!("File not found: {}", path.display())));
+    }
+
+    let mut files = Vec::new();
+    let mut entries: Vec<_> = std::fs::read_dir(path)?.filter_map(|entry| entry.ok()).collect();
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let entry_path = entry.path();
+        if entry_path.is_dir() {
+            files.extend(collect_files(&entry_path)?);
+        } else if entry_path.is_file() {
+            files.push(entry_path);
+        }
+    }
+    Ok(files)
+}
+
+/// ディレクトリ指定時は、指定ディレクトリからの相対パスをプレフィックスに含める
+fn s3_key_config_for(root: &Path, file: &Path, prefix: Option<&str>) -> S3KeyConfig {
+    let relative_dir = file.parent()
+        .and_then(|parent| parent.strip_prefix(root).ok())
+        .filter(|relative| !relative.as_os_str().is_empty())
+        .map(|relative| relative.to_string_lossy().replace('\\', "/"));
+    let prefix = match (prefix, relative_dir) {
+        (Some(prefix), Some(dir)) => Some(format!("{}/{}", prefix.trim_end_matches('/'), dir)),
+        (Some(prefix), None) => Some(prefix.to_string()),
+        (None, dir) => dir,
+    };
+
+    S3KeyConfig {
+        prefix,
+        use_date_folder: false,
+        preserve_directory_structure: false,
+        custom_naming_pattern: None,
+    }
+}
+
+async fn upload(
+    output: &Output,
+    credentials: AwsCredentials,
+    bucket: String,
+    path: &Path,
+    prefix: Option<&str>,
+) -> Result<(), InternalError> {
+    let files = collect_files(path)?;
+    if files.is_empty() {
+        return Err(InternalError::File(format!("アップロード対象のファイルがありません: {}", path.display())));
+    }
+    let root = if path.is_dir() { path } else { path.parent().unwrap_or(path) };
+
+    let upload_config = UploadConfig {
+        region: credentials.region.clone(),
+        ..UploadConfig::new(bucket.clone())
+    };
+
+    let mut failed = 0;
+    for file in &files {
+        let file_path = file.to_string_lossy().to_string();
+        let file_name = file.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| file_path.clone());
+        let s3_key = generate_s3_key(&file_path, &s3_key_config_for(root, file, prefix))?;
+
+        let (tx, mut rx) = mpsc::channel::<UploadProgress>(100);
+        let upload = upload_file_to_s3(
+            file_path.clone(),
+            s3_key.clone(),
+            &credentials,
+            upload_config.clone(),
+            tx,
+            Uuid::new_v4().to_string(),
+        );
+        tokio::pin!(upload);
+
+        let result = loop {
+            tokio::select! {
+                result = &mut upload => break result,
+                Some(progress) = rx.recv() => output.progress(&file_name, &progress),
+            }
+        };
+        while let Ok(progress) = rx.try_recv() {
+            output.progress(&file_name, &progress);
+        }
+
+        match result {
+            Ok(outcome) => output.event(
+                "uploaded",
+                serde_json::json!({ "file": file_path, "bucket": bucket, "key": outcome.s3_key }),
+                || format!("完了: {} -> s3://{}/{}", file_name, bucket, outcome.s3_key),
+            ),
+            Err(e) => {
+                failed += 1;
+                output.event(
+                    "failed",
+                    serde_json::json!({ "file": file_path, "key": s3_key, "message": e }),
+                    || format!("失敗: {}: {}", file_name, e),
+                );
+            }
+        }
+    }
+
+    let succeeded = files.len() - failed;
+    output.event(
+        "summary",
+        serde_json::json!({ "succeeded": succeeded, "failed": failed }),
+        || format!("アップロード結果: 成功 {}件 / 失敗 {}件", succeeded, failed),
+    );
+
+    if failed > 0 {
+        return Err(InternalError::Other(format!("{}件のアップロードに失敗しました", failed)));
+    }
+    Ok(())
+}
+
+fn unfinished_upload_count() -> usize {
+    app_data_dir()
+        .map(|dir| dir.join(QUEUE_STATE_FILE))
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<Vec<UploadItem>>(&content).ok())
+        .map(|items| items.len())
+        .unwrap_or(0)
+}
+
+async fn status(
+    output: &Output,
+    profile: Option<&str>,
+    region: Option<&str>,
+    config: &AppConfig,
+    bucket: Option<String>,
+) -> Result<(), InternalError> {
+    let mut report = StatusReport {
+        credential_source: None,
+        authenticated: false,
+        identity_arn: None,
+        message: String::new(),
+        bucket,
+        unfinished_uploads: unfinished_upload_count(),
+    };
+
+    match resolve_credentials(profile, region, config).await {
+        Ok((credentials, source)) => {
+            report.credential_source = Some(source);
+            match verify_aws_credentials(credentials).await {
+                Ok(result) => {
+                    report.authenticated = result.success;
+                    report.identity_arn = result.user_identity.map(|identity| identity.arn);
+                    report.message = result.message;
+                }
+                Err(e) => report.message = e,
+            }
+        }
+        Err(e) => report.message = e.to_string(),
+    }
+
+    output.result(&report, || {
+        let source = match &report.credential_source {
+            Some(CredentialSource::Environment) => "環境変数".to_string(),
+            Some(CredentialSource::Keychain { profile }) => format!("Keychain（{}）", profile),
+            None => "なし".to_string(),
+        };
+        [
+            format!("認証情報: {}", source),
+            format!(
+                "認証: {}{}",
+                if report.authenticated { "OK" } else { "NG" },
+                report.identity_arn.as_ref().map(|arn| format!("（{}）", arn)).unwrap_or_default()
+            ),
+            format!("メッセージ: {}", report.message),
+            format!("バケット: {}", report.bucket.as_deref().unwrap_or("未設定")),
+            format!("前回中断したアップロード: {}件", report.unfinished_uploads),
+        ]
+        .join("\n")
+    });
+
+    if report.authenticated {
+        Ok(())
+    } else {
+        Err(InternalError::AwsConfig(report.message.clone()))
+    }
+}
+
+/// 標準出力への出力（テキストまたはJSON Lines）
+struct Output {
+    json: bool,
+}
+
+impl Output {
+    fn event(&self, event: &str, payload: serde_json::Value, text: impl FnOnce() -> String) {
+        if self.json {
+            let mut line = payload;
+            if let Some(object) = line.as_object_mut() {
+                object.insert("event".to_string(), serde_json::Value::from(event));
+            }
+            println!("{}", line);
+        } else {
+            println!("{}", text());
+        }
+    }
+
+    fn progress(&self, file_name: &str, progress: &UploadProgress) {
+        self.event(
+            "progress",
+            serde_json::json!({
+                "file": file_name,
+                "uploaded_bytes": progress.uploaded_bytes,
+                "total_bytes": progress.total_bytes,
+                "percentage": progress.percentage,
+                "speed_mbps": progress.speed_mbps,
+                "eta_seconds": progress.eta_seconds,
+            }),
+            || format!("{} {:>5.1}% ({:.1} MB/s)", file_name, progress.percentage, progress.speed_mbps),
+        );
+    }
+
+    fn result<T: Serialize>(&self, value: &T, text: impl FnOnce() -> String) {
+        let payload = serde_json::to_value(value).unwrap_or(serde_json::Value::Null);
+        self.event("result", serde_json::json!({ "result": payload }), text);
+    }
+
+    fn error(&self, error: &InternalError) {
+        if self.json {
+            println!("{}", serde_json::json!({ "event": "error", "message": error.to_string() }));
+        } else {
+            eprintln!("エラー: {}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_is_cli_invocation() {
+        assert!(is_cli_invocation(&args(&["reelvault", "upload", "a.mov"])));
+        assert!(is_cli_invocation(&args(&["reelvault", "status"])));
+        assert!(!is_cli_invocation(&args(&["reelvault"])));
+        assert!(!is_cli_invocation(&args(&["reelvault", "--hidden"])));
+        assert!(!is_cli_invocation(&args(&["reelvault", "/Users/me/upload.mov"])));
+    }
+
+    #[test]
+    fn test_is_cli_invocation_skips_global_options() {
+        assert!(is_cli_invocation(&args(&["reelvault", "--json", "upload", "a.mov"])));
+        assert!(is_cli_invocation(&args(&["reelvault", "-v", "--profile", "work", "status"])));
+        assert!(is_cli_invocation(&args(&["reelvault", "--region=us-east-1", "restore", "a.mov"])));
+        // 値付きオプションの値はサブコマンドとして扱わない
+        assert!(!is_cli_invocation(&args(&["reelvault", "--profile", "status"])));
+        assert!(!is_cli_invocation(&args(&["reelvault", "--json"])));
+        assert!(!is_cli_invocation(&args(&["reelvault", "--json", "--hidden"])));
+    }
+
+    #[test]
+    fn test_parse_upload_command() {
+        let cli = Cli::try_parse_from(["reelvault", "upload", "/tmp/a.mov", "--bucket", "b", "--prefix", "p", "--json"]).unwrap();
+        assert!(cli.json);
+        match cli.command {
+            CliCommand::Upload { path, bucket, prefix } => {
+                assert_eq!(path, PathBuf::from("/tmp/a.mov"));
+                assert_eq!(bucket.as_deref(), Some("b"));
+                assert_eq!(prefix.as_deref(), Some("p"));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_restore_default_tier() {
+        let cli = Cli::try_parse_from(["reelvault", "restore", "uploads/a.mov"]).unwrap();
+        match cli.command {
+            CliCommand::Restore { key, tier, days, .. } => {
+                assert_eq!(key, "uploads/a.mov");
+                assert_eq!(tier, "Standard");
+                assert_eq!(days, None);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_credentials_from_env() {
+        let mut vars = HashMap::new();
+        vars.insert("AWS_ACCESS_KEY_ID", "AKIATEST");
+        let env = |name: &str| vars.get(name).map(|value| value.to_string());
+        assert!(credentials_from_env(env, "us-east-1").is_none());
+
+        vars.insert("AWS_SECRET_ACCESS_KEY", "secret");
+        let env = |name: &str| vars.get(name).map(|value| value.to_string());
+        let credentials = credentials_from_env(env, "us-east-1").unwrap();
+        assert_eq!(credentials.access_key_id, "AKIATEST");
+        assert_eq!(credentials.region, "us-east-1");
+        assert!(credentials.session_token.is_none());
+    }
+
+    #[test]
+    fn test_resolve_region_precedence() {
+        let config = AppConfig::default();
+        let env = |name: &str| (name == "AWS_DEFAULT_REGION").then(|| "eu-west-1".to_string());
+        assert_eq!(resolve_region(Some("us-west-2"), env, &config), "us-west-2");
+        assert_eq!(resolve_region(None, env, &config), "eu-west-1");
+        assert_eq!(resolve_region(None, |_: &str| None, &config), config.aws_settings.default_region);
+    }
+
+    #[test]
+    fn test_s3_key_config_for_directory_upload() {
+        let root = Path::new("/footage");
+        let config = s3_key_config_for(root, Path::new("/footage/day1/a.mov"), Some("projects/"));
+        assert_eq!(config.prefix.as_deref(), Some("projects/day1"));
+
+        let config = s3_key_config_for(root, Path::new("/footage/b.mov"), None);
+        assert_eq!(config.prefix, None);
+    }
+
+    #[test]
+    fn test_collect_files_skips_hidden() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp_dir.path().join("day1")).unwrap();
+        std::fs::write(temp_dir.path().join("day1/a.mov"), b"a").unwrap();
+        std::fs::write(temp_dir.path().join("b.mov"), b"b").unwrap();
+        std::fs::write(temp_dir.path().join(".DS_Store"), b"x").unwrap();
+
+        let files = collect_files(temp_dir.path()).unwrap();
+        assert_eq!(files, vec![temp_dir.path().join("b.mov"), temp_dir.path().join("day1/a.mov")]);
+    }
+}
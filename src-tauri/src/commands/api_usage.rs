@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use chrono::{Datelike, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle};
+
+use crate::commands::config::load_config;
+use crate::commands::metadata::{metadata_db_path, MetadataDatabase};
+use crate::commands::mock_aws::is_mock_aws_mode;
+use crate::internal::{AppError, InternalError};
+
+/// 集計をSQLiteへ書き出す間隔
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// 単価表にないリージョンで使う単価のリージョン
+const FALLBACK_PRICING_REGION: &str = "us-east-1";
+/// 日付の保存形式
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// 回数を数えるS3 APIの種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum S3ApiKind {
+    PutObject,
+    CreateMultipartUpload,
+    UploadPart,
+    CompleteMultipartUpload,
+    AbortMultipartUpload,
+    ListObjects,
+    GetObject,
+    HeadObject,
+    HeadBucket,
+    DeleteObject,
+    RestoreObject,
+    CopyObject,
+    GetObjectTagging,
+    PutObjectTagging,
+    /// ライフサイクル・バージョニング・暗号化などバケット設定の取得・変更
+    BucketConfiguration,
+}
+
+/// リクエスト料金の区分
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PricingClass {
+    /// PUT・COPY・POST・LIST
+    Write,
+    /// GET・HEADなど
+    Read,
+    /// Deep Archiveの復元リクエスト
+    Restore,
+    /// DELETE・マルチパートの中止（無料）
+    Free,
+}
+
+impl S3ApiKind {
+    pub const ALL: [S3ApiKind; 15] = [
+        S3ApiKind::PutObject,
+        S3ApiKind::CreateMultipartUpload,
+        S3ApiKind::UploadPart,
+        S3ApiKind::CompleteMultipartUpload,
+        S3ApiKind::AbortMultipartUpload,
+        S3ApiKind::ListObjects,
+        S3ApiKind::GetObject,
+        S3ApiKind::HeadObject,
+        S3ApiKind::HeadBucket,
+        S3ApiKind::DeleteObject,
+        S3ApiKind::RestoreObject,
+        S3ApiKind::CopyObject,
+        S3ApiKind::GetObjectTagging,
+        S3ApiKind::PutObjectTagging,
+        S3ApiKind::BucketConfiguration,
+    ];
+
+    /// DBに保存する文字列表現
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            S3ApiKind::PutObject => "put_object",
+            S3ApiKind::CreateMultipartUpload => "create_multipart_upload",
+            S3ApiKind::UploadPart => "upload_part",
+            S3ApiKind::CompleteMultipartUpload => "complete_multipart_upload",
+            S3ApiKind::AbortMultipartUpload => "abort_multipart_upload",
+            S3ApiKind::ListObjects => "list_objects",
+            S3ApiKind::GetObject => "get_object",
+            S3ApiKind::HeadObject => "head_object",
+            S3ApiKind::HeadBucket => "head_bucket",
+            S3ApiKind::DeleteObject => "delete_object",
+            S3ApiKind::RestoreObject => "restore_object",
+            S3ApiKind::CopyObject => "copy_object",
+            S3ApiKind::GetObjectTagging => "get_object_tagging",
+            S3ApiKind::PutObjectTagging => "put_object_tagging",
+            S3ApiKind::BucketConfiguration => "bucket_configuration",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+
+    pub fn pricing_class(&self) -> PricingClass {
+        match self {
+            S3ApiKind::PutObject
+            | S3ApiKind::CreateMultipartUpload
+            | S3ApiKind::UploadPart
+            | S3ApiKind::CompleteMultipartUpload
+            | S3ApiKind::ListObjects
+            | S3ApiKind::CopyObject
+            | S3ApiKind::PutObjectTagging
+            | S3ApiKind::BucketConfiguration => PricingClass::Write,
+            S3ApiKind::GetObject
+            | S3ApiKind::HeadObject
+            | S3ApiKind::HeadBucket
+            | S3ApiKind::GetObjectTagging => PricingClass::Read,
+            S3ApiKind::RestoreObject => PricingClass::Restore,
+            S3ApiKind::AbortMultipartUpload | S3ApiKind::DeleteObject => PricingClass::Free,
+        }
+    }
+}
+
+/// リージョン別のリクエスト単価（USD / 1,000リクエスト）
+struct RequestPrice {
+    region: &'static str,
+    write: f64,
+    read: f64,
+    restore: f64,
+}
+
+const REQUEST_PRICES: &[RequestPrice] = &[
+    RequestPrice { region: "us-east-1", write: 0.005, read: 0.0004, restore: 0.10 },
+    RequestPrice { region: "us-west-2", write: 0.005, read: 0.0004, restore: 0.10 },
+    RequestPrice { region: "ap-northeast-1", write: 0.0047, read: 0.00037, restore: 0.10 },
+];
+
+fn request_price(region: &str) -> &'static RequestPrice {
+    REQUEST_PRICES.iter()
+        .find(|price| price.region == region)
+        .or_else(|| REQUEST_PRICES.iter().find(|price| price.region == FALLBACK_PRICING_REGION))
+        .expect("fallback pricing region is defined")
+}
+
+/// 1リクエストあたりの概算料金（USD）
+pub fn estimated_cost_per_request(kind: S3ApiKind, region: &str) -> f64 {
+    let price = request_price(region);
+    let per_thousand = match kind.pricing_class() {
+        PricingClass::Write => price.write,
+        PricingClass::Read => price.read,
+        PricingClass::Restore => price.restore,
+        PricingClass::Free => 0.0,
+    };
+    per_thousand / 1000.0
+}
+
+lazy_static::lazy_static! {
+    /// SQLiteへ未書き出しの日別・種別ごとの回数
+    static ref PENDING_USAGE: Mutex<HashMap<(NaiveDate, S3ApiKind), u64>> = Mutex::new(HashMap::new());
+}
+
+/// S3 APIの呼び出しを1回記録（モックAWSモードでは課金されないため記録しない）
+pub fn record_api_call(kind: S3ApiKind) {
+    if is_mock_aws_mode() {
+        return;
+    }
+    let mut pending = PENDING_USAGE.lock().unwrap_or_else(|e| e.into_inner());
+    *pending.entry((Local::now().date_naive(), kind)).or_insert(0) += 1;
+}
+
+/// 未書き出しの回数をSQLiteへ加算（失敗した場合は次回に持ち越す）
+pub fn flush_api_usage(db: &MetadataDatabase) -> Result<usize, InternalError> {
+    let pending = std::mem::take(&mut *PENDING_USAGE.lock().unwrap_or_else(|e| e.into_inner()));
+    let mut failed = HashMap::new();
+    let mut result = Ok(pending.len());
+    for ((date, kind), count) in pending {
+        if let Err(e) = db.add_api_usage(&date.format(DATE_FORMAT).to_string(), kind.as_str(), count) {
+            result = Err(InternalError::Database(format!("Failed to persist API usage: {}", e)));
+            failed.insert((date, kind), count);
+        }
+    }
+    if !failed.is_empty() {
+        let mut current = PENDING_USAGE.lock().unwrap_or_else(|e| e.into_inner());
+        for (key, count) in failed {
+            *current.entry(key).or_insert(0) += count;
+        }
+    }
+    result
+}
+
+/// 集計期間
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApiUsagePeriod {
+    Today,
+    /// 今月1日から今日まで
+    CurrentMonth,
+    /// 今日を含む直近の日数
+    LastDays { days: u32 },
+}
+
+impl ApiUsagePeriod {
+    /// 集計対象の日付範囲（両端を含む）
+    pub fn date_range(&self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let from = match self {
+            ApiUsagePeriod::Today => today,
+            ApiUsagePeriod::CurrentMonth => today.with_day(1).unwrap_or(today),
+            ApiUsagePeriod::LastDays { days } => today - chrono::Duration::days(i64::from(days.saturating_sub(1))),
+        };
+        (from, today)
+    }
+}
+
+/// API種別ごとの回数と概算料金
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiUsageEntry {
+    pub api: S3ApiKind,
+    pub requests: u64,
+    pub estimated_cost_usd: f64,
+}
+
+/// get_api_usage_summaryの結果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiUsageSummary {
+    pub from: String,
+    pub to: String,
+    pub region: String,
+    /// 単価に使ったリージョン（単価表にないリージョンはus-east-1の単価で概算）
+    pub pricing_region: String,
+    pub total_requests: u64,
+    pub estimated_cost_usd: f64,
+    pub by_api: Vec<ApiUsageEntry>,
+}
+
+/// 種別ごとの回数から概算料金を集計
+pub fn summarize_usage(counts: &HashMap<S3ApiKind, u64>, region: &str, from: NaiveDate, to: NaiveDate) -> ApiUsageSummary {
+    let by_api: Vec<ApiUsageEntry> = S3ApiKind::ALL.into_iter()
+        .filter_map(|api| {
+            let requests = counts.get(&api).copied().filter(|count| *count > 0)?;
+            Some(ApiUsageEntry {
+                api,
+                requests,
+                estimated_cost_usd: requests as f64 * estimated_cost_per_request(api, region),
+            })
+        })
+        .collect();
+    ApiUsageSummary {
+        from: from.format(DATE_FORMAT).to_string(),
+        to: to.format(DATE_FORMAT).to_string(),
+        region: region.to_string(),
+        pricing_region: request_price(region).region.to_string(),
+        total_requests: by_api.iter().map(|entry| entry.requests).sum(),
+        estimated_cost_usd: by_api.iter().map(|entry| entry.estimated_cost_usd).sum(),
+        by_api,
+    }
+}
+
+fn open_metadata_db(app: &AppHandle) -> Result<MetadataDatabase, InternalError> {
+    let db_path = metadata_db_path(app)?;
+    MetadataDatabase::new(&db_path.to_string_lossy())
+        .map_err(|e| InternalError::Database(format!("Failed to create database connection: {}", e)))
+}
+
+/// 未書き出しの回数をアプリのメタデータDBへ書き出す（終了時用）
+pub fn persist_api_usage(app: &AppHandle) -> Result<usize, InternalError> {
+    flush_api_usage(&open_metadata_db(app)?)
+}
+
+/// 未書き出し分を反映してから期間内の利用状況を集計
+pub(crate) fn api_usage_summary(app: &AppHandle, period: ApiUsagePeriod) -> Result<ApiUsageSummary, InternalError> {
+    let db = open_metadata_db(app)?;
+    flush_api_usage(&db)?;
+
+    let (from, to) = period.date_range(Local::now().date_naive());
+    let mut counts = HashMap::new();
+    let rows = db.list_api_usage(&from.format(DATE_FORMAT).to_string(), &to.format(DATE_FORMAT).to_string())
+        .map_err(|e| InternalError::Database(format!("Failed to read API usage: {}", e)))?;
+    for (api, count) in rows {
+        if let Some(kind) = S3ApiKind::from_db(&api) {
+            *counts.entry(kind).or_insert(0) += count;
+        }
+    }
+
+    let region = load_config(app)
+        .map(|config| config.aws_settings.default_region)
+        .unwrap_or_else(|_| FALLBACK_PRICING_REGION.to_string());
+    Ok(summarize_usage(&counts, &region, from, to))
+}
+
+/// トレイに表示する今月の概算
+pub fn format_monthly_cost(summary: &ApiUsageSummary) -> String {
+    format!("今月のS3リクエスト: {}件（約${:.2}）", summary.total_requests, summary.estimated_cost_usd)
+}
+
+/// 定期的に回数をSQLiteへ書き出し、トレイの今月の概算を更新
+pub fn start_api_usage_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            match api_usage_summary(&app, ApiUsagePeriod::CurrentMonth) {
+                Ok(summary) => crate::tray::update_api_cost(&app, &format_monthly_cost(&summary)),
+                Err(e) => log::warn!("Failed to update API usage: {}", e),
+            }
+            tokio::time::sleep(FLUSH_INTERVAL).await;
+        }
+    });
+}
+
+/// S3 APIのリクエスト数と概算料金を取得
+#[command]
+pub async fn get_api_usage_summary(app: AppHandle, period: ApiUsagePeriod) -> Result<ApiUsageSummary, AppError> {
+    Ok(api_usage_summary(&app, period)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_usage_uses_region_prices() {
+        let counts = HashMap::from([
+            (S3ApiKind::UploadPart, 2000),
+            (S3ApiKind::HeadObject, 1000),
+            (S3ApiKind::DeleteObject, 50),
+        ]);
+        let day = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let summary = summarize_usage(&counts, "us-east-1", day, day);
+        assert_eq!(summary.total_requests, 3050);
+        assert!((summary.estimated_cost_usd - (0.01 + 0.0004)).abs() < 1e-9);
+        assert_eq!(summary.by_api.len(), 3);
+
+        // 単価表にないリージョンはus-east-1の単価で概算
+        let fallback = summarize_usage(&counts, "eu-north-1", day, day);
+        assert_eq!(fallback.pricing_region, "us-east-1");
+        let tokyo = summarize_usage(&counts, "ap-northeast-1", day, day);
+        assert!(tokyo.estimated_cost_usd < summary.estimated_cost_usd);
+    }
+
+    #[test]
+    fn test_period_date_range_and_kind_roundtrip() {
+        let today = NaiveDate::from_ymd_opt(2024, 5, 17).unwrap();
+        assert_eq!(ApiUsagePeriod::CurrentMonth.date_range(today).0, NaiveDate::from_ymd_opt(2024, 5, 1).unwrap());
+        assert_eq!(ApiUsagePeriod::LastDays { days: 7 }.date_range(today).0, NaiveDate::from_ymd_opt(2024, 5, 11).unwrap());
+        assert_eq!(ApiUsagePeriod::Today.date_range(today), (today, today));
+        for kind in S3ApiKind::ALL {
+            assert_eq!(S3ApiKind::from_db(kind.as_str()), Some(kind));
+        }
+    }
+}
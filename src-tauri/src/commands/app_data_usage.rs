@@ -0,0 +1,478 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Manager};
+
+use crate::commands::bundle::bundle_staging_dir;
+use crate::commands::compression::{COMPRESSED_TEMP_PREFIX, ZSTD_EXTENSION};
+use crate::commands::config::{get_config_path, load_config};
+use crate::commands::metadata::METADATA_DB_FILE_NAME;
+use crate::commands::metadata_db_repair::SIDECAR_SUFFIXES;
+use crate::commands::startup_diagnostics::metadata_db_backup_path;
+use crate::commands::upload_system::{queue_lock_error, UploadQueueState};
+use crate::internal::{AppError, InternalError};
+use crate::logger::LOG_FILE_NAME;
+
+/// 定期クリーンアップの間隔
+const APP_DATA_CLEANUP_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+/// 起動直後の負荷を避けるための初回クリーンアップまでの待ち時間
+const INITIAL_APP_DATA_CLEANUP_DELAY: Duration = Duration::from_secs(10 * 60);
+/// アプリのキャッシュディレクトリ内のサムネイルの保存先
+const THUMBNAILS_DIR_NAME: &str = "thumbnails";
+/// 定期クリーンアップで削除するまでの日数のデフォルト値
+pub const DEFAULT_APP_DATA_CLEANUP_DAYS: u32 = 30;
+
+/// アプリが作るデータの種類
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AppDataCategory {
+    /// ReelVault.log*（書き込み中の最新のログは削除しない）
+    Logs,
+    Thumbnails,
+    /// メタデータDBと付随ファイル（集計のみで削除しない）
+    Db,
+    /// 設定ファイル・メタデータDBのバックアップと、破損時に退避したファイル
+    Backups,
+    /// バンドルのステージング先と圧縮アップロードの一時ファイル
+    Temp,
+}
+
+impl AppDataCategory {
+    pub const ALL: [Self; 5] = [Self::Logs, Self::Thumbnails, Self::Db, Self::Backups, Self::Temp];
+
+    pub fn is_deletable(self) -> bool {
+        self != Self::Db
+    }
+}
+
+/// 定期自動クリーンアップの設定
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AppDataCleanupSettings {
+    pub enabled: bool,
+    pub categories: Vec<AppDataCategory>,
+    /// 最終更新からこの日数を過ぎたファイルを削除する
+    pub older_than_days: u32,
+}
+
+impl Default for AppDataCleanupSettings {
+    fn default() -> Self {
+        AppDataCleanupSettings {
+            enabled: false,
+            categories: vec![AppDataCategory::Thumbnails, AppDataCategory::Temp],
+            older_than_days: DEFAULT_APP_DATA_CLEANUP_DAYS,
+        }
+    }
+}
+
+/// カテゴリごとのデータの置き場所（取得できない場所はNone）
+#[derive(Debug, Clone, Default)]
+pub struct AppDataLocations {
+    pub log_dir: Option<PathBuf>,
+    /// 設定ファイル・メタデータDB・バックアップのあるアプリのデータディレクトリ
+    pub data_dir: Option<PathBuf>,
+    pub thumbnail_dir: Option<PathBuf>,
+    pub bundle_dir: Option<PathBuf>,
+    /// 圧縮アップロードの一時ファイルを作るOSの一時ディレクトリ
+    pub temp_dir: Option<PathBuf>,
+}
+
+impl AppDataLocations {
+    pub fn for_app(app: &AppHandle) -> Self {
+        Self {
+            log_dir: app.path().app_log_dir().ok(),
+            data_dir: app.path().app_data_dir().ok(),
+            thumbnail_dir: app.path().app_cache_dir().ok().map(|dir| dir.join(THUMBNAILS_DIR_NAME)),
+            bundle_dir: bundle_staging_dir(app).ok(),
+            temp_dir: Some(std::env::temp_dir()),
+        }
+    }
+}
+
+/// 削除してはいけないファイル
+#[derive(Debug, Clone, Default)]
+pub struct CleanupGuard {
+    /// アクティブな設定ファイル・未完了のキューアイテムのファイル
+    pub protected_paths: HashSet<PathBuf>,
+    /// アップロード中は圧縮の一時ファイルが使用中の可能性がある
+    pub uploads_active: bool,
+}
+
+impl CleanupGuard {
+    fn allows(&self, file: &AppDataFile) -> bool {
+        !file.protected
+            && !self.protected_paths.contains(&file.path)
+            && !(self.uploads_active && is_compressed_temp(&file.path))
+    }
+}
+
+/// カテゴリの使用量
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AppDataCategoryUsage {
+    pub category: AppDataCategory,
+    pub size_bytes: u64,
+    pub file_count: usize,
+    pub deletable: bool,
+    /// 削除の対象外（DB・書き込み中のログ・使用中のファイル）のサイズ
+    pub protected_bytes: u64,
+}
+
+/// アプリのデータの使用量
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AppDataUsage {
+    pub categories: Vec<AppDataCategoryUsage>,
+    pub total_bytes: u64,
+    pub total_files: usize,
+}
+
+/// カテゴリごとのクリーンアップ結果
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AppDataCleanupCategoryResult {
+    pub category: AppDataCategory,
+    pub removed_files: usize,
+    pub freed_bytes: u64,
+    /// 保護対象・新しいため残したファイル数
+    pub skipped_files: usize,
+}
+
+/// クリーンアップの実行結果
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AppDataCleanupResult {
+    pub categories: Vec<AppDataCleanupCategoryResult>,
+    pub freed_bytes: u64,
+    pub removed_files: usize,
+    pub errors: Vec<String>,
+}
+
+/// 集計・削除の対象のファイル
+#[derive(Debug, Clone)]
+struct AppDataFile {
+    path: PathBuf,
+    size: u64,
+    modified: SystemTime,
+    /// カテゴリ自体の保護対象（DB・書き込み中のログ）
+    protected: bool,
+}
+
+fn is_compressed_temp(path: &Path) -> bool {
+    path.file_name().map(|name| name.to_string_lossy()).is_some_and(|name| {
+        name.starts_with(COMPRESSED_TEMP_PREFIX) && name.ends_with(ZSTD_EXTENSION)
+    })
+}
+
+fn is_backup_file_name(name: &str) -> bool {
+    let db_backup = metadata_db_backup_path(Path::new(METADATA_DB_FILE_NAME));
+    name == "config.backup.json"
+        || (name.starts_with("config_backup_") && name.ends_with(".json"))
+        || db_backup.file_name().is_some_and(|backup| backup == name)
+        || name.contains(".corrupt-")
+}
+
+fn is_db_file_name(name: &str) -> bool {
+    name == METADATA_DB_FILE_NAME
+        || SIDECAR_SUFFIXES.iter().any(|suffix| name.strip_suffix(suffix) == Some(METADATA_DB_FILE_NAME))
+}
+
+fn file_entry(path: PathBuf) -> Option<AppDataFile> {
+    let metadata = std::fs::metadata(&path).ok().filter(|metadata| metadata.is_file())?;
+    let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    Some(AppDataFile { path, size: metadata.len(), modified, protected: false })
+}
+
+/// ディレクトリ直下のファイルのうち名前が条件に合うもの
+fn files_in(dir: Option<&Path>, matches: impl Fn(&str) -> bool) -> Vec<AppDataFile> {
+    let Some(entries) = dir.and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| matches(&entry.file_name().to_string_lossy()))
+        .filter_map(|entry| file_entry(entry.path()))
+        .collect()
+}
+
+/// ディレクトリ以下の全ファイル
+fn files_under(dir: &Path, files: &mut Vec<AppDataFile>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+            files_under(&path, files);
+        } else if let Some(file) = file_entry(path) {
+            files.push(file);
+        }
+    }
+}
+
+/// カテゴリに属するファイル
+fn category_files(locations: &AppDataLocations, category: AppDataCategory) -> Vec<AppDataFile> {
+    match category {
+        AppDataCategory::Logs => {
+            let mut files = files_in(locations.log_dir.as_deref(), |name| name.starts_with(LOG_FILE_NAME));
+            // 日次ローテートの書き込み先は最も新しく更新されたファイル
+            if let Some(active) = files.iter_mut().max_by_key(|file| file.modified) {
+                active.protected = true;
+            }
+            files
+        }
+        AppDataCategory::Thumbnails => {
+            let mut files = Vec::new();
+            if let Some(dir) = &locations.thumbnail_dir {
+                files_under(dir, &mut files);
+            }
+            files
+        }
+        AppDataCategory::Db => {
+            let mut files = files_in(locations.data_dir.as_deref(), is_db_file_name);
+            for file in &mut files {
+                file.protected = true;
+            }
+            files
+        }
+        AppDataCategory::Backups => files_in(locations.data_dir.as_deref(), is_backup_file_name),
+        AppDataCategory::Temp => {
+            let mut files = files_in(locations.temp_dir.as_deref(), |name| {
+                name.starts_with(COMPRESSED_TEMP_PREFIX) && name.ends_with(ZSTD_EXTENSION)
+            });
+            if let Some(dir) = &locations.bundle_dir {
+                files_under(dir, &mut files);
+            }
+            files
+        }
+    }
+}
+
+/// カテゴリ別の使用量を集計する
+pub fn collect_app_data_usage(locations: &AppDataLocations, guard: &CleanupGuard) -> AppDataUsage {
+    let categories: Vec<_> = AppDataCategory::ALL.iter().map(|&category| {
+        let files = category_files(locations, category);
+        AppDataCategoryUsage {
+            category,
+            size_bytes: files.iter().map(|file| file.size).sum(),
+            file_count: files.len(),
+            deletable: category.is_deletable(),
+            protected_bytes: files.iter().filter(|file| !guard.allows(file)).map(|file| file.size).sum(),
+        }
+    }).collect();
+    AppDataUsage {
+        total_bytes: categories.iter().map(|usage| usage.size_bytes).sum(),
+        total_files: categories.iter().map(|usage| usage.file_count).sum(),
+        categories,
+    }
+}
+
+/// 選択したカテゴリのファイルを直接削除する（`older_than_days`は最終更新からの日数、Noneは全て）
+pub fn cleanup_app_data_files(
+    locations: &AppDataLocations,
+    guard: &CleanupGuard,
+    categories: &[AppDataCategory],
+    older_than_days: Option<u32>,
+    now: SystemTime,
+) -> AppDataCleanupResult {
+    let cutoff = older_than_days
+        .map(|days| now.checked_sub(Duration::from_secs(u64::from(days) * 24 * 60 * 60)).unwrap_or(SystemTime::UNIX_EPOCH));
+    let mut requested: Vec<AppDataCategory> = Vec::new();
+    for category in categories {
+        if !requested.contains(category) {
+            requested.push(*category);
+        }
+    }
+
+    let mut results = Vec::new();
+    let mut errors = Vec::new();
+    for category in requested {
+        let mut result = AppDataCleanupCategoryResult { category, removed_files: 0, freed_bytes: 0, skipped_files: 0 };
+        for file in category_files(locations, category) {
+            let expired = match cutoff {
+                Some(cutoff) => file.modified <= cutoff,
+                None => true,
+            };
+            if !category.is_deletable() || !guard.allows(&file) || !expired {
+                result.skipped_files += 1;
+                continue;
+            }
+            match std::fs::remove_file(&file.path) {
+                Ok(()) => {
+                    result.removed_files += 1;
+                    result.freed_bytes += file.size;
+                }
+                Err(e) => errors.push(format!("Failed to remove {}: {}", file.path.display(), e)),
+            }
+        }
+        results.push(result);
+    }
+    AppDataCleanupResult {
+        freed_bytes: results.iter().map(|result| result.freed_bytes).sum(),
+        removed_files: results.iter().map(|result| result.removed_files).sum(),
+        categories: results,
+        errors,
+    }
+}
+
+/// アクティブな設定ファイル・メタデータDB・未完了のキューアイテムのファイルを保護する
+fn cleanup_guard(app: &AppHandle) -> Result<CleanupGuard, InternalError> {
+    let mut guard = CleanupGuard::default();
+    guard.protected_paths.insert(get_config_path(app)?);
+    if let Ok(data_dir) = app.path().app_data_dir() {
+        guard.protected_paths.insert(data_dir.join(METADATA_DB_FILE_NAME));
+    }
+    let queue_state = app.state::<UploadQueueState>();
+    let queue = queue_state.lock().map_err(queue_lock_error)?;
+    guard.protected_paths.extend(queue.items.iter()
+        .filter(|item| !item.status.is_finished())
+        .map(|item| PathBuf::from(&item.file_path)));
+    guard.uploads_active = queue.get_active_upload_count() > 0;
+    Ok(guard)
+}
+
+fn run_cleanup(app: &AppHandle, categories: &[AppDataCategory], older_than_days: Option<u32>) -> Result<AppDataCleanupResult, InternalError> {
+    let guard = cleanup_guard(app)?;
+    let result = cleanup_app_data_files(&AppDataLocations::for_app(app), &guard, categories, older_than_days, SystemTime::now());
+    log::info!("App data cleanup removed {} file(s), freed {} bytes", result.removed_files, result.freed_bytes);
+    for error in &result.errors {
+        log::warn!("App data cleanup: {}", error);
+    }
+    Ok(result)
+}
+
+/// 設定で有効な場合に日次でアプリのデータを自動クリーンアップ
+pub fn start_app_data_cleanup_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(INITIAL_APP_DATA_CLEANUP_DELAY).await;
+        loop {
+            let settings = load_config(&app).map(|config| config.app_data_cleanup).unwrap_or_default();
+            if settings.enabled {
+                if let Err(e) = run_cleanup(&app, &settings.categories, Some(settings.older_than_days)) {
+                    log::error!("App data cleanup failed: {}", e);
+                }
+            }
+            tokio::time::sleep(APP_DATA_CLEANUP_INTERVAL).await;
+        }
+    });
+}
+
+/// アプリのデータのカテゴリ別の使用量を取得
+#[command]
+pub async fn get_app_data_usage(app: AppHandle) -> Result<AppDataUsage, AppError> {
+    let guard = cleanup_guard(&app)?;
+    let locations = AppDataLocations::for_app(&app);
+    Ok(tauri::async_runtime::spawn_blocking(move || collect_app_data_usage(&locations, &guard))
+        .await
+        .map_err(|e| InternalError::Other(format!("App data usage task failed: {}", e)))?)
+}
+
+/// 選択したカテゴリのアプリのデータを削除（DB・アクティブな設定ファイルは対象外）
+#[command]
+pub async fn cleanup_app_data(
+    app: AppHandle,
+    categories: Vec<AppDataCategory>,
+    older_than_days: Option<u32>,
+) -> Result<AppDataCleanupResult, AppError> {
+    Ok(tauri::async_runtime::spawn_blocking(move || run_cleanup(&app, &categories, older_than_days))
+        .await
+        .map_err(|e| InternalError::Other(format!("App data cleanup task failed: {}", e)))??)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, size: usize) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![0u8; size]).unwrap();
+    }
+
+    fn locations(root: &Path) -> AppDataLocations {
+        AppDataLocations {
+            log_dir: Some(root.join("logs")),
+            data_dir: Some(root.join("data")),
+            thumbnail_dir: Some(root.join("cache").join(THUMBNAILS_DIR_NAME)),
+            bundle_dir: Some(root.join("bundles")),
+            temp_dir: Some(root.join("tmp")),
+        }
+    }
+
+    fn usage_of(usage: &AppDataUsage, category: AppDataCategory) -> &AppDataCategoryUsage {
+        usage.categories.iter().find(|usage| usage.category == category).unwrap()
+    }
+
+    #[test]
+    fn test_usage_groups_files_by_category() {
+        let root = TempDir::new().unwrap();
+        let root = root.path();
+        write(&root.join("logs/ReelVault.log.2024-01-01"), 10);
+        write(&root.join("logs/unrelated.txt"), 1000);
+        write(&root.join("cache/thumbnails/ab/clip.jpg"), 20);
+        write(&root.join("data/config.json"), 1000);
+        write(&root.join("data/metadata.db"), 30);
+        write(&root.join("data/metadata.db-wal"), 5);
+        write(&root.join("data/metadata.db.bak"), 40);
+        write(&root.join("data/config_backup_20240101.json"), 4);
+        write(&root.join("data/config.json.corrupt-20240101-120000"), 6);
+        write(&root.join("tmp/reelvault-0123.zst"), 50);
+        write(&root.join("tmp/other.zst"), 1000);
+        write(&root.join("bundles/day01.tar"), 60);
+
+        let usage = collect_app_data_usage(&locations(root), &CleanupGuard::default());
+        let sizes: Vec<_> = usage.categories.iter().map(|usage| (usage.category, usage.size_bytes, usage.file_count)).collect();
+        assert_eq!(sizes, vec![
+            (AppDataCategory::Logs, 10, 1),
+            (AppDataCategory::Thumbnails, 20, 1),
+            (AppDataCategory::Db, 35, 2),
+            (AppDataCategory::Backups, 50, 3),
+            (AppDataCategory::Temp, 110, 2),
+        ]);
+        assert_eq!((usage.total_bytes, usage.total_files), (225, 9));
+        assert!(!usage_of(&usage, AppDataCategory::Db).deletable);
+        assert_eq!(usage_of(&usage, AppDataCategory::Db).protected_bytes, 35);
+        // 書き込み中のログは削除対象外
+        assert_eq!(usage_of(&usage, AppDataCategory::Logs).protected_bytes, 10);
+
+        let guard = CleanupGuard { uploads_active: true, ..CleanupGuard::default() };
+        let usage = collect_app_data_usage(&locations(root), &guard);
+        assert_eq!(usage_of(&usage, AppDataCategory::Temp).protected_bytes, 50);
+    }
+
+    #[test]
+    fn test_cleanup_keeps_protected_and_recent_files() {
+        let root = TempDir::new().unwrap();
+        let root = root.path();
+        write(&root.join("logs/ReelVault.log.2024-01-01"), 10);
+        write(&root.join("cache/thumbnails/clip.jpg"), 20);
+        write(&root.join("data/metadata.db"), 30);
+        write(&root.join("data/config.backup.json"), 40);
+        write(&root.join("bundles/queued.tar"), 50);
+        write(&root.join("bundles/done.tar"), 60);
+
+        let guard = CleanupGuard {
+            protected_paths: HashSet::from([root.join("bundles/queued.tar")]),
+            uploads_active: false,
+        };
+        let categories = AppDataCategory::ALL;
+        // 最終更新から1日経っていないため削除しない
+        let result = cleanup_app_data_files(&locations(root), &guard, &categories, Some(1), SystemTime::now());
+        assert_eq!((result.removed_files, result.freed_bytes), (0, 0));
+
+        let later = SystemTime::now() + Duration::from_secs(2 * 24 * 60 * 60);
+        let result = cleanup_app_data_files(&locations(root), &guard, &categories, Some(1), later);
+        assert_eq!((result.removed_files, result.freed_bytes), (3, 120));
+        assert!(result.errors.is_empty());
+        let skipped: Vec<_> = result.categories.iter().map(|result| (result.category, result.skipped_files)).collect();
+        assert_eq!(skipped, vec![
+            (AppDataCategory::Logs, 1),
+            (AppDataCategory::Thumbnails, 0),
+            (AppDataCategory::Db, 1),
+            (AppDataCategory::Backups, 0),
+            (AppDataCategory::Temp, 1),
+        ]);
+        assert!(root.join("data/metadata.db").exists());
+        assert!(root.join("logs/ReelVault.log.2024-01-01").exists());
+        assert!(root.join("bundles/queued.tar").exists());
+        assert!(!root.join("bundles/done.tar").exists());
+        assert!(!root.join("data/config.backup.json").exists());
+    }
+}
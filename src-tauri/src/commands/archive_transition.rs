@@ -0,0 +1,264 @@
+use std::collections::BTreeMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle};
+
+use crate::commands::aws_auth::AwsCredentials;
+use crate::commands::aws_operations::{create_real_s3_client, create_s3_client_for_credentials, AwsConfig, LifecycleRule, S3ClientTrait, S3Object};
+use crate::commands::lifecycle::ARCHIVE_STORAGE_CLASSES;
+use crate::internal::i18n::MessageKey;
+use crate::internal::{AppError, ErrorCode, InternalError};
+
+/// S3は既定でこのサイズ未満のオブジェクトをライフサイクルで移行しない
+pub const MIN_TRANSITION_OBJECT_SIZE: u64 = 128 * 1024;
+/// 移行日数を過ぎてから遅延とみなすまでの猶予（ライフサイクルは非同期に実行される）
+const TRANSITION_GRACE_DAYS: i64 = 2;
+
+/// まだアーカイブ系ストレージクラスへ移行していないオブジェクトの分類
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionState {
+    /// ルールの移行日数（＋猶予）に達していない
+    OnSchedule,
+    /// 移行日数を過ぎても移行されていない
+    PossiblyDelayed,
+    /// どのアーカイブ移行ルールの対象にもならない
+    NotCovered,
+}
+
+/// ルール対象外と推定した理由
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UncoveredReason {
+    /// 有効なアーカイブ移行ルールのプレフィックスに一致しない
+    PrefixMismatch,
+    /// 移行対象の最小サイズ（128KB）未満
+    BelowMinimumSize,
+}
+
+/// 移行していないオブジェクト
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UntransitionedObject {
+    pub key: String,
+    pub size: u64,
+    pub storage_class: String,
+    pub last_modified: String,
+    /// アップロードからの経過日数（日時を解釈できない場合はNone）
+    pub age_days: Option<i64>,
+    /// 一致したルールのうち最も早い移行日数
+    pub transition_days: Option<i32>,
+    pub state: TransitionState,
+    pub reason: Option<UncoveredReason>,
+}
+
+/// プレフィックス配下のアーカイブ移行状況
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArchiveTransitionStatus {
+    pub bucket: String,
+    pub prefix: Option<String>,
+    pub total_objects: usize,
+    pub archived_objects: usize,
+    /// 0.0〜1.0（オブジェクトがない場合は1.0）
+    pub archived_ratio: f64,
+    /// ストレージクラス別のオブジェクト数
+    pub storage_classes: BTreeMap<String, usize>,
+    pub on_schedule_count: usize,
+    pub delayed_count: usize,
+    pub not_covered_count: usize,
+    /// 移行していないオブジェクト（遅延・対象外を先に、経過日数の長い順）
+    pub untransitioned: Vec<UntransitionedObject>,
+    pub checked_at: String,
+}
+
+/// 有効なアーカイブ移行ルールの（プレフィックス, 移行日数）
+fn archive_transitions(rules: &[LifecycleRule]) -> Vec<(&str, i32)> {
+    rules.iter()
+        .filter(|rule| rule.status == "Enabled")
+        .filter_map(|rule| {
+            let days = rule.transitions.iter()
+                .filter(|t| ARCHIVE_STORAGE_CLASSES.contains(&t.storage_class.as_str()))
+                .map(|t| t.days)
+                .min()?;
+            Some((rule.prefix.as_deref().unwrap_or(""), days))
+        })
+        .collect()
+}
+
+fn classify(object: &S3Object, transitions: &[(&str, i32)], now: DateTime<Utc>) -> UntransitionedObject {
+    let age_days = DateTime::parse_from_rfc3339(&object.last_modified)
+        .ok()
+        .map(|modified| (now - modified.with_timezone(&Utc)).num_days());
+    let transition_days = transitions.iter()
+        .filter(|(prefix, _)| object.key.starts_with(prefix))
+        .map(|(_, days)| *days)
+        .min();
+    let (state, reason) = match transition_days {
+        None => (TransitionState::NotCovered, Some(UncoveredReason::PrefixMismatch)),
+        Some(_) if object.size < MIN_TRANSITION_OBJECT_SIZE => {
+            (TransitionState::NotCovered, Some(UncoveredReason::BelowMinimumSize))
+        }
+        Some(days) => match age_days {
+            Some(age) if age > i64::from(days) + TRANSITION_GRACE_DAYS => (TransitionState::PossiblyDelayed, None),
+            _ => (TransitionState::OnSchedule, None),
+        },
+    };
+    UntransitionedObject {
+        key: object.key.clone(),
+        size: object.size,
+        storage_class: object.storage_class.clone(),
+        last_modified: object.last_modified.clone(),
+        age_days,
+        transition_days,
+        state,
+        reason,
+    }
+}
+
+/// オブジェクト一覧とライフサイクルルールから移行状況を集計
+pub fn evaluate_archive_transitions(
+    bucket: &str,
+    prefix: Option<&str>,
+    objects: &[S3Object],
+    rules: &[LifecycleRule],
+    now: DateTime<Utc>,
+) -> ArchiveTransitionStatus {
+    let transitions = archive_transitions(rules);
+    let mut storage_classes = BTreeMap::new();
+    let mut untransitioned = Vec::new();
+    for object in objects {
+        *storage_classes.entry(object.storage_class.clone()).or_insert(0) += 1;
+        if !ARCHIVE_STORAGE_CLASSES.contains(&object.storage_class.as_str()) {
+            untransitioned.push(classify(object, &transitions, now));
+        }
+    }
+    let count = |state: TransitionState| untransitioned.iter().filter(|o| o.state == state).count();
+    let (on_schedule_count, delayed_count, not_covered_count) = (
+        count(TransitionState::OnSchedule),
+        count(TransitionState::PossiblyDelayed),
+        count(TransitionState::NotCovered),
+    );
+    untransitioned.sort_by_key(|o| (o.state == TransitionState::OnSchedule, std::cmp::Reverse(o.age_days)));
+
+    let archived_objects = objects.len() - untransitioned.len();
+    ArchiveTransitionStatus {
+        bucket: bucket.to_string(),
+        prefix: prefix.map(str::to_string),
+        total_objects: objects.len(),
+        archived_objects,
+        archived_ratio: if objects.is_empty() { 1.0 } else { archived_objects as f64 / objects.len() as f64 },
+        storage_classes,
+        on_schedule_count,
+        delayed_count,
+        not_covered_count,
+        untransitioned,
+        checked_at: now.to_rfc3339(),
+    }
+}
+
+/// ライフサイクルルールとオブジェクト一覧を取得して集計（ルール未設定は全オブジェクト対象外）
+pub async fn inspect_archive_transitions(
+    s3_client: &dyn S3ClientTrait,
+    bucket: &str,
+    prefix: Option<&str>,
+) -> Result<ArchiveTransitionStatus, String> {
+    let rules = match s3_client.get_bucket_lifecycle_configuration(bucket).await {
+        Ok(rules) => rules,
+        Err(e) if e.contains("NoSuchLifecycleConfiguration") => Vec::new(),
+        Err(e) => return Err(e),
+    };
+    let objects = s3_client.list_objects(bucket, prefix).await?;
+    let status = evaluate_archive_transitions(bucket, prefix, &objects, &rules, Utc::now());
+    log::info!(
+        "Archive transition status for s3://{}/{}: {}/{} archived, {} delayed, {} not covered",
+        bucket, prefix.unwrap_or(""), status.archived_objects, status.total_objects,
+        status.delayed_count, status.not_covered_count
+    );
+    Ok(status)
+}
+
+/// ヘルスレポート用（キュー設定の認証情報で確認する）
+pub async fn archive_transitions_for_credentials(
+    credentials: &AwsCredentials,
+    bucket: &str,
+    prefix: Option<&str>,
+) -> Result<ArchiveTransitionStatus, String> {
+    let s3_client = create_s3_client_for_credentials(credentials).await?;
+    inspect_archive_transitions(s3_client.as_ref(), bucket, prefix).await
+}
+
+/// プレフィックス配下のオブジェクトがアーカイブ系ストレージクラスへ移行済みか確認
+#[command]
+pub async fn check_archive_transition_status(
+    app: AppHandle,
+    config: AwsConfig,
+    prefix: Option<String>,
+) -> Result<ArchiveTransitionStatus, AppError> {
+    if config.bucket_name.is_empty() {
+        return Err(InternalError::localized(ErrorCode::Config, MessageKey::BucketNameRequired).into());
+    }
+    let s3_client = create_real_s3_client(&app, &config).await?;
+    let prefix = prefix.filter(|prefix| !prefix.is_empty());
+    Ok(inspect_archive_transitions(s3_client.as_ref(), &config.bucket_name, prefix.as_deref())
+        .await
+        .map_err(InternalError::s3)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::aws_operations::LifecycleTransition;
+
+    fn object(key: &str, size: u64, last_modified: &str, storage_class: &str) -> S3Object {
+        S3Object {
+            key: key.to_string(),
+            size,
+            last_modified: last_modified.to_string(),
+            storage_class: storage_class.to_string(),
+            etag: String::new(),
+        }
+    }
+
+    fn uploads_rule(days: i32) -> LifecycleRule {
+        LifecycleRule {
+            id: "archive".to_string(),
+            status: "Enabled".to_string(),
+            prefix: Some("uploads/".to_string()),
+            transitions: vec![LifecycleTransition { days, storage_class: "DEEP_ARCHIVE".to_string() }],
+        }
+    }
+
+    #[test]
+    fn test_classifies_untransitioned_objects() {
+        let now = DateTime::parse_from_rfc3339("2024-06-10T00:00:00Z").unwrap().with_timezone(&Utc);
+        let mb = 1024 * 1024;
+        let objects = vec![
+            object("uploads/archived.mov", mb, "2024-06-01T00:00:00Z", "DEEP_ARCHIVE"),
+            object("uploads/new.mov", mb, "2024-06-09T12:00:00Z", "STANDARD"),
+            object("uploads/stuck.mov", mb, "2024-06-01T00:00:00Z", "STANDARD"),
+            object("uploads/thumb.jpg", 10 * 1024, "2024-06-01T00:00:00Z", "STANDARD"),
+            object("exports/cut.mov", mb, "2024-06-01T00:00:00Z", "STANDARD"),
+        ];
+        let status = evaluate_archive_transitions("footage", None, &objects, &[uploads_rule(1)], now);
+
+        assert_eq!((status.total_objects, status.archived_objects), (5, 1));
+        assert_eq!(status.archived_ratio, 0.2);
+        assert_eq!(status.storage_classes["STANDARD"], 4);
+        assert_eq!((status.on_schedule_count, status.delayed_count, status.not_covered_count), (1, 1, 2));
+        let by_key = |key: &str| status.untransitioned.iter().find(|o| o.key == key).unwrap().clone();
+        assert_eq!(by_key("uploads/stuck.mov").state, TransitionState::PossiblyDelayed);
+        assert_eq!(by_key("uploads/thumb.jpg").reason, Some(UncoveredReason::BelowMinimumSize));
+        assert_eq!(by_key("exports/cut.mov").reason, Some(UncoveredReason::PrefixMismatch));
+        // 予定どおりのものは最後
+        assert_eq!(status.untransitioned.last().unwrap().key, "uploads/new.mov");
+    }
+
+    #[test]
+    fn test_disabled_rules_leave_everything_uncovered() {
+        let mut rule = uploads_rule(1);
+        rule.status = "Disabled".to_string();
+        let objects = vec![object("uploads/a.mov", 1024 * 1024, "2024-06-01T00:00:00Z", "STANDARD")];
+        let status = evaluate_archive_transitions("footage", Some("uploads/"), &objects, &[rule], Utc::now());
+        assert_eq!(status.not_covered_count, 1);
+        assert_eq!(status.untransitioned[0].transition_days, None);
+    }
+}
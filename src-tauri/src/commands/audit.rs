@@ -0,0 +1,321 @@
+use std::path::PathBuf;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle};
+
+use crate::commands::metadata::{metadata_db_path, MetadataDatabase};
+use crate::internal::{AppError, InternalError};
+
+/// 監査ログの既定の取得件数
+const DEFAULT_AUDIT_LOG_LIMIT: usize = 200;
+
+/// 監査対象の操作種別
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOperation {
+    Upload,
+    Download,
+    RestoreRequest,
+    S3Delete,
+    LocalDelete,
+    /// S3上のオブジェクトとメタデータの整合性検証
+    RemoteVerify,
+    /// バケットのReelVault用初期化
+    BucketInitialize,
+    /// ミラー先バケットへのアップロード
+    MirrorUpload,
+}
+
+impl AuditOperation {
+    /// DBに保存する文字列表現
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditOperation::Upload => "upload",
+            AuditOperation::Download => "download",
+            AuditOperation::RestoreRequest => "restore_request",
+            AuditOperation::S3Delete => "s3_delete",
+            AuditOperation::LocalDelete => "local_delete",
+            AuditOperation::RemoteVerify => "remote_verify",
+            AuditOperation::BucketInitialize => "bucket_initialize",
+            AuditOperation::MirrorUpload => "mirror_upload",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Option<Self> {
+        match value {
+            "upload" => Some(AuditOperation::Upload),
+            "download" => Some(AuditOperation::Download),
+            "restore_request" => Some(AuditOperation::RestoreRequest),
+            "s3_delete" => Some(AuditOperation::S3Delete),
+            "local_delete" => Some(AuditOperation::LocalDelete),
+            "remote_verify" => Some(AuditOperation::RemoteVerify),
+            "bucket_initialize" => Some(AuditOperation::BucketInitialize),
+            "mirror_upload" => Some(AuditOperation::MirrorUpload),
+            _ => None,
+        }
+    }
+}
+
+/// 操作の監査記録
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OperationAuditEntry {
+    pub id: Option<i64>,
+    pub operation: AuditOperation,
+    pub local_path: Option<String>,
+    pub bucket: Option<String>,
+    pub s3_key: Option<String>,
+    pub success: bool,
+    pub size_bytes: Option<u64>,
+    pub duration_ms: Option<u64>,
+    pub occurred_at: String,
+    pub error_message: Option<String>,
+    /// アップロードしたアイテムの案件ID
+    #[serde(default)]
+    pub project_id: Option<String>,
+}
+
+impl OperationAuditEntry {
+    /// 現在時刻で記録を作成（結果は`Result`から設定）
+    pub fn new<T, E: std::fmt::Display>(operation: AuditOperation, result: &Result<T, E>) -> Self {
+        Self {
+            id: None,
+            operation,
+            local_path: None,
+            bucket: None,
+            s3_key: None,
+            success: result.is_ok(),
+            size_bytes: None,
+            duration_ms: None,
+            occurred_at: chrono::Utc::now().to_rfc3339(),
+            error_message: result.as_ref().err().map(|e| e.to_string()),
+            project_id: None,
+        }
+    }
+
+    pub fn local_path(mut self, path: impl Into<String>) -> Self {
+        self.local_path = Some(path.into());
+        self
+    }
+
+    pub fn s3_object(mut self, bucket: impl Into<String>, key: impl Into<String>) -> Self {
+        self.bucket = Some(bucket.into());
+        self.s3_key = Some(key.into());
+        self
+    }
+
+    pub fn size(mut self, size_bytes: u64) -> Self {
+        self.size_bytes = Some(size_bytes);
+        self
+    }
+
+    pub fn duration(mut self, elapsed: Duration) -> Self {
+        self.duration_ms = Some(elapsed.as_millis() as u64);
+        self
+    }
+
+    pub fn project_id(mut self, project_id: Option<String>) -> Self {
+        self.project_id = project_id;
+        self
+    }
+}
+
+/// 監査ログの検索条件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditLogFilter {
+    pub operation: Option<AuditOperation>,
+    pub success: Option<bool>,
+    /// ローカルパスまたはS3キーの部分一致
+    pub path_contains: Option<String>,
+    /// RFC3339形式の開始日時（この日時以降）
+    pub since: Option<String>,
+    /// RFC3339形式の終了日時（この日時以前）
+    pub until: Option<String>,
+}
+
+/// 監査ログのエクスポート形式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditExportFormat {
+    Csv,
+    Json,
+}
+
+/// 監査ログの書き込み先
+///
+/// DBパスを解決済みで保持するため、AppHandleを持たない処理にも渡せる
+#[derive(Debug, Clone)]
+pub struct AuditLogger {
+    db_path: PathBuf,
+}
+
+impl AuditLogger {
+    pub fn new(db_path: PathBuf) -> Self {
+        Self { db_path }
+    }
+
+    /// アプリのメタデータDBへ書き込むロガー
+    pub fn for_app(app: &AppHandle) -> Option<Self> {
+        match metadata_db_path(app) {
+            Ok(db_path) => Some(Self::new(db_path)),
+            Err(e) => {
+                log::error!("Failed to resolve audit log database: {}", e);
+                None
+            }
+        }
+    }
+
+    /// 監査記録を書き込む（失敗しても操作自体は継続させる）
+    pub fn record(&self, entry: &OperationAuditEntry) {
+        if let Err(e) = self.try_record(entry) {
+            log::error!("Failed to write {} audit entry: {}", entry.operation.as_str(), e);
+        }
+    }
+
+    fn try_record(&self, entry: &OperationAuditEntry) -> Result<(), InternalError> {
+        let db = MetadataDatabase::new(&self.db_path.to_string_lossy())?;
+        db.insert_operation_audit(entry)?;
+        Ok(())
+    }
+}
+
+/// アプリのメタデータDBへ監査記録を書き込む
+pub fn record_operation(app: &AppHandle, entry: OperationAuditEntry) {
+    if let Some(logger) = AuditLogger::for_app(app) {
+        logger.record(&entry);
+    }
+}
+
+fn open_audit_db(app: &AppHandle) -> Result<MetadataDatabase, InternalError> {
+    let db_path = metadata_db_path(app)?;
+    Ok(MetadataDatabase::new(&db_path.to_string_lossy())?)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 監査記録をCSVへ変換
+fn entries_to_csv(entries: &[OperationAuditEntry]) -> String {
+    let mut csv = String::from(
+        "id,occurred_at,operation,success,local_path,bucket,s3_key,size_bytes,duration_ms,error_message,project_id\n",
+    );
+    for entry in entries {
+        let fields = [
+            entry.id.map(|id| id.to_string()).unwrap_or_default(),
+            entry.occurred_at.clone(),
+            entry.operation.as_str().to_string(),
+            entry.success.to_string(),
+            entry.local_path.clone().unwrap_or_default(),
+            entry.bucket.clone().unwrap_or_default(),
+            entry.s3_key.clone().unwrap_or_default(),
+            entry.size_bytes.map(|s| s.to_string()).unwrap_or_default(),
+            entry.duration_ms.map(|d| d.to_string()).unwrap_or_default(),
+            entry.error_message.clone().unwrap_or_default(),
+            entry.project_id.clone().unwrap_or_default(),
+        ];
+        let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        csv.push_str(&line.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// 操作の監査ログを新しい順に取得
+#[command]
+pub async fn get_audit_log(
+    app: AppHandle,
+    filter: Option<AuditLogFilter>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> Result<Vec<OperationAuditEntry>, AppError> {
+    let db = open_audit_db(&app)?;
+    let entries = db.query_operation_audit(
+        &filter.unwrap_or_default(),
+        limit.unwrap_or(DEFAULT_AUDIT_LOG_LIMIT),
+        offset.unwrap_or(0),
+    ).map_err(InternalError::from)?;
+    Ok(entries)
+}
+
+/// 条件に一致する監査ログをCSVまたはJSONで書き出し、件数を返す
+#[command]
+pub async fn export_audit_log(
+    app: AppHandle,
+    format: AuditExportFormat,
+    output_path: String,
+    filter: Option<AuditLogFilter>,
+) -> Result<usize, AppError> {
+    let db = open_audit_db(&app)?;
+    let entries = db.query_operation_audit(&filter.unwrap_or_default(), usize::MAX, 0)
+        .map_err(InternalError::from)?;
+
+    let content = match format {
+        AuditExportFormat::Csv => entries_to_csv(&entries),
+        AuditExportFormat::Json => serde_json::to_string_pretty(&entries).map_err(InternalError::from)?,
+    };
+    std::fs::write(&output_path, content).map_err(InternalError::from)?;
+
+    log::info!("Exported {} audit entries to {}", entries.len(), output_path);
+    Ok(entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample_entry(operation: AuditOperation, result: Result<(), String>) -> OperationAuditEntry {
+        OperationAuditEntry::new(operation, &result)
+            .local_path("/videos/clip, final.mov")
+            .s3_object("bucket", "2024/clip.mov")
+            .size(1024)
+            .duration(Duration::from_millis(1500))
+            .project_id(Some("PRJ-001".to_string()))
+    }
+
+    #[test]
+    fn test_entry_captures_result() {
+        let ok = sample_entry(AuditOperation::Upload, Ok(()));
+        assert!(ok.success);
+        assert_eq!(ok.duration_ms, Some(1500));
+        assert!(ok.error_message.is_none());
+
+        let failed = sample_entry(AuditOperation::Download, Err("timeout".to_string()));
+        assert!(!failed.success);
+        assert_eq!(failed.error_message.as_deref(), Some("timeout"));
+    }
+
+    #[test]
+    fn test_logger_roundtrip_with_filter() {
+        let dir = TempDir::new().unwrap();
+        let logger = AuditLogger::new(dir.path().join("metadata.db"));
+        logger.record(&sample_entry(AuditOperation::Upload, Ok(())));
+        logger.record(&sample_entry(AuditOperation::S3Delete, Err("denied".to_string())));
+
+        let db = MetadataDatabase::new(&dir.path().join("metadata.db").to_string_lossy()).unwrap();
+        let all = db.query_operation_audit(&AuditLogFilter::default(), 10, 0).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].operation, AuditOperation::S3Delete);
+        assert_eq!(all[1].project_id.as_deref(), Some("PRJ-001"));
+
+        let failures = AuditLogFilter { success: Some(false), ..Default::default() };
+        let failed = db.query_operation_audit(&failures, 10, 0).unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].error_message.as_deref(), Some("denied"));
+
+        let paged = db.query_operation_audit(&AuditLogFilter::default(), 1, 1).unwrap();
+        assert_eq!(paged[0].operation, AuditOperation::Upload);
+    }
+
+    #[test]
+    fn test_csv_quotes_special_characters() {
+        let csv = entries_to_csv(&[sample_entry(AuditOperation::Upload, Ok(()))]);
+        let line = csv.lines().nth(1).unwrap();
+        assert!(line.contains("\"/videos/clip, final.mov\""));
+        assert!(line.contains(",upload,true,"));
+    }
+}
@@ -0,0 +1,525 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use notify::EventKind;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter, Manager, State};
+use tokio::sync::mpsc;
+
+use crate::commands::aws_auth::AwsConfig;
+use crate::commands::credential_cache::cached_credentials_in_region;
+use crate::commands::file_operations::{should_exclude_file, validate_file_path, validate_file_size, record_watch_event, RenameCompletionTracker, WatchConfig, WatcherActivityGuard};
+use crate::commands::lifecycle::{enable_reelvault_lifecycle, get_lifecycle_status};
+use crate::commands::metadata_jobs::enqueue_metadata_job;
+use crate::commands::watch_backend::{start_watch, WatchHandle};
+use crate::commands::state_management::AppStateManager;
+use crate::commands::upload_system::{
+    generate_s3_key, new_upload_item, queue_lock_error, start_queue_processing, stop_queue_processing, S3KeyConfig, UploadConfig,
+    UploadQueueState,
+};
+use crate::internal::{AppError, InternalError};
+
+/// 自動アーカイブの状態変化をフロントエンドへ通知するイベント名
+pub const AUTO_ARCHIVE_STATUS_EVENT: &str = "auto-archive-status-changed";
+
+/// 書き込み中のファイルを検出するためのサイズ確認間隔
+const STABILITY_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+/// この回数連続でサイズが変わらなければ書き込み完了とみなす
+const REQUIRED_STABLE_CHECKS: u32 = 2;
+
+/// 自動アーカイブ全体のヘルス状態
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AutoArchiveHealth {
+    Disabled,
+    Healthy,
+    /// いずれかの段階で失敗している（理由はreasonを参照）
+    Degraded,
+}
+
+/// 自動アーカイブの状態
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoArchiveStatus {
+    pub enabled: bool,
+    pub health: AutoArchiveHealth,
+    pub reason: Option<String>,
+    pub watch_path: Option<String>,
+    pub queued_count: u64,
+    pub last_queued_at: Option<String>,
+}
+
+impl Default for AutoArchiveStatus {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            health: AutoArchiveHealth::Disabled,
+            reason: None,
+            watch_path: None,
+            queued_count: 0,
+            last_queued_at: None,
+        }
+    }
+}
+
+/// 自動アーカイブで使うアップロード設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoArchiveUploadSettings {
+    pub config: UploadConfig,
+    pub s3_key_config: S3KeyConfig,
+    /// ReelVault標準ライフサイクルポリシーを確認し、未設定なら有効化する
+    #[serde(default = "default_ensure_lifecycle")]
+    pub ensure_lifecycle: bool,
+}
+
+fn default_ensure_lifecycle() -> bool {
+    true
+}
+
+/// 実行中の自動アーカイブ（破棄すると監視が止まる）
+struct AutoArchiveRuntime {
+    _watcher: WatchHandle,
+    task: tauri::async_runtime::JoinHandle<()>,
+    /// 自動アーカイブ側でキュー処理を開始したか（停止時に合わせて止める）
+    started_processing: bool,
+}
+
+#[derive(Default)]
+struct AutoArchiveInner {
+    runtime: Option<AutoArchiveRuntime>,
+    status: AutoArchiveStatus,
+    /// トレイから再度有効化するための前回の設定
+    last_settings: Option<(WatchConfig, AutoArchiveUploadSettings)>,
+}
+
+/// 自動アーカイブの状態（Tauriのmanage対象）
+#[derive(Default)]
+pub struct AutoArchiveState(Mutex<AutoArchiveInner>);
+
+impl AutoArchiveState {
+    pub fn status(&self) -> AutoArchiveStatus {
+        self.0.lock().map(|inner| inner.status.clone()).unwrap_or_default()
+    }
+}
+
+/// ファイルの書き込み完了（サイズが一定回数変化しない）を判定する
+#[derive(Debug, Default)]
+pub struct StabilityTracker {
+    pending: HashMap<PathBuf, (u64, u32)>,
+    /// キュー済みのファイルとそのサイズ（同じ内容の再キューを防ぐ）
+    queued: HashMap<PathBuf, u64>,
+}
+
+impl StabilityTracker {
+    /// 変更を検知したファイルを確認対象に加える
+    pub fn observe(&mut self, path: PathBuf) {
+        self.pending.entry(path).or_insert((u64::MAX, 0));
+    }
+
+    /// 書き込み完了を待っているファイル数
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// 現在のサイズで確認し、書き込みが完了したファイルを返す
+    pub fn poll(&mut self, size_of: impl Fn(&Path) -> Option<u64>) -> Vec<(PathBuf, u64)> {
+        let mut ready = Vec::new();
+        self.pending.retain(|path, (last_size, stable_checks)| {
+            let Some(size) = size_of(path) else {
+                // 確認前に削除・移動されたファイルは対象外
+                return false;
+            };
+            if size == *last_size && size > 0 {
+                *stable_checks += 1;
+            } else {
+                *last_size = size;
+                *stable_checks = 0;
+            }
+            if *stable_checks >= REQUIRED_STABLE_CHECKS {
+                ready.push((path.clone(), size));
+                return false;
+            }
+            true
+        });
+        ready.retain(|(path, size)| self.queued.get(path) != Some(size));
+        for (path, size) in &ready {
+            self.queued.insert(path.clone(), *size);
+        }
+        ready
+    }
+}
+
+/// 状態を更新し、AppState・トレイ・フロントエンドへ反映
+fn publish_status(app: &AppHandle, update: impl FnOnce(&mut AutoArchiveStatus)) -> AutoArchiveStatus {
+    let state = app.state::<AutoArchiveState>();
+    let status = {
+        let mut inner = state.0.lock().unwrap_or_else(|e| e.into_inner());
+        update(&mut inner.status);
+        inner.status.clone()
+    };
+
+    if let Some(app_state) = app.try_state::<AppStateManager>() {
+        if let Ok(mut app_state) = app_state.lock() {
+            app_state.auto_archive_enabled = status.enabled;
+        }
+    }
+    crate::tray::sync_auto_archive_state(app, status.enabled);
+    if let Err(e) = app.emit(AUTO_ARCHIVE_STATUS_EVENT, &status) {
+        log::error!("Failed to emit auto archive status: {}", e);
+    }
+    status
+}
+
+/// 段階の失敗を記録してdegradedへ移行
+fn mark_degraded(app: &AppHandle, reason: String) {
+    log::warn!("Auto archive degraded: {}", reason);
+    publish_status(app, |status| {
+        if status.enabled {
+            status.health = AutoArchiveHealth::Degraded;
+            status.reason = Some(reason);
+        }
+    });
+}
+
+/// ReelVault標準ライフサイクルポリシーが有効か確認し、未設定なら有効化
+async fn ensure_lifecycle(app: &AppHandle, config: &UploadConfig) -> Result<(), String> {
+    let aws_config = AwsConfig {
+        region: config.region.clone(),
+        bucket_name: config.bucket_name.clone(),
+    };
+    if get_lifecycle_status(app.clone(), aws_config.clone()).await?.enabled {
+        return Ok(());
+    }
+    let result = enable_reelvault_lifecycle(app.clone(), aws_config).await?;
+    if !result.success {
+        return Err(result.message);
+    }
+    Ok(())
+}
+
+/// 書き込みが完了したファイルのメタデータを作成してキューに追加
+async fn archive_file(
+    app: &AppHandle,
+    path: &Path,
+    watch_config: &WatchConfig,
+    s3_key_config: &S3KeyConfig,
+) -> Result<(), String> {
+    if let Some(max_size) = watch_config.max_file_size_mb {
+        if let Err(e) = validate_file_size(&path.to_path_buf(), max_size) {
+            log::warn!("Auto archive skipped {}: {}", path.display(), e);
+            return Ok(());
+        }
+    }
+
+    // メタデータは後から作成されるため、アップロードをハッシュ計算の完了まで待たせない
+    if watch_config.auto_metadata {
+        enqueue_metadata_job(path);
+    }
+
+    let file_path = path.to_string_lossy().to_string();
+    let queue_state = app.state::<UploadQueueState>();
+    {
+        let mut queue = queue_state.lock()
+            .map_err(|e| format!("Failed to lock upload queue: {}", e))?;
+        queue.check_tier_limits(1)
+            .map_err(|e| format!("キューに追加できません: {}", e))?;
+        let s3_key = generate_s3_key(&file_path, s3_key_config).map_err(|e| e.to_string())?;
+        let item = new_upload_item(&file_path, s3_key).map_err(|e| e.to_string())?;
+        queue.items.push(item);
+    }
+    crate::badge::update_badge(app, queue_state.inner());
+
+    // 処理ループが止まっていれば再開
+    start_queue_processing(app, queue_state.inner())
+        .map_err(|e| format!("アップロード処理を開始できません: {}", e))?;
+
+    log::info!("Auto archive queued: {}", file_path);
+    publish_status(app, |status| {
+        status.queued_count += 1;
+        status.last_queued_at = Some(chrono::Utc::now().to_rfc3339());
+    });
+    Ok(())
+}
+
+/// 監視イベントを受け取り、安定化待ちの後にアーカイブする
+async fn run_pipeline(
+    app: AppHandle,
+    watch_config: WatchConfig,
+    s3_key_config: S3KeyConfig,
+    mut events: mpsc::UnboundedReceiver<notify::Result<notify::Event>>,
+) {
+    let _activity = WatcherActivityGuard::start();
+    let mut tracker = StabilityTracker::default();
+    let mut rename_tracker = RenameCompletionTracker::from_config(&watch_config);
+    let mut ticker = tokio::time::interval(STABILITY_CHECK_INTERVAL);
+
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                match event {
+                    Some(Ok(event)) => {
+                        record_watch_event();
+                        // リネーム完了ベースの検知が有効な場合は、最終名が確定したパスのみ安定化待ちに入れる
+                        let paths = match rename_tracker.as_mut() {
+                            Some(rename_tracker) => rename_tracker.observe(&event, std::time::Instant::now()),
+                            None if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => event.paths,
+                            None => Vec::new(),
+                        };
+                        for path in paths {
+                            if path.is_file() && !should_exclude_file(&path, &watch_config) {
+                                tracker.observe(path);
+                            }
+                        }
+                    }
+                    Some(Err(e)) => mark_degraded(&app, format!("ファイル監視でエラーが発生しました: {}", e)),
+                    None => {
+                        mark_degraded(&app, "ファイル監視が停止しました".to_string());
+                        return;
+                    }
+                }
+            }
+            _ = ticker.tick() => {
+                if let Some(rename_tracker) = rename_tracker.as_mut() {
+                    for path in rename_tracker.flush_expired(std::time::Instant::now()) {
+                        if path.is_file() && !should_exclude_file(&path, &watch_config) {
+                            tracker.observe(path);
+                        }
+                    }
+                }
+                let ready = tracker.poll(|path| std::fs::metadata(path).ok().map(|m| m.len()));
+                for (path, _) in ready {
+                    if let Err(reason) = archive_file(&app, &path, &watch_config, &s3_key_config).await {
+                        mark_degraded(&app, format!("{}: {}", path.display(), reason));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 監視・メタデータ作成・キュー追加・アップロード・ライフサイクル移行を一括で開始
+pub async fn start_auto_archive(
+    app: &AppHandle,
+    watch_config: WatchConfig,
+    mut upload_settings: AutoArchiveUploadSettings,
+) -> Result<AutoArchiveStatus, InternalError> {
+    stop_auto_archive(app);
+    // 認証情報はキャッシュから取得するため、ロック中は開始しない
+    upload_settings.config.region = cached_credentials_in_region(app, &upload_settings.config.region)?.region;
+
+    let watch_path = validate_file_path(&PathBuf::from(&watch_config.path))?;
+    if !watch_path.is_dir() {
+        return Err(InternalError::File(format!("Watch path is not a directory: {}", watch_path.display())));
+    }
+    if watch_config.file_patterns.is_empty() {
+        return Err(InternalError::Config("File patterns cannot be empty".to_string()));
+    }
+
+    // アップロード設定を反映（処理中は進行中の設定を維持）
+    let queue_state = app.state::<UploadQueueState>();
+    {
+        let mut queue = queue_state.lock()
+            .map_err(queue_lock_error)?;
+        if !queue.is_processing {
+            queue.config = Some(upload_settings.config.clone());
+        }
+    }
+
+    let mut degraded_reason = None;
+    if upload_settings.ensure_lifecycle {
+        if let Err(e) = ensure_lifecycle(app, &upload_settings.config).await {
+            degraded_reason = Some(format!("ライフサイクルポリシーを設定できません: {}", e));
+        }
+    }
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    // NASなどネットワークボリュームでは自動でポーリング監視になる
+    let watcher = start_watch(&watch_path, &watch_config, move |result| {
+        let _ = tx.send(result);
+    })?;
+
+    let started_processing = match start_queue_processing(app, queue_state.inner()) {
+        Ok(started) => started,
+        Err(e) => {
+            degraded_reason.get_or_insert(format!("アップロード処理を開始できません: {}", e));
+            false
+        }
+    };
+
+    let task = tauri::async_runtime::spawn(run_pipeline(
+        app.clone(),
+        watch_config.clone(),
+        upload_settings.s3_key_config.clone(),
+        rx,
+    ));
+
+    {
+        let state = app.state::<AutoArchiveState>();
+        let mut inner = state.0.lock().unwrap_or_else(|e| e.into_inner());
+        inner.runtime = Some(AutoArchiveRuntime { _watcher: watcher, task, started_processing });
+        inner.last_settings = Some((watch_config, upload_settings));
+    }
+
+    log::info!("Auto archive enabled for: {}", watch_path.display());
+    if let Some(reason) = &degraded_reason {
+        log::warn!("Auto archive started in degraded state: {}", reason);
+    }
+    Ok(publish_status(app, |status| {
+        *status = AutoArchiveStatus {
+            enabled: true,
+            health: if degraded_reason.is_some() { AutoArchiveHealth::Degraded } else { AutoArchiveHealth::Healthy },
+            reason: degraded_reason,
+            watch_path: Some(watch_path.to_string_lossy().to_string()),
+            ..AutoArchiveStatus::default()
+        };
+    }))
+}
+
+/// 監視・安定化待ちを停止し、自動アーカイブで開始したキュー処理も止める
+pub fn stop_auto_archive(app: &AppHandle) -> AutoArchiveStatus {
+    let runtime = {
+        let state = app.state::<AutoArchiveState>();
+        let mut inner = state.0.lock().unwrap_or_else(|e| e.into_inner());
+        inner.runtime.take()
+    };
+
+    if let Some(runtime) = runtime {
+        runtime.task.abort();
+        if runtime.started_processing {
+            if let Err(e) = stop_queue_processing(app.state::<UploadQueueState>().inner()) {
+                log::error!("Failed to stop upload processing: {}", e);
+            }
+        }
+        // ウォッチャーはここで破棄され、監視が停止する
+        drop(runtime._watcher);
+        log::info!("Auto archive disabled");
+    }
+
+    publish_status(app, |status| {
+        status.enabled = false;
+        status.health = AutoArchiveHealth::Disabled;
+        status.reason = None;
+    })
+}
+
+/// 動作中の自動アーカイブの設定（認証情報は消去済み。停止中はNone）
+pub fn active_auto_archive_settings(app: &AppHandle) -> Option<(WatchConfig, AutoArchiveUploadSettings)> {
+    let state = app.try_state::<AutoArchiveState>()?;
+    let inner = state.0.lock().ok()?;
+    inner.runtime.as_ref().and(inner.last_settings.clone())
+}
+
+/// 終了時にウォッチャーと安定化待ちのタスクを解放する
+///
+/// 状態は公開しないため、AppStateには有効だったことが残る
+pub fn release_auto_archive_watcher(app: &AppHandle) -> bool {
+    let runtime = {
+        let state = app.state::<AutoArchiveState>();
+        let mut inner = state.0.lock().unwrap_or_else(|e| e.into_inner());
+        inner.runtime.take()
+    };
+
+    match runtime {
+        Some(runtime) => {
+            runtime.task.abort();
+            drop(runtime._watcher);
+            true
+        }
+        None => false,
+    }
+}
+
+/// トレイから前回の設定で自動アーカイブを切り替え
+pub fn toggle_auto_archive(app: &AppHandle) {
+    let state = app.state::<AutoArchiveState>();
+    if state.status().enabled {
+        stop_auto_archive(app);
+        return;
+    }
+
+    let last_settings = state.0.lock().ok().and_then(|inner| inner.last_settings.clone());
+    let Some((watch_config, upload_settings)) = last_settings else {
+        // 一度も設定されていない場合は設定画面で有効化してもらう
+        crate::tray::sync_auto_archive_state(app, false);
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.show();
+            let _ = window.set_focus();
+            let _ = app.emit("open-tab", "auto-archive");
+        }
+        return;
+    };
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = start_auto_archive(&app, watch_config, upload_settings).await {
+            log::error!("Failed to enable auto archive from tray: {}", e);
+            crate::tray::sync_auto_archive_state(&app, false);
+        }
+    });
+}
+
+/// 自動アーカイブを有効化
+#[command]
+pub async fn enable_auto_archive(
+    app_handle: AppHandle,
+    watch_config: WatchConfig,
+    upload_settings: AutoArchiveUploadSettings,
+) -> Result<AutoArchiveStatus, AppError> {
+    Ok(start_auto_archive(&app_handle, watch_config, upload_settings).await?)
+}
+
+/// 自動アーカイブを無効化
+#[command]
+pub async fn disable_auto_archive(app_handle: AppHandle) -> Result<AutoArchiveStatus, AppError> {
+    Ok(stop_auto_archive(&app_handle))
+}
+
+/// 自動アーカイブの状態を取得
+#[command]
+pub async fn get_auto_archive_status(state: State<'_, AutoArchiveState>) -> Result<AutoArchiveStatus, AppError> {
+    Ok(state.status())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stability_tracker_waits_for_stable_size() {
+        let mut tracker = StabilityTracker::default();
+        let path = PathBuf::from("/tmp/clip.mov");
+        tracker.observe(path.clone());
+
+        // 書き込み中はサイズが変化し続ける
+        assert!(tracker.poll(|_| Some(100)).is_empty());
+        assert!(tracker.poll(|_| Some(200)).is_empty());
+        assert!(tracker.poll(|_| Some(200)).is_empty());
+        assert_eq!(tracker.poll(|_| Some(200)), vec![(path.clone(), 200)]);
+
+        // 同じ内容の再通知はキューしない
+        tracker.observe(path.clone());
+        for _ in 0..3 {
+            assert!(tracker.poll(|_| Some(200)).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_stability_tracker_drops_removed_files() {
+        let mut tracker = StabilityTracker::default();
+        tracker.observe(PathBuf::from("/tmp/removed.mov"));
+        assert!(tracker.poll(|_| None).is_empty());
+        assert!(tracker.pending.is_empty());
+    }
+
+    #[test]
+    fn test_status_serialization() {
+        let status = AutoArchiveStatus {
+            enabled: true,
+            health: AutoArchiveHealth::Degraded,
+            reason: Some("lifecycle".to_string()),
+            ..AutoArchiveStatus::default()
+        };
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["health"], "degraded");
+    }
+}
@@ -0,0 +1,256 @@
+use std::path::Path;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter, Manager};
+
+use crate::commands::file_operations::WatchConfig;
+use crate::commands::metadata::{metadata_db_path, MetadataDatabase};
+use crate::commands::upload_system::{add_files_to_upload_queue, S3KeyConfig, UploadQueueState};
+use crate::internal::{AppError, InternalError};
+
+/// ガードを超えて自動アップロードを保留したときのイベント
+pub const AUTO_UPLOAD_THROTTLED_EVENT: &str = "auto-upload-throttled";
+
+/// 自動アップロードを保留した理由
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoUploadThrottleReason {
+    /// 直近1時間の件数がmax_auto_uploads_per_hourに達した
+    HourlyCount,
+    /// 直近24時間の容量がmax_auto_upload_bytes_per_dayを超える
+    DailyBytes,
+}
+
+impl AutoUploadThrottleReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::HourlyCount => "hourly_count",
+            Self::DailyBytes => "daily_bytes",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "daily_bytes" => Self::DailyBytes,
+            _ => Self::HourlyCount,
+        }
+    }
+}
+
+/// ユーザーの承認待ちになっている自動アップロード
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PendingAutoUpload {
+    pub file_path: String,
+    pub file_size: u64,
+    /// 検知したウォッチフォルダ
+    pub watch_path: String,
+    pub detected_at: String,
+    pub reason: AutoUploadThrottleReason,
+}
+
+/// auto-upload-throttledイベントのペイロード
+#[derive(Debug, Clone, Serialize)]
+pub struct AutoUploadThrottled {
+    pub item: PendingAutoUpload,
+    /// 保留中の合計件数（一覧はlist_pending_auto_uploadsで取得する）
+    pub pending_count: u64,
+}
+
+/// 直近の自動アップロードの実績
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AutoUploadUsage {
+    pub uploads_last_hour: u64,
+    pub bytes_last_day: u64,
+}
+
+/// ガードの判定（上限が未設定の項目は判定しない）
+pub fn throttle_reason(config: &WatchConfig, usage: AutoUploadUsage, file_size: u64) -> Option<AutoUploadThrottleReason> {
+    if let Some(max) = config.max_auto_uploads_per_hour {
+        if usage.uploads_last_hour >= u64::from(max) {
+            return Some(AutoUploadThrottleReason::HourlyCount);
+        }
+    }
+    if let Some(max) = config.max_auto_upload_bytes_per_day {
+        if usage.bytes_last_day.saturating_add(file_size) > max {
+            return Some(AutoUploadThrottleReason::DailyBytes);
+        }
+    }
+    None
+}
+
+fn open_metadata_db(app: &AppHandle) -> Result<MetadataDatabase, InternalError> {
+    let db_path = metadata_db_path(app)?;
+    MetadataDatabase::new(&db_path.to_string_lossy())
+        .map_err(|e| InternalError::Database(format!("Failed to create database connection: {}", e)))
+}
+
+/// 直近の実績（カウンタはDBに記録するため再起動ではリセットされない）
+pub fn auto_upload_usage(db: &MetadataDatabase, now: DateTime<Utc>) -> Result<AutoUploadUsage, InternalError> {
+    let hour_ago = (now - chrono::Duration::hours(1)).to_rfc3339();
+    let day_ago = (now - chrono::Duration::days(1)).to_rfc3339();
+    let (uploads_last_hour, _) = db.auto_upload_totals_since(&hour_ago)?;
+    let (_, bytes_last_day) = db.auto_upload_totals_since(&day_ago)?;
+    Ok(AutoUploadUsage { uploads_last_hour, bytes_last_day })
+}
+
+/// ガードの範囲内なら実績として記録してNone、超える場合は保留リストへ入れて保留内容を返す
+pub fn admit_auto_upload(
+    db: &MetadataDatabase,
+    config: &WatchConfig,
+    file_path: &str,
+    file_size: u64,
+    now: DateTime<Utc>,
+) -> Result<Option<PendingAutoUpload>, InternalError> {
+    let usage = auto_upload_usage(db, now)?;
+    match throttle_reason(config, usage, file_size) {
+        None => {
+            db.record_auto_upload(file_path, file_size, &now.to_rfc3339())?;
+            // 判定に使わなくなった古い実績は削除する
+            db.prune_auto_upload_log(&(now - chrono::Duration::days(1)).to_rfc3339())?;
+            Ok(None)
+        }
+        Some(reason) => {
+            let pending = PendingAutoUpload {
+                file_path: file_path.to_string(),
+                file_size,
+                watch_path: config.path.clone(),
+                detected_at: now.to_rfc3339(),
+                reason,
+            };
+            db.insert_pending_auto_upload(&pending)?;
+            Ok(Some(pending))
+        }
+    }
+}
+
+async fn enqueue(app: &AppHandle, file_paths: Vec<String>) -> Result<String, AppError> {
+    let s3_key_config = S3KeyConfig::from_queue(&app.state::<UploadQueueState>())?;
+    add_files_to_upload_queue(app.clone(), file_paths, s3_key_config, None, None, None, app.state()).await
+}
+
+/// ウォッチイベントで検知したファイルをガードを通してアップロードキューへ追加
+pub async fn queue_auto_upload(app: &AppHandle, file_path: &Path, config: &WatchConfig) -> Result<(), String> {
+    let path = file_path.to_string_lossy().to_string();
+    let file_size = std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
+    let throttled = {
+        let db = open_metadata_db(app).map_err(|e| e.to_string())?;
+        match admit_auto_upload(&db, config, &path, file_size, Utc::now()).map_err(|e| e.to_string())? {
+            Some(item) => Some(AutoUploadThrottled {
+                item,
+                pending_count: db.count_pending_auto_uploads().map_err(|e| e.to_string())?,
+            }),
+            None => None,
+        }
+    };
+    match throttled {
+        Some(throttled) => {
+            log::warn!("Auto upload throttled ({}): {}", throttled.item.reason.as_str(), path);
+            if let Err(e) = app.emit(AUTO_UPLOAD_THROTTLED_EVENT, throttled) {
+                log::warn!("Failed to emit {}: {}", AUTO_UPLOAD_THROTTLED_EVENT, e);
+            }
+        }
+        None => {
+            enqueue(app, vec![path.clone()]).await.map_err(|e| e.message)?;
+            log::info!("Queued for auto upload: {}", path);
+        }
+    }
+    Ok(())
+}
+
+/// 承認待ちの自動アップロードを検知した順に取得
+#[command]
+pub async fn list_pending_auto_uploads(app: AppHandle) -> Result<Vec<PendingAutoUpload>, AppError> {
+    let db = open_metadata_db(&app)?;
+    Ok(db.list_pending_auto_uploads().map_err(InternalError::from)?)
+}
+
+/// 承認待ちのファイルをキューへ追加（パスの指定がない場合はすべて）
+///
+/// 明示的な承認のため、自動アップロードの実績には数えない
+#[command]
+pub async fn approve_pending_auto_uploads(
+    app: AppHandle,
+    file_paths: Option<Vec<String>>,
+) -> Result<Option<String>, AppError> {
+    let pending: Vec<String> = open_metadata_db(&app)?
+        .list_pending_auto_uploads()
+        .map_err(InternalError::from)?
+        .into_iter()
+        .map(|item| item.file_path)
+        .filter(|path| file_paths.as_ref().map(|paths| paths.contains(path)).unwrap_or(true))
+        .collect();
+    if pending.is_empty() {
+        return Ok(None);
+    }
+    let message = enqueue(&app, pending.clone()).await?;
+    open_metadata_db(&app)?.delete_pending_auto_uploads(Some(pending.as_slice())).map_err(InternalError::from)?;
+    log::info!("Approved {} pending auto upload(s)", pending.len());
+    Ok(Some(message))
+}
+
+/// 承認待ちのファイルを破棄（パスの指定がない場合はすべて）、破棄した件数を返す
+#[command]
+pub async fn discard_pending_auto_uploads(
+    app: AppHandle,
+    file_paths: Option<Vec<String>>,
+) -> Result<usize, AppError> {
+    let db = open_metadata_db(&app)?;
+    let discarded = db.delete_pending_auto_uploads(file_paths.as_deref()).map_err(InternalError::from)?;
+    log::info!("Discarded {} pending auto upload(s)", discarded);
+    Ok(discarded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guarded_config(per_hour: Option<u32>, bytes_per_day: Option<u64>) -> WatchConfig {
+        serde_json::from_value(serde_json::json!({
+            "path": "/watch",
+            "recursive": true,
+            "file_patterns": ["*.mov"],
+            "auto_upload": true,
+            "exclude_patterns": [],
+            "exclude_directories": [],
+            "auto_metadata": false,
+            "max_auto_uploads_per_hour": per_hour,
+            "max_auto_upload_bytes_per_day": bytes_per_day,
+        })).unwrap()
+    }
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_throttle_reason_checks_configured_limits_only() {
+        let usage = AutoUploadUsage { uploads_last_hour: 5, bytes_last_day: 900 };
+        assert_eq!(throttle_reason(&guarded_config(None, None), usage, 10_000), None);
+        assert_eq!(throttle_reason(&guarded_config(Some(5), None), usage, 1), Some(AutoUploadThrottleReason::HourlyCount));
+        assert_eq!(throttle_reason(&guarded_config(Some(6), Some(1000)), usage, 100), None);
+        assert_eq!(throttle_reason(&guarded_config(Some(6), Some(1000)), usage, 101), Some(AutoUploadThrottleReason::DailyBytes));
+    }
+
+    #[test]
+    fn test_admit_counts_persisted_window_and_holds_overflow() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("metadata.db");
+        let config = guarded_config(Some(2), None);
+        {
+            let db = MetadataDatabase::new(db_path.to_str().unwrap()).unwrap();
+            assert!(admit_auto_upload(&db, &config, "/watch/a.mov", 10, at("2024-06-01T10:00:00Z")).unwrap().is_none());
+            assert!(admit_auto_upload(&db, &config, "/watch/b.mov", 10, at("2024-06-01T10:10:00Z")).unwrap().is_none());
+        }
+
+        // 接続を開き直しても（再起動相当）カウンタは残る
+        let db = MetadataDatabase::new(db_path.to_str().unwrap()).unwrap();
+        let held = admit_auto_upload(&db, &config, "/watch/c.mov", 10, at("2024-06-01T10:20:00Z")).unwrap().unwrap();
+        assert_eq!(held.reason, AutoUploadThrottleReason::HourlyCount);
+        assert_eq!(db.list_pending_auto_uploads().unwrap(), vec![held]);
+
+        // 1時間経てば再び自動で積める
+        assert!(admit_auto_upload(&db, &config, "/watch/d.mov", 10, at("2024-06-01T11:05:00Z")).unwrap().is_none());
+        assert_eq!(db.delete_pending_auto_uploads(Some(&["/watch/c.mov".to_string()][..])).unwrap(), 1);
+        assert_eq!(db.count_pending_auto_uploads().unwrap(), 0);
+    }
+}
@@ -0,0 +1,252 @@
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Manager, State};
+use tauri_plugin_autostart::ManagerExt;
+
+use crate::commands::auto_archive::{active_auto_archive_settings, start_auto_archive, AutoArchiveUploadSettings};
+use crate::commands::config::{load_config, set_config};
+use crate::commands::credential_cache::{cached_credentials, unlock_credentials, unlock_credentials_on_startup};
+use crate::commands::file_operations::WatchConfig;
+use crate::commands::hot_folder::{active_hot_folder_settings, start_hot_folder, HotFolderSettings};
+use crate::commands::mock_aws::is_mock_aws_mode;
+use crate::commands::upload_system::{initialize_upload_queue, start_upload_processing, UploadConfig, UploadQueueState, UploadStatus};
+use crate::internal::{AppError, InternalError};
+
+/// ログイン時の自動起動で付与される起動引数（ウィンドウを表示せずトレイのみで開始）
+pub const HIDDEN_FLAG: &str = "--hidden";
+
+/// 起動時の状況（--hidden付きの起動ではresume_after_autostartで監視・アップロードを再開する）
+#[derive(Debug, Clone, Serialize)]
+pub struct LaunchContext {
+    /// --hidden付きで起動された（ログイン時の自動起動）
+    pub launched_hidden: bool,
+}
+
+impl LaunchContext {
+    pub fn from_args<I, S>(args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            launched_hidden: args.into_iter().any(|arg| arg.as_ref() == HIDDEN_FLAG),
+        }
+    }
+}
+
+/// 自動起動時に再開する監視とキューの設定を保存するファイル
+pub(crate) const RESUME_STATE_FILE: &str = "autostart_resume.json";
+
+/// 終了時に動作していた監視とアップロードキューの設定（ログイン時の自動起動で再開する）
+///
+/// キュー設定は認証情報を含まないため、再開時にキャッシュまたはKeychainから取り直す
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct ResumeState {
+    pub upload_config: Option<UploadConfig>,
+    pub auto_archive: Option<(WatchConfig, AutoArchiveUploadSettings)>,
+    pub hot_folder: Option<HotFolderSettings>,
+}
+
+impl ResumeState {
+    pub fn is_empty(&self) -> bool {
+        self.upload_config.is_none() && self.auto_archive.is_none() && self.hot_folder.is_none()
+    }
+}
+
+/// 現在のキュー設定と動作中の監視を取得（終了時に呼び出す）
+pub fn capture_resume_state(app: &AppHandle) -> ResumeState {
+    let upload_config = app.try_state::<UploadQueueState>()
+        .and_then(|queue_state| queue_state.lock().ok().and_then(|queue| queue.config.clone()));
+    ResumeState {
+        upload_config,
+        auto_archive: active_auto_archive_settings(app),
+        hot_folder: active_hot_folder_settings(app),
+    }
+}
+
+/// 再開する状態をファイルに保存（再開するものがなければファイルを削除）
+pub fn persist_resume_state(path: &Path, state: &ResumeState) -> Result<(), InternalError> {
+    if state.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        return Ok(());
+    }
+    std::fs::write(path, serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+/// 保存済みの再開する状態を読み込む（通常起動ではフロントが初期化するため、ファイルは次の終了時まで残す）
+pub fn load_resume_state(path: &Path) -> Option<ResumeState> {
+    let content = std::fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(state) => Some(state),
+        Err(e) => {
+            log::warn!("Ignoring invalid autostart resume state: {}", e);
+            None
+        }
+    }
+}
+
+/// 保存済みのキュー設定でキューを初期化し、前回中断したアップロードがあれば処理を開始
+async fn resume_upload_queue(app: &AppHandle, config: UploadConfig) -> Result<(), AppError> {
+    initialize_upload_queue(app.clone(), config, app.state::<UploadQueueState>()).await?;
+    let pending = app.state::<UploadQueueState>()
+        .lock()
+        .map(|queue| queue.items.iter().any(|item| item.status == UploadStatus::Pending))
+        .unwrap_or(false);
+    if pending {
+        start_upload_processing(app.clone(), app.state::<UploadQueueState>()).await?;
+        log::info!("Upload queue resumed after autostart");
+    }
+    Ok(())
+}
+
+/// ログイン時の自動起動で、前回終了時のアップロードキューと監視を再開する
+///
+/// ウィンドウを表示しないとフロントがキューを初期化しないため、バックエンドから再開する
+pub async fn resume_after_autostart(app: &AppHandle) {
+    let state = app.path().app_data_dir().ok()
+        .and_then(|dir| load_resume_state(&dir.join(RESUME_STATE_FILE)))
+        .unwrap_or_default();
+    if state.is_empty() {
+        // 再開するものがなければ通常の起動と同じく設定に従って読み込む
+        unlock_credentials_on_startup(app);
+        return;
+    }
+
+    if cached_credentials(app).is_err() && !is_mock_aws_mode() {
+        if let Err(e) = unlock_credentials(app.clone(), None).await {
+            log::warn!("Cannot resume uploads and watchers after autostart: {}", e);
+            return;
+        }
+    }
+
+    // 取り込みフォルダはキューの設定を使うため、キューを先に初期化する
+    if let Some(config) = state.upload_config {
+        if let Err(e) = resume_upload_queue(app, config).await {
+            log::warn!("Failed to resume upload queue after autostart: {}", e);
+        }
+    }
+    if let Some((watch_config, upload_settings)) = state.auto_archive {
+        match start_auto_archive(app, watch_config, upload_settings).await {
+            Ok(_) => log::info!("Auto archive resumed after autostart"),
+            Err(e) => log::warn!("Failed to resume auto archive after autostart: {}", e),
+        }
+    }
+    if let Some(settings) = state.hot_folder {
+        match start_hot_folder(app, settings) {
+            Ok(_) => log::info!("Hot folder resumed after autostart"),
+            Err(e) => log::warn!("Failed to resume hot folder after autostart: {}", e),
+        }
+    }
+}
+
+/// 自動起動の状態
+#[derive(Debug, Serialize)]
+pub struct AutostartStatus {
+    /// OS（ログイン項目）に登録されているか
+    pub enabled: bool,
+    /// AppConfigに保存されている設定値
+    pub configured: bool,
+}
+
+fn autostart_error(action: &str, e: impl std::fmt::Display) -> InternalError {
+    InternalError::Config(format!(
+        "ログイン時の自動起動を{}できませんでした。システム設定の「一般 > ログイン項目」でReelVaultが許可されているか確認してください（{}）",
+        action, e
+    ))
+}
+
+/// ログイン時の自動起動を設定
+#[command]
+pub async fn set_autostart(app: AppHandle, enabled: bool) -> Result<AutostartStatus, AppError> {
+    let autolaunch = app.autolaunch();
+    if enabled {
+        autolaunch.enable().map_err(|e| autostart_error("有効に", e))?;
+    } else {
+        autolaunch.disable().map_err(|e| autostart_error("無効に", e))?;
+    }
+
+    let mut config = load_config(&app)?;
+    config.app_settings.launch_at_login = enabled;
+    set_config(app.clone(), config).await?;
+
+    log::info!("Autostart {}", if enabled { "enabled" } else { "disabled" });
+    get_autostart_status(app).await
+}
+
+/// ログイン時の自動起動の状態を取得
+#[command]
+pub async fn get_autostart_status(app: AppHandle) -> Result<AutostartStatus, AppError> {
+    let enabled = app.autolaunch()
+        .is_enabled()
+        .map_err(|e| autostart_error("確認", e))?;
+    let configured = load_config(&app)?.app_settings.launch_at_login;
+
+    Ok(AutostartStatus { enabled, configured })
+}
+
+/// 起動時の状況を取得
+#[command]
+pub async fn get_launch_context(launch_context: State<'_, LaunchContext>) -> Result<LaunchContext, AppError> {
+    Ok(launch_context.inner().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::hot_folder::HotFolderAfterUpload;
+
+    #[test]
+    fn test_launch_context_detects_hidden_flag() {
+        let context = LaunchContext::from_args(["/Applications/ReelVault.app/Contents/MacOS/ReelVault", "--hidden"]);
+        assert!(context.launched_hidden);
+
+        let context = LaunchContext::from_args(["/Applications/ReelVault.app/Contents/MacOS/ReelVault"]);
+        assert!(!context.launched_hidden);
+    }
+
+    #[test]
+    fn test_resume_state_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(RESUME_STATE_FILE);
+        let state = ResumeState {
+            upload_config: Some(UploadConfig {
+                region: "ap-northeast-1".to_string(),
+                ..UploadConfig::new("archive-bucket".to_string())
+            }),
+            auto_archive: None,
+            hot_folder: Some(HotFolderSettings {
+                path: "/Volumes/Card/DCIM".to_string(),
+                s3_prefix: "ingest".to_string(),
+                after_upload: HotFolderAfterUpload::Keep,
+            }),
+        };
+
+        persist_resume_state(&path, &state).unwrap();
+
+        let loaded = load_resume_state(&path).unwrap();
+        assert_eq!(loaded.upload_config.unwrap().bucket_name, "archive-bucket");
+        assert_eq!(loaded.hot_folder, state.hot_folder);
+        assert!(loaded.auto_archive.is_none());
+    }
+
+    #[test]
+    fn test_empty_resume_state_removes_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(RESUME_STATE_FILE);
+        std::fs::write(&path, "{}").unwrap();
+
+        persist_resume_state(&path, &ResumeState::default()).unwrap();
+        assert!(!path.exists());
+        assert!(load_resume_state(&path).is_none());
+    }
+
+    #[test]
+    fn test_autostart_error_message() {
+        let message = autostart_error("有効に", "permission denied").to_string();
+        assert!(message.contains("ログイン項目"));
+        assert!(message.contains("permission denied"));
+    }
+}
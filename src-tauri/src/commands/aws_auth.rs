@@ -1,21 +1,23 @@
 use serde::{Deserialize, Serialize};
-use tauri::command;
+use tauri::{command, AppHandle};
 use aws_config::{BehaviorVersion, Region};
-use crate::commands::aws_operations::{S3ClientTrait, RealS3Client, create_s3_client};
+use crate::commands::aws_operations::{S3ClientTrait, RealS3Client, create_s3_client, create_s3_client_for_credentials};
 use aws_sdk_sts::Client as StsClient;
-use crate::internal::{InternalError, standardize_error};
+use crate::commands::mock_aws::{is_mock_aws_mode, label_message};
+use crate::commands::s3_client_cache::{invalidate_s3_client_cache, spawn_s3_warm_up};
+use crate::internal::{ErrorCode, InternalError, standardize_error};
+use crate::internal::i18n::{tr, LocalizedMessage, MessageKey};
+use crate::internal::sanitize::{register_secret, MASK};
 
-// AWS設定構造体（他のモジュールと共有用）
+// AWS設定構造体（他のモジュールと共有用、認証情報は含めずキャッシュから取得する）
 #[derive(Debug, Deserialize, Clone)]
 pub struct AwsConfig {
-    pub access_key_id: String,
-    pub secret_access_key: String,
     pub region: String,
     pub bucket_name: String,
 }
 
 /// AWS認証情報
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub struct AwsCredentials {
     pub access_key_id: String,
     pub secret_access_key: String,
@@ -23,6 +25,39 @@ pub struct AwsCredentials {
     pub session_token: Option<String>,
 }
 
+impl std::fmt::Debug for AwsCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AwsCredentials")
+            .field("access_key_id", &MASK)
+            .field("secret_access_key", &MASK)
+            .field("region", &self.region)
+            .field("session_token", &self.session_token.as_ref().map(|_| MASK))
+            .finish()
+    }
+}
+
+impl AwsCredentials {
+    /// ログ・エラーメッセージのマスク対象として認証情報を登録
+    pub fn register_for_masking(&self) {
+        register_secret(&self.access_key_id);
+        register_secret(&self.secret_access_key);
+        if let Some(token) = &self.session_token {
+            register_secret(token);
+        }
+    }
+
+    /// 秘匿フィールドのメモリを消去（リージョンは残す）
+    pub fn zeroize_secrets(&mut self) {
+        use zeroize::Zeroize;
+        self.access_key_id.zeroize();
+        self.secret_access_key.zeroize();
+        if let Some(token) = self.session_token.as_mut() {
+            token.zeroize();
+        }
+        self.session_token = None;
+    }
+}
+
 /// AWS認証結果
 #[derive(Debug, Serialize)]
 pub struct AwsAuthResult {
@@ -52,20 +87,31 @@ pub struct PermissionCheck {
 /// 認証情報の基本検証を行う
 pub fn validate_aws_credentials(credentials: &AwsCredentials) -> Result<(), String> {
     if credentials.access_key_id.is_empty() {
-        return Err("Access Key ID is required".to_string());
+        return Err(tr(MessageKey::AuthAccessKeyRequired));
     }
     if credentials.secret_access_key.is_empty() {
-        return Err("Secret Access Key is required".to_string());
+        return Err(tr(MessageKey::AuthSecretKeyRequired));
     }
     if credentials.region.is_empty() {
-        return Err("Region is required".to_string());
+        return Err(tr(MessageKey::AuthRegionRequired));
     }
     Ok(())
 }
 
-/// AWS認証を実行する
+/// AWS認証を実行し、成功時はS3への接続をバックグラウンドでウォームアップする
 #[command]
-pub async fn authenticate_aws(credentials: AwsCredentials) -> Result<AwsAuthResult, String> {
+pub async fn authenticate_aws(app: AppHandle, credentials: AwsCredentials) -> Result<AwsAuthResult, String> {
+    let result = verify_aws_credentials(credentials.clone()).await?;
+    if result.success {
+        spawn_s3_warm_up(&app, credentials);
+    }
+    Ok(result)
+}
+
+/// STSへ問い合わせて認証情報を検証する（CLIからも使用）
+pub async fn verify_aws_credentials(credentials: AwsCredentials) -> Result<AwsAuthResult, String> {
+    credentials.register_for_masking();
+
     // 認証情報の基本検証
     if let Err(e) = validate_aws_credentials(&credentials) {
         return Ok(AwsAuthResult {
@@ -76,6 +122,20 @@ pub async fn authenticate_aws(credentials: AwsCredentials) -> Result<AwsAuthResu
         });
     }
 
+    // モックAWSモードではSTSへ問い合わせずダミーの認証結果を返す
+    if is_mock_aws_mode() {
+        return Ok(AwsAuthResult {
+            success: true,
+            message: label_message(tr(MessageKey::AuthSimulated)),
+            user_identity: Some(AwsUserIdentity {
+                user_id: "MOCKUSERID".to_string(),
+                arn: "arn:aws:iam::000000000000:user/reelvault-mock".to_string(),
+                account: "000000000000".to_string(),
+            }),
+            permissions: vec!["s3:ListBucket (mock)".to_string(), "s3:PutObject (mock)".to_string()],
+        });
+    }
+
     // AWS設定を構築
     let region = Region::new(credentials.region.clone());
     let mut config_builder = aws_config::defaults(BehaviorVersion::latest())
@@ -122,7 +182,7 @@ pub async fn authenticate_aws(credentials: AwsCredentials) -> Result<AwsAuthResu
 
             Ok(AwsAuthResult {
                 success: true,
-                message: "AWS authentication successful".to_string(),
+                message: tr(MessageKey::AuthSucceeded),
                 user_identity: Some(user_identity),
                 permissions,
             })
@@ -131,7 +191,7 @@ pub async fn authenticate_aws(credentials: AwsCredentials) -> Result<AwsAuthResu
             log::error!("AWS authentication failed: {}", e);
             Ok(AwsAuthResult {
                 success: false,
-                message: format!("Authentication failed: {}", e),
+                message: LocalizedMessage::new(MessageKey::AuthFailed).param("error", e).render(),
                 user_identity: None,
                 permissions: vec![],
             })
@@ -146,11 +206,14 @@ pub async fn test_s3_bucket_access(
     bucket_name: String,
 ) -> Result<PermissionCheck, String> {
     // S3ClientTraitを使用
-    let s3_client = match create_s3_client(&credentials).await {
-        Ok(client) => RealS3Client::new(client),
+    let s3_client = match create_s3_client_for_credentials(&credentials).await {
+        Ok(client) => client,
         Err(e) => {
             log::error!("Failed to create S3 client: {}", e);
-            return Err(standardize_error(InternalError::AwsConfig(format!("S3 client creation failed: {}", e))));
+            return Err(standardize_error(InternalError::localized(
+                ErrorCode::AwsConfig,
+                LocalizedMessage::new(MessageKey::S3ClientCreationFailed).param("error", e),
+            )));
         }
     };
 
@@ -161,24 +224,30 @@ pub async fn test_s3_bucket_access(
             
             // 成功時に自動でライフサイクルポリシーを適用し、確実に反映されるまで待機
             log::debug!("Starting auto-setup lifecycle policy for bucket: {}", bucket_name);
-            match auto_setup_lifecycle_policy_with_client(&s3_client, &bucket_name).await {
+            match auto_setup_lifecycle_policy_with_client(s3_client.as_ref(), &bucket_name).await {
                 Ok(_) => {
                     log::info!("ReelVault lifecycle policy applied, now verifying...");
                     
                     // ライフサイクル設定が反映されるまで待機（最大60秒、5秒間隔）
-                    match verify_lifecycle_policy_applied_with_client(&s3_client, &bucket_name, 60, 5).await {
+                    match verify_lifecycle_policy_applied_with_client(s3_client.as_ref(), &bucket_name, 60, 5).await {
                         Ok(_) => {
                             log::info!("ReelVault lifecycle policy verified and active for bucket: {}", bucket_name);
                         }
                         Err(e) => {
                             log::error!("Lifecycle policy verification failed for bucket {}: {}", bucket_name, e);
-                            return Err(standardize_error(InternalError::AwsConfig(format!("ライフサイクル設定の確認に失敗しました: {}", e))));
+                            return Err(standardize_error(InternalError::localized(
+                                ErrorCode::AwsConfig,
+                                LocalizedMessage::new(MessageKey::LifecycleVerifyFailed).param("error", e),
+                            )));
                         }
                     }
                 }
                 Err(e) => {
                     log::error!("Failed to auto-setup lifecycle policy for bucket {}: {}", bucket_name, e);
-                    return Err(standardize_error(InternalError::AwsConfig(format!("ライフサイクル設定に失敗しました: {}", e))));
+                    return Err(standardize_error(InternalError::localized(
+                        ErrorCode::AwsConfig,
+                        LocalizedMessage::new(MessageKey::LifecycleSetupFailed).param("error", e),
+                    )));
                 }
             }
             
@@ -201,18 +270,18 @@ pub async fn test_s3_bucket_access(
     }
 }
 
-/// AwsConfigからaws_config::SdkConfigを作成
-pub async fn create_aws_config(config: &AwsConfig) -> Result<aws_config::SdkConfig, String> {
-    let region = Region::new(config.region.clone());
+/// AwsCredentialsからaws_config::SdkConfigを作成
+pub async fn create_aws_config(credentials: &AwsCredentials) -> Result<aws_config::SdkConfig, String> {
+    let region = Region::new(credentials.region.clone());
     let mut config_builder = aws_config::defaults(BehaviorVersion::latest())
         .region(region);
 
     // 認証情報を設定
     use aws_credential_types::Credentials;
     let creds = Credentials::new(
-        &config.access_key_id,
-        &config.secret_access_key,
-        None,
+        &credentials.access_key_id,
+        &credentials.secret_access_key,
+        credentials.session_token.clone(),
         None,
         "manual",
     );
@@ -302,7 +371,8 @@ pub async fn save_aws_credentials_secure(
                  }
              }
             log::info!("AWS credentials saved with Touch ID/Face ID for profile: {}", profile_name);
-            return Ok("Credentials saved with Touch ID/Face ID".to_string());
+            invalidate_s3_client_cache("credentials saved");
+            return Ok(tr(MessageKey::AuthCredentialsSavedWithBiometry));
         } else {
             log::info!("Touch ID/Face ID not available, using standard keychain");
         }
@@ -310,8 +380,9 @@ pub async fn save_aws_credentials_secure(
 
     // 非macOSまたはTouch ID非対応の場合
     fallback_save_credentials(&service_name, &profile_name, &credentials_json)?;
+    invalidate_s3_client_cache("credentials saved");
     log::info!("AWS credentials saved securely for profile: {}", profile_name);
-    Ok("Credentials saved securely".to_string())
+    Ok(tr(MessageKey::AuthCredentialsSaved))
 }
 
 /// 従来のKeychain保存（フォールバック用）
@@ -362,6 +433,7 @@ pub async fn load_aws_credentials_secure(profile_name: String) -> Result<AwsCred
     // JSONからデシリアライズ
     let credentials: AwsCredentials = serde_json::from_str(&credentials_json)
         .map_err(|e| format!("Failed to deserialize credentials: {}", e))?;
+    credentials.register_for_masking();
 
     log::info!("AWS credentials loaded securely for profile: {}", profile_name);
     Ok(credentials)
@@ -473,7 +545,8 @@ async fn verify_lifecycle_policy_applied_with_client(
 
 #[cfg(target_os = "macos")]
 mod macos_keychain {
-    use crate::internal::{InternalError, standardize_error};
+    use crate::internal::{ErrorCode, InternalError, standardize_error};
+    use crate::internal::i18n::{LocalizedMessage, MessageKey};
 
     /// Touch ID/Face ID必須でKeychainに保存
     pub fn save_password_with_biometry(
@@ -572,7 +645,7 @@ mod macos_keychain {
                 .to_string();
             
             if password.is_empty() {
-                Err(standardize_error(InternalError::Other("保存された認証情報が見つかりません".to_string())))
+                Err(standardize_error(InternalError::localized(ErrorCode::Auth, MessageKey::AuthCredentialsNotFound)))
             } else {
                 log::info!("Touch ID/Face ID認証で情報を読み込みました");
                 Ok(password)
@@ -580,11 +653,14 @@ mod macos_keychain {
         } else {
             let error_msg = String::from_utf8_lossy(&output.stderr);
             if error_msg.contains("could not be found") {
-                Err(standardize_error(InternalError::Other("保存された認証情報が見つかりません".to_string())))
+                Err(standardize_error(InternalError::localized(ErrorCode::Auth, MessageKey::AuthCredentialsNotFound)))
             } else if error_msg.contains("user canceled") {
-                Err(standardize_error(InternalError::Other("Touch ID/Face ID認証がキャンセルされました".to_string())))
+                Err(standardize_error(InternalError::localized(ErrorCode::Auth, MessageKey::AuthBiometryCancelled)))
             } else {
-                Err(standardize_error(InternalError::Other(format!("Touch ID/Face ID認証に失敗しました: {}", error_msg.trim()))))
+                Err(standardize_error(InternalError::localized(
+                    ErrorCode::Auth,
+                    LocalizedMessage::new(MessageKey::AuthBiometryFailed).param("error", error_msg.trim()),
+                )))
             }
         }
     }
@@ -592,21 +668,22 @@ mod macos_keychain {
 
 #[cfg(not(target_os = "macos"))]
 mod macos_keychain {
-    use crate::internal::{InternalError, standardize_error};
+    use crate::internal::{ErrorCode, InternalError, standardize_error};
+    use crate::internal::i18n::MessageKey;
 
     pub fn save_password_with_biometry(
         _service: &str,
         _account: &str,
         _password: &str,
     ) -> Result<(), String> {
-        Err(standardize_error(InternalError::Other("Touch ID/Face ID is only available on macOS".to_string())))
+        Err(standardize_error(InternalError::localized(ErrorCode::Auth, MessageKey::AuthBiometryUnavailable)))
     }
 
     pub fn load_password_with_biometry(
         _service: &str,
         _account: &str,
     ) -> Result<String, String> {
-        Err(standardize_error(InternalError::Other("Touch ID/Face ID is only available on macOS".to_string())))
+        Err(standardize_error(InternalError::localized(ErrorCode::Auth, MessageKey::AuthBiometryUnavailable)))
     }
 
     pub fn is_biometry_available() -> bool {
@@ -651,14 +728,10 @@ mod tests {
     #[test]
     fn test_aws_config_creation() {
         let config = AwsConfig {
-            access_key_id: "test_key".to_string(),
-            secret_access_key: "test_secret".to_string(),
             region: "us-east-1".to_string(),
             bucket_name: "test-bucket".to_string(),
         };
         
-        assert_eq!(config.access_key_id, "test_key");
-        assert_eq!(config.secret_access_key, "test_secret");
         assert_eq!(config.region, "us-east-1");
         assert_eq!(config.bucket_name, "test-bucket");
     }
@@ -736,7 +809,7 @@ mod tests {
         
         let result = validate_aws_credentials(&credentials);
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "Access Key ID is required");
+        assert_eq!(result.unwrap_err(), tr(MessageKey::AuthAccessKeyRequired));
     }
 
     #[test]
@@ -750,7 +823,7 @@ mod tests {
         
         let result = validate_aws_credentials(&credentials);
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "Secret Access Key is required");
+        assert_eq!(result.unwrap_err(), tr(MessageKey::AuthSecretKeyRequired));
     }
 
     #[test]
@@ -764,7 +837,7 @@ mod tests {
         
         let result = validate_aws_credentials(&credentials);
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "Region is required");
+        assert_eq!(result.unwrap_err(), tr(MessageKey::AuthRegionRequired));
     }
 
     #[test]
@@ -779,7 +852,7 @@ mod tests {
         let result = validate_aws_credentials(&credentials);
         assert!(result.is_err());
         // 最初のエラー（Access Key ID）が返される
-        assert_eq!(result.unwrap_err(), "Access Key ID is required");
+        assert_eq!(result.unwrap_err(), tr(MessageKey::AuthAccessKeyRequired));
     }
 
     #[test]
@@ -789,11 +862,11 @@ mod tests {
             use super::macos_keychain;
             let save_result = macos_keychain::save_password_with_biometry("service", "account", "password");
             assert!(save_result.is_err());
-            assert!(save_result.unwrap_err().contains("Touch ID/Face ID is only available on macOS"));
+            assert!(save_result.unwrap_err().contains(&tr(MessageKey::AuthBiometryUnavailable)));
 
             let load_result = macos_keychain::load_password_with_biometry("service", "account");
             assert!(load_result.is_err());
-            assert!(load_result.unwrap_err().contains("Touch ID/Face ID is only available on macOS"));
+            assert!(load_result.unwrap_err().contains(&tr(MessageKey::AuthBiometryUnavailable)));
 
             assert!(!macos_keychain::is_biometry_available());
         }
@@ -807,9 +880,9 @@ mod tests {
             region: "".to_string(),
             session_token: None,
         };
-        let result = authenticate_aws(credentials).await.unwrap();
+        let result = verify_aws_credentials(credentials).await.unwrap();
         assert!(!result.success);
-        assert!(result.message.contains("Access Key ID is required"));
+        assert_eq!(result.message, tr(MessageKey::AuthAccessKeyRequired));
     }
 
     #[tokio::test]
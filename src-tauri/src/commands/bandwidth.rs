@@ -0,0 +1,310 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Local, NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::commands::config::load_config;
+use crate::commands::license::TierPolicy;
+use crate::commands::upload_system::UploadConfig;
+
+/// トレイの「今だけ無制限」で制限を外す時間
+pub const BANDWIDTH_OVERRIDE_DURATION: Duration = Duration::from_secs(60 * 60);
+
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+/// 時間帯別の帯域制限（start〜endはローカル時刻の"HH:MM"、endがstartより前なら日付を跨ぐ）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BandwidthWindow {
+    pub start: String,
+    pub end: String,
+    /// 上限（Mbps = メガビット毎秒、Noneは無制限）
+    pub limit_mbps: Option<f64>,
+}
+
+impl BandwidthWindow {
+    /// 0時起点の分で表した半開区間（日付を跨ぐ区間は2つに分ける）
+    fn segments(&self) -> Result<Vec<(u32, u32)>, String> {
+        let start = parse_minutes(&self.start).ok_or_else(|| format!("invalid start time: {}", self.start))?;
+        let end = parse_minutes(&self.end).ok_or_else(|| format!("invalid end time: {}", self.end))?;
+        if start == end {
+            return Err(format!("start and end are the same ({})", self.start));
+        }
+        if start < end {
+            return Ok(vec![(start, end)]);
+        }
+        let mut segments = vec![(start, MINUTES_PER_DAY)];
+        if end > 0 {
+            segments.push((0, end));
+        }
+        Ok(segments)
+    }
+
+    fn contains(&self, minute: u32) -> bool {
+        self.segments()
+            .map(|segments| segments.iter().any(|(start, end)| (*start..*end).contains(&minute)))
+            .unwrap_or(false)
+    }
+}
+
+fn parse_minutes(value: &str) -> Option<u32> {
+    NaiveTime::parse_from_str(value, "%H:%M").ok().map(|time| time.hour() * 60 + time.minute())
+}
+
+/// スケジュールの検証エラー（時刻の形式・上限値・区間の重複）
+pub fn bandwidth_schedule_errors(schedule: &[BandwidthWindow]) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut parsed = Vec::new();
+    for (index, window) in schedule.iter().enumerate() {
+        if let Some(limit) = window.limit_mbps {
+            if !limit.is_finite() || limit <= 0.0 {
+                errors.push(format!("Bandwidth schedule #{}: limit must be a positive number", index + 1));
+            }
+        }
+        match window.segments() {
+            Ok(segments) => parsed.push((index, segments)),
+            Err(e) => errors.push(format!("Bandwidth schedule #{}: {}", index + 1, e)),
+        }
+    }
+    for (i, (a_index, a)) in parsed.iter().enumerate() {
+        for (b_index, b) in &parsed[i + 1..] {
+            let overlaps = a.iter().any(|(a_start, a_end)| b.iter().any(|(b_start, b_end)| a_start < b_end && b_start < a_end));
+            if overlaps {
+                errors.push(format!("Bandwidth schedule #{} overlaps #{}", a_index + 1, b_index + 1));
+            }
+        }
+    }
+    errors
+}
+
+/// 適用中の制限の決まり方
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BandwidthProfileSource {
+    /// UploadConfigのbandwidth_limit_mbps
+    Default,
+    Schedule,
+    /// トレイの「今だけ無制限」
+    Override,
+}
+
+/// 現在適用中の帯域制限（get_upload_queue_statusで返す）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BandwidthProfile {
+    /// 上限（Mbps、Noneは無制限）
+    pub limit_mbps: Option<f64>,
+    pub source: BandwidthProfileSource,
+    /// スケジュールで決まった場合の区間
+    pub window: Option<BandwidthWindow>,
+    /// 一時的な無制限の終了日時
+    pub override_until: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct LimiterState {
+    schedule: Vec<BandwidthWindow>,
+    override_until: Option<DateTime<Local>>,
+    /// 共有帯域が次に空く時刻
+    next_free: Option<Instant>,
+}
+
+impl LimiterState {
+    fn active_override(&self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        self.override_until.filter(|until| *until > now)
+    }
+
+    fn profile(&self, default_limit_mbps: Option<f64>, limits_allowed: bool, now: DateTime<Local>) -> BandwidthProfile {
+        if let Some(until) = self.active_override(now) {
+            return BandwidthProfile {
+                limit_mbps: None,
+                source: BandwidthProfileSource::Override,
+                window: None,
+                override_until: Some(until.to_rfc3339()),
+            };
+        }
+        // 帯域制限を使えないティアではスケジュールも適用しない
+        let minute = now.hour() * 60 + now.minute();
+        let window = self.schedule.iter().find(|window| window.contains(minute)).filter(|_| limits_allowed);
+        match window {
+            Some(window) => BandwidthProfile {
+                limit_mbps: window.limit_mbps,
+                source: BandwidthProfileSource::Schedule,
+                window: Some(window.clone()),
+                override_until: None,
+            },
+            None => BandwidthProfile {
+                limit_mbps: default_limit_mbps,
+                source: BandwidthProfileSource::Default,
+                window: None,
+                override_until: None,
+            },
+        }
+    }
+}
+
+/// 全アップロードで共有するレートリミッタ（チャンク送信のたびに現在時刻の制限値で待ち時間を決める）
+#[derive(Debug, Default)]
+pub struct BandwidthLimiter {
+    state: Mutex<LimiterState>,
+}
+
+lazy_static::lazy_static! {
+    pub static ref BANDWIDTH_LIMITER: BandwidthLimiter = BandwidthLimiter::default();
+}
+
+impl BandwidthLimiter {
+    fn state(&self) -> std::sync::MutexGuard<'_, LimiterState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set_schedule(&self, schedule: Vec<BandwidthWindow>) {
+        self.state().schedule = schedule;
+    }
+
+    pub fn set_override(&self, until: Option<DateTime<Local>>) {
+        self.state().override_until = until;
+    }
+
+    /// 有効な一時オーバーライドの終了日時
+    pub fn override_until(&self) -> Option<DateTime<Local>> {
+        self.state().active_override(Local::now())
+    }
+
+    pub fn profile(&self, config: &UploadConfig) -> BandwidthProfile {
+        self.state().profile(config.bandwidth_limit_mbps, TierPolicy::for_tier(config.tier).bandwidth_limit, Local::now())
+    }
+
+    /// `bytes`を送信してよくなるまで待つ
+    pub async fn acquire(&self, bytes: u64, config: &UploadConfig) {
+        let wait = self.reserve(
+            bytes,
+            config.bandwidth_limit_mbps,
+            TierPolicy::for_tier(config.tier).bandwidth_limit,
+            Local::now(),
+            Instant::now(),
+        );
+        if !wait.is_zero() {
+            log::debug!("Bandwidth limit: waiting {:?} before sending {} bytes", wait, bytes);
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// 送信枠を予約し、送信を始めるまでの待ち時間を返す
+    fn reserve(
+        &self,
+        bytes: u64,
+        default_limit_mbps: Option<f64>,
+        limits_allowed: bool,
+        now_local: DateTime<Local>,
+        now: Instant,
+    ) -> Duration {
+        let mut state = self.state();
+        let limit = state.profile(default_limit_mbps, limits_allowed, now_local)
+            .limit_mbps
+            .filter(|limit| limit.is_finite() && *limit > 0.0);
+        let Some(limit) = limit else {
+            state.next_free = None;
+            return Duration::ZERO;
+        };
+        let start = state.next_free.filter(|next_free| *next_free > now).unwrap_or(now);
+        state.next_free = Some(start + Duration::from_secs_f64(bytes as f64 * 8.0 / (limit * 1_000_000.0)));
+        start - now
+    }
+}
+
+/// 設定ファイルの帯域制限スケジュールを反映
+pub fn refresh_bandwidth_schedule(app: &AppHandle) {
+    match load_config(app) {
+        Ok(config) => BANDWIDTH_LIMITER.set_schedule(config.bandwidth_schedule),
+        Err(e) => log::warn!("Failed to load bandwidth schedule: {}", e),
+    }
+}
+
+/// トレイから「今だけ無制限（1時間）」を切り替え
+pub fn toggle_bandwidth_override(app: &AppHandle) {
+    if BANDWIDTH_LIMITER.override_until().is_some() {
+        BANDWIDTH_LIMITER.set_override(None);
+        log::info!("Bandwidth override cleared");
+        crate::tray::sync_bandwidth_override(app, None);
+        return;
+    }
+
+    let until = Local::now() + chrono::Duration::seconds(BANDWIDTH_OVERRIDE_DURATION.as_secs() as i64);
+    BANDWIDTH_LIMITER.set_override(Some(until));
+    log::info!("Bandwidth limit lifted until {}", until.to_rfc3339());
+    crate::tray::sync_bandwidth_override(app, Some(until));
+
+    // 期限が来たらトレイの表示を戻す（途中で解除・再設定された場合はそちらに任せる）
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(BANDWIDTH_OVERRIDE_DURATION).await;
+        if BANDWIDTH_LIMITER.override_until().is_none() {
+            crate::tray::sync_bandwidth_override(&app, None);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn window(start: &str, end: &str, limit_mbps: Option<f64>) -> BandwidthWindow {
+        BandwidthWindow { start: start.to_string(), end: end.to_string(), limit_mbps }
+    }
+
+    fn at(hour: u32, minute: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 6, 1, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_schedule_validation_handles_midnight_and_overlap() {
+        assert!(bandwidth_schedule_errors(&[
+            window("09:00", "18:00", Some(10.0)),
+            window("22:00", "06:00", None),
+            window("18:00", "22:00", Some(50.0)),
+        ]).is_empty());
+
+        // 日付を跨ぐ区間の翌日側（05:00〜）も重複として検出する
+        let errors = bandwidth_schedule_errors(&[window("22:00", "06:00", None), window("05:00", "09:00", Some(10.0))]);
+        assert_eq!(errors, vec!["Bandwidth schedule #1 overlaps #2".to_string()]);
+
+        let errors = bandwidth_schedule_errors(&[window("25:00", "06:00", None), window("08:00", "08:00", Some(0.0))]);
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn test_profile_follows_schedule_and_override() {
+        let mut state = LimiterState {
+            schedule: vec![window("09:00", "18:00", Some(10.0)), window("22:00", "06:00", None)],
+            ..LimiterState::default()
+        };
+
+        let daytime = state.profile(Some(100.0), true, at(12, 0));
+        assert_eq!((daytime.limit_mbps, daytime.source), (Some(10.0), BandwidthProfileSource::Schedule));
+        let night = state.profile(Some(100.0), true, at(2, 30));
+        assert_eq!((night.limit_mbps, night.source), (None, BandwidthProfileSource::Schedule));
+        let evening = state.profile(Some(100.0), true, at(19, 0));
+        assert_eq!((evening.limit_mbps, evening.source), (Some(100.0), BandwidthProfileSource::Default));
+        assert_eq!(state.profile(None, false, at(12, 0)).source, BandwidthProfileSource::Default);
+
+        state.override_until = Some(at(13, 0));
+        assert_eq!(state.profile(Some(100.0), true, at(12, 0)).source, BandwidthProfileSource::Override);
+        assert_eq!(state.profile(Some(100.0), true, at(13, 30)).limit_mbps, Some(10.0));
+    }
+
+    #[test]
+    fn test_reserve_paces_chunks_and_switches_limit() {
+        let limiter = BandwidthLimiter::default();
+        limiter.set_schedule(vec![window("09:00", "18:00", Some(8.0))]);
+        let now = Instant::now();
+
+        // 8Mbps = 1MB/s: 1MBのチャンクは1秒ずつずらして送る
+        assert_eq!(limiter.reserve(1_000_000, None, true, at(12, 0), now), Duration::ZERO);
+        assert_eq!(limiter.reserve(1_000_000, None, true, at(12, 0), now), Duration::from_secs(1));
+
+        // 制限のない時間帯に入ると次のチャンクから待たない
+        assert_eq!(limiter.reserve(1_000_000, None, true, at(18, 0), now), Duration::ZERO);
+        assert_eq!(limiter.reserve(1_000_000, None, true, at(12, 0), now), Duration::ZERO);
+    }
+}
@@ -0,0 +1,256 @@
+use std::time::Instant;
+use serde::Serialize;
+use tauri::{command, AppHandle};
+
+use crate::commands::audit::{record_operation, AuditOperation, OperationAuditEntry};
+use crate::commands::aws_operations::{create_real_s3_client, AwsConfig, LifecycleRule, S3ClientTrait};
+use crate::commands::manifest::REMOTE_MANIFEST_PREFIX;
+use crate::commands::lifecycle::{has_enabled_reelvault_rule, reelvault_lifecycle_rule, REELVAULT_UPLOAD_PREFIX};
+use crate::internal::{AppError, ErrorCode, InternalError};
+use crate::internal::i18n::MessageKey;
+
+/// ReelVaultがバケット内で使う標準プレフィックス
+pub const REELVAULT_PREFIXES: &[&str] = &[REELVAULT_UPLOAD_PREFIX, ".reelvault/metadata/", REMOTE_MANIFEST_PREFIX];
+
+/// 初期化の各ステップ（実行順）
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BucketInitStep {
+    /// バケットへのアクセス確認
+    Access,
+    /// 標準プレフィックスの作成
+    Prefixes,
+    /// ReelVault標準ライフサイクルルールの適用
+    Lifecycle,
+    /// バージョニングの確認
+    Versioning,
+    /// 既定暗号化の確認
+    Encryption,
+}
+
+const BUCKET_INIT_STEPS: [BucketInitStep; 5] = [
+    BucketInitStep::Access,
+    BucketInitStep::Prefixes,
+    BucketInitStep::Lifecycle,
+    BucketInitStep::Versioning,
+    BucketInitStep::Encryption,
+];
+
+/// ステップの結果
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BucketInitStatus {
+    /// 今回変更を加えた
+    Applied,
+    /// 設定済みのため何もしなかった
+    AlreadyConfigured,
+    /// 確認のみのステップで推奨設定になっていない
+    Warning,
+    Failed,
+    /// 前のステップが失敗したため未実施
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BucketInitStepResult {
+    pub step: BucketInitStep,
+    pub status: BucketInitStatus,
+    pub detail: Option<String>,
+}
+
+/// 初期化レポート（失敗時はfailed_step以降がSkippedになる）
+#[derive(Debug, Clone, Serialize)]
+pub struct BucketInitReport {
+    pub bucket: String,
+    pub steps: Vec<BucketInitStepResult>,
+    pub completed: bool,
+    pub failed_step: Option<BucketInitStep>,
+}
+
+impl BucketInitReport {
+    /// 今回変更を加えたステップがあるか（初期化済みバケットへの再実行ではfalse）
+    pub fn changed(&self) -> bool {
+        self.steps.iter().any(|step| step.status == BucketInitStatus::Applied)
+    }
+}
+
+/// 既存ルールにReelVault標準ルールを加えたルール一覧（有効なルールがあればNone）
+///
+/// 同じIDの無効化されたルールは置き換え、他のルールはそのまま残す
+pub fn merge_reelvault_rule(existing: Vec<LifecycleRule>) -> Option<Vec<LifecycleRule>> {
+    if has_enabled_reelvault_rule(&existing) {
+        return None;
+    }
+    let standard = reelvault_lifecycle_rule();
+    let mut rules: Vec<LifecycleRule> = existing.into_iter().filter(|rule| rule.id != standard.id).collect();
+    rules.push(standard);
+    Some(rules)
+}
+
+/// 1ステップの結果（Errは失敗として以降を打ち切る）
+type StepOutcome = Result<(BucketInitStatus, Option<String>), String>;
+
+async fn run_step(s3_client: &dyn S3ClientTrait, bucket: &str, step: BucketInitStep) -> StepOutcome {
+    match step {
+        BucketInitStep::Access => {
+            s3_client.head_bucket(bucket).await?;
+            Ok((BucketInitStatus::AlreadyConfigured, None))
+        }
+        BucketInitStep::Prefixes => {
+            let mut created = Vec::new();
+            for prefix in REELVAULT_PREFIXES {
+                if s3_client.head_object_info(bucket, prefix).await?.is_none() {
+                    // フォルダとして表示されるよう空のオブジェクトを置く
+                    s3_client.put_object(bucket, prefix, Vec::new()).await?;
+                    created.push(*prefix);
+                }
+            }
+            if created.is_empty() {
+                Ok((BucketInitStatus::AlreadyConfigured, None))
+            } else {
+                Ok((BucketInitStatus::Applied, Some(format!("created {}", created.join(", ")))))
+            }
+        }
+        BucketInitStep::Lifecycle => {
+            let existing = match s3_client.get_bucket_lifecycle_configuration(bucket).await {
+                Ok(rules) => rules,
+                Err(e) if e.contains("NoSuchLifecycleConfiguration") => Vec::new(),
+                Err(e) => return Err(e),
+            };
+            match merge_reelvault_rule(existing) {
+                Some(rules) => {
+                    s3_client.put_bucket_lifecycle_configuration(bucket, rules).await?;
+                    Ok((BucketInitStatus::Applied, Some(format!("added rule for {}", REELVAULT_UPLOAD_PREFIX))))
+                }
+                None => Ok((BucketInitStatus::AlreadyConfigured, None)),
+            }
+        }
+        BucketInitStep::Versioning => {
+            let status = s3_client.get_bucket_versioning(bucket).await?;
+            if status == "Enabled" {
+                Ok((BucketInitStatus::AlreadyConfigured, Some(status)))
+            } else {
+                // 上書き・削除からの復旧に必要だが、保存容量が増えるため自動では有効化しない
+                Ok((BucketInitStatus::Warning, Some(format!("versioning is {}", status))))
+            }
+        }
+        BucketInitStep::Encryption => match s3_client.get_bucket_encryption(bucket).await? {
+            Some(algorithm) => Ok((BucketInitStatus::AlreadyConfigured, Some(algorithm))),
+            None => Ok((BucketInitStatus::Warning, Some("default encryption is not configured".to_string()))),
+        },
+    }
+}
+
+/// 全ステップを順に実行（失敗したステップ以降はSkipped）
+pub(crate) async fn initialize_bucket(s3_client: &dyn S3ClientTrait, bucket: &str) -> BucketInitReport {
+    let mut report = BucketInitReport {
+        bucket: bucket.to_string(),
+        steps: Vec::with_capacity(BUCKET_INIT_STEPS.len()),
+        completed: false,
+        failed_step: None,
+    };
+    for step in BUCKET_INIT_STEPS {
+        let (status, detail) = if report.failed_step.is_some() {
+            (BucketInitStatus::Skipped, None)
+        } else {
+            match run_step(s3_client, bucket, step).await {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    log::warn!("Bucket initialization step {:?} failed for {}: {}", step, bucket, e);
+                    report.failed_step = Some(step);
+                    (BucketInitStatus::Failed, Some(e))
+                }
+            }
+        };
+        report.steps.push(BucketInitStepResult { step, status, detail });
+    }
+    report.completed = report.failed_step.is_none();
+    report
+}
+
+/// 変更を加えたステップと失敗したステップを監査ログに記録
+fn record_init_audit(app: &AppHandle, report: &BucketInitReport, started_at: Instant) {
+    for step in &report.steps {
+        let result: Result<(), String> = match step.status {
+            BucketInitStatus::Applied => Ok(()),
+            BucketInitStatus::Failed => Err(step.detail.clone().unwrap_or_default()),
+            _ => continue,
+        };
+        let mut entry = OperationAuditEntry::new(AuditOperation::BucketInitialize, &result)
+            .duration(started_at.elapsed());
+        entry.bucket = Some(report.bucket.clone());
+        entry.s3_key = Some(format!("{:?}", step.step).to_lowercase());
+        record_operation(app, entry);
+    }
+}
+
+/// バケットをReelVault用に初期化（標準プレフィックス・ライフサイクル・バージョニング・暗号化）
+///
+/// 初期化済みの項目には何もしないため、繰り返し実行しても差分のみが適用される
+#[command]
+pub async fn initialize_bucket_for_reelvault(app: AppHandle, config: AwsConfig) -> Result<BucketInitReport, AppError> {
+    if config.bucket_name.is_empty() {
+        return Err(InternalError::localized(ErrorCode::Config, MessageKey::BucketNameRequired).into());
+    }
+    let started_at = Instant::now();
+    let s3_client = create_real_s3_client(&app, &config).await?;
+    let report = initialize_bucket(s3_client.as_ref(), &config.bucket_name).await;
+    log::info!(
+        "Bucket initialization for {} finished: completed={}, changed={}",
+        report.bucket, report.completed, report.changed()
+    );
+    record_init_audit(&app, &report, started_at);
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::commands::aws_operations::{LifecycleTransition, MockS3Client};
+
+    #[test]
+    fn test_merge_reelvault_rule_keeps_other_rules() {
+        let other = LifecycleRule {
+            id: "expire-logs".to_string(),
+            status: "Enabled".to_string(),
+            prefix: Some("logs/".to_string()),
+            transitions: vec![LifecycleTransition { days: 30, storage_class: "GLACIER".to_string() }],
+        };
+        let mut disabled = reelvault_lifecycle_rule();
+        disabled.status = "Disabled".to_string();
+
+        let merged = merge_reelvault_rule(vec![other.clone(), disabled]).unwrap();
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].id, "expire-logs");
+        assert!(has_enabled_reelvault_rule(&merged));
+
+        assert!(merge_reelvault_rule(merged).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_initialize_bucket_creates_missing_prefixes() {
+        let client = MockS3Client::with_remote_objects(HashMap::new());
+        let report = initialize_bucket(&client, "footage").await;
+        assert!(report.completed);
+        assert_eq!(report.steps.len(), BUCKET_INIT_STEPS.len());
+        assert_eq!(report.steps[1].status, BucketInitStatus::Applied);
+        let created: Vec<String> = client.calls().put_objects.into_iter().map(|(key, _)| key).collect();
+        assert_eq!(created, REELVAULT_PREFIXES.iter().map(|p| p.to_string()).collect::<Vec<_>>());
+
+        // 初期化済みのバケットには何もしない
+        let initialized = MockS3Client::new();
+        let report = initialize_bucket(&initialized, "footage").await;
+        assert!(report.completed && !report.changed());
+        assert!(initialized.calls().put_objects.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_initialize_bucket_warns_without_versioning() {
+        let client = MockS3Client::with_versioning("Suspended");
+        let report = initialize_bucket(&client, "footage").await;
+        assert!(report.completed);
+        assert_eq!(report.steps[3].step, BucketInitStep::Versioning);
+        assert_eq!(report.steps[3].status, BucketInitStatus::Warning);
+    }
+}
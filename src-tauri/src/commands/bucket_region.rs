@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::commands::aws_auth::AwsCredentials;
+use crate::commands::aws_operations::{create_s3_client_for_credentials, AwsConfig};
+use crate::commands::credential_cache::cached_credentials_in_region;
+use crate::commands::config::load_config;
+use crate::commands::mock_aws::is_mock_aws_mode;
+use crate::internal::i18n::{LocalizedMessage, MessageKey};
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// 転送元リージョン別のリージョン間データ転送料金（USD / GB）
+const INTER_REGION_TRANSFER_PRICES: &[(&str, f64)] = &[
+    ("us-east-1", 0.02),
+    ("us-west-2", 0.02),
+    ("ap-northeast-1", 0.09),
+];
+/// 単価表にないリージョンの転送料金（USD / GB）
+const FALLBACK_TRANSFER_PRICE_PER_GB: f64 = 0.02;
+
+lazy_static::lazy_static! {
+    /// バケット名ごとのリージョン（バケットのリージョンは変わらないため期限なしで保持）
+    static ref BUCKET_REGIONS: Mutex<HashMap<String, String>> = Mutex::new(HashMap::new());
+}
+
+/// クロスリージョン転送の警告
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CrossRegionWarning {
+    pub bucket: String,
+    pub client_region: String,
+    pub bucket_region: String,
+    pub price_per_gb_usd: f64,
+    /// 見積もり対象の転送量（不明な場合はNone）
+    pub transfer_bytes: Option<u64>,
+    /// 転送量から概算した追加コスト（USD）
+    pub estimated_extra_cost_usd: Option<f64>,
+    pub message: String,
+}
+
+/// GetBucketLocationのLocationConstraintをリージョン名にする（空はus-east-1、旧表記のEUはeu-west-1）
+pub fn normalize_location(location: &str) -> String {
+    match location {
+        "" | "US" => "us-east-1".to_string(),
+        "EU" => "eu-west-1".to_string(),
+        region => region.to_string(),
+    }
+}
+
+/// リージョン間データ転送の単価（USD / GB）
+pub fn inter_region_transfer_price_per_gb(source_region: &str) -> f64 {
+    INTER_REGION_TRANSFER_PRICES.iter()
+        .find(|(region, _)| *region == source_region)
+        .map(|(_, price)| *price)
+        .unwrap_or(FALLBACK_TRANSFER_PRICE_PER_GB)
+}
+
+/// クライアントとバケットのリージョンが異なる場合の警告（転送料金はバケット側を転送元として概算）
+pub fn cross_region_warning(
+    bucket: &str,
+    client_region: &str,
+    bucket_region: &str,
+    transfer_bytes: Option<u64>,
+) -> Option<CrossRegionWarning> {
+    if client_region == bucket_region {
+        return None;
+    }
+    let price_per_gb_usd = inter_region_transfer_price_per_gb(bucket_region);
+    let estimated_extra_cost_usd = transfer_bytes.map(|bytes| bytes as f64 / BYTES_PER_GB * price_per_gb_usd);
+    let message = match estimated_extra_cost_usd {
+        Some(cost) => LocalizedMessage::new(MessageKey::CrossRegionTransfer)
+            .param("cost", format!("{:.2}", cost)),
+        None => LocalizedMessage::new(MessageKey::CrossRegionTransferUnsized),
+    }
+        .param("bucket", bucket)
+        .param("bucket_region", bucket_region)
+        .param("client_region", client_region)
+        .param("price", price_per_gb_usd)
+        .render();
+    Some(CrossRegionWarning {
+        bucket: bucket.to_string(),
+        client_region: client_region.to_string(),
+        bucket_region: bucket_region.to_string(),
+        price_per_gb_usd,
+        transfer_bytes,
+        estimated_extra_cost_usd,
+        message,
+    })
+}
+
+/// バケットのリージョンを取得（一度取得したバケットはキャッシュから返す）
+pub async fn bucket_region(credentials: &AwsCredentials, bucket: &str) -> Result<String, String> {
+    if let Some(region) = BUCKET_REGIONS.lock().unwrap_or_else(|e| e.into_inner()).get(bucket) {
+        return Ok(region.clone());
+    }
+    let s3_client = create_s3_client_for_credentials(credentials).await?;
+    let region = normalize_location(&s3_client.get_bucket_location(bucket).await?);
+    BUCKET_REGIONS.lock().unwrap_or_else(|e| e.into_inner()).insert(bucket.to_string(), region.clone());
+    Ok(region)
+}
+
+/// リージョン自動補正・警告抑制の設定
+#[derive(Debug, Clone, Copy, Default)]
+struct RegionSettings {
+    auto_correct: bool,
+    suppress_warning: bool,
+}
+
+fn region_settings(app: &AppHandle) -> RegionSettings {
+    load_config(app)
+        .map(|config| RegionSettings {
+            auto_correct: config.aws_settings.auto_correct_region,
+            suppress_warning: config.aws_settings.suppress_cross_region_warning,
+        })
+        .unwrap_or_default()
+}
+
+/// 問い合わせ対象のバケットのリージョン（モックAWSモード・取得失敗時はNone）
+async fn lookup_bucket_region(credentials: &AwsCredentials, bucket: &str) -> Option<String> {
+    if is_mock_aws_mode() || bucket.is_empty() {
+        return None;
+    }
+    match bucket_region(credentials, bucket).await {
+        Ok(region) => Some(region),
+        Err(e) => {
+            log::warn!("Failed to get region of bucket {} (non-fatal): {}", bucket, e);
+            None
+        }
+    }
+}
+
+/// リージョン自動補正が有効で、認証情報のリージョンがバケットと異なる場合はバケットのリージョンに合わせる
+pub async fn correct_credentials_region(app: &AppHandle, credentials: &mut AwsCredentials, bucket: &str) -> bool {
+    if !region_settings(app).auto_correct {
+        return false;
+    }
+    match lookup_bucket_region(credentials, bucket).await {
+        Some(region) if region != credentials.region => {
+            log::info!("Region auto-correction: {} -> {} for bucket {}", credentials.region, region, bucket);
+            credentials.region = region;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// AwsConfig版のcorrect_credentials_region
+pub async fn correct_config_region(app: &AppHandle, config: &mut AwsConfig) -> bool {
+    let Ok(mut credentials) = cached_credentials_in_region(app, &config.region) else {
+        return false;
+    };
+    let corrected = correct_credentials_region(app, &mut credentials, &config.bucket_name).await;
+    if corrected {
+        config.region = credentials.region;
+    }
+    corrected
+}
+
+/// クロスリージョン転送が発生する場合の警告（抑制設定時やリージョン自動補正で合わせられる場合はNone）
+pub async fn check_cross_region_transfer(
+    app: &AppHandle,
+    credentials: &AwsCredentials,
+    bucket: &str,
+    transfer_bytes: Option<u64>,
+) -> Option<CrossRegionWarning> {
+    let settings = region_settings(app);
+    if settings.suppress_warning || settings.auto_correct {
+        return None;
+    }
+    let bucket_region = lookup_bucket_region(credentials, bucket).await?;
+    let warning = cross_region_warning(bucket, &credentials.region, &bucket_region, transfer_bytes);
+    if let Some(warning) = &warning {
+        log::warn!("Cross-region transfer for bucket {}: {} -> {}", bucket, warning.client_region, warning.bucket_region);
+    }
+    warning
+}
+
+/// AwsConfig版のcheck_cross_region_transfer
+pub async fn check_cross_region_transfer_for_config(
+    app: &AppHandle,
+    config: &AwsConfig,
+    transfer_bytes: Option<u64>,
+) -> Option<CrossRegionWarning> {
+    let credentials = cached_credentials_in_region(app, &config.region).ok()?;
+    check_cross_region_transfer(app, &credentials, &config.bucket_name, transfer_bytes).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_location() {
+        assert_eq!(normalize_location(""), "us-east-1");
+        assert_eq!(normalize_location("EU"), "eu-west-1");
+        assert_eq!(normalize_location("ap-northeast-1"), "ap-northeast-1");
+    }
+
+    #[test]
+    fn test_cross_region_warning_estimates_extra_cost() {
+        assert!(cross_region_warning("archive", "ap-northeast-1", "ap-northeast-1", Some(1)).is_none());
+
+        let warning = cross_region_warning("archive", "us-west-2", "ap-northeast-1", Some(10 * 1024 * 1024 * 1024)).unwrap();
+        assert_eq!(warning.price_per_gb_usd, 0.09);
+        assert!((warning.estimated_extra_cost_usd.unwrap() - 0.9).abs() < 1e-9);
+        assert!(warning.message.contains("ap-northeast-1"));
+
+        let no_size = cross_region_warning("archive", "ap-northeast-1", "eu-central-1", None).unwrap();
+        assert_eq!(no_size.price_per_gb_usd, FALLBACK_TRANSFER_PRICE_PER_GB);
+        assert_eq!(no_size.estimated_extra_cost_usd, None);
+    }
+}
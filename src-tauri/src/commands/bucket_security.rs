@@ -0,0 +1,225 @@
+use serde::Serialize;
+use tauri::{command, AppHandle};
+
+use crate::commands::aws_operations::{create_real_s3_client, AwsConfig, PublicAccessBlock, S3ClientTrait, VERSIONING_DISABLED};
+use crate::internal::i18n::{LocalizedMessage, MessageKey};
+use crate::internal::{AppError, ErrorCode, InternalError};
+
+/// 安全性チェックの項目
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BucketSecurityItem {
+    /// パブリックアクセスブロック（GetPublicAccessBlock）
+    PublicAccessBlock,
+    /// バケットポリシーによる公開（GetBucketPolicyStatus）
+    BucketPolicy,
+    /// デフォルト暗号化（GetBucketEncryption）
+    Encryption,
+    /// バージョニング（GetBucketVersioning）
+    Versioning,
+}
+
+impl BucketSecurityItem {
+    /// スコアへの配点（合計100）
+    fn weight(&self) -> u32 {
+        match self {
+            Self::PublicAccessBlock => 30,
+            Self::BucketPolicy => 40,
+            Self::Encryption => 20,
+            Self::Versioning => 10,
+        }
+    }
+
+    /// 確認に必要なIAMアクション
+    fn required_permission(&self) -> &'static str {
+        match self {
+            Self::PublicAccessBlock => "s3:GetBucketPublicAccessBlock",
+            Self::BucketPolicy => "s3:GetBucketPolicyStatus",
+            Self::Encryption => "s3:GetEncryptionConfiguration",
+            Self::Versioning => "s3:GetBucketVersioning",
+        }
+    }
+}
+
+/// 項目別の判定
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BucketSecurityStatus {
+    Pass,
+    /// 推奨設定ではないが公開には直結しない
+    Warning,
+    Fail,
+    /// 権限不足などで取得できなかった
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BucketSecurityCheck {
+    pub item: BucketSecurityItem,
+    pub status: BucketSecurityStatus,
+    pub detail: String,
+}
+
+/// バケットの安全性チェックの結果
+#[derive(Debug, Clone, Serialize)]
+pub struct BucketSecurityReport {
+    pub bucket: String,
+    pub checks: Vec<BucketSecurityCheck>,
+    /// 取得できた項目のみで算出した0〜100のスコア（すべてunknownの場合はNone）
+    pub score: Option<u32>,
+    pub recommendations: Vec<String>,
+}
+
+impl BucketSecurityReport {
+    /// 公開状態と判定された項目があるか
+    pub fn has_failures(&self) -> bool {
+        self.checks.iter().any(|check| check.status == BucketSecurityStatus::Fail)
+    }
+}
+
+/// S3から取得した各設定（取得失敗はErr）
+pub struct BucketSecurityFindings {
+    pub public_access_block: Result<Option<PublicAccessBlock>, String>,
+    pub policy_is_public: Result<Option<bool>, String>,
+    pub encryption: Result<Option<String>, String>,
+    pub versioning: Result<String, String>,
+}
+
+/// 4項目の設定を取得（1項目の失敗で他の項目を止めない）
+pub async fn collect_bucket_security(s3_client: &dyn S3ClientTrait, bucket: &str) -> BucketSecurityFindings {
+    BucketSecurityFindings {
+        public_access_block: s3_client.get_public_access_block(bucket).await,
+        policy_is_public: s3_client.get_bucket_policy_is_public(bucket).await,
+        encryption: s3_client.get_bucket_encryption(bucket).await,
+        versioning: s3_client.get_bucket_versioning(bucket).await,
+    }
+}
+
+fn judge<T>(
+    result: &Result<T, String>,
+    judge_value: impl FnOnce(&T) -> (BucketSecurityStatus, String),
+) -> (BucketSecurityStatus, String) {
+    match result {
+        Ok(value) => judge_value(value),
+        Err(e) => (BucketSecurityStatus::Unknown, e.clone()),
+    }
+}
+
+/// 取得結果からスコアと推奨事項を判定
+pub fn evaluate_bucket_security(bucket: &str, findings: &BucketSecurityFindings) -> BucketSecurityReport {
+    let checks = vec![
+        (BucketSecurityItem::PublicAccessBlock, judge(&findings.public_access_block, |block| match block {
+            Some(block) if block.enabled_count() == 4 => (BucketSecurityStatus::Pass, "all four settings are enabled".to_string()),
+            Some(block) => (BucketSecurityStatus::Warning, format!("{} of 4 settings are enabled", block.enabled_count())),
+            None => (BucketSecurityStatus::Fail, "public access block is not configured".to_string()),
+        })),
+        (BucketSecurityItem::BucketPolicy, judge(&findings.policy_is_public, |is_public| match is_public {
+            Some(true) => (BucketSecurityStatus::Fail, "bucket policy grants public access".to_string()),
+            Some(false) => (BucketSecurityStatus::Pass, "bucket policy is not public".to_string()),
+            None => (BucketSecurityStatus::Pass, "no bucket policy".to_string()),
+        })),
+        (BucketSecurityItem::Encryption, judge(&findings.encryption, |algorithm| match algorithm {
+            Some(algorithm) => (BucketSecurityStatus::Pass, algorithm.clone()),
+            None => (BucketSecurityStatus::Fail, "default encryption is not configured".to_string()),
+        })),
+        (BucketSecurityItem::Versioning, judge(&findings.versioning, |status| match status.as_str() {
+            "Enabled" => (BucketSecurityStatus::Pass, status.clone()),
+            VERSIONING_DISABLED => (BucketSecurityStatus::Warning, "versioning has never been enabled".to_string()),
+            _ => (BucketSecurityStatus::Warning, status.clone()),
+        })),
+    ];
+
+    let mut recommendations = Vec::new();
+    let (mut earned, mut possible) = (0u32, 0u32);
+    for (item, (status, _)) in &checks {
+        let weight = item.weight();
+        match status {
+            BucketSecurityStatus::Pass => earned += weight,
+            BucketSecurityStatus::Warning => earned += weight / 2,
+            BucketSecurityStatus::Fail | BucketSecurityStatus::Unknown => {}
+        }
+        if *status != BucketSecurityStatus::Unknown {
+            possible += weight;
+        }
+        let key = match (item, status) {
+            (_, BucketSecurityStatus::Pass) => continue,
+            (_, BucketSecurityStatus::Unknown) => MessageKey::BucketSecurityUnknown,
+            (BucketSecurityItem::PublicAccessBlock, _) => MessageKey::BucketSecurityEnablePublicAccessBlock,
+            (BucketSecurityItem::BucketPolicy, _) => MessageKey::BucketSecurityPolicyPublic,
+            (BucketSecurityItem::Encryption, _) => MessageKey::BucketSecurityEnableEncryption,
+            (BucketSecurityItem::Versioning, _) => MessageKey::BucketSecurityEnableVersioning,
+        };
+        recommendations.push(
+            LocalizedMessage::new(key)
+                .param("bucket", bucket)
+                .param("permission", item.required_permission())
+                .render(),
+        );
+    }
+
+    BucketSecurityReport {
+        bucket: bucket.to_string(),
+        checks: checks.into_iter()
+            .map(|(item, (status, detail))| BucketSecurityCheck { item, status, detail })
+            .collect(),
+        score: if possible > 0 { Some(earned * 100 / possible) } else { None },
+        recommendations,
+    }
+}
+
+/// 取得と判定をまとめて実行
+pub async fn inspect_bucket_security(s3_client: &dyn S3ClientTrait, bucket: &str) -> BucketSecurityReport {
+    let findings = collect_bucket_security(s3_client, bucket).await;
+    let report = evaluate_bucket_security(bucket, &findings);
+    log::info!(
+        "Bucket security check for {}: score={:?}, recommendations={}",
+        bucket, report.score, report.recommendations.len()
+    );
+    report
+}
+
+/// アーカイブ先バケットのパブリックアクセス・暗号化・バージョニング設定を確認
+#[command]
+pub async fn check_bucket_security(app: AppHandle, config: AwsConfig) -> Result<BucketSecurityReport, AppError> {
+    if config.bucket_name.is_empty() {
+        return Err(InternalError::localized(ErrorCode::Config, MessageKey::BucketNameRequired).into());
+    }
+    let s3_client = create_real_s3_client(&app, &config).await?;
+    Ok(inspect_bucket_security(s3_client.as_ref(), &config.bucket_name).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::aws_operations::MockS3Client;
+
+    #[tokio::test]
+    async fn test_secure_bucket_scores_full() {
+        let report = inspect_bucket_security(&MockS3Client::new(), "footage").await;
+        assert_eq!(report.score, Some(100));
+        assert!(report.recommendations.is_empty());
+        assert!(!report.has_failures());
+    }
+
+    #[test]
+    fn test_public_bucket_fails_and_unknown_is_excluded_from_score() {
+        let findings = BucketSecurityFindings {
+            public_access_block: Ok(None),
+            policy_is_public: Ok(Some(true)),
+            encryption: Err("AccessDenied".to_string()),
+            versioning: Ok(VERSIONING_DISABLED.to_string()),
+        };
+        let report = evaluate_bucket_security("footage", &findings);
+        let statuses: Vec<BucketSecurityStatus> = report.checks.iter().map(|check| check.status).collect();
+        assert_eq!(statuses, vec![
+            BucketSecurityStatus::Fail,
+            BucketSecurityStatus::Fail,
+            BucketSecurityStatus::Unknown,
+            BucketSecurityStatus::Warning,
+        ]);
+        // 暗号化（20点）を除いた80点満点中、バージョニングの半分（5点）のみ
+        assert_eq!(report.score, Some(5 * 100 / 80));
+        assert_eq!(report.recommendations.len(), 4);
+        assert!(report.has_failures());
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Manager};
+use uuid::Uuid;
+
+use crate::commands::metadata::{calculate_file_hash, MetadataDatabase};
+use crate::internal::{standardize_error, InternalError};
+
+/// バンドル対象とするファイルサイズの既定の上限
+pub const DEFAULT_SMALL_FILE_THRESHOLD_BYTES: u64 = 1024 * 1024;
+/// バンドルのアーカイブ拡張子
+pub const BUNDLE_EXTENSION: &str = ".tar";
+/// バンドルに並置する目録のキーの接尾辞
+pub const MANIFEST_SUFFIX: &str = ".manifest.json";
+/// 目録のスキーマバージョン
+pub const MANIFEST_VERSION: u32 = 1;
+/// 1ファイルだけならバンドルしても効果がないため、この件数未満は個別アップロード
+const MIN_BUNDLE_FILES: usize = 2;
+
+fn default_small_file_threshold() -> u64 {
+    DEFAULT_SMALL_FILE_THRESHOLD_BYTES
+}
+
+/// 小ファイルのtarバンドル設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleOptions {
+    /// このサイズ以下のファイルをバンドルにまとめる
+    #[serde(default = "default_small_file_threshold")]
+    pub small_file_threshold_bytes: u64,
+    /// 目録を保存するメタデータDBのパス
+    pub db_path: String,
+    /// バンドル名（未指定時は日時から生成）
+    pub bundle_name: Option<String>,
+}
+
+/// バンドル内の1ファイルの情報
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundleEntry {
+    /// tarアーカイブ内のパス
+    pub path: String,
+    /// バンドル元のローカルパス
+    pub source_path: String,
+    pub file_size: u64,
+    pub file_hash: String,
+}
+
+/// バンドルの目録（S3には`<バンドルキー>.manifest.json`として並置）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub version: u32,
+    pub bundle_key: String,
+    pub created_at: String,
+    pub total_size: u64,
+    pub entries: Vec<BundleEntry>,
+}
+
+/// バンドル検索結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleLookupResult {
+    pub bundle_key: String,
+    pub entry_path: String,
+    pub source_path: String,
+    pub file_size: u64,
+    pub file_hash: String,
+    pub created_at: String,
+}
+
+/// ステージング済みのtarアーカイブ
+#[derive(Debug)]
+pub struct BundleArchive {
+    pub archive_path: PathBuf,
+    /// S3キー生成に使うパス（バンドル元の共通ディレクトリ直下に置いた場合のパス）
+    pub virtual_path: PathBuf,
+    pub entries: Vec<BundleEntry>,
+}
+
+/// バンドルの準備結果
+#[derive(Debug)]
+pub struct StagedBundle {
+    /// 閾値を超えるため個別にアップロードするファイル
+    pub individual_files: Vec<String>,
+    pub archive: Option<BundleArchive>,
+}
+
+/// キューに投入済みでアップロードを待っているバンドル
+#[derive(Debug)]
+struct QueuedBundle {
+    manifest_path: PathBuf,
+    manifest: BundleManifest,
+    db_path: String,
+    /// まだアップロードが終わっていないステージング上のファイル（アーカイブ・目録）
+    remaining_files: HashSet<PathBuf>,
+}
+
+lazy_static::lazy_static! {
+    /// アーカイブのパスをキーにした、アップロード待ちのバンドル
+    static ref QUEUED_BUNDLES: Mutex<HashMap<PathBuf, QueuedBundle>> = Mutex::new(HashMap::new());
+}
+
+/// ステージングしたアーカイブ・目録を置くディレクトリ
+pub fn bundle_staging_dir(app: &AppHandle) -> Result<PathBuf, InternalError> {
+    let cache_dir = app.path().app_cache_dir()
+        .map_err(|e| InternalError::Config(format!("Failed to get app cache directory: {}", e)))?;
+    Ok(cache_dir.join("bundles"))
+}
+
+/// アーカイブ・目録のどちらかのパスから、そのバンドルのアーカイブのパスを求める
+fn queued_bundle_key(bundles: &HashMap<PathBuf, QueuedBundle>, file_path: &Path) -> Option<PathBuf> {
+    bundles.iter()
+        .find(|(archive_path, bundle)| archive_path.as_path() == file_path || bundle.manifest_path == file_path)
+        .map(|(archive_path, _)| archive_path.clone())
+}
+
+fn remove_staged_file(path: &Path) {
+    if let Err(e) = std::fs::remove_file(path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            log::warn!("Failed to remove staged bundle file {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// アップロード待ちのバンドルのアーカイブ・目録か
+pub fn is_queued_bundle_file(file_path: &str) -> bool {
+    let bundles = QUEUED_BUNDLES.lock().unwrap_or_else(|e| e.into_inner());
+    queued_bundle_key(&bundles, Path::new(file_path)).is_some()
+}
+
+/// バンドルのファイルのアップロード完了を反映する
+///
+/// アーカイブがS3に揃った時点で目録をメタデータDBへ保存し、アップロードしたファイルはステージングから削除する
+pub fn complete_queued_bundle_file(file_path: &str) {
+    let path = Path::new(file_path);
+    let mut bundles = QUEUED_BUNDLES.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(archive_path) = queued_bundle_key(&bundles, path) else {
+        return;
+    };
+    let Some(bundle) = bundles.get_mut(&archive_path) else {
+        return;
+    };
+
+    if path == archive_path {
+        match save_bundle_manifest(&bundle.manifest, &bundle.db_path) {
+            Ok(()) => log::info!("Recorded bundle {} with {} file(s)", bundle.manifest.bundle_key, bundle.manifest.entries.len()),
+            Err(e) => log::error!("Failed to record bundle {}: {}", bundle.manifest.bundle_key, e),
+        }
+    }
+    bundle.remaining_files.remove(path);
+    remove_staged_file(path);
+    if bundle.remaining_files.is_empty() {
+        bundles.remove(&archive_path);
+    }
+}
+
+/// アップロードの失敗・キャンセル時に、そのバンドルの未アップロードのステージングファイルを削除する
+///
+/// アーカイブが揃わなければ目録も使えないため、どちらのファイルからでもバンドル全体を破棄する
+pub fn discard_queued_bundle(file_path: &str) {
+    let mut bundles = QUEUED_BUNDLES.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(archive_path) = queued_bundle_key(&bundles, Path::new(file_path)) else {
+        return;
+    };
+    if let Some(bundle) = bundles.remove(&archive_path) {
+        for path in &bundle.remaining_files {
+            remove_staged_file(path);
+        }
+        log::info!("Discarded staged bundle {}", bundle.manifest.bundle_key);
+    }
+}
+
+fn save_bundle_manifest(manifest: &BundleManifest, db_path: &str) -> Result<(), InternalError> {
+    let db = MetadataDatabase::new(db_path)
+        .map_err(|e| InternalError::Database(format!("Failed to create database connection: {}", e)))?;
+    db.save_bundle_manifest(manifest)
+        .map_err(|e| InternalError::Database(format!("Failed to save bundle manifest: {}", e)))
+}
+
+/// 指定パス（フォルダは再帰的に展開）をバンドル対象と個別アップロード対象に振り分け
+fn partition_files(file_paths: &[String], threshold: u64) -> Result<(Vec<PathBuf>, Vec<String>), InternalError> {
+    let mut small = Vec::new();
+    let mut individual = Vec::new();
+    let mut pending: Vec<PathBuf> = file_paths.iter().map(PathBuf::from).collect();
+
+    while let Some(path) = pending.pop() {
+        let metadata = std::fs::metadata(&path)
+            .map_err(|e| InternalError::File(format!("File not found: {} ({})", path.display(), e)))?;
+        if metadata.is_dir() {
+            for entry in std::fs::read_dir(&path)? {
+                pending.push(entry?.path());
+            }
+        } else if metadata.len() <= threshold {
+            small.push(path);
+        } else {
+            individual.push(path.to_string_lossy().to_string());
+        }
+    }
+
+    small.sort();
+    individual.sort();
+    Ok((small, individual))
+}
+
+/// ファイル群の親ディレクトリに共通する最も深いディレクトリ
+fn common_root(paths: &[PathBuf]) -> PathBuf {
+    let mut parents = paths.iter().filter_map(|p| p.parent());
+    let Some(first) = parents.next() else {
+        return PathBuf::new();
+    };
+    let mut root = first.to_path_buf();
+    for parent in parents {
+        while !parent.starts_with(&root) {
+            if !root.pop() {
+                break;
+            }
+        }
+    }
+    root
+}
+
+/// ファイル群をtarアーカイブにまとめ、目録のエントリを返す
+fn write_tar_archive(files: &[PathBuf], root: &Path, archive_path: &Path) -> Result<Vec<BundleEntry>, InternalError> {
+    let mut builder = tar::Builder::new(BufWriter::new(File::create(archive_path)?));
+    let mut entries = Vec::with_capacity(files.len());
+
+    for file in files {
+        let entry_path = file.strip_prefix(root).unwrap_or(file);
+        builder.append_path_with_name(file, entry_path)
+            .map_err(|e| InternalError::File(format!("Failed to add {} to bundle: {}", file.display(), e)))?;
+        entries.push(BundleEntry {
+            path: entry_path.to_string_lossy().replace('\\', "/"),
+            source_path: file.to_string_lossy().to_string(),
+            file_size: std::fs::metadata(file)?.len(),
+            file_hash: calculate_file_hash(file)?,
+        });
+    }
+
+    builder.into_inner()
+        .map_err(|e| InternalError::File(format!("Failed to finish bundle {}: {}", archive_path.display(), e)))?;
+    Ok(entries)
+}
+
+/// 小ファイルをtarアーカイブへまとめてステージング
+pub fn stage_bundle(file_paths: &[String], options: &BundleOptions, staging_dir: &Path) -> Result<StagedBundle, InternalError> {
+    let (small, mut individual) = partition_files(file_paths, options.small_file_threshold_bytes)?;
+    if small.len() < MIN_BUNDLE_FILES {
+        individual.extend(small.iter().map(|p| p.to_string_lossy().to_string()));
+        return Ok(StagedBundle { individual_files: individual, archive: None });
+    }
+
+    std::fs::create_dir_all(staging_dir)?;
+    let bundle_name = options.bundle_name.clone()
+        .unwrap_or_else(|| format!("bundle-{}", chrono::Utc::now().format("%Y%m%d-%H%M%S")));
+    let file_name = format!("{}{}", bundle_name.trim_end_matches(BUNDLE_EXTENSION), BUNDLE_EXTENSION);
+    // 同名バンドルの同時作成で衝突しないよう、ステージング上のファイル名は一意にする
+    let archive_path = staging_dir.join(format!("{}-{}", Uuid::new_v4().simple(), file_name));
+
+    let root = common_root(&small);
+    let entries = match write_tar_archive(&small, &root, &archive_path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            let _ = std::fs::remove_file(&archive_path);
+            return Err(e);
+        }
+    };
+    log::info!("Bundled {} small file(s) into {}", entries.len(), archive_path.display());
+
+    Ok(StagedBundle {
+        individual_files: individual,
+        archive: Some(BundleArchive {
+            archive_path,
+            virtual_path: root.join(file_name),
+            entries,
+        }),
+    })
+}
+
+/// ブロッキングスレッドでバンドルをステージング
+pub async fn stage_bundle_async(file_paths: Vec<String>, options: BundleOptions, staging_dir: PathBuf) -> Result<StagedBundle, InternalError> {
+    tokio::task::spawn_blocking(move || stage_bundle(&file_paths, &options, &staging_dir))
+        .await
+        .map_err(|e| InternalError::Other(format!("Bundle task failed: {}", e)))?
+}
+
+impl BundleArchive {
+    /// アップロード先のキーを確定して目録を作成
+    pub fn manifest(&self, bundle_key: &str) -> BundleManifest {
+        BundleManifest {
+            version: MANIFEST_VERSION,
+            bundle_key: bundle_key.to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            total_size: self.entries.iter().map(|e| e.file_size).sum(),
+            entries: self.entries.clone(),
+        }
+    }
+
+    /// 並置アップロード用の目録JSONを書き出してそのパスを返す
+    ///
+    /// メタデータDBへの保存はアーカイブのアップロード完了時に行う（complete_queued_bundle_file）
+    pub fn finalize(&self, bundle_key: &str, db_path: &str) -> Result<PathBuf, InternalError> {
+        let manifest = self.manifest(bundle_key);
+        let manifest_path = PathBuf::from(format!("{}{}", self.archive_path.display(), MANIFEST_SUFFIX));
+        std::fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+
+        QUEUED_BUNDLES.lock().unwrap_or_else(|e| e.into_inner()).insert(
+            self.archive_path.clone(),
+            QueuedBundle {
+                manifest_path: manifest_path.clone(),
+                manifest,
+                db_path: db_path.to_string(),
+                remaining_files: HashSet::from([self.archive_path.clone(), manifest_path.clone()]),
+            },
+        );
+        Ok(manifest_path)
+    }
+}
+
+/// ファイルパス・ファイル名から、そのファイルを含むバンドルを検索
+#[command]
+pub async fn find_file_in_bundles(
+    query: String,
+    db_path: String,
+) -> Result<Vec<BundleLookupResult>, String> {
+    let db = MetadataDatabase::new(&db_path)
+        .map_err(|e| standardize_error(InternalError::Database(format!("Failed to create database connection: {}", e))))?;
+
+    db.find_bundle_entries(&query)
+        .map_err(|e| standardize_error(InternalError::Database(format!("Failed to search bundles: {}", e))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(path: &Path, size: usize) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![b'x'; size]).unwrap();
+    }
+
+    #[test]
+    fn test_common_root() {
+        let paths = vec![
+            PathBuf::from("/shoot/day1/a.xmp"),
+            PathBuf::from("/shoot/day1/sub/b.srt"),
+            PathBuf::from("/shoot/day2/c.xmp"),
+        ];
+        assert_eq!(common_root(&paths), PathBuf::from("/shoot"));
+    }
+
+    #[test]
+    fn test_stage_bundle_and_lookup() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("shoot");
+        write_file(&source.join("a.xmp"), 10);
+        write_file(&source.join("sub/b.srt"), 20);
+        write_file(&source.join("clip.mov"), 2048);
+
+        let options = BundleOptions {
+            small_file_threshold_bytes: 1024,
+            db_path: dir.path().join("metadata.db").to_string_lossy().to_string(),
+            bundle_name: Some("sidecars".to_string()),
+        };
+        let staging = dir.path().join("staging");
+        let staged = stage_bundle(&[source.to_string_lossy().to_string()], &options, &staging).unwrap();
+
+        assert_eq!(staged.individual_files, vec![source.join("clip.mov").to_string_lossy().to_string()]);
+        let archive = staged.archive.unwrap();
+        assert_eq!(archive.virtual_path, source.join("sidecars.tar"));
+        let paths: Vec<&str> = archive.entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["a.xmp", "sub/b.srt"]);
+
+        let mut tar = tar::Archive::new(File::open(&archive.archive_path).unwrap());
+        assert_eq!(tar.entries().unwrap().count(), 2);
+
+        let manifest_path = archive.finalize("uploads/sidecars.tar", &options.db_path).unwrap();
+        let manifest: BundleManifest = serde_json::from_str(&std::fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        assert_eq!(manifest.total_size, 30);
+
+        // アーカイブのアップロードが終わるまでは検索対象にしない
+        let db = MetadataDatabase::new(&options.db_path).unwrap();
+        assert!(db.find_bundle_entries("b.srt").unwrap().is_empty());
+        let archive_path = archive.archive_path.to_string_lossy().to_string();
+        assert!(is_queued_bundle_file(&archive_path));
+        complete_queued_bundle_file(&archive_path);
+        assert!(!archive.archive_path.exists());
+
+        let found = db.find_bundle_entries("b.srt").unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].bundle_key, "uploads/sidecars.tar");
+        assert_eq!(found[0].entry_path, "sub/b.srt");
+
+        let manifest_path = manifest_path.to_string_lossy().to_string();
+        complete_queued_bundle_file(&manifest_path);
+        assert!(!is_queued_bundle_file(&manifest_path));
+    }
+
+    #[test]
+    fn test_failed_bundle_upload_discards_staged_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("shoot");
+        write_file(&source.join("a.xmp"), 10);
+        write_file(&source.join("b.xmp"), 20);
+        let options = BundleOptions {
+            small_file_threshold_bytes: 1024,
+            db_path: dir.path().join("metadata.db").to_string_lossy().to_string(),
+            bundle_name: Some("failed".to_string()),
+        };
+
+        let staged = stage_bundle(&[source.to_string_lossy().to_string()], &options, &dir.path().join("staging")).unwrap();
+        let archive = staged.archive.unwrap();
+        let manifest_path = archive.finalize("uploads/failed.tar", &options.db_path).unwrap();
+
+        discard_queued_bundle(&archive.archive_path.to_string_lossy());
+        assert!(!archive.archive_path.exists());
+        assert!(!manifest_path.exists());
+        assert!(!is_queued_bundle_file(&manifest_path.to_string_lossy()));
+
+        let db = MetadataDatabase::new(&options.db_path).unwrap();
+        assert!(db.find_bundle_entries("a.xmp").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_single_small_file_is_not_bundled() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("only.xmp");
+        write_file(&file, 10);
+        let options = BundleOptions {
+            small_file_threshold_bytes: 1024,
+            db_path: String::new(),
+            bundle_name: None,
+        };
+
+        let staged = stage_bundle(&[file.to_string_lossy().to_string()], &options, &dir.path().join("staging")).unwrap();
+        assert!(staged.archive.is_none());
+        assert_eq!(staged.individual_files.len(), 1);
+    }
+}
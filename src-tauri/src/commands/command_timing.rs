@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use tauri::ipc::{Invoke, InvokeBody};
+use tauri::{command, AppHandle, Emitter, Runtime};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Registry;
+
+use crate::commands::metrics::{MetricsRegistry, METRICS};
+use crate::internal::AppError;
+
+/// 全コマンドの所要時間を送るイベント（デバッグモード時のみ）
+pub const COMMAND_TIMING_EVENT: &str = "command-timing";
+/// スローコマンドとみなす既定の所要時間
+pub const DEFAULT_SLOW_COMMAND_THRESHOLD_MS: u64 = 1_000;
+/// 引数サマリに含める文字列の最大長
+const SUMMARY_VALUE_MAX_CHARS: usize = 60;
+/// 値を伏せる引数名（小文字で部分一致）
+const REDACTED_ARGUMENTS: &[&str] = &["secret", "password", "token", "credential", "license"];
+/// Tauriのtracing機能が作るspanのターゲット
+pub const IPC_SPAN_TARGET: &str = "tauri::ipc::protocol";
+/// invoke_handlerの呼び出し中に入っているspan（1回のIPCリクエスト）
+const IPC_REQUEST_SPAN: &str = "ipc::request";
+/// コマンドの応答を返すときに作られるspan（親は`ipc::request::handle`、その親が`ipc::request`）
+const IPC_RESPOND_SPAN: &str = "ipc::request::respond";
+
+static SLOW_COMMAND_THRESHOLD_MS: AtomicU64 = AtomicU64::new(DEFAULT_SLOW_COMMAND_THRESHOLD_MS);
+static EMIT_TIMING_EVENTS: AtomicBool = AtomicBool::new(false);
+static TIMING_APP: OnceLock<AppHandle> = OnceLock::new();
+
+static COMMAND_STATS: OnceLock<Mutex<HashMap<String, CommandStats>>> = OnceLock::new();
+
+/// 受け付けてまだ応答していない呼び出し（`ipc::request`のspanに保持する）
+struct RunningCommand {
+    command: String,
+    started: Instant,
+    summary: String,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct CommandStats {
+    calls: u64,
+    slow_calls: u64,
+    total: Duration,
+    max: Duration,
+}
+
+/// コマンドごとの実行時間の集計
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CommandPerformance {
+    pub command: String,
+    pub calls: u64,
+    pub slow_calls: u64,
+    pub mean_ms: f64,
+    pub max_ms: f64,
+}
+
+/// command-timingイベントのペイロード
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandTiming {
+    pub command: String,
+    pub duration_ms: f64,
+    pub arguments: String,
+    pub slow: bool,
+}
+
+fn command_stats() -> &'static Mutex<HashMap<String, CommandStats>> {
+    COMMAND_STATS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 閾値とcommand-timingイベントの送信有無を設定
+pub fn set_command_timing_settings(slow_threshold_ms: u64, emit_events: bool) {
+    SLOW_COMMAND_THRESHOLD_MS.store(slow_threshold_ms.max(1), Ordering::Relaxed);
+    EMIT_TIMING_EVENTS.store(emit_events, Ordering::Relaxed);
+}
+
+/// command-timingイベントの送信に使うアプリを登録
+pub fn init_command_timing(app: &AppHandle) {
+    let _ = TIMING_APP.set(app.clone());
+}
+
+fn summarize_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) if text.chars().count() > SUMMARY_VALUE_MAX_CHARS => {
+            format!("\"{}…\"", text.chars().take(SUMMARY_VALUE_MAX_CHARS).collect::<String>())
+        }
+        serde_json::Value::Array(items) => format!("[{} items]", items.len()),
+        serde_json::Value::Object(fields) => format!("{{{} fields}}", fields.len()),
+        other => other.to_string(),
+    }
+}
+
+/// 引数のサマリ（配列・オブジェクトは件数のみ、認証情報らしき引数は伏せる）
+pub fn summarize_arguments(body: &InvokeBody) -> String {
+    match body {
+        InvokeBody::Json(serde_json::Value::Object(arguments)) => arguments.iter()
+            .map(|(name, value)| {
+                let lower = name.to_ascii_lowercase();
+                if REDACTED_ARGUMENTS.iter().any(|redacted| lower.contains(redacted)) {
+                    format!("{}=***", name)
+                } else {
+                    format!("{}={}", name, summarize_value(value))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        InvokeBody::Json(value) => summarize_value(value),
+        InvokeBody::Raw(bytes) => format!("<{} bytes>", bytes.len()),
+    }
+}
+
+/// invoke_handlerを包み、受け付けた時刻と引数サマリを現在の`ipc::request`のspanに記録する
+///
+/// 非同期コマンドはハンドラーから戻った後に実行されるため、完了はCommandTimingLayerが応答のspanから記録する
+pub fn timed_invoke_handler<R, F>(handler: F) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static
+where
+    R: Runtime,
+    F: Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+{
+    move |invoke| {
+        let running = RunningCommand {
+            command: invoke.message.command().to_string(),
+            started: Instant::now(),
+            summary: summarize_arguments(invoke.message.payload()),
+        };
+        attach_to_current_request(running);
+        handler(invoke)
+    }
+}
+
+fn attach_to_current_request(running: RunningCommand) {
+    let current = tracing::Span::current();
+    let is_request = current.metadata().is_some_and(|metadata| metadata.name() == IPC_REQUEST_SPAN);
+    if !is_request {
+        return;
+    }
+    current.with_subscriber(|(id, dispatch)| {
+        if let Some(span) = dispatch.downcast_ref::<Registry>().and_then(|registry| registry.span(id)) {
+            span.extensions_mut().replace(running);
+        }
+    });
+}
+
+/// `ipc::request::respond`のspanが作られた時点でコマンドの完了を記録するレイヤー
+pub struct CommandTimingLayer;
+
+impl<S> Layer<S> for CommandTimingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if attrs.metadata().name() != IPC_RESPOND_SPAN {
+            return;
+        }
+        let Some(request) = ctx.span(id).and_then(|respond| respond.parent()).and_then(|handle| handle.parent()) else {
+            return;
+        };
+        let running = request.extensions_mut().remove::<RunningCommand>();
+        if let Some(running) = running {
+            record_command(&running.command, running.started.elapsed(), &running.summary);
+        }
+    }
+}
+
+/// 所要時間を集計し、閾値を超えたらwarnログとメトリクスに記録
+fn record_command(command: &str, elapsed: Duration, arguments: &str) -> bool {
+    let slow = elapsed >= Duration::from_millis(SLOW_COMMAND_THRESHOLD_MS.load(Ordering::Relaxed));
+    {
+        let mut stats = command_stats().lock().unwrap_or_else(|e| e.into_inner());
+        let entry = stats.entry(command.to_string()).or_default();
+        entry.calls += 1;
+        entry.total += elapsed;
+        entry.max = entry.max.max(elapsed);
+        if slow {
+            entry.slow_calls += 1;
+        }
+    }
+    METRICS.command_duration.record(elapsed);
+    if slow {
+        MetricsRegistry::increment(&METRICS.slow_commands);
+        log::warn!("Slow command {} took {}ms ({})", command, elapsed.as_millis(), arguments);
+    }
+    if EMIT_TIMING_EVENTS.load(Ordering::Relaxed) {
+        if let Some(app) = TIMING_APP.get() {
+            let timing = CommandTiming {
+                command: command.to_string(),
+                duration_ms: elapsed.as_secs_f64() * 1_000.0,
+                arguments: arguments.to_string(),
+                slow,
+            };
+            if let Err(e) = app.emit(COMMAND_TIMING_EVENT, &timing) {
+                log::debug!("Failed to emit {}: {}", COMMAND_TIMING_EVENT, e);
+            }
+        }
+    }
+    slow
+}
+
+/// 集計のスナップショット（最大時間の長い順）
+fn performance_snapshot() -> Vec<CommandPerformance> {
+    let stats = command_stats().lock().unwrap_or_else(|e| e.into_inner());
+    let mut performance: Vec<CommandPerformance> = stats.iter()
+        .map(|(command, stats)| CommandPerformance {
+            command: command.to_string(),
+            calls: stats.calls,
+            slow_calls: stats.slow_calls,
+            mean_ms: stats.total.as_secs_f64() * 1_000.0 / stats.calls.max(1) as f64,
+            max_ms: stats.max.as_secs_f64() * 1_000.0,
+        })
+        .collect();
+    performance.sort_by(|a, b| b.max_ms.total_cmp(&a.max_ms).then_with(|| a.command.cmp(&b.command)));
+    performance
+}
+
+/// コマンドごとの呼び出し回数・平均/最大時間の一覧を取得
+#[command]
+pub async fn get_command_performance_stats() -> Result<Vec<CommandPerformance>, AppError> {
+    Ok(performance_snapshot())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::prelude::*;
+
+    #[test]
+    fn test_arguments_are_summarized_without_secrets() {
+        let body = InvokeBody::Json(serde_json::json!({
+            "s3Key": "footage/a.mov",
+            "filePaths": ["/a.mov", "/b.mov"],
+            "config": { "bucket_name": "footage", "secret_access_key": "x" },
+            "secretAccessKey": "abc",
+            "days": 3,
+        }));
+        let summary = summarize_arguments(&body);
+        assert!(summary.contains("s3Key=\"footage/a.mov\""));
+        assert!(summary.contains("filePaths=[2 items]"));
+        assert!(summary.contains("config={2 fields}"));
+        assert!(summary.contains("secretAccessKey=***"));
+        assert!(summary.contains("days=3"));
+        assert_eq!(summarize_arguments(&InvokeBody::Raw(vec![0; 16])), "<16 bytes>");
+    }
+
+    #[test]
+    fn test_respond_span_records_invoked_commands() {
+        let subscriber = tracing_subscriber::registry().with(CommandTimingLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            let request = tracing::trace_span!(target: IPC_SPAN_TARGET, "ipc::request").entered();
+            attach_to_current_request(RunningCommand {
+                command: "test_timed_command".to_string(),
+                started: Instant::now() - Duration::from_secs(2),
+                summary: "days=3".to_string(),
+            });
+            let handle = tracing::trace_span!(target: IPC_SPAN_TARGET, "ipc::request::handle", cmd = "test_timed_command");
+            drop(request);
+            drop(tracing::trace_span!(target: IPC_SPAN_TARGET, parent: &handle, "ipc::request::respond"));
+            // 記録済みの呼び出しは二重に集計しない
+            drop(tracing::trace_span!(target: IPC_SPAN_TARGET, parent: &handle, "ipc::request::respond"));
+
+            // ipc::request以外のspanの中から呼ばれた場合は記録しない
+            let _other = tracing::info_span!("startup").entered();
+            attach_to_current_request(RunningCommand {
+                command: "test_untimed_command".to_string(),
+                started: Instant::now(),
+                summary: String::new(),
+            });
+        });
+
+        let snapshot = performance_snapshot();
+        let stats = snapshot.iter().find(|p| p.command == "test_timed_command").unwrap();
+        assert_eq!((stats.calls, stats.slow_calls), (1, 1));
+        assert!(stats.max_ms >= 2_000.0);
+        assert!(!snapshot.iter().any(|p| p.command == "test_untimed_command"));
+        assert!(!record_command("test_fast_command", Duration::from_millis(1), ""));
+    }
+}
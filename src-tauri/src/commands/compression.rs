@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use crate::commands::metadata::detect_mime_type;
+use crate::internal::InternalError;
+
+/// 圧縮対象とする最小ファイルサイズ（小さいファイルは効果が薄いため対象外）
+pub const COMPRESSION_MIN_SIZE_BYTES: u64 = 1024 * 1024;
+/// 圧縮したオブジェクトのキーに付与する拡張子
+pub const ZSTD_EXTENSION: &str = ".zst";
+/// 一時ディレクトリに作る圧縮ファイルの接頭辞
+pub const COMPRESSED_TEMP_PREFIX: &str = "reelvault-";
+
+/// オブジェクトメタデータのキー（x-amz-meta-*）
+pub const META_ORIGINAL_NAME: &str = "reelvault-original-name";
+pub const META_ORIGINAL_SIZE: &str = "reelvault-original-size";
+pub const META_COMPRESSION: &str = "reelvault-compression";
+/// 圧縮方式
+pub const COMPRESSION_ZSTD: &str = "zstd";
+
+/// 既に圧縮済みで再圧縮の効果がない拡張子
+const PRECOMPRESSED_EXTENSIONS: &[&str] = &[
+    "zst", "zip", "gz", "tgz", "bz2", "xz", "7z", "rar",
+    "jpg", "jpeg", "png", "heic", "webp", "mp3", "aac", "m4a", "pdf",
+];
+
+pub fn default_compression_level() -> i32 {
+    3
+}
+
+/// 圧縮対象か判定（動画以外かつ一定サイズ以上、既に圧縮済みの形式は除く）
+pub fn should_compress(path: &Path, file_size: u64) -> bool {
+    if file_size < COMPRESSION_MIN_SIZE_BYTES {
+        return false;
+    }
+    if detect_mime_type(&path.to_path_buf()).starts_with("video/") {
+        return false;
+    }
+    let extension = path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    !PRECOMPRESSED_EXTENSIONS.contains(&extension.as_str())
+}
+
+/// 一時ディレクトリに作成した圧縮ファイル（破棄時に削除）
+#[derive(Debug)]
+pub struct CompressedFile {
+    path: PathBuf,
+    pub original_size: u64,
+    pub compressed_size: u64,
+}
+
+impl CompressedFile {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 圧縮による削減量
+    pub fn saved_bytes(&self) -> u64 {
+        self.original_size.saturating_sub(self.compressed_size)
+    }
+}
+
+impl Drop for CompressedFile {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            log::warn!("Failed to remove temporary compressed file {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// ファイルを一時ディレクトリへzstd圧縮
+pub fn compress_file(source: &Path, level: i32) -> Result<CompressedFile, InternalError> {
+    let original_size = std::fs::metadata(source)?.len();
+    let path = std::env::temp_dir().join(format!("{}{}{}", COMPRESSED_TEMP_PREFIX, Uuid::new_v4().simple(), ZSTD_EXTENSION));
+
+    let reader = BufReader::new(File::open(source)?);
+    let writer = BufWriter::new(File::create(&path)?);
+    // 以降はエラー時もDropで一時ファイルを削除させる
+    let mut compressed = CompressedFile { path, original_size, compressed_size: 0 };
+    zstd::stream::copy_encode(reader, writer, level)
+        .map_err(|e| InternalError::File(format!("Failed to compress {}: {}", source.display(), e)))?;
+    compressed.compressed_size = std::fs::metadata(&compressed.path)?.len();
+
+    log::info!(
+        "Compressed {} with zstd (level {}): {} -> {} bytes",
+        source.display(), level, original_size, compressed.compressed_size
+    );
+    Ok(compressed)
+}
+
+/// 圧縮処理をブロッキングスレッドで実行
+pub async fn compress_file_async(source: PathBuf, level: i32) -> Result<CompressedFile, InternalError> {
+    tokio::task::spawn_blocking(move || compress_file(&source, level))
+        .await
+        .map_err(|e| InternalError::Other(format!("Compression task failed: {}", e)))?
+}
+
+/// 圧縮したオブジェクトに記録するメタデータ
+pub fn compression_metadata(original_name: &str, original_size: u64) -> HashMap<String, String> {
+    HashMap::from([
+        (META_ORIGINAL_NAME.to_string(), original_name.to_string()),
+        (META_ORIGINAL_SIZE.to_string(), original_size.to_string()),
+        (META_COMPRESSION.to_string(), COMPRESSION_ZSTD.to_string()),
+    ])
+}
+
+/// メタデータでzstd圧縮が示されている場合、ダウンロードしたファイルを解凍
+/// 解凍した場合は元のファイル名で同じディレクトリに書き出し、圧縮ファイルを削除してそのパスを返す
+pub fn decompress_downloaded_file(
+    local_path: &Path,
+    metadata: &HashMap<String, String>,
+) -> Result<Option<PathBuf>, InternalError> {
+    if metadata.get(META_COMPRESSION).map(String::as_str) != Some(COMPRESSION_ZSTD) {
+        return Ok(None);
+    }
+
+    let file_name = metadata.get(META_ORIGINAL_NAME)
+        .and_then(|name| Path::new(name).file_name())
+        .map(|name| name.to_os_string())
+        .or_else(|| {
+            local_path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.trim_end_matches(ZSTD_EXTENSION).into())
+        })
+        .ok_or_else(|| InternalError::File(format!("Invalid download path: {}", local_path.display())))?;
+    let mut output_path = local_path.with_file_name(file_name);
+    if output_path == local_path {
+        output_path = local_path.with_extension("decompressed");
+    }
+
+    let reader = BufReader::new(File::open(local_path)?);
+    let writer = BufWriter::new(File::create(&output_path)?);
+    zstd::stream::copy_decode(reader, writer)
+        .map_err(|e| InternalError::File(format!("Failed to decompress {}: {}", local_path.display(), e)))?;
+    std::fs::remove_file(local_path)?;
+
+    log::info!("Decompressed {} -> {}", local_path.display(), output_path.display());
+    Ok(Some(output_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_should_compress() {
+        let large = COMPRESSION_MIN_SIZE_BYTES * 2;
+        assert!(should_compress(Path::new("/tmp/project.prproj"), large));
+        assert!(should_compress(Path::new("/tmp/subtitles.srt"), large));
+        assert!(!should_compress(Path::new("/tmp/clip.mov"), large));
+        assert!(!should_compress(Path::new("/tmp/archive.zip"), large));
+        assert!(!should_compress(Path::new("/tmp/small.log"), 10));
+    }
+
+    #[test]
+    fn test_compress_and_decompress_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("render.log");
+        let content = "frame rendered\n".repeat(10_000);
+        File::create(&source).unwrap().write_all(content.as_bytes()).unwrap();
+
+        let compressed = compress_file(&source, default_compression_level()).unwrap();
+        assert!(compressed.compressed_size < compressed.original_size);
+        assert!(compressed.saved_bytes() > 0);
+
+        let downloaded = dir.path().join("render.log.zst");
+        std::fs::copy(compressed.path(), &downloaded).unwrap();
+        let metadata = compression_metadata("render.log", compressed.original_size);
+        std::fs::remove_file(&source).unwrap();
+
+        let restored = decompress_downloaded_file(&downloaded, &metadata).unwrap().unwrap();
+        assert_eq!(restored, source);
+        assert_eq!(std::fs::read_to_string(&restored).unwrap(), content);
+        assert!(!downloaded.exists());
+
+        let temp_path = compressed.path().to_path_buf();
+        drop(compressed);
+        assert!(!temp_path.exists());
+    }
+
+    #[test]
+    fn test_decompress_skips_uncompressed_objects() {
+        let result = decompress_downloaded_file(Path::new("/tmp/none.bin"), &HashMap::new()).unwrap();
+        assert!(result.is_none());
+    }
+}
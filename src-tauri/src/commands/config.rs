@@ -1,9 +1,17 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
-use tauri::{AppHandle, Manager};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use crate::commands::app_data_usage::AppDataCleanupSettings;
+use crate::commands::bandwidth::{bandwidth_schedule_errors, BandwidthWindow};
+use crate::commands::mock_aws::DEFAULT_MOCK_SPEED_MBPS;
+use crate::commands::quick_upload::{parse_shortcut, DEFAULT_QUICK_UPLOAD_SHORTCUT};
+use crate::commands::transfer_cap::TransferCapAction;
+use crate::tray::{TrayRecentUploadLabel, DEFAULT_TRAY_RECENT_UPLOADS, MAX_TRAY_RECENT_UPLOADS};
 use crate::internal::{InternalError, standardize_error};
+use crate::internal::i18n::{set_language, Language};
 
 // 設定データ構造
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +20,39 @@ pub struct AppConfig {
     pub app_settings: AppSettings,
     pub user_preferences: UserPreferences,
     pub aws_settings: AwsSettings,
+    #[serde(default)]
+    pub notification_settings: NotificationSettings,
+    /// アップロード検証済みのローカル原本を削除するまでの日数（未設定は削除しない）
+    #[serde(default)]
+    pub local_retention_days: Option<u32>,
+    #[serde(default)]
+    pub retention_settings: RetentionSettings,
+    /// AWSへ一切アクセスせずダミー応答を返すデモ・オフライン用モード
+    #[serde(default)]
+    pub mock_aws_mode: bool,
+    /// モックAWSモードでシミュレートする転送速度（MB/s）
+    #[serde(default = "default_mock_aws_speed_mbps")]
+    pub mock_aws_speed_mbps: f64,
+    #[serde(default)]
+    pub quick_upload: QuickUploadSettings,
+    /// 時間帯別の帯域制限（どの区間にも入らない時間はUploadConfigのbandwidth_limit_mbps）
+    #[serde(default)]
+    pub bandwidth_schedule: Vec<BandwidthWindow>,
+    /// 復元ダウンロードした作業コピーの保存先（未設定はダウンロードフォルダ内の"ReelVault Restored"）
+    #[serde(default)]
+    pub restored_files_dir: Option<String>,
+    /// 復元済みの作業コピーをゴミ箱へ移すまでの日数
+    #[serde(default = "default_restored_retention_days")]
+    pub restored_retention_days: u32,
+    /// アップロード・ダウンロードを合わせた月次の転送量の上限（GB、未設定は無制限）
+    #[serde(default)]
+    pub monthly_transfer_cap_gb: Option<f64>,
+    /// 月次上限に達したときの挙動
+    #[serde(default)]
+    pub transfer_cap_action: TransferCapAction,
+    /// ログ・サムネイル・一時ファイルなどの定期自動クリーンアップ
+    #[serde(default)]
+    pub app_data_cleanup: AppDataCleanupSettings,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,12 +60,51 @@ pub struct AppSettings {
     pub log_level: String,
     pub theme: String,
     pub language: String,
+    /// ログファイルの保持日数（0は無制限）
+    #[serde(default = "default_log_retention_days")]
+    pub log_retention_days: u32,
+    /// ログイン時に自動起動する（トレイ常駐状態で開始）
+    #[serde(default)]
+    pub launch_at_login: bool,
+    /// アップロード・ダウンロード中はシステムのアイドルスリープを抑止する
+    #[serde(default = "default_prevent_sleep_during_transfer")]
+    pub prevent_sleep_during_transfer: bool,
+    /// 監視フォルダで検知したファイルのメタデータ作成（ハッシュ計算）の同時実行数
+    #[serde(default = "default_metadata_job_concurrency")]
+    pub metadata_job_concurrency: usize,
+    /// 完了・失敗から何日経過したアイテムをキューからアップロード履歴へ移すか
+    #[serde(default = "default_upload_history_retention_days")]
+    pub upload_history_retention_days: u32,
+    /// 1回のアーカイブで履歴へ移す最大件数
+    #[serde(default = "default_upload_history_archive_limit")]
+    pub upload_history_archive_limit: usize,
+    /// この時間（ミリ秒）を超えたコマンドをスローコマンドとして警告する
+    #[serde(default = "default_slow_command_threshold_ms")]
+    pub slow_command_threshold_ms: u64,
+    /// トレイメニューの「最近のアップロード」に表示する件数（0は表示しない）
+    #[serde(default = "default_tray_recent_uploads_count")]
+    pub tray_recent_uploads_count: usize,
+    /// 「最近のアップロード」の項目にS3キーも表示するか
+    #[serde(default)]
+    pub tray_recent_uploads_label: TrayRecentUploadLabel,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserPreferences {
     pub default_bucket_name: Option<String>,
     pub default_storage_class: String,
+    /// ダウンロードの既定の保存先フォルダ（未設定はOSのダウンロードフォルダ）
+    #[serde(default)]
+    pub default_download_dir: Option<String>,
+    /// 期限切れになった復元ジョブの記録を残す日数
+    #[serde(default = "default_expired_restore_retention_days")]
+    pub expired_restore_retention_days: u32,
+    /// 復元リクエストで指定する復元済みコピーの保持日数の既定値
+    #[serde(default = "default_restore_days")]
+    pub default_restore_days: u32,
+    /// 復元通知を残す日数
+    #[serde(default = "default_restore_notification_retention_days")]
+    pub restore_notification_retention_days: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +113,96 @@ pub struct AwsSettings {
     pub timeout_seconds: u64,
     pub max_retries: u32,
     pub profile_name: Option<String>,
+    /// Keychainから読み込んだ認証情報をメモリに保持する時間
+    #[serde(default = "default_credential_cache_hours")]
+    pub credential_cache_hours: u32,
+    /// 起動時にKeychainから認証情報を読み込んでキャッシュしておく
+    #[serde(default)]
+    pub unlock_credentials_on_startup: bool,
+    /// バケットのリージョンと異なる場合にS3クライアントをバケットのリージョンで作り直す
+    #[serde(default)]
+    pub auto_correct_region: bool,
+    /// クロスリージョン転送の警告を表示しない
+    #[serde(default)]
+    pub suppress_cross_region_warning: bool,
+    /// 認証成功時に既定のバケットへhead_bucketを1回送り、S3への接続を確立しておく
+    #[serde(default = "default_warm_up_s3_connection")]
+    pub warm_up_s3_connection: bool,
+}
+
+/// OSネイティブ通知の設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub enabled: bool,
+    /// キュー内の全ファイル完了時に通知
+    pub notify_queue_completed: bool,
+    /// 閾値以上の大きなファイルの完了時に通知
+    pub notify_large_file_completed: bool,
+    pub large_file_threshold_mb: u64,
+    /// 復元完了時に通知
+    pub notify_restore_completed: bool,
+    /// ダウンロード完了時に通知
+    pub notify_download_completed: bool,
+    /// エラー発生時に通知
+    pub notify_errors: bool,
+    /// アプリがフォアグラウンドのときは通知しない
+    pub suppress_when_focused: bool,
+    /// Dock・トレイに未完了件数のバッジを表示
+    pub show_badge: bool,
+    /// 日次ヘルスレポートで問題が見つかったときに通知
+    pub notify_health_issues: bool,
+    /// 日次ヘルスレポートを生成する時刻（ローカル時刻のHH:MM）
+    pub health_report_time: String,
+}
+
+/// ローカル原本のリテンション実行方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RetentionMode {
+    /// 対象一覧を通知し、確認後に実行
+    Confirm,
+    /// 確認なしで実行
+    Auto,
+}
+
+/// リテンション対象ファイルの処理方法
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionAction {
+    /// ゴミ箱へ移動
+    Trash,
+    /// アーカイブフォルダへ移動
+    MoveToArchive,
+}
+
+/// ローカル原本のリテンション設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionSettings {
+    pub mode: RetentionMode,
+    pub action: RetentionAction,
+    /// MoveToArchive時の移動先
+    pub archive_dir: Option<String>,
+}
+
+/// クイックアップロードで追加するファイルの取得元
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum QuickUploadSource {
+    /// ファイル選択ダイアログを開く
+    Dialog,
+    /// クリップボードのファイルパスをそのまま使う
+    Clipboard,
+}
+
+/// グローバルショートカットによるクイックアップロードの設定
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct QuickUploadSettings {
+    /// ショートカット（例: "CmdOrCtrl+Shift+U"、Noneは登録しない）
+    pub shortcut: Option<String>,
+    pub source: QuickUploadSource,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -42,6 +212,90 @@ pub struct ConfigValidationResult {
     pub warnings: Vec<String>,
 }
 
+/// ログ保持日数のデフォルト値
+pub const DEFAULT_LOG_RETENTION_DAYS: u32 = 14;
+
+fn default_log_retention_days() -> u32 {
+    DEFAULT_LOG_RETENTION_DAYS
+}
+
+/// 期限切れの復元ジョブを残す日数のデフォルト値
+pub const DEFAULT_EXPIRED_RESTORE_RETENTION_DAYS: u32 = 7;
+
+fn default_expired_restore_retention_days() -> u32 {
+    DEFAULT_EXPIRED_RESTORE_RETENTION_DAYS
+}
+
+/// 復元通知を残す日数のデフォルト値
+pub const DEFAULT_RESTORE_NOTIFICATION_RETENTION_DAYS: u32 = 90;
+
+fn default_restore_notification_retention_days() -> u32 {
+    DEFAULT_RESTORE_NOTIFICATION_RETENTION_DAYS
+}
+
+/// 復元済みコピーの保持日数のデフォルト値
+pub const DEFAULT_RESTORE_DAYS: u32 = 7;
+/// 復元済みコピーの保持日数の上限（STANDARD相当の保管料金が日数分かかるため1年までに抑える）
+pub const MAX_RESTORE_DAYS: u32 = 365;
+
+fn default_restore_days() -> u32 {
+    DEFAULT_RESTORE_DAYS
+}
+
+fn default_mock_aws_speed_mbps() -> f64 {
+    DEFAULT_MOCK_SPEED_MBPS
+}
+
+fn default_prevent_sleep_during_transfer() -> bool {
+    true
+}
+
+fn default_warm_up_s3_connection() -> bool {
+    true
+}
+
+/// メタデータ作成ジョブの同時実行数のデフォルト値（アップロードの読み込みと競合しないよう低く抑える）
+pub const DEFAULT_METADATA_JOB_CONCURRENCY: usize = 1;
+
+fn default_metadata_job_concurrency() -> usize {
+    DEFAULT_METADATA_JOB_CONCURRENCY
+}
+
+/// 完了アイテムをキューに残す日数のデフォルト値
+pub const DEFAULT_UPLOAD_HISTORY_RETENTION_DAYS: u32 = 30;
+/// 1回のアーカイブ件数の上限のデフォルト値
+pub const DEFAULT_UPLOAD_HISTORY_ARCHIVE_LIMIT: usize = 1000;
+
+/// 復元済みの作業コピーの保持日数のデフォルト値
+pub const DEFAULT_RESTORED_RETENTION_DAYS: u32 = 14;
+
+fn default_restored_retention_days() -> u32 {
+    DEFAULT_RESTORED_RETENTION_DAYS
+}
+
+fn default_upload_history_retention_days() -> u32 {
+    DEFAULT_UPLOAD_HISTORY_RETENTION_DAYS
+}
+
+fn default_upload_history_archive_limit() -> usize {
+    DEFAULT_UPLOAD_HISTORY_ARCHIVE_LIMIT
+}
+
+fn default_slow_command_threshold_ms() -> u64 {
+    crate::commands::command_timing::DEFAULT_SLOW_COMMAND_THRESHOLD_MS
+}
+
+fn default_tray_recent_uploads_count() -> usize {
+    DEFAULT_TRAY_RECENT_UPLOADS
+}
+
+/// 認証情報キャッシュの有効時間のデフォルト値
+pub const DEFAULT_CREDENTIAL_CACHE_HOURS: u32 = 8;
+
+fn default_credential_cache_hours() -> u32 {
+    DEFAULT_CREDENTIAL_CACHE_HOURS
+}
+
 // デフォルト設定実装
 impl Default for AppConfig {
     fn default() -> Self {
@@ -50,6 +304,18 @@ impl Default for AppConfig {
             app_settings: AppSettings::default(),
             user_preferences: UserPreferences::default(),
             aws_settings: AwsSettings::default(),
+            notification_settings: NotificationSettings::default(),
+            local_retention_days: None,
+            retention_settings: RetentionSettings::default(),
+            mock_aws_mode: false,
+            mock_aws_speed_mbps: DEFAULT_MOCK_SPEED_MBPS,
+            quick_upload: QuickUploadSettings::default(),
+            bandwidth_schedule: Vec::new(),
+            restored_files_dir: None,
+            restored_retention_days: DEFAULT_RESTORED_RETENTION_DAYS,
+            monthly_transfer_cap_gb: None,
+            transfer_cap_action: TransferCapAction::default(),
+            app_data_cleanup: AppDataCleanupSettings::default(),
         }
     }
 }
@@ -60,6 +326,15 @@ impl Default for AppSettings {
             log_level: "info".to_string(),
             theme: "dark".to_string(),
             language: "ja".to_string(),
+            log_retention_days: DEFAULT_LOG_RETENTION_DAYS,
+            launch_at_login: false,
+            prevent_sleep_during_transfer: true,
+            metadata_job_concurrency: DEFAULT_METADATA_JOB_CONCURRENCY,
+            upload_history_retention_days: DEFAULT_UPLOAD_HISTORY_RETENTION_DAYS,
+            upload_history_archive_limit: DEFAULT_UPLOAD_HISTORY_ARCHIVE_LIMIT,
+            slow_command_threshold_ms: default_slow_command_threshold_ms(),
+            tray_recent_uploads_count: DEFAULT_TRAY_RECENT_UPLOADS,
+            tray_recent_uploads_label: TrayRecentUploadLabel::default(),
         }
     }
 }
@@ -69,6 +344,10 @@ impl Default for UserPreferences {
         UserPreferences {
             default_bucket_name: None,
             default_storage_class: "DEEP_ARCHIVE".to_string(),
+            default_download_dir: None,
+            expired_restore_retention_days: DEFAULT_EXPIRED_RESTORE_RETENTION_DAYS,
+            default_restore_days: DEFAULT_RESTORE_DAYS,
+            restore_notification_retention_days: DEFAULT_RESTORE_NOTIFICATION_RETENTION_DAYS,
         }
     }
 }
@@ -80,12 +359,217 @@ impl Default for AwsSettings {
             timeout_seconds: 300,
             max_retries: 3,
             profile_name: None,
+            credential_cache_hours: default_credential_cache_hours(),
+            unlock_credentials_on_startup: false,
+            auto_correct_region: false,
+            suppress_cross_region_warning: false,
+            warm_up_s3_connection: true,
+        }
+    }
+}
+
+/// 日次ヘルスレポートの既定の生成時刻
+pub const DEFAULT_HEALTH_REPORT_TIME: &str = "09:00";
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        NotificationSettings {
+            enabled: true,
+            notify_queue_completed: true,
+            notify_large_file_completed: true,
+            large_file_threshold_mb: 1024,
+            notify_restore_completed: true,
+            notify_download_completed: true,
+            notify_errors: true,
+            suppress_when_focused: true,
+            show_badge: true,
+            notify_health_issues: true,
+            health_report_time: DEFAULT_HEALTH_REPORT_TIME.to_string(),
+        }
+    }
+}
+
+impl Default for QuickUploadSettings {
+    fn default() -> Self {
+        QuickUploadSettings {
+            shortcut: Some(DEFAULT_QUICK_UPLOAD_SHORTCUT.to_string()),
+            source: QuickUploadSource::Dialog,
+        }
+    }
+}
+
+impl Default for RetentionSettings {
+    fn default() -> Self {
+        RetentionSettings {
+            mode: RetentionMode::Confirm,
+            action: RetentionAction::Trash,
+            archive_dir: None,
         }
     }
 }
 
+/// 壊れた設定ファイルから復旧したときのイベント
+pub const CONFIG_RECOVERED_EVENT: &str = "config-recovered";
+/// 読み込めなかった設定ファイルの退避先（config.json → config.json.corrupted）
+const CORRUPTED_CONFIG_SUFFIX: &str = "corrupted";
+
+/// 設定ファイルの復旧方法
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case", tag = "action")]
+pub enum ConfigRecoveryAction {
+    /// 読み込めた最新のバックアップでconfig.jsonを置き換えた
+    RestoredFromBackup { backup_path: String },
+    /// 使えるバックアップがなく、デフォルト設定のセーフモードで起動した
+    SafeMode,
+}
+
+/// config-recoveredイベントのペイロード
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConfigRecovery {
+    pub action: ConfigRecoveryAction,
+    /// 設定ファイルを読み込めなかった理由
+    pub error: String,
+    /// 壊れたファイルの退避先（退避できなかった場合はNone）
+    pub corrupted_path: Option<String>,
+    /// 試したが読み込めなかったバックアップ
+    pub rejected_backups: Vec<String>,
+    pub recovered_at: String,
+}
+
+impl ConfigRecovery {
+    pub fn is_safe_mode(&self) -> bool {
+        self.action == ConfigRecoveryAction::SafeMode
+    }
+}
+
+/// このセッションで行った復旧（イベントはフロントの購読前に送られる場合があるため保持する）
+static CONFIG_RECOVERY: Mutex<Option<ConfigRecovery>> = Mutex::new(None);
+
+/// セーフモード中か（AWS系の自動処理を開始しない）
+pub fn is_safe_mode() -> bool {
+    CONFIG_RECOVERY.lock()
+        .map(|recovery| recovery.as_ref().is_some_and(ConfigRecovery::is_safe_mode))
+        .unwrap_or(false)
+}
+
+fn corrupted_config_path(config_path: &Path) -> PathBuf {
+    let mut name = config_path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}", CORRUPTED_CONFIG_SUFFIX));
+    config_path.with_file_name(name)
+}
+
+/// 設定ファイルと同じフォルダのバックアップ（config.backup.json・config_backup_*.json）を新しい順に返す
+fn find_config_backups(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<(std::time::SystemTime, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name == "config.backup.json" || (name.starts_with("config_backup_") && name.ends_with(".json"))
+        })
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, entry.path()))
+        })
+        .collect();
+    backups.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.cmp(&a.1)));
+    backups.into_iter().map(|(_, path)| path).collect()
+}
+
+/// 設定ファイルを読み込めない場合に復旧する（読み込めればNone）
+///
+/// 壊れたファイルをconfig.json.corruptedへ退避し、読み込めて検証も通る最新のバックアップで置き換える。
+/// 使えるバックアップがなければconfig.jsonを置かずにデフォルト設定（セーフモード）とする
+pub fn recover_config_file(config_path: &Path) -> Option<ConfigRecovery> {
+    let error = load_config_from_path(config_path).err()?.to_string();
+    log::error!("Config file is unreadable: {}", error);
+
+    let corrupted_path = corrupted_config_path(config_path);
+    let corrupted_path = match fs::rename(config_path, &corrupted_path) {
+        Ok(()) => Some(corrupted_path.to_string_lossy().to_string()),
+        Err(e) => {
+            log::warn!("Failed to move broken config aside: {}", e);
+            None
+        }
+    };
+
+    let mut rejected_backups = Vec::new();
+    let backups = config_path.parent().map(find_config_backups).unwrap_or_default();
+    for backup in backups {
+        let restored = load_config_from_path(&backup)
+            .and_then(|config| {
+                let validation = validate_config(&config);
+                if validation.valid {
+                    Ok(())
+                } else {
+                    Err(InternalError::Config(validation.errors.join(", ")))
+                }
+            })
+            .and_then(|_| {
+                fs::copy(&backup, config_path)
+                    .map_err(|e| InternalError::Config(format!("Failed to restore backup: {}", e)))
+            });
+        match restored {
+            Ok(_) => {
+                log::warn!("Config restored from backup {}", backup.display());
+                return Some(ConfigRecovery {
+                    action: ConfigRecoveryAction::RestoredFromBackup { backup_path: backup.to_string_lossy().to_string() },
+                    error,
+                    corrupted_path,
+                    rejected_backups,
+                    recovered_at: chrono::Utc::now().to_rfc3339(),
+                });
+            }
+            Err(e) => {
+                log::warn!("Skipping config backup {}: {}", backup.display(), e);
+                rejected_backups.push(backup.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    log::warn!("No usable config backup found, starting in safe mode with default settings");
+    Some(ConfigRecovery {
+        action: ConfigRecoveryAction::SafeMode,
+        error,
+        corrupted_path,
+        rejected_backups,
+        recovered_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// 復旧結果を保持し、config-recoveredイベントで通知
+pub fn record_config_recovery(app: &AppHandle, recovery: &ConfigRecovery) {
+    if let Ok(mut current) = CONFIG_RECOVERY.lock() {
+        // 一度セーフモードになったらセッション中は維持する
+        if !current.as_ref().is_some_and(ConfigRecovery::is_safe_mode) {
+            *current = Some(recovery.clone());
+        }
+    }
+    if let Err(e) = app.emit(CONFIG_RECOVERED_EVENT, recovery) {
+        log::warn!("Failed to emit {}: {}", CONFIG_RECOVERED_EVENT, e);
+    }
+}
+
+/// 設定を読み込み、壊れていれば復旧してから読み込み直す
+fn load_config_or_recover(app: &AppHandle) -> Result<AppConfig, InternalError> {
+    let config_path = get_config_path(app)?;
+    if let Some(recovery) = recover_config_file(&config_path) {
+        record_config_recovery(app, &recovery);
+    }
+    match load_config_from_path(&config_path) {
+        // 壊れたファイルを退避できなかった場合もセーフモードではデフォルト設定で動かす
+        Err(e) if is_safe_mode() => {
+            log::warn!("Using default config in safe mode: {}", e);
+            Ok(AppConfig::default())
+        }
+        result => result,
+    }
+}
+
 // 設定ファイルパス取得
-fn get_config_path(app: &AppHandle) -> Result<PathBuf, InternalError> {
+pub(crate) fn get_config_path(app: &AppHandle) -> Result<PathBuf, InternalError> {
     let app_data_dir = app
         .path()
         .app_data_dir()
@@ -126,12 +610,58 @@ fn validate_config(config: &AppConfig) -> ConfigValidationResult {
         errors.push(format!("Invalid log level: {}", config.app_settings.log_level));
     }
 
+    // 表示言語検証
+    let valid_languages = ["ja", "en"];
+    if !valid_languages.contains(&config.app_settings.language.as_str()) {
+        errors.push(format!("Invalid language: {}", config.app_settings.language));
+    }
+
+    // ログ保持期間検証
+    if config.app_settings.log_retention_days > 365 {
+        warnings.push(format!("Log retention is very long ({} days)", config.app_settings.log_retention_days));
+    }
+
+    // アップロード履歴のアーカイブ件数検証
+    if config.app_settings.upload_history_archive_limit == 0 {
+        errors.push("Upload history archive limit must be at least 1".to_string());
+    }
+
+    // スローコマンドの閾値検証
+    if config.app_settings.slow_command_threshold_ms == 0 {
+        errors.push("Slow command threshold must be at least 1ms".to_string());
+    }
+
+    // トレイの最近のアップロード件数検証
+    if config.app_settings.tray_recent_uploads_count > MAX_TRAY_RECENT_UPLOADS {
+        errors.push(format!("Tray recent uploads count must be at most {}", MAX_TRAY_RECENT_UPLOADS));
+    }
+
     // テーマ検証
     let valid_themes = ["light", "dark", "auto"];
     if !valid_themes.contains(&config.app_settings.theme.as_str()) {
         warnings.push(format!("Unknown theme: {}", config.app_settings.theme));
     }
 
+    // クイックアップロードのショートカット検証
+    if let Some(shortcut) = &config.quick_upload.shortcut {
+        if parse_shortcut(shortcut).is_err() {
+            errors.push(format!("Invalid quick upload shortcut: {}", shortcut));
+        }
+    }
+
+    // 復元済みの作業コピーの保持日数検証
+    if config.restored_retention_days == 0 {
+        errors.push("Restored file retention must be at least 1 day".to_string());
+    }
+
+    // 帯域制限スケジュール検証（日付を跨ぐ区間も含めて重複を禁止）
+    errors.extend(bandwidth_schedule_errors(&config.bandwidth_schedule));
+
+    // ヘルスレポート時刻検証
+    if chrono::NaiveTime::parse_from_str(&config.notification_settings.health_report_time, "%H:%M").is_err() {
+        errors.push(format!("Invalid health report time: {}", config.notification_settings.health_report_time));
+    }
+
     // AWS設定検証
     if config.aws_settings.timeout_seconds == 0 {
         errors.push("AWS timeout cannot be zero".to_string());
@@ -141,6 +671,49 @@ fn validate_config(config: &AppConfig) -> ConfigValidationResult {
         warnings.push("AWS timeout is very long (>1 hour)".to_string());
     }
 
+    if !(1..=MAX_RESTORE_DAYS).contains(&config.user_preferences.default_restore_days) {
+        errors.push(format!("Default restore days must be between 1 and {}", MAX_RESTORE_DAYS));
+    }
+
+    if config.aws_settings.credential_cache_hours == 0 {
+        errors.push("Credential cache duration cannot be zero".to_string());
+    } else if config.aws_settings.credential_cache_hours > 24 {
+        warnings.push("Credential cache duration is very long (>24 hours)".to_string());
+    }
+
+    // リテンション設定検証
+    if config.local_retention_days.is_some()
+        && config.retention_settings.action == RetentionAction::MoveToArchive
+        && config.retention_settings.archive_dir.as_deref().map_or(true, str::is_empty)
+    {
+        errors.push("Archive folder is required when retention action is move_to_archive".to_string());
+    }
+    if config.local_retention_days == Some(0) {
+        warnings.push("Local retention of 0 days removes originals right after upload verification".to_string());
+    }
+
+    // 転送量の月次上限検証
+    if let Some(cap) = config.monthly_transfer_cap_gb {
+        if !cap.is_finite() || cap <= 0.0 {
+            errors.push("Monthly transfer cap must be a positive number of GB".to_string());
+        }
+    }
+
+    // モックAWSモード検証
+    if config.mock_aws_speed_mbps <= 0.0 || !config.mock_aws_speed_mbps.is_finite() {
+        errors.push("Mock AWS simulation speed must be a positive number".to_string());
+    }
+    if config.mock_aws_mode {
+        warnings.push("Mock AWS mode is enabled: uploads are simulated and nothing is sent to AWS".to_string());
+    }
+
+    // ダウンロード先フォルダ検証
+    if let Some(dir) = &config.user_preferences.default_download_dir {
+        if !Path::new(dir).is_dir() {
+            warnings.push(format!("Default download folder does not exist and will be created: {}", dir));
+        }
+    }
+
     // ストレージクラス検証
     let valid_storage_classes = ["STANDARD", "STANDARD_IA", "ONEZONE_IA", "REDUCED_REDUNDANCY", "GLACIER", "DEEP_ARCHIVE"];
     if !valid_storage_classes.contains(&config.user_preferences.default_storage_class.as_str()) {
@@ -154,28 +727,44 @@ fn validate_config(config: &AppConfig) -> ConfigValidationResult {
     }
 }
 
-// Tauri Commands
+/// 設定ファイルを同期的に読み込む（コマンド外のバックエンド処理用）
+/// バックエンドが返すメッセージの言語を設定に合わせる
+pub fn apply_language_setting(config: &AppConfig) {
+    set_language(Language::from_code(&config.app_settings.language));
+}
 
-#[tauri::command]
-pub async fn get_config(app: AppHandle) -> Result<AppConfig, String> {
-    let config_path = get_config_path(&app)
-        .map_err(standardize_error)?;
-    
+pub fn load_config(app: &AppHandle) -> Result<AppConfig, InternalError> {
+    let config_path = get_config_path(app)?;
+    load_config_from_path(&config_path)
+}
+
+/// 指定パスの設定ファイルを読み込む（AppHandleを持たないCLIからも使用）
+pub fn load_config_from_path(config_path: &Path) -> Result<AppConfig, InternalError> {
     if !config_path.exists() {
         // 設定ファイルが存在しない場合はデフォルト設定を返す
-        let default_config = AppConfig::default();
-        return Ok(default_config);
+        return Ok(AppConfig::default());
     }
 
     let config_content = fs::read_to_string(&config_path)
-        .map_err(|e| InternalError::Config(format!("Failed to read config file: {}", e)))
-        .map_err(standardize_error)?;
+        .map_err(|e| InternalError::Config(format!("Failed to read config file: {}", e)))?;
 
-    let config: AppConfig = serde_json::from_str(&config_content)
+    serde_json::from_str(&config_content)
         .map_err(|e| InternalError::Config(format!("Failed to parse config file: {}", e)))
-        .map_err(standardize_error)?;
+}
 
-    Ok(config)
+// Tauri Commands
+
+#[tauri::command]
+pub async fn get_config(app: AppHandle) -> Result<AppConfig, String> {
+    load_config_or_recover(&app).map_err(standardize_error)
+}
+
+/// このセッションで設定ファイルを復旧した場合はその内容（セーフモードの判定にも使う）
+#[tauri::command]
+pub async fn get_config_recovery() -> Result<Option<ConfigRecovery>, String> {
+    CONFIG_RECOVERY.lock()
+        .map(|recovery| recovery.clone())
+        .map_err(|e| standardize_error(InternalError::Other(format!("Failed to lock config recovery: {}", e))))
 }
 
 #[tauri::command]
@@ -207,6 +796,19 @@ pub async fn set_config(app: AppHandle, config: AppConfig) -> Result<bool, Strin
         .map_err(|e| InternalError::Config(format!("Failed to write config file: {}", e)))
         .map_err(standardize_error)?;
 
+    // 表示言語・バッジ表示設定・スリープ抑止設定・モックAWSモードの変更を反映
+    apply_language_setting(&config);
+    crate::badge::refresh_badge(&app);
+    crate::power::refresh_power_settings(&app);
+    crate::commands::mock_aws::refresh_mock_aws_mode(&app);
+    crate::commands::metrics::refresh_metrics_settings(&app);
+    crate::commands::metadata_jobs::refresh_metadata_job_settings(&app);
+    crate::commands::bandwidth::refresh_bandwidth_schedule(&app);
+    crate::commands::transfer_cap::refresh_transfer_cap_settings(&app);
+    crate::tray::schedule_recent_uploads_refresh(&app);
+    // ショートカットの登録失敗は通知済みのため、設定の保存は成功として扱う
+    let _ = crate::commands::quick_upload::refresh_quick_upload_shortcut(&app, &config);
+
     Ok(true)
 }
 
@@ -234,6 +836,40 @@ pub async fn update_config(app: AppHandle, updates: HashMap<String, serde_json::
                     config.app_settings.language = v.to_string();
                 }
             }
+            "app_settings.log_retention_days" => {
+                if let Some(v) = value.as_u64() {
+                    config.app_settings.log_retention_days = v as u32;
+                }
+            }
+            "app_settings.upload_history_retention_days" => {
+                if let Some(v) = value.as_u64() {
+                    config.app_settings.upload_history_retention_days = v as u32;
+                }
+            }
+            "app_settings.upload_history_archive_limit" => {
+                if let Some(v) = value.as_u64() {
+                    config.app_settings.upload_history_archive_limit = v as usize;
+                }
+            }
+            "app_settings.slow_command_threshold_ms" => {
+                if let Some(v) = value.as_u64() {
+                    config.app_settings.slow_command_threshold_ms = v;
+                }
+            }
+            "app_settings.tray_recent_uploads_count" => {
+                if let Some(v) = value.as_u64() {
+                    config.app_settings.tray_recent_uploads_count = v as usize;
+                }
+            }
+            "app_settings.tray_recent_uploads_label" => {
+                config.app_settings.tray_recent_uploads_label = serde_json::from_value(value)
+                    .map_err(|e| standardize_error(InternalError::Config(format!("Invalid tray recent uploads label: {}", e))))?;
+            }
+            "app_settings.prevent_sleep_during_transfer" => {
+                if let Some(v) = value.as_bool() {
+                    config.app_settings.prevent_sleep_during_transfer = v;
+                }
+            }
             "user_preferences.default_bucket_name" => {
                 config.user_preferences.default_bucket_name = value.as_str().map(String::from);
             }
@@ -242,6 +878,26 @@ pub async fn update_config(app: AppHandle, updates: HashMap<String, serde_json::
                     config.user_preferences.default_storage_class = v.to_string();
                 }
             }
+            "user_preferences.default_download_dir" => {
+                config.user_preferences.default_download_dir = value.as_str()
+                    .filter(|v| !v.is_empty())
+                    .map(String::from);
+            }
+            "user_preferences.expired_restore_retention_days" => {
+                if let Some(v) = value.as_u64() {
+                    config.user_preferences.expired_restore_retention_days = v as u32;
+                }
+            }
+            "user_preferences.default_restore_days" => {
+                if let Some(v) = value.as_u64() {
+                    config.user_preferences.default_restore_days = v as u32;
+                }
+            }
+            "user_preferences.restore_notification_retention_days" => {
+                if let Some(v) = value.as_u64() {
+                    config.user_preferences.restore_notification_retention_days = v as u32;
+                }
+            }
 
             "aws_settings.default_region" => {
                 if let Some(v) = value.as_str() {
@@ -261,6 +917,138 @@ pub async fn update_config(app: AppHandle, updates: HashMap<String, serde_json::
             "aws_settings.profile_name" => {
                 config.aws_settings.profile_name = value.as_str().map(String::from);
             }
+            "aws_settings.credential_cache_hours" => {
+                if let Some(v) = value.as_u64() {
+                    config.aws_settings.credential_cache_hours = v as u32;
+                }
+            }
+            "aws_settings.unlock_credentials_on_startup" => {
+                if let Some(v) = value.as_bool() {
+                    config.aws_settings.unlock_credentials_on_startup = v;
+                }
+            }
+            "aws_settings.auto_correct_region" => {
+                if let Some(v) = value.as_bool() {
+                    config.aws_settings.auto_correct_region = v;
+                }
+            }
+            "aws_settings.warm_up_s3_connection" => {
+                if let Some(v) = value.as_bool() {
+                    config.aws_settings.warm_up_s3_connection = v;
+                }
+            }
+            "aws_settings.suppress_cross_region_warning" => {
+                if let Some(v) = value.as_bool() {
+                    config.aws_settings.suppress_cross_region_warning = v;
+                }
+            }
+            "notification_settings.enabled" => {
+                if let Some(v) = value.as_bool() {
+                    config.notification_settings.enabled = v;
+                }
+            }
+            "notification_settings.notify_queue_completed" => {
+                if let Some(v) = value.as_bool() {
+                    config.notification_settings.notify_queue_completed = v;
+                }
+            }
+            "notification_settings.notify_large_file_completed" => {
+                if let Some(v) = value.as_bool() {
+                    config.notification_settings.notify_large_file_completed = v;
+                }
+            }
+            "notification_settings.large_file_threshold_mb" => {
+                if let Some(v) = value.as_u64() {
+                    config.notification_settings.large_file_threshold_mb = v;
+                }
+            }
+            "notification_settings.notify_restore_completed" => {
+                if let Some(v) = value.as_bool() {
+                    config.notification_settings.notify_restore_completed = v;
+                }
+            }
+            "notification_settings.notify_download_completed" => {
+                if let Some(v) = value.as_bool() {
+                    config.notification_settings.notify_download_completed = v;
+                }
+            }
+            "notification_settings.notify_errors" => {
+                if let Some(v) = value.as_bool() {
+                    config.notification_settings.notify_errors = v;
+                }
+            }
+            "notification_settings.suppress_when_focused" => {
+                if let Some(v) = value.as_bool() {
+                    config.notification_settings.suppress_when_focused = v;
+                }
+            }
+            "local_retention_days" => {
+                config.local_retention_days = value.as_u64().map(|v| v as u32);
+            }
+            "restored_files_dir" => {
+                config.restored_files_dir = value.as_str().filter(|v| !v.is_empty()).map(|v| v.to_string());
+            }
+            "monthly_transfer_cap_gb" => {
+                config.monthly_transfer_cap_gb = value.as_f64();
+            }
+            "transfer_cap_action" => {
+                config.transfer_cap_action = serde_json::from_value(value)
+                    .map_err(|e| standardize_error(InternalError::Config(format!("Invalid transfer cap action: {}", e))))?;
+            }
+            "restored_retention_days" => {
+                if let Some(v) = value.as_u64() {
+                    config.restored_retention_days = v as u32;
+                }
+            }
+            "retention_settings.mode" => {
+                config.retention_settings.mode = serde_json::from_value(value)
+                    .map_err(|e| standardize_error(InternalError::Config(format!("Invalid retention mode: {}", e))))?;
+            }
+            "retention_settings.action" => {
+                config.retention_settings.action = serde_json::from_value(value)
+                    .map_err(|e| standardize_error(InternalError::Config(format!("Invalid retention action: {}", e))))?;
+            }
+            "retention_settings.archive_dir" => {
+                config.retention_settings.archive_dir = value.as_str().map(String::from);
+            }
+            "mock_aws_mode" => {
+                if let Some(v) = value.as_bool() {
+                    config.mock_aws_mode = v;
+                }
+            }
+            "mock_aws_speed_mbps" => {
+                if let Some(v) = value.as_f64() {
+                    config.mock_aws_speed_mbps = v;
+                }
+            }
+            "notification_settings.show_badge" => {
+                if let Some(v) = value.as_bool() {
+                    config.notification_settings.show_badge = v;
+                }
+            }
+            "quick_upload.shortcut" => {
+                config.quick_upload.shortcut = value.as_str().map(|v| v.to_string());
+            }
+            "quick_upload.source" => {
+                if let Ok(v) = serde_json::from_value(value) {
+                    config.quick_upload.source = v;
+                }
+            }
+            "bandwidth_schedule" => {
+                if let Ok(v) = serde_json::from_value(value) {
+                    config.bandwidth_schedule = v;
+                }
+            }
+            "notification_settings.notify_health_issues" => {
+                if let Some(v) = value.as_bool() {
+                    config.notification_settings.notify_health_issues = v;
+                }
+            }
+            "notification_settings.health_report_time" => {
+                if let Some(v) = value.as_str() {
+                    config.notification_settings.health_report_time = v.to_string();
+                }
+            }
             _ => {
                 return Err(standardize_error(InternalError::Other(format!("Unknown config key: {}", key))));
             }
@@ -441,6 +1229,32 @@ mod tests {
         assert_eq!(config.aws_settings.timeout_seconds, 300);
         assert_eq!(config.aws_settings.max_retries, 3);
         assert_eq!(config.user_preferences.default_storage_class, "DEEP_ARCHIVE");
+        assert_eq!(config.app_settings.log_retention_days, 14);
+    }
+
+    #[test]
+    fn test_log_retention_days_defaults_when_missing() {
+        // log_retention_days導入前の設定ファイルも読み込めること
+        let json = r#"{
+            "version": "1.0.0",
+            "app_settings": { "log_level": "info", "theme": "dark", "language": "ja" },
+            "user_preferences": { "default_bucket_name": null, "default_storage_class": "DEEP_ARCHIVE" },
+            "aws_settings": { "default_region": "ap-northeast-1", "timeout_seconds": 300, "max_retries": 3, "profile_name": null }
+        }"#;
+        let config: AppConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.app_settings.log_retention_days, DEFAULT_LOG_RETENTION_DAYS);
+        assert!(config.notification_settings.enabled);
+        assert!(!config.app_settings.launch_at_login);
+        assert!(config.app_settings.prevent_sleep_during_transfer);
+    }
+
+    #[test]
+    fn test_notification_settings_partial_json() {
+        // 一部の項目のみ指定された場合も残りはデフォルト値になること
+        let settings: NotificationSettings = serde_json::from_str(r#"{ "notify_errors": false }"#).unwrap();
+        assert!(!settings.notify_errors);
+        assert!(settings.enabled);
+        assert_eq!(settings.large_file_threshold_mb, 1024);
     }
 
     #[test]
@@ -460,6 +1274,64 @@ mod tests {
         assert!(result.errors.iter().any(|e| e.contains("Invalid log level")));
     }
 
+    #[test]
+    fn test_validate_config_invalid_quick_upload_shortcut() {
+        let mut config = AppConfig::default();
+        config.quick_upload.shortcut = Some("Cmd+Shift+NotAKey".to_string());
+        let result = validate_config(&config);
+        assert!(result.errors.iter().any(|e| e.contains("Invalid quick upload shortcut")));
+
+        // 未設定（ショートカットを登録しない）は有効
+        config.quick_upload.shortcut = None;
+        assert!(validate_config(&config).valid);
+    }
+
+    #[test]
+    fn test_validate_config_invalid_bandwidth_schedule() {
+        let config = AppConfig {
+            bandwidth_schedule: vec![
+                BandwidthWindow { start: "09:00".to_string(), end: "18:00".to_string(), limit_mbps: Some(10.0) },
+                BandwidthWindow { start: "17:00".to_string(), end: "01:00".to_string(), limit_mbps: None },
+            ],
+            ..AppConfig::default()
+        };
+        let result = validate_config(&config);
+        assert!(!result.valid);
+        assert!(result.errors.iter().any(|e| e.contains("overlaps")));
+    }
+
+    #[test]
+    fn test_validate_config_invalid_health_report_time() {
+        let mut config = AppConfig::default();
+        config.notification_settings.health_report_time = "25:00".to_string();
+        let result = validate_config(&config);
+        assert!(result.errors.iter().any(|e| e.contains("Invalid health report time")));
+
+        config.notification_settings.health_report_time = "06:30".to_string();
+        assert!(validate_config(&config).valid);
+    }
+
+    #[test]
+    fn test_validate_config_invalid_language() {
+        let mut config = AppConfig::default();
+        config.app_settings.language = "fr".to_string();
+        let result = validate_config(&config);
+        assert!(!result.valid);
+        assert!(result.errors.iter().any(|e| e.contains("Invalid language")));
+    }
+
+    #[test]
+    fn test_validate_config_retention_archive_dir_required() {
+        let mut config = AppConfig::default();
+        config.local_retention_days = Some(30);
+        config.retention_settings.action = RetentionAction::MoveToArchive;
+        let result = validate_config(&config);
+        assert!(!result.valid);
+
+        config.retention_settings.archive_dir = Some("/Volumes/Archive".to_string());
+        assert!(validate_config(&config).valid);
+    }
+
     #[test]
     fn test_validate_config_invalid_storage_class() {
         let mut config = AppConfig::default();
@@ -469,6 +1341,63 @@ mod tests {
         assert!(result.errors.iter().any(|e| e.contains("Invalid storage class")));
     }
 
+    fn write_config(path: &Path, config: &AppConfig) {
+        fs::write(path, serde_json::to_string_pretty(config).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_readable_config_needs_no_recovery() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+        assert!(recover_config_file(&config_path).is_none());
+        write_config(&config_path, &AppConfig::default());
+        assert!(recover_config_file(&config_path).is_none());
+    }
+
+    #[test]
+    fn test_broken_config_is_restored_from_latest_usable_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+        write_config(&dir.path().join("config_backup_20240101_000000.json"), &AppConfig::default());
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let mut newer = AppConfig::default();
+        newer.app_settings.log_level = "debug".to_string();
+        write_config(&dir.path().join("config.backup.json"), &newer);
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        // 最も新しいものは検証に通らない
+        let mut invalid = AppConfig::default();
+        invalid.app_settings.log_level = "loud".to_string();
+        write_config(&dir.path().join("config_backup_20240301_000000.json"), &invalid);
+        fs::write(&config_path, "{ not json").unwrap();
+
+        let recovery = recover_config_file(&config_path).unwrap();
+        let ConfigRecoveryAction::RestoredFromBackup { backup_path } = &recovery.action else {
+            panic!("expected restore from backup");
+        };
+        assert!(backup_path.ends_with("config.backup.json"));
+        assert_eq!(recovery.rejected_backups.len(), 1);
+        assert!(!recovery.is_safe_mode());
+        assert_eq!(fs::read_to_string(recovery.corrupted_path.unwrap()).unwrap(), "{ not json");
+        assert_eq!(load_config_from_path(&config_path).unwrap().app_settings.log_level, "debug");
+    }
+
+    #[test]
+    fn test_broken_config_without_backup_falls_back_to_safe_mode() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+        fs::write(dir.path().join("config.backup.json"), "also broken").unwrap();
+        fs::write(&config_path, "{ not json").unwrap();
+
+        let recovery = recover_config_file(&config_path).unwrap();
+        assert!(recovery.is_safe_mode());
+        assert_eq!(recovery.corrupted_path.as_deref(), Some(dir.path().join("config.json.corrupted").to_str().unwrap()));
+        assert_eq!(recovery.rejected_backups.len(), 1);
+        // config.jsonは置かず、デフォルト設定として読み込む
+        assert!(!config_path.exists());
+        assert_eq!(load_config_from_path(&config_path).unwrap().app_settings.log_level, "info");
+        assert_eq!(serde_json::to_value(&recovery.action).unwrap()["action"], "safe_mode");
+    }
+
     #[tokio::test]
     async fn test_get_config_success() {
         // AppHandleのモックは複雑なので、基本的な構造体テストのみ実行
@@ -486,17 +1415,47 @@ mod tests {
                 log_level: "debug".to_string(),
                 theme: "dark".to_string(),
                 language: "en".to_string(),
+                log_retention_days: 30,
+                launch_at_login: true,
+                prevent_sleep_during_transfer: false,
+                metadata_job_concurrency: 2,
+                upload_history_retention_days: 14,
+                upload_history_archive_limit: 500,
+                slow_command_threshold_ms: 2_000,
+                tray_recent_uploads_count: 3,
+                tray_recent_uploads_label: TrayRecentUploadLabel::WithKey,
             },
             user_preferences: UserPreferences {
                 default_bucket_name: Some("test-bucket".to_string()),
                 default_storage_class: "GLACIER".to_string(),
+                default_download_dir: Some("/tmp/downloads".to_string()),
+                expired_restore_retention_days: 3,
+                default_restore_days: 30,
+                restore_notification_retention_days: 30,
             },
             aws_settings: AwsSettings {
                 default_region: "us-west-2".to_string(),
                 timeout_seconds: 600,
                 max_retries: 5,
                 profile_name: Some("test-profile".to_string()),
+                credential_cache_hours: 4,
+                unlock_credentials_on_startup: true,
+                auto_correct_region: true,
+                suppress_cross_region_warning: false,
+                warm_up_s3_connection: false,
             },
+            notification_settings: NotificationSettings::default(),
+            local_retention_days: Some(30),
+            retention_settings: RetentionSettings::default(),
+            mock_aws_mode: false,
+            mock_aws_speed_mbps: DEFAULT_MOCK_SPEED_MBPS,
+            quick_upload: QuickUploadSettings::default(),
+            bandwidth_schedule: Vec::new(),
+            restored_files_dir: None,
+            restored_retention_days: DEFAULT_RESTORED_RETENTION_DAYS,
+            monthly_transfer_cap_gb: None,
+            transfer_cap_action: TransferCapAction::default(),
+            app_data_cleanup: AppDataCleanupSettings::default(),
         };
         
         // 構造体の検証
@@ -564,5 +1523,3 @@ mod tests {
         assert!(validation.errors.iter().any(|e| e.contains("Invalid storage class")));
     }
 }
-
- 
\ No newline at end of file
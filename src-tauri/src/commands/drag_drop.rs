@@ -0,0 +1,270 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use serde::Serialize;
+use tauri::{command, AppHandle, Emitter, Manager};
+
+use crate::commands::file_operations::{matches_pattern, validate_file_path};
+use crate::commands::upload_system::{add_files_to_upload_queue, S3KeyConfig, UploadQueueState};
+use crate::internal::i18n::{tr, MessageKey};
+use crate::internal::{AppError, ErrorCode, InternalError};
+
+/// ドロップ受領を通知するイベント名
+pub const FILES_DROPPED_EVENT: &str = "files-dropped";
+/// フォルダを展開した場合に1回のドロップで受け付けるファイル数の上限
+pub const MAX_DROPPED_FILES: usize = 1000;
+/// ドロップ時に除外するファイルパターン
+const DROP_EXCLUDE_PATTERNS: &[&str] = &["*.tmp", "*.part", "*.crdownload", ".DS_Store", "Thumbs.db"];
+
+/// フォルダ展開後に確認待ちのファイル（Tauriのmanage対象）
+#[derive(Default)]
+pub struct PendingDropState(Mutex<Option<Vec<String>>>);
+
+/// files-droppedイベントのペイロード
+#[derive(Debug, Clone, Serialize)]
+pub struct DroppedFiles {
+    pub file_count: usize,
+    pub total_size: u64,
+    pub folder_count: usize,
+    /// 検証・パターンフィルタで除外した件数
+    pub skipped_count: usize,
+    /// 上限を超えたため途中で展開を打ち切った
+    pub truncated: bool,
+    /// フォルダを含むため、confirm_dropped_filesでの確認後にキューへ追加する
+    pub requires_confirmation: bool,
+    pub error: Option<String>,
+}
+
+/// ドロップされたパスを展開した結果
+#[derive(Debug, Default)]
+pub struct ExpandedDrop {
+    pub files: Vec<String>,
+    pub total_size: u64,
+    pub folder_count: usize,
+    pub skipped_count: usize,
+    pub truncated: bool,
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| n.starts_with('.'))
+        .unwrap_or(true)
+}
+
+fn is_drop_excluded(path: &Path) -> bool {
+    let path = path.to_path_buf();
+    DROP_EXCLUDE_PATTERNS.iter().any(|pattern| matches_pattern(&path, pattern))
+}
+
+impl ExpandedDrop {
+    fn push_file(&mut self, path: &Path, size: u64, limit: usize) -> bool {
+        if self.files.len() >= limit {
+            self.truncated = true;
+            return false;
+        }
+        let path_str = path.to_string_lossy().to_string();
+        if !self.files.contains(&path_str) {
+            self.files.push(path_str);
+            self.total_size += size;
+        }
+        true
+    }
+}
+
+/// ドロップされたパスをファイル一覧に展開（フォルダは隠しファイルを除いて再帰展開し、上限で打ち切る）
+pub fn expand_dropped_paths(paths: &[PathBuf], limit: usize) -> ExpandedDrop {
+    let mut expanded = ExpandedDrop::default();
+
+    'paths: for path in paths {
+        if path.is_dir() {
+            expanded.folder_count += 1;
+            let mut pending = vec![path.clone()];
+            while let Some(dir) = pending.pop() {
+                let entries = match std::fs::read_dir(&dir) {
+                    Ok(entries) => entries,
+                    Err(e) => {
+                        log::warn!("Skipping dropped directory {}: {}", dir.display(), e);
+                        expanded.skipped_count += 1;
+                        continue;
+                    }
+                };
+                let mut entries: Vec<_> = entries.flatten().map(|entry| entry.path()).collect();
+                entries.sort();
+                for entry in entries {
+                    if is_hidden(&entry) || is_drop_excluded(&entry) {
+                        expanded.skipped_count += 1;
+                        continue;
+                    }
+                    match entry.metadata() {
+                        Ok(metadata) if metadata.is_dir() => pending.push(entry),
+                        Ok(metadata) => {
+                            if !expanded.push_file(&entry, metadata.len(), limit) {
+                                break 'paths;
+                            }
+                        }
+                        Err(e) => {
+                            log::warn!("Skipping dropped file {}: {}", entry.display(), e);
+                            expanded.skipped_count += 1;
+                        }
+                    }
+                }
+            }
+        } else if is_drop_excluded(path) {
+            expanded.skipped_count += 1;
+        } else {
+            match path.metadata() {
+                Ok(metadata) => {
+                    if !expanded.push_file(path, metadata.len(), limit) {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    log::warn!("Skipping dropped file {}: {}", path.display(), e);
+                    expanded.skipped_count += 1;
+                }
+            }
+        }
+    }
+    expanded
+}
+
+fn emit_dropped(app: &AppHandle, payload: DroppedFiles) {
+    if let Err(e) = app.emit(FILES_DROPPED_EVENT, payload) {
+        log::warn!("Failed to emit dropped files: {}", e);
+    }
+}
+
+fn notify_drop_error(app: &AppHandle, message: &str) {
+    log::warn!("Dropped files were not queued: {}", message);
+    crate::notifications::notify_error(app, &tr(MessageKey::DropTitle), message);
+}
+
+/// ウィンドウへのドロップを処理（lib.rsのDragDropイベントから呼び出す）
+pub fn handle_dropped_paths(app: &AppHandle, paths: Vec<PathBuf>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let total_dropped = paths.len();
+        // パストラバーサル・ホームディレクトリ外のパスを除外してから展開する
+        let validated: Vec<PathBuf> = paths.iter()
+            .filter_map(|path| match validate_file_path(path) {
+                Ok(path) => Some(path),
+                Err(e) => {
+                    log::warn!("Rejected dropped path {}: {}", path.display(), e);
+                    None
+                }
+            })
+            .collect();
+        let rejected = total_dropped - validated.len();
+
+        let mut expanded = match tauri::async_runtime::spawn_blocking(move || {
+            expand_dropped_paths(&validated, MAX_DROPPED_FILES)
+        }).await {
+            Ok(expanded) => expanded,
+            Err(e) => {
+                log::error!("Failed to expand dropped paths: {}", e);
+                return;
+            }
+        };
+        expanded.skipped_count += rejected;
+        log::info!(
+            "Received drop: {} file(s), {} folder(s), {} skipped{}",
+            expanded.files.len(), expanded.folder_count, expanded.skipped_count,
+            if expanded.truncated { " (truncated)" } else { "" }
+        );
+
+        let mut payload = DroppedFiles {
+            file_count: expanded.files.len(),
+            total_size: expanded.total_size,
+            folder_count: expanded.folder_count,
+            skipped_count: expanded.skipped_count,
+            truncated: expanded.truncated,
+            requires_confirmation: false,
+            error: None,
+        };
+
+        let key_config = S3KeyConfig::from_queue(&app.state::<UploadQueueState>());
+        let error = match (&key_config, expanded.files.is_empty()) {
+            (Err(e), _) => Some(e.to_string()),
+            (Ok(_), true) => Some(tr(MessageKey::DropNoFiles)),
+            _ => None,
+        };
+        if let Some(message) = error {
+            notify_drop_error(&app, &message);
+            payload.error = Some(message);
+            emit_dropped(&app, payload);
+            return;
+        }
+
+        // フォルダを展開した場合は件数を確認してからキューへ追加する
+        if expanded.folder_count > 0 {
+            if let Ok(mut pending) = app.state::<PendingDropState>().0.lock() {
+                *pending = Some(expanded.files);
+            }
+            payload.requires_confirmation = true;
+            emit_dropped(&app, payload);
+            return;
+        }
+
+        emit_dropped(&app, payload);
+        if let Ok(s3_key_config) = key_config {
+            if let Err(e) = add_files_to_upload_queue(app.clone(), expanded.files, s3_key_config, None, None, None, app.state()).await {
+                notify_drop_error(&app, &e.message);
+            }
+        }
+    });
+}
+
+/// フォルダのドロップで確認待ちになっているファイルをキューへ追加（acceptがfalseなら破棄）
+#[command]
+pub async fn confirm_dropped_files(app: AppHandle, accept: bool) -> Result<Option<String>, AppError> {
+    let files = app.state::<PendingDropState>().0.lock()
+        .map_err(|e| InternalError::Other(format!("Failed to lock pending drop: {}", e)))?
+        .take()
+        .ok_or_else(|| InternalError::localized(ErrorCode::File, MessageKey::DropNothingPending))?;
+    if !accept {
+        log::info!("Discarded {} dropped file(s)", files.len());
+        return Ok(None);
+    }
+
+    let s3_key_config = S3KeyConfig::from_queue(&app.state::<UploadQueueState>())?;
+    let message = add_files_to_upload_queue(app.clone(), files, s3_key_config, None, None, None, app.state()).await?;
+    Ok(Some(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_dropped_paths_recurses_and_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("day1");
+        std::fs::create_dir(&nested).unwrap();
+        std::fs::write(dir.path().join("a.mov"), b"12345").unwrap();
+        std::fs::write(nested.join("b.mov"), b"123").unwrap();
+        std::fs::write(nested.join("render.tmp"), b"x").unwrap();
+        std::fs::write(dir.path().join(".DS_Store"), b"").unwrap();
+        let other = tempfile::tempdir().unwrap();
+        let single = other.path().join("c.mov");
+        std::fs::write(&single, b"1").unwrap();
+
+        let expanded = expand_dropped_paths(&[dir.path().to_path_buf(), single], 10);
+        assert_eq!(expanded.files.len(), 3);
+        assert_eq!(expanded.total_size, 9);
+        assert_eq!(expanded.folder_count, 1);
+        assert_eq!(expanded.skipped_count, 2);
+        assert!(!expanded.truncated);
+    }
+
+    #[test]
+    fn test_expand_dropped_paths_truncates_at_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..5 {
+            std::fs::write(dir.path().join(format!("clip{}.mov", i)), b"x").unwrap();
+        }
+
+        let expanded = expand_dropped_paths(&[dir.path().to_path_buf()], 3);
+        assert_eq!(expanded.files.len(), 3);
+        assert!(expanded.truncated);
+    }
+}
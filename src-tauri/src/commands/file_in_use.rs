@@ -0,0 +1,198 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use crate::internal::InternalError;
+
+/// 使用中で保留したファイルを再確認する間隔
+pub const FILE_IN_USE_RECHECK_INTERVAL: Duration = Duration::from_secs(10);
+/// 使用中のファイルの解放を待つ上限（超えたらFailedにする）
+pub const FILE_IN_USE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+/// 1回のlsofに渡すパスの最大数（引数長の上限を避ける）
+const LSOF_BATCH_SIZE: usize = 200;
+
+/// 他プロセスが書き込み用に開いているかで保留しているアイテムの状態
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileInUseHold {
+    pub since: Instant,
+    pub next_check: Instant,
+}
+
+impl FileInUseHold {
+    pub fn new(now: Instant) -> Self {
+        Self { since: now, next_check: now + FILE_IN_USE_RECHECK_INTERVAL }
+    }
+
+    /// 再確認の時刻を過ぎたか
+    pub fn is_due(&self, now: Instant) -> bool {
+        now >= self.next_check
+    }
+
+    /// 解放を待つ上限を過ぎたか
+    pub fn is_timed_out(&self, now: Instant) -> bool {
+        now.duration_since(self.since) >= FILE_IN_USE_TIMEOUT
+    }
+
+    /// まだ使用中だったため次の再確認を先送りする
+    pub fn defer(&mut self, now: Instant) {
+        self.next_check = now + FILE_IN_USE_RECHECK_INTERVAL;
+    }
+}
+
+/// `lsof -F pan`の出力から、自プロセス以外が書き込み（w/u）で開いているファイル名を取り出す
+fn parse_lsof_writers(output: &str, own_pid: u32) -> HashSet<PathBuf> {
+    let mut writers = HashSet::new();
+    let mut pid = None;
+    let mut writable = false;
+    for line in output.lines() {
+        let mut chars = line.chars();
+        let field = chars.next();
+        let value = chars.as_str();
+        match field {
+            Some('p') => pid = value.parse::<u32>().ok(),
+            Some('f') => writable = false,
+            Some('a') => writable = matches!(value, "w" | "u"),
+            Some('n') => {
+                if writable && pid.is_some() && pid != Some(own_pid) {
+                    writers.insert(PathBuf::from(value));
+                }
+            }
+            _ => {}
+        }
+    }
+    writers
+}
+
+/// 指定したファイルのうち、他プロセスが書き込み用に開いているもの（`paths`の要素で返す）
+///
+/// 書き出し中の動画やコピー途中のファイルを検出するためlsofを使う。lsofを起動できない場合はエラー
+pub fn files_in_use(paths: &[PathBuf]) -> Result<HashSet<PathBuf>, InternalError> {
+    let own_pid = std::process::id();
+    let mut in_use = HashSet::new();
+    for batch in paths.chunks(LSOF_BATCH_SIZE) {
+        // lsofは実体のパスで出力するため、比較用に正規化したパスと元のパスを対応付ける
+        let resolved: Vec<(PathBuf, &PathBuf)> = batch.iter()
+            .filter_map(|path| std::fs::canonicalize(path).ok().map(|real| (real, path)))
+            .collect();
+        if resolved.is_empty() {
+            continue;
+        }
+        let output = Command::new("lsof")
+            .arg("-w")
+            .arg("-F")
+            .arg("pan")
+            .arg("--")
+            .args(resolved.iter().map(|(real, _)| real))
+            .output()
+            .map_err(|e| InternalError::Other(format!("Failed to run lsof: {}", e)))?;
+        // 誰も開いていないファイルがあると終了コードは1になるため、出力だけで判定する
+        let writers = parse_lsof_writers(&String::from_utf8_lossy(&output.stdout), own_pid);
+        in_use.extend(resolved.into_iter()
+            .filter(|(real, _)| writers.contains(real))
+            .map(|(_, path)| path.clone()));
+    }
+    Ok(in_use)
+}
+
+/// ファイルが他プロセスに書き込み用に開かれているか
+pub fn is_file_in_use(path: &Path) -> Result<bool, InternalError> {
+    let path = path.to_path_buf();
+    Ok(files_in_use(std::slice::from_ref(&path))?.contains(&path))
+}
+
+/// 判定をブロッキングスレッドで実行する（判定できない場合は使用中でないとみなし、アップロードを止めない）
+async fn run_in_use_check<T: Default + Send + 'static>(check: impl FnOnce() -> Result<T, InternalError> + Send + 'static) -> T {
+    match tokio::task::spawn_blocking(check).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(e)) => {
+            tracing::warn!("Skipped file-in-use check: {}", e);
+            T::default()
+        }
+        Err(e) => {
+            tracing::warn!("File-in-use check task failed: {}", e);
+            T::default()
+        }
+    }
+}
+
+/// キュー追加時にまとめて確認する
+pub async fn check_files_in_use(paths: Vec<PathBuf>) -> HashSet<PathBuf> {
+    if paths.is_empty() {
+        return HashSet::new();
+    }
+    run_in_use_check(move || files_in_use(&paths)).await
+}
+
+/// アップロード開始直前に確認する
+pub async fn check_file_in_use(path: PathBuf) -> bool {
+    run_in_use_check(move || is_file_in_use(&path)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lsof_writers_ignores_readers_and_own_process() {
+        let output = "p100\nfcwd\na \nn/Volumes/Media\nf12\naw\nn/Volumes/Media/export.mov\n\
+                      p200\nf3\nar\nn/Volumes/Media/clip.mov\nf4\nau\nn/Volumes/Media/copying.mov\n\
+                      p300\nf5\naw\nn/Volumes/Media/own.mov\n";
+        let writers = parse_lsof_writers(output, 300);
+        let mut names: Vec<_> = writers.iter().map(|p| p.to_string_lossy().to_string()).collect();
+        names.sort();
+        assert_eq!(names, vec!["/Volumes/Media/copying.mov", "/Volumes/Media/export.mov"]);
+        assert!(parse_lsof_writers("", 1).is_empty());
+    }
+
+    #[test]
+    fn test_hold_rechecks_and_times_out() {
+        let start = Instant::now();
+        let mut hold = FileInUseHold::new(start);
+        assert!(!hold.is_due(start + Duration::from_secs(1)));
+        assert!(hold.is_due(start + FILE_IN_USE_RECHECK_INTERVAL));
+
+        hold.defer(start + FILE_IN_USE_RECHECK_INTERVAL);
+        assert!(!hold.is_due(start + FILE_IN_USE_RECHECK_INTERVAL));
+        assert!(!hold.is_timed_out(start + FILE_IN_USE_RECHECK_INTERVAL));
+        assert!(hold.is_timed_out(start + FILE_IN_USE_TIMEOUT));
+    }
+
+    /// 別プロセスが書き込み用に開いている間だけ使用中と判定する（lsofが無い環境ではスキップ）
+    #[test]
+    fn test_file_locked_by_other_process() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("export.mov");
+        std::fs::write(&path, b"partial").unwrap();
+        let Ok(false) = is_file_in_use(&path) else {
+            eprintln!("lsof is not available; skipping");
+            return;
+        };
+
+        let mut writer = Command::new("sh")
+            .arg("-c")
+            .arg("exec 3>>\"$1\"; exec sleep 30")
+            .arg("sh")
+            .arg(&path)
+            .spawn()
+            .unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut detected = false;
+        while Instant::now() < deadline {
+            if is_file_in_use(&path).unwrap() {
+                detected = true;
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        writer.kill().unwrap();
+        writer.wait().unwrap();
+
+        assert!(detected);
+        assert!(!is_file_in_use(&path).unwrap());
+
+        // 自プロセスが開いている場合（ハッシュ計算中など）は使用中とみなさない
+        let _reader = std::fs::File::open(&path).unwrap();
+        assert!(!is_file_in_use(&path).unwrap());
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+
+use crate::internal::InternalError;
+
+/// シンボリックリンクをたどる最大回数（カーネルのELOOPと同程度）
+const MAX_SYMLINK_HOPS: usize = 40;
+
+/// キューへ追加しなかったパスの理由
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case", tag = "reason")]
+pub enum SkippedPathReason {
+    /// follow_symlinksが無効なためリンクを追わなかった
+    SymlinkNotFollowed,
+    /// 同じ実体（同一inode）を指すパスがすでに追加対象にある
+    DuplicateOf { original: String },
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SkippedPath {
+    pub path: String,
+    #[serde(flatten)]
+    pub reason: SkippedPathReason,
+}
+
+/// リンクを解決したキューの追加対象
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResolvedPaths {
+    /// 追加するパス（シンボリックリンクは実体のパス）
+    pub files: Vec<String>,
+    pub skipped: Vec<SkippedPath>,
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> InternalError {
+    InternalError::File(format!("Failed to read {}: {}", path.display(), e))
+}
+
+/// シンボリックリンクを実体までたどり、正規化した実体パスを返す（循環リンクはエラー）
+pub fn resolve_symlink(path: &Path) -> Result<PathBuf, InternalError> {
+    let mut visited = HashSet::new();
+    let mut current = path.to_path_buf();
+    for _ in 0..MAX_SYMLINK_HOPS {
+        let metadata = std::fs::symlink_metadata(&current).map_err(|e| io_error(&current, e))?;
+        if !metadata.file_type().is_symlink() {
+            return std::fs::canonicalize(&current).map_err(|e| io_error(&current, e));
+        }
+        if !visited.insert(current.clone()) {
+            break;
+        }
+        let target = std::fs::read_link(&current).map_err(|e| io_error(&current, e))?;
+        current = match current.parent() {
+            Some(parent) if target.is_relative() => parent.join(target),
+            _ => target,
+        };
+    }
+    Err(InternalError::File(format!("Symlink loop detected: {}", path.display())))
+}
+
+/// 同じ実体かを判定するためのキー（Unixはデバイス番号とinode、それ以外は正規化したパス）
+#[cfg(unix)]
+fn file_identity(_path: &Path, metadata: &Metadata) -> String {
+    use std::os::unix::fs::MetadataExt;
+    format!("{}:{}", metadata.dev(), metadata.ino())
+}
+
+#[cfg(not(unix))]
+fn file_identity(path: &Path, _metadata: &Metadata) -> String {
+    std::fs::canonicalize(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .to_string_lossy()
+        .to_string()
+}
+
+/// キューへ追加するパスのシンボリックリンク・ハードリンクを解決する
+///
+/// シンボリックリンクは`follow_symlinks`が有効なら実体のパスに置き換え、無効ならスキップする。
+/// 同じ実体を指すパス（リンクとリンク先・ハードリンク同士）は最初の1件だけを残す
+pub fn resolve_queue_paths(file_paths: Vec<String>, follow_symlinks: bool) -> Result<ResolvedPaths, InternalError> {
+    let mut resolved = ResolvedPaths::default();
+    let mut seen: HashMap<String, String> = HashMap::new();
+    for file_path in file_paths {
+        let path = Path::new(&file_path);
+        let link_metadata = std::fs::symlink_metadata(path)
+            .map_err(|_| InternalError::File(format!("File not found: {}", file_path)))?;
+        let real_path = if link_metadata.file_type().is_symlink() {
+            if !follow_symlinks {
+                log::info!("Skipping symlink (follow_symlinks is disabled): {}", file_path);
+                resolved.skipped.push(SkippedPath { path: file_path, reason: SkippedPathReason::SymlinkNotFollowed });
+                continue;
+            }
+            let target = resolve_symlink(path)?.to_string_lossy().to_string();
+            log::debug!("Resolved symlink {} -> {}", file_path, target);
+            target
+        } else {
+            file_path.clone()
+        };
+
+        let metadata = std::fs::metadata(&real_path).map_err(|e| io_error(Path::new(&real_path), e))?;
+        let identity = file_identity(Path::new(&real_path), &metadata);
+        if let Some(first) = seen.get(&identity) {
+            log::info!("Skipping {} (same file as {})", file_path, first);
+            resolved.skipped.push(SkippedPath {
+                path: file_path,
+                reason: SkippedPathReason::DuplicateOf { original: first.clone() },
+            });
+            continue;
+        }
+        seen.insert(identity, real_path.clone());
+        resolved.files.push(real_path);
+    }
+    Ok(resolved)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+
+    fn path_string(path: &Path) -> String {
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_symlinks_are_followed_or_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(dir.path()).unwrap();
+        let original = root.join("clip.mov");
+        std::fs::write(&original, vec![0u8; 1024]).unwrap();
+        let link = root.join("clip-link.mov");
+        symlink("clip.mov", &link).unwrap();
+        let chained = root.join("chained.mov");
+        symlink(&link, &chained).unwrap();
+
+        let followed = resolve_queue_paths(vec![path_string(&chained)], true).unwrap();
+        assert_eq!(followed.files, vec![path_string(&original)]);
+        assert_eq!(std::fs::metadata(&followed.files[0]).unwrap().len(), 1024);
+
+        let skipped = resolve_queue_paths(vec![path_string(&link), path_string(&original)], false).unwrap();
+        assert_eq!(skipped.files, vec![path_string(&original)]);
+        assert_eq!(skipped.skipped, vec![SkippedPath {
+            path: path_string(&link),
+            reason: SkippedPathReason::SymlinkNotFollowed,
+        }]);
+    }
+
+    #[test]
+    fn test_links_to_same_file_are_deduplicated() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(dir.path()).unwrap();
+        let original = root.join("clip.mov");
+        std::fs::write(&original, b"footage").unwrap();
+        let hard_link = root.join("clip-hard.mov");
+        std::fs::hard_link(&original, &hard_link).unwrap();
+        let link = root.join("clip-link.mov");
+        symlink(&original, &link).unwrap();
+
+        let paths = vec![path_string(&original), path_string(&hard_link), path_string(&link)];
+        let resolved = resolve_queue_paths(paths, true).unwrap();
+        assert_eq!(resolved.files, vec![path_string(&original)]);
+        let duplicate_of = SkippedPathReason::DuplicateOf { original: path_string(&original) };
+        assert_eq!(resolved.skipped.iter().map(|s| &s.reason).collect::<Vec<_>>(), vec![&duplicate_of, &duplicate_of]);
+    }
+
+    #[test]
+    fn test_symlink_loop_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.mov");
+        let b = dir.path().join("b.mov");
+        symlink(&b, &a).unwrap();
+        symlink(&a, &b).unwrap();
+        let self_loop = dir.path().join("self.mov");
+        symlink("./self.mov", &self_loop).unwrap();
+
+        assert!(resolve_queue_paths(vec![path_string(&a)], true).is_err());
+        assert!(resolve_queue_paths(vec![path_string(&self_loop)], true).is_err());
+        // 追わない設定ならループしていてもスキップとして扱う
+        assert_eq!(resolve_queue_paths(vec![path_string(&a)], false).unwrap().skipped.len(), 1);
+    }
+}
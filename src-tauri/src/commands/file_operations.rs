@@ -1,11 +1,17 @@
 use std::path::PathBuf;
-use std::sync::mpsc::channel;
-use std::time::Duration;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
-use tauri::command;
-use notify::{RecommendedWatcher, RecursiveMode, Watcher, Config, Event, EventKind};
+use tauri::{command, AppHandle, Emitter};
+use notify::{Event, EventKind};
+use notify::event::{ModifyKind, RenameMode};
 use std::collections::HashMap;
+use crate::commands::auto_upload_guard::queue_auto_upload;
+use crate::commands::watch_backend::{choose_watch_mode, volume_file_system, RecoveryOutcome, SelfHealingWatch, WatchMode};
 use crate::internal::{InternalError, standardize_error};
+use crate::internal::time::file_time_to_rfc3339;
 use uuid::Uuid;
 
 /// ファイル情報を表す構造体
@@ -14,7 +20,10 @@ pub struct FileInfo {
     pub name: String,
     pub path: String,
     pub size: u64,
-    pub modified: String,
+    /// RFC3339形式の更新日時（取得できない場合はNone）
+    pub modified: Option<String>,
+    /// RFC3339形式の作成日時（取得できない場合はNone）
+    pub created: Option<String>,
     pub is_directory: bool,
     pub extension: Option<String>,
 }
@@ -30,6 +39,430 @@ pub struct WatchConfig {
     pub exclude_patterns: Vec<String>, // 除外パターン (例: ["*.tmp", "*/.DS_Store"])
     pub exclude_directories: Vec<String>, // 除外ディレクトリ
     pub auto_metadata: bool, // 自動メタデータ作成
+    /// 書き出し時の「一時ファイル→最終名へのリネーム」を待ってから処理する
+    #[serde(default)]
+    pub rename_completion_detection: bool,
+    /// 最終名へのリネームを待つ一時ファイルの拡張子（例: ["tmp", "part"]）
+    #[serde(default = "default_temp_extensions")]
+    pub temp_extensions: Vec<String>,
+    /// FSEventsではなくポーリングで監視する（NASなどネットワークボリュームは未指定でも自動でポーリング）
+    #[serde(default)]
+    pub force_polling: bool,
+    /// ポーリング監視の間隔（秒）
+    #[serde(default = "default_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// シンボリックリンクを検知対象にする（無効時はリンクを無視する）
+    #[serde(default = "default_follow_symlinks")]
+    pub follow_symlinks: bool,
+    /// 直近1時間に自動で積むファイル数の上限（超えた分は承認待ちにする、未指定は無制限）
+    #[serde(default)]
+    pub max_auto_uploads_per_hour: Option<u32>,
+    /// 直近24時間に自動で積む合計バイト数の上限（未指定は無制限）
+    #[serde(default)]
+    pub max_auto_upload_bytes_per_day: Option<u64>,
+    /// システム定義の除外セット（DEFAULT_EXCLUDE_PATTERNS・DEFAULT_EXCLUDE_DIRECTORIES）を併せて適用する
+    #[serde(default = "default_use_default_excludes")]
+    pub use_default_excludes: bool,
+}
+
+fn default_follow_symlinks() -> bool {
+    true
+}
+
+fn default_use_default_excludes() -> bool {
+    true
+}
+
+/// OS・編集ソフトが作る、アップロード対象にならないファイルの除外パターン
+pub const DEFAULT_EXCLUDE_PATTERNS: &[&str] = &[
+    ".DS_Store",
+    "._*",
+    ".localized",
+    "Thumbs.db",
+    "desktop.ini",
+    "~$*",
+    "*.tmp",
+    "*.temp",
+    "*.part",
+    "*.partial",
+    "*.crdownload",
+    // Adobe Premiere Pro / Auditionのピーク・準拠オーディオ・ロックファイル
+    "*.pek",
+    "*.cfa",
+    "*.prlock",
+];
+
+/// OS・編集ソフトのキャッシュ・プレビュー用フォルダ（パスに含まれていれば除外）
+pub const DEFAULT_EXCLUDE_DIRECTORIES: &[&str] = &[
+    "Adobe Premiere Pro Preview Files",
+    "Adobe Premiere Pro Video Previews",
+    "Adobe Premiere Pro Audio Previews",
+    "Adobe Premiere Pro Auto-Save",
+    "Media Cache",
+    "Peak Files",
+    ".Trashes",
+    ".Spotlight-V100",
+    ".fseventsd",
+    ".TemporaryItems",
+    "$RECYCLE.BIN",
+    "System Volume Information",
+];
+
+/// 自動で除外されるパターン・フォルダ（get_default_exclude_patternsの結果）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DefaultExcludePatterns {
+    pub patterns: Vec<String>,
+    pub directories: Vec<String>,
+}
+
+/// デフォルトとユーザー定義を重複なく結合（前後の空白を除き、先に現れた順を保つ）
+fn merge_excludes(defaults: &[&str], user: &[String]) -> Vec<String> {
+    let mut merged: Vec<String> = Vec::with_capacity(defaults.len() + user.len());
+    for pattern in defaults.iter().copied().chain(user.iter().map(String::as_str)) {
+        let pattern = pattern.trim();
+        if !pattern.is_empty() && !merged.iter().any(|existing| existing == pattern) {
+            merged.push(pattern.to_string());
+        }
+    }
+    merged
+}
+
+impl WatchConfig {
+    /// 取り込みフォルダ用の監視設定（全ファイル・再帰・自動メタデータ、システム定義の除外のみ適用、リンクは対象外）
+    pub fn hot_folder(path: String) -> Self {
+        Self {
+            path,
+            recursive: true,
+            file_patterns: vec!["*".to_string()],
+            max_file_size_mb: None,
+            auto_upload: true,
+            exclude_patterns: Vec::new(),
+            exclude_directories: Vec::new(),
+            auto_metadata: true,
+            rename_completion_detection: true,
+            temp_extensions: default_temp_extensions(),
+            force_polling: false,
+            poll_interval_secs: default_poll_interval_secs(),
+            follow_symlinks: false,
+            max_auto_uploads_per_hour: None,
+            max_auto_upload_bytes_per_day: None,
+            use_default_excludes: true,
+        }
+    }
+
+    /// 実際に適用する除外パターン
+    pub fn effective_exclude_patterns(&self) -> Vec<String> {
+        let defaults: &[&str] = if self.use_default_excludes { DEFAULT_EXCLUDE_PATTERNS } else { &[] };
+        merge_excludes(defaults, &self.exclude_patterns)
+    }
+
+    /// 実際に適用する除外ディレクトリ
+    pub fn effective_exclude_directories(&self) -> Vec<String> {
+        let defaults: &[&str] = if self.use_default_excludes { DEFAULT_EXCLUDE_DIRECTORIES } else { &[] };
+        merge_excludes(defaults, &self.exclude_directories)
+    }
+}
+
+fn default_temp_extensions() -> Vec<String> {
+    ["tmp", "temp", "part", "partial", "crdownload", "download"]
+        .iter()
+        .map(|ext| ext.to_string())
+        .collect()
+}
+
+fn default_poll_interval_secs() -> u64 {
+    5
+}
+
+/// Create後にリネームを待つ時間（この間にリネームされなければ作成時の名前で処理）
+const RENAME_COMPLETION_WINDOW: Duration = Duration::from_secs(5);
+/// 一時ファイルの最終リネームを待つ上限（書き出しの中断などで残った一時ファイルを破棄）
+const TEMP_FILE_MAX_WAIT: Duration = Duration::from_secs(6 * 60 * 60);
+/// リネーム待ちのファイルを確定させる間隔
+const RENAME_COMPLETION_TICK: Duration = Duration::from_secs(1);
+
+/// 稼働中のディレクトリ監視数（ヘルスレポート用）
+static ACTIVE_WATCHERS: AtomicUsize = AtomicUsize::new(0);
+/// 最後に監視イベントを受け取った日時（RFC3339）
+static LAST_WATCH_EVENT_AT: Mutex<Option<String>> = Mutex::new(None);
+
+/// 監視ループの稼働を記録する（破棄すると稼働数から外れる）
+pub(crate) struct WatcherActivityGuard;
+
+impl WatcherActivityGuard {
+    pub(crate) fn start() -> Self {
+        ACTIVE_WATCHERS.fetch_add(1, Ordering::SeqCst);
+        Self
+    }
+}
+
+impl Drop for WatcherActivityGuard {
+    fn drop(&mut self) {
+        ACTIVE_WATCHERS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// 監視を作り直せたときのイベント
+pub const WATCHER_RECOVERED_EVENT: &str = "watcher-recovered";
+/// 監視の作り直しに失敗したときのイベント（gave_upなら監視はerror状態）
+pub const WATCHER_FAILED_EVENT: &str = "watcher-failed";
+
+/// 監視の状態
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WatcherState {
+    Watching,
+    /// エラーやドライブの切断で止まり、作り直しを待っている
+    Recovering,
+    /// 作り直しが上限回数失敗したため監視をやめた（watch_directoryで再開する）
+    Error,
+}
+
+/// 監視中のディレクトリの状態
+#[derive(Debug, Clone, Serialize)]
+pub struct WatcherStatus {
+    pub path: String,
+    pub state: WatcherState,
+    pub mode: Option<WatchMode>,
+    pub recovery_attempts: u32,
+    pub last_error: Option<String>,
+    pub updated_at: String,
+}
+
+/// watcher-recoveredイベントのペイロード
+#[derive(Debug, Clone, Serialize)]
+pub struct WatcherRecovered {
+    pub path: String,
+    pub attempts: u32,
+    pub mode: WatchMode,
+}
+
+/// watcher-failedイベントのペイロード
+#[derive(Debug, Clone, Serialize)]
+pub struct WatcherFailed {
+    pub path: String,
+    pub attempt: u32,
+    pub error: String,
+    /// 次に作り直しを試すまでの秒数（gave_upならNone）
+    pub retry_in_secs: Option<u64>,
+    pub gave_up: bool,
+}
+
+/// 監視パスごとの状態（フロントの購読前に起きた変化も取得できるよう保持する）
+static WATCHER_STATUSES: Mutex<Vec<WatcherStatus>> = Mutex::new(Vec::new());
+
+fn update_watcher_status(path: &str, state: WatcherState, mode: Option<WatchMode>, recovery_attempts: u32, last_error: Option<String>) {
+    let status = WatcherStatus {
+        path: path.to_string(),
+        state,
+        mode,
+        recovery_attempts,
+        last_error,
+        updated_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let mut statuses = WATCHER_STATUSES.lock().unwrap_or_else(|e| e.into_inner());
+    match statuses.iter_mut().find(|existing| existing.path == path) {
+        Some(existing) => *existing = status,
+        None => statuses.push(status),
+    }
+}
+
+/// 監視の作り直しの結果を記録し、イベントで通知する
+fn report_watch_recovery(app: &AppHandle, path: &str, outcome: RecoveryOutcome) {
+    match outcome {
+        RecoveryOutcome::Recovered { attempts, mode } => {
+            log::info!("Watcher for {} recovered after {} attempt(s) ({:?})", path, attempts, mode);
+            update_watcher_status(path, WatcherState::Watching, Some(mode), 0, None);
+            let payload = WatcherRecovered { path: path.to_string(), attempts, mode };
+            if let Err(e) = app.emit(WATCHER_RECOVERED_EVENT, &payload) {
+                log::warn!("Failed to emit {}: {}", WATCHER_RECOVERED_EVENT, e);
+            }
+        }
+        RecoveryOutcome::Failed { attempt, error, retry_in } => {
+            let gave_up = retry_in.is_none();
+            if gave_up {
+                log::error!("Gave up watching {} after {} attempt(s): {}", path, attempt, error);
+                update_watcher_status(path, WatcherState::Error, None, attempt, Some(error.clone()));
+                crate::notifications::notify_error(app, "フォルダの監視を停止しました", &format!("{} を監視できません: {}", path, error));
+            } else {
+                log::warn!("Failed to recreate watcher for {} (attempt {}): {}", path, attempt, error);
+                update_watcher_status(path, WatcherState::Recovering, None, attempt, Some(error.clone()));
+            }
+            let payload = WatcherFailed {
+                path: path.to_string(),
+                attempt,
+                error,
+                retry_in_secs: retry_in.map(|retry_in| retry_in.as_secs()),
+                gave_up,
+            };
+            if let Err(e) = app.emit(WATCHER_FAILED_EVENT, &payload) {
+                log::warn!("Failed to emit {}: {}", WATCHER_FAILED_EVENT, e);
+            }
+        }
+    }
+}
+
+/// 監視中のディレクトリの状態を取得
+#[command]
+pub async fn get_watcher_statuses() -> Result<Vec<WatcherStatus>, String> {
+    Ok(WATCHER_STATUSES.lock().unwrap_or_else(|e| e.into_inner()).clone())
+}
+
+/// 監視イベントの受信日時を記録
+pub(crate) fn record_watch_event() {
+    if let Ok(mut last_event_at) = LAST_WATCH_EVENT_AT.lock() {
+        *last_event_at = Some(chrono::Utc::now().to_rfc3339());
+    }
+}
+
+/// 稼働中の監視数と最後のイベント受信日時
+pub fn watcher_activity() -> (usize, Option<String>) {
+    let last_event_at = LAST_WATCH_EVENT_AT.lock().ok().and_then(|last| last.clone());
+    (ACTIVE_WATCHERS.load(Ordering::SeqCst), last_event_at)
+}
+
+/// リネーム完了ベースのファイル検知
+///
+/// 書き出しアプリが一時ファイルに書き込んでから最終名へリネームする場合に、最終名のみを処理対象にする。
+/// リネーム元と先はnotifyのイベントcookie（tracker）で対応付ける
+#[derive(Debug)]
+pub(crate) struct RenameCompletionTracker {
+    window: Duration,
+    temp_extensions: Vec<String>,
+    /// Create後にリネームを待っているファイル（最後にイベントを受けた時刻）
+    pending: HashMap<PathBuf, Instant>,
+    /// 最終名へのリネームを待っている一時ファイル
+    awaiting_rename: HashMap<PathBuf, Instant>,
+    /// 対応するリネーム先を待っているリネーム元（cookie→旧パス）
+    rename_sources: HashMap<usize, (PathBuf, Instant)>,
+}
+
+impl RenameCompletionTracker {
+    pub(crate) fn new(window: Duration, temp_extensions: Vec<String>) -> Self {
+        Self {
+            window,
+            temp_extensions: temp_extensions.iter().map(|ext| ext.trim_start_matches('.').to_lowercase()).collect(),
+            pending: HashMap::new(),
+            awaiting_rename: HashMap::new(),
+            rename_sources: HashMap::new(),
+        }
+    }
+
+    /// 監視設定で有効な場合のみ作成
+    pub(crate) fn from_config(config: &WatchConfig) -> Option<Self> {
+        config.rename_completion_detection
+            .then(|| Self::new(RENAME_COMPLETION_WINDOW, config.temp_extensions.clone()))
+    }
+
+    fn is_temp_file(&self, path: &std::path::Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.temp_extensions.iter().any(|temp| temp.eq_ignore_ascii_case(ext)))
+    }
+
+    fn track(&mut self, path: PathBuf, now: Instant) {
+        if self.is_temp_file(&path) {
+            self.awaiting_rename.insert(path, now);
+        } else {
+            self.pending.insert(path, now);
+        }
+    }
+
+    fn forget(&mut self, path: &std::path::Path) {
+        self.pending.remove(path);
+        self.awaiting_rename.remove(path);
+    }
+
+    /// リネームを反映し、最終名になったパスを返す
+    fn complete_rename(&mut self, from: Option<&std::path::Path>, to: PathBuf, now: Instant) -> Option<PathBuf> {
+        if let Some(from) = from {
+            self.forget(from);
+        }
+        if self.is_temp_file(&to) {
+            self.awaiting_rename.insert(to, now);
+            return None;
+        }
+        self.pending.remove(&to);
+        Some(to)
+    }
+
+    /// 監視イベントを取り込み、処理対象として確定したパスを返す
+    pub(crate) fn observe(&mut self, event: &Event, now: Instant) -> Vec<PathBuf> {
+        let mut completed = Vec::new();
+        match event.kind {
+            EventKind::Create(_) => {
+                for path in &event.paths {
+                    self.track(path.clone(), now);
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+                if let [from, to] = event.paths.as_slice() {
+                    completed.extend(self.complete_rename(Some(from), to.clone(), now));
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                for path in &event.paths {
+                    self.forget(path);
+                    if let Some(cookie) = event.attrs.tracker() {
+                        self.rename_sources.insert(cookie, (path.clone(), now));
+                    }
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                let from = event.attrs.tracker()
+                    .and_then(|cookie| self.rename_sources.remove(&cookie))
+                    .map(|(from, _)| from);
+                for path in &event.paths {
+                    completed.extend(self.complete_rename(from.as_deref(), path.clone(), now));
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(_)) => {
+                // FSEventsなどリネーム元・先が区別されない場合は、存在するパスをリネーム先とみなす
+                for path in &event.paths {
+                    if path.exists() {
+                        completed.extend(self.complete_rename(None, path.clone(), now));
+                    } else {
+                        self.forget(path);
+                    }
+                }
+            }
+            EventKind::Modify(_) => {
+                // 書き込み中はリネーム待ちの期限を延ばす
+                for path in &event.paths {
+                    self.track(path.clone(), now);
+                }
+            }
+            EventKind::Remove(_) => {
+                for path in &event.paths {
+                    self.forget(path);
+                }
+            }
+            _ => {}
+        }
+        completed
+    }
+
+    /// リネームされないまま待ち時間を過ぎたファイルを確定させて返す
+    pub(crate) fn flush_expired(&mut self, now: Instant) -> Vec<PathBuf> {
+        let window = self.window;
+        let expired: Vec<PathBuf> = self.pending.iter()
+            .filter(|(_, last_seen)| now.duration_since(**last_seen) >= window)
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in &expired {
+            self.pending.remove(path);
+        }
+
+        self.awaiting_rename.retain(|path, last_seen| {
+            let keep = now.duration_since(*last_seen) < TEMP_FILE_MAX_WAIT;
+            if !keep {
+                log::warn!("Temporary file was never renamed, ignoring: {}", path.display());
+            }
+            keep
+        });
+        self.rename_sources.retain(|_, (_, seen)| now.duration_since(*seen) < window);
+        expired
+    }
 }
 
 /// セキュリティ検証用の定数
@@ -42,7 +475,7 @@ const ALLOWED_FILE_EXTENSIONS: &[&str] = &[
 ];
 
 /// ファイルパスのセキュリティ検証
-fn validate_file_path(path: &PathBuf) -> Result<PathBuf, InternalError> {
+pub(crate) fn validate_file_path(path: &PathBuf) -> Result<PathBuf, InternalError> {
     // パストラバーサル攻撃対策
     let canonical_path = path.canonicalize()
         .map_err(|e| InternalError::File(format!("Failed to resolve path: {}", e)))?;
@@ -60,8 +493,30 @@ fn validate_file_path(path: &PathBuf) -> Result<PathBuf, InternalError> {
     Ok(canonical_path)
 }
 
+/// Finderで指定パスを選択状態で表示
+pub fn reveal_path_in_finder(path: &std::path::Path) -> Result<(), InternalError> {
+    if !path.exists() {
+        return Err(InternalError::File(format!("Path does not exist: {}", path.display())));
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg("-R")
+            .arg(path)
+            .spawn()
+            .map_err(|e| InternalError::File(format!("Failed to reveal in Finder: {}", e)))?;
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        Err(InternalError::Other("Reveal in Finder is only supported on macOS".to_string()))
+    }
+}
+
 /// ファイルサイズ検証
-fn validate_file_size(file_path: &PathBuf, max_size_mb: u64) -> Result<(), InternalError> {
+pub(crate) fn validate_file_size(file_path: &PathBuf, max_size_mb: u64) -> Result<(), InternalError> {
     if let Ok(metadata) = file_path.metadata() {
         let file_size_mb = metadata.len() / (1024 * 1024);
         if file_size_mb > max_size_mb {
@@ -76,7 +531,7 @@ fn validate_file_size(file_path: &PathBuf, max_size_mb: u64) -> Result<(), Inter
 }
 
 /// ファイルパターンマッチング（glob風）
-fn matches_pattern(file_path: &PathBuf, pattern: &str) -> bool {
+pub(crate) fn matches_pattern(file_path: &PathBuf, pattern: &str) -> bool {
     let file_name = file_path.file_name()
         .and_then(|s| s.to_str())
         .unwrap_or("");
@@ -89,8 +544,8 @@ fn matches_pattern(file_path: &PathBuf, pattern: &str) -> bool {
             .map(|s| s.eq_ignore_ascii_case(ext))
             .unwrap_or(false);
     } else if pattern.contains('*') {
-        // 単純なワイルドカードマッチング
-        let pattern_regex = pattern.replace('*', ".*");
+        // 単純なワイルドカードマッチング（"*"以外の文字はそのまま比較する）
+        let pattern_regex = regex::escape(pattern).replace(r"\*", ".*");
         return regex::Regex::new(&pattern_regex)
             .map(|r| r.is_match(file_name))
             .unwrap_or(false);
@@ -101,13 +556,19 @@ fn matches_pattern(file_path: &PathBuf, pattern: &str) -> bool {
 }
 
 /// ファイルを除外すべきかチェック
-fn should_exclude_file(file_path: &PathBuf, config: &WatchConfig) -> bool {
+pub(crate) fn should_exclude_file(file_path: &PathBuf, config: &WatchConfig) -> bool {
     let _file_name = file_path.file_name()
         .and_then(|s| s.to_str())
         .unwrap_or("");
     
+    // シンボリックリンクを追わない設定ではリンク自体を無視する
+    if !config.follow_symlinks && file_path.is_symlink() {
+        log::debug!("Symlink excluded (follow_symlinks is disabled): {}", file_path.display());
+        return true;
+    }
+    
     // 除外パターンチェック
-    for pattern in &config.exclude_patterns {
+    for pattern in &config.effective_exclude_patterns() {
         if matches_pattern(file_path, pattern) {
             log::debug!("File excluded by pattern '{}': {}", pattern, file_path.display());
             return true;
@@ -115,7 +576,7 @@ fn should_exclude_file(file_path: &PathBuf, config: &WatchConfig) -> bool {
     }
     
     // 除外ディレクトリチェック
-    for exclude_dir in &config.exclude_directories {
+    for exclude_dir in &config.effective_exclude_directories() {
         if file_path.to_string_lossy().contains(exclude_dir) {
             log::debug!("File excluded by directory '{}': {}", exclude_dir, file_path.display());
             return true;
@@ -141,45 +602,61 @@ fn should_exclude_file(file_path: &PathBuf, config: &WatchConfig) -> bool {
 }
 
 /// ファイル変更イベントを処理
-async fn handle_file_event(event: Event, config: &WatchConfig) -> Result<(), String> {
-    match event.kind {
-        EventKind::Create(_) | EventKind::Modify(_) => {
-            for path in event.paths {
-                if path.is_file() && !should_exclude_file(&path, config) {
-                    log::info!("File event detected: {}", path.display());
-                    
-                    // ファイルサイズチェック
-                    if let Some(max_size) = config.max_file_size_mb {
-                        if let Err(e) = validate_file_size(&path, max_size) {
-                            log::warn!("File size validation failed: {}", e);
-                            continue;
-                        }
-                    }
-                    
-                    // 自動メタデータ作成
-                    if config.auto_metadata {
-                        if let Err(e) = create_auto_metadata(&path).await {
-                            log::error!("Failed to create metadata for {}: {}", path.display(), e);
-                        }
-                    }
-                    
-                    // 自動アップロード
-                    if config.auto_upload {
-                        if let Err(e) = queue_auto_upload(&path).await {
-                            log::error!("Failed to queue upload for {}: {}", path.display(), e);
-                        }
-                    }
-                }
-            }
-        }
-        _ => {} // その他のイベントは無視
+///
+/// リネーム完了ベースの検知が有効な場合は、確定したパスのみを処理する
+async fn handle_file_event(
+    app: &AppHandle,
+    event: Event,
+    config: &WatchConfig,
+    rename_tracker: Option<&mut RenameCompletionTracker>,
+) -> Result<(), String> {
+    let paths = match rename_tracker {
+        Some(tracker) => tracker.observe(&event, Instant::now()),
+        None => match event.kind {
+            EventKind::Create(_) | EventKind::Modify(_) => event.paths,
+            _ => Vec::new(), // その他のイベントは無視
+        },
+    };
+    for path in paths {
+        process_detected_file(app, &path, config).await;
     }
     Ok(())
 }
 
+/// 検知したファイルのサイズ確認・メタデータ作成・自動アップロード
+async fn process_detected_file(app: &AppHandle, path: &PathBuf, config: &WatchConfig) {
+    if !path.is_file() || should_exclude_file(path, config) {
+        return;
+    }
+    log::info!("File event detected: {}", path.display());
+    
+    // ファイルサイズチェック
+    if let Some(max_size) = config.max_file_size_mb {
+        if let Err(e) = validate_file_size(path, max_size) {
+            log::warn!("File size validation failed: {}", e);
+            return;
+        }
+    }
+    
+    // 自動メタデータ作成（ジョブキューで後から処理し、後続のイベント処理を待たせない）
+    if config.auto_metadata {
+        crate::commands::metadata_jobs::enqueue_metadata_job(path);
+    }
+    
+    // 自動アップロード（件数・容量のガードを超えた分は承認待ちにする。セーフモード中は行わない）
+    if config.auto_upload && crate::commands::config::is_safe_mode() {
+        log::warn!("Safe mode: skipping auto upload for {}", path.display());
+    } else if config.auto_upload {
+        if let Err(e) = queue_auto_upload(app, path, config).await {
+            log::error!("Failed to queue upload for {}: {}", path.display(), e);
+        }
+    }
+}
+
 /// 自動メタデータ作成
-async fn create_auto_metadata(file_path: &PathBuf) -> Result<(), String> {
-    use crate::commands::metadata::{create_file_metadata, save_file_metadata};
+pub(crate) async fn create_auto_metadata(file_path: &PathBuf) -> Result<(), String> {
+    use crate::commands::metadata::{build_file_metadata, save_file_metadata};
+    use crate::commands::operations::CancellationToken;
     
     let file_path_str = file_path.to_string_lossy().to_string();
     
@@ -200,7 +677,7 @@ async fn create_auto_metadata(file_path: &PathBuf) -> Result<(), String> {
     custom_fields.insert("watch_path".to_string(), file_path_str.clone());
     
     // メタデータ作成
-    match create_file_metadata(file_path_str.clone(), auto_tags, custom_fields).await {
+    match build_file_metadata(file_path_str.clone(), auto_tags, custom_fields, &CancellationToken::new()).await {
         Ok(metadata) => {
             // データベースに保存
             let db_path = "./metadata.db".to_string(); // TODO: 設定から取得
@@ -217,19 +694,6 @@ async fn create_auto_metadata(file_path: &PathBuf) -> Result<(), String> {
     Ok(())
 }
 
-/// 自動アップロードキューに追加
-async fn queue_auto_upload(file_path: &PathBuf) -> Result<(), String> {
-    // TODO: アップロードキューへの追加実装
-    // 現在はログ出力のみ
-    log::info!("Queued for auto upload: {}", file_path.display());
-    
-    // 将来的にはupload_file関数と連携
-    // let s3_key = generate_s3_key(&file_path);
-    // queue_upload_task(file_path, s3_key).await?;
-    
-    Ok(())
-}
-
 /// ディレクトリ選択ダイアログを開く
 #[command]
 pub async fn select_directory(app: tauri::AppHandle) -> Result<Option<String>, String> {
@@ -246,6 +710,12 @@ pub async fn select_directory(app: tauri::AppHandle) -> Result<Option<String>, S
     }
 }
 
+/// 指定したファイル・フォルダをFinderで選択状態で表示
+#[command]
+pub async fn reveal_in_finder(path: String) -> Result<(), String> {
+    reveal_path_in_finder(std::path::Path::new(&path)).map_err(standardize_error)
+}
+
 /// ディレクトリ内のファイル一覧を取得
 #[command]
 pub async fn list_files(directory: String) -> Result<Vec<FileInfo>, String> {
@@ -277,16 +747,12 @@ pub async fn list_files(directory: String) -> Result<Vec<FileInfo>, String> {
                             .and_then(|s| s.to_str())
                             .map(|s| s.to_string());
                         
-                        let modified = metadata
-                            .modified()
-                            .map(|t| format!("{:?}", t))
-                            .unwrap_or_else(|_| "Unknown".to_string());
-                        
                         files.push(FileInfo {
                             name: file_name,
                             path: file_path,
                             size: metadata.len(),
-                            modified,
+                            modified: file_time_to_rfc3339(metadata.modified()),
+                            created: file_time_to_rfc3339(metadata.created()),
                             is_directory: metadata.is_dir(),
                             extension,
                         });
@@ -324,16 +790,12 @@ pub async fn get_file_info(file_path: String) -> Result<FileInfo, String> {
         .and_then(|s| s.to_str())
         .map(|s| s.to_string());
     
-    let modified = metadata
-        .modified()
-        .map(|t| format!("{:?}", t))
-        .unwrap_or_else(|_| "Unknown".to_string());
-    
     Ok(FileInfo {
         name: file_name,
         path: validated_path.to_string_lossy().to_string(),
         size: metadata.len(),
-        modified,
+        modified: file_time_to_rfc3339(metadata.modified()),
+        created: file_time_to_rfc3339(metadata.created()),
         is_directory: metadata.is_dir(),
         extension,
     })
@@ -341,7 +803,7 @@ pub async fn get_file_info(file_path: String) -> Result<FileInfo, String> {
 
 /// ディレクトリ監視を開始（notify crate実装版）
 #[command]
-pub async fn watch_directory(config: WatchConfig) -> Result<String, String> {
+pub async fn watch_directory(app: AppHandle, config: WatchConfig) -> Result<String, String> {
     let path = PathBuf::from(&config.path);
     
     // セキュリティ検証
@@ -360,22 +822,14 @@ pub async fn watch_directory(config: WatchConfig) -> Result<String, String> {
     // 実際のファイル監視実装
     let (tx, rx) = channel();
     
-    let notify_config = Config::default()
-        .with_poll_interval(Duration::from_secs(1));
-        
-    let mut watcher = RecommendedWatcher::new(tx, notify_config)
-        .map_err(|e| format!("Failed to create watcher: {}", e))?;
-    
-    let recursive_mode = if config.recursive {
-        RecursiveMode::Recursive
-    } else {
-        RecursiveMode::NonRecursive
-    };
-    
-    watcher.watch(&canonical_path, recursive_mode)
+    // ネットワークボリュームや設定に応じてFSEventsかポーリングを選ぶ
+    let mut watcher = SelfHealingWatch::start(&canonical_path, &config, tx)
         .map_err(|e| format!("Failed to start watching: {}", e))?;
+    let watch_mode = watcher.mode();
+    let watch_path = canonical_path.display().to_string();
+    update_watcher_status(&watch_path, WatcherState::Watching, watch_mode, 0, None);
     
-    log::info!("File watching started for: {}", canonical_path.display());
+    log::info!("File watching started for: {} ({:?})", canonical_path.display(), watch_mode);
     log::info!("Recursive: {}", config.recursive);
     log::info!("Patterns: {:?}", config.file_patterns);
     
@@ -385,21 +839,52 @@ pub async fn watch_directory(config: WatchConfig) -> Result<String, String> {
         log::info!("Advanced file watching started with features:");
         log::info!("  - Auto upload: {}", config_clone.auto_upload);
         log::info!("  - Auto metadata: {}", config_clone.auto_metadata);
-        log::info!("  - Exclude patterns: {:?}", config_clone.exclude_patterns);
+        log::info!("  - Exclude patterns: {:?} (default excludes: {})", config_clone.exclude_patterns, config_clone.use_default_excludes);
         log::info!("  - Exclude directories: {:?}", config_clone.exclude_directories);
+        log::info!("  - Rename completion detection: {}", config_clone.rename_completion_detection);
+        log::info!("  - Watch mode: {:?} (poll interval: {}s)", watch_mode, config_clone.poll_interval_secs);
         
-        for result in rx {
-            match result {
-                Ok(event) => {
+        let _activity = WatcherActivityGuard::start();
+        let mut rename_tracker = RenameCompletionTracker::from_config(&config_clone);
+        loop {
+            match rx.recv_timeout(RENAME_COMPLETION_TICK) {
+                Ok(Ok(event)) => {
                     log::debug!("File event: {:?}", event);
+                    record_watch_event();
                     
                     // 拡張されたイベント処理
-                    if let Err(e) = handle_file_event(event, &config_clone).await {
+                    if let Err(e) = handle_file_event(&app, event, &config_clone, rename_tracker.as_mut()).await {
                         log::error!("Failed to handle file event: {}", e);
                     }
                 }
-                Err(error) => {
+                Ok(Err(error)) => {
                     log::error!("Watch error: {:?}", error);
+                    // エラー後はイベントが届かなくなることがあるため、監視を作り直す
+                    if watcher.mark_broken(Instant::now()) {
+                        update_watcher_status(&watch_path, WatcherState::Recovering, None, 0, Some(error.to_string()));
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            
+            // ドライブの取り外しなどで対象パスが消えた場合も作り直しを待つ
+            let now = Instant::now();
+            if watcher.check_path(now) {
+                log::warn!("Watch path disappeared: {}", watch_path);
+                update_watcher_status(&watch_path, WatcherState::Recovering, None, 0, Some("Watch path is not available".to_string()));
+            }
+            if let Some(outcome) = watcher.poll_recovery(now) {
+                report_watch_recovery(&app, &watch_path, outcome);
+            }
+            if watcher.has_given_up() {
+                break;
+            }
+            
+            // リネームされないまま待ち時間を過ぎたファイルを処理
+            if let Some(tracker) = rename_tracker.as_mut() {
+                for path in tracker.flush_expired(Instant::now()) {
+                    process_detected_file(&app, &path, &config_clone).await;
                 }
             }
         }
@@ -466,9 +951,21 @@ pub async fn test_watch_system(config: WatchConfig) -> Result<String, String> {
         results.push(result_msg);
     }
     
+    // ネットワークボリュームではFSEventsが届かないためポーリングを推奨する
+    let file_system = volume_file_system(&canonical_path);
+    let watch_mode = choose_watch_mode(config.force_polling, file_system.as_deref());
+    if watch_mode == WatchMode::Polling && !config.force_polling {
+        results.push(format!(
+            "⚠️ ネットワークボリューム（{}）のため、ポーリング監視（{}秒間隔）で監視します",
+            file_system.as_deref().unwrap_or("unknown"),
+            config.poll_interval_secs
+        ));
+    }
+    
     let summary = format!(
-        "Watch system test completed for: {}\nResults:\n{}", 
+        "Watch system test completed for: {} (watch mode: {:?})\nResults:\n{}", 
         canonical_path.display(),
+        watch_mode,
         results.join("\n")
     );
     
@@ -476,65 +973,119 @@ pub async fn test_watch_system(config: WatchConfig) -> Result<String, String> {
     Ok(summary)
 }
 
-/// 現在のディレクトリでテスト可能なサンプル設定を生成
+/// 監視設定のプリセット（フロントはnameとdescriptionを選択肢として表示し、
+/// configを一部編集してwatch_directoryに渡せる）
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WatchConfigPreset {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub config: WatchConfig,
+}
+
+/// 動画素材として扱う拡張子
+const PRESET_VIDEO_PATTERNS: &[&str] = &["*.mp4", "*.mov", "*.mxf", "*.avi", "*.mkv", "*.m4v", "*.mts", "*.braw", "*.r3d"];
+/// 写真として扱う拡張子（RAWを含む）
+const PRESET_PHOTO_PATTERNS: &[&str] = &["*.jpg", "*.jpeg", "*.heic", "*.png", "*.tif", "*.tiff", "*.dng", "*.cr3", "*.arw", "*.nef"];
+/// プリセット共通の除外パターン
+const PRESET_EXCLUDE_PATTERNS: &[&str] = &["*.tmp", "*.part", "*.crdownload", ".DS_Store", "Thumbs.db"];
+/// プリセット共通の除外ディレクトリ
+const PRESET_EXCLUDE_DIRECTORIES: &[&str] = &[".git", "node_modules", ".Trash", "Cache", "Render Files"];
+
+fn to_strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+/// ホーム直下の候補のうち、最初に実在するディレクトリ
+fn first_existing_dir(home: &std::path::Path, candidates: &[&str]) -> Option<String> {
+    candidates.iter()
+        .map(|name| home.join(name))
+        .find(|dir| dir.is_dir())
+        .map(|dir| dir.to_string_lossy().to_string())
+}
+
+fn preset_config(path: String, file_patterns: Vec<String>) -> WatchConfig {
+    WatchConfig {
+        path,
+        recursive: true,
+        file_patterns,
+        max_file_size_mb: None,
+        auto_upload: false,
+        exclude_patterns: to_strings(PRESET_EXCLUDE_PATTERNS),
+        exclude_directories: to_strings(PRESET_EXCLUDE_DIRECTORIES),
+        auto_metadata: true,
+        rename_completion_detection: true,
+        temp_extensions: default_temp_extensions(),
+        force_polling: false,
+        poll_interval_secs: default_poll_interval_secs(),
+        follow_symlinks: true,
+        max_auto_uploads_per_hour: None,
+        max_auto_upload_bytes_per_day: None,
+        use_default_excludes: true,
+    }
+}
+
+/// ホームディレクトリ配下の実在するフォルダでプリセットを生成（対象フォルダがないプリセットは含めない）
+pub fn build_watch_config_presets(home: &std::path::Path) -> Vec<WatchConfigPreset> {
+    let mut presets = Vec::new();
+
+    if let Some(path) = first_existing_dir(home, &["Movies", "Desktop", "Downloads"]) {
+        presets.push(WatchConfigPreset {
+            id: "video_production".to_string(),
+            name: "ビデオ制作".to_string(),
+            description: format!("{} の動画ファイルのみを監視し、書き出しが終わったものから自動でアップロードします", path),
+            config: WatchConfig {
+                auto_upload: true,
+                max_auto_uploads_per_hour: Some(100),
+                ..preset_config(path, to_strings(PRESET_VIDEO_PATTERNS))
+            },
+        });
+    }
+
+    if let Some(path) = first_existing_dir(home, &["Pictures", "Movies", "Desktop", "Downloads"]) {
+        let file_patterns = PRESET_PHOTO_PATTERNS.iter()
+            .chain(PRESET_VIDEO_PATTERNS)
+            .map(|pattern| pattern.to_string())
+            .collect();
+        presets.push(WatchConfigPreset {
+            id: "photo_and_video".to_string(),
+            name: "写真＋動画".to_string(),
+            description: format!("{} の写真（RAWを含む）と動画を監視します。アップロードは手動で行います", path),
+            config: preset_config(path, file_patterns),
+        });
+    }
+
+    if let Some(path) = first_existing_dir(home, &["Downloads", "Desktop"]) {
+        presets.push(WatchConfigPreset {
+            id: "careful_all_files".to_string(),
+            name: "全ファイル慎重モード".to_string(),
+            description: format!("{} 直下のすべてのファイルを検知しますが、自動アップロードもメタデータ作成も行いません", path),
+            config: WatchConfig {
+                recursive: false,
+                max_file_size_mb: Some(10 * 1024), // 10GB
+                auto_metadata: false,
+                ..preset_config(path, vec!["*".to_string()])
+            },
+        });
+    }
+
+    presets
+}
+
+/// use_default_excludesで自動的に除外されるパターン・フォルダを取得
 #[command]
-pub async fn get_sample_watch_configs() -> Result<Vec<WatchConfig>, String> {
-    let current_dir = std::env::current_dir()
-        .map_err(|e| format!("Failed to get current directory: {}", e))?
-        .to_string_lossy()
-        .to_string();
-    
-    Ok(vec![
-        WatchConfig {
-            path: current_dir.clone(),
-            recursive: false,
-            file_patterns: vec!["*.mp4".to_string()],
-            max_file_size_mb: Some(100),
-            auto_upload: false,
-            exclude_patterns: vec!["*.tmp".to_string()],
-            exclude_directories: vec![],
-            auto_metadata: true,
-        },
-        WatchConfig {
-            path: current_dir.clone(),
-            recursive: true,
-            file_patterns: vec!["*.mp4".to_string(), "*.mov".to_string(), "*.avi".to_string()],
-            max_file_size_mb: Some(5120), // 5GB
-            auto_upload: true,
-            exclude_patterns: vec![
-                "*.tmp".to_string(),
-                "*.part".to_string(),
-                "*/.DS_Store".to_string(),
-                "*/Thumbs.db".to_string(),
-            ],
-            exclude_directories: vec![
-                ".git".to_string(),
-                "node_modules".to_string(),
-                ".cache".to_string(),
-            ],
-            auto_metadata: true,
-        },
-        WatchConfig {
-            path: current_dir,
-            recursive: true,
-            file_patterns: vec!["*.*".to_string()], // すべてのファイル
-            max_file_size_mb: Some(1024), // 1GB
-            auto_upload: false,
-            exclude_patterns: vec![
-                "*.log".to_string(),
-                "*.tmp".to_string(),
-                "*.cache".to_string(),
-                "*/.DS_Store".to_string(),
-            ],
-            exclude_directories: vec![
-                ".git".to_string(),
-                "target".to_string(),
-                "dist".to_string(),
-                "build".to_string(),
-            ],
-            auto_metadata: false,
-        },
-    ])
+pub async fn get_default_exclude_patterns() -> Result<DefaultExcludePatterns, String> {
+    Ok(DefaultExcludePatterns {
+        patterns: to_strings(DEFAULT_EXCLUDE_PATTERNS),
+        directories: to_strings(DEFAULT_EXCLUDE_DIRECTORIES),
+    })
+}
+
+/// ユーザーのホーム配下の実在ディレクトリで監視設定のプリセットを取得
+#[command]
+pub async fn get_watch_config_presets() -> Result<Vec<WatchConfigPreset>, String> {
+    let home = dirs::home_dir().ok_or_else(|| "Failed to get home directory".to_string())?;
+    Ok(build_watch_config_presets(&home))
 }
 
 #[cfg(test)]
@@ -554,6 +1105,14 @@ mod tests {
             exclude_patterns: vec!["*.tmp".to_string(), "*/.DS_Store".to_string()],
             exclude_directories: vec![".git".to_string(), "node_modules".to_string()],
             auto_metadata: true,
+            rename_completion_detection: false,
+            temp_extensions: default_temp_extensions(),
+            force_polling: false,
+            poll_interval_secs: default_poll_interval_secs(),
+            follow_symlinks: true,
+            max_auto_uploads_per_hour: None,
+            max_auto_upload_bytes_per_day: None,
+            use_default_excludes: true,
         }
     }
 
@@ -689,6 +1248,14 @@ mod tests {
             exclude_patterns: vec![], // 空の除外パターン配列
             exclude_directories: vec![],
             auto_metadata: true,
+            rename_completion_detection: false,
+            temp_extensions: default_temp_extensions(),
+            force_polling: false,
+            poll_interval_secs: default_poll_interval_secs(),
+            follow_symlinks: true,
+            max_auto_uploads_per_hour: None,
+            max_auto_upload_bytes_per_day: None,
+            use_default_excludes: true,
         };
         
         let test_file = temp_dir.path().join("test.mp4");
@@ -710,6 +1277,29 @@ mod tests {
         assert!(!should_exclude_file(&test_file, &config_with_exclude));
     }
 
+    #[test]
+    fn test_default_excludes_merge_without_duplicates() {
+        let mut config = create_test_watch_config("/footage");
+        config.exclude_patterns = vec!["*.tmp".to_string(), " *.log ".to_string(), "*.log".to_string()];
+
+        let patterns = config.effective_exclude_patterns();
+        assert_eq!(patterns.len(), DEFAULT_EXCLUDE_PATTERNS.len() + 1);
+        assert_eq!(patterns.iter().filter(|pattern| pattern.as_str() == "*.tmp").count(), 1);
+        assert_eq!(patterns.last().map(String::as_str), Some("*.log"));
+
+        let preview = PathBuf::from("/footage/Adobe Premiere Pro Preview Files/A001.mov");
+        let apple_double = PathBuf::from("/footage/._A001.mov");
+        assert!(should_exclude_file(&preview, &config));
+        assert!(should_exclude_file(&apple_double, &config));
+        // "."は任意の1文字ではなくそのまま比較する
+        assert!(!should_exclude_file(&PathBuf::from("/footage/a_b.mov"), &config));
+
+        config.use_default_excludes = false;
+        assert_eq!(config.effective_exclude_patterns(), vec!["*.tmp".to_string(), "*.log".to_string()]);
+        assert!(!should_exclude_file(&preview, &config));
+        assert!(!should_exclude_file(&apple_double, &config));
+    }
+
     #[tokio::test]
     async fn test_list_files_success() {
         // UUID付きテストディレクトリで並列衝突防止
@@ -790,7 +1380,7 @@ mod tests {
         assert!(file_info.size > 0);
         assert!(!file_info.is_directory);
         assert_eq!(file_info.extension, Some("txt".to_string()));
-        assert!(!file_info.modified.is_empty());
+        assert!(file_info.modified.as_deref().is_some_and(|m| chrono::DateTime::parse_from_rfc3339(m).is_ok()));
     }
 
     #[tokio::test]
@@ -803,32 +1393,35 @@ mod tests {
         assert!(error_msg.contains("File operation error") || error_msg.contains("No such file or directory"));
     }
 
-    #[tokio::test]
-    async fn test_get_sample_watch_configs() {
-        let result = get_sample_watch_configs().await;
-        
-        assert!(result.is_ok());
-        let configs = result.unwrap();
-        
-        // サンプル設定が3つ作成されることを確認
-        assert_eq!(configs.len(), 3);
-        
-        // 各設定の基本構造を確認
-        for config in &configs {
-            assert!(!config.path.is_empty());
-            assert!(!config.file_patterns.is_empty());
-            assert!(config.max_file_size_mb.is_some());
+    #[test]
+    fn test_watch_config_presets_use_existing_home_dirs() {
+        let home = TempDir::new().unwrap();
+        fs::create_dir(home.path().join("Movies")).unwrap();
+        fs::create_dir(home.path().join("Downloads")).unwrap();
+
+        let presets = build_watch_config_presets(home.path());
+        let ids: Vec<&str> = presets.iter().map(|preset| preset.id.as_str()).collect();
+        assert_eq!(ids, vec!["video_production", "photo_and_video", "careful_all_files"]);
+        for preset in &presets {
+            assert!(!preset.name.is_empty() && !preset.description.is_empty());
+            assert!(PathBuf::from(&preset.config.path).is_dir());
         }
-        
-        // 最初の設定（非再帰的）を確認
-        assert!(!configs[0].recursive);
-        assert!(!configs[0].auto_upload);
-        assert!(configs[0].auto_metadata);
-        
-        // 2番目の設定（再帰的、自動アップロード）を確認
-        assert!(configs[1].recursive);
-        assert!(configs[1].auto_upload);
-        assert!(configs[1].auto_metadata);
+
+        // ビデオ制作はMovies・動画拡張子のみ・自動アップロードON
+        let video = &presets[0].config;
+        assert_eq!(video.path, home.path().join("Movies").to_string_lossy());
+        assert!(video.auto_upload);
+        assert!(!should_exclude_file(&PathBuf::from(&video.path).join("A001.mov"), video));
+        assert!(should_exclude_file(&PathBuf::from(&video.path).join("still.jpg"), video));
+
+        // PicturesがないためMoviesへ、慎重モードはDownloadsですべてのファイルを対象にする
+        assert_eq!(presets[1].config.path, video.path);
+        let careful = &presets[2].config;
+        assert_eq!(careful.path, home.path().join("Downloads").to_string_lossy());
+        assert!(!careful.auto_upload);
+        assert!(!should_exclude_file(&PathBuf::from(&careful.path).join("notes.txt"), careful));
+
+        assert!(build_watch_config_presets(&home.path().join("missing")).is_empty());
     }
 
     #[tokio::test]
@@ -864,8 +1457,7 @@ mod tests {
         assert!(file_info.size > 0);
         assert!(!file_info.is_directory);
         assert_eq!(file_info.extension, None); // 拡張子なし
-        assert!(!file_info.modified.is_empty());
-        assert_ne!(file_info.modified, "Unknown"); // modifiedが"Unknown"でないことを確認
+        assert!(file_info.modified.as_deref().is_some_and(|m| chrono::DateTime::parse_from_rfc3339(m).is_ok()));
         
         // 隠しファイルの検証
         let hidden_path = hidden_file.to_str().unwrap().to_string();
@@ -879,8 +1471,7 @@ mod tests {
         assert!(file_info.size > 0);
         assert!(!file_info.is_directory);
         assert_eq!(file_info.extension, None); // 隠しファイルは拡張子なし
-        assert!(!file_info.modified.is_empty());
-        assert_ne!(file_info.modified, "Unknown"); // modifiedが"Unknown"でないことを確認
+        assert!(file_info.modified.as_deref().is_some_and(|m| chrono::DateTime::parse_from_rfc3339(m).is_ok()));
         
         // クリーンアップ（再帰的に削除）
         let _ = fs::remove_dir_all(&test_dir); // エラーを無視
@@ -952,4 +1543,91 @@ mod tests {
             }
         }
     }
-} 
\ No newline at end of file
+
+    mod rename_events {
+        use super::*;
+        use notify::event::{CreateKind, DataChange};
+
+        pub fn create(path: &str) -> Event {
+            Event::new(EventKind::Create(CreateKind::File)).add_path(PathBuf::from(path))
+        }
+
+        pub fn write(path: &str) -> Event {
+            Event::new(EventKind::Modify(ModifyKind::Data(DataChange::Content))).add_path(PathBuf::from(path))
+        }
+
+        pub fn rename_from(path: &str, cookie: usize) -> Event {
+            Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::From)))
+                .add_path(PathBuf::from(path))
+                .set_tracker(cookie)
+        }
+
+        pub fn rename_to(path: &str, cookie: usize) -> Event {
+            Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::To)))
+                .add_path(PathBuf::from(path))
+                .set_tracker(cookie)
+        }
+
+        pub fn rename_both(from: &str, to: &str) -> Event {
+            Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+                .add_path(PathBuf::from(from))
+                .add_path(PathBuf::from(to))
+        }
+    }
+
+    fn rename_tracker() -> RenameCompletionTracker {
+        RenameCompletionTracker::new(RENAME_COMPLETION_WINDOW, default_temp_extensions())
+    }
+
+    #[test]
+    fn test_rename_detection_waits_for_temp_file_rename() {
+        use rename_events::*;
+        // 一時拡張子で書き出してから最終名へリネームするパターン（inotifyのcookieで対応付け）
+        let mut tracker = rename_tracker();
+        let start = Instant::now();
+        assert!(tracker.observe(&create("/exports/clip.mov.part"), start).is_empty());
+        assert!(tracker.observe(&write("/exports/clip.mov.part"), start + Duration::from_secs(30)).is_empty());
+        assert!(tracker.flush_expired(start + Duration::from_secs(60)).is_empty());
+
+        assert!(tracker.observe(&rename_from("/exports/clip.mov.part", 7), start + Duration::from_secs(61)).is_empty());
+        let completed = tracker.observe(&rename_to("/exports/clip.mov", 7), start + Duration::from_secs(61));
+        assert_eq!(completed, vec![PathBuf::from("/exports/clip.mov")]);
+        assert!(tracker.flush_expired(start + Duration::from_secs(120)).is_empty());
+    }
+
+    #[test]
+    fn test_rename_detection_processes_only_final_name() {
+        use rename_events::*;
+        // 仮の名前で作成した直後に最終名へリネームするパターン
+        let mut tracker = rename_tracker();
+        let start = Instant::now();
+        tracker.observe(&create("/exports/Untitled.mov"), start);
+        tracker.observe(&write("/exports/Untitled.mov"), start + Duration::from_secs(1));
+        let completed = tracker.observe(&rename_both("/exports/Untitled.mov", "/exports/render_final.mov"), start + Duration::from_secs(2));
+        assert_eq!(completed, vec![PathBuf::from("/exports/render_final.mov")]);
+        assert!(tracker.flush_expired(start + Duration::from_secs(30)).is_empty());
+
+        // リネーム先が一時拡張子の場合は、さらに最終名へのリネームを待つ
+        tracker.observe(&create("/exports/a.mov"), start);
+        assert!(tracker.observe(&rename_both("/exports/a.mov", "/exports/a.mov.tmp"), start).is_empty());
+        assert!(tracker.flush_expired(start + Duration::from_secs(30)).is_empty());
+    }
+
+    #[test]
+    fn test_rename_detection_flushes_direct_writes_after_window() {
+        use rename_events::*;
+        // カードからのコピーなどリネームを伴わない書き込みは、最後の書き込みから待ち時間後に処理
+        let mut tracker = rename_tracker();
+        let start = Instant::now();
+        tracker.observe(&create("/exports/A001.mp4"), start);
+        tracker.observe(&write("/exports/A001.mp4"), start + Duration::from_secs(3));
+        assert!(tracker.flush_expired(start + Duration::from_secs(6)).is_empty());
+        assert_eq!(tracker.flush_expired(start + Duration::from_secs(9)), vec![PathBuf::from("/exports/A001.mp4")]);
+        assert!(tracker.flush_expired(start + Duration::from_secs(20)).is_empty());
+
+        // 監視外へ移動されたファイル（リネーム先が来ない）は処理しない
+        tracker.observe(&create("/exports/B001.mp4"), start);
+        tracker.observe(&rename_from("/exports/B001.mp4", 9), start + Duration::from_secs(1));
+        assert!(tracker.flush_expired(start + Duration::from_secs(20)).is_empty());
+    }
+}
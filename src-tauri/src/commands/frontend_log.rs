@@ -0,0 +1,266 @@
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use tauri::command;
+use tracing::field::{Field, Visit};
+use tracing_subscriber::layer::{Context, Layer};
+
+use crate::internal::{AppError, InternalError};
+use crate::internal::sanitize::sanitize;
+
+/// フロントエンドのログを書き込むtracingのターゲット
+pub const FRONTEND_LOG_TARGET: &str = "frontend";
+/// 1秒あたりに受け付けるフロントエンドログの上限
+const MAX_FRONTEND_EVENTS_PER_SEC: u32 = 100;
+/// メッセージの最大文字数（超過分は切り詰める）
+const MAX_MESSAGE_CHARS: usize = 4096;
+/// contextをJSON化した文字列の最大文字数
+const MAX_CONTEXT_CHARS: usize = 4096;
+/// リングバッファに保持する統合ログの件数
+const TIMELINE_CAPACITY: usize = 5000;
+
+/// 統合ログ（バックエンド・フロントエンド共通の時系列）の1件
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TimelineEntry {
+    pub timestamp: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+impl TimelineEntry {
+    fn to_line(&self) -> String {
+        format!("{} {:>5} {}: {}", self.timestamp, self.level, self.target, self.message)
+    }
+}
+
+/// log_frontend_eventの結果
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FrontendLogResult {
+    /// レートリミットで破棄された場合false
+    pub accepted: bool,
+    /// メッセージまたはcontextを切り詰めた
+    pub truncated: bool,
+    /// 起動後にレートリミットで破棄した累計件数
+    pub dropped_total: u64,
+}
+
+/// 1秒単位の固定ウィンドウで件数を数えるレートリミッター
+struct FrontendRateLimiter {
+    window_start: Option<Instant>,
+    window_count: u32,
+    window_dropped: u64,
+    dropped_total: u64,
+}
+
+impl FrontendRateLimiter {
+    const fn new() -> Self {
+        Self { window_start: None, window_count: 0, window_dropped: 0, dropped_total: 0 }
+    }
+
+    /// 受け付けるか判定し、ウィンドウが切り替わった場合は直前のウィンドウのドロップ数を返す
+    fn check(&mut self, now: Instant) -> (bool, Option<u64>) {
+        let mut previous_dropped = None;
+        let expired = match self.window_start {
+            Some(start) => now.duration_since(start) >= Duration::from_secs(1),
+            None => true,
+        };
+        if expired {
+            if self.window_dropped > 0 {
+                previous_dropped = Some(self.window_dropped);
+            }
+            self.window_start = Some(now);
+            self.window_count = 0;
+            self.window_dropped = 0;
+        }
+
+        if self.window_count < MAX_FRONTEND_EVENTS_PER_SEC {
+            self.window_count += 1;
+            (true, previous_dropped)
+        } else {
+            self.window_dropped += 1;
+            self.dropped_total += 1;
+            (false, previous_dropped)
+        }
+    }
+}
+
+static RATE_LIMITER: Mutex<FrontendRateLimiter> = Mutex::new(FrontendRateLimiter::new());
+static TIMELINE: Mutex<VecDeque<TimelineEntry>> = Mutex::new(VecDeque::new());
+
+fn push_timeline_entry(entry: TimelineEntry) {
+    let mut timeline = TIMELINE.lock().unwrap_or_else(|e| e.into_inner());
+    if timeline.len() >= TIMELINE_CAPACITY {
+        timeline.pop_front();
+    }
+    timeline.push_back(entry);
+}
+
+/// リングバッファ内の統合ログを古い順に1行ずつ整形して返す
+pub fn unified_timeline_lines() -> Vec<String> {
+    TIMELINE.lock().unwrap_or_else(|e| e.into_inner()).iter().map(TimelineEntry::to_line).collect()
+}
+
+/// イベントのmessageとフィールドを1行の文字列にまとめる
+#[derive(Default)]
+struct TimelineVisitor {
+    message: String,
+    fields: String,
+}
+
+impl TimelineVisitor {
+    fn finish(self) -> String {
+        if self.fields.is_empty() {
+            self.message
+        } else if self.message.is_empty() {
+            self.fields
+        } else {
+            format!("{}{}", self.message, self.fields)
+        }
+    }
+}
+
+impl Visit for TimelineVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// 全てのtracingイベントを統合ログのリングバッファへ書き込むレイヤー
+pub struct TimelineLayer;
+
+impl<S: tracing::Subscriber> Layer<S> for TimelineLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = TimelineVisitor::default();
+        event.record(&mut visitor);
+        push_timeline_entry(TimelineEntry {
+            timestamp: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3f%z").to_string(),
+            level: metadata.level().to_string(),
+            target: metadata.target().to_string(),
+            message: sanitize(&visitor.finish()),
+        });
+    }
+}
+
+/// 文字数の上限で切り詰める（切り詰めた場合はtrue）
+fn truncate_chars(text: &str, max_chars: usize) -> (String, bool) {
+    match text.char_indices().nth(max_chars) {
+        Some((index, _)) => (format!("{}…", &text[..index]), true),
+        None => (text.to_string(), false),
+    }
+}
+
+fn parse_level(level: &str) -> Result<tracing::Level, InternalError> {
+    match level.to_ascii_lowercase().as_str() {
+        "error" => Ok(tracing::Level::ERROR),
+        "warn" | "warning" => Ok(tracing::Level::WARN),
+        "info" => Ok(tracing::Level::INFO),
+        "debug" => Ok(tracing::Level::DEBUG),
+        "trace" => Ok(tracing::Level::TRACE),
+        _ => Err(InternalError::Other(format!("Invalid log level: {}", level))),
+    }
+}
+
+fn write_frontend_event(level: tracing::Level, message: &str, context: &str) {
+    match level {
+        tracing::Level::ERROR => tracing::error!(target: FRONTEND_LOG_TARGET, context = %context, "{}", message),
+        tracing::Level::WARN => tracing::warn!(target: FRONTEND_LOG_TARGET, context = %context, "{}", message),
+        tracing::Level::INFO => tracing::info!(target: FRONTEND_LOG_TARGET, context = %context, "{}", message),
+        tracing::Level::DEBUG => tracing::debug!(target: FRONTEND_LOG_TARGET, context = %context, "{}", message),
+        tracing::Level::TRACE => tracing::trace!(target: FRONTEND_LOG_TARGET, context = %context, "{}", message),
+    }
+}
+
+/// フロントエンドのログをバックエンドのログファイルと統合ログへ書き込む
+#[command]
+pub async fn log_frontend_event(
+    level: String,
+    message: String,
+    context: Option<serde_json::Value>,
+) -> Result<FrontendLogResult, AppError> {
+    let level = parse_level(&level)?;
+
+    let (accepted, previous_dropped, dropped_total) = {
+        let mut limiter = RATE_LIMITER.lock().unwrap_or_else(|e| e.into_inner());
+        let (accepted, previous_dropped) = limiter.check(Instant::now());
+        (accepted, previous_dropped, limiter.dropped_total)
+    };
+    if let Some(dropped) = previous_dropped {
+        tracing::warn!(target: FRONTEND_LOG_TARGET, "Dropped {} frontend log event(s) due to rate limit", dropped);
+    }
+    if !accepted {
+        return Ok(FrontendLogResult { accepted: false, truncated: false, dropped_total });
+    }
+
+    let (message, message_truncated) = truncate_chars(&message, MAX_MESSAGE_CHARS);
+    let context = context.unwrap_or(serde_json::Value::Null).to_string();
+    let (context, context_truncated) = truncate_chars(&context, MAX_CONTEXT_CHARS);
+    write_frontend_event(level, &message, &context);
+
+    Ok(FrontendLogResult { accepted: true, truncated: message_truncated || context_truncated, dropped_total })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_drops_excess_and_reports_on_next_window() {
+        let mut limiter = FrontendRateLimiter::new();
+        let start = Instant::now();
+        for _ in 0..MAX_FRONTEND_EVENTS_PER_SEC {
+            assert!(limiter.check(start).0);
+        }
+        assert_eq!(limiter.check(start + Duration::from_millis(500)), (false, None));
+        assert_eq!(limiter.check(start + Duration::from_millis(900)), (false, None));
+        assert_eq!(limiter.dropped_total, 2);
+
+        // 次のウィンドウでは受け付け、直前のドロップ数を通知する
+        assert_eq!(limiter.check(start + Duration::from_secs(1)), (true, Some(2)));
+        assert_eq!(limiter.check(start + Duration::from_millis(1100)), (true, None));
+    }
+
+    #[test]
+    fn test_level_validation_and_truncation() {
+        assert_eq!(parse_level("WARN").unwrap(), tracing::Level::WARN);
+        assert_eq!(parse_level("trace").unwrap(), tracing::Level::TRACE);
+        assert!(parse_level("fatal").is_err());
+
+        let (text, truncated) = truncate_chars("撮影素材", 2);
+        assert_eq!(text, "撮影…");
+        assert!(truncated);
+        assert_eq!(truncate_chars("abc", 3), ("abc".to_string(), false));
+    }
+
+    #[test]
+    fn test_timeline_layer_records_frontend_and_backend_events() {
+        use tracing_subscriber::prelude::*;
+
+        let subscriber = tracing_subscriber::registry().with(TimelineLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(target: "reel_vault::upload", item_id = "a1", "upload started");
+            write_frontend_event(tracing::Level::WARN, "button clicked", r#"{"view":"queue"}"#);
+        });
+
+        let lines = unified_timeline_lines();
+        let backend = lines.iter().position(|l| l.contains("reel_vault::upload: upload started item_id=a1")).unwrap();
+        let frontend = lines.iter().position(|l| l.contains(r#"frontend: button clicked context={"view":"queue"}"#)).unwrap();
+        assert!(backend < frontend);
+        assert!(lines[frontend].contains(" WARN "));
+    }
+}
@@ -0,0 +1,516 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use chrono::{DateTime, Local, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter, Manager};
+
+use crate::commands::api_usage::{api_usage_summary, ApiUsagePeriod, ApiUsageSummary};
+use crate::commands::archive_transition::{archive_transitions_for_credentials, ArchiveTransitionStatus};
+use crate::commands::auto_archive::{AutoArchiveHealth, AutoArchiveState};
+use crate::commands::aws_operations::restore_tracker_counts;
+use crate::commands::config::{load_config, DEFAULT_HEALTH_REPORT_TIME};
+use crate::commands::credential_cache::cached_credentials;
+use crate::commands::file_operations::watcher_activity;
+use crate::commands::license::{refresh_license_status, LicenseState};
+use crate::commands::lifecycle::is_reelvault_lifecycle_enabled;
+use crate::commands::metadata::{metadata_db_path, MetadataDatabase};
+use crate::commands::upload_system::{UploadQueueState, UploadStatus};
+use crate::internal::i18n::{LocalizedMessage, MessageKey};
+use crate::internal::{AppError, InternalError};
+use crate::notifications::{format_bytes, notify_health_issues};
+
+/// 日次ヘルスレポートを通知するイベント名
+pub const DAILY_HEALTH_REPORT_EVENT: &str = "daily-health-report";
+
+/// ヘルスレポートの保持日数
+const HEALTH_REPORT_RETENTION_DAYS: i64 = 30;
+/// 空き容量がこれを下回ると問題として扱う
+pub(crate) const LOW_DISK_SPACE_BYTES: u64 = 10 * 1024 * 1024 * 1024;
+/// ライセンスの有効期限がこの日数以内なら問題として扱う
+const LICENSE_EXPIRY_WARNING_DAYS: i64 = 7;
+/// レポートに含める未移行オブジェクトの最大件数
+const ARCHIVE_TRANSITION_SAMPLE_LIMIT: usize = 10;
+
+/// フォルダ監視の状態
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WatcherHealth {
+    pub active_watchers: usize,
+    pub last_event_at: Option<String>,
+    pub auto_archive: AutoArchiveHealth,
+    pub auto_archive_reason: Option<String>,
+}
+
+/// アップロードキューの状態
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QueueHealth {
+    pub total_count: usize,
+    pub pending_count: usize,
+    pub failed_count: usize,
+}
+
+/// 復元トラッカーの状態
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrackerHealth {
+    pub restore_in_progress: usize,
+    pub restore_failed: usize,
+}
+
+/// ライフサイクル設定の状態（アップロード設定がない場合は未確認）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LifecycleHealth {
+    pub bucket: Option<String>,
+    /// ReelVaultルールが有効か（確認できなかった場合はNone）
+    pub enabled: Option<bool>,
+    pub error: Option<String>,
+}
+
+/// 認証情報とライセンスの状態
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuthHealth {
+    pub credentials_configured: bool,
+    /// セッショントークン付きの一時的な認証情報か
+    pub temporary_credentials: bool,
+    pub license_state: LicenseState,
+    pub license_expires_at: Option<String>,
+    pub license_days_remaining: Option<i64>,
+}
+
+/// ディスク残量
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DiskHealth {
+    pub path: String,
+    pub available_bytes: Option<u64>,
+    pub total_bytes: Option<u64>,
+}
+
+/// 日次ヘルスレポート
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HealthReport {
+    pub generated_at: String,
+    /// 問題が1件もなければtrue
+    pub healthy: bool,
+    pub issues: Vec<String>,
+    pub watchers: WatcherHealth,
+    pub queue: QueueHealth,
+    pub trackers: TrackerHealth,
+    pub lifecycle: LifecycleHealth,
+    pub auth: AuthHealth,
+    pub disk: DiskHealth,
+    /// 今月のS3リクエスト数と概算料金（集計できなかった場合はNone）
+    #[serde(default)]
+    pub api_usage: Option<ApiUsageSummary>,
+    /// アップロード先プレフィックスのアーカイブ移行状況（確認できなかった場合はNone）
+    #[serde(default)]
+    pub archive_transition: Option<ArchiveTransitionStatus>,
+}
+
+impl HealthReport {
+    /// 各項目から問題を判定してレポートを組み立てる
+    pub fn new(
+        generated_at: DateTime<Utc>,
+        watchers: WatcherHealth,
+        queue: QueueHealth,
+        trackers: TrackerHealth,
+        lifecycle: LifecycleHealth,
+        auth: AuthHealth,
+        disk: DiskHealth,
+    ) -> Self {
+        let mut report = Self {
+            generated_at: generated_at.to_rfc3339(),
+            healthy: true,
+            issues: Vec::new(),
+            watchers,
+            queue,
+            trackers,
+            lifecycle,
+            auth,
+            disk,
+            api_usage: None,
+            archive_transition: None,
+        };
+        report.issues = report.collect_issues();
+        report.healthy = report.issues.is_empty();
+        report
+    }
+
+    /// アーカイブ移行状況を追加して問題を判定し直す（未移行オブジェクトは先頭の一部のみ保持）
+    pub fn with_archive_transition(mut self, mut status: ArchiveTransitionStatus) -> Self {
+        status.untransitioned.truncate(ARCHIVE_TRANSITION_SAMPLE_LIMIT);
+        self.archive_transition = Some(status);
+        self.issues = self.collect_issues();
+        self.healthy = self.issues.is_empty();
+        self
+    }
+
+    fn collect_issues(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        match self.watchers.auto_archive {
+            AutoArchiveHealth::Degraded => issues.push(
+                LocalizedMessage::new(MessageKey::HealthAutoArchiveDegraded)
+                    .param("reason", self.watchers.auto_archive_reason.as_deref().unwrap_or("-"))
+                    .render(),
+            ),
+            AutoArchiveHealth::Healthy if self.watchers.active_watchers == 0 => {
+                issues.push(LocalizedMessage::new(MessageKey::HealthWatcherStopped).render());
+            }
+            _ => {}
+        }
+
+        if self.queue.failed_count > 0 {
+            issues.push(
+                LocalizedMessage::new(MessageKey::HealthQueueFailed)
+                    .param("count", self.queue.failed_count)
+                    .render(),
+            );
+        }
+        if self.trackers.restore_failed > 0 {
+            issues.push(
+                LocalizedMessage::new(MessageKey::HealthRestoreFailed)
+                    .param("count", self.trackers.restore_failed)
+                    .render(),
+            );
+        }
+
+        if let Some(bucket) = &self.lifecycle.bucket {
+            if let Some(error) = &self.lifecycle.error {
+                issues.push(
+                    LocalizedMessage::new(MessageKey::HealthLifecycleCheckFailed)
+                        .param("bucket", bucket)
+                        .param("error", error)
+                        .render(),
+                );
+            } else if self.lifecycle.enabled == Some(false) {
+                issues.push(
+                    LocalizedMessage::new(MessageKey::HealthLifecycleDisabled)
+                        .param("bucket", bucket)
+                        .render(),
+                );
+            }
+        }
+
+        if let Some(status) = &self.archive_transition {
+            if status.delayed_count > 0 {
+                issues.push(
+                    LocalizedMessage::new(MessageKey::HealthArchiveTransitionDelayed)
+                        .param("bucket", &status.bucket)
+                        .param("count", status.delayed_count)
+                        .render(),
+                );
+            }
+            if status.not_covered_count > 0 {
+                issues.push(
+                    LocalizedMessage::new(MessageKey::HealthArchiveTransitionUncovered)
+                        .param("bucket", &status.bucket)
+                        .param("count", status.not_covered_count)
+                        .render(),
+                );
+            }
+        }
+
+        if self.auth.license_state == LicenseState::Expired {
+            issues.push(LocalizedMessage::new(MessageKey::HealthLicenseExpired).render());
+        } else if let Some(days) = self.auth.license_days_remaining.filter(|d| *d <= LICENSE_EXPIRY_WARNING_DAYS) {
+            issues.push(
+                LocalizedMessage::new(MessageKey::HealthLicenseExpiring)
+                    .param("days", days.max(0))
+                    .render(),
+            );
+        }
+
+        if let Some(available) = self.disk.available_bytes.filter(|b| *b < LOW_DISK_SPACE_BYTES) {
+            issues.push(
+                LocalizedMessage::new(MessageKey::HealthLowDiskSpace)
+                    .param("path", &self.disk.path)
+                    .param("available", format_bytes(available))
+                    .render(),
+            );
+        }
+
+        issues
+    }
+}
+
+/// 次の生成時刻までの待ち時間（当日の時刻を過ぎていれば翌日）
+pub fn duration_until_next(now: DateTime<Local>, at: NaiveTime) -> Duration {
+    let today = now.date_naive().and_time(at);
+    let next = match today.and_local_timezone(Local).earliest() {
+        Some(next) if next > now => next,
+        _ => (today + chrono::Duration::days(1))
+            .and_local_timezone(Local)
+            .earliest()
+            .unwrap_or_else(|| now + chrono::Duration::days(1)),
+    };
+    (next - now).to_std().unwrap_or(Duration::ZERO)
+}
+
+/// 設定された生成時刻（不正な値は既定値）
+fn configured_report_time(app: &AppHandle) -> NaiveTime {
+    let configured = load_config(app)
+        .map(|config| config.notification_settings.health_report_time)
+        .unwrap_or_else(|_| DEFAULT_HEALTH_REPORT_TIME.to_string());
+    NaiveTime::parse_from_str(&configured, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(DEFAULT_HEALTH_REPORT_TIME, "%H:%M"))
+        .unwrap_or(NaiveTime::MIN)
+}
+
+/// パスを含むボリュームの空き容量と全体容量
+pub(crate) fn disk_space_for(path: &Path) -> (Option<u64>, Option<u64>) {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks.list().iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().components().count())
+        .map(|disk| (Some(disk.available_space()), Some(disk.total_space())))
+        .unwrap_or((None, None))
+}
+
+fn open_metadata_db(app: &AppHandle) -> Result<MetadataDatabase, InternalError> {
+    let db_path = metadata_db_path(app)?;
+    MetadataDatabase::new(&db_path.to_string_lossy())
+        .map_err(|e| InternalError::Database(format!("Failed to create database connection: {}", e)))
+}
+
+/// 現在の状態からヘルスレポートを生成
+pub async fn generate_health_report(app: &AppHandle) -> HealthReport {
+    let now = Utc::now();
+
+    let (active_watchers, last_event_at) = watcher_activity();
+    let auto_archive = app.try_state::<AutoArchiveState>()
+        .map(|state| state.status())
+        .unwrap_or_default();
+    let watchers = WatcherHealth {
+        active_watchers,
+        last_event_at,
+        auto_archive: auto_archive.health,
+        auto_archive_reason: auto_archive.reason,
+    };
+
+    let (queue, upload_config) = app.try_state::<UploadQueueState>()
+        .and_then(|state| {
+            let q = state.lock().ok()?;
+            let count = |status: UploadStatus| q.items.iter().filter(|item| item.status == status).count();
+            let queue = QueueHealth {
+                total_count: q.items.len(),
+                pending_count: count(UploadStatus::Pending),
+                failed_count: count(UploadStatus::Failed),
+            };
+            Some((queue, q.config.clone()))
+        })
+        .unwrap_or((QueueHealth { total_count: 0, pending_count: 0, failed_count: 0 }, None));
+
+    let (restore_in_progress, restore_failed) = restore_tracker_counts().unwrap_or_else(|e| {
+        log::warn!("Failed to read restore tracker for health report: {}", e);
+        (0, 0)
+    });
+    let trackers = TrackerHealth { restore_in_progress, restore_failed };
+
+    // 認証情報はキュー設定ではなくロック解除済みのキャッシュから参照する
+    let credentials = cached_credentials(app);
+    let lifecycle = match (&upload_config, &credentials) {
+        (Some(config), Ok(credentials)) => match is_reelvault_lifecycle_enabled(credentials, &config.bucket_name).await {
+            Ok(enabled) => LifecycleHealth { bucket: Some(config.bucket_name.clone()), enabled: Some(enabled), error: None },
+            Err(e) => LifecycleHealth { bucket: Some(config.bucket_name.clone()), enabled: None, error: Some(e) },
+        },
+        (Some(config), Err(e)) => LifecycleHealth { bucket: Some(config.bucket_name.clone()), enabled: None, error: Some(e.to_string()) },
+        (None, _) => LifecycleHealth { bucket: None, enabled: None, error: None },
+    };
+    let archive_transition = match (&upload_config, &credentials) {
+        (Some(config), Ok(credentials)) => archive_transitions_for_credentials(
+            credentials,
+            &config.bucket_name,
+            config.s3_key_prefix.as_deref().filter(|prefix| !prefix.is_empty()),
+        )
+        .await
+        .map_err(|e| log::warn!("Failed to check archive transitions for health report: {}", e))
+        .ok(),
+        _ => None,
+    };
+
+    let license = refresh_license_status(app);
+    let license_expires_at = license.claims.as_ref().and_then(|claims| claims.expires_at.clone());
+    let license_days_remaining = license_expires_at.as_deref()
+        .and_then(|expires_at| DateTime::parse_from_rfc3339(expires_at).ok())
+        .map(|expires_at| (expires_at.with_timezone(&Utc) - now).num_days());
+    let auth = AuthHealth {
+        credentials_configured: upload_config.is_some(),
+        temporary_credentials: credentials.as_ref()
+            .is_ok_and(|credentials| credentials.session_token.is_some()),
+        license_state: license.state,
+        license_expires_at,
+        license_days_remaining,
+    };
+
+    // 監視フォルダがあればそのボリューム、なければアプリのデータ領域を確認する
+    let disk_path = auto_archive.watch_path.map(PathBuf::from)
+        .or_else(|| app.path().app_data_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("/"));
+    let (available_bytes, total_bytes) = disk_space_for(&disk_path);
+    let disk = DiskHealth {
+        path: disk_path.to_string_lossy().to_string(),
+        available_bytes,
+        total_bytes,
+    };
+
+    let mut report = HealthReport::new(now, watchers, queue, trackers, lifecycle, auth, disk);
+    if let Some(status) = archive_transition {
+        report = report.with_archive_transition(status);
+    }
+    report.api_usage = api_usage_summary(app, ApiUsagePeriod::CurrentMonth)
+        .map_err(|e| log::warn!("Failed to summarize API usage for health report: {}", e))
+        .ok();
+    report
+}
+
+/// レポートを保存して古い履歴を削除
+fn store_health_report(app: &AppHandle, report: &HealthReport) -> Result<(), InternalError> {
+    let db = open_metadata_db(app)?;
+    db.insert_health_report(report)
+        .map_err(|e| InternalError::Database(format!("Failed to save health report: {}", e)))?;
+    let cutoff = Utc::now() - chrono::Duration::days(HEALTH_REPORT_RETENTION_DAYS);
+    let pruned = db.prune_health_reports(&cutoff.to_rfc3339())
+        .map_err(|e| InternalError::Database(format!("Failed to prune health reports: {}", e)))?;
+    if pruned > 0 {
+        log::debug!("Pruned {} health report(s) older than {} days", pruned, HEALTH_REPORT_RETENTION_DAYS);
+    }
+    Ok(())
+}
+
+/// ヘルスレポートを生成し、保存・イベント・通知（問題がある場合のみ）を行う
+pub async fn run_health_report(app: &AppHandle) {
+    let report = generate_health_report(app).await;
+    log::info!("Daily health report generated: healthy={}, issues={}", report.healthy, report.issues.len());
+
+    if let Err(e) = store_health_report(app, &report) {
+        log::error!("Failed to store health report: {}", e);
+    }
+    if let Err(e) = app.emit(DAILY_HEALTH_REPORT_EVENT, &report) {
+        log::error!("Failed to emit daily health report: {}", e);
+    }
+    if !report.healthy {
+        notify_health_issues(app, &report.issues);
+    }
+}
+
+/// 設定された時刻に日次ヘルスレポートを生成するタスクを開始
+pub fn start_health_report_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            // 設定変更を反映するため、毎回生成時刻を読み直す
+            let wait = duration_until_next(Local::now(), configured_report_time(&app));
+            tokio::time::sleep(wait).await;
+            run_health_report(&app).await;
+        }
+    });
+}
+
+/// 保存済みのヘルスレポートを新しい順に取得（既定は直近30件）
+#[command]
+pub async fn get_health_reports(app: AppHandle, limit: Option<usize>) -> Result<Vec<HealthReport>, AppError> {
+    let db = open_metadata_db(&app)?;
+    let reports = db.list_health_reports(limit.unwrap_or(HEALTH_REPORT_RETENTION_DAYS as usize))
+        .map_err(|e| InternalError::Database(format!("Failed to load health reports: {}", e)))?;
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::internal::i18n::tr;
+
+    fn healthy_report() -> HealthReport {
+        HealthReport::new(
+            Utc::now(),
+            WatcherHealth {
+                active_watchers: 1,
+                last_event_at: None,
+                auto_archive: AutoArchiveHealth::Healthy,
+                auto_archive_reason: None,
+            },
+            QueueHealth { total_count: 3, pending_count: 1, failed_count: 0 },
+            TrackerHealth { restore_in_progress: 0, restore_failed: 0 },
+            LifecycleHealth { bucket: Some("bucket".to_string()), enabled: Some(true), error: None },
+            AuthHealth {
+                credentials_configured: true,
+                temporary_credentials: false,
+                license_state: LicenseState::Unlicensed,
+                license_expires_at: None,
+                license_days_remaining: None,
+            },
+            DiskHealth { path: "/".to_string(), available_bytes: Some(LOW_DISK_SPACE_BYTES * 2), total_bytes: None },
+        )
+    }
+
+    #[test]
+    fn test_health_report_detects_issues() {
+        let report = healthy_report();
+        assert!(report.healthy);
+        assert!(report.issues.is_empty());
+
+        let report = HealthReport::new(
+            Utc::now(),
+            WatcherHealth { active_watchers: 0, ..report.watchers },
+            QueueHealth { failed_count: 2, ..report.queue },
+            report.trackers,
+            LifecycleHealth { enabled: Some(false), ..report.lifecycle },
+            AuthHealth { license_days_remaining: Some(3), ..report.auth },
+            DiskHealth { available_bytes: Some(1024), ..report.disk },
+        );
+        assert!(!report.healthy);
+        assert_eq!(report.issues.len(), 5);
+        assert!(report.issues.contains(&tr(MessageKey::HealthWatcherStopped)));
+    }
+
+    #[test]
+    fn test_archive_transition_issues_and_sample_limit() {
+        use crate::commands::archive_transition::evaluate_archive_transitions;
+        use crate::commands::aws_operations::S3Object;
+
+        let objects: Vec<S3Object> = (0..15)
+            .map(|i| S3Object {
+                key: format!("exports/{}.mov", i),
+                size: 1024 * 1024,
+                last_modified: "2024-06-01T00:00:00Z".to_string(),
+                storage_class: "STANDARD".to_string(),
+                etag: String::new(),
+            })
+            .collect();
+        let status = evaluate_archive_transitions("bucket", None, &objects, &[], Utc::now());
+        let report = healthy_report().with_archive_transition(status);
+
+        assert!(!report.healthy);
+        assert_eq!(report.issues.len(), 1);
+        let status = report.archive_transition.unwrap();
+        assert_eq!(status.not_covered_count, 15);
+        assert_eq!(status.untransitioned.len(), ARCHIVE_TRANSITION_SAMPLE_LIMIT);
+    }
+
+    #[test]
+    fn test_duration_until_next_rolls_over_to_next_day() {
+        let now = Local.with_ymd_and_hms(2024, 5, 1, 10, 0, 0).unwrap();
+        let later = NaiveTime::from_hms_opt(12, 30, 0).unwrap();
+        assert_eq!(duration_until_next(now, later), Duration::from_secs(150 * 60));
+
+        let earlier = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        assert_eq!(duration_until_next(now, earlier), Duration::from_secs(23 * 60 * 60));
+    }
+
+    #[test]
+    fn test_health_reports_are_stored_and_pruned() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db = MetadataDatabase::new(db_path.to_str().unwrap()).unwrap();
+
+        let mut old = healthy_report();
+        old.generated_at = "2024-01-01T09:00:00+00:00".to_string();
+        let recent = healthy_report();
+        db.insert_health_report(&old).unwrap();
+        db.insert_health_report(&recent).unwrap();
+
+        let reports = db.list_health_reports(10).unwrap();
+        assert_eq!(reports, vec![recent.clone(), old]);
+
+        assert_eq!(db.prune_health_reports("2024-02-01T00:00:00+00:00").unwrap(), 1);
+        assert_eq!(db.list_health_reports(10).unwrap(), vec![recent]);
+    }
+}
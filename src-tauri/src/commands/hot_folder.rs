@@ -0,0 +1,734 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use notify::EventKind;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter, Manager};
+use tokio::sync::mpsc;
+
+use crate::commands::auto_archive::StabilityTracker;
+use crate::commands::file_operations::{should_exclude_file, validate_file_path, record_watch_event, RenameCompletionTracker, WatchConfig, WatcherActivityGuard};
+use crate::commands::local_file_lock::{is_local_file_locked, unlock_local_file_at};
+use crate::commands::metadata::{metadata_db_path, MetadataDatabase};
+use crate::commands::metadata_jobs::enqueue_metadata_job;
+use crate::commands::mock_aws::is_mock_aws_mode;
+use crate::commands::upload_overrides::apply_type_override;
+use crate::commands::upload_system::{new_upload_item, queue_lock_error, start_queue_processing, UploadQueueState};
+use crate::commands::watch_backend::{start_watch, WatchHandle};
+use crate::internal::i18n::{LocalizedMessage, MessageKey};
+use crate::internal::time::file_time_to_rfc3339;
+use crate::internal::unicode::to_nfc;
+use crate::internal::{AppError, ErrorCode, InternalError};
+
+/// 取り込みフォルダの状態変化をフロントエンドへ通知するイベント名
+pub const HOT_FOLDER_STATUS_EVENT: &str = "hot-folder-status-changed";
+
+/// 書き込み中のファイルを検出するためのサイズ確認間隔
+const STABILITY_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// アップロード後の元ファイルの扱い
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HotFolderAfterUpload {
+    Keep,
+    Delete,
+    /// 取り込みフォルダ外のフォルダへ、フォルダ構成を保ったまま移動する
+    Move { destination: String },
+}
+
+/// 処理済みファイルに対して実際に行った操作
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HotFolderAction {
+    Kept,
+    Deleted,
+    Moved,
+}
+
+impl HotFolderAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HotFolderAction::Kept => "kept",
+            HotFolderAction::Deleted => "deleted",
+            HotFolderAction::Moved => "moved",
+        }
+    }
+}
+
+/// 処理済みファイルの記録（パス・サイズ・更新日時が同じファイルは再取り込みしない）
+#[derive(Debug, Clone, PartialEq)]
+pub struct HotFolderProcessedFile {
+    pub file_path: String,
+    pub file_size: u64,
+    pub modified_at: String,
+    pub watch_path: String,
+    pub s3_key: String,
+    pub action: HotFolderAction,
+    pub processed_at: String,
+}
+
+/// 取り込みフォルダの設定
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HotFolderSettings {
+    pub path: String,
+    pub s3_prefix: String,
+    pub after_upload: HotFolderAfterUpload,
+}
+
+/// 直近のエラー
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HotFolderError {
+    pub message: String,
+    pub file_path: Option<String>,
+    pub occurred_at: String,
+}
+
+/// 取り込みフォルダの状態
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct HotFolderStatus {
+    pub enabled: bool,
+    pub settings: Option<HotFolderSettings>,
+    /// このフォルダで処理済みのファイル数（DBの記録、過去の起動分を含む）
+    pub processed_count: u64,
+    /// 書き込み完了待ちと、キューに追加してアップロードが終わっていないファイルの合計
+    pub pending_count: u64,
+    pub last_error: Option<HotFolderError>,
+}
+
+/// キューに追加したファイル（アップロード後に変更されていないか確認してから削除・移動する）
+struct InFlightFile {
+    file_path: PathBuf,
+    file_size: u64,
+    modified_at: String,
+    watch_path: PathBuf,
+    after_upload: HotFolderAfterUpload,
+}
+
+/// 実行中の取り込みフォルダ（破棄すると監視が止まる）
+struct HotFolderRuntime {
+    _watcher: WatchHandle,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+#[derive(Default)]
+struct HotFolderInner {
+    runtime: Option<HotFolderRuntime>,
+    settings: Option<HotFolderSettings>,
+    processed_count: u64,
+    stabilizing_count: usize,
+    /// キューのアイテムID→ファイル（設定を変更しても追加済みのファイルは追加時の設定で処理する）
+    in_flight: HashMap<String, InFlightFile>,
+    last_error: Option<HotFolderError>,
+}
+
+/// 取り込みフォルダの状態（Tauriのmanage対象）
+#[derive(Default)]
+pub struct HotFolderState(Mutex<HotFolderInner>);
+
+impl HotFolderAfterUpload {
+    /// コマンドの引数から作成（削除と移動は同時に指定できない）
+    pub fn from_options(delete_after_upload: bool, move_to: Option<String>) -> Result<Self, InternalError> {
+        match (delete_after_upload, move_to.filter(|destination| !destination.trim().is_empty())) {
+            (true, Some(_)) => Err(InternalError::Config("delete_after_upload and move_to cannot be used together".to_string())),
+            (true, None) => Ok(HotFolderAfterUpload::Delete),
+            (false, Some(destination)) => Ok(HotFolderAfterUpload::Move { destination }),
+            (false, None) => Ok(HotFolderAfterUpload::Keep),
+        }
+    }
+}
+
+fn open_metadata_db(app: &AppHandle) -> Result<MetadataDatabase, InternalError> {
+    let db_path = metadata_db_path(app)?;
+    MetadataDatabase::new(&db_path.to_string_lossy())
+        .map_err(|e| InternalError::Database(format!("Failed to create database connection: {}", e)))
+}
+
+/// 取り込みフォルダからの相対パスをプレフィックスの下に並べたS3キー
+pub fn hot_folder_s3_key(watch_path: &Path, path: &Path, s3_prefix: &str) -> Result<String, InternalError> {
+    let relative = path.strip_prefix(watch_path)
+        .map_err(|_| InternalError::File(format!("{} is outside the hot folder {}", path.display(), watch_path.display())))?;
+    let relative: Vec<String> = relative.components()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .collect();
+    let prefix = s3_prefix.trim().trim_matches('/');
+    let key = if prefix.is_empty() {
+        relative.join("/")
+    } else {
+        format!("{}/{}", prefix, relative.join("/"))
+    };
+    Ok(to_nfc(&key))
+}
+
+/// 移動先に同名のファイルがあれば「名前 (n).拡張子」にする
+fn unique_destination(destination: &Path, relative: &Path) -> PathBuf {
+    let target = destination.join(relative);
+    if !target.exists() {
+        return target;
+    }
+    let stem = target.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    let extension = target.extension().map(|ext| format!(".{}", ext.to_string_lossy())).unwrap_or_default();
+    let mut n = 1;
+    loop {
+        let candidate = target.with_file_name(format!("{} ({}){}", stem, n, extension));
+        if !candidate.exists() {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// 別ボリュームへの移動はコピーしてから元ファイルを削除する
+fn move_file(source: &Path, target: &Path) -> Result<(), InternalError> {
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if std::fs::rename(source, target).is_err() {
+        std::fs::copy(source, target)?;
+        std::fs::remove_file(source)?;
+    }
+    Ok(())
+}
+
+/// アップロード済みの元ファイルを設定に従って削除・移動する（読み取り専用化していれば先に解除する）
+pub fn apply_after_upload(path: &Path, watch_path: &Path, after_upload: &HotFolderAfterUpload) -> Result<HotFolderAction, InternalError> {
+    if *after_upload == HotFolderAfterUpload::Keep {
+        return Ok(HotFolderAction::Kept);
+    }
+    if is_local_file_locked(path)? {
+        unlock_local_file_at(path)?;
+    }
+    match after_upload {
+        HotFolderAfterUpload::Keep => Ok(HotFolderAction::Kept),
+        HotFolderAfterUpload::Delete => {
+            std::fs::remove_file(path)?;
+            Ok(HotFolderAction::Deleted)
+        }
+        HotFolderAfterUpload::Move { destination } => {
+            let relative = path.strip_prefix(watch_path)
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|_| PathBuf::from(path.file_name().unwrap_or_default()));
+            move_file(path, &unique_destination(Path::new(destination), &relative))?;
+            Ok(HotFolderAction::Moved)
+        }
+    }
+}
+
+/// ファイルの(サイズ, 更新日時)
+fn file_identity(path: &Path) -> Result<(u64, String), InternalError> {
+    let metadata = std::fs::metadata(path)?;
+    Ok((metadata.len(), file_time_to_rfc3339(metadata.modified()).unwrap_or_default()))
+}
+
+/// 設定時点で置かれていたファイル（隠しファイル・リンクは対象外）
+fn scan_existing_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                log::warn!("Skipping hot folder directory {}: {}", dir.display(), e);
+                continue;
+            }
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_hidden = path.file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| n.starts_with('.'))
+                .unwrap_or(true);
+            match entry.file_type() {
+                Ok(file_type) if is_hidden || file_type.is_symlink() => {}
+                Ok(file_type) if file_type.is_dir() => pending.push(path),
+                Ok(_) => files.push(path),
+                Err(e) => log::warn!("Skipping {}: {}", path.display(), e),
+            }
+        }
+    }
+    files
+}
+
+/// 現在の状態（待機中の件数はキューと突き合わせて数える）
+fn current_status(app: &AppHandle) -> HotFolderStatus {
+    let Some(state) = app.try_state::<HotFolderState>() else {
+        return HotFolderStatus::default();
+    };
+    let (mut status, in_flight_ids) = {
+        let inner = state.0.lock().unwrap_or_else(|e| e.into_inner());
+        let status = HotFolderStatus {
+            enabled: inner.runtime.is_some(),
+            settings: inner.settings.clone(),
+            processed_count: inner.processed_count,
+            pending_count: inner.stabilizing_count as u64,
+            last_error: inner.last_error.clone(),
+        };
+        (status, inner.in_flight.keys().cloned().collect::<Vec<_>>())
+    };
+    if let Some(queue_state) = app.try_state::<UploadQueueState>() {
+        if let Ok(queue) = queue_state.lock() {
+            status.pending_count += queue.items.iter()
+                .filter(|item| in_flight_ids.contains(&item.id) && !item.status.is_finished())
+                .count() as u64;
+        }
+    }
+    status
+}
+
+/// 状態をトレイ・フロントエンドへ反映
+fn publish_status(app: &AppHandle) -> HotFolderStatus {
+    let status = current_status(app);
+    crate::tray::update_hot_folder_status(app, &status);
+    if let Err(e) = app.emit(HOT_FOLDER_STATUS_EVENT, &status) {
+        log::error!("Failed to emit hot folder status: {}", e);
+    }
+    status
+}
+
+/// 直近のエラーを記録
+fn record_error(app: &AppHandle, file_path: Option<&Path>, message: impl Into<LocalizedMessage>) {
+    let message = message.into().render();
+    log::warn!("Hot folder error{}: {}", file_path.map(|path| format!(" ({})", path.display())).unwrap_or_default(), message);
+    if let Some(state) = app.try_state::<HotFolderState>() {
+        let mut inner = state.0.lock().unwrap_or_else(|e| e.into_inner());
+        inner.last_error = Some(HotFolderError {
+            message,
+            file_path: file_path.map(|path| path.to_string_lossy().to_string()),
+            occurred_at: chrono::Utc::now().to_rfc3339(),
+        });
+    }
+    publish_status(app);
+}
+
+/// キューに追加し、アップロード後の処理対象として記録する（追加済みで後処理が終わっていないファイルは追加せずfalse）
+///
+/// 再スキャンや、アップロード後のロック・タグ付け・更新日時の変更による監視イベントで同じファイルを二重に追加しない
+fn queue_hot_folder_file(
+    queue_state: &UploadQueueState,
+    state: &HotFolderState,
+    settings: &HotFolderSettings,
+    path: &Path,
+    (file_size, modified_at): (u64, String),
+) -> Result<bool, InternalError> {
+    if state.0.lock().unwrap_or_else(|e| e.into_inner()).in_flight.values().any(|file| file.file_path == path) {
+        return Ok(false);
+    }
+
+    let file_path = path.to_string_lossy().to_string();
+    let watch_path = Path::new(&settings.path);
+    let s3_key = hot_folder_s3_key(watch_path, path, &settings.s3_prefix)?;
+    let item_id = {
+        let mut queue = queue_state.lock().map_err(queue_lock_error)?;
+        let config = queue.config.clone()
+            .ok_or_else(|| InternalError::localized(ErrorCode::Config, MessageKey::UploadQueueNotInitialized))?;
+        queue.check_tier_limits(1)?;
+        let mut item = new_upload_item(&file_path, s3_key)?;
+        apply_type_override(&mut item, &config);
+        let item_id = item.id.clone();
+        queue.items.push(item);
+        item_id
+    };
+    state.0.lock().unwrap_or_else(|e| e.into_inner()).in_flight.insert(item_id, InFlightFile {
+        file_path: path.to_path_buf(),
+        file_size,
+        modified_at,
+        watch_path: watch_path.to_path_buf(),
+        after_upload: settings.after_upload.clone(),
+    });
+    Ok(true)
+}
+
+/// 書き込みが完了したファイルを、未処理であればキューに追加
+fn ingest_file(app: &AppHandle, settings: &HotFolderSettings, path: &Path) -> Result<(), InternalError> {
+    let (file_size, modified_at) = file_identity(path)?;
+    let file_path = path.to_string_lossy().to_string();
+    if open_metadata_db(app)?.is_hot_folder_file_processed(&file_path, file_size, &modified_at)? {
+        log::debug!("Hot folder skipped already processed file: {}", file_path);
+        return Ok(());
+    }
+
+    let queue_state = app.state::<UploadQueueState>();
+    if !queue_hot_folder_file(queue_state.inner(), app.state::<HotFolderState>().inner(), settings, path, (file_size, modified_at))? {
+        log::debug!("Hot folder skipped file already in the upload queue: {}", file_path);
+        return Ok(());
+    }
+    // メタデータは後から作成されるため、アップロードをハッシュ計算の完了まで待たせない
+    enqueue_metadata_job(path);
+    crate::badge::update_badge(app, queue_state.inner());
+
+    // 処理ループが止まっていれば再開
+    start_queue_processing(app, queue_state.inner())?;
+    log::info!("Hot folder queued: {}", file_path);
+    Ok(())
+}
+
+/// 監視イベントを受け取り、安定化待ちの後にキューへ追加する
+async fn run_pipeline(
+    app: AppHandle,
+    settings: HotFolderSettings,
+    watch_config: WatchConfig,
+    mut events: mpsc::UnboundedReceiver<notify::Result<notify::Event>>,
+) {
+    let _activity = WatcherActivityGuard::start();
+    let mut tracker = StabilityTracker::default();
+    let mut rename_tracker = RenameCompletionTracker::from_config(&watch_config);
+    let mut ticker = tokio::time::interval(STABILITY_CHECK_INTERVAL);
+    let mut last_stabilizing = 0;
+
+    // 設定前から置かれていたファイルも取り込む（処理済みかはDBで判定する）
+    for path in scan_existing_files(Path::new(&settings.path)) {
+        if !should_exclude_file(&path, &watch_config) {
+            tracker.observe(path);
+        }
+    }
+
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                match event {
+                    Some(Ok(event)) => {
+                        record_watch_event();
+                        let paths = match rename_tracker.as_mut() {
+                            Some(rename_tracker) => rename_tracker.observe(&event, std::time::Instant::now()),
+                            None if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => event.paths,
+                            None => Vec::new(),
+                        };
+                        for path in paths {
+                            if path.is_file() && !should_exclude_file(&path, &watch_config) {
+                                tracker.observe(path);
+                            }
+                        }
+                    }
+                    Some(Err(e)) => record_error(&app, None, LocalizedMessage::new(MessageKey::HotFolderWatchError).param("error", e)),
+                    None => {
+                        record_error(&app, None, MessageKey::HotFolderWatchStopped);
+                        return;
+                    }
+                }
+            }
+            _ = ticker.tick() => {
+                if let Some(rename_tracker) = rename_tracker.as_mut() {
+                    for path in rename_tracker.flush_expired(std::time::Instant::now()) {
+                        if path.is_file() && !should_exclude_file(&path, &watch_config) {
+                            tracker.observe(path);
+                        }
+                    }
+                }
+                let ready = tracker.poll(|path| std::fs::metadata(path).ok().map(|m| m.len()));
+                let queued = !ready.is_empty();
+                for (path, _) in ready {
+                    if let Err(e) = ingest_file(&app, &settings, &path) {
+                        record_error(&app, Some(&path), LocalizedMessage::new(MessageKey::HotFolderQueueFailed).param("error", e));
+                    }
+                }
+
+                let stabilizing = tracker.pending_count();
+                if let Some(state) = app.try_state::<HotFolderState>() {
+                    state.0.lock().unwrap_or_else(|e| e.into_inner()).stabilizing_count = stabilizing;
+                }
+                if queued || stabilizing != last_stabilizing {
+                    last_stabilizing = stabilizing;
+                    publish_status(&app);
+                }
+            }
+        }
+    }
+}
+
+/// 監視を止める（状態の公開は呼び出し元で行う）
+fn take_runtime(app: &AppHandle) -> Option<HotFolderRuntime> {
+    let state = app.state::<HotFolderState>();
+    let mut inner = state.0.lock().unwrap_or_else(|e| e.into_inner());
+    inner.stabilizing_count = 0;
+    inner.runtime.take()
+}
+
+/// 移動先を作成して正規化する（取り込みフォルダの中は再取り込みになるため不可）
+fn resolve_move_destination(destination: &str, watch_path: &Path) -> Result<String, InternalError> {
+    std::fs::create_dir_all(destination)?;
+    let destination = validate_file_path(&PathBuf::from(destination))?;
+    if destination.starts_with(watch_path) {
+        return Err(InternalError::Config(format!(
+            "Move destination {} must be outside the hot folder {}",
+            destination.display(),
+            watch_path.display()
+        )));
+    }
+    Ok(destination.to_string_lossy().to_string())
+}
+
+/// 監視・安定化・キュー追加・アップロード後の削除/移動を一括で開始
+pub fn start_hot_folder(app: &AppHandle, mut settings: HotFolderSettings) -> Result<HotFolderStatus, InternalError> {
+    let watch_path = validate_file_path(&PathBuf::from(&settings.path))?;
+    if !watch_path.is_dir() {
+        return Err(InternalError::File(format!("Hot folder is not a directory: {}", watch_path.display())));
+    }
+    settings.path = watch_path.to_string_lossy().to_string();
+    if let HotFolderAfterUpload::Move { destination } = &settings.after_upload {
+        settings.after_upload = HotFolderAfterUpload::Move { destination: resolve_move_destination(destination, &watch_path)? };
+    }
+    // アップロード先・認証情報はアップロード画面で初期化したキューの設定を使う
+    {
+        let queue_state = app.state::<UploadQueueState>();
+        let queue = queue_state.lock().map_err(queue_lock_error)?;
+        if queue.config.is_none() {
+            return Err(InternalError::localized(ErrorCode::Config, MessageKey::UploadQueueNotInitialized));
+        }
+    }
+    let processed_count = open_metadata_db(app)?.count_hot_folder_processed(&settings.path)?;
+
+    if let Some(runtime) = take_runtime(app) {
+        runtime.task.abort();
+    }
+    let watch_config = WatchConfig::hot_folder(settings.path.clone());
+    let (tx, rx) = mpsc::unbounded_channel();
+    // NASなどネットワークボリュームでは自動でポーリング監視になる
+    let watcher = start_watch(&watch_path, &watch_config, move |result| {
+        let _ = tx.send(result);
+    })?;
+    let task = tauri::async_runtime::spawn(run_pipeline(app.clone(), settings.clone(), watch_config, rx));
+
+    {
+        let state = app.state::<HotFolderState>();
+        let mut inner = state.0.lock().unwrap_or_else(|e| e.into_inner());
+        inner.runtime = Some(HotFolderRuntime { _watcher: watcher, task });
+        inner.settings = Some(settings);
+        inner.processed_count = processed_count;
+        inner.last_error = None;
+    }
+    log::info!("Hot folder enabled for: {}", watch_path.display());
+    Ok(publish_status(app))
+}
+
+/// 監視を停止する（キューに追加済みのファイルはアップロード後に設定どおり処理する）
+pub fn stop_hot_folder(app: &AppHandle) -> HotFolderStatus {
+    if let Some(runtime) = take_runtime(app) {
+        runtime.task.abort();
+        drop(runtime._watcher);
+        log::info!("Hot folder disabled");
+    }
+    publish_status(app)
+}
+
+/// 動作中の取り込みフォルダの設定（停止中はNone）
+pub fn active_hot_folder_settings(app: &AppHandle) -> Option<HotFolderSettings> {
+    let state = app.try_state::<HotFolderState>()?;
+    let inner = state.0.lock().ok()?;
+    inner.runtime.as_ref().and(inner.settings.clone())
+}
+
+/// 終了時にウォッチャーと安定化待ちのタスクを解放する
+pub fn release_hot_folder_watcher(app: &AppHandle) -> bool {
+    match take_runtime(app) {
+        Some(runtime) => {
+            runtime.task.abort();
+            drop(runtime._watcher);
+            true
+        }
+        None => false,
+    }
+}
+
+/// 検証済みのアップロードを処理済みとして記録し、元ファイルを削除・移動する
+fn finish_in_flight(app: &AppHandle, file: InFlightFile, s3_key: &str, verified: bool) {
+    let action = if !verified {
+        record_error(app, Some(&file.file_path), MessageKey::HotFolderKeptUnverified);
+        HotFolderAction::Kept
+    } else if is_mock_aws_mode() {
+        // モックAWSモードでは実際にはアップロードされていないため元ファイルを残す
+        log::info!("Mock AWS mode: keeping hot folder file {}", file.file_path.display());
+        HotFolderAction::Kept
+    } else if file_identity(&file.file_path).ok() != Some((file.file_size, file.modified_at.clone())) {
+        record_error(app, Some(&file.file_path), MessageKey::HotFolderKeptModified);
+        HotFolderAction::Kept
+    } else {
+        match apply_after_upload(&file.file_path, &file.watch_path, &file.after_upload) {
+            Ok(action) => action,
+            Err(e) => {
+                record_error(app, Some(&file.file_path), LocalizedMessage::new(MessageKey::HotFolderAfterUploadFailed).param("error", e));
+                HotFolderAction::Kept
+            }
+        }
+    };
+
+    let watch_path = file.watch_path.to_string_lossy().to_string();
+    let processed = HotFolderProcessedFile {
+        file_path: file.file_path.to_string_lossy().to_string(),
+        file_size: file.file_size,
+        modified_at: file.modified_at,
+        watch_path: watch_path.clone(),
+        s3_key: s3_key.to_string(),
+        action,
+        processed_at: chrono::Utc::now().to_rfc3339(),
+    };
+    if let Err(e) = open_metadata_db(app).and_then(|db| db.record_hot_folder_processed(&processed).map_err(InternalError::from)) {
+        record_error(app, Some(&file.file_path), LocalizedMessage::new(MessageKey::HotFolderRecordFailed).param("error", e));
+    }
+    log::info!("Hot folder processed {} ({})", processed.file_path, action.as_str());
+
+    if let Some(state) = app.try_state::<HotFolderState>() {
+        let mut inner = state.0.lock().unwrap_or_else(|e| e.into_inner());
+        if inner.settings.as_ref().is_some_and(|settings| settings.path == watch_path) {
+            inner.processed_count += 1;
+        }
+    }
+    publish_status(app);
+}
+
+/// アップロード完了時の後処理（取り込みフォルダから追加したアイテムのみ、削除・移動はブロッキングスレッドで行う）
+///
+/// `verified`がfalseの場合は処理済みとして記録するが元ファイルは残す
+pub fn finish_hot_folder_upload(app: &AppHandle, item_id: &str, s3_key: &str, verified: bool) {
+    let Some(state) = app.try_state::<HotFolderState>() else {
+        return;
+    };
+    let Some(file) = state.0.lock().unwrap_or_else(|e| e.into_inner()).in_flight.remove(item_id) else {
+        return;
+    };
+    let app = app.clone();
+    let s3_key = s3_key.to_string();
+    tauri::async_runtime::spawn_blocking(move || finish_in_flight(&app, file, &s3_key, verified));
+}
+
+/// アップロード失敗を直近のエラーとして記録（再試行で成功すれば後処理を行う）
+pub fn record_hot_folder_upload_failure(app: &AppHandle, item_id: &str, error: &str) {
+    let Some(state) = app.try_state::<HotFolderState>() else {
+        return;
+    };
+    let file_path = state.0.lock().unwrap_or_else(|e| e.into_inner())
+        .in_flight.get(item_id)
+        .map(|file| file.file_path.clone());
+    if let Some(file_path) = file_path {
+        record_error(app, Some(&file_path), LocalizedMessage::new(MessageKey::HotFolderUploadFailed).param("error", error));
+    }
+}
+
+/// 取り込みフォルダを設定して監視を開始
+///
+/// 全ファイル・サブフォルダを対象とし、`delete_after_upload`または`move_to`で検証済みの元ファイルを削除・移動する
+#[command]
+pub async fn configure_hot_folder(
+    app_handle: AppHandle,
+    path: String,
+    s3_prefix: String,
+    delete_after_upload: bool,
+    move_to: Option<String>,
+) -> Result<HotFolderStatus, AppError> {
+    let settings = HotFolderSettings {
+        path,
+        s3_prefix,
+        after_upload: HotFolderAfterUpload::from_options(delete_after_upload, move_to)?,
+    };
+    Ok(start_hot_folder(&app_handle, settings)?)
+}
+
+/// 取り込みフォルダの監視を停止
+#[command]
+pub async fn disable_hot_folder(app_handle: AppHandle) -> Result<HotFolderStatus, AppError> {
+    Ok(stop_hot_folder(&app_handle))
+}
+
+/// 取り込みフォルダの状態を取得
+#[command]
+pub async fn get_hot_folder_status(app_handle: AppHandle) -> Result<HotFolderStatus, AppError> {
+    Ok(current_status(&app_handle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::commands::upload_system::{UploadConfig, UploadQueue};
+
+    #[test]
+    fn test_hot_folder_s3_key_keeps_subfolders_under_prefix() {
+        let root = Path::new("/Users/editor/HotFolder");
+        assert_eq!(
+            hot_folder_s3_key(root, &root.join("DayA/Cam1/A001.mov"), "/archive/incoming/").unwrap(),
+            "archive/incoming/DayA/Cam1/A001.mov"
+        );
+        assert_eq!(hot_folder_s3_key(root, &root.join("clip.mxf"), "").unwrap(), "clip.mxf");
+        assert!(hot_folder_s3_key(root, Path::new("/Users/editor/Other/clip.mxf"), "archive").is_err());
+
+        assert_eq!(HotFolderAfterUpload::from_options(false, Some(" ".to_string())).unwrap(), HotFolderAfterUpload::Keep);
+        assert_eq!(HotFolderAfterUpload::from_options(true, None).unwrap(), HotFolderAfterUpload::Delete);
+        assert!(HotFolderAfterUpload::from_options(true, Some("/Users/editor/Done".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_apply_after_upload_deletes_or_moves_with_structure() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().join("hot");
+        let done = temp_dir.path().join("done");
+        std::fs::create_dir_all(root.join("DayA")).unwrap();
+        std::fs::create_dir_all(done.join("DayA")).unwrap();
+        std::fs::write(done.join("DayA/A001.mov"), b"earlier").unwrap();
+
+        let moved = root.join("DayA/A001.mov");
+        std::fs::write(&moved, b"footage").unwrap();
+        let after = HotFolderAfterUpload::Move { destination: done.to_string_lossy().to_string() };
+        assert_eq!(apply_after_upload(&moved, &root, &after).unwrap(), HotFolderAction::Moved);
+        assert!(!moved.exists());
+        // 移動先に同名があれば連番を付ける
+        assert_eq!(std::fs::read(done.join("DayA/A001 (1).mov")).unwrap(), b"footage");
+
+        let deleted = root.join("B001.mov");
+        std::fs::write(&deleted, b"footage").unwrap();
+        crate::commands::local_file_lock::lock_local_file(&deleted, false).unwrap();
+        assert_eq!(apply_after_upload(&deleted, &root, &HotFolderAfterUpload::Delete).unwrap(), HotFolderAction::Deleted);
+        assert!(!deleted.exists());
+
+        let kept = root.join("C001.mov");
+        std::fs::write(&kept, b"footage").unwrap();
+        assert_eq!(apply_after_upload(&kept, &root, &HotFolderAfterUpload::Keep).unwrap(), HotFolderAction::Kept);
+        assert!(kept.exists());
+    }
+
+    #[test]
+    fn test_file_already_in_flight_is_not_queued_twice() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("A001.mov");
+        std::fs::write(&path, b"footage").unwrap();
+        let mut queue = UploadQueue::new();
+        queue.config = Some(UploadConfig::new("archive-bucket".to_string()));
+        let queue_state: UploadQueueState = Arc::new(std::sync::Mutex::new(queue));
+        let state = HotFolderState::default();
+        let settings = HotFolderSettings {
+            path: temp_dir.path().to_string_lossy().to_string(),
+            s3_prefix: "incoming".to_string(),
+            after_upload: HotFolderAfterUpload::Delete,
+        };
+
+        assert!(queue_hot_folder_file(&queue_state, &state, &settings, &path, file_identity(&path).unwrap()).unwrap());
+        // 再スキャンや更新日時の変更で同じファイルを再び取り込んでもキューには1件だけ
+        std::fs::write(&path, b"footage, touched").unwrap();
+        assert!(!queue_hot_folder_file(&queue_state, &state, &settings, &path, file_identity(&path).unwrap()).unwrap());
+
+        let queue = queue_state.lock().unwrap();
+        assert_eq!(queue.items.len(), 1);
+        assert_eq!(queue.items[0].s3_key, "incoming/A001.mov");
+        assert_eq!(state.0.lock().unwrap().in_flight.len(), 1);
+    }
+
+    #[test]
+    fn test_processed_files_are_recorded_by_content() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db = MetadataDatabase::new(&temp_dir.path().join("metadata.db").to_string_lossy()).unwrap();
+        let processed = HotFolderProcessedFile {
+            file_path: "/Users/editor/HotFolder/A001.mov".to_string(),
+            file_size: 1024,
+            modified_at: "2024-05-01T10:00:00+00:00".to_string(),
+            watch_path: "/Users/editor/HotFolder".to_string(),
+            s3_key: "archive/A001.mov".to_string(),
+            action: HotFolderAction::Deleted,
+            processed_at: "2024-05-01T10:05:00+00:00".to_string(),
+        };
+        db.record_hot_folder_processed(&processed).unwrap();
+
+        assert!(db.is_hot_folder_file_processed(&processed.file_path, 1024, &processed.modified_at).unwrap());
+        // 同じ名前でも内容が変わっていれば新しいファイルとして取り込む
+        assert!(!db.is_hot_folder_file_processed(&processed.file_path, 2048, &processed.modified_at).unwrap());
+        assert_eq!(db.count_hot_folder_processed("/Users/editor/HotFolder").unwrap(), 1);
+        assert_eq!(db.count_hot_folder_processed("/Users/editor/Other").unwrap(), 0);
+    }
+}
@@ -0,0 +1,212 @@
+//! S3互換ストレージ（MinIO / LocalStack）を使った統合テスト
+//!
+//! 通常の`cargo test`では実行されない（`#[ignore]`）。実行手順はDEVELOPER_GUIDE.mdを参照:
+//!
+//! ```sh
+//! docker run -d -p 9000:9000 minio/minio server /data
+//! REELVAULT_TEST_S3_ENDPOINT=http://localhost:9000 cargo test integration_tests -- --ignored
+//! ```
+
+use std::io::Write;
+use tokio::sync::mpsc;
+
+use crate::commands::aws_auth::AwsCredentials;
+use crate::commands::aws_operations::{
+    create_s3_client_with_endpoint, LifecycleRule, LifecycleTransition, RealS3Client, S3ClientTrait,
+};
+use crate::commands::upload_system::{abort_multipart_uploads, upload_file_to_s3_internal, ActiveMultipartUpload, UploadConfig};
+
+/// 接続先エンドポイント（必須）
+const ENDPOINT_ENV: &str = "REELVAULT_TEST_S3_ENDPOINT";
+const ACCESS_KEY_ENV: &str = "REELVAULT_TEST_S3_ACCESS_KEY";
+const SECRET_KEY_ENV: &str = "REELVAULT_TEST_S3_SECRET_KEY";
+const REGION_ENV: &str = "REELVAULT_TEST_S3_REGION";
+
+/// 統合テスト用の接続情報とテストごとのバケット
+struct TestHarness {
+    sdk_client: aws_sdk_s3::Client,
+    bucket: String,
+}
+
+impl TestHarness {
+    /// 環境変数から接続情報を読み込み、テスト専用のバケットを作成
+    async fn setup(name: &str) -> Self {
+        let endpoint = std::env::var(ENDPOINT_ENV).unwrap_or_else(|_| {
+            panic!(
+                "{} is not set. Start MinIO or LocalStack and set the endpoint (e.g. http://localhost:9000)",
+                ENDPOINT_ENV
+            )
+        });
+        let credentials = AwsCredentials {
+            // MinIOの既定の認証情報
+            access_key_id: std::env::var(ACCESS_KEY_ENV).unwrap_or_else(|_| "minioadmin".to_string()),
+            secret_access_key: std::env::var(SECRET_KEY_ENV).unwrap_or_else(|_| "minioadmin".to_string()),
+            region: std::env::var(REGION_ENV).unwrap_or_else(|_| "us-east-1".to_string()),
+            session_token: None,
+        };
+
+        let sdk_client = create_s3_client_with_endpoint(&credentials, Some(&endpoint))
+            .await
+            .expect("Failed to create S3 client");
+
+        let bucket = format!("reelvault-it-{}-{}", name, uuid::Uuid::new_v4().simple());
+        sdk_client
+            .create_bucket()
+            .bucket(&bucket)
+            .send()
+            .await
+            .unwrap_or_else(|e| panic!("Failed to create test bucket {}: {:?}", bucket, e));
+
+        Self { sdk_client, bucket }
+    }
+
+    fn s3_client(&self) -> RealS3Client {
+        RealS3Client::new(self.sdk_client.clone())
+    }
+
+    fn upload_config(&self, chunk_size_mb: u64) -> UploadConfig {
+        let mut config = UploadConfig::new(self.bucket.clone());
+        config.chunk_size_mb = chunk_size_mb;
+        config
+    }
+}
+
+/// 指定サイズのテスト用ファイルを作成
+fn create_test_file(size: usize) -> tempfile::NamedTempFile {
+    let mut file = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+    let data: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+    file.write_all(&data).expect("Failed to write temp file");
+    file
+}
+
+#[tokio::test]
+#[ignore = "requires MinIO/LocalStack (REELVAULT_TEST_S3_ENDPOINT)"]
+async fn test_multipart_upload_completes() {
+    let harness = TestHarness::setup("multipart").await;
+    let s3_client = harness.s3_client();
+    // 5MBチャンクで3パートになるサイズ
+    let file_size = 12 * 1024 * 1024;
+    let file = create_test_file(file_size);
+    let (progress_tx, mut progress_rx) = mpsc::channel(1000);
+
+    let result = upload_file_to_s3_internal(
+        &s3_client,
+        file.path().to_string_lossy().to_string(),
+        "uploads/multipart.bin".to_string(),
+        harness.upload_config(5),
+        progress_tx,
+        "it-multipart".to_string(),
+    )
+    .await;
+    assert!(result.is_ok(), "upload failed: {:?}", result);
+
+    let head = harness
+        .sdk_client
+        .head_object()
+        .bucket(&harness.bucket)
+        .key("uploads/multipart.bin")
+        .send()
+        .await
+        .expect("uploaded object should exist");
+    assert_eq!(head.content_length(), Some(file_size as i64));
+
+    let mut last_uploaded = 0;
+    while let Ok(progress) = progress_rx.try_recv() {
+        last_uploaded = progress.uploaded_bytes;
+    }
+    assert_eq!(last_uploaded, file_size as u64);
+}
+
+#[tokio::test]
+#[ignore = "requires MinIO/LocalStack (REELVAULT_TEST_S3_ENDPOINT)"]
+async fn test_abort_cleans_up_incomplete_parts() {
+    let harness = TestHarness::setup("abort").await;
+    let s3_client = harness.s3_client();
+    let key = "uploads/aborted.bin";
+
+    let upload_id = s3_client
+        .create_multipart_upload(&harness.bucket, key)
+        .await
+        .expect("Failed to create multipart upload");
+    s3_client
+        .upload_part(&harness.bucket, key, &upload_id, 1, vec![0u8; 5 * 1024 * 1024])
+        .await
+        .expect("Failed to upload part");
+
+    let uploads = vec![ActiveMultipartUpload {
+        bucket: harness.bucket.clone(),
+        key: key.to_string(),
+        upload_id,
+    }];
+    assert_eq!(abort_multipart_uploads(&s3_client, &uploads).await, 1);
+
+    let remaining = harness
+        .sdk_client
+        .list_multipart_uploads()
+        .bucket(&harness.bucket)
+        .send()
+        .await
+        .expect("Failed to list multipart uploads");
+    assert!(remaining.uploads().is_empty(), "incomplete uploads remain: {:?}", remaining.uploads());
+}
+
+#[tokio::test]
+#[ignore = "requires MinIO/LocalStack (REELVAULT_TEST_S3_ENDPOINT)"]
+async fn test_list_objects_paginates() {
+    let harness = TestHarness::setup("paging").await;
+    let s3_client = harness.s3_client();
+    // ListObjectsV2の1ページ上限（1000件）を超える件数
+    let object_count = 1001;
+
+    for batch in (0..object_count).collect::<Vec<_>>().chunks(100) {
+        let puts = batch.iter().map(|i| {
+            harness
+                .sdk_client
+                .put_object()
+                .bucket(&harness.bucket)
+                .key(format!("uploads/page/{:04}.txt", i))
+                .body(aws_sdk_s3::primitives::ByteStream::from_static(b"x"))
+                .send()
+        });
+        for result in futures::future::join_all(puts).await {
+            result.expect("Failed to put object");
+        }
+    }
+
+    let objects = s3_client
+        .list_objects(&harness.bucket, Some("uploads/page/"))
+        .await
+        .expect("Failed to list objects");
+    assert_eq!(objects.len(), object_count);
+}
+
+#[tokio::test]
+#[ignore = "requires LocalStack (MinIO does not accept DEEP_ARCHIVE transitions)"]
+async fn test_lifecycle_configuration_round_trip() {
+    let harness = TestHarness::setup("lifecycle").await;
+    let s3_client = harness.s3_client();
+    let rule = LifecycleRule {
+        id: "ReelVault-Default-Auto-Archive".to_string(),
+        status: "Enabled".to_string(),
+        prefix: Some("uploads/".to_string()),
+        transitions: vec![LifecycleTransition {
+            days: 1,
+            storage_class: "DEEP_ARCHIVE".to_string(),
+        }],
+    };
+
+    s3_client
+        .put_bucket_lifecycle_configuration(&harness.bucket, vec![rule])
+        .await
+        .expect("Failed to put lifecycle configuration");
+
+    let rules = s3_client
+        .get_bucket_lifecycle_configuration(&harness.bucket)
+        .await
+        .expect("Failed to get lifecycle configuration");
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0].id, "ReelVault-Default-Auto-Archive");
+    assert_eq!(rules[0].prefix.as_deref(), Some("uploads/"));
+    assert_eq!(rules[0].transitions[0].storage_class, "DEEP_ARCHIVE");
+    assert_eq!(rules[0].transitions[0].days, 1);
+}
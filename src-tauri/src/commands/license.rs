@@ -0,0 +1,484 @@
+use std::path::PathBuf;
+use std::time::Duration;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ring::signature::{UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Manager};
+
+use crate::commands::state_management::AppStateManager;
+use crate::commands::upload_system::{UploadConfig, UploadQueueState, UploadTier};
+use crate::internal::{AppError, ErrorCode, InternalError};
+use crate::internal::i18n::{tr, LocalizedMessage, MessageKey};
+
+/// ライセンストークンの署名検証用公開鍵（Ed25519）
+const LICENSE_PUBLIC_KEY: [u8; 32] = [
+    0x4d, 0x26, 0x20, 0xe8, 0x42, 0x74, 0x62, 0x94, 0x87, 0x2d, 0x68, 0x17, 0x9b, 0xbf, 0x6a, 0x3f,
+    0xae, 0x06, 0xe6, 0xb9, 0x5d, 0xb5, 0xd4, 0x97, 0x68, 0x1b, 0x91, 0x61, 0xae, 0x38, 0xd8, 0xc3,
+];
+/// 有効化したライセンスキーを保存するファイル名（app_data_dir直下）
+const LICENSE_FILE: &str = "license.key";
+/// ライセンスの有効期限を再確認する間隔
+const LICENSE_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// S3マルチパートの最小パートサイズ
+const S3_MIN_PART_SIZE_MB: u64 = 5;
+/// S3マルチパートの最大パートサイズ（5GiB）
+const S3_MAX_PART_SIZE_MB: u64 = 5 * 1024;
+/// 無料版の上限
+const FREE_MAX_QUEUE_FILES: usize = 10;
+const FREE_MAX_TOTAL_SIZE_MB: u64 = 100;
+/// プレミアム版の並列数上限
+const PREMIUM_MAX_CONCURRENT_UPLOADS: usize = 8;
+const PREMIUM_MAX_CONCURRENT_PARTS: usize = 8;
+
+/// ティアごとの機能ゲート
+///
+/// ティアによる制限判定はすべてここを経由する（フロントから渡された設定値は信用しない）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TierPolicy {
+    pub tier: UploadTier,
+    pub max_concurrent_uploads: usize,
+    pub max_concurrent_parts: usize,
+    pub min_chunk_size_mb: u64,
+    pub max_chunk_size_mb: u64,
+    pub adaptive_chunk_size: bool,
+    pub bandwidth_limit: bool,
+    pub resume: bool,
+    /// 標準以外のライフサイクルルール設定（UIでの表示切り替え用）
+    pub custom_lifecycle: bool,
+    /// キューに入れられるファイル数の上限（Noneは無制限）
+    pub max_queue_files: Option<usize>,
+    /// アップロード済みとキュー内の合計サイズの上限（Noneは無制限）
+    pub max_total_size_mb: Option<u64>,
+}
+
+impl TierPolicy {
+    pub fn for_tier(tier: UploadTier) -> Self {
+        match tier {
+            UploadTier::Free => Self {
+                tier,
+                max_concurrent_uploads: 1,
+                max_concurrent_parts: 1,
+                min_chunk_size_mb: S3_MIN_PART_SIZE_MB,
+                max_chunk_size_mb: S3_MIN_PART_SIZE_MB,
+                adaptive_chunk_size: false,
+                bandwidth_limit: false,
+                resume: false,
+                custom_lifecycle: false,
+                max_queue_files: Some(FREE_MAX_QUEUE_FILES),
+                max_total_size_mb: Some(FREE_MAX_TOTAL_SIZE_MB),
+            },
+            UploadTier::Premium => Self {
+                tier,
+                max_concurrent_uploads: PREMIUM_MAX_CONCURRENT_UPLOADS,
+                max_concurrent_parts: PREMIUM_MAX_CONCURRENT_PARTS,
+                min_chunk_size_mb: S3_MIN_PART_SIZE_MB,
+                max_chunk_size_mb: S3_MAX_PART_SIZE_MB,
+                adaptive_chunk_size: true,
+                bandwidth_limit: true,
+                resume: true,
+                custom_lifecycle: true,
+                max_queue_files: None,
+                max_total_size_mb: None,
+            },
+        }
+    }
+
+    /// ティアの上限に合わせてアップロード設定を補正し、補正した項目名を返す
+    pub fn apply(&self, config: &mut UploadConfig) -> Vec<&'static str> {
+        let mut adjusted = Vec::new();
+        config.tier = self.tier;
+
+        let max_concurrent_uploads = config.max_concurrent_uploads.clamp(1, self.max_concurrent_uploads);
+        if max_concurrent_uploads != config.max_concurrent_uploads {
+            config.max_concurrent_uploads = max_concurrent_uploads;
+            adjusted.push("max_concurrent_uploads");
+        }
+        let max_concurrent_parts = config.max_concurrent_parts.clamp(1, self.max_concurrent_parts);
+        if max_concurrent_parts != config.max_concurrent_parts {
+            config.max_concurrent_parts = max_concurrent_parts;
+            adjusted.push("max_concurrent_parts");
+        }
+
+        let min_chunk = config.min_chunk_size_mb.clamp(self.min_chunk_size_mb, self.max_chunk_size_mb);
+        let max_chunk = config.max_chunk_size_mb.clamp(min_chunk, self.max_chunk_size_mb);
+        let chunk = config.chunk_size_mb.clamp(min_chunk, max_chunk);
+        if (min_chunk, max_chunk, chunk) != (config.min_chunk_size_mb, config.max_chunk_size_mb, config.chunk_size_mb) {
+            config.min_chunk_size_mb = min_chunk;
+            config.max_chunk_size_mb = max_chunk;
+            config.chunk_size_mb = chunk;
+            adjusted.push("chunk_size_mb");
+        }
+
+        if config.adaptive_chunk_size && !self.adaptive_chunk_size {
+            config.adaptive_chunk_size = false;
+            adjusted.push("adaptive_chunk_size");
+        }
+        if config.bandwidth_limit_mbps.is_some() && !self.bandwidth_limit {
+            config.bandwidth_limit_mbps = None;
+            adjusted.push("bandwidth_limit_mbps");
+        }
+        if config.enable_resume && !self.resume {
+            config.enable_resume = false;
+            adjusted.push("enable_resume");
+        }
+        adjusted
+    }
+
+    /// 新しいアップロードを開始できるか（設定値とティア上限の小さい方で判定）
+    pub fn check_concurrent_uploads(&self, active: usize, configured_limit: usize) -> Result<(), InternalError> {
+        let limit = configured_limit.min(self.max_concurrent_uploads);
+        if active < limit {
+            return Ok(());
+        }
+        match self.tier {
+            UploadTier::Free => Err(InternalError::localized(
+                ErrorCode::Unknown,
+                LocalizedMessage::new(MessageKey::TierFreeConcurrentLimit).param("active", active),
+            )),
+            UploadTier::Premium => Err(InternalError::localized(
+                ErrorCode::Unknown,
+                LocalizedMessage::new(MessageKey::TierConcurrentLimit).param("active", active).param("limit", limit),
+            )),
+        }
+    }
+
+    /// キューのファイル数・合計サイズの上限チェック
+    pub fn check_queue_limits(&self, total_files: usize, total_size_bytes: u64) -> Result<(), InternalError> {
+        if let Some(max_files) = self.max_queue_files {
+            if total_files > max_files {
+                return Err(InternalError::localized(
+                    ErrorCode::Unknown,
+                    LocalizedMessage::new(MessageKey::TierFreeFileLimit).param("max_files", max_files),
+                ));
+            }
+        }
+        if let Some(max_size_mb) = self.max_total_size_mb {
+            if total_size_bytes / 1024 / 1024 > max_size_mb {
+                return Err(InternalError::localized(
+                    ErrorCode::Unknown,
+                    LocalizedMessage::new(MessageKey::TierFreeSizeLimit).param("max_size_mb", max_size_mb),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// ライセンストークンの内容
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LicenseClaims {
+    pub license_id: String,
+    pub licensee: String,
+    pub tier: UploadTier,
+    pub issued_at: String,
+    /// RFC3339形式の有効期限（Noneは無期限）
+    #[serde(default)]
+    pub expires_at: Option<String>,
+}
+
+/// ライセンスの状態
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseState {
+    Unlicensed,
+    Active,
+    Expired,
+    Invalid,
+}
+
+/// 検証済みのライセンス状況（AppStateに保持する）
+///
+/// 有効なライセンス以外はすべて無料版として扱う
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LicenseStatus {
+    pub state: LicenseState,
+    /// 実際に適用するティア
+    pub tier: UploadTier,
+    pub claims: Option<LicenseClaims>,
+    pub policy: TierPolicy,
+    pub message: Option<String>,
+}
+
+impl Default for LicenseStatus {
+    fn default() -> Self {
+        Self::free(LicenseState::Unlicensed, None, None)
+    }
+}
+
+impl LicenseStatus {
+    fn free(state: LicenseState, claims: Option<LicenseClaims>, message: Option<String>) -> Self {
+        Self {
+            state,
+            tier: UploadTier::Free,
+            claims,
+            policy: TierPolicy::for_tier(UploadTier::Free),
+            message,
+        }
+    }
+
+    fn active(claims: LicenseClaims) -> Self {
+        Self {
+            state: LicenseState::Active,
+            tier: claims.tier,
+            policy: TierPolicy::for_tier(claims.tier),
+            claims: Some(claims),
+            message: None,
+        }
+    }
+
+    /// 有効期限を過ぎていれば無料版へダウングレードし、変化があればtrueを返す
+    pub fn refresh_expiry(&mut self, now: DateTime<Utc>) -> bool {
+        if self.state != LicenseState::Active {
+            return false;
+        }
+        let expired = self.claims.as_ref()
+            .and_then(|claims| claims.expires_at.as_deref())
+            .and_then(|expires_at| DateTime::parse_from_rfc3339(expires_at).ok())
+            .is_some_and(|expires_at| now >= expires_at);
+        if expired {
+            *self = Self::free(
+                LicenseState::Expired,
+                self.claims.take(),
+                Some(tr(MessageKey::LicenseExpiredFallback)),
+            );
+        }
+        expired
+    }
+}
+
+/// ライセンスキー（`base64url(JSON).base64url(署名)`）の署名を検証して内容を取り出す
+pub fn verify_license_key(key: &str, public_key: &[u8]) -> Result<LicenseClaims, InternalError> {
+    let (payload, signature) = key.trim().split_once('.')
+        .ok_or_else(|| InternalError::Config("Malformed license key".to_string()))?;
+    let payload = URL_SAFE_NO_PAD.decode(payload)
+        .map_err(|e| InternalError::Config(format!("Malformed license key: {}", e)))?;
+    let signature = URL_SAFE_NO_PAD.decode(signature)
+        .map_err(|e| InternalError::Config(format!("Malformed license signature: {}", e)))?;
+
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(&payload, &signature)
+        .map_err(|_| InternalError::Config("License key signature is invalid".to_string()))?;
+
+    serde_json::from_slice(&payload)
+        .map_err(|e| InternalError::Config(format!("Invalid license payload: {}", e)))
+}
+
+/// ライセンスキーを検証し、現在時刻での状況を判定
+pub fn evaluate_license_key(key: &str, public_key: &[u8], now: DateTime<Utc>) -> LicenseStatus {
+    match verify_license_key(key, public_key) {
+        Ok(claims) => {
+            let mut status = LicenseStatus::active(claims);
+            status.refresh_expiry(now);
+            status
+        }
+        Err(e) => LicenseStatus::free(LicenseState::Invalid, None, Some(e.to_string())),
+    }
+}
+
+fn license_file(app: &AppHandle) -> Result<PathBuf, InternalError> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| InternalError::Config(format!("Failed to get app data directory: {}", e)))?;
+    std::fs::create_dir_all(&app_data_dir)?;
+    Ok(app_data_dir.join(LICENSE_FILE))
+}
+
+/// 保存済みのライセンスキーを検証（未保存なら無料版）
+fn load_license_status(app: &AppHandle) -> LicenseStatus {
+    let key = match license_file(app).and_then(|path| {
+        if path.exists() { Ok(Some(std::fs::read_to_string(path)?)) } else { Ok(None) }
+    }) {
+        Ok(Some(key)) => key,
+        Ok(None) => return LicenseStatus::default(),
+        Err(e) => {
+            log::error!("Failed to read license key: {}", e);
+            return LicenseStatus::default();
+        }
+    };
+    evaluate_license_key(&key, &LICENSE_PUBLIC_KEY, Utc::now())
+}
+
+/// 初期化済みのアップロード設定へティアの上限を反映
+fn apply_policy_to_upload_queue(app: &AppHandle, policy: &TierPolicy) {
+    let Some(queue_state) = app.try_state::<UploadQueueState>() else { return };
+    let Ok(mut queue) = queue_state.lock() else { return };
+    if let Some(config) = queue.config.as_mut() {
+        let adjusted = policy.apply(config);
+        if !adjusted.is_empty() {
+            log::info!("Upload config adjusted for {:?} tier: {}", policy.tier, adjusted.join(", "));
+        }
+    }
+}
+
+/// ライセンス状況をAppStateへ保存し、ティアが変わった場合はアップロード設定にも反映
+fn apply_license_status(app: &AppHandle, status: LicenseStatus) {
+    let previous_tier = match app.state::<AppStateManager>().lock() {
+        Ok(mut app_state) => std::mem::replace(&mut app_state.license, status.clone()).tier,
+        Err(e) => {
+            log::error!("Failed to lock app state: {}", e);
+            return;
+        }
+    };
+    if previous_tier != status.tier {
+        log::info!("License tier changed: {:?} -> {:?} ({:?})", previous_tier, status.tier, status.state);
+        apply_policy_to_upload_queue(app, &status.policy);
+    }
+}
+
+/// AppStateのライセンス状況を取得（期限切れならその場で無料版へダウングレード）
+pub(crate) fn refresh_license_status(app: &AppHandle) -> LicenseStatus {
+    let Some(state) = app.try_state::<AppStateManager>() else {
+        return LicenseStatus::default();
+    };
+    let (status, downgraded) = match state.lock() {
+        Ok(mut app_state) => {
+            let downgraded = app_state.license.refresh_expiry(Utc::now());
+            (app_state.license.clone(), downgraded)
+        }
+        Err(e) => {
+            log::error!("Failed to lock app state: {}", e);
+            return LicenseStatus::default();
+        }
+    };
+    if downgraded {
+        log::warn!("License expired, downgraded to free tier");
+        apply_policy_to_upload_queue(app, &status.policy);
+    }
+    status
+}
+
+/// 現在適用するティアの機能ゲート
+pub fn current_policy(app: &AppHandle) -> TierPolicy {
+    refresh_license_status(app).policy
+}
+
+/// 起動時に保存済みライセンスを検証し、有効期限の定期確認を開始する
+pub fn initialize_license(app: &AppHandle) {
+    let status = load_license_status(app);
+    log::info!("License status: {:?} ({:?})", status.state, status.tier);
+    apply_license_status(app, status);
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(LICENSE_CHECK_INTERVAL).await;
+            refresh_license_status(&app);
+        }
+    });
+}
+
+/// ライセンスキーを検証して有効化する（有効なキーのみ保存）
+#[command]
+pub async fn activate_license(app: AppHandle, key: String) -> Result<LicenseStatus, AppError> {
+    let key = key.trim();
+    let status = evaluate_license_key(key, &LICENSE_PUBLIC_KEY, Utc::now());
+    if status.state != LicenseState::Active {
+        return Err(match status.message {
+            Some(reason) => InternalError::Config(reason),
+            None => InternalError::localized(ErrorCode::Config, MessageKey::LicenseNotActive),
+        }.into());
+    }
+
+    std::fs::write(license_file(&app)?, key).map_err(InternalError::from)?;
+    log::info!("License activated: {:?}", status.tier);
+    apply_license_status(&app, status.clone());
+    Ok(status)
+}
+
+/// 現在のライセンス状況と機能ゲートを取得
+#[command]
+pub async fn get_license_status(app: AppHandle) -> Result<LicenseStatus, AppError> {
+    Ok(refresh_license_status(&app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    fn generate_key_pair() -> Ed25519KeyPair {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap()
+    }
+
+    fn sign_license(key_pair: &Ed25519KeyPair, expires_at: Option<&str>) -> String {
+        let claims = LicenseClaims {
+            license_id: "lic-001".to_string(),
+            licensee: "studio@example.com".to_string(),
+            tier: UploadTier::Premium,
+            issued_at: "2024-01-01T00:00:00Z".to_string(),
+            expires_at: expires_at.map(str::to_string),
+        };
+        let payload = serde_json::to_vec(&claims).unwrap();
+        let signature = key_pair.sign(&payload);
+        format!("{}.{}", URL_SAFE_NO_PAD.encode(&payload), URL_SAFE_NO_PAD.encode(signature.as_ref()))
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-06-01T00:00:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_valid_license_activates_premium() {
+        let key_pair = generate_key_pair();
+        let key = sign_license(&key_pair, Some("2025-01-01T00:00:00Z"));
+
+        let status = evaluate_license_key(&key, key_pair.public_key().as_ref(), now());
+        assert_eq!(status.state, LicenseState::Active);
+        assert_eq!(status.tier, UploadTier::Premium);
+        assert!(status.policy.resume);
+
+        // 別の鍵で署名されたキー・改ざんされたキーは無料版
+        let other_pair = generate_key_pair();
+        let other = evaluate_license_key(&key, other_pair.public_key().as_ref(), now());
+        assert_eq!(other.state, LicenseState::Invalid);
+        let (_, signature) = key.split_once('.').unwrap();
+        let forged_payload = URL_SAFE_NO_PAD.encode(br#"{"license_id":"x","licensee":"x","tier":"Premium","issued_at":"x"}"#);
+        let forged = evaluate_license_key(&format!("{}.{}", forged_payload, signature), key_pair.public_key().as_ref(), now());
+        assert_eq!(forged.state, LicenseState::Invalid);
+        assert_eq!(forged.tier, UploadTier::Free);
+    }
+
+    #[test]
+    fn test_expired_license_downgrades_to_free() {
+        let key_pair = generate_key_pair();
+        let expired = evaluate_license_key(&sign_license(&key_pair, Some("2024-05-01T00:00:00Z")), key_pair.public_key().as_ref(), now());
+        assert_eq!(expired.state, LicenseState::Expired);
+        assert_eq!(expired.tier, UploadTier::Free);
+
+        let mut status = evaluate_license_key(&sign_license(&key_pair, Some("2024-07-01T00:00:00Z")), key_pair.public_key().as_ref(), now());
+        assert_eq!(status.state, LicenseState::Active);
+        let later = DateTime::parse_from_rfc3339("2024-07-02T00:00:00Z").unwrap().with_timezone(&Utc);
+        assert!(status.refresh_expiry(later));
+        assert_eq!(status.tier, UploadTier::Free);
+        assert!(!status.refresh_expiry(later));
+    }
+
+    #[test]
+    fn test_free_policy_clamps_premium_settings() {
+        let mut config = UploadConfig::new("bucket".to_string());
+        config.max_concurrent_uploads = 4;
+        config.max_concurrent_parts = 8;
+        config.adaptive_chunk_size = true;
+        config.bandwidth_limit_mbps = Some(50.0);
+        config.enable_resume = true;
+
+        let policy = TierPolicy::for_tier(UploadTier::Free);
+        let adjusted = policy.apply(&mut config);
+        assert_eq!(config.tier, UploadTier::Free);
+        assert_eq!((config.max_concurrent_uploads, config.max_concurrent_parts), (1, 1));
+        assert_eq!((config.min_chunk_size_mb, config.max_chunk_size_mb, config.chunk_size_mb), (5, 5, 5));
+        assert!(!config.enable_resume && !config.adaptive_chunk_size);
+        assert!(config.bandwidth_limit_mbps.is_none());
+        assert_eq!(adjusted.len(), 6);
+
+        assert!(policy.check_concurrent_uploads(1, 4).is_err());
+        assert!(policy.check_queue_limits(11, 0).is_err());
+        assert!(TierPolicy::for_tier(UploadTier::Premium).check_queue_limits(11, u64::MAX).is_ok());
+    }
+}
@@ -1,13 +1,35 @@
+use std::time::Duration;
 use serde::Serialize;
-use tauri::command;
-use crate::commands::aws_operations::{S3ClientTrait, RealS3Client, create_s3_client, LifecycleRule, LifecycleTransition};
+use tauri::{command, AppHandle, Emitter, Manager};
+use crate::commands::aws_operations::{create_s3_client_for_credentials, LifecycleRule, LifecycleTransition};
 use crate::commands::aws_auth::{AwsConfig, create_aws_config, AwsCredentials};
-use crate::internal::{InternalError, standardize_error};
+use crate::commands::bucket_security::{inspect_bucket_security, BucketSecurityReport};
+use crate::commands::bucket_region::{check_cross_region_transfer, correct_credentials_region, CrossRegionWarning};
+use crate::commands::credential_cache::{cached_credentials, cached_credentials_in_region};
+use crate::commands::state_management::{AppStateManager, UploadReadinessSnapshot};
+use crate::commands::upload_system::{generate_s3_key, S3KeyConfig, UploadQueueState};
+use crate::internal::{ErrorCode, InternalError, standardize_error};
+use crate::internal::i18n::{tr, LocalizedMessage, MessageKey};
 
 /// ReelVault固定ライフサイクル設定
 const REELVAULT_TRANSITION_DAYS: i32 = 1;  // 1日後移行
 const REELVAULT_RULE_ID: &str = "ReelVault-Default-Auto-Archive";
 const REELVAULT_STORAGE_CLASS: &str = "DEEP_ARCHIVE";
+/// ReelVault標準ルールの対象プレフィックス
+pub const REELVAULT_UPLOAD_PREFIX: &str = "uploads/";
+
+/// アーカイブ移行とみなす移行先ストレージクラス
+pub(crate) const ARCHIVE_STORAGE_CLASSES: &[&str] = &["DEEP_ARCHIVE", "GLACIER"];
+/// 警告に含めるキー例の最大件数
+const MAX_UNCOVERED_KEY_EXAMPLES: usize = 3;
+/// 事前確認でキー生成に使うサンプルファイル名
+const READINESS_SAMPLE_FILE_NAME: &str = "sample.mov";
+/// 安全確認の結果をキャッシュから返す期間（秒）
+const READINESS_CACHE_TTL_SECS: i64 = 300;
+/// バックグラウンドで安全確認を更新する間隔
+const READINESS_REFRESH_INTERVAL: Duration = Duration::from_secs(10 * 60);
+/// 安全確認の結果が悪化したことを通知するイベント名
+pub const UPLOAD_READINESS_CHANGED_EVENT: &str = "upload-readiness-changed";
 
 
 
@@ -39,10 +61,17 @@ pub struct LifecyclePolicyStatus {
 /// - 1日後にDEEP_ARCHIVEに移行
 /// - 128KB以上のファイルのみ（AWS制限）
 #[command]
-pub async fn enable_reelvault_lifecycle(config: AwsConfig) -> Result<LifecyclePolicyResult, String> {
+pub async fn enable_reelvault_lifecycle(app: AppHandle, config: AwsConfig) -> Result<LifecyclePolicyResult, String> {
+    // 認証情報はロック解除済みであること
+    cached_credentials_in_region(&app, &config.region).map_err(standardize_error)?;
+    enable_reelvault_lifecycle_internal(&config).await
+}
+
+/// 内部実装：ReelVault標準ライフサイクルポリシーの有効化
+async fn enable_reelvault_lifecycle_internal(config: &AwsConfig) -> Result<LifecyclePolicyResult, String> {
     // 設定の基本検証
     if config.bucket_name.is_empty() {
-        return Err(standardize_error(InternalError::Config("Bucket name is required".to_string())));
+        return Err(standardize_error(InternalError::localized(ErrorCode::Config, MessageKey::BucketNameRequired)));
     }
 
     // TODO: AWS SDK for Rustを使った実装
@@ -65,10 +94,10 @@ pub async fn enable_reelvault_lifecycle(config: AwsConfig) -> Result<LifecyclePo
 
     Ok(LifecyclePolicyResult {
         success: true,
-        message: format!(
-            "ReelVault lifecycle policy enabled: {} day(s) -> {}",
-            REELVAULT_TRANSITION_DAYS, REELVAULT_STORAGE_CLASS
-        ),
+        message: LocalizedMessage::new(MessageKey::LifecycleEnabled)
+            .param("days", REELVAULT_TRANSITION_DAYS)
+            .param("storage_class", REELVAULT_STORAGE_CLASS)
+            .render(),
         rule_id: REELVAULT_RULE_ID.to_string(),
         transition_days: REELVAULT_TRANSITION_DAYS,
         storage_class: REELVAULT_STORAGE_CLASS.to_string(),
@@ -77,35 +106,25 @@ pub async fn enable_reelvault_lifecycle(config: AwsConfig) -> Result<LifecyclePo
 
 /// 現在のライフサイクルポリシー設定状況を取得
 #[command]
-pub async fn get_lifecycle_status(config: AwsConfig) -> Result<LifecyclePolicyStatus, String> {
+pub async fn get_lifecycle_status(app: AppHandle, config: AwsConfig) -> Result<LifecyclePolicyStatus, String> {
+    // 認証情報はロック解除済みのキャッシュから取得
+    let aws_credentials = cached_credentials_in_region(&app, &config.region).map_err(standardize_error)?;
+    get_lifecycle_status_internal(&config, &aws_credentials).await
+}
+
+/// 内部実装：S3ClientTraitを使ったライフサイクルポリシー設定状況の取得
+async fn get_lifecycle_status_internal(
+    config: &AwsConfig,
+    aws_credentials: &AwsCredentials,
+) -> Result<LifecyclePolicyStatus, String> {
     // 設定の基本検証
     if config.bucket_name.is_empty() {
-        return Err(standardize_error(InternalError::Config("Bucket name is required".to_string())));
+        return Err(standardize_error(InternalError::localized(ErrorCode::Config, MessageKey::BucketNameRequired)));
     }
 
     // S3ClientTraitを使用
-    let aws_credentials = match create_aws_config(&config).await {
-        Ok(aws_config) => AwsCredentials {
-            access_key_id: config.access_key_id.clone(),
-            secret_access_key: config.secret_access_key.clone(),
-            session_token: None,
-            region: config.region.clone(),
-        },
-        Err(e) => {
-            log::error!("Failed to create AWS config: {}", e);
-            return Ok(LifecyclePolicyStatus {
-                enabled: false,
-                rule_id: None,
-                transition_days: None,
-                storage_class: None,
-                prefix: None,
-                error_message: Some(format!("AWS config creation failed: {}", e)),
-            });
-        }
-    };
-    
-    let s3_client = match create_s3_client(&aws_credentials).await {
-        Ok(client) => RealS3Client::new(client),
+    let s3_client = match create_s3_client_for_credentials(aws_credentials).await {
+        Ok(client) => client,
         Err(e) => {
             log::error!("Failed to create S3 client: {}", e);
             return Ok(LifecyclePolicyStatus {
@@ -114,7 +133,7 @@ pub async fn get_lifecycle_status(config: AwsConfig) -> Result<LifecyclePolicySt
                 transition_days: None,
                 storage_class: None,
                 prefix: None,
-                error_message: Some(format!("S3 client creation failed: {}", e)),
+                error_message: Some(LocalizedMessage::new(MessageKey::S3ClientCreationFailed).param("error", e).render()),
             });
         }
     };
@@ -135,7 +154,7 @@ pub async fn get_lifecycle_status(config: AwsConfig) -> Result<LifecyclePolicySt
                 transition_days: None,
                 storage_class: None,
                 prefix: None,
-                error_message: Some(format!("バケットアクセスエラー: {}", e)),
+                error_message: Some(LocalizedMessage::new(MessageKey::LifecycleBucketAccessFailed).param("error", e).render()),
             });
         }
     }
@@ -188,7 +207,7 @@ pub async fn get_lifecycle_status(config: AwsConfig) -> Result<LifecyclePolicySt
                 transition_days: None,
                 storage_class: None,
                 prefix: None,
-                error_message: Some("ReelVault lifecycle rule not found".to_string()),
+                error_message: Some(tr(MessageKey::LifecycleRuleNotFound)),
             })
         }
         Err(e) => {
@@ -199,7 +218,7 @@ pub async fn get_lifecycle_status(config: AwsConfig) -> Result<LifecyclePolicySt
                 transition_days: None,
                 storage_class: None,
                 prefix: None,
-                error_message: Some(format!("ライフサイクル設定取得エラー: {}", e)),
+                error_message: Some(LocalizedMessage::new(MessageKey::LifecycleFetchFailed).param("error", e).render()),
             })
         }
     }
@@ -207,10 +226,17 @@ pub async fn get_lifecycle_status(config: AwsConfig) -> Result<LifecyclePolicySt
 
 /// ライフサイクルポリシーを無効化
 #[command]
-pub async fn disable_lifecycle_policy(config: AwsConfig) -> Result<LifecyclePolicyResult, String> {
+pub async fn disable_lifecycle_policy(app: AppHandle, config: AwsConfig) -> Result<LifecyclePolicyResult, String> {
+    // 認証情報はロック解除済みであること
+    cached_credentials_in_region(&app, &config.region).map_err(standardize_error)?;
+    disable_lifecycle_policy_internal(&config).await
+}
+
+/// 内部実装：ライフサイクルポリシーの無効化
+async fn disable_lifecycle_policy_internal(config: &AwsConfig) -> Result<LifecyclePolicyResult, String> {
     // 設定の基本検証
     if config.bucket_name.is_empty() {
-        return Err(standardize_error(InternalError::Config("Bucket name is required".to_string())));
+        return Err(standardize_error(InternalError::localized(ErrorCode::Config, MessageKey::BucketNameRequired)));
     }
 
     // TODO: AWS SDK for Rustを使った実装
@@ -230,7 +256,7 @@ pub async fn disable_lifecycle_policy(config: AwsConfig) -> Result<LifecyclePoli
 
     Ok(LifecyclePolicyResult {
         success: true,
-        message: "ReelVault lifecycle policy disabled".to_string(),
+        message: tr(MessageKey::LifecycleDisabled),
         rule_id: REELVAULT_RULE_ID.to_string(),
         transition_days: 0,
         storage_class: "STANDARD".to_string(),
@@ -239,31 +265,31 @@ pub async fn disable_lifecycle_policy(config: AwsConfig) -> Result<LifecyclePoli
 
 /// 全ライフサイクルルール一覧を取得（Phase 2準備）
 #[command]
-pub async fn list_lifecycle_rules(config: AwsConfig) -> Result<Vec<LifecycleRule>, String> {
+pub async fn list_lifecycle_rules(app: AppHandle, config: AwsConfig) -> Result<Vec<LifecycleRule>, String> {
+    // 認証情報はロック解除済みのキャッシュから取得
+    let aws_credentials = cached_credentials_in_region(&app, &config.region).map_err(standardize_error)?;
+    list_lifecycle_rules_internal(&config, &aws_credentials).await
+}
+
+/// 内部実装：S3ClientTraitを使った全ライフサイクルルールの取得
+async fn list_lifecycle_rules_internal(
+    config: &AwsConfig,
+    aws_credentials: &AwsCredentials,
+) -> Result<Vec<LifecycleRule>, String> {
     // 設定の基本検証
     if config.bucket_name.is_empty() {
-        return Err(standardize_error(InternalError::Config("Bucket name is required".to_string())));
+        return Err(standardize_error(InternalError::localized(ErrorCode::Config, MessageKey::BucketNameRequired)));
     }
 
     // S3ClientTraitを使用
-    let aws_credentials = match create_aws_config(&config).await {
-        Ok(aws_config) => AwsCredentials {
-            access_key_id: config.access_key_id.clone(),
-            secret_access_key: config.secret_access_key.clone(),
-            session_token: None,
-            region: config.region.clone(),
-        },
-        Err(e) => {
-            log::error!("Failed to create AWS config: {}", e);
-            return Err(standardize_error(InternalError::Config(format!("AWS config creation failed: {}", e))));
-        }
-    };
-    
-    let s3_client = match create_s3_client(&aws_credentials).await {
-        Ok(client) => RealS3Client::new(client),
+    let s3_client = match create_s3_client_for_credentials(aws_credentials).await {
+        Ok(client) => client,
         Err(e) => {
             log::error!("Failed to create S3 client: {}", e);
-            return Err(standardize_error(InternalError::S3(format!("S3 client creation failed: {}", e))));
+            return Err(standardize_error(InternalError::localized(
+                ErrorCode::AwsS3,
+                LocalizedMessage::new(MessageKey::S3ClientCreationFailed).param("error", e),
+            )));
         }
     };
 
@@ -273,29 +299,31 @@ pub async fn list_lifecycle_rules(config: AwsConfig) -> Result<Vec<LifecycleRule
         }
         Err(e) => {
             log::error!("Failed to get lifecycle rules: {}", e);
-            Err(standardize_error(InternalError::S3(format!("Failed to get lifecycle rules: {}", e))))
+            Err(standardize_error(InternalError::localized(
+                ErrorCode::AwsS3,
+                LocalizedMessage::new(MessageKey::LifecycleFetchFailed).param("error", e),
+            )))
         }
     }
 }
 
 /// ライフサイクル設定のバリデーション
 #[command]
-pub async fn validate_lifecycle_config(config: AwsConfig) -> Result<bool, String> {
+pub async fn validate_lifecycle_config(app: AppHandle, config: AwsConfig) -> Result<bool, String> {
+    // 認証情報はロック解除済みであること
+    cached_credentials_in_region(&app, &config.region).map_err(standardize_error)?;
+    validate_lifecycle_config_internal(&config).await
+}
+
+/// 内部実装：ライフサイクル設定のバリデーション
+async fn validate_lifecycle_config_internal(config: &AwsConfig) -> Result<bool, String> {
     // 基本的なバリデーション
     if config.bucket_name.is_empty() {
-        return Err(standardize_error(InternalError::Config("Bucket name is required".to_string())));
-    }
-
-    if config.access_key_id.is_empty() {
-        return Err(standardize_error(InternalError::Config("Access Key ID is required".to_string())));
-    }
-
-    if config.secret_access_key.is_empty() {
-        return Err(standardize_error(InternalError::Config("Secret Access Key is required".to_string())));
+        return Err(standardize_error(InternalError::localized(ErrorCode::Config, MessageKey::BucketNameRequired)));
     }
 
     if config.region.is_empty() {
-        return Err(standardize_error(InternalError::Config("Region is required".to_string())));
+        return Err(standardize_error(InternalError::localized(ErrorCode::Config, MessageKey::AuthRegionRequired)));
     }
 
     // TODO: AWS接続テストとバケット権限チェック
@@ -326,78 +354,236 @@ pub struct UploadReadinessResult {
     pub safe: bool,
     pub message: String,
     pub lifecycle_healthy: bool,
+    /// 生成されるS3キーがライフサイクルルールの対象外の場合の警告
+    pub prefix_warning: Option<LifecyclePrefixWarning>,
+    /// 設定リージョンとバケットのリージョンが異なる場合の警告
+    pub cross_region_warning: Option<CrossRegionWarning>,
+    /// `include_security`を指定した場合のバケットの安全性チェック（safeの判定には含めない）
+    pub bucket_security: Option<BucketSecurityReport>,
 }
 
-/// アップロード前の安全確認
-#[command]
-pub async fn check_upload_readiness(config: AwsConfig) -> Result<UploadReadinessResult, String> {
-    log::info!("Checking upload readiness for bucket: {}", config.bucket_name);
+/// ライフサイクルルールの対象外となるS3キーの警告
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LifecyclePrefixWarning {
+    pub uncovered_count: usize,
+    pub total_count: usize,
+    pub example_keys: Vec<String>,
+    /// アーカイブ移行が有効なルールのプレフィックス（空文字はバケット全体）
+    pub covered_prefixes: Vec<String>,
+    pub message: String,
+}
 
-    // 基本設定チェック
-    if config.bucket_name.is_empty() {
-        return Ok(UploadReadinessResult {
-            safe: false,
-            message: "S3バケット名が設定されていません".to_string(),
-            lifecycle_healthy: false,
-        });
+/// ルールが有効かつアーカイブ系ストレージクラスへ移行する場合、そのプレフィックスを返す
+fn archive_rule_prefix(rule: &LifecycleRule) -> Option<&str> {
+    if rule.status != "Enabled" {
+        return None;
     }
+    let archives = rule.transitions.iter()
+        .any(|t| ARCHIVE_STORAGE_CLASSES.contains(&t.storage_class.as_str()));
+    archives.then(|| rule.prefix.as_deref().unwrap_or(""))
+}
 
-    if config.access_key_id.is_empty() || config.secret_access_key.is_empty() {
-        return Ok(UploadReadinessResult {
-            safe: false,
-            message: "AWS認証情報が不完全です".to_string(),
-            lifecycle_healthy: false,
-        });
+/// S3キーがどのアーカイブ移行ルールにも含まれない場合に警告を作成
+///
+/// 複数ルール・カスタムプレフィックスのいずれかに前方一致すれば対象とみなす
+pub fn find_lifecycle_prefix_warning(rules: &[LifecycleRule], keys: &[String]) -> Option<LifecyclePrefixWarning> {
+    let covered_prefixes: Vec<String> = rules.iter()
+        .filter_map(archive_rule_prefix)
+        .map(str::to_string)
+        .collect();
+
+    let uncovered: Vec<&String> = keys.iter()
+        .filter(|key| !covered_prefixes.iter().any(|prefix| key.starts_with(prefix.as_str())))
+        .collect();
+    if uncovered.is_empty() {
+        return None;
     }
 
-    // AWS設定を作成
-    let aws_credentials = match create_aws_config(&config).await {
-        Ok(aws_config) => AwsCredentials {
-            access_key_id: config.access_key_id.clone(),
-            secret_access_key: config.secret_access_key.clone(),
-            session_token: None,
-            region: config.region.clone(),
-        },
+    let example_keys: Vec<String> = uncovered.iter()
+        .take(MAX_UNCOVERED_KEY_EXAMPLES)
+        .map(|key| key.to_string())
+        .collect();
+    let prefixes_text = if covered_prefixes.is_empty() {
+        tr(MessageKey::LifecyclePrefixNone)
+    } else {
+        covered_prefixes.iter()
+            .map(|p| if p.is_empty() {
+                tr(MessageKey::LifecyclePrefixWholeBucket)
+            } else {
+                LocalizedMessage::new(MessageKey::LifecyclePrefixQuoted).param("prefix", p).render()
+            })
+            .collect::<Vec<_>>()
+            .join(&tr(MessageKey::ListSeparator))
+    };
+    let message = LocalizedMessage::new(MessageKey::LifecyclePrefixUncovered)
+        .param("total", keys.len())
+        .param("uncovered", uncovered.len())
+        .param("examples", example_keys.join(", "))
+        .param("prefixes", prefixes_text)
+        .render();
+
+    Some(LifecyclePrefixWarning {
+        uncovered_count: uncovered.len(),
+        total_count: keys.len(),
+        example_keys,
+        covered_prefixes,
+        message,
+    })
+}
+
+/// バケットのライフサイクルルールを取得し、S3キーが移行対象か確認
+///
+/// ルールを取得できない場合は判定できないため`None`（ルール未設定は全キー対象外として扱う）
+pub async fn check_lifecycle_prefix_coverage(
+    credentials: &AwsCredentials,
+    bucket: &str,
+    keys: &[String],
+) -> Option<LifecyclePrefixWarning> {
+    let s3_client = match create_s3_client_for_credentials(credentials).await {
+        Ok(client) => client,
         Err(e) => {
-            log::error!("Failed to create AWS config: {}", e);
-            return Ok(UploadReadinessResult {
-                safe: false,
-                message: format!("AWS設定の作成に失敗: {}", e),
-                lifecycle_healthy: false,
-            });
+            log::warn!("Skipping lifecycle prefix check, failed to create S3 client: {}", e);
+            return None;
         }
     };
-    
-    let s3_client = match create_s3_client(&aws_credentials).await {
-        Ok(client) => RealS3Client::new(client),
+
+    match s3_client.get_bucket_lifecycle_configuration(bucket).await {
+        Ok(rules) => find_lifecycle_prefix_warning(&rules, keys),
+        Err(e) if e.contains("NoSuchLifecycleConfiguration") => find_lifecycle_prefix_warning(&[], keys),
+        Err(e) => {
+            log::warn!("Skipping lifecycle prefix check for bucket {}: {}", bucket, e);
+            None
+        }
+    }
+}
+
+/// ReelVaultのライフサイクルルールが有効か確認（ライフサイクル設定がない場合は無効とみなす）
+pub async fn is_reelvault_lifecycle_enabled(credentials: &AwsCredentials, bucket: &str) -> Result<bool, String> {
+    let s3_client = create_s3_client_for_credentials(credentials).await?;
+    match s3_client.get_bucket_lifecycle_configuration(bucket).await {
+        Ok(rules) => Ok(has_enabled_reelvault_rule(&rules)),
+        Err(e) if e.contains("NoSuchLifecycleConfiguration") => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// 有効なReelVault標準ルールが含まれるか
+pub fn has_enabled_reelvault_rule(rules: &[LifecycleRule]) -> bool {
+    rules.iter().any(|rule| rule.id == REELVAULT_RULE_ID && rule.status == "Enabled")
+}
+
+/// ReelVault標準ルール（uploads/配下を1日後にDEEP_ARCHIVEへ移行）
+pub fn reelvault_lifecycle_rule() -> LifecycleRule {
+    LifecycleRule {
+        id: REELVAULT_RULE_ID.to_string(),
+        status: "Enabled".to_string(),
+        prefix: Some(REELVAULT_UPLOAD_PREFIX.to_string()),
+        transitions: vec![LifecycleTransition {
+            days: REELVAULT_TRANSITION_DAYS,
+            storage_class: REELVAULT_STORAGE_CLASS.to_string(),
+        }],
+    }
+}
+
+/// 安全確認の結果を作成（ライフサイクルの状態と、生成されるS3キーのプレフィックス確認から判定）
+fn readiness_result(
+    bucket: &str,
+    lifecycle_healthy: bool,
+    lifecycle_rules: &[LifecycleRule],
+    s3_key_config: Option<&S3KeyConfig>,
+) -> UploadReadinessResult {
+    // 生成されるS3キーがライフサイクルルールの対象か確認
+    let prefix_warning = s3_key_config.and_then(|key_config| {
+        match generate_s3_key(READINESS_SAMPLE_FILE_NAME, key_config) {
+            Ok(sample_key) => find_lifecycle_prefix_warning(lifecycle_rules, &[sample_key]),
+            Err(e) => {
+                log::warn!("Failed to generate sample S3 key for readiness check: {}", e);
+                None
+            }
+        }
+    });
+
+    if lifecycle_healthy {
+        let message = match &prefix_warning {
+            Some(warning) => {
+                log::warn!("⚠️ Upload keys fall outside lifecycle rules for bucket: {}", bucket);
+                LocalizedMessage::new(MessageKey::ReadinessReadyWithWarning).param("warning", &warning.message).render()
+            }
+            None => {
+                log::info!("✅ Upload readiness check passed for bucket: {}", bucket);
+                tr(MessageKey::ReadinessReady)
+            }
+        };
+        UploadReadinessResult {
+            safe: true,
+            message,
+            lifecycle_healthy: true,
+            prefix_warning,
+            cross_region_warning: None,
+            bucket_security: None,
+        }
+    } else {
+        log::warn!("⚠️ Upload readiness check failed - lifecycle not configured for bucket: {}", bucket);
+        UploadReadinessResult {
+            safe: false,
+            message: LocalizedMessage::new(MessageKey::ReadinessLifecycleMissing)
+                .param("bucket", bucket)
+                .render(),
+            lifecycle_healthy: false,
+            prefix_warning,
+            cross_region_warning: None,
+            bucket_security: None,
+        }
+    }
+}
+
+/// S3へ問い合わせて安全確認を実行（結果と取得したライフサイクルルールを返す）
+async fn evaluate_upload_readiness(
+    credentials: &AwsCredentials,
+    bucket: &str,
+    s3_key_config: Option<&S3KeyConfig>,
+) -> (UploadReadinessResult, Vec<LifecycleRule>) {
+    let s3_client = match create_s3_client_for_credentials(credentials).await {
+        Ok(client) => client,
         Err(e) => {
             log::error!("Failed to create S3 client: {}", e);
-            return Ok(UploadReadinessResult {
+            return (UploadReadinessResult {
                 safe: false,
-                message: format!("S3クライアントの作成に失敗: {}", e),
+                message: LocalizedMessage::new(MessageKey::S3ClientCreationFailed).param("error", e).render(),
                 lifecycle_healthy: false,
-            });
+                prefix_warning: None,
+                cross_region_warning: None,
+                bucket_security: None,
+            }, Vec::new());
         }
     };
 
     // 1. バケットアクセス確認
-    match s3_client.head_bucket(&config.bucket_name).await {
+    match s3_client.head_bucket(bucket).await {
         Ok(_) => {
-            log::info!("✅ Bucket access confirmed: {}", config.bucket_name);
+            log::info!("✅ Bucket access confirmed: {}", bucket);
         }
         Err(e) => {
             log::warn!("❌ Bucket access check failed: {:?}", e);
-            return Ok(UploadReadinessResult {
+            return (UploadReadinessResult {
                 safe: false,
-                message: format!("バケット「{}」にアクセスできません: {}", config.bucket_name, e),
+                message: LocalizedMessage::new(MessageKey::ReadinessBucketInaccessible)
+                    .param("bucket", bucket)
+                    .param("error", e)
+                    .render(),
                 lifecycle_healthy: false,
-            });
+                prefix_warning: None,
+                cross_region_warning: None,
+                bucket_security: None,
+            }, Vec::new());
         }
     }
 
     // 2. ライフサイクル設定確認
-    let lifecycle_healthy = match s3_client.get_bucket_lifecycle_configuration(&config.bucket_name).await {
+    let mut lifecycle_rules = Vec::new();
+    let lifecycle_healthy = match s3_client.get_bucket_lifecycle_configuration(bucket).await {
         Ok(rules) => {
+            lifecycle_rules = rules.clone();
             // ReelVaultルールが存在するかチェック
             let reelvault_rule = rules.iter().find(|rule| {
                 rule.id == REELVAULT_RULE_ID
@@ -419,7 +605,7 @@ pub async fn check_upload_readiness(config: AwsConfig) -> Result<UploadReadiness
             log::warn!("Error checking lifecycle configuration: {}", error_string);
             
             if error_string.contains("NoSuchLifecycleConfiguration") {
-                log::info!("No lifecycle configuration found for bucket: {} - upload not safe", config.bucket_name);
+                log::info!("No lifecycle configuration found for bucket: {} - upload not safe", bucket);
                 false
             } else {
                 log::error!("Unexpected lifecycle check error: {:?}", e);
@@ -429,23 +615,189 @@ pub async fn check_upload_readiness(config: AwsConfig) -> Result<UploadReadiness
     };
 
     // 3. 結果判定
-    if lifecycle_healthy {
-        log::info!("✅ Upload readiness check passed for bucket: {}", config.bucket_name);
-        Ok(UploadReadinessResult {
-            safe: true,
-            message: "アップロード準備完了。ライフサイクル設定も正常です。".to_string(),
-            lifecycle_healthy: true,
-        })
-    } else {
-        log::warn!("⚠️ Upload readiness check failed - lifecycle not configured for bucket: {}", config.bucket_name);
-        Ok(UploadReadinessResult {
+    let result = readiness_result(bucket, lifecycle_healthy, &lifecycle_rules, s3_key_config);
+    (result, lifecycle_rules)
+}
+
+/// TTL内のキャッシュ済み結果を取得
+fn cached_upload_readiness(app: &AppHandle, bucket: &str) -> Option<UploadReadinessSnapshot> {
+    let app_state = app.try_state::<AppStateManager>()?;
+    let state = app_state.lock().ok()?;
+    state.system_status.upload_readiness.clone().filter(|snapshot| {
+        snapshot.bucket == bucket
+            && snapshot.is_fresh(chrono::Utc::now(), chrono::Duration::seconds(READINESS_CACHE_TTL_SECS))
+    })
+}
+
+/// 最後に確認した結果（TTLを過ぎていても返す）
+pub(crate) fn last_upload_readiness(app: &AppHandle, bucket: &str) -> Option<UploadReadinessSnapshot> {
+    let app_state = app.try_state::<AppStateManager>()?;
+    let state = app_state.lock().ok()?;
+    state.system_status.upload_readiness.clone().filter(|snapshot| snapshot.bucket == bucket)
+}
+
+/// キャッシュ済みの結果から応答を作成（プレフィックス警告は保持したルールで再計算）
+fn readiness_from_snapshot(snapshot: &UploadReadinessSnapshot, s3_key_config: Option<&S3KeyConfig>) -> UploadReadinessResult {
+    if snapshot.lifecycle_healthy {
+        return readiness_result(&snapshot.bucket, true, &snapshot.lifecycle_rules, s3_key_config);
+    }
+    UploadReadinessResult {
+        safe: snapshot.safe,
+        message: snapshot.message.clone(),
+        lifecycle_healthy: snapshot.lifecycle_healthy,
+        prefix_warning: None,
+        cross_region_warning: None,
+        bucket_security: None,
+    }
+}
+
+/// 結果をAppState.system_statusに保存（前回より悪化した場合のみイベントを発行）
+fn store_upload_readiness(app: &AppHandle, bucket: &str, result: &UploadReadinessResult, lifecycle_rules: Vec<LifecycleRule>) {
+    let snapshot = UploadReadinessSnapshot {
+        bucket: bucket.to_string(),
+        safe: result.safe,
+        lifecycle_healthy: result.lifecycle_healthy,
+        message: result.message.clone(),
+        checked_at: chrono::Utc::now().to_rfc3339(),
+        lifecycle_rules,
+    };
+    let Some(app_state) = app.try_state::<AppStateManager>() else {
+        return;
+    };
+    let previous = match app_state.lock() {
+        Ok(mut state) => state.system_status.upload_readiness.replace(snapshot.clone()),
+        Err(e) => {
+            log::warn!("Failed to cache upload readiness: {}", e);
+            return;
+        }
+    };
+
+    if previous.is_some_and(|previous| snapshot.is_worse_than(&previous)) {
+        log::warn!("Upload readiness degraded for bucket {}: {}", bucket, snapshot.message);
+        if let Err(e) = app.emit(UPLOAD_READINESS_CHANGED_EVENT, &snapshot) {
+            log::warn!("Failed to emit upload readiness change: {}", e);
+        }
+    }
+}
+
+/// キャッシュが古い場合（またはforce指定時）のみ再確認した安全確認の結果
+pub(crate) async fn refresh_upload_readiness(
+    app: &AppHandle,
+    credentials: &AwsCredentials,
+    bucket: &str,
+    force: bool,
+) -> UploadReadinessResult {
+    if !force {
+        if let Some(snapshot) = cached_upload_readiness(app, bucket) {
+            return readiness_from_snapshot(&snapshot, None);
+        }
+    }
+    let (result, lifecycle_rules) = evaluate_upload_readiness(credentials, bucket, None).await;
+    store_upload_readiness(app, bucket, &result, lifecycle_rules);
+    result
+}
+
+/// 初期化済みのアップロード設定で安全確認を定期的に更新
+pub fn start_upload_readiness_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(READINESS_REFRESH_INTERVAL).await;
+            let bucket = app.try_state::<UploadQueueState>().and_then(|queue_state| {
+                let queue = queue_state.lock().ok()?;
+                queue.config.as_ref().map(|config| config.bucket_name.clone())
+            });
+            // 認証情報がロック中・期限切れの間は更新しない
+            if let (Some(bucket), Ok(credentials)) = (bucket, cached_credentials(&app)) {
+                refresh_upload_readiness(&app, &credentials, &bucket, true).await;
+            }
+        }
+    });
+}
+
+/// アップロード前の安全確認
+///
+/// `s3_key_config`を指定すると、その設定で生成されるキーがライフサイクルルールの対象かも確認する。
+/// TTL内の結果はキャッシュから返し、`force`で強制的に再確認する。
+/// `include_security`を指定するとバケットの公開設定・暗号化も確認する（キャッシュしない）
+#[command]
+pub async fn check_upload_readiness(
+    app: AppHandle,
+    config: AwsConfig,
+    s3_key_config: Option<S3KeyConfig>,
+    force: Option<bool>,
+    include_security: Option<bool>,
+) -> Result<UploadReadinessResult, String> {
+    log::info!("Checking upload readiness for bucket: {}", config.bucket_name);
+
+    // 基本設定チェック
+    if config.bucket_name.is_empty() {
+        return Ok(UploadReadinessResult {
+            safe: false,
+            message: tr(MessageKey::ReadinessBucketMissing),
+            lifecycle_healthy: false,
+            prefix_warning: None,
+            cross_region_warning: None,
+            bucket_security: None,
+        });
+    }
+
+    // ロック中・期限切れの場合は認証情報が揃っていないものとして扱う
+    let Ok(mut aws_credentials) = cached_credentials_in_region(&app, &config.region) else {
+        return Ok(UploadReadinessResult {
+            safe: false,
+            message: tr(MessageKey::ReadinessCredentialsIncomplete),
+            lifecycle_healthy: false,
+            prefix_warning: None,
+            cross_region_warning: None,
+            bucket_security: None,
+        });
+    };
+    // リージョン自動補正が有効ならバケットのリージョンで確認する（取得結果はキャッシュされる）
+    correct_credentials_region(&app, &mut aws_credentials, &config.bucket_name).await;
+
+    if !force.unwrap_or(false) {
+        if let Some(snapshot) = cached_upload_readiness(&app, &config.bucket_name) {
+            log::debug!("Using cached upload readiness from {}", snapshot.checked_at);
+            let mut result = readiness_from_snapshot(&snapshot, s3_key_config.as_ref());
+            result.cross_region_warning = check_cross_region_transfer(&app, &aws_credentials, &config.bucket_name, None).await;
+            if include_security.unwrap_or(false) {
+                result.bucket_security = readiness_bucket_security(&aws_credentials, &config.bucket_name).await;
+            }
+            return Ok(result);
+        }
+    }
+
+    // AWS設定を作成
+    if let Err(e) = create_aws_config(&aws_credentials).await {
+        log::error!("Failed to create AWS config: {}", e);
+        return Ok(UploadReadinessResult {
             safe: false,
-            message: format!(
-                "ライフサイクル設定に問題があります。バケット「{}」のライフサイクルポリシーが見つかりません。AWS認証タブで「🔄 ライフサイクル再設定」を実行してください。", 
-                config.bucket_name
-            ),
+            message: LocalizedMessage::new(MessageKey::AwsConfigCreationFailed).param("error", e).render(),
             lifecycle_healthy: false,
-        })
+            prefix_warning: None,
+            cross_region_warning: None,
+            bucket_security: None,
+        });
+    }
+
+    let (mut result, lifecycle_rules) =
+        evaluate_upload_readiness(&aws_credentials, &config.bucket_name, s3_key_config.as_ref()).await;
+    store_upload_readiness(&app, &config.bucket_name, &result, lifecycle_rules);
+    result.cross_region_warning = check_cross_region_transfer(&app, &aws_credentials, &config.bucket_name, None).await;
+    if include_security.unwrap_or(false) {
+        result.bucket_security = readiness_bucket_security(&aws_credentials, &config.bucket_name).await;
+    }
+    Ok(result)
+}
+
+/// 安全確認のオプション項目としてのバケットの安全性チェック（クライアントを作れない場合はNone）
+async fn readiness_bucket_security(credentials: &AwsCredentials, bucket: &str) -> Option<BucketSecurityReport> {
+    match create_s3_client_for_credentials(credentials).await {
+        Ok(s3_client) => Some(inspect_bucket_security(s3_client.as_ref(), bucket).await),
+        Err(e) => {
+            log::warn!("Skipping bucket security check for {}: {}", bucket, e);
+            None
+        }
     }
 }
 
@@ -506,6 +858,15 @@ pub async fn check_upload_readiness(config: AwsConfig) -> Result<UploadReadiness
 mod tests {
     use super::*;
 
+    fn test_credentials() -> AwsCredentials {
+        AwsCredentials {
+            access_key_id: "AKIA1234567890".to_string(),
+            secret_access_key: "secret123".to_string(),
+            region: "ap-northeast-1".to_string(),
+            session_token: None,
+        }
+    }
+
     #[test]
     fn test_lifecycle_policy_result_creation() {
         let result = LifecyclePolicyResult {
@@ -620,12 +981,10 @@ mod tests {
     #[tokio::test]
     async fn test_validate_lifecycle_config_valid() {
         let config = AwsConfig {
-            access_key_id: "AKIA1234567890".to_string(),
-            secret_access_key: "secret123".to_string(),
             region: "ap-northeast-1".to_string(),
             bucket_name: "test-bucket".to_string(),
         };
-        let result = validate_lifecycle_config(config).await;
+        let result = validate_lifecycle_config_internal(&config).await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), true);
     }
@@ -634,55 +993,47 @@ mod tests {
     async fn test_validate_lifecycle_config_invalid() {
         // 空のconfigは不正
         let config = AwsConfig {
-            access_key_id: "".to_string(),
-            secret_access_key: "".to_string(),
             region: "".to_string(),
             bucket_name: "".to_string(),
         };
-        let result = validate_lifecycle_config(config).await;
+        let result = validate_lifecycle_config_internal(&config).await;
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert!(err.contains("required"));
+        assert_eq!(err, tr(MessageKey::BucketNameRequired));
     }
 
     #[tokio::test]
     async fn test_enable_reelvault_lifecycle_success() {
         let config = AwsConfig {
-            access_key_id: "AKIA1234567890".to_string(),
-            secret_access_key: "secret123".to_string(),
             region: "ap-northeast-1".to_string(),
             bucket_name: "test-bucket".to_string(),
         };
-        let result = enable_reelvault_lifecycle(config).await;
+        let result = enable_reelvault_lifecycle_internal(&config).await;
         assert!(result.is_ok());
         let policy_result = result.unwrap();
         assert!(policy_result.success);
-        assert!(policy_result.message.contains("enabled"));
+        assert!(policy_result.message.contains(REELVAULT_STORAGE_CLASS));
     }
 
     #[tokio::test]
     async fn test_enable_reelvault_lifecycle_invalid() {
         let config = AwsConfig {
-            access_key_id: "".to_string(),
-            secret_access_key: "".to_string(),
             region: "".to_string(),
             bucket_name: "".to_string(),
         };
-        let result = enable_reelvault_lifecycle(config).await;
+        let result = enable_reelvault_lifecycle_internal(&config).await;
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert!(err.contains("required"));
+        assert_eq!(err, tr(MessageKey::BucketNameRequired));
     }
 
     #[tokio::test]
     async fn test_get_lifecycle_status_success() {
         let config = AwsConfig {
-            access_key_id: "AKIA1234567890".to_string(),
-            secret_access_key: "secret123".to_string(),
             region: "ap-northeast-1".to_string(),
             bucket_name: "test-bucket".to_string(),
         };
-        let result = get_lifecycle_status(config).await;
+        let result = get_lifecycle_status_internal(&config, &test_credentials()).await;
         // モック実装では成功またはエラーメッセージ付きで失敗する可能性がある
         if let Ok(status) = result {
             // 成功した場合、基本的な構造を確認
@@ -697,53 +1048,45 @@ mod tests {
     #[tokio::test]
     async fn test_get_lifecycle_status_invalid() {
         let config = AwsConfig {
-            access_key_id: "".to_string(),
-            secret_access_key: "".to_string(),
             region: "".to_string(),
             bucket_name: "".to_string(),
         };
-        let result = get_lifecycle_status(config).await;
+        let result = get_lifecycle_status_internal(&config, &test_credentials()).await;
         assert!(result.is_err() || result.as_ref().unwrap().error_message.is_some());
     }
 
     #[tokio::test]
     async fn test_disable_lifecycle_policy_success() {
         let config = AwsConfig {
-            access_key_id: "AKIA1234567890".to_string(),
-            secret_access_key: "secret123".to_string(),
             region: "ap-northeast-1".to_string(),
             bucket_name: "test-bucket".to_string(),
         };
-        let result = disable_lifecycle_policy(config).await;
+        let result = disable_lifecycle_policy_internal(&config).await;
         assert!(result.is_ok());
         let policy_result = result.unwrap();
         assert!(policy_result.success);
-        assert!(policy_result.message.contains("disabled"));
+        assert_eq!(policy_result.message, tr(MessageKey::LifecycleDisabled));
     }
 
     #[tokio::test]
     async fn test_disable_lifecycle_policy_invalid() {
         let config = AwsConfig {
-            access_key_id: "".to_string(),
-            secret_access_key: "".to_string(),
             region: "".to_string(),
             bucket_name: "".to_string(),
         };
-        let result = disable_lifecycle_policy(config).await;
+        let result = disable_lifecycle_policy_internal(&config).await;
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert!(err.contains("required"));
+        assert_eq!(err, tr(MessageKey::BucketNameRequired));
     }
 
     #[tokio::test]
     async fn test_list_lifecycle_rules_success() {
         let config = AwsConfig {
-            access_key_id: "AKIA1234567890".to_string(),
-            secret_access_key: "secret123".to_string(),
             region: "ap-northeast-1".to_string(),
             bucket_name: "test-bucket".to_string(),
         };
-        let result = list_lifecycle_rules(config).await;
+        let result = list_lifecycle_rules_internal(&config, &test_credentials()).await;
         // モック実装では成功またはエラーが返る可能性がある
         if let Ok(rules) = result {
             // 成功した場合、配列の長さを確認（0以上）
@@ -758,14 +1101,88 @@ mod tests {
     #[tokio::test]
     async fn test_list_lifecycle_rules_invalid() {
         let config = AwsConfig {
-            access_key_id: "".to_string(),
-            secret_access_key: "".to_string(),
             region: "".to_string(),
             bucket_name: "".to_string(),
         };
-        let result = list_lifecycle_rules(config).await;
+        let result = list_lifecycle_rules_internal(&config, &test_credentials()).await;
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert!(err.contains("required"));
+        assert_eq!(err, tr(MessageKey::BucketNameRequired));
     }
-} 
\ No newline at end of file
+
+    fn archive_rule(id: &str, status: &str, prefix: Option<&str>, storage_class: &str) -> LifecycleRule {
+        LifecycleRule {
+            id: id.to_string(),
+            status: status.to_string(),
+            prefix: prefix.map(str::to_string),
+            transitions: vec![LifecycleTransition { days: 1, storage_class: storage_class.to_string() }],
+        }
+    }
+
+    #[test]
+    fn test_prefix_warning_lists_uncovered_keys() {
+        let rules = vec![archive_rule(REELVAULT_RULE_ID, "Enabled", Some("uploads/"), "DEEP_ARCHIVE")];
+        let keys: Vec<String> = ["uploads/a.mov", "projects/b.mov", "projects/c.mov"]
+            .iter().map(|k| k.to_string()).collect();
+
+        let warning = find_lifecycle_prefix_warning(&rules, &keys).unwrap();
+        assert_eq!(warning.uncovered_count, 2);
+        assert_eq!(warning.total_count, 3);
+        assert_eq!(warning.example_keys, vec!["projects/b.mov", "projects/c.mov"]);
+        assert!(warning.message.contains("アーカイブ移行されず"));
+        assert!(warning.message.contains("projects/b.mov"));
+    }
+
+    #[test]
+    fn test_prefix_warning_considers_all_enabled_archive_rules() {
+        let rules = vec![
+            archive_rule(REELVAULT_RULE_ID, "Enabled", Some("uploads/"), "DEEP_ARCHIVE"),
+            archive_rule("Projects", "Enabled", Some("projects/"), "GLACIER"),
+            archive_rule("Disabled", "Disabled", Some("raw/"), "DEEP_ARCHIVE"),
+            archive_rule("InfrequentAccess", "Enabled", Some("proxies/"), "STANDARD_IA"),
+        ];
+        let covered = vec!["uploads/a.mov".to_string(), "projects/b.mov".to_string()];
+        assert!(find_lifecycle_prefix_warning(&rules, &covered).is_none());
+
+        let uncovered = vec!["raw/c.mov".to_string(), "proxies/d.mov".to_string()];
+        let warning = find_lifecycle_prefix_warning(&rules, &uncovered).unwrap();
+        assert_eq!(warning.uncovered_count, 2);
+        assert_eq!(warning.covered_prefixes, vec!["uploads/", "projects/"]);
+
+        // プレフィックスなしのルールはバケット全体が対象
+        let bucket_wide = vec![archive_rule("All", "Enabled", None, "DEEP_ARCHIVE")];
+        assert!(find_lifecycle_prefix_warning(&bucket_wide, &uncovered).is_none());
+        assert!(find_lifecycle_prefix_warning(&[], &uncovered).is_some());
+    }
+
+    #[test]
+    fn test_readiness_from_snapshot_rechecks_prefix_with_cached_rules() {
+        let snapshot = UploadReadinessSnapshot {
+            bucket: "footage".to_string(),
+            safe: true,
+            lifecycle_healthy: true,
+            message: tr(MessageKey::ReadinessReady),
+            checked_at: chrono::Utc::now().to_rfc3339(),
+            lifecycle_rules: vec![archive_rule(REELVAULT_RULE_ID, "Enabled", Some("uploads/"), "DEEP_ARCHIVE")],
+        };
+        let key_config = |prefix: &str| S3KeyConfig {
+            prefix: Some(prefix.to_string()),
+            use_date_folder: false,
+            preserve_directory_structure: false,
+            custom_naming_pattern: None,
+        };
+
+        let covered = readiness_from_snapshot(&snapshot, Some(&key_config("uploads")));
+        assert!(covered.safe);
+        assert!(covered.prefix_warning.is_none());
+
+        let uncovered = readiness_from_snapshot(&snapshot, Some(&key_config("projects")));
+        assert!(uncovered.safe);
+        assert!(uncovered.prefix_warning.is_some());
+
+        let unhealthy = UploadReadinessSnapshot { safe: false, lifecycle_healthy: false, message: "missing".to_string(), ..snapshot };
+        let result = readiness_from_snapshot(&unhealthy, None);
+        assert!(!result.safe);
+        assert_eq!(result.message, "missing");
+    }
+}
@@ -0,0 +1,165 @@
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+use crate::internal::{AppError, InternalError};
+
+/// 書き込みビット（所有者・グループ・その他）
+#[cfg(unix)]
+const WRITE_BITS: u32 = 0o222;
+/// 解除時に戻す所有者の書き込みビット
+#[cfg(unix)]
+const OWNER_WRITE_BIT: u32 = 0o200;
+
+/// 書き込みビットを落としたパーミッション
+#[cfg(unix)]
+fn readonly_mode(mode: u32) -> u32 {
+    mode & !WRITE_BITS
+}
+
+/// 所有者の書き込みビットを戻したパーミッション
+#[cfg(unix)]
+fn writable_mode(mode: u32) -> u32 {
+    mode | OWNER_WRITE_BIT
+}
+
+#[cfg(unix)]
+fn set_readonly(path: &Path, readonly: bool) -> Result<(), InternalError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = std::fs::metadata(path)?.permissions();
+    let mode = permissions.mode();
+    let updated = if readonly { readonly_mode(mode) } else { writable_mode(mode) };
+    if updated != mode {
+        permissions.set_mode(updated);
+        std::fs::set_permissions(path, permissions)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_readonly(path: &Path, readonly: bool) -> Result<(), InternalError> {
+    let mut permissions = std::fs::metadata(path)?.permissions();
+    if permissions.readonly() != readonly {
+        permissions.set_readonly(readonly);
+        std::fs::set_permissions(path, permissions)?;
+    }
+    Ok(())
+}
+
+/// macOSのユーザー変更不可フラグ（uchg）の操作
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::os::macos::fs::MetadataExt;
+    use std::path::Path;
+    use std::process::Command;
+
+    use crate::internal::InternalError;
+
+    /// sys/stat.hのUF_IMMUTABLE
+    const UF_IMMUTABLE: u32 = 0x0000_0002;
+
+    pub fn has_immutable_flag(path: &Path) -> Result<bool, InternalError> {
+        Ok(std::fs::metadata(path)?.st_flags() & UF_IMMUTABLE != 0)
+    }
+
+    pub fn set_immutable_flag(path: &Path, immutable: bool) -> Result<(), InternalError> {
+        let flag = if immutable { "uchg" } else { "nouchg" };
+        let output = Command::new("chflags")
+            .arg(flag)
+            .arg(path)
+            .output()
+            .map_err(|e| InternalError::Other(format!("Failed to run chflags: {}", e)))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(InternalError::File(format!(
+                "chflags {} failed for {}: {}",
+                flag,
+                path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )))
+        }
+    }
+}
+
+/// macOS以外ではuchgフラグを扱わない
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use std::path::Path;
+
+    use crate::internal::InternalError;
+
+    pub fn has_immutable_flag(_path: &Path) -> Result<bool, InternalError> {
+        Ok(false)
+    }
+
+    pub fn set_immutable_flag(_path: &Path, _immutable: bool) -> Result<(), InternalError> {
+        Ok(())
+    }
+}
+
+/// アップロード済みの原本を読み取り専用にする（`immutable`はmacOSでuchgフラグも付ける）
+pub fn lock_local_file(path: &Path, immutable: bool) -> Result<(), InternalError> {
+    set_readonly(path, true)?;
+    if immutable {
+        platform::set_immutable_flag(path, true)?;
+    }
+    Ok(())
+}
+
+/// 読み取り専用・uchgフラグを解除する（uchgが付いているとパーミッションを変更できないため先に外す）
+pub fn unlock_local_file_at(path: &Path) -> Result<(), InternalError> {
+    if platform::has_immutable_flag(path)? {
+        platform::set_immutable_flag(path, false)?;
+    }
+    set_readonly(path, false)
+}
+
+/// ファイルが読み取り専用またはuchgフラグ付きか
+pub fn is_local_file_locked(path: &Path) -> Result<bool, InternalError> {
+    Ok(std::fs::metadata(path)?.permissions().readonly() || platform::has_immutable_flag(path)?)
+}
+
+/// アップロード後に読み取り専用にした原本を編集できるよう元に戻す
+#[command]
+pub async fn unlock_local_file(path: String) -> Result<(), AppError> {
+    let path = PathBuf::from(path);
+    if !path.is_file() {
+        return Err(InternalError::File(format!("File not found: {}", path.display())).into());
+    }
+    unlock_local_file_at(&path)?;
+    log::info!("Unlocked local file: {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_readonly_and_writable_modes() {
+        assert_eq!(readonly_mode(0o664), 0o444);
+        assert_eq!(readonly_mode(0o755), 0o555);
+        // 解除時は所有者の書き込みのみ戻す
+        assert_eq!(writable_mode(0o444), 0o644);
+        assert_eq!(writable_mode(0o644), 0o644);
+    }
+
+    #[test]
+    fn test_lock_and_unlock_local_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("clip.mov");
+        std::fs::write(&path, b"original").unwrap();
+        assert!(!is_local_file_locked(&path).unwrap());
+
+        lock_local_file(&path, false).unwrap();
+        assert!(is_local_file_locked(&path).unwrap());
+        // 既に読み取り専用でも失敗しない
+        lock_local_file(&path, false).unwrap();
+
+        unlock_local_file_at(&path).unwrap();
+        assert!(!is_local_file_locked(&path).unwrap());
+        std::fs::write(&path, b"edited").unwrap();
+    }
+}
@@ -0,0 +1,167 @@
+use std::path::{Path, PathBuf};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::commands::aws_operations::S3ClientTrait;
+use crate::commands::remote_verify::VerifyTarget;
+use crate::internal::InternalError;
+
+/// アーカイブしたフォルダに書き出すマニフェストのファイル名
+pub const MANIFEST_FILE_NAME: &str = ".reelvault-manifest.json";
+/// マニフェストの形式のバージョン（互換性のない変更で上げる）
+pub const MANIFEST_VERSION: u32 = 1;
+/// マニフェストをアップロードするS3のプレフィックス
+pub const REMOTE_MANIFEST_PREFIX: &str = ".reelvault/manifests/";
+
+/// マニフェストに記録するファイル1件
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifestEntry {
+    /// フォルダからの相対パス
+    pub path: String,
+    pub bucket: String,
+    pub s3_key: String,
+    pub size: u64,
+    /// SHA-256（メタデータ作成前にアップロードした場合はNone）
+    pub sha256: Option<String>,
+    pub uploaded_at: String,
+}
+
+/// フォルダ単位のアーカイブの証跡（.reelvault-manifest.json）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LocalManifest {
+    pub version: u32,
+    pub updated_at: String,
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl Default for LocalManifest {
+    fn default() -> Self {
+        Self { version: MANIFEST_VERSION, updated_at: Utc::now().to_rfc3339(), entries: Vec::new() }
+    }
+}
+
+impl LocalManifest {
+    /// 同じパスの記録は新しいもので置き換え、それ以外は残す
+    pub fn merge(&mut self, entries: Vec<ManifestEntry>) {
+        for entry in entries {
+            match self.entries.iter_mut().find(|existing| existing.path == entry.path) {
+                Some(existing) => *existing = entry,
+                None => self.entries.push(entry),
+            }
+        }
+        self.entries.sort_by(|a, b| a.path.cmp(&b.path));
+        self.version = MANIFEST_VERSION;
+        self.updated_at = Utc::now().to_rfc3339();
+    }
+
+    /// 整合性検証の対象に変換（指定バケットの記録のみ）
+    pub fn verify_targets(&self, folder: &Path, bucket: &str) -> Vec<VerifyTarget> {
+        self.entries.iter()
+            .filter(|entry| entry.bucket == bucket)
+            .map(|entry| VerifyTarget {
+                file_path: folder.join(&entry.path).to_string_lossy().to_string(),
+                s3_key: entry.s3_key.clone(),
+                file_size: entry.size,
+                file_hash: entry.sha256.clone(),
+            })
+            .collect()
+    }
+}
+
+pub fn manifest_path(folder: &Path) -> PathBuf {
+    folder.join(MANIFEST_FILE_NAME)
+}
+
+/// フォルダのマニフェストを読み込む（存在しなければNone）
+pub fn read_manifest(folder: &Path) -> Result<Option<LocalManifest>, InternalError> {
+    let path = manifest_path(folder);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let manifest: LocalManifest = serde_json::from_str(&std::fs::read_to_string(&path)?)
+        .map_err(|e| InternalError::File(format!("Failed to parse manifest {}: {}", path.display(), e)))?;
+    if manifest.version > MANIFEST_VERSION {
+        return Err(InternalError::File(format!(
+            "Manifest {} has unsupported version {} (supported: {})",
+            path.display(), manifest.version, MANIFEST_VERSION
+        )));
+    }
+    Ok(Some(manifest))
+}
+
+/// 既存のマニフェストに追記マージして書き出す（一時ファイル経由で置き換える）
+pub fn write_manifest(folder: &Path, entries: Vec<ManifestEntry>) -> Result<LocalManifest, InternalError> {
+    let mut manifest = read_manifest(folder)?.unwrap_or_default();
+    manifest.merge(entries);
+    let json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| InternalError::Other(format!("Failed to serialize manifest: {}", e)))?;
+    let path = manifest_path(folder);
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, json)?;
+    std::fs::rename(&temp_path, &path)?;
+    Ok(manifest)
+}
+
+/// S3上のマニフェストのキー（同名フォルダが衝突しないようパスのハッシュを付ける）
+pub fn remote_manifest_key(folder: &Path) -> String {
+    let name = folder.file_name().and_then(|n| n.to_str()).unwrap_or("root");
+    let digest = Sha256::digest(folder.to_string_lossy().as_bytes());
+    let hash: String = digest.iter().take(4).map(|byte| format!("{:02x}", byte)).collect();
+    format!("{}{}-{}.json", REMOTE_MANIFEST_PREFIX, name, hash)
+}
+
+/// マニフェストをS3へアップロード
+pub async fn upload_manifest(
+    s3_client: &dyn S3ClientTrait,
+    bucket: &str,
+    folder: &Path,
+    manifest: &LocalManifest,
+) -> Result<String, String> {
+    let key = remote_manifest_key(folder);
+    let json = serde_json::to_vec_pretty(manifest).map_err(|e| e.to_string())?;
+    s3_client.put_object(bucket, &key, json).await?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, size: u64) -> ManifestEntry {
+        ManifestEntry {
+            path: path.to_string(),
+            bucket: "footage".to_string(),
+            s3_key: format!("uploads/{}", path),
+            size,
+            sha256: None,
+            uploaded_at: "2024-05-01T00:00:00+00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_write_manifest_merges_existing_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        write_manifest(dir.path(), vec![entry("b.mov", 1), entry("a.mov", 2)]).unwrap();
+        let manifest = write_manifest(dir.path(), vec![entry("b.mov", 10), entry("c.mov", 3)]).unwrap();
+
+        let sizes: Vec<(&str, u64)> = manifest.entries.iter().map(|e| (e.path.as_str(), e.size)).collect();
+        assert_eq!(sizes, vec![("a.mov", 2), ("b.mov", 10), ("c.mov", 3)]);
+        assert_eq!(read_manifest(dir.path()).unwrap(), Some(manifest.clone()));
+
+        let targets = manifest.verify_targets(dir.path(), "footage");
+        assert_eq!(targets[0].file_path, dir.path().join("a.mov").to_string_lossy());
+        assert!(manifest.verify_targets(dir.path(), "other").is_empty());
+    }
+
+    #[test]
+    fn test_read_manifest_rejects_newer_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = LocalManifest { version: MANIFEST_VERSION + 1, ..LocalManifest::default() };
+        std::fs::write(manifest_path(dir.path()), serde_json::to_string(&manifest).unwrap()).unwrap();
+        assert!(read_manifest(dir.path()).is_err());
+
+        let key = remote_manifest_key(Path::new("/Volumes/Media/DAY01"));
+        assert!(key.starts_with(".reelvault/manifests/DAY01-") && key.ends_with(".json"));
+    }
+}
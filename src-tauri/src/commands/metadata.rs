@@ -7,7 +7,23 @@ use sha2::{Sha256, Digest};
 use std::fs::File;
 use std::io::{BufReader, Read};
 use std::sync::Mutex;
+use crate::commands::auto_upload_guard::{AutoUploadThrottleReason, PendingAutoUpload};
+use crate::commands::bundle::{BundleLookupResult, BundleManifest};
+use crate::commands::audit::{AuditLogFilter, AuditOperation, OperationAuditEntry};
+use crate::commands::health_report::HealthReport;
+use crate::commands::hot_folder::HotFolderProcessedFile;
+use crate::commands::metadata_db_repair::{record_metadata_db_recovery, repair_metadata_db, MetadataDbRecovery};
+use crate::commands::operations::{begin_operation, CancellationToken, OperationKind};
+use crate::commands::remote_verify::{VerifyScope, VerifyTarget};
+use crate::commands::projects::{Project, StorageClassUsage};
+use crate::commands::queue_duplicates::UploadedCopy;
+use crate::commands::restored_files::RestoredFile;
+use crate::commands::s3_cache::{CachedS3Object, S3CacheFilter};
+use crate::commands::aws_operations::{RestoreNotification, S3Object};
+use crate::commands::upload_history::UploadHistoryEntry;
 use crate::internal::{InternalError, standardize_error};
+use crate::internal::unicode::{normalization_variants, to_nfc};
+use crate::internal::time::{file_time_to_rfc3339, legacy_system_time_to_rfc3339};
 
 /// ファイルメタデータを表す構造体
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -23,6 +39,9 @@ pub struct FileMetadata {
     pub video_metadata: Option<VideoMetadata>,
     pub tags: Vec<String>,
     pub custom_fields: HashMap<String, String>,
+    /// 所属するプロジェクト（projectsテーブルのid）
+    #[serde(default)]
+    pub project_id: Option<String>,
 }
 
 /// 動画メタデータを表す構造体
@@ -37,6 +56,34 @@ pub struct VideoMetadata {
     pub format: Option<String>,
 }
 
+/// アップロード済みファイルの記録
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UploadRecord {
+    pub file_path: String,
+    pub bucket: String,
+    pub s3_key: String,
+    pub file_size: u64,
+    pub uploaded_at: String,
+    /// S3上のオブジェクトサイズを確認済み
+    pub verified: bool,
+    /// リテンションでローカル原本を削除・移動した日時
+    pub local_removed_at: Option<String>,
+    /// キューで指定した案件ID・メモ
+    #[serde(default)]
+    pub project_id: Option<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+    /// アップロード検証時のストレージクラス（検証前・旧レコードはNone）
+    #[serde(default)]
+    pub storage_class: Option<String>,
+    /// アップロード時のローカル原本の更新日時（旧レコードはNone）
+    #[serde(default)]
+    pub source_modified_at: Option<String>,
+    /// アップロード時のローカル原本のハッシュ（メタデータ作成済みのファイルのみ）
+    #[serde(default)]
+    pub file_hash: Option<String>,
+}
+
 /// メタデータ検索条件
 #[derive(Debug, Deserialize)]
 pub struct MetadataSearchQuery {
@@ -106,7 +153,235 @@ impl MetadataDatabase {
             [],
         )?;
 
+        // tarバンドル内のファイル目録テーブル
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS bundle_entries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                bundle_key TEXT NOT NULL,
+                entry_path TEXT NOT NULL,
+                source_path TEXT NOT NULL,
+                file_size INTEGER NOT NULL,
+                file_hash TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                UNIQUE (bundle_key, entry_path)
+            )",
+            [],
+        )?;
+
+        // アップロード記録テーブル
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS upload_records (
+                file_path TEXT PRIMARY KEY,
+                bucket TEXT NOT NULL,
+                s3_key TEXT NOT NULL,
+                file_size INTEGER NOT NULL,
+                uploaded_at TEXT NOT NULL,
+                verified INTEGER NOT NULL DEFAULT 0,
+                local_removed_at TEXT
+            )",
+            [],
+        )?;
+
+        // 操作の監査ログテーブル
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS operations_audit (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                operation TEXT NOT NULL,
+                local_path TEXT,
+                bucket TEXT,
+                s3_key TEXT,
+                success INTEGER NOT NULL,
+                size_bytes INTEGER,
+                duration_ms INTEGER,
+                occurred_at TEXT NOT NULL,
+                error_message TEXT
+            )",
+            [],
+        )?;
+
+        // 日次ヘルスレポートの履歴テーブル
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS health_reports (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                generated_at TEXT NOT NULL,
+                healthy INTEGER NOT NULL,
+                report_json TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // S3 APIの日別・種別ごとのリクエスト数
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS s3_api_usage (
+                usage_date TEXT NOT NULL,
+                api TEXT NOT NULL,
+                request_count INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (usage_date, api)
+            )",
+            [],
+        )?;
+
+        // キューからアーカイブした完了・失敗アイテム
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS upload_history (
+                item_id TEXT PRIMARY KEY,
+                project_id TEXT,
+                finished_at TEXT NOT NULL,
+                archived_at TEXT NOT NULL,
+                item_json TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // 素材をまとめるプロジェクト（案件）
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS projects (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                client TEXT,
+                description TEXT,
+                default_s3_prefix TEXT,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // Restoredフォルダへダウンロードした作業コピー（期限が来たらゴミ箱へ移す）
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS restored_files (
+                local_path TEXT PRIMARY KEY,
+                s3_key TEXT NOT NULL,
+                bucket TEXT NOT NULL,
+                downloaded_at TEXT NOT NULL,
+                expires_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // 復元ジョブの完了・失敗通知（削除した通知は再作成されないようdeletedで残す）
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS restore_notifications (
+                id TEXT PRIMARY KEY,
+                s3_key TEXT NOT NULL,
+                status TEXT NOT NULL,
+                message TEXT NOT NULL,
+                timestamp TEXT NOT NULL,
+                read INTEGER NOT NULL DEFAULT 0,
+                deleted INTEGER NOT NULL DEFAULT 0
+            )",
+            [],
+        )?;
+
+        // ウォッチイベント起点の自動アップロードの実績（件数・容量ガードの判定に使う）
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS auto_upload_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                file_path TEXT NOT NULL,
+                file_size INTEGER NOT NULL,
+                queued_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // ガードを超えたためユーザーの承認待ちになっている自動アップロード
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS auto_upload_pending (
+                file_path TEXT PRIMARY KEY,
+                file_size INTEGER NOT NULL,
+                watch_path TEXT NOT NULL,
+                detected_at TEXT NOT NULL,
+                reason TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // S3オブジェクト一覧のキャッシュ（ネットワークなしでの閲覧用）
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS s3_objects_cache (
+                bucket TEXT NOT NULL,
+                s3_key TEXT NOT NULL,
+                size INTEGER NOT NULL,
+                storage_class TEXT NOT NULL,
+                etag TEXT NOT NULL,
+                last_modified TEXT NOT NULL,
+                fetched_at TEXT NOT NULL,
+                PRIMARY KEY (bucket, s3_key)
+            )",
+            [],
+        )?;
+
+        // プレフィックスごとのキャッシュの最終同期日時
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS s3_cache_sync (
+                bucket TEXT NOT NULL,
+                prefix TEXT NOT NULL,
+                synced_at TEXT NOT NULL,
+                PRIMARY KEY (bucket, prefix)
+            )",
+            [],
+        )?;
+
+        // 月別・方向別の転送量（月次上限の判定に使う）
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS transfer_usage (
+                usage_month TEXT NOT NULL,
+                direction TEXT NOT NULL,
+                bytes INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (usage_month, direction)
+            )",
+            [],
+        )?;
+
+        // 転送量の上限を手動で解除した月
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS transfer_cap_overrides (
+                usage_month TEXT PRIMARY KEY,
+                overridden_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // 取り込みフォルダで処理済みのファイル（同じ内容の再取り込みを防ぐ）
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS hot_folder_processed (
+                file_path TEXT NOT NULL,
+                file_size INTEGER NOT NULL,
+                modified_at TEXT NOT NULL,
+                watch_path TEXT NOT NULL,
+                s3_key TEXT NOT NULL,
+                action TEXT NOT NULL,
+                processed_at TEXT NOT NULL,
+                PRIMARY KEY (file_path, file_size, modified_at)
+            )",
+            [],
+        )?;
+
         // インデックス作成
+        self.connection.execute(
+            "CREATE INDEX IF NOT EXISTS idx_auto_upload_log_queued_at ON auto_upload_log(queued_at)",
+            [],
+        )?;
+
+        self.connection.execute(
+            "CREATE INDEX IF NOT EXISTS idx_upload_history_finished_at ON upload_history(finished_at)",
+            [],
+        )?;
+
+        self.connection.execute(
+            "CREATE INDEX IF NOT EXISTS idx_health_reports_generated_at ON health_reports(generated_at)",
+            [],
+        )?;
+
+        self.connection.execute(
+            "CREATE INDEX IF NOT EXISTS idx_operations_audit_occurred_at ON operations_audit(occurred_at)",
+            [],
+        )?;
+
+        self.connection.execute(
+            "CREATE INDEX IF NOT EXISTS idx_bundle_source_path ON bundle_entries(source_path)",
+            [],
+        )?;
+
         self.connection.execute(
             "CREATE INDEX IF NOT EXISTS idx_file_path ON file_metadata(file_path)",
             [],
@@ -117,6 +392,54 @@ impl MetadataDatabase {
             [],
         )?;
 
+        // 案件ID・メモは後から追加した列
+        self.add_column_if_missing("upload_records", "project_id", "TEXT")?;
+        self.add_column_if_missing("upload_records", "note", "TEXT")?;
+        self.add_column_if_missing("operations_audit", "project_id", "TEXT")?;
+        self.add_column_if_missing("file_metadata", "project_id", "TEXT REFERENCES projects(id)")?;
+        self.add_column_if_missing("upload_records", "storage_class", "TEXT")?;
+        self.add_column_if_missing("upload_records", "source_modified_at", "TEXT")?;
+        self.add_column_if_missing("upload_records", "file_hash", "TEXT")?;
+
+        self.migrate_legacy_timestamps()?;
+
+        Ok(())
+    }
+
+    /// 既存のDBに列がなければ追加
+    fn add_column_if_missing(&self, table: &str, column: &str, definition: &str) -> SqliteResult<()> {
+        let exists: bool = self.connection.query_row(
+            &format!("SELECT COUNT(*) > 0 FROM pragma_table_info('{}') WHERE name = ?1", table),
+            [column],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            self.connection.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+        }
+        Ok(())
+    }
+
+    /// SystemTimeのDebug出力で保存された旧レコードの日時をRFC3339へ変換
+    fn migrate_legacy_timestamps(&self) -> SqliteResult<()> {
+        let mut stmt = self.connection.prepare(
+            "SELECT id, created_at, modified_at FROM file_metadata
+             WHERE created_at LIKE 'SystemTime%' OR modified_at LIKE 'SystemTime%'"
+        )?;
+        let rows: Vec<(i64, String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<SqliteResult<_>>()?;
+
+        for (id, created_at, modified_at) in &rows {
+            let created_at = legacy_system_time_to_rfc3339(created_at).unwrap_or_else(|| created_at.clone());
+            let modified_at = legacy_system_time_to_rfc3339(modified_at).unwrap_or_else(|| modified_at.clone());
+            self.connection.execute(
+                "UPDATE file_metadata SET created_at = ?1, modified_at = ?2 WHERE id = ?3",
+                rusqlite::params![created_at, modified_at, id],
+            )?;
+        }
+        if !rows.is_empty() {
+            log::info!("Migrated {} metadata record(s) to RFC3339 timestamps", rows.len());
+        }
         Ok(())
     }
 
@@ -129,15 +452,27 @@ impl MetadataDatabase {
         let custom_fields_json = serde_json::to_string(&metadata.custom_fields)
             .unwrap_or_default();
 
+        // パス・ファイル名はNFCで保存し、NFDで保存済みの旧レコードは置き換え対象にする
+        let file_path = to_nfc(&metadata.file_path);
+        let file_name = to_nfc(&metadata.file_name);
+        for legacy_path in normalization_variants(&metadata.file_path).iter().filter(|p| **p != file_path) {
+            self.connection.execute(
+                "UPDATE OR IGNORE file_metadata SET file_path = ?1 WHERE file_path = ?2",
+                [&file_path, legacy_path],
+            )?;
+        }
+
+        // プロジェクトの指定がなければ既存の割り当てを引き継ぐ
         let mut stmt = self.connection.prepare(
             "INSERT OR REPLACE INTO file_metadata 
-             (file_path, file_name, file_size, file_hash, mime_type, created_at, modified_at, video_metadata, custom_fields)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"
+             (file_path, file_name, file_size, file_hash, mime_type, created_at, modified_at, video_metadata, custom_fields, project_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9,
+                     COALESCE(?10, (SELECT project_id FROM file_metadata WHERE file_path = ?1)))"
         )?;
 
-        stmt.execute([
-            &metadata.file_path,
-            &metadata.file_name,
+        stmt.execute(rusqlite::params![
+            &file_path,
+            &file_name,
             &metadata.file_size.to_string(),
             &metadata.file_hash,
             &metadata.mime_type,
@@ -145,6 +480,7 @@ impl MetadataDatabase {
             &metadata.modified_at,
             &video_metadata_json.unwrap_or_default(),
             &custom_fields_json,
+            &metadata.project_id,
         ])?;
 
         let file_id = self.connection.last_insert_rowid();
@@ -193,8 +529,11 @@ impl MetadataDatabase {
         let mut params: Vec<String> = Vec::new();
 
         if let Some(pattern) = &query.file_name_pattern {
-            sql.push_str(" AND file_name LIKE ?");
-            params.push(format!("%{}%", pattern));
+            // NFC・NFDどちらで保存されたレコードにも一致させる
+            let variants = normalization_variants(pattern);
+            let conditions = vec!["file_name LIKE ?"; variants.len()].join(" OR ");
+            sql.push_str(&format!(" AND ({})", conditions));
+            params.extend(variants.iter().map(|variant| format!("%{}%", variant)));
         }
 
         if let Some(mime_type) = &query.mime_type {
@@ -243,6 +582,7 @@ impl MetadataDatabase {
                     video_metadata,
                     tags,
                     custom_fields,
+                    project_id: row.get(10)?,
                 })
             },
         )?;
@@ -275,8 +615,19 @@ impl MetadataDatabase {
         Ok(tags)
     }
 
-    /// ファイルパスでメタデータを取得
+    /// ファイルパスでメタデータを取得（NFCで見つからなければNFDで保存された旧レコードを探す）
     pub fn get_metadata_by_path(&self, file_path: &str) -> SqliteResult<FileMetadata> {
+        let mut last_error = rusqlite::Error::QueryReturnedNoRows;
+        for variant in normalization_variants(file_path) {
+            match self.get_metadata_by_exact_path(&variant) {
+                Ok(metadata) => return Ok(metadata),
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+
+    fn get_metadata_by_exact_path(&self, file_path: &str) -> SqliteResult<FileMetadata> {
         let mut stmt = self.connection.prepare(
             "SELECT * FROM file_metadata WHERE file_path = ?1"
         )?;
@@ -307,6 +658,7 @@ impl MetadataDatabase {
                 video_metadata,
                 tags,
                 custom_fields,
+                project_id: row.get(10)?,
             })
         })?;
 
@@ -316,11 +668,9 @@ impl MetadataDatabase {
     /// ファイルパスでメタデータを削除
     pub fn delete_metadata(&self, file_path: &str) -> SqliteResult<()> {
         // ファイルIDを取得
-        let file_id: i64 = self.connection.query_row(
-            "SELECT id FROM file_metadata WHERE file_path = ?1",
-            [file_path],
-            |row| row.get(0),
-        )?;
+        let file_id = self.get_metadata_by_path(file_path)?
+            .id
+            .ok_or(rusqlite::Error::QueryReturnedNoRows)?;
 
         // タグ関連を削除
         self.connection.execute(
@@ -337,6 +687,823 @@ impl MetadataDatabase {
         Ok(())
     }
 
+    /// バンドルの目録を保存
+    pub fn save_bundle_manifest(&self, manifest: &BundleManifest) -> SqliteResult<()> {
+        for entry in &manifest.entries {
+            self.connection.execute(
+                "INSERT OR REPLACE INTO bundle_entries 
+                 (bundle_key, entry_path, source_path, file_size, file_hash, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                rusqlite::params![
+                    manifest.bundle_key,
+                    entry.path,
+                    entry.source_path,
+                    entry.file_size as i64,
+                    entry.file_hash,
+                    manifest.created_at,
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// ファイルパス・ファイル名（部分一致）から格納先のバンドルを検索
+    pub fn find_bundle_entries(&self, pattern: &str) -> SqliteResult<Vec<BundleLookupResult>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT bundle_key, entry_path, source_path, file_size, file_hash, created_at
+             FROM bundle_entries
+             WHERE source_path LIKE ?1 OR entry_path LIKE ?1
+             ORDER BY created_at DESC, entry_path"
+        )?;
+
+        let rows = stmt.query_map([format!("%{}%", pattern)], |row| {
+            Ok(BundleLookupResult {
+                bundle_key: row.get(0)?,
+                entry_path: row.get(1)?,
+                source_path: row.get(2)?,
+                file_size: row.get::<_, i64>(3)? as u64,
+                file_hash: row.get(4)?,
+                created_at: row.get(5)?,
+            })
+        })?;
+
+        rows.collect()
+    }
+
+    /// アップロード完了を記録（再アップロード時は上書き）
+    pub fn record_upload(&self, record: &UploadRecord) -> SqliteResult<()> {
+        self.connection.execute(
+            "INSERT OR REPLACE INTO upload_records
+             (file_path, bucket, s3_key, file_size, uploaded_at, verified, local_removed_at, project_id, note, storage_class, source_modified_at, file_hash)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            rusqlite::params![
+                to_nfc(&record.file_path),
+                record.bucket,
+                record.s3_key,
+                record.file_size as i64,
+                record.uploaded_at,
+                record.verified,
+                record.local_removed_at,
+                record.project_id,
+                record.note,
+                record.storage_class,
+                record.source_modified_at,
+                record.file_hash,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// 整合性検証の対象となるアップロード記録を取得（ハッシュはメタデータから補完）
+    pub fn list_verification_targets(&self, bucket: &str, scope: &VerifyScope) -> SqliteResult<Vec<VerifyTarget>> {
+        let mut sql = "SELECT r.file_path, r.s3_key, r.file_size, m.file_hash
+                       FROM upload_records r
+                       LEFT JOIN file_metadata m ON m.file_path = r.file_path
+                       WHERE r.bucket = ?".to_string();
+        let mut params: Vec<String> = vec![bucket.to_string()];
+
+        match scope {
+            VerifyScope::All => {}
+            VerifyScope::Tag { tag } => {
+                sql.push_str(" AND EXISTS (SELECT 1 FROM file_tags ft JOIN tags t ON t.id = ft.tag_id
+                               WHERE ft.file_id = m.id AND t.name = ?)");
+                params.push(tag.clone());
+            }
+            VerifyScope::Period { from, to } => {
+                if let Some(from) = from {
+                    sql.push_str(" AND r.uploaded_at >= ?");
+                    params.push(from.clone());
+                }
+                if let Some(to) = to {
+                    sql.push_str(" AND r.uploaded_at <= ?");
+                    params.push(to.clone());
+                }
+            }
+            // マニフェストがない場合はフォルダ直下のアップロード記録を対象にする
+            VerifyScope::Manifest { folder } => {
+                let folder = folder.trim_end_matches('/');
+                sql.push_str(" AND r.file_path LIKE ? ESCAPE '\\' AND r.file_path NOT LIKE ? ESCAPE '\\'");
+                let escaped = folder.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+                params.push(format!("{}/%", escaped));
+                params.push(format!("{}/%/%", escaped));
+            }
+        }
+        sql.push_str(" ORDER BY r.uploaded_at");
+
+        let mut stmt = self.connection.prepare(&sql)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(params.iter()), |row| {
+            Ok(VerifyTarget {
+                file_path: row.get(0)?,
+                s3_key: row.get(1)?,
+                file_size: row.get::<_, i64>(2)? as u64,
+                file_hash: row.get(3)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// 同じハッシュのファイルのアップロード記録（最新のもの、メタデータ未作成のファイルは対象外）
+    pub fn find_uploaded_copy(&self, file_hash: &str) -> SqliteResult<Option<UploadedCopy>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT r.file_path, r.bucket, r.s3_key, r.uploaded_at
+             FROM upload_records r
+             JOIN file_metadata m ON m.file_path = r.file_path
+             WHERE m.file_hash = ?1
+             ORDER BY r.uploaded_at DESC LIMIT 1"
+        )?;
+        let mut rows = stmt.query_map([file_hash], |row| {
+            Ok(UploadedCopy {
+                file_path: row.get(0)?,
+                bucket: row.get(1)?,
+                s3_key: row.get(2)?,
+                uploaded_at: row.get(3)?,
+            })
+        })?;
+        rows.next().transpose()
+    }
+
+    /// 指定日時以前にアップロード・検証され、ローカル原本が残っている記録を取得
+    pub fn find_retention_candidates(&self, uploaded_before: &str) -> SqliteResult<Vec<UploadRecord>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT file_path, bucket, s3_key, file_size, uploaded_at, verified, local_removed_at, project_id, note, storage_class, source_modified_at, file_hash
+             FROM upload_records
+             WHERE verified = 1 AND local_removed_at IS NULL AND uploaded_at <= ?1
+             ORDER BY uploaded_at"
+        )?;
+
+        let rows = stmt.query_map([uploaded_before], |row| {
+            Ok(UploadRecord {
+                file_path: row.get(0)?,
+                bucket: row.get(1)?,
+                s3_key: row.get(2)?,
+                file_size: row.get::<_, i64>(3)? as u64,
+                uploaded_at: row.get(4)?,
+                verified: row.get(5)?,
+                local_removed_at: row.get(6)?,
+                project_id: row.get(7)?,
+                note: row.get(8)?,
+                storage_class: row.get(9)?,
+                source_modified_at: row.get(10)?,
+                file_hash: row.get(11)?,
+            })
+        })?;
+
+        rows.collect()
+    }
+
+    /// ローカル原本を削除・移動したことを記録
+    pub fn mark_local_removed(&self, file_path: &str, removed_at: &str) -> SqliteResult<()> {
+        for variant in normalization_variants(file_path) {
+            self.connection.execute(
+                "UPDATE upload_records SET local_removed_at = ?2 WHERE file_path = ?1",
+                [variant.as_str(), removed_at],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// 操作の監査記録を追加
+    pub fn insert_operation_audit(&self, entry: &OperationAuditEntry) -> SqliteResult<i64> {
+        self.connection.execute(
+            "INSERT INTO operations_audit
+             (operation, local_path, bucket, s3_key, success, size_bytes, duration_ms, occurred_at, error_message, project_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            rusqlite::params![
+                entry.operation.as_str(),
+                entry.local_path,
+                entry.bucket,
+                entry.s3_key,
+                entry.success,
+                entry.size_bytes.map(|s| s as i64),
+                entry.duration_ms.map(|d| d as i64),
+                entry.occurred_at,
+                entry.error_message,
+                entry.project_id,
+            ],
+        )?;
+        Ok(self.connection.last_insert_rowid())
+    }
+
+    /// 条件に一致する操作の監査記録を新しい順に取得
+    pub fn query_operation_audit(
+        &self,
+        filter: &AuditLogFilter,
+        limit: usize,
+        offset: usize,
+    ) -> SqliteResult<Vec<OperationAuditEntry>> {
+        let mut sql = "SELECT id, operation, local_path, bucket, s3_key, success, size_bytes, duration_ms, occurred_at, error_message, project_id
+                       FROM operations_audit WHERE 1=1".to_string();
+        let mut params: Vec<String> = Vec::new();
+
+        if let Some(operation) = &filter.operation {
+            sql.push_str(" AND operation = ?");
+            params.push(operation.as_str().to_string());
+        }
+
+        if let Some(success) = filter.success {
+            sql.push_str(" AND success = ?");
+            params.push(if success { "1" } else { "0" }.to_string());
+        }
+
+        if let Some(pattern) = &filter.path_contains {
+            sql.push_str(" AND (local_path LIKE ? OR s3_key LIKE ?)");
+            params.push(format!("%{}%", pattern));
+            params.push(format!("%{}%", pattern));
+        }
+
+        if let Some(since) = &filter.since {
+            sql.push_str(" AND occurred_at >= ?");
+            params.push(since.clone());
+        }
+
+        if let Some(until) = &filter.until {
+            sql.push_str(" AND occurred_at <= ?");
+            params.push(until.clone());
+        }
+
+        sql.push_str(&format!(
+            " ORDER BY id DESC LIMIT {} OFFSET {}",
+            limit.min(i64::MAX as usize),
+            offset
+        ));
+
+        let mut stmt = self.connection.prepare(&sql)?;
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(params.iter()),
+            |row| {
+                let operation: String = row.get(1)?;
+                Ok(OperationAuditEntry {
+                    id: Some(row.get(0)?),
+                    operation: AuditOperation::from_db(&operation).ok_or_else(|| {
+                        rusqlite::Error::FromSqlConversionFailure(
+                            1,
+                            rusqlite::types::Type::Text,
+                            format!("unknown audit operation: {}", operation).into(),
+                        )
+                    })?,
+                    local_path: row.get(2)?,
+                    bucket: row.get(3)?,
+                    s3_key: row.get(4)?,
+                    success: row.get(5)?,
+                    size_bytes: row.get::<_, Option<i64>>(6)?.map(|s| s as u64),
+                    duration_ms: row.get::<_, Option<i64>>(7)?.map(|d| d as u64),
+                    occurred_at: row.get(8)?,
+                    error_message: row.get(9)?,
+                    project_id: row.get(10)?,
+                })
+            },
+        )?;
+
+        rows.collect()
+    }
+
+    /// ヘルスレポートを履歴へ追加
+    pub fn insert_health_report(&self, report: &HealthReport) -> SqliteResult<()> {
+        let report_json = serde_json::to_string(report)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        self.connection.execute(
+            "INSERT INTO health_reports (generated_at, healthy, report_json) VALUES (?1, ?2, ?3)",
+            rusqlite::params![report.generated_at, report.healthy, report_json],
+        )?;
+        Ok(())
+    }
+
+    /// ヘルスレポートを新しい順に取得
+    pub fn list_health_reports(&self, limit: usize) -> SqliteResult<Vec<HealthReport>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT report_json FROM health_reports ORDER BY generated_at DESC, id DESC LIMIT ?1"
+        )?;
+
+        let rows = stmt.query_map([limit as i64], |row| {
+            let report_json: String = row.get(0)?;
+            serde_json::from_str(&report_json).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+            })
+        })?;
+
+        rows.collect()
+    }
+
+    /// 指定日時より前のヘルスレポートを削除（削除件数を返す）
+    pub fn prune_health_reports(&self, generated_before: &str) -> SqliteResult<usize> {
+        self.connection.execute(
+            "DELETE FROM health_reports WHERE generated_at < ?1",
+            [generated_before],
+        )
+    }
+
+    /// キューから取り出したアイテムを履歴へ追加（まとめて1トランザクションで書き込む）
+    pub fn insert_upload_history(&self, entries: &[UploadHistoryEntry]) -> SqliteResult<usize> {
+        let tx = self.connection.unchecked_transaction()?;
+        for entry in entries {
+            let item_json = serde_json::to_string(&entry.item)
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+            tx.execute(
+                "INSERT OR REPLACE INTO upload_history (item_id, project_id, finished_at, archived_at, item_json)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                rusqlite::params![entry.item.id, entry.item.project_id, entry.finished_at, entry.archived_at, item_json],
+            )?;
+        }
+        tx.commit()?;
+        Ok(entries.len())
+    }
+
+    /// アップロード履歴を新しい順に取得（案件IDを指定した場合はその案件のみ）
+    pub fn list_upload_history(&self, project_id: Option<&str>, limit: usize, offset: usize) -> SqliteResult<Vec<UploadHistoryEntry>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT finished_at, archived_at, item_json FROM upload_history
+             WHERE ?1 IS NULL OR project_id = ?1
+             ORDER BY finished_at DESC LIMIT ?2 OFFSET ?3"
+        )?;
+
+        let rows = stmt.query_map(
+            rusqlite::params![project_id, limit.min(i64::MAX as usize) as i64, offset as i64],
+            |row| {
+                let item_json: String = row.get(2)?;
+                let item = serde_json::from_str(&item_json).map_err(|e| {
+                    rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, Box::new(e))
+                })?;
+                Ok(UploadHistoryEntry { item, finished_at: row.get(0)?, archived_at: row.get(1)? })
+            },
+        )?;
+
+        rows.collect()
+    }
+
+    pub fn count_upload_history(&self) -> SqliteResult<u64> {
+        self.connection.query_row("SELECT COUNT(*) FROM upload_history", [], |row| row.get::<_, i64>(0))
+            .map(|count| count as u64)
+    }
+
+    /// 復元済みの作業コピーを記録（同じパスへ再ダウンロードした場合は置き換える）
+    pub fn upsert_restored_file(&self, file: &RestoredFile) -> SqliteResult<()> {
+        self.connection.execute(
+            "INSERT OR REPLACE INTO restored_files (local_path, s3_key, bucket, downloaded_at, expires_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![file.local_path, file.s3_key, file.bucket, file.downloaded_at, file.expires_at],
+        )?;
+        Ok(())
+    }
+
+    /// 復元済みの作業コピーを削除予定日の早い順に取得（指定時は期限切れのもののみ）
+    pub fn list_restored_files(&self, expired_at: Option<&str>) -> SqliteResult<Vec<RestoredFile>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT local_path, s3_key, bucket, downloaded_at, expires_at FROM restored_files
+             WHERE ?1 IS NULL OR expires_at <= ?1
+             ORDER BY expires_at ASC"
+        )?;
+        let rows = stmt.query_map([expired_at], |row| {
+            Ok(RestoredFile {
+                local_path: row.get(0)?,
+                s3_key: row.get(1)?,
+                bucket: row.get(2)?,
+                downloaded_at: row.get(3)?,
+                expires_at: row.get(4)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// 削除予定日を変更（記録がなければ0を返す）
+    pub fn update_restored_file_expiry(&self, local_path: &str, expires_at: &str) -> SqliteResult<usize> {
+        self.connection.execute(
+            "UPDATE restored_files SET expires_at = ?2 WHERE local_path = ?1",
+            [local_path, expires_at],
+        )
+    }
+
+    pub fn delete_restored_file(&self, local_path: &str) -> SqliteResult<usize> {
+        self.connection.execute("DELETE FROM restored_files WHERE local_path = ?1", [local_path])
+    }
+
+    /// プレフィックス配下のキャッシュ済みオブジェクト
+    pub fn cached_s3_objects_under(&self, bucket: &str, prefix: &str) -> SqliteResult<Vec<S3Object>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT s3_key, size, storage_class, etag, last_modified FROM s3_objects_cache
+             WHERE bucket = ?1 AND substr(s3_key, 1, length(?2)) = ?2"
+        )?;
+        let rows = stmt.query_map([bucket, prefix], |row| {
+            Ok(S3Object {
+                key: row.get(0)?,
+                size: row.get::<_, i64>(1)? as u64,
+                storage_class: row.get(2)?,
+                etag: row.get(3)?,
+                last_modified: row.get(4)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// 一覧の取得結果を書き込み、なくなったキーを削除して同期日時を記録（1トランザクション）
+    pub fn apply_s3_cache_update(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        listed: &[S3Object],
+        removed: &[String],
+        synced_at: &str,
+    ) -> SqliteResult<()> {
+        let tx = self.connection.unchecked_transaction()?;
+        for object in listed {
+            tx.execute(
+                "INSERT OR REPLACE INTO s3_objects_cache (bucket, s3_key, size, storage_class, etag, last_modified, fetched_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                rusqlite::params![
+                    bucket, object.key, object.size as i64, object.storage_class,
+                    object.etag, object.last_modified, synced_at
+                ],
+            )?;
+        }
+        for key in removed {
+            tx.execute("DELETE FROM s3_objects_cache WHERE bucket = ?1 AND s3_key = ?2", [bucket, key])?;
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO s3_cache_sync (bucket, prefix, synced_at) VALUES (?1, ?2, ?3)",
+            [bucket, prefix, synced_at],
+        )?;
+        tx.commit()
+    }
+
+    /// キャッシュを検索し、同じキーの最新のアップロード記録と対応付けてキー順に返す
+    pub fn list_cached_s3_objects(&self, bucket: &str, prefix: &str, filter: &S3CacheFilter) -> SqliteResult<Vec<CachedS3Object>> {
+        let mut sql = "SELECT c.s3_key, c.size, c.storage_class, c.last_modified, c.etag, c.fetched_at, u.file_path, u.local_removed_at
+                       FROM s3_objects_cache c
+                       LEFT JOIN (
+                           SELECT bucket, s3_key, file_path, local_removed_at, MAX(uploaded_at)
+                           FROM upload_records GROUP BY bucket, s3_key
+                       ) u ON u.bucket = c.bucket AND u.s3_key = c.s3_key
+                       WHERE c.bucket = ?1 AND substr(c.s3_key, 1, length(?2)) = ?2".to_string();
+        let mut params: Vec<String> = vec![bucket.to_string(), prefix.to_string()];
+
+        if let Some(query) = filter.query.as_deref().filter(|query| !query.is_empty()) {
+            params.push(query.to_string());
+            sql.push_str(&format!(" AND instr(lower(c.s3_key), lower(?{})) > 0", params.len()));
+        }
+
+        if let Some(storage_class) = &filter.storage_class {
+            params.push(storage_class.clone());
+            sql.push_str(&format!(" AND c.storage_class = ?{}", params.len()));
+        }
+
+        match filter.has_local_copy {
+            Some(true) => sql.push_str(" AND u.file_path IS NOT NULL AND u.local_removed_at IS NULL"),
+            Some(false) => sql.push_str(" AND (u.file_path IS NULL OR u.local_removed_at IS NOT NULL)"),
+            None => {}
+        }
+
+        sql.push_str(" ORDER BY c.s3_key ASC");
+        if let Some(limit) = filter.limit {
+            sql.push_str(&format!(" LIMIT {}", limit.min(i64::MAX as usize)));
+        }
+
+        let mut stmt = self.connection.prepare(&sql)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(params.iter()), |row| {
+            Ok(CachedS3Object {
+                key: row.get(0)?,
+                size: row.get::<_, i64>(1)? as u64,
+                storage_class: row.get(2)?,
+                last_modified: row.get(3)?,
+                etag: row.get(4)?,
+                fetched_at: row.get(5)?,
+                local_path: row.get(6)?,
+                local_removed_at: row.get(7)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// プレフィックスを含む範囲を最後に同期した日時（上位のプレフィックスでの同期も含む）
+    pub fn s3_cache_synced_at(&self, bucket: &str, prefix: &str) -> SqliteResult<Option<String>> {
+        self.connection.query_row(
+            "SELECT MAX(synced_at) FROM s3_cache_sync
+             WHERE bucket = ?1 AND substr(?2, 1, length(prefix)) = prefix",
+            [bucket, prefix],
+            |row| row.get(0),
+        )
+    }
+
+    /// 復元通知を保存（同じIDが保存済みならfalse）
+    pub fn insert_restore_notification(&self, notification: &RestoreNotification) -> SqliteResult<bool> {
+        let inserted = self.connection.execute(
+            "INSERT OR IGNORE INTO restore_notifications (id, s3_key, status, message, timestamp, read)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                notification.id, notification.key, notification.status,
+                notification.message, notification.timestamp, notification.read
+            ],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// 削除していない復元通知を新しい順に取得
+    pub fn list_restore_notifications(&self, unread_only: bool) -> SqliteResult<Vec<RestoreNotification>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT id, s3_key, status, message, timestamp, read FROM restore_notifications
+             WHERE deleted = 0 AND (?1 = 0 OR read = 0)
+             ORDER BY timestamp DESC"
+        )?;
+        let rows = stmt.query_map([unread_only], |row| {
+            Ok(RestoreNotification {
+                id: row.get(0)?,
+                key: row.get(1)?,
+                status: row.get(2)?,
+                message: row.get(3)?,
+                timestamp: row.get(4)?,
+                read: row.get(5)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// 復元通知を既読にする（IDの指定がない場合はすべて）、既読にした件数を返す
+    pub fn mark_restore_notification_read(&self, id: Option<&str>) -> SqliteResult<usize> {
+        self.connection.execute(
+            "UPDATE restore_notifications SET read = 1 WHERE deleted = 0 AND read = 0 AND (?1 IS NULL OR id = ?1)",
+            [id],
+        )
+    }
+
+    pub fn delete_restore_notification(&self, id: &str) -> SqliteResult<usize> {
+        self.connection.execute(
+            "UPDATE restore_notifications SET deleted = 1 WHERE id = ?1 AND deleted = 0",
+            [id],
+        )
+    }
+
+    /// `before`より前の復元通知を削除済みのものも含めて削除
+    pub fn prune_restore_notifications(&self, before: &str) -> SqliteResult<usize> {
+        self.connection.execute("DELETE FROM restore_notifications WHERE timestamp < ?1", [before])
+    }
+
+    pub fn record_auto_upload(&self, file_path: &str, file_size: u64, queued_at: &str) -> SqliteResult<()> {
+        self.connection.execute(
+            "INSERT INTO auto_upload_log (file_path, file_size, queued_at) VALUES (?1, ?2, ?3)",
+            rusqlite::params![file_path, file_size as i64, queued_at],
+        )?;
+        Ok(())
+    }
+
+    /// 指定日時以降の自動アップロードの件数と合計バイト数
+    pub fn auto_upload_totals_since(&self, since: &str) -> SqliteResult<(u64, u64)> {
+        self.connection.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(file_size), 0) FROM auto_upload_log WHERE queued_at >= ?1",
+            [since],
+            |row| Ok((row.get::<_, i64>(0)? as u64, row.get::<_, i64>(1)? as u64)),
+        )
+    }
+
+    pub fn prune_auto_upload_log(&self, queued_before: &str) -> SqliteResult<usize> {
+        self.connection.execute("DELETE FROM auto_upload_log WHERE queued_at < ?1", [queued_before])
+    }
+
+    /// 承認待ちに追加（同じファイルがすでに承認待ちなら何もしない）
+    pub fn insert_pending_auto_upload(&self, pending: &PendingAutoUpload) -> SqliteResult<()> {
+        self.connection.execute(
+            "INSERT OR IGNORE INTO auto_upload_pending (file_path, file_size, watch_path, detected_at, reason)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![
+                pending.file_path,
+                pending.file_size as i64,
+                pending.watch_path,
+                pending.detected_at,
+                pending.reason.as_str(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn list_pending_auto_uploads(&self) -> SqliteResult<Vec<PendingAutoUpload>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT file_path, file_size, watch_path, detected_at, reason FROM auto_upload_pending
+             ORDER BY detected_at ASC, file_path ASC"
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(PendingAutoUpload {
+                file_path: row.get(0)?,
+                file_size: row.get::<_, i64>(1)? as u64,
+                watch_path: row.get(2)?,
+                detected_at: row.get(3)?,
+                reason: AutoUploadThrottleReason::parse(&row.get::<_, String>(4)?),
+            })
+        })?;
+        rows.collect()
+    }
+
+    pub fn count_pending_auto_uploads(&self) -> SqliteResult<u64> {
+        self.connection.query_row("SELECT COUNT(*) FROM auto_upload_pending", [], |row| {
+            Ok(row.get::<_, i64>(0)? as u64)
+        })
+    }
+
+    /// 承認待ちから削除（パスの指定がない場合はすべて）、削除した件数を返す
+    pub fn delete_pending_auto_uploads(&self, file_paths: Option<&[String]>) -> SqliteResult<usize> {
+        match file_paths {
+            None => self.connection.execute("DELETE FROM auto_upload_pending", []),
+            Some(paths) => {
+                let mut deleted = 0;
+                for path in paths {
+                    deleted += self.connection.execute("DELETE FROM auto_upload_pending WHERE file_path = ?1", [path])?;
+                }
+                Ok(deleted)
+            }
+        }
+    }
+
+    /// 取り込みフォルダの処理済みとして記録（同じ内容は上書き）
+    pub fn record_hot_folder_processed(&self, processed: &HotFolderProcessedFile) -> SqliteResult<()> {
+        self.connection.execute(
+            "INSERT OR REPLACE INTO hot_folder_processed
+             (file_path, file_size, modified_at, watch_path, s3_key, action, processed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            rusqlite::params![
+                processed.file_path,
+                processed.file_size as i64,
+                processed.modified_at,
+                processed.watch_path,
+                processed.s3_key,
+                processed.action.as_str(),
+                processed.processed_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// パス・サイズ・更新日時が同じファイルを処理済みか
+    pub fn is_hot_folder_file_processed(&self, file_path: &str, file_size: u64, modified_at: &str) -> SqliteResult<bool> {
+        self.connection.query_row(
+            "SELECT COUNT(*) > 0 FROM hot_folder_processed WHERE file_path = ?1 AND file_size = ?2 AND modified_at = ?3",
+            rusqlite::params![file_path, file_size as i64, modified_at],
+            |row| row.get(0),
+        )
+    }
+
+    pub fn count_hot_folder_processed(&self, watch_path: &str) -> SqliteResult<u64> {
+        self.connection.query_row(
+            "SELECT COUNT(*) FROM hot_folder_processed WHERE watch_path = ?1",
+            [watch_path],
+            |row| Ok(row.get::<_, i64>(0)? as u64),
+        )
+    }
+
+    /// プロジェクトを登録（同じIDがあればエラー）
+    pub fn insert_project(&self, project: &Project) -> SqliteResult<()> {
+        self.connection.execute(
+            "INSERT INTO projects (id, name, client, description, default_s3_prefix, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                project.id,
+                project.name,
+                project.client,
+                project.description,
+                project.default_s3_prefix,
+                project.created_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// プロジェクトを作成日時の新しい順に取得（ID指定時はそのプロジェクトのみ）
+    pub fn list_projects(&self, id: Option<&str>) -> SqliteResult<Vec<Project>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT id, name, client, description, default_s3_prefix, created_at FROM projects
+             WHERE ?1 IS NULL OR id = ?1
+             ORDER BY created_at DESC, id ASC"
+        )?;
+        let rows = stmt.query_map([id], |row| {
+            Ok(Project {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                client: row.get(2)?,
+                description: row.get(3)?,
+                default_s3_prefix: row.get(4)?,
+                created_at: row.get(5)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// プロジェクトを削除し、割り当て済みのファイル・アップロード記録を未割り当てに戻す
+    pub fn delete_project(&self, id: &str) -> SqliteResult<usize> {
+        self.connection.execute("UPDATE file_metadata SET project_id = NULL WHERE project_id = ?1", [id])?;
+        self.connection.execute("UPDATE upload_records SET project_id = NULL WHERE project_id = ?1", [id])?;
+        self.connection.execute("DELETE FROM projects WHERE id = ?1", [id])
+    }
+
+    /// ファイルのプロジェクトを変更（Noneで割り当て解除）し、どちらのテーブルにもないパスを返す
+    pub fn assign_project(&self, project_id: Option<&str>, file_paths: &[String]) -> SqliteResult<Vec<String>> {
+        let mut not_found = Vec::new();
+        for file_path in file_paths {
+            let file_path = to_nfc(file_path);
+            let metadata_rows = self.connection.execute(
+                "UPDATE file_metadata SET project_id = ?1 WHERE file_path = ?2",
+                rusqlite::params![project_id, file_path],
+            )?;
+            let upload_rows = self.connection.execute(
+                "UPDATE upload_records SET project_id = ?1 WHERE file_path = ?2",
+                rusqlite::params![project_id, file_path],
+            )?;
+            if metadata_rows == 0 && upload_rows == 0 {
+                not_found.push(file_path);
+            }
+        }
+        Ok(not_found)
+    }
+
+    /// プロジェクトに属するファイル数と合計サイズ（メタデータとアップロード記録の和集合）
+    pub fn project_file_totals(&self, project_id: &str) -> SqliteResult<(u64, u64)> {
+        self.connection.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(file_size), 0) FROM (
+                 SELECT file_path, file_size FROM file_metadata WHERE project_id = ?1
+                 UNION
+                 SELECT file_path, file_size FROM upload_records
+                 WHERE project_id = ?1
+                   AND file_path NOT IN (SELECT file_path FROM file_metadata WHERE project_id = ?1)
+             )",
+            [project_id],
+            |row| Ok((row.get::<_, i64>(0)? as u64, row.get::<_, i64>(1)? as u64)),
+        )
+    }
+
+    /// プロジェクトのアップロード済み（検証済み）ファイルのストレージクラス別の内訳
+    pub fn project_storage_classes(&self, project_id: &str) -> SqliteResult<Vec<StorageClassUsage>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT COALESCE(storage_class, 'UNKNOWN') AS class, COUNT(*), COALESCE(SUM(file_size), 0)
+             FROM upload_records
+             WHERE project_id = ?1 AND verified = 1
+             GROUP BY class
+             ORDER BY class ASC"
+        )?;
+        let rows = stmt.query_map([project_id], |row| {
+            Ok(StorageClassUsage {
+                storage_class: row.get(0)?,
+                file_count: row.get::<_, i64>(1)? as u64,
+                total_bytes: row.get::<_, i64>(2)? as u64,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// 日別のリクエスト数を加算
+    pub fn add_api_usage(&self, usage_date: &str, api: &str, count: u64) -> SqliteResult<()> {
+        self.connection.execute(
+            "INSERT INTO s3_api_usage (usage_date, api, request_count) VALUES (?1, ?2, ?3)
+             ON CONFLICT(usage_date, api) DO UPDATE SET request_count = request_count + excluded.request_count",
+            rusqlite::params![usage_date, api, count as i64],
+        )?;
+        Ok(())
+    }
+
+    /// 月別・方向別の転送量を加算
+    pub fn add_transfer_usage(&self, usage_month: &str, direction: &str, bytes: u64) -> SqliteResult<()> {
+        self.connection.execute(
+            "INSERT INTO transfer_usage (usage_month, direction, bytes) VALUES (?1, ?2, ?3)
+             ON CONFLICT(usage_month, direction) DO UPDATE SET bytes = bytes + excluded.bytes",
+            rusqlite::params![usage_month, direction, bytes as i64],
+        )?;
+        Ok(())
+    }
+
+    /// 月の方向別の転送量
+    pub fn list_transfer_usage(&self, usage_month: &str) -> SqliteResult<Vec<(String, u64)>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT direction, bytes FROM transfer_usage WHERE usage_month = ?1 ORDER BY direction"
+        )?;
+        let rows = stmt.query_map([usage_month], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?.max(0) as u64))
+        })?;
+        rows.collect()
+    }
+
+    pub fn set_transfer_cap_override(&self, usage_month: &str, overridden_at: &str) -> SqliteResult<()> {
+        self.connection.execute(
+            "INSERT OR REPLACE INTO transfer_cap_overrides (usage_month, overridden_at) VALUES (?1, ?2)",
+            [usage_month, overridden_at],
+        )?;
+        Ok(())
+    }
+
+    pub fn is_transfer_cap_overridden(&self, usage_month: &str) -> SqliteResult<bool> {
+        self.connection.query_row(
+            "SELECT COUNT(*) > 0 FROM transfer_cap_overrides WHERE usage_month = ?1",
+            [usage_month],
+            |row| row.get(0),
+        )
+    }
+
+    /// 期間内（両端を含む）のAPI種別ごとのリクエスト数
+    pub fn list_api_usage(&self, from_date: &str, to_date: &str) -> SqliteResult<Vec<(String, u64)>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT api, SUM(request_count) FROM s3_api_usage
+             WHERE usage_date >= ?1 AND usage_date <= ?2 GROUP BY api ORDER BY api"
+        )?;
+
+        let rows = stmt.query_map([from_date, to_date], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?.max(0) as u64))
+        })?;
+
+        rows.collect()
+    }
+
     /// すべてのタグを取得
     pub fn get_all_tags(&self) -> SqliteResult<Vec<String>> {
         let mut stmt = self.connection.prepare("SELECT name FROM tags ORDER BY name")?;
@@ -356,6 +1523,11 @@ impl MetadataDatabase {
 
 /// ファイルハッシュを計算
 pub fn calculate_file_hash(file_path: &PathBuf) -> Result<String, InternalError> {
+    calculate_file_hash_cancellable(file_path, &CancellationToken::new())
+}
+
+/// ファイルハッシュを計算（トークンが中断されたら読み込みを打ち切る）
+pub fn calculate_file_hash_cancellable(file_path: &PathBuf, token: &CancellationToken) -> Result<String, InternalError> {
     let file = File::open(file_path)
         .map_err(|e| InternalError::File(format!("Failed to open file: {}", e)))?;
     
@@ -364,6 +1536,7 @@ pub fn calculate_file_hash(file_path: &PathBuf) -> Result<String, InternalError>
     let mut buffer = [0; 1024];
 
     loop {
+        token.check()?;
         let count = reader.read(&mut buffer)
             .map_err(|e| InternalError::File(format!("Failed to read file: {}", e)))?;
         if count == 0 {
@@ -393,27 +1566,67 @@ pub fn detect_mime_type(file_path: &PathBuf) -> String {
     }
 }
 
+/// アプリのデータディレクトリに置くメタデータDBのファイル名
+pub const METADATA_DB_FILE_NAME: &str = "metadata.db";
+
+/// アプリのデータディレクトリに置くメタデータDBのパス
+pub fn metadata_db_path(app: &tauri::AppHandle) -> Result<PathBuf, InternalError> {
+    use tauri::Manager;
+
+    let app_data_dir = app.path().app_data_dir()
+        .map_err(|e| InternalError::Config(format!("Failed to get app data directory: {}", e)))?;
+    std::fs::create_dir_all(&app_data_dir)?;
+    Ok(app_data_dir.join(METADATA_DB_FILE_NAME))
+}
+
 // Tauri Command API実装
 
 /// グローバルなメタデータデータベース接続
 #[allow(dead_code)]
 pub struct MetadataState(pub Mutex<Option<MetadataDatabase>>);
 
-/// メタデータデータベースを初期化
+/// initialize_metadata_dbの結果
+#[derive(Debug, Clone, Serialize)]
+pub struct MetadataDbInitResult {
+    pub message: String,
+    /// 破損を検出して修復した場合の結果（metadata-db-recoveredイベントでも通知する）
+    pub recovery: Option<MetadataDbRecovery>,
+}
+
+/// メタデータデータベースを初期化（整合性確認で破損が見つかれば修復してから開く）
 #[command]
-pub async fn initialize_metadata_db(db_path: String) -> Result<String, String> {
+pub async fn initialize_metadata_db(app: tauri::AppHandle, db_path: String) -> Result<MetadataDbInitResult, String> {
+    let recovery = repair_metadata_db(std::path::Path::new(&db_path)).map_err(standardize_error)?;
+    if let Some(recovery) = &recovery {
+        record_metadata_db_recovery(&app, recovery);
+    }
     match MetadataDatabase::new(&db_path) {
-        Ok(_) => Ok("Metadata database initialized successfully".to_string()),
+        Ok(_) => Ok(MetadataDbInitResult {
+            message: "Metadata database initialized successfully".to_string(),
+            recovery,
+        }),
         Err(e) => Err(standardize_error(InternalError::Database(format!("Failed to initialize metadata database: {}", e))))
     }
 }
 
-/// ファイルメタデータを作成
+/// ファイルメタデータを作成（ハッシュ計算はcancel_operationで中断できる）
 #[command]
 pub async fn create_file_metadata(
+    app: tauri::AppHandle,
     file_path: String,
     tags: Vec<String>,
     custom_fields: HashMap<String, String>,
+) -> Result<FileMetadata, String> {
+    let operation = begin_operation(&app, OperationKind::HashCalculation, file_path.clone());
+    build_file_metadata(file_path, tags, custom_fields, operation.token()).await
+}
+
+/// ファイルメタデータを組み立てる（バックグラウンド処理からの呼び出し用）
+pub async fn build_file_metadata(
+    file_path: String,
+    tags: Vec<String>,
+    custom_fields: HashMap<String, String>,
+    token: &CancellationToken,
 ) -> Result<FileMetadata, String> {
     let path = PathBuf::from(&file_path);
     
@@ -427,8 +1640,8 @@ pub async fn create_file_metadata(
         .map_err(|e| standardize_error(InternalError::File(format!("Failed to get file metadata: {}", e))))?;
 
     // ファイルハッシュを計算
-    let file_hash = calculate_file_hash(&path)
-        .map_err(|e| standardize_error(e))?;
+    let file_hash = calculate_file_hash_cancellable(&path, token)
+        .map_err(standardize_error)?;
 
     // MIMEタイプを検出
     let mime_type = detect_mime_type(&path);
@@ -440,6 +1653,8 @@ pub async fn create_file_metadata(
         None
     };
 
+    // アップロード時に渡された案件IDはプロジェクトの割り当てとしても保存する
+    let project_id = custom_fields.get("project_id").cloned();
     let metadata = FileMetadata {
         id: None,
         file_path,
@@ -450,11 +1665,12 @@ pub async fn create_file_metadata(
         file_size: metadata.len(),
         file_hash,
         mime_type,
-        created_at: format!("{:?}", metadata.created().unwrap_or(std::time::SystemTime::now())),
-        modified_at: format!("{:?}", metadata.modified().unwrap_or(std::time::SystemTime::now())),
+        created_at: file_time_to_rfc3339(metadata.created()).unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+        modified_at: file_time_to_rfc3339(metadata.modified()).unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
         video_metadata,
         tags,
         custom_fields,
+        project_id,
     };
 
     Ok(metadata)
@@ -600,6 +1816,7 @@ mod tests {
             }),
             tags: vec!["test".to_string(), "video".to_string()],
             custom_fields,
+            project_id: None,
         }
     }
 
@@ -652,6 +1869,18 @@ mod tests {
         assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
+    #[test]
+    fn test_file_hash_stops_when_cancelled() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("clip.mov");
+        fs::write(&file_path, vec![0u8; 4096]).unwrap();
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let result = calculate_file_hash_cancellable(&file_path, &token);
+        assert!(matches!(result, Err(InternalError::Cancelled(_))));
+    }
+
     #[test]
     fn test_mime_type_detection() {
         let test_cases = vec![
@@ -770,4 +1999,151 @@ mod tests {
         let custom_fields = &results[0].custom_fields;
         assert_eq!(custom_fields.get("description").unwrap(), "Test video file");
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_japanese_file_name_is_saved_as_nfc_and_found_from_nfd() {
+        let (db, _temp_dir) = create_test_db();
+        let nfd_name = "\u{30DB}\u{309A}\u{30FC}\u{30C8}\u{30D5}\u{30A9}\u{30EA}\u{30AA}.mp4";
+        let mut metadata = create_test_metadata();
+        metadata.file_path = format!("/Volumes/Media/{}", nfd_name);
+        metadata.file_name = nfd_name.to_string();
+        db.save_metadata(&metadata).unwrap();
+
+        // NFCのパスでもNFDのパスでも同じレコードが見つかる
+        let saved = db.get_metadata_by_path("/Volumes/Media/ポートフォリオ.mp4").unwrap();
+        assert_eq!(saved.file_name, "ポートフォリオ.mp4");
+        assert!(db.get_metadata_by_path(&metadata.file_path).is_ok());
+
+        let search_query = MetadataSearchQuery {
+            file_name_pattern: Some("ポートフォリオ".to_string()),
+            tags: None,
+            size_min: None,
+            size_max: None,
+            date_from: None,
+            date_to: None,
+            mime_type: None,
+        };
+        assert_eq!(db.search_metadata(&search_query).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_legacy_nfd_record_is_still_found() {
+        let (db, _temp_dir) = create_test_db();
+        let nfd_path = "/Volumes/Media/\u{30C6}\u{3099}\u{30FC}\u{30BF}.mov";
+        // NFC正規化導入前に保存されたNFDのレコードを再現
+        db.connection.execute(
+            "INSERT INTO file_metadata (file_path, file_name, file_size, file_hash, mime_type, created_at, modified_at, video_metadata, custom_fields)
+             VALUES (?1, 'legacy.mov', 1, 'hash', 'video/quicktime', '0', '0', NULL, '{}')",
+            [nfd_path],
+        ).unwrap();
+
+        let found = db.get_metadata_by_path("/Volumes/Media/データ.mov").unwrap();
+        assert_eq!(found.file_path, nfd_path);
+        db.delete_metadata("/Volumes/Media/データ.mov").unwrap();
+        assert!(db.get_metadata_by_path(nfd_path).is_err());
+    }
+
+    #[test]
+    fn test_legacy_timestamps_are_migrated_to_rfc3339() {
+        let (db, temp_dir) = create_test_db();
+        db.connection.execute(
+            "INSERT INTO file_metadata (file_path, file_name, file_size, file_hash, mime_type, created_at, modified_at, video_metadata, custom_fields)
+             VALUES ('/videos/old.mov', 'old.mov', 1, 'hash', 'video/quicktime',
+                     'SystemTime { tv_sec: 1704067200, tv_nsec: 0 }', 'SystemTime { tv_sec: 1704153600, tv_nsec: 0 }', NULL, '{}')",
+            [],
+        ).unwrap();
+        drop(db);
+
+        let db = MetadataDatabase::new(temp_dir.path().join("test.db").to_str().unwrap()).unwrap();
+        let migrated = db.get_metadata_by_path("/videos/old.mov").unwrap();
+        assert_eq!(migrated.created_at, "2024-01-01T00:00:00+00:00");
+        assert_eq!(migrated.modified_at, "2024-01-02T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_list_verification_targets_by_scope() {
+        let (db, _temp_dir) = create_test_db();
+        db.save_metadata(&create_test_metadata()).unwrap();
+        let record = |file_path: &str, uploaded_at: &str| UploadRecord {
+            file_path: file_path.to_string(),
+            bucket: "footage".to_string(),
+            s3_key: format!("uploads{}", file_path),
+            file_size: 10,
+            uploaded_at: uploaded_at.to_string(),
+            verified: true,
+            local_removed_at: None,
+            project_id: None,
+            note: None,
+            storage_class: None,
+            source_modified_at: None,
+            file_hash: None,
+        };
+        db.record_upload(&record("/test/video.mp4", "2024-03-01T00:00:00+00:00")).unwrap();
+        db.record_upload(&record("/test/untagged.mov", "2024-01-01T00:00:00+00:00")).unwrap();
+
+        let all = db.list_verification_targets("footage", &VerifyScope::All).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].file_path, "/test/untagged.mov");
+        assert_eq!(all[0].file_hash, None);
+        assert_eq!(all[1].file_hash.as_deref(), Some("abc123def456"));
+
+        let tagged = db.list_verification_targets("footage", &VerifyScope::Tag { tag: "video".to_string() }).unwrap();
+        assert_eq!(tagged.len(), 1);
+        assert_eq!(tagged[0].s3_key, "uploads/test/video.mp4");
+
+        let period = VerifyScope::Period { from: Some("2024-02-01T00:00:00+00:00".to_string()), to: None };
+        assert_eq!(db.list_verification_targets("footage", &period).unwrap().len(), 1);
+        assert!(db.list_verification_targets("other-bucket", &VerifyScope::All).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_api_usage_accumulates_per_day() {
+        let (db, _temp_dir) = create_test_db();
+        db.add_api_usage("2024-05-01", "upload_part", 10).unwrap();
+        db.add_api_usage("2024-05-01", "upload_part", 5).unwrap();
+        db.add_api_usage("2024-05-02", "upload_part", 1).unwrap();
+        db.add_api_usage("2024-05-02", "head_object", 3).unwrap();
+        db.add_api_usage("2024-04-30", "head_object", 100).unwrap();
+
+        let usage = db.list_api_usage("2024-05-01", "2024-05-31").unwrap();
+        assert_eq!(usage, vec![("head_object".to_string(), 3), ("upload_part".to_string(), 16)]);
+    }
+
+    #[test]
+    fn test_upload_records_gain_project_columns_on_existing_db() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("legacy.db");
+        Connection::open(&db_path).unwrap().execute(
+            "CREATE TABLE upload_records (
+                file_path TEXT PRIMARY KEY,
+                bucket TEXT NOT NULL,
+                s3_key TEXT NOT NULL,
+                file_size INTEGER NOT NULL,
+                uploaded_at TEXT NOT NULL,
+                verified INTEGER NOT NULL DEFAULT 0,
+                local_removed_at TEXT
+            )",
+            [],
+        ).unwrap();
+
+        let db = MetadataDatabase::new(db_path.to_str().unwrap()).unwrap();
+        db.record_upload(&UploadRecord {
+            file_path: "/footage/a.mov".to_string(),
+            bucket: "footage".to_string(),
+            s3_key: "uploads/a.mov".to_string(),
+            file_size: 10,
+            uploaded_at: "2024-03-01T00:00:00+00:00".to_string(),
+            verified: true,
+            local_removed_at: None,
+            project_id: Some("PRJ-001".to_string()),
+            note: Some("本編素材".to_string()),
+            storage_class: None,
+            source_modified_at: None,
+            file_hash: None,
+        }).unwrap();
+
+        let records = db.find_retention_candidates("2024-12-31T00:00:00+00:00").unwrap();
+        assert_eq!(records[0].project_id.as_deref(), Some("PRJ-001"));
+        assert_eq!(records[0].note.as_deref(), Some("本編素材"));
+    }
+}
@@ -0,0 +1,345 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use rusqlite::types::Value;
+use rusqlite::{Connection, OpenFlags};
+use serde::Serialize;
+use tauri::{command, AppHandle, Emitter};
+
+use crate::commands::metadata::MetadataDatabase;
+use crate::commands::startup_diagnostics::{integrity_check, metadata_db_backup_path, quarantine_path};
+use crate::internal::{AppError, InternalError};
+
+/// 壊れたメタデータDBを修復したときのイベント
+pub const METADATA_DB_RECOVERED_EVENT: &str = "metadata-db-recovered";
+/// 救出したレコードを書き込む作業用DB（metadata.db → metadata.db.salvage）
+const SALVAGE_SUFFIX: &str = "salvage";
+/// DB本体と一緒に退避するSQLiteの付随ファイル（残すと新しいDBにジャーナルが適用される）
+pub(crate) const SIDECAR_SUFFIXES: [&str; 3] = ["-journal", "-wal", "-shm"];
+
+/// メタデータDBの修復方法（この順に試す）
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case", tag = "action")]
+pub enum MetadataDbRecoveryAction {
+    /// 整合性確認に通ったバックアップで置き換えた
+    RestoredFromBackup { backup_path: String },
+    /// バックアップがなく、壊れたDBから読み出せたレコードを新しいDBへ移した
+    SalvagedRecords,
+    /// レコードを読み出せず、空のDBを作り直した
+    Recreated,
+}
+
+/// metadata-db-recoveredイベントのペイロード
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MetadataDbRecovery {
+    pub action: MetadataDbRecoveryAction,
+    /// integrity_checkの結果
+    pub error: String,
+    /// 壊れたDBの退避先
+    pub corrupted_path: String,
+    /// 修復後のDBのレコード数
+    pub recovered_records: u64,
+    /// 失われた可能性のあるレコード数（壊れたDBの件数を数えられなかった場合はNone）
+    pub lost_records: Option<u64>,
+    /// 途中で読み出せなくなり、一部のレコードしか移せなかったテーブル
+    pub incomplete_tables: Vec<String>,
+    pub recovered_at: String,
+}
+
+impl MetadataDbRecovery {
+    /// ログ・起動時診断向けの要約
+    pub fn summary(&self) -> String {
+        let action = match &self.action {
+            MetadataDbRecoveryAction::RestoredFromBackup { backup_path } => format!("restored from {}", backup_path),
+            MetadataDbRecoveryAction::SalvagedRecords => format!("salvaged {} records", self.recovered_records),
+            MetadataDbRecoveryAction::Recreated => "recreated an empty database".to_string(),
+        };
+        match self.lost_records {
+            Some(lost) => format!("{}, {} records may be lost", action, lost),
+            None => action,
+        }
+    }
+}
+
+/// このセッションで行った修復（イベントはフロントの購読前に送られる場合があるため保持する）
+static METADATA_DB_RECOVERY: Mutex<Option<MetadataDbRecovery>> = Mutex::new(None);
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn salvage_path(db_path: &Path) -> PathBuf {
+    let mut name = db_path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}", SALVAGE_SUFFIX));
+    db_path.with_file_name(name)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// ユーザーテーブルの名前と作成SQL（sqlite_sequenceなどの内部テーブルは除く）
+fn user_tables(connection: &Connection) -> rusqlite::Result<Vec<(String, String)>> {
+    let mut stmt = connection.prepare(
+        "SELECT name, sql FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY rootpage",
+    )?;
+    let tables = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    tables.collect()
+}
+
+/// 全テーブルのレコード数の合計（破損で数えられないテーブルがあればNone）
+fn count_records(db_path: &Path) -> Option<u64> {
+    let connection = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY).ok()?;
+    let mut total = 0;
+    for (name, _) in user_tables(&connection).ok()? {
+        let count: i64 = connection
+            .query_row(&format!("SELECT COUNT(*) FROM {}", quote_identifier(&name)), [], |row| row.get(0))
+            .ok()?;
+        total += count as u64;
+    }
+    Some(total)
+}
+
+fn copy_rows(source: &Connection, target: &Connection, table: &str) -> rusqlite::Result<()> {
+    let mut select = source.prepare(&format!("SELECT * FROM {}", quote_identifier(table)))?;
+    let columns: Vec<String> = select.column_names().iter().map(|column| quote_identifier(column)).collect();
+    let mut insert = target.prepare(&format!(
+        "INSERT OR IGNORE INTO {} ({}) VALUES ({})",
+        quote_identifier(table),
+        columns.join(", "),
+        vec!["?"; columns.len()].join(", "),
+    ))?;
+    let mut rows = select.query([])?;
+    while let Some(row) = rows.next()? {
+        let values = (0..columns.len()).map(|index| row.get::<_, Value>(index)).collect::<rusqlite::Result<Vec<_>>>()?;
+        insert.execute(rusqlite::params_from_iter(values))?;
+    }
+    Ok(())
+}
+
+/// テーブルのレコードを読み出せる所まで移す（移した件数と、最後まで読めたか）
+///
+/// 読み出しが途中で失敗しても、それまでに移したレコードは残す
+fn copy_table(source: &Connection, target: &Connection, table: &str) -> (u64, bool) {
+    let complete = match copy_rows(source, target, table) {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("Stopped salvaging table {}: {}", table, e);
+            false
+        }
+    };
+    (count_table(target, table), complete)
+}
+
+fn count_table(connection: &Connection, table: &str) -> u64 {
+    connection
+        .query_row(&format!("SELECT COUNT(*) FROM {}", quote_identifier(table)), [], |row| row.get::<_, i64>(0))
+        .map(|count| count as u64)
+        .unwrap_or(0)
+}
+
+/// 壊れたDBから読み出せるレコードを新しいDBへ移す（.dump相当）。スキーマを読めなければエラー
+fn salvage_records(corrupted: &Path, destination: &Path) -> Result<Vec<String>, InternalError> {
+    let source = Connection::open_with_flags(corrupted, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let tables = user_tables(&source)?;
+    MetadataDatabase::new(&destination.to_string_lossy())?;
+
+    let mut target = Connection::open(destination)?;
+    let transaction = target.transaction()?;
+    let mut incomplete_tables = Vec::new();
+    for (name, sql) in tables {
+        let exists: bool = transaction.query_row(
+            "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [&name],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            if let Err(e) = transaction.execute_batch(&sql) {
+                log::warn!("Could not recreate table {}: {}", name, e);
+                incomplete_tables.push(name);
+                continue;
+            }
+        }
+        let (copied, complete) = copy_table(&source, &transaction, &name);
+        log::info!("Salvaged {} records from table {}", copied, name);
+        if !complete {
+            incomplete_tables.push(name);
+        }
+    }
+    transaction.commit()?;
+    Ok(incomplete_tables)
+}
+
+/// 壊れたDBを付随ファイルごと退避する
+fn move_corrupted_aside(db_path: &Path) -> Result<PathBuf, InternalError> {
+    let moved_to = quarantine_path(db_path);
+    std::fs::rename(db_path, &moved_to)?;
+    for suffix in SIDECAR_SUFFIXES {
+        let sidecar = with_suffix(db_path, suffix);
+        if sidecar.exists() {
+            std::fs::rename(&sidecar, with_suffix(&moved_to, suffix))?;
+        }
+    }
+    log::warn!("Corrupted metadata database moved to {}", moved_to.display());
+    Ok(moved_to)
+}
+
+fn usable_backup(backup_path: &Path) -> bool {
+    if !backup_path.exists() {
+        return false;
+    }
+    match integrity_check(backup_path) {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("Metadata database backup is also corrupted: {}", e);
+            false
+        }
+    }
+}
+
+/// メタデータDBの整合性を確認し、壊れていれば段階的に修復する（正常・未作成ならNone）
+///
+/// 整合性確認に通ったバックアップがあればリストアし、なければ壊れたDBから読み出せるレコードを新しいDBへ移す。
+/// スキーマも読めない場合は壊れたDBを退避して空のDBを作り直す
+pub fn repair_metadata_db(db_path: &Path) -> Result<Option<MetadataDbRecovery>, InternalError> {
+    if !db_path.exists() {
+        return Ok(None);
+    }
+    let error = match integrity_check(db_path) {
+        Ok(()) => return Ok(None),
+        Err(e) => e,
+    };
+    log::error!("Metadata database integrity check failed: {}", error);
+    let records_before = count_records(db_path);
+
+    let backup_path = metadata_db_backup_path(db_path);
+    let mut incomplete_tables = Vec::new();
+    let (action, corrupted_path) = if usable_backup(&backup_path) {
+        let moved_to = move_corrupted_aside(db_path)?;
+        std::fs::copy(&backup_path, db_path)?;
+        (MetadataDbRecoveryAction::RestoredFromBackup { backup_path: backup_path.to_string_lossy().to_string() }, moved_to)
+    } else {
+        let salvage_path = salvage_path(db_path);
+        if salvage_path.exists() {
+            std::fs::remove_file(&salvage_path)?;
+        }
+        match salvage_records(db_path, &salvage_path) {
+            Ok(tables) => {
+                incomplete_tables = tables;
+                let moved_to = move_corrupted_aside(db_path)?;
+                std::fs::rename(&salvage_path, db_path)?;
+                (MetadataDbRecoveryAction::SalvagedRecords, moved_to)
+            }
+            Err(e) => {
+                log::warn!("Could not salvage records from metadata database: {}", e);
+                if salvage_path.exists() {
+                    std::fs::remove_file(&salvage_path)?;
+                }
+                let moved_to = move_corrupted_aside(db_path)?;
+                (MetadataDbRecoveryAction::Recreated, moved_to)
+            }
+        }
+    };
+    // バックアップ・救出先が古いスキーマでも、以降の操作で使うテーブルをそろえる
+    MetadataDatabase::new(&db_path.to_string_lossy())?;
+
+    let recovered_records = count_records(db_path).unwrap_or(0);
+    let recovery = MetadataDbRecovery {
+        action,
+        error,
+        corrupted_path: corrupted_path.to_string_lossy().to_string(),
+        recovered_records,
+        lost_records: records_before.map(|before| before.saturating_sub(recovered_records)),
+        incomplete_tables,
+        recovered_at: chrono::Utc::now().to_rfc3339(),
+    };
+    log::warn!("Metadata database repaired: {}", recovery.summary());
+    Ok(Some(recovery))
+}
+
+/// 修復結果を保持し、metadata-db-recoveredイベントで通知
+pub fn record_metadata_db_recovery(app: &AppHandle, recovery: &MetadataDbRecovery) {
+    if let Ok(mut current) = METADATA_DB_RECOVERY.lock() {
+        *current = Some(recovery.clone());
+    }
+    if let Err(e) = app.emit(METADATA_DB_RECOVERED_EVENT, recovery) {
+        log::warn!("Failed to emit {}: {}", METADATA_DB_RECOVERED_EVENT, e);
+    }
+}
+
+/// このセッションで行ったメタデータDBの修復を取得
+#[command]
+pub async fn get_metadata_db_recovery() -> Result<Option<MetadataDbRecovery>, AppError> {
+    let recovery = METADATA_DB_RECOVERY.lock()
+        .map_err(|e| InternalError::Other(format!("Failed to lock metadata database recovery: {}", e)))?;
+    Ok(recovery.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GARBAGE: &[u8] = b"this is not a sqlite database at all, just garbage bytes";
+
+    #[test]
+    fn test_corrupted_db_restores_backup_or_recreates() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("metadata.db");
+        MetadataDatabase::new(db_path.to_str().unwrap()).unwrap();
+        Connection::open(&db_path).unwrap().execute("INSERT INTO tags (name) VALUES ('interview')", []).unwrap();
+        assert_eq!(repair_metadata_db(&db_path).unwrap(), None);
+        std::fs::copy(&db_path, metadata_db_backup_path(&db_path)).unwrap();
+
+        std::fs::write(&db_path, GARBAGE).unwrap();
+        let recovery = repair_metadata_db(&db_path).unwrap().unwrap();
+        assert!(matches!(recovery.action, MetadataDbRecoveryAction::RestoredFromBackup { .. }));
+        assert_eq!(recovery.recovered_records, 1);
+        assert_eq!(std::fs::read(&recovery.corrupted_path).unwrap(), GARBAGE);
+        assert_eq!(repair_metadata_db(&db_path).unwrap(), None);
+
+        // バックアップもなく、スキーマすら読めなければ作り直す
+        std::fs::remove_file(metadata_db_backup_path(&db_path)).unwrap();
+        std::fs::write(&db_path, GARBAGE).unwrap();
+        let recovery = repair_metadata_db(&db_path).unwrap().unwrap();
+        assert_eq!(recovery.action, MetadataDbRecoveryAction::Recreated);
+        assert_eq!(recovery.recovered_records, 0);
+        assert_eq!(recovery.lost_records, None);
+        assert!(integrity_check(&db_path).is_ok());
+        assert!(!salvage_path(&db_path).exists());
+    }
+
+    #[test]
+    fn test_partially_corrupted_db_salvages_readable_records() {
+        const PAGE_SIZE: usize = 4096;
+        const CLIPS: i64 = 200;
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("metadata.db");
+        let mut connection = Connection::open(&db_path).unwrap();
+        connection
+            .execute_batch("PRAGMA page_size = 4096; CREATE TABLE clips (id INTEGER PRIMARY KEY, note TEXT NOT NULL);")
+            .unwrap();
+        let transaction = connection.transaction().unwrap();
+        for id in 0..CLIPS {
+            transaction.execute("INSERT INTO clips (id, note) VALUES (?1, ?2)", rusqlite::params![id, "x".repeat(1000)]).unwrap();
+        }
+        transaction.commit().unwrap();
+        drop(connection);
+
+        // 最後のページ（最後に追加したレコードが入る葉ページ）だけを壊す
+        let mut bytes = std::fs::read(&db_path).unwrap();
+        let len = bytes.len();
+        bytes[len - PAGE_SIZE..].fill(0xFF);
+        std::fs::write(&db_path, bytes).unwrap();
+
+        let recovery = repair_metadata_db(&db_path).unwrap().unwrap();
+        assert_eq!(recovery.action, MetadataDbRecoveryAction::SalvagedRecords);
+        assert_eq!(recovery.incomplete_tables, vec!["clips".to_string()]);
+        assert!(recovery.recovered_records > 0 && recovery.recovered_records < CLIPS as u64);
+        assert!(integrity_check(&db_path).is_ok());
+        // 救出先にもアプリのテーブルがそろっている
+        let salvaged = Connection::open(&db_path).unwrap();
+        assert_eq!(count_table(&salvaged, "clips"), recovery.recovered_records);
+        assert_eq!(count_table(&salvaged, "file_metadata"), 0);
+    }
+}
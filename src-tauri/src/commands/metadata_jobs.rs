@@ -0,0 +1,237 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use serde::Serialize;
+use tauri::{command, AppHandle};
+
+use crate::commands::config::load_config;
+use crate::commands::file_operations::create_auto_metadata;
+use crate::internal::{AppError, InternalError};
+
+/// 終了時に未処理のメタデータ作成ジョブを保存するファイル名（アプリデータディレクトリ内）
+pub const METADATA_JOBS_FILE: &str = "metadata_jobs.json";
+/// メタデータ作成の同時実行数の上限
+pub const MAX_METADATA_JOB_CONCURRENCY: usize = 8;
+
+/// メタデータ作成ジョブの状態
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MetadataJobStatus {
+    pub pending: usize,
+    pub running: Vec<String>,
+    pub completed: u64,
+    pub failed: u64,
+    pub concurrency: usize,
+    pub last_error: Option<String>,
+}
+
+/// ハッシュ計算とDB書き込みを監視イベントの処理から切り離すジョブキュー
+#[derive(Debug)]
+pub struct MetadataJobQueue {
+    pending: VecDeque<PathBuf>,
+    running: Vec<PathBuf>,
+    completed: u64,
+    failed: u64,
+    concurrency: usize,
+    last_error: Option<String>,
+}
+
+impl Default for MetadataJobQueue {
+    fn default() -> Self {
+        Self {
+            pending: VecDeque::new(),
+            running: Vec::new(),
+            completed: 0,
+            failed: 0,
+            concurrency: crate::commands::config::DEFAULT_METADATA_JOB_CONCURRENCY,
+            last_error: None,
+        }
+    }
+}
+
+impl MetadataJobQueue {
+    /// ジョブを末尾に追加（同じファイルが待機中・実行中なら追加しない）
+    pub fn enqueue(&mut self, path: PathBuf) -> bool {
+        if self.pending.contains(&path) || self.running.contains(&path) {
+            return false;
+        }
+        self.pending.push_back(path);
+        true
+    }
+
+    pub fn set_concurrency(&mut self, concurrency: usize) {
+        self.concurrency = concurrency.clamp(1, MAX_METADATA_JOB_CONCURRENCY);
+    }
+
+    /// 同時実行数に空きがあれば次のジョブを実行中に移す
+    fn next_job(&mut self) -> Option<PathBuf> {
+        if self.running.len() >= self.concurrency {
+            return None;
+        }
+        let path = self.pending.pop_front()?;
+        self.running.push(path.clone());
+        Some(path)
+    }
+
+    fn finish(&mut self, path: &Path, result: Result<(), String>) {
+        self.running.retain(|running| running != path);
+        match result {
+            Ok(()) => self.completed += 1,
+            Err(e) => {
+                self.failed += 1;
+                self.last_error = Some(format!("{}: {}", path.display(), e));
+            }
+        }
+    }
+
+    /// 未処理のジョブ（実行中のものは中断されるため先頭に戻す）
+    pub fn unfinished(&self) -> Vec<PathBuf> {
+        self.running.iter().chain(self.pending.iter()).cloned().collect()
+    }
+
+    pub fn status(&self) -> MetadataJobStatus {
+        MetadataJobStatus {
+            pending: self.pending.len(),
+            running: self.running.iter().map(|path| path.to_string_lossy().to_string()).collect(),
+            completed: self.completed,
+            failed: self.failed,
+            concurrency: self.concurrency,
+            last_error: self.last_error.clone(),
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref METADATA_JOBS: Mutex<MetadataJobQueue> = Mutex::new(MetadataJobQueue::default());
+}
+
+/// 空きのある分だけジョブを開始
+fn spawn_metadata_workers() {
+    loop {
+        let next = METADATA_JOBS.lock().unwrap_or_else(|e| e.into_inner()).next_job();
+        let Some(path) = next else {
+            break;
+        };
+        tauri::async_runtime::spawn(run_metadata_job(path));
+    }
+}
+
+async fn run_metadata_job(path: PathBuf) {
+    // ハッシュ計算は同期I/Oのため、非同期ランタイムのスレッドを塞がないよう専用スレッドで実行する
+    let job_path = path.clone();
+    let result = tokio::task::spawn_blocking(move || futures::executor::block_on(create_auto_metadata(&job_path)))
+        .await
+        .unwrap_or_else(|e| Err(format!("Metadata job panicked: {}", e)));
+    if let Err(e) = &result {
+        log::error!("Failed to create metadata for {}: {}", path.display(), e);
+    }
+    METADATA_JOBS.lock().unwrap_or_else(|e| e.into_inner()).finish(&path, result);
+    spawn_metadata_workers();
+}
+
+/// メタデータ作成をジョブキューへ追加してすぐに戻る
+///
+/// アップロードはメタデータの完了を待たない（ハッシュは後からメタデータDBに埋まる）
+pub fn enqueue_metadata_job(path: &Path) {
+    let added = METADATA_JOBS.lock().unwrap_or_else(|e| e.into_inner()).enqueue(path.to_path_buf());
+    if added {
+        log::debug!("Queued metadata job: {}", path.display());
+        spawn_metadata_workers();
+    }
+}
+
+/// 設定の同時実行数を反映（増えた場合は待機中のジョブを開始）
+pub fn refresh_metadata_job_settings(app: &AppHandle) {
+    if let Ok(config) = load_config(app) {
+        METADATA_JOBS.lock().unwrap_or_else(|e| e.into_inner())
+            .set_concurrency(config.app_settings.metadata_job_concurrency);
+    }
+    spawn_metadata_workers();
+}
+
+/// 未処理のジョブをファイルへ保存（終了時用）
+pub fn persist_metadata_jobs(path: &Path) -> Result<usize, InternalError> {
+    let jobs = METADATA_JOBS.lock().unwrap_or_else(|e| e.into_inner()).unfinished();
+    if jobs.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        return Ok(0);
+    }
+    let json = serde_json::to_string_pretty(&jobs)
+        .map_err(|e| InternalError::Other(format!("Failed to serialize metadata jobs: {}", e)))?;
+    std::fs::write(path, json)?;
+    Ok(jobs.len())
+}
+
+/// 前回終了時に保存したジョブをキューへ戻して処理を再開し、ファイルを削除する
+pub fn restore_persisted_metadata_jobs(path: &Path) -> Result<usize, InternalError> {
+    if !path.exists() {
+        return Ok(0);
+    }
+    let jobs = std::fs::read_to_string(path)
+        .map_err(InternalError::from)
+        .and_then(|json| serde_json::from_str::<Vec<PathBuf>>(&json)
+            .map_err(|e| InternalError::Other(format!("Failed to parse metadata jobs: {}", e))));
+    if let Err(e) = std::fs::remove_file(path) {
+        log::warn!("Failed to remove persisted metadata jobs: {}", e);
+    }
+
+    let restored = {
+        let mut queue = METADATA_JOBS.lock().unwrap_or_else(|e| e.into_inner());
+        // 起動までに削除・移動されたファイルは作成できないため除く
+        jobs?.into_iter()
+            .filter(|job| job.is_file())
+            .filter(|job| queue.enqueue(job.clone()))
+            .count()
+    };
+    spawn_metadata_workers();
+    Ok(restored)
+}
+
+/// メタデータ作成ジョブの進捗・残数を取得
+#[command]
+pub async fn get_metadata_job_status() -> Result<MetadataJobStatus, AppError> {
+    Ok(METADATA_JOBS.lock().unwrap_or_else(|e| e.into_inner()).status())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jobs_respect_concurrency_and_skip_duplicates() {
+        let mut queue = MetadataJobQueue::default();
+        queue.set_concurrency(2);
+        for name in ["a.mov", "b.mov", "c.mov"] {
+            assert!(queue.enqueue(PathBuf::from(name)));
+        }
+        assert!(!queue.enqueue(PathBuf::from("a.mov")));
+
+        let first = queue.next_job().unwrap();
+        let second = queue.next_job().unwrap();
+        assert!(queue.next_job().is_none());
+        // 実行中のファイルも重複として扱う
+        assert!(!queue.enqueue(first.clone()));
+
+        queue.finish(&first, Ok(()));
+        queue.finish(&second, Err("hash failed".to_string()));
+        assert_eq!(queue.next_job(), Some(PathBuf::from("c.mov")));
+
+        let status = queue.status();
+        assert_eq!((status.pending, status.completed, status.failed), (0, 1, 1));
+        assert_eq!(status.running, vec!["c.mov".to_string()]);
+        assert_eq!(status.last_error.as_deref(), Some("b.mov: hash failed"));
+    }
+
+    #[test]
+    fn test_unfinished_puts_running_jobs_first() {
+        let mut queue = MetadataJobQueue::default();
+        queue.enqueue(PathBuf::from("a.mov"));
+        queue.enqueue(PathBuf::from("b.mov"));
+        queue.next_job();
+        assert_eq!(queue.unfinished(), vec![PathBuf::from("a.mov"), PathBuf::from("b.mov")]);
+
+        queue.set_concurrency(0);
+        assert_eq!(queue.status().concurrency, 1);
+    }
+}
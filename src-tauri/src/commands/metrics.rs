@@ -0,0 +1,484 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{LockResult, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use tauri::{command, AppHandle};
+
+use crate::commands::api_usage::{record_api_call, S3ApiKind};
+use crate::commands::aws_operations::{LifecycleRule, ObjectArchiveState, ObjectHeadInfo, PublicAccessBlock, S3ClientTrait, S3Object, S3ObjectStream, S3ObjectVersion};
+use crate::commands::config::load_config;
+use crate::commands::part_progress::PartProgressHook;
+use crate::commands::transfer_cap::{count_download, record_transfer, TransferDirection};
+use crate::commands::upload_checksum::{ChecksumAlgorithm, PartUpload, UploadChecksum};
+use crate::internal::AppError;
+
+/// ヒストグラムのバケット上限（ミリ秒、最後のバケットは上限なし）
+const BUCKET_BOUNDS_MS: [u64; 12] = [1, 5, 10, 25, 50, 100, 250, 500, 1_000, 5_000, 30_000, 120_000];
+const BUCKET_COUNT: usize = BUCKET_BOUNDS_MS.len() + 1;
+
+/// バケット分布を記録するか（デバッグモード時のみ有効）
+static DETAILED_HISTOGRAMS: AtomicBool = AtomicBool::new(false);
+
+/// 処理全体で共有するメトリクス
+pub static METRICS: MetricsRegistry = MetricsRegistry::new();
+
+/// 所要時間の分布
+///
+/// 件数・合計・最大は常に記録し、バケット分布は詳細ヒストグラムが有効な場合のみ記録する
+pub struct Histogram {
+    count: AtomicU64,
+    sum_us: AtomicU64,
+    max_us: AtomicU64,
+    buckets: [AtomicU64; BUCKET_COUNT],
+}
+
+impl Histogram {
+    const fn new() -> Self {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const ZERO: AtomicU64 = AtomicU64::new(0);
+        Self {
+            count: ZERO,
+            sum_us: ZERO,
+            max_us: ZERO,
+            buckets: [ZERO; BUCKET_COUNT],
+        }
+    }
+
+    pub fn record(&self, elapsed: Duration) {
+        let micros = elapsed.as_micros().min(u64::MAX as u128) as u64;
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_us.fetch_add(micros, Ordering::Relaxed);
+        self.max_us.fetch_max(micros, Ordering::Relaxed);
+        if DETAILED_HISTOGRAMS.load(Ordering::Relaxed) {
+            let millis = micros / 1_000;
+            let index = BUCKET_BOUNDS_MS.iter().position(|bound| millis <= *bound).unwrap_or(BUCKET_BOUNDS_MS.len());
+            self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn snapshot(&self) -> HistogramSnapshot {
+        let count = self.count.load(Ordering::Relaxed);
+        let sum_us = self.sum_us.load(Ordering::Relaxed);
+        let buckets = DETAILED_HISTOGRAMS.load(Ordering::Relaxed).then(|| {
+            self.buckets.iter().enumerate()
+                .map(|(index, bucket)| HistogramBucket {
+                    le_ms: BUCKET_BOUNDS_MS.get(index).copied(),
+                    count: bucket.load(Ordering::Relaxed),
+                })
+                .collect()
+        });
+        HistogramSnapshot {
+            count,
+            mean_ms: if count > 0 { sum_us as f64 / count as f64 / 1_000.0 } else { 0.0 },
+            max_ms: self.max_us.load(Ordering::Relaxed) as f64 / 1_000.0,
+            buckets,
+        }
+    }
+
+    fn reset(&self) {
+        self.count.store(0, Ordering::Relaxed);
+        self.sum_us.store(0, Ordering::Relaxed);
+        self.max_us.store(0, Ordering::Relaxed);
+        for bucket in &self.buckets {
+            bucket.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// チェックサムの計算時間と計算したバイト数（アルゴリズムごとのスループット比較用）
+pub struct ChecksumMetrics {
+    duration: Histogram,
+    bytes: AtomicU64,
+}
+
+impl ChecksumMetrics {
+    const fn new() -> Self {
+        Self { duration: Histogram::new(), bytes: AtomicU64::new(0) }
+    }
+
+    fn record(&self, bytes: u64, elapsed: Duration) {
+        self.duration.record(elapsed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> ChecksumMetricsSnapshot {
+        let bytes = self.bytes.load(Ordering::Relaxed);
+        let total_secs = self.duration.sum_us.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        ChecksumMetricsSnapshot {
+            duration: self.duration.snapshot(),
+            bytes,
+            throughput_mbps: if total_secs > 0.0 { bytes as f64 / (1024.0 * 1024.0) / total_secs } else { 0.0 },
+        }
+    }
+
+    fn reset(&self) {
+        self.duration.reset();
+        self.bytes.store(0, Ordering::Relaxed);
+    }
+}
+
+/// アップロード・S3操作の内部メトリクス
+pub struct MetricsRegistry {
+    /// パート1回の送信にかかった時間（成功したもの）
+    pub part_upload_duration: Histogram,
+    /// パート送信のリトライ回数
+    pub part_retries: AtomicU64,
+    /// キューアイテムの手動リトライ回数
+    pub upload_retries: AtomicU64,
+    pub s3_api_calls: AtomicU64,
+    pub s3_api_failures: AtomicU64,
+    /// キューに追加されてからアップロードが始まるまでの時間
+    pub queue_wait: Histogram,
+    /// アップロードキューのロック取得待ち時間
+    pub lock_wait: Histogram,
+    /// invokeで受け付けてからコマンドが完了するまでの時間
+    pub command_duration: Histogram,
+    /// 閾値を超えたコマンドの回数
+    pub slow_commands: AtomicU64,
+    /// 完全性検証のためのCRC32Cの計算
+    pub checksum_crc32c: ChecksumMetrics,
+    /// 完全性検証のためのSHA-256の計算
+    pub checksum_sha256: ChecksumMetrics,
+}
+
+impl MetricsRegistry {
+    const fn new() -> Self {
+        Self {
+            part_upload_duration: Histogram::new(),
+            part_retries: AtomicU64::new(0),
+            upload_retries: AtomicU64::new(0),
+            s3_api_calls: AtomicU64::new(0),
+            s3_api_failures: AtomicU64::new(0),
+            queue_wait: Histogram::new(),
+            lock_wait: Histogram::new(),
+            command_duration: Histogram::new(),
+            slow_commands: AtomicU64::new(0),
+            checksum_crc32c: ChecksumMetrics::new(),
+            checksum_sha256: ChecksumMetrics::new(),
+        }
+    }
+
+    pub fn increment(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// S3 APIの呼び出し結果を記録
+    pub fn record_s3_call(&self, success: bool) {
+        Self::increment(&self.s3_api_calls);
+        if !success {
+            Self::increment(&self.s3_api_failures);
+        }
+    }
+
+    /// チェックサムの計算を記録
+    pub fn record_checksum(&self, algorithm: ChecksumAlgorithm, bytes: u64, elapsed: Duration) {
+        match algorithm {
+            ChecksumAlgorithm::None => {}
+            ChecksumAlgorithm::Crc32c => self.checksum_crc32c.record(bytes, elapsed),
+            ChecksumAlgorithm::Sha256 => self.checksum_sha256.record(bytes, elapsed),
+        }
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            detailed_histograms: DETAILED_HISTOGRAMS.load(Ordering::Relaxed),
+            part_upload_duration: self.part_upload_duration.snapshot(),
+            part_retries: self.part_retries.load(Ordering::Relaxed),
+            upload_retries: self.upload_retries.load(Ordering::Relaxed),
+            s3_api_calls: self.s3_api_calls.load(Ordering::Relaxed),
+            s3_api_failures: self.s3_api_failures.load(Ordering::Relaxed),
+            queue_wait: self.queue_wait.snapshot(),
+            lock_wait: self.lock_wait.snapshot(),
+            command_duration: self.command_duration.snapshot(),
+            slow_commands: self.slow_commands.load(Ordering::Relaxed),
+            checksum_crc32c: self.checksum_crc32c.snapshot(),
+            checksum_sha256: self.checksum_sha256.snapshot(),
+        }
+    }
+
+    pub fn reset(&self) {
+        self.part_upload_duration.reset();
+        self.part_retries.store(0, Ordering::Relaxed);
+        self.upload_retries.store(0, Ordering::Relaxed);
+        self.s3_api_calls.store(0, Ordering::Relaxed);
+        self.s3_api_failures.store(0, Ordering::Relaxed);
+        self.queue_wait.reset();
+        self.lock_wait.reset();
+        self.command_duration.reset();
+        self.slow_commands.store(0, Ordering::Relaxed);
+        self.checksum_crc32c.reset();
+        self.checksum_sha256.reset();
+    }
+}
+
+/// ヒストグラムのバケット（le_msがNoneは上限なし）
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct HistogramBucket {
+    pub le_ms: Option<u64>,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct HistogramSnapshot {
+    pub count: u64,
+    pub mean_ms: f64,
+    pub max_ms: f64,
+    /// 詳細ヒストグラムが無効な場合はNone
+    pub buckets: Option<Vec<HistogramBucket>>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ChecksumMetricsSnapshot {
+    pub duration: HistogramSnapshot,
+    pub bytes: u64,
+    /// 計算時間の合計から求めたスループット（MB/s）
+    pub throughput_mbps: f64,
+}
+
+/// get_metricsで返すスナップショット
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MetricsSnapshot {
+    pub detailed_histograms: bool,
+    pub part_upload_duration: HistogramSnapshot,
+    pub part_retries: u64,
+    pub upload_retries: u64,
+    pub s3_api_calls: u64,
+    pub s3_api_failures: u64,
+    pub queue_wait: HistogramSnapshot,
+    pub lock_wait: HistogramSnapshot,
+    pub command_duration: HistogramSnapshot,
+    pub slow_commands: u64,
+    pub checksum_crc32c: ChecksumMetricsSnapshot,
+    pub checksum_sha256: ChecksumMetricsSnapshot,
+}
+
+/// ロックを取得し、取得までの待ち時間を記録する
+pub fn timed_lock<T>(mutex: &Mutex<T>) -> LockResult<MutexGuard<'_, T>> {
+    let started = Instant::now();
+    let guard = mutex.lock();
+    METRICS.lock_wait.record(started.elapsed());
+    guard
+}
+
+type S3Future<'a, T> = Pin<Box<dyn Future<Output = Result<T, String>> + Send + 'a>>;
+
+/// S3 APIの呼び出し回数・失敗回数を記録するクライアントのラッパー
+pub struct MeteredS3Client {
+    inner: Box<dyn S3ClientTrait>,
+}
+
+impl MeteredS3Client {
+    pub fn new(inner: Box<dyn S3ClientTrait>) -> Self {
+        Self { inner }
+    }
+}
+
+fn metered<'a, T: Send + 'a>(kind: S3ApiKind, future: S3Future<'a, T>) -> S3Future<'a, T> {
+    Box::pin(async move {
+        let result = future.await;
+        METRICS.record_s3_call(result.is_ok());
+        // 失敗したリクエストも課金対象になるため結果に関係なく数える
+        record_api_call(kind);
+        result
+    })
+}
+
+/// 成功したアップロードの送信量を月次の転送量として記録する
+fn metered_upload<'a, T: Send + 'a>(kind: S3ApiKind, bytes: u64, future: S3Future<'a, T>) -> S3Future<'a, T> {
+    Box::pin(async move {
+        let result = metered(kind, future).await;
+        if result.is_ok() {
+            record_transfer(TransferDirection::Upload, bytes);
+        }
+        result
+    })
+}
+
+/// 本体を読み出した分を月次の転送量として記録する
+fn metered_download<'a>(future: S3Future<'a, S3ObjectStream>) -> S3Future<'a, S3ObjectStream> {
+    Box::pin(async move { metered(S3ApiKind::GetObject, future).await.map(count_download) })
+}
+
+impl S3ClientTrait for MeteredS3Client {
+    fn list_objects<'a>(&'a self, bucket: &'a str, prefix: Option<&'a str>) -> S3Future<'a, Vec<S3Object>> {
+        metered(S3ApiKind::ListObjects, self.inner.list_objects(bucket, prefix))
+    }
+    fn get_object<'a>(&'a self, bucket: &'a str, key: &'a str) -> S3Future<'a, S3ObjectStream> {
+        metered_download(self.inner.get_object(bucket, key))
+    }
+    fn put_object<'a>(&'a self, bucket: &'a str, key: &'a str, data: Vec<u8>) -> S3Future<'a, ()> {
+        metered_upload(S3ApiKind::PutObject, data.len() as u64, self.inner.put_object(bucket, key, data))
+    }
+    fn delete_object<'a>(&'a self, bucket: &'a str, key: &'a str) -> S3Future<'a, ()> {
+        metered(S3ApiKind::DeleteObject, self.inner.delete_object(bucket, key))
+    }
+    fn restore_object<'a>(&'a self, bucket: &'a str, key: &'a str, tier: &'a str, days: u32, version_id: Option<&'a str>) -> S3Future<'a, ()> {
+        metered(S3ApiKind::RestoreObject, self.inner.restore_object(bucket, key, tier, days, version_id))
+    }
+    fn put_object_with_metadata<'a>(&'a self, bucket: &'a str, key: &'a str, data: Vec<u8>, metadata: HashMap<String, String>, storage_class: Option<&'a str>) -> S3Future<'a, ()> {
+        metered_upload(S3ApiKind::PutObject, data.len() as u64, self.inner.put_object_with_metadata(bucket, key, data, metadata, storage_class))
+    }
+    fn put_object_with_checksum<'a>(&'a self, bucket: &'a str, key: &'a str, data: Vec<u8>, metadata: HashMap<String, String>, storage_class: Option<&'a str>, checksum: &'a UploadChecksum) -> S3Future<'a, ()> {
+        metered_upload(S3ApiKind::PutObject, data.len() as u64, self.inner.put_object_with_checksum(bucket, key, data, metadata, storage_class, checksum))
+    }
+    fn head_object_metadata<'a>(&'a self, bucket: &'a str, key: &'a str) -> S3Future<'a, HashMap<String, String>> {
+        metered(S3ApiKind::HeadObject, self.inner.head_object_metadata(bucket, key))
+    }
+    fn head_object_info<'a>(&'a self, bucket: &'a str, key: &'a str) -> S3Future<'a, Option<ObjectHeadInfo>> {
+        metered(S3ApiKind::HeadObject, self.inner.head_object_info(bucket, key))
+    }
+    fn head_object_checksum<'a>(&'a self, bucket: &'a str, key: &'a str, algorithm: ChecksumAlgorithm) -> S3Future<'a, Option<String>> {
+        metered(S3ApiKind::HeadObject, self.inner.head_object_checksum(bucket, key, algorithm))
+    }
+    fn head_bucket<'a>(&'a self, bucket: &'a str) -> S3Future<'a, ()> {
+        metered(S3ApiKind::HeadBucket, self.inner.head_bucket(bucket))
+    }
+    fn create_multipart_upload<'a>(&'a self, bucket: &'a str, key: &'a str) -> S3Future<'a, String> {
+        metered(S3ApiKind::CreateMultipartUpload, self.inner.create_multipart_upload(bucket, key))
+    }
+    fn create_multipart_upload_with_metadata<'a>(&'a self, bucket: &'a str, key: &'a str, metadata: HashMap<String, String>, storage_class: Option<&'a str>) -> S3Future<'a, String> {
+        metered(S3ApiKind::CreateMultipartUpload, self.inner.create_multipart_upload_with_metadata(bucket, key, metadata, storage_class))
+    }
+    fn create_multipart_upload_with_checksum<'a>(&'a self, bucket: &'a str, key: &'a str, metadata: HashMap<String, String>, storage_class: Option<&'a str>, algorithm: ChecksumAlgorithm) -> S3Future<'a, String> {
+        metered(S3ApiKind::CreateMultipartUpload, self.inner.create_multipart_upload_with_checksum(bucket, key, metadata, storage_class, algorithm))
+    }
+    fn upload_part<'a>(&'a self, bucket: &'a str, key: &'a str, upload_id: &'a str, part_number: i32, data: Vec<u8>) -> S3Future<'a, String> {
+        metered_upload(S3ApiKind::UploadPart, data.len() as u64, self.inner.upload_part(bucket, key, upload_id, part_number, data))
+    }
+    fn upload_part_with_progress<'a>(&'a self, bucket: &'a str, key: &'a str, upload_id: &'a str, part_number: i32, data: Vec<u8>, progress: PartProgressHook) -> S3Future<'a, String> {
+        metered_upload(S3ApiKind::UploadPart, data.len() as u64, self.inner.upload_part_with_progress(bucket, key, upload_id, part_number, data, progress))
+    }
+    fn upload_part_with_checksum<'a>(&'a self, bucket: &'a str, key: &'a str, upload_id: &'a str, part: PartUpload, progress: PartProgressHook) -> S3Future<'a, String> {
+        metered_upload(S3ApiKind::UploadPart, part.data.len() as u64, self.inner.upload_part_with_checksum(bucket, key, upload_id, part, progress))
+    }
+    fn complete_multipart_upload<'a>(&'a self, bucket: &'a str, key: &'a str, upload_id: &'a str, parts: Vec<(i32, String)>) -> S3Future<'a, ()> {
+        metered(S3ApiKind::CompleteMultipartUpload, self.inner.complete_multipart_upload(bucket, key, upload_id, parts))
+    }
+    fn complete_multipart_upload_with_checksums<'a>(&'a self, bucket: &'a str, key: &'a str, upload_id: &'a str, algorithm: ChecksumAlgorithm, parts: Vec<(i32, String, String)>) -> S3Future<'a, ()> {
+        metered(S3ApiKind::CompleteMultipartUpload, self.inner.complete_multipart_upload_with_checksums(bucket, key, upload_id, algorithm, parts))
+    }
+    fn abort_multipart_upload<'a>(&'a self, bucket: &'a str, key: &'a str, upload_id: &'a str) -> S3Future<'a, ()> {
+        metered(S3ApiKind::AbortMultipartUpload, self.inner.abort_multipart_upload(bucket, key, upload_id))
+    }
+    fn get_bucket_lifecycle_configuration<'a>(&'a self, bucket: &'a str) -> S3Future<'a, Vec<LifecycleRule>> {
+        metered(S3ApiKind::BucketConfiguration, self.inner.get_bucket_lifecycle_configuration(bucket))
+    }
+    fn put_bucket_lifecycle_configuration<'a>(&'a self, bucket: &'a str, rules: Vec<LifecycleRule>) -> S3Future<'a, ()> {
+        metered(S3ApiKind::BucketConfiguration, self.inner.put_bucket_lifecycle_configuration(bucket, rules))
+    }
+    fn delete_bucket_lifecycle_configuration<'a>(&'a self, bucket: &'a str) -> S3Future<'a, ()> {
+        metered(S3ApiKind::BucketConfiguration, self.inner.delete_bucket_lifecycle_configuration(bucket))
+    }
+    fn get_bucket_location<'a>(&'a self, bucket: &'a str) -> S3Future<'a, String> {
+        metered(S3ApiKind::BucketConfiguration, self.inner.get_bucket_location(bucket))
+    }
+    fn get_bucket_versioning<'a>(&'a self, bucket: &'a str) -> S3Future<'a, String> {
+        metered(S3ApiKind::BucketConfiguration, self.inner.get_bucket_versioning(bucket))
+    }
+    fn list_object_versions<'a>(&'a self, bucket: &'a str, key: &'a str) -> S3Future<'a, Vec<S3ObjectVersion>> {
+        metered(S3ApiKind::ListObjects, self.inner.list_object_versions(bucket, key))
+    }
+    fn get_object_version<'a>(&'a self, bucket: &'a str, key: &'a str, version_id: &'a str) -> S3Future<'a, S3ObjectStream> {
+        metered_download(self.inner.get_object_version(bucket, key, version_id))
+    }
+    fn head_object_version_metadata<'a>(&'a self, bucket: &'a str, key: &'a str, version_id: &'a str) -> S3Future<'a, HashMap<String, String>> {
+        metered(S3ApiKind::HeadObject, self.inner.head_object_version_metadata(bucket, key, version_id))
+    }
+    fn head_object_archive_state<'a>(&'a self, bucket: &'a str, key: &'a str, version_id: Option<&'a str>) -> S3Future<'a, ObjectArchiveState> {
+        metered(S3ApiKind::HeadObject, self.inner.head_object_archive_state(bucket, key, version_id))
+    }
+    fn get_object_tagging<'a>(&'a self, bucket: &'a str, key: &'a str) -> S3Future<'a, HashMap<String, String>> {
+        metered(S3ApiKind::GetObjectTagging, self.inner.get_object_tagging(bucket, key))
+    }
+    fn put_object_tagging<'a>(&'a self, bucket: &'a str, key: &'a str, tags: HashMap<String, String>) -> S3Future<'a, ()> {
+        metered(S3ApiKind::PutObjectTagging, self.inner.put_object_tagging(bucket, key, tags))
+    }
+    fn copy_object_replace_metadata<'a>(&'a self, bucket: &'a str, key: &'a str, metadata: HashMap<String, String>) -> S3Future<'a, ()> {
+        // 実装は現在のContent-Type等を引き継ぐためHEADも呼ぶが、料金の大きいCOPYとして数える
+        metered(S3ApiKind::CopyObject, self.inner.copy_object_replace_metadata(bucket, key, metadata))
+    }
+    fn get_bucket_encryption<'a>(&'a self, bucket: &'a str) -> S3Future<'a, Option<String>> {
+        metered(S3ApiKind::BucketConfiguration, self.inner.get_bucket_encryption(bucket))
+    }
+    fn get_public_access_block<'a>(&'a self, bucket: &'a str) -> S3Future<'a, Option<PublicAccessBlock>> {
+        metered(S3ApiKind::BucketConfiguration, self.inner.get_public_access_block(bucket))
+    }
+    fn get_bucket_policy_is_public<'a>(&'a self, bucket: &'a str) -> S3Future<'a, Option<bool>> {
+        metered(S3ApiKind::BucketConfiguration, self.inner.get_bucket_policy_is_public(bucket))
+    }
+}
+
+pub fn set_detailed_histograms(enabled: bool) {
+    DETAILED_HISTOGRAMS.store(enabled, Ordering::Relaxed);
+}
+
+/// ログレベルがdebugの場合のみ詳細ヒストグラムを有効化
+pub fn refresh_metrics_settings(app: &AppHandle) {
+    if let Ok(config) = load_config(app) {
+        let debug = config.app_settings.log_level == "debug";
+        set_detailed_histograms(debug);
+        crate::commands::command_timing::set_command_timing_settings(config.app_settings.slow_command_threshold_ms, debug);
+    }
+}
+
+/// 内部メトリクスのスナップショットを取得
+#[command]
+pub async fn get_metrics() -> Result<MetricsSnapshot, AppError> {
+    Ok(METRICS.snapshot())
+}
+
+/// 内部メトリクスをクリア
+#[command]
+pub async fn reset_metrics() -> Result<(), AppError> {
+    METRICS.reset();
+    log::info!("Metrics reset");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_records_summary_and_buckets() {
+        let histogram = Histogram::new();
+        set_detailed_histograms(true);
+        histogram.record(Duration::from_millis(3));
+        histogram.record(Duration::from_millis(200));
+        histogram.record(Duration::from_secs(300));
+
+        let snapshot = histogram.snapshot();
+        assert_eq!(snapshot.count, 3);
+        assert_eq!(snapshot.max_ms, 300_000.0);
+        let buckets = snapshot.buckets.unwrap();
+        assert_eq!(buckets.len(), BUCKET_COUNT);
+        assert_eq!(buckets[1], HistogramBucket { le_ms: Some(5), count: 1 });
+        assert_eq!(buckets[6], HistogramBucket { le_ms: Some(250), count: 1 });
+        assert_eq!(buckets[BUCKET_COUNT - 1], HistogramBucket { le_ms: None, count: 1 });
+
+        histogram.reset();
+        assert_eq!(histogram.snapshot().count, 0);
+    }
+
+    #[test]
+    fn test_registry_counts_s3_calls() {
+        let registry = MetricsRegistry::new();
+        registry.record_s3_call(true);
+        registry.record_s3_call(false);
+        MetricsRegistry::increment(&registry.part_retries);
+
+        registry.record_checksum(ChecksumAlgorithm::Crc32c, 4 * 1024 * 1024, Duration::from_millis(2));
+        registry.record_checksum(ChecksumAlgorithm::None, 1024, Duration::from_millis(1));
+
+        let snapshot = registry.snapshot();
+        assert_eq!((snapshot.s3_api_calls, snapshot.s3_api_failures, snapshot.part_retries), (2, 1, 1));
+        assert_eq!((snapshot.checksum_crc32c.duration.count, snapshot.checksum_crc32c.bytes), (1, 4 * 1024 * 1024));
+        assert!((snapshot.checksum_crc32c.throughput_mbps - 2000.0).abs() < 1e-6);
+        assert_eq!(snapshot.checksum_sha256.duration.count, 0);
+        registry.reset();
+        assert_eq!(registry.snapshot().s3_api_calls, 0);
+    }
+}
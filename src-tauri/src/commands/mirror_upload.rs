@@ -0,0 +1,293 @@
+use std::time::Instant;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter, State};
+use tokio::sync::mpsc;
+use tracing::Instrument;
+
+use crate::commands::audit::{record_operation, AuditOperation, OperationAuditEntry};
+use crate::commands::aws_auth::{load_aws_credentials_secure, AwsCredentials};
+use crate::commands::aws_operations::create_s3_client_for_credentials;
+use crate::commands::credential_cache::cached_credentials;
+use crate::commands::upload_system::{
+    item_not_found, queue_lock_error, upload_file_to_s3_with_labels, UploadConfig, UploadItem, UploadItemLabels,
+    UploadQueueChanged, UploadQueueState, UploadStatus, UPLOAD_QUEUE_CHANGED_EVENT,
+};
+use crate::internal::{AppError, ErrorCode, InternalError};
+use crate::internal::i18n::MessageKey;
+
+/// プライマリとは別にバックアップとしてアップロードするミラー先
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MirrorTarget {
+    pub bucket: String,
+    pub region: String,
+    /// 認証情報を読み込むプロファイル（未設定はプライマリと同じ認証情報）
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// プライマリのS3キーの前に付けるプレフィックス
+    #[serde(default)]
+    pub prefix: Option<String>,
+}
+
+impl MirrorTarget {
+    /// プライマリのキーに対応するミラー先のキー
+    pub fn s3_key(&self, primary_key: &str) -> String {
+        match self.prefix.as_deref().map(|prefix| prefix.trim_matches('/')).filter(|prefix| !prefix.is_empty()) {
+            Some(prefix) => format!("{}/{}", prefix, primary_key),
+            None => primary_key.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MirrorStatus {
+    InProgress,
+    Completed,
+    Failed,
+}
+
+/// ミラー先ごとのアップロード結果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MirrorResult {
+    pub target: MirrorTarget,
+    /// ミラー先のキー（圧縮してアップロードした場合は".zst"付き）
+    pub s3_key: String,
+    pub status: MirrorStatus,
+    pub error: Option<String>,
+    pub attempts: u32,
+    pub completed_at: Option<String>,
+}
+
+impl MirrorResult {
+    fn in_progress(target: MirrorTarget, s3_key: String, attempts: u32) -> Self {
+        Self { target, s3_key, status: MirrorStatus::InProgress, error: None, attempts, completed_at: None }
+    }
+}
+
+/// ミラー結果を反映し、アイテムの状態を更新（失敗が残っていればWarning、すべて成功ならCompleted）
+///
+/// プライマリが完了していないアイテムの状態は変えない
+pub fn apply_mirror_results(item: &mut UploadItem, results: Vec<MirrorResult>) {
+    for result in results {
+        match item.mirrors.iter_mut().find(|mirror| mirror.target.bucket == result.target.bucket) {
+            Some(mirror) => *mirror = result,
+            None => item.mirrors.push(result),
+        }
+    }
+    if !matches!(item.status, UploadStatus::Completed | UploadStatus::Warning) {
+        return;
+    }
+    let failed = item.mirrors.iter().filter(|mirror| mirror.status == MirrorStatus::Failed).count();
+    if failed > 0 {
+        item.status = UploadStatus::Warning;
+        item.error_message = Some(format!("{} of {} mirror upload(s) failed", failed, item.mirrors.len()));
+    } else if item.mirrors.iter().all(|mirror| mirror.status == MirrorStatus::Completed) {
+        item.status = UploadStatus::Completed;
+        item.error_message = None;
+    }
+}
+
+/// ミラー先の認証情報（プロファイル指定時はキーチェーン、未指定はキャッシュから読み込み、リージョンはミラー先に合わせる）
+async fn mirror_credentials(app: &AppHandle, target: &MirrorTarget) -> Result<AwsCredentials, String> {
+    let mut credentials = match &target.profile {
+        Some(profile) => load_aws_credentials_secure(profile.clone()).await?,
+        None => cached_credentials(app).map_err(|e| e.to_string())?,
+    };
+    credentials.region = target.region.clone();
+    Ok(credentials)
+}
+
+async fn upload_to_mirror(
+    app: &AppHandle,
+    config: &UploadConfig,
+    item: &UploadItem,
+    mut result: MirrorResult,
+) -> MirrorResult {
+    let started_at = Instant::now();
+    let labels = UploadItemLabels::from_item(item);
+    // 進捗はプライマリのアイテムへ反映しない
+    let (progress_tx, mut progress_rx) = mpsc::channel(100);
+    tokio::spawn(async move { while progress_rx.recv().await.is_some() {} });
+
+    let uploaded = async {
+        let credentials = mirror_credentials(app, &result.target).await?;
+        let s3_client = create_s3_client_for_credentials(&credentials).await?;
+        let mirror_config = UploadConfig {
+            region: credentials.region.clone(),
+            bucket_name: result.target.bucket.clone(),
+            // メタデータはプライマリのアップロードで作成済み
+            auto_create_metadata: false,
+            ..config.clone()
+        };
+        upload_file_to_s3_with_labels(
+            s3_client.as_ref(),
+            item.file_path.clone(),
+            result.s3_key.clone(),
+            mirror_config,
+            progress_tx,
+            format!("{}:mirror:{}", item.id, result.target.bucket),
+            &labels,
+        ).await
+    }.await;
+
+    record_operation(
+        app,
+        OperationAuditEntry::new(AuditOperation::MirrorUpload, &uploaded)
+            .local_path(item.file_path.clone())
+            .s3_object(result.target.bucket.clone(), result.s3_key.clone())
+            .size(item.file_size)
+            .duration(started_at.elapsed())
+            .project_id(item.project_id.clone()),
+    );
+    match uploaded {
+        Ok(outcome) => {
+            tracing::info!("Mirrored {} to s3://{}/{}", item.file_name, result.target.bucket, outcome.s3_key);
+            result.s3_key = outcome.s3_key;
+            result.status = MirrorStatus::Completed;
+            result.error = None;
+            result.completed_at = Some(chrono::Utc::now().to_rfc3339());
+        }
+        Err(e) => {
+            tracing::error!("Mirror upload failed for {} to {}: {}", item.file_name, result.target.bucket, e);
+            result.status = MirrorStatus::Failed;
+            result.error = Some(e);
+        }
+    }
+    result
+}
+
+fn update_mirrors(app: &AppHandle, queue_state: &UploadQueueState, item_id: &str, results: Vec<MirrorResult>) {
+    match queue_state.lock() {
+        Ok(mut queue) => {
+            if let Some(item) = queue.items.iter_mut().find(|item| item.id == item_id) {
+                apply_mirror_results(item, results);
+            }
+        }
+        Err(e) => {
+            tracing::error!("Failed to lock upload queue for mirror results: {}", e);
+            return;
+        }
+    }
+    if let Err(e) = app.emit(UPLOAD_QUEUE_CHANGED_EVENT, UploadQueueChanged { reason: "mirrored", item_ids: vec![item_id.to_string()] }) {
+        tracing::error!("Failed to emit upload queue changed event: {}", e);
+    }
+}
+
+/// ミラー先へ順にアップロードし、結果をアイテムへ記録
+pub(crate) async fn run_mirror_uploads(
+    app: &AppHandle,
+    queue_state: &UploadQueueState,
+    config: &UploadConfig,
+    item: &UploadItem,
+    pending: Vec<MirrorResult>,
+) {
+    if pending.is_empty() {
+        return;
+    }
+    update_mirrors(app, queue_state, &item.id, pending.clone());
+    let mut results = Vec::with_capacity(pending.len());
+    for mirror in pending {
+        results.push(upload_to_mirror(app, config, item, mirror).await);
+    }
+    let failed: Vec<&str> = results.iter()
+        .filter(|result| result.status == MirrorStatus::Failed)
+        .map(|result| result.target.bucket.as_str())
+        .collect();
+    if !failed.is_empty() {
+        crate::notifications::notify_error(
+            app,
+            "ミラーへのアップロードに失敗しました",
+            &format!("{}: {}", item.file_name, failed.join(", ")),
+        );
+    }
+    update_mirrors(app, queue_state, &item.id, results);
+}
+
+/// プライマリの完了後に実行するミラー（要求したキーは圧縮前のもの）
+pub(crate) fn initial_mirrors(targets: &[MirrorTarget], requested_key: &str) -> Vec<MirrorResult> {
+    targets.iter()
+        .map(|target| MirrorResult::in_progress(target.clone(), target.s3_key(requested_key), 1))
+        .collect()
+}
+
+/// Warning状態のアイテムの失敗したミラーのみを再実行（バックグラウンドで実行し、対象の件数を返す）
+#[command]
+pub async fn retry_mirror(
+    app_handle: AppHandle,
+    item_id: String,
+    queue_state: State<'_, UploadQueueState>,
+) -> Result<usize, AppError> {
+    let (item, config, pending) = {
+        let mut queue = queue_state.lock().map_err(queue_lock_error)?;
+        let config = queue.config.clone()
+            .ok_or_else(|| InternalError::localized(ErrorCode::Config, MessageKey::UploadQueueNotInitialized))?;
+        let item = queue.items.iter_mut()
+            .find(|item| item.id == item_id)
+            .ok_or_else(|| item_not_found(&item_id))?;
+        let pending: Vec<MirrorResult> = item.mirrors.iter()
+            .filter(|mirror| mirror.status == MirrorStatus::Failed)
+            .map(|mirror| MirrorResult::in_progress(mirror.target.clone(), mirror.s3_key.clone(), mirror.attempts + 1))
+            .collect();
+        if item.status != UploadStatus::Warning || pending.is_empty() {
+            return Err(InternalError::Config(format!("Upload item has no failed mirror to retry: {}", item_id)).into());
+        }
+        if !std::path::Path::new(&item.file_path).exists() {
+            return Err(InternalError::File(format!("File does not exist: {}", item.file_path)).into());
+        }
+        // 再実行中に重ねて呼ばれても同じミラーを二重に送らないよう、ロック中に進行中へ変える
+        apply_mirror_results(item, pending.clone());
+        (item.clone(), config, pending)
+    };
+
+    let count = pending.len();
+    tracing::info!("Retrying {} mirror upload(s) for {}", count, item_id);
+    let queue_state = queue_state.inner().clone();
+    let span = tracing::info_span!("retry_mirror", item_id = %item.id, file_name = %item.file_name, s3_key = %item.s3_key);
+    tauri::async_runtime::spawn(async move {
+        run_mirror_uploads(&app_handle, &queue_state, &config, &item, pending).await;
+    }.instrument(span));
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(bucket: &str, prefix: Option<&str>) -> MirrorTarget {
+        MirrorTarget {
+            bucket: bucket.to_string(),
+            region: "us-west-2".to_string(),
+            profile: None,
+            prefix: prefix.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_mirror_key_prepends_prefix() {
+        assert_eq!(target("backup", Some("/mirror/")).s3_key("uploads/a.mov"), "mirror/uploads/a.mov");
+        assert_eq!(target("backup", Some("")).s3_key("uploads/a.mov"), "uploads/a.mov");
+    }
+
+    #[test]
+    fn test_failed_mirror_marks_item_warning_until_retried() {
+        let mut item = UploadItem {
+            id: "item".to_string(),
+            file_size: 10,
+            ..UploadItem::for_test("/footage/a.mov", UploadStatus::Completed)
+        };
+        let mut mirrors = initial_mirrors(&[target("backup-a", None), target("backup-b", None)], "a.mov");
+        apply_mirror_results(&mut item, mirrors.clone());
+        assert_eq!(item.status, UploadStatus::Completed);
+
+        mirrors[0].status = MirrorStatus::Completed;
+        mirrors[1].status = MirrorStatus::Failed;
+        apply_mirror_results(&mut item, mirrors.clone());
+        assert_eq!(item.status, UploadStatus::Warning);
+        assert!(item.error_message.is_some());
+
+        mirrors[1].status = MirrorStatus::Completed;
+        apply_mirror_results(&mut item, vec![mirrors[1].clone()]);
+        assert_eq!(item.status, UploadStatus::Completed);
+        assert_eq!(item.mirrors.len(), 2);
+    }
+}
@@ -0,0 +1,398 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use serde::Serialize;
+use tauri::{command, AppHandle};
+
+use crate::commands::aws_operations::{LifecycleRule, LifecycleTransition, ObjectHeadInfo, S3ClientTrait, S3Object, S3ObjectStream, S3ObjectVersion};
+use crate::commands::config::load_config;
+use crate::commands::part_progress::{PartProgressHook, PART_PROGRESS_STEP_BYTES};
+
+/// モックAWSモードのレスポンスに付与する表示ラベル
+pub const MOCK_LABEL: &str = "[MOCK]";
+/// シミュレーションの既定の転送速度
+pub const DEFAULT_MOCK_SPEED_MBPS: f64 = 50.0;
+/// シミュレーションで返す現在のバージョンID
+const MOCK_CURRENT_VERSION_ID: &str = "mock-current";
+/// 各API呼び出しに加える疑似レイテンシ
+const MOCK_API_LATENCY: Duration = Duration::from_millis(80);
+
+static MOCK_AWS_ENABLED: AtomicBool = AtomicBool::new(false);
+/// f64のビット表現で保持する転送速度（0は既定値）
+static MOCK_SPEED_BITS: AtomicU64 = AtomicU64::new(0);
+
+/// モックAWSモードの状態
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MockAwsStatus {
+    pub enabled: bool,
+    pub simulated_speed_mbps: f64,
+    pub label: String,
+}
+
+/// モックAWSモードが有効か
+pub fn is_mock_aws_mode() -> bool {
+    MOCK_AWS_ENABLED.load(Ordering::SeqCst)
+}
+
+/// シミュレーションの転送速度（MB/s）
+pub fn mock_speed_mbps() -> f64 {
+    match MOCK_SPEED_BITS.load(Ordering::SeqCst) {
+        0 => DEFAULT_MOCK_SPEED_MBPS,
+        bits => f64::from_bits(bits),
+    }
+}
+
+pub fn set_mock_aws_mode(enabled: bool, speed_mbps: f64) {
+    let bits = if speed_mbps > 0.0 { speed_mbps.to_bits() } else { 0 };
+    MOCK_SPEED_BITS.store(bits, Ordering::SeqCst);
+    let was_enabled = MOCK_AWS_ENABLED.swap(enabled, Ordering::SeqCst);
+    if enabled && !was_enabled {
+        log::warn!("{} Mock AWS mode enabled: no requests will be sent to AWS", MOCK_LABEL);
+    } else if !enabled && was_enabled {
+        log::info!("Mock AWS mode disabled");
+    }
+}
+
+/// 設定ファイルの内容をモックAWSモードとトレイ表示へ反映
+pub fn refresh_mock_aws_mode(app: &AppHandle) {
+    if let Ok(config) = load_config(app) {
+        set_mock_aws_mode(config.mock_aws_mode, config.mock_aws_speed_mbps);
+    }
+    crate::tray::sync_mock_aws_indicator(app, is_mock_aws_mode());
+}
+
+/// モックAWSモード時のみメッセージにラベルを付与
+pub fn label_message(message: impl Into<String>) -> String {
+    let message = message.into();
+    if is_mock_aws_mode() {
+        format!("{} {}", MOCK_LABEL, message)
+    } else {
+        message
+    }
+}
+
+/// モックAWSモードでS3の代わりに使うオブジェクトストア（プロセス内で共有）
+#[derive(Debug, Default)]
+struct MockStore {
+    objects: BTreeMap<String, S3Object>,
+    multipart_uploads: HashMap<String, u64>,
+    lifecycle_rules: Option<Vec<LifecycleRule>>,
+}
+
+fn mock_store() -> &'static Mutex<MockStore> {
+    static STORE: OnceLock<Mutex<MockStore>> = OnceLock::new();
+    STORE.get_or_init(|| {
+        let objects = sample_objects()
+            .into_iter()
+            .map(|object| (object.key.clone(), object))
+            .collect();
+        Mutex::new(MockStore { objects, ..MockStore::default() })
+    })
+}
+
+fn mock_object(key: &str, size: u64, storage_class: &str) -> S3Object {
+    S3Object {
+        key: key.to_string(),
+        size,
+        last_modified: chrono::Utc::now().to_rfc3339(),
+        storage_class: storage_class.to_string(),
+        etag: format!("mock-{:x}", size),
+    }
+}
+
+/// 一覧表示用のサンプルデータ
+fn sample_objects() -> Vec<S3Object> {
+    const MB: u64 = 1024 * 1024;
+    vec![
+        mock_object("mock-demo/2024/01/interview_a_cam.mov", 4200 * MB, "DEEP_ARCHIVE"),
+        mock_object("mock-demo/2024/01/interview_b_cam.mov", 3900 * MB, "DEEP_ARCHIVE"),
+        mock_object("mock-demo/2024/02/city_council_full.mp4", 12800 * MB, "DEEP_ARCHIVE"),
+        mock_object("mock-demo/2024/02/broll_downtown.mxf", 860 * MB, "STANDARD"),
+        mock_object("mock-demo/projects/festival.prproj", 24 * MB, "STANDARD"),
+    ]
+}
+
+/// 本物のAWSへ一切アクセスしないS3クライアント
+///
+/// 転送量に応じて待機することでアップロード・ダウンロードの進捗をシミュレートする
+pub struct SimulatedS3Client {
+    speed_mbps: f64,
+}
+
+impl SimulatedS3Client {
+    pub fn new(speed_mbps: f64) -> Self {
+        Self { speed_mbps: if speed_mbps > 0.0 { speed_mbps } else { DEFAULT_MOCK_SPEED_MBPS } }
+    }
+
+    /// 現在のモックAWSモード設定でクライアントを作成
+    pub fn from_settings() -> Self {
+        Self::new(mock_speed_mbps())
+    }
+
+    fn transfer_delay(&self, bytes: usize) -> Duration {
+        MOCK_API_LATENCY + Duration::from_secs_f64(bytes as f64 / (self.speed_mbps * 1024.0 * 1024.0))
+    }
+
+    fn store(&self) -> std::sync::MutexGuard<'static, MockStore> {
+        mock_store().lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+type MockFuture<'a, T> = std::pin::Pin<Box<dyn std::future::Future<Output = Result<T, String>> + Send + 'a>>;
+
+impl S3ClientTrait for SimulatedS3Client {
+    fn list_objects<'a>(&'a self, _bucket: &'a str, prefix: Option<&'a str>) -> MockFuture<'a, Vec<S3Object>> {
+        Box::pin(async move {
+            tokio::time::sleep(MOCK_API_LATENCY).await;
+            let store = self.store();
+            Ok(store.objects
+                .values()
+                .filter(|object| prefix.map_or(true, |p| object.key.starts_with(p)))
+                .cloned()
+                .collect())
+        })
+    }
+
+    fn get_object<'a>(&'a self, _bucket: &'a str, key: &'a str) -> MockFuture<'a, S3ObjectStream> {
+        Box::pin(async move {
+            let content = format!("{} ReelVault mock object: {}\n", MOCK_LABEL, key).into_bytes();
+            tokio::time::sleep(self.transfer_delay(content.len())).await;
+            Ok(S3ObjectStream::from_bytes(content))
+        })
+    }
+
+    fn put_object<'a>(&'a self, _bucket: &'a str, key: &'a str, data: Vec<u8>) -> MockFuture<'a, ()> {
+        Box::pin(async move {
+            tokio::time::sleep(self.transfer_delay(data.len())).await;
+            self.store().objects.insert(key.to_string(), mock_object(key, data.len() as u64, "DEEP_ARCHIVE"));
+            log::info!("{} Simulated upload: {} ({} bytes)", MOCK_LABEL, key, data.len());
+            Ok(())
+        })
+    }
+
+    fn get_object_tagging<'a>(&'a self, _bucket: &'a str, _key: &'a str) -> MockFuture<'a, HashMap<String, String>> {
+        Box::pin(async move {
+            tokio::time::sleep(MOCK_API_LATENCY).await;
+            Ok(HashMap::new())
+        })
+    }
+
+    fn put_object_tagging<'a>(&'a self, _bucket: &'a str, key: &'a str, tags: HashMap<String, String>) -> MockFuture<'a, ()> {
+        Box::pin(async move {
+            tokio::time::sleep(MOCK_API_LATENCY).await;
+            log::info!("{} Simulated tagging: {} ({} tag(s))", MOCK_LABEL, key, tags.len());
+            Ok(())
+        })
+    }
+
+    fn copy_object_replace_metadata<'a>(&'a self, _bucket: &'a str, key: &'a str, metadata: HashMap<String, String>) -> MockFuture<'a, ()> {
+        Box::pin(async move {
+            tokio::time::sleep(MOCK_API_LATENCY).await;
+            log::info!("{} Simulated metadata update: {} ({} field(s))", MOCK_LABEL, key, metadata.len());
+            Ok(())
+        })
+    }
+
+    fn delete_object<'a>(&'a self, _bucket: &'a str, key: &'a str) -> MockFuture<'a, ()> {
+        Box::pin(async move {
+            tokio::time::sleep(MOCK_API_LATENCY).await;
+            self.store().objects.remove(key);
+            Ok(())
+        })
+    }
+
+    fn restore_object<'a>(&'a self, _bucket: &'a str, key: &'a str, tier: &'a str, days: u32, _version_id: Option<&'a str>) -> MockFuture<'a, ()> {
+        Box::pin(async move {
+            tokio::time::sleep(MOCK_API_LATENCY).await;
+            log::info!("{} Simulated restore request: {} (tier: {}, days: {})", MOCK_LABEL, key, tier, days);
+            Ok(())
+        })
+    }
+
+    fn head_object_info<'a>(&'a self, _bucket: &'a str, key: &'a str) -> MockFuture<'a, Option<ObjectHeadInfo>> {
+        Box::pin(async move {
+            tokio::time::sleep(MOCK_API_LATENCY).await;
+            Ok(self.store().objects.get(key).map(|object| ObjectHeadInfo {
+                size: object.size,
+                metadata: HashMap::new(),
+                etag: None,
+            }))
+        })
+    }
+
+    fn head_bucket<'a>(&'a self, _bucket: &'a str) -> MockFuture<'a, ()> {
+        Box::pin(async move {
+            tokio::time::sleep(MOCK_API_LATENCY).await;
+            Ok(())
+        })
+    }
+
+    fn create_multipart_upload<'a>(&'a self, _bucket: &'a str, key: &'a str) -> MockFuture<'a, String> {
+        Box::pin(async move {
+            tokio::time::sleep(MOCK_API_LATENCY).await;
+            let upload_id = format!("mock-upload-{}", uuid::Uuid::new_v4());
+            self.store().multipart_uploads.insert(upload_id.clone(), 0);
+            log::info!("{} Simulated multipart upload started: {}", MOCK_LABEL, key);
+            Ok(upload_id)
+        })
+    }
+
+    fn upload_part<'a>(&'a self, _bucket: &'a str, _key: &'a str, upload_id: &'a str, part_number: i32, data: Vec<u8>) -> MockFuture<'a, String> {
+        Box::pin(async move {
+            tokio::time::sleep(self.transfer_delay(data.len())).await;
+            if let Some(total) = self.store().multipart_uploads.get_mut(upload_id) {
+                *total += data.len() as u64;
+            }
+            Ok(format!("mock-etag-{}", part_number))
+        })
+    }
+
+    fn upload_part_with_progress<'a>(&'a self, _bucket: &'a str, _key: &'a str, upload_id: &'a str, part_number: i32, data: Vec<u8>, progress: PartProgressHook) -> MockFuture<'a, String> {
+        Box::pin(async move {
+            // 実際の送信と同様にパート内の進捗を少しずつ通知する
+            tokio::time::sleep(MOCK_API_LATENCY).await;
+            let part_bytes = data.len() as u64;
+            let mut sent = 0u64;
+            while sent < part_bytes {
+                let step = PART_PROGRESS_STEP_BYTES.min(part_bytes - sent);
+                tokio::time::sleep(self.transfer_delay(step as usize).saturating_sub(MOCK_API_LATENCY)).await;
+                sent += step;
+                progress(sent);
+            }
+            if let Some(total) = self.store().multipart_uploads.get_mut(upload_id) {
+                *total += part_bytes;
+            }
+            Ok(format!("mock-etag-{}", part_number))
+        })
+    }
+
+    fn complete_multipart_upload<'a>(&'a self, _bucket: &'a str, key: &'a str, upload_id: &'a str, _parts: Vec<(i32, String)>) -> MockFuture<'a, ()> {
+        Box::pin(async move {
+            tokio::time::sleep(MOCK_API_LATENCY).await;
+            let mut store = self.store();
+            let size = store.multipart_uploads.remove(upload_id).unwrap_or(0);
+            store.objects.insert(key.to_string(), mock_object(key, size, "DEEP_ARCHIVE"));
+            Ok(())
+        })
+    }
+
+    fn abort_multipart_upload<'a>(&'a self, _bucket: &'a str, _key: &'a str, upload_id: &'a str) -> MockFuture<'a, ()> {
+        Box::pin(async move {
+            self.store().multipart_uploads.remove(upload_id);
+            Ok(())
+        })
+    }
+
+    fn get_bucket_lifecycle_configuration<'a>(&'a self, _bucket: &'a str) -> MockFuture<'a, Vec<LifecycleRule>> {
+        Box::pin(async move {
+            tokio::time::sleep(MOCK_API_LATENCY).await;
+            Ok(self.store().lifecycle_rules.clone().unwrap_or_else(|| vec![LifecycleRule {
+                id: "ReelVault-Default-Auto-Archive".to_string(),
+                status: "Enabled".to_string(),
+                prefix: Some(String::new()),
+                transitions: vec![LifecycleTransition {
+                    days: 1,
+                    storage_class: "DEEP_ARCHIVE".to_string(),
+                }],
+            }]))
+        })
+    }
+
+    fn put_bucket_lifecycle_configuration<'a>(&'a self, _bucket: &'a str, rules: Vec<LifecycleRule>) -> MockFuture<'a, ()> {
+        Box::pin(async move {
+            self.store().lifecycle_rules = Some(rules);
+            Ok(())
+        })
+    }
+
+    fn delete_bucket_lifecycle_configuration<'a>(&'a self, _bucket: &'a str) -> MockFuture<'a, ()> {
+        Box::pin(async move {
+            self.store().lifecycle_rules = Some(Vec::new());
+            Ok(())
+        })
+    }
+
+    fn get_bucket_location<'a>(&'a self, _bucket: &'a str) -> MockFuture<'a, String> {
+        Box::pin(async move { Ok("mock-region-1".to_string()) })
+    }
+
+    fn get_bucket_versioning<'a>(&'a self, _bucket: &'a str) -> MockFuture<'a, String> {
+        Box::pin(async move {
+            tokio::time::sleep(MOCK_API_LATENCY).await;
+            Ok("Enabled".to_string())
+        })
+    }
+
+    /// シミュレーションでは旧バージョンを保持しないため、現在のオブジェクトのみを返す
+    fn list_object_versions<'a>(&'a self, _bucket: &'a str, key: &'a str) -> MockFuture<'a, Vec<S3ObjectVersion>> {
+        Box::pin(async move {
+            tokio::time::sleep(MOCK_API_LATENCY).await;
+            Ok(self.store().objects.get(key)
+                .map(|object| S3ObjectVersion {
+                    key: object.key.clone(),
+                    version_id: MOCK_CURRENT_VERSION_ID.to_string(),
+                    size: object.size,
+                    last_modified: object.last_modified.clone(),
+                    is_latest: true,
+                    is_delete_marker: false,
+                    storage_class: Some(object.storage_class.clone()),
+                    etag: Some(object.etag.clone()),
+                })
+                .into_iter()
+                .collect())
+        })
+    }
+
+    fn get_object_version<'a>(&'a self, bucket: &'a str, key: &'a str, _version_id: &'a str) -> MockFuture<'a, S3ObjectStream> {
+        self.get_object(bucket, key)
+    }
+}
+
+/// モックAWSモードの状態を取得
+#[command]
+pub async fn get_mock_aws_status() -> MockAwsStatus {
+    MockAwsStatus {
+        enabled: is_mock_aws_mode(),
+        simulated_speed_mbps: mock_speed_mbps(),
+        label: MOCK_LABEL.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_delay_scales_with_speed() {
+        let fast = SimulatedS3Client::new(100.0);
+        let slow = SimulatedS3Client::new(10.0);
+        let bytes = 10 * 1024 * 1024;
+        assert!(slow.transfer_delay(bytes) > fast.transfer_delay(bytes));
+        assert_eq!(SimulatedS3Client::new(0.0).speed_mbps, DEFAULT_MOCK_SPEED_MBPS);
+    }
+
+    #[tokio::test]
+    async fn test_simulated_uploads_appear_in_listing() {
+        let client = SimulatedS3Client::new(1000.0);
+        client.put_object("bucket", "mock-test/simple.txt", vec![1u8; 64]).await.unwrap();
+
+        let upload_id = client.create_multipart_upload("bucket", "mock-test/large.bin").await.unwrap();
+        client.upload_part("bucket", "mock-test/large.bin", &upload_id, 1, vec![0u8; 128]).await.unwrap();
+        client.complete_multipart_upload("bucket", "mock-test/large.bin", &upload_id, vec![]).await.unwrap();
+
+        let objects = client.list_objects("bucket", Some("mock-test/")).await.unwrap();
+        let sizes: Vec<(String, u64)> = objects.iter().map(|o| (o.key.clone(), o.size)).collect();
+        assert_eq!(sizes, vec![
+            ("mock-test/large.bin".to_string(), 128),
+            ("mock-test/simple.txt".to_string(), 64),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_sample_listing_is_labelled_as_mock() {
+        let objects = SimulatedS3Client::new(1000.0).list_objects("bucket", Some("mock-demo/")).await.unwrap();
+        assert!(!objects.is_empty());
+        assert!(objects.iter().all(|o| o.etag.starts_with("mock-")));
+    }
+}
@@ -0,0 +1,223 @@
+use std::path::{Path, PathBuf};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::command;
+use uuid::Uuid;
+
+use crate::commands::metadata::calculate_file_hash;
+use crate::internal::i18n::{LocalizedMessage, MessageKey};
+use crate::internal::{ErrorCode, InternalError};
+
+/// {date}に書式を指定しなかった場合の書式
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+/// {hash8}をその場で計算するファイルサイズの上限（超える場合はメタデータの作成が必要）
+pub const HASH_TOKEN_MAX_FILE_SIZE: u64 = 1024 * 1024 * 1024;
+/// {seq}のゼロ埋め桁数
+const SEQ_WIDTH: usize = 3;
+
+/// 命名パターンで使えるトークン
+const KNOWN_TOKENS: &[&str] = &["filename", "basename", "ext", "parent", "timestamp", "date", "uuid", "hash8", "seq"];
+
+/// パターン展開時に外から与える値
+#[derive(Debug, Clone, Default)]
+pub struct NamingContext {
+    /// 同一バッチ内の連番（1始まり）
+    pub seq: usize,
+    /// メタデータ作成済みのSHA-256（{hash8}で優先して使う）
+    pub file_hash: Option<String>,
+}
+
+impl NamingContext {
+    pub fn new(seq: usize) -> Self {
+        Self { seq, file_hash: None }
+    }
+
+    pub fn with_hash(mut self, file_hash: Option<String>) -> Self {
+        self.file_hash = file_hash;
+        self
+    }
+}
+
+/// UIに表示する命名パターンのプリセット
+#[derive(Debug, Clone, Serialize)]
+pub struct NamingPatternPreset {
+    pub id: &'static str,
+    pub label: &'static str,
+    pub pattern: &'static str,
+}
+
+pub const NAMING_PATTERN_PRESETS: &[NamingPatternPreset] = &[
+    NamingPatternPreset { id: "original", label: "元のファイル名", pattern: "{filename}" },
+    NamingPatternPreset { id: "dated", label: "日付 + ファイル名", pattern: "{date:%Y-%m-%d}_{filename}" },
+    NamingPatternPreset { id: "folder_sequence", label: "フォルダ名 + 連番", pattern: "{parent}_{seq}.{ext}" },
+    NamingPatternPreset { id: "hashed", label: "ファイル名 + ハッシュ", pattern: "{basename}_{hash8}.{ext}" },
+    NamingPatternPreset { id: "unique", label: "タイムスタンプ + UUID", pattern: "{timestamp}_{uuid}.{ext}" },
+];
+
+/// パターン中のトークン（名前と書式指定）
+#[derive(Debug, Clone, PartialEq)]
+enum Segment<'a> {
+    Literal(&'a str),
+    Token { name: &'a str, format: Option<&'a str> },
+}
+
+fn naming_error(key: MessageKey, pattern: &str, token: &str) -> InternalError {
+    InternalError::localized(
+        ErrorCode::Config,
+        LocalizedMessage::new(key).param("pattern", pattern).param("token", token),
+    )
+}
+
+fn parse_pattern(pattern: &str) -> Result<Vec<Segment<'_>>, InternalError> {
+    let mut segments = Vec::new();
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            segments.push(Segment::Literal(&rest[..start]));
+        }
+        let after = &rest[start + 1..];
+        let end = after.find('}')
+            .ok_or_else(|| naming_error(MessageKey::NamingUnclosedToken, pattern, &rest[start..]))?;
+        let body = &after[..end];
+        let (name, format) = match body.split_once(':') {
+            Some((name, format)) => (name, Some(format)),
+            None => (body, None),
+        };
+        if !KNOWN_TOKENS.contains(&name) {
+            return Err(naming_error(MessageKey::NamingUnknownToken, pattern, body));
+        }
+        match (name, format) {
+            ("date", Some(format)) => {
+                if format.is_empty() || StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+                    return Err(naming_error(MessageKey::NamingInvalidFormat, pattern, body));
+                }
+            }
+            (_, Some(_)) => return Err(naming_error(MessageKey::NamingInvalidFormat, pattern, body)),
+            _ => {}
+        }
+        segments.push(Segment::Token { name, format });
+        rest = &after[end + 1..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Literal(rest));
+    }
+    Ok(segments)
+}
+
+/// パターンの構文と未知のトークンを検証（キュー追加前の事前チェック用）
+pub fn validate_naming_pattern(pattern: &str) -> Result<(), InternalError> {
+    parse_pattern(pattern).map(|_| ())
+}
+
+/// パターンが{hash8}を使うか
+pub fn pattern_uses_hash(pattern: &str) -> bool {
+    pattern.contains("{hash8}")
+}
+
+fn hash8(path: &Path, context: &NamingContext) -> Result<String, InternalError> {
+    if let Some(hash) = &context.file_hash {
+        return Ok(hash.chars().take(8).collect());
+    }
+    let size = path.metadata()
+        .map_err(|e| InternalError::File(format!("Failed to read file size: {}", e)))?
+        .len();
+    if size > HASH_TOKEN_MAX_FILE_SIZE {
+        return Err(InternalError::localized(
+            ErrorCode::Config,
+            LocalizedMessage::new(MessageKey::NamingHashTooLarge)
+                .param("file", path.display())
+                .param("limit_mb", HASH_TOKEN_MAX_FILE_SIZE / 1024 / 1024),
+        ));
+    }
+    Ok(calculate_file_hash(&PathBuf::from(path))?.chars().take(8).collect())
+}
+
+/// 命名パターンを展開してファイル名を作る
+pub fn expand_naming_pattern(
+    pattern: &str,
+    path: &Path,
+    context: &NamingContext,
+    now: DateTime<Utc>,
+) -> Result<String, InternalError> {
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    let mut name = String::new();
+    for segment in parse_pattern(pattern)? {
+        match segment {
+            Segment::Literal(text) => name.push_str(text),
+            Segment::Token { name: token, format } => match token {
+                "filename" => name.push_str(file_name),
+                "basename" => name.push_str(path.file_stem().and_then(|s| s.to_str()).unwrap_or(file_name)),
+                "ext" => name.push_str(path.extension().and_then(|s| s.to_str()).unwrap_or_default()),
+                "parent" => name.push_str(
+                    path.parent().and_then(|p| p.file_name()).and_then(|n| n.to_str()).unwrap_or_default(),
+                ),
+                "timestamp" => name.push_str(&now.timestamp().to_string()),
+                "date" => name.push_str(&now.format(format.unwrap_or(DEFAULT_DATE_FORMAT)).to_string()),
+                "uuid" => name.push_str(&Uuid::new_v4().to_string()),
+                "hash8" => name.push_str(&hash8(path, context)?),
+                "seq" => name.push_str(&format!("{:0width$}", context.seq, width = SEQ_WIDTH)),
+                _ => unreachable!("token validated by parse_pattern"),
+            },
+        }
+    }
+    Ok(name)
+}
+
+/// 命名パターンのプリセット一覧を取得
+#[command]
+pub async fn get_naming_pattern_presets() -> Vec<NamingPatternPreset> {
+    NAMING_PATTERN_PRESETS.to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn fixed_now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 9, 12, 30, 0).unwrap()
+    }
+
+    fn expand(pattern: &str, context: &NamingContext) -> String {
+        expand_naming_pattern(pattern, Path::new("/Volumes/Media/DAY01/A001_C002.mov"), context, fixed_now()).unwrap()
+    }
+
+    #[test]
+    fn test_expand_path_and_date_tokens() {
+        let context = NamingContext::new(7);
+        assert_eq!(expand("{filename}", &context), "A001_C002.mov");
+        assert_eq!(expand("{basename}.{ext}", &context), "A001_C002.mov");
+        assert_eq!(expand("{parent}/{basename}", &context), "DAY01/A001_C002");
+        assert_eq!(expand("{date}_{filename}", &context), "2024-03-09_A001_C002.mov");
+        assert_eq!(expand("{date:%Y%m%d-%H%M}", &context), "20240309-1230");
+        assert_eq!(expand("{timestamp}", &context), fixed_now().timestamp().to_string());
+        assert_eq!(expand("{parent}_{seq}.{ext}", &context), "DAY01_007.mov");
+        assert_eq!(expand("{uuid}", &context).len(), 36);
+    }
+
+    #[test]
+    fn test_expand_hash8_prefers_metadata_hash() {
+        let context = NamingContext::new(1).with_hash(Some("0123456789abcdef".to_string()));
+        assert_eq!(expand("{basename}_{hash8}", &context), "A001_C002_01234567");
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("clip.mov");
+        std::fs::write(&file, b"abc").unwrap();
+        let computed = expand_naming_pattern("{hash8}", &file, &NamingContext::new(1), fixed_now()).unwrap();
+        // SHA-256("abc")の先頭8桁
+        assert_eq!(computed, "ba7816bf");
+    }
+
+    #[test]
+    fn test_validate_naming_pattern_rejects_unknown_tokens() {
+        assert!(validate_naming_pattern("{date:%Y}_{seq}_{filename}").is_ok());
+        assert!(validate_naming_pattern("{filenme}").is_err());
+        assert!(validate_naming_pattern("{filename").is_err());
+        assert!(validate_naming_pattern("{seq:4}").is_err());
+        assert!(validate_naming_pattern("{date:%Q}").is_err());
+        for preset in NAMING_PATTERN_PRESETS {
+            assert!(validate_naming_pattern(preset.pattern).is_ok(), "{}", preset.id);
+        }
+    }
+}
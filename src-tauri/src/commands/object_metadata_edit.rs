@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use futures::stream::{FuturesUnordered, StreamExt};
+use serde::Serialize;
+use tauri::{command, AppHandle, Emitter};
+
+use crate::commands::aws_operations::{create_real_s3_client, AwsConfig, S3ClientTrait};
+use crate::commands::bucket_region::correct_config_region;
+use crate::commands::operations::{begin_operation, CancellationToken, OperationKind};
+use crate::internal::{AppError, InternalError};
+
+/// キーごとの処理が終わるたびに送る進捗イベント
+pub const OBJECT_METADATA_PROGRESS_EVENT: &str = "object-metadata-progress";
+/// 同時に更新するオブジェクト数
+const METADATA_UPDATE_CONCURRENCY: usize = 4;
+/// S3のオブジェクトタグの上限
+const MAX_OBJECT_TAGS: usize = 10;
+const MAX_TAG_KEY_CHARS: usize = 128;
+const MAX_TAG_VALUE_CHARS: usize = 256;
+/// ユーザーメタデータ（キーと値の合計）の上限
+const MAX_USER_METADATA_BYTES: usize = 2 * 1024;
+
+/// タグ・メタデータの変更（Noneは削除、未指定のキーは現在の値を維持する）
+pub type FieldChanges = HashMap<String, Option<String>>;
+
+/// タグ・メタデータそれぞれの更新結果
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub enum FieldUpdateOutcome {
+    Updated,
+    /// 変更がない（指定なし、または現在の値と同じ）
+    Unchanged,
+    /// 更新できない状態のため行わなかった
+    Skipped { reason: String },
+    Failed { error: String },
+}
+
+impl FieldUpdateOutcome {
+    fn is_failed(&self) -> bool {
+        matches!(self, FieldUpdateOutcome::Failed { .. })
+    }
+}
+
+/// キーごとの更新結果
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ObjectMetadataUpdateResult {
+    pub key: String,
+    pub metadata: FieldUpdateOutcome,
+    pub tags: FieldUpdateOutcome,
+}
+
+/// update_s3_objects_metadataの結果
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ObjectMetadataUpdateReport {
+    /// 指定したキーの順に並べた結果
+    pub results: Vec<ObjectMetadataUpdateResult>,
+    pub updated: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+impl ObjectMetadataUpdateReport {
+    fn from_results(results: Vec<ObjectMetadataUpdateResult>) -> Self {
+        let count = |matches: fn(&FieldUpdateOutcome) -> bool| {
+            results.iter().filter(|result| matches(&result.metadata) || matches(&result.tags)).count()
+        };
+        Self {
+            updated: count(|outcome| *outcome == FieldUpdateOutcome::Updated),
+            skipped: count(|outcome| matches!(outcome, FieldUpdateOutcome::Skipped { .. })),
+            failed: count(FieldUpdateOutcome::is_failed),
+            results,
+        }
+    }
+}
+
+/// object-metadata-progressイベントのペイロード
+#[derive(Debug, Clone, Serialize)]
+pub struct ObjectMetadataProgress {
+    pub operation_id: String,
+    pub processed: usize,
+    pub total: usize,
+    pub failed: usize,
+    pub key: String,
+}
+
+/// 現在の値に変更を適用する
+pub fn apply_field_changes(current: &HashMap<String, String>, changes: &FieldChanges) -> HashMap<String, String> {
+    let mut updated = current.clone();
+    for (name, value) in changes {
+        match value {
+            Some(value) => updated.insert(name.clone(), value.clone()),
+            None => updated.remove(name),
+        };
+    }
+    updated
+}
+
+fn validate_tags(tags: &HashMap<String, String>) -> Result<(), String> {
+    if tags.len() > MAX_OBJECT_TAGS {
+        return Err(format!("S3 objects can have at most {} tags ({} requested)", MAX_OBJECT_TAGS, tags.len()));
+    }
+    if let Some((name, _)) = tags.iter().find(|(name, value)| {
+        name.is_empty() || name.chars().count() > MAX_TAG_KEY_CHARS || value.chars().count() > MAX_TAG_VALUE_CHARS
+    }) {
+        return Err(format!("Tag key or value is empty or too long: {}", name));
+    }
+    Ok(())
+}
+
+fn validate_metadata(metadata: &HashMap<String, String>) -> Result<(), String> {
+    let size: usize = metadata.iter().map(|(name, value)| name.len() + value.len()).sum();
+    if size > MAX_USER_METADATA_BYTES {
+        return Err(format!("User metadata exceeds {} bytes ({} bytes)", MAX_USER_METADATA_BYTES, size));
+    }
+    Ok(())
+}
+
+/// 自己コピーでユーザーメタデータを置き換える（アーカイブ済みのオブジェクトはコピーできないためスキップ）
+async fn update_metadata(s3_client: &dyn S3ClientTrait, bucket: &str, key: &str, changes: &FieldChanges) -> FieldUpdateOutcome {
+    if changes.is_empty() {
+        return FieldUpdateOutcome::Unchanged;
+    }
+    let result = async {
+        let state = s3_client.head_object_archive_state(bucket, key, None).await?;
+        if state.is_archived() {
+            return Ok(FieldUpdateOutcome::Skipped {
+                reason: format!(
+                    "{} objects cannot be copied in place; only tags were updated",
+                    state.storage_class.unwrap_or_default()
+                ),
+            });
+        }
+        let current = s3_client.head_object_metadata(bucket, key).await?;
+        let updated = apply_field_changes(&current, changes);
+        if updated == current {
+            return Ok(FieldUpdateOutcome::Unchanged);
+        }
+        validate_metadata(&updated)?;
+        s3_client.copy_object_replace_metadata(bucket, key, updated).await?;
+        Ok::<_, String>(FieldUpdateOutcome::Updated)
+    }.await;
+    result.unwrap_or_else(|error| FieldUpdateOutcome::Failed { error })
+}
+
+async fn update_tags(s3_client: &dyn S3ClientTrait, bucket: &str, key: &str, changes: &FieldChanges) -> FieldUpdateOutcome {
+    if changes.is_empty() {
+        return FieldUpdateOutcome::Unchanged;
+    }
+    let result = async {
+        let current = s3_client.get_object_tagging(bucket, key).await?;
+        let updated = apply_field_changes(&current, changes);
+        if updated == current {
+            return Ok(FieldUpdateOutcome::Unchanged);
+        }
+        validate_tags(&updated)?;
+        s3_client.put_object_tagging(bucket, key, updated).await?;
+        Ok::<_, String>(FieldUpdateOutcome::Updated)
+    }.await;
+    result.unwrap_or_else(|error| FieldUpdateOutcome::Failed { error })
+}
+
+/// 1オブジェクトを更新（自己コピーで新しい版ができるため、メタデータを先に更新してからタグを付ける）
+async fn update_object(
+    s3_client: &dyn S3ClientTrait,
+    bucket: &str,
+    key: &str,
+    metadata_changes: &FieldChanges,
+    tag_changes: &FieldChanges,
+) -> ObjectMetadataUpdateResult {
+    let metadata = update_metadata(s3_client, bucket, key, metadata_changes).await;
+    let tags = update_tags(s3_client, bucket, key, tag_changes).await;
+    ObjectMetadataUpdateResult { key: key.to_string(), metadata, tags }
+}
+
+/// 並列度を制限して各キーを更新し、指定した順の結果を返す（中断後に未着手のキーは結果に含めない）
+pub async fn update_objects_metadata(
+    s3_client: &dyn S3ClientTrait,
+    bucket: &str,
+    keys: &[String],
+    metadata_changes: &FieldChanges,
+    tag_changes: &FieldChanges,
+    token: &CancellationToken,
+    on_progress: impl Fn(&ObjectMetadataUpdateResult, usize),
+) -> Vec<ObjectMetadataUpdateResult> {
+    // streamのコンビネータにクロージャを渡すとコマンドのFutureがSendと判定できないため、並列数を手動で管理する
+    let mut in_flight = FuturesUnordered::new();
+    let mut pending = keys.iter().enumerate();
+    let mut results: Vec<(usize, ObjectMetadataUpdateResult)> = Vec::with_capacity(keys.len());
+    loop {
+        while in_flight.len() < METADATA_UPDATE_CONCURRENCY && !token.is_cancelled() {
+            let Some((index, key)) = pending.next() else {
+                break;
+            };
+            in_flight.push(async move {
+                (index, update_object(s3_client, bucket, key, metadata_changes, tag_changes).await)
+            });
+        }
+        let Some((index, result)) = in_flight.next().await else {
+            break;
+        };
+        on_progress(&result, results.len() + 1);
+        results.push((index, result));
+    }
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+fn normalize_changes(changes: Option<FieldChanges>) -> FieldChanges {
+    changes.unwrap_or_default()
+        .into_iter()
+        .map(|(name, value)| (name.trim().to_string(), value))
+        .filter(|(name, _)| !name.is_empty())
+        .collect()
+}
+
+/// アップロード済みオブジェクトのユーザーメタデータ・タグを一括で付け直す
+///
+/// タグはput_object_tagging、メタデータは同一キーへの自己コピー（REPLACE）で更新する。
+/// DEEP_ARCHIVEなど自己コピーできないオブジェクトはタグのみ更新し、メタデータはスキップとして返す
+#[command]
+pub async fn update_s3_objects_metadata(
+    app: AppHandle,
+    mut config: AwsConfig,
+    keys: Vec<String>,
+    metadata_changes: Option<FieldChanges>,
+    tag_changes: Option<FieldChanges>,
+) -> Result<ObjectMetadataUpdateReport, AppError> {
+    // S3はユーザーメタデータのキーを小文字で返すため、現在の値と比較できるように揃える
+    let metadata_changes: FieldChanges = normalize_changes(metadata_changes).into_iter()
+        .map(|(name, value)| (name.to_lowercase(), value))
+        .collect();
+    let tag_changes = normalize_changes(tag_changes);
+    if metadata_changes.is_empty() && tag_changes.is_empty() {
+        return Err(InternalError::AwsConfig("No metadata or tag changes were specified".to_string()).into());
+    }
+
+    correct_config_region(&app, &mut config).await;
+    let s3_client = create_real_s3_client(&app, &config).await?;
+    let operation = begin_operation(&app, OperationKind::MetadataUpdate, format!("{} object(s)", keys.len()));
+    let failed = AtomicUsize::new(0);
+    let results = update_objects_metadata(
+        s3_client.as_ref(),
+        &config.bucket_name,
+        &keys,
+        &metadata_changes,
+        &tag_changes,
+        operation.token(),
+        |result, processed| {
+            if result.metadata.is_failed() || result.tags.is_failed() {
+                failed.fetch_add(1, Ordering::Relaxed);
+            }
+            let progress = ObjectMetadataProgress {
+                operation_id: operation.operation_id().to_string(),
+                processed,
+                total: keys.len(),
+                failed: failed.load(Ordering::Relaxed),
+                key: result.key.clone(),
+            };
+            if let Err(e) = app.emit(OBJECT_METADATA_PROGRESS_EVENT, &progress) {
+                log::warn!("Failed to emit {}: {}", OBJECT_METADATA_PROGRESS_EVENT, e);
+            }
+        },
+    ).await;
+    operation.token().check()?;
+
+    let report = ObjectMetadataUpdateReport::from_results(results);
+    log::info!(
+        "Updated metadata/tags of {} object(s) in {}: {} updated, {} skipped, {} failed",
+        keys.len(), config.bucket_name, report.updated, report.skipped, report.failed
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::aws_operations::MockS3Client;
+
+    fn changes(entries: &[(&str, Option<&str>)]) -> FieldChanges {
+        entries.iter().map(|(name, value)| (name.to_string(), value.map(String::from))).collect()
+    }
+
+    #[test]
+    fn test_changes_set_and_remove_fields() {
+        let current = HashMap::from([
+            ("project".to_string(), "old-project".to_string()),
+            ("client".to_string(), "acme".to_string()),
+        ]);
+        let updated = apply_field_changes(&current, &changes(&[("project", Some("new-project")), ("client", None), ("shoot", Some("day1"))]));
+        assert_eq!(updated, HashMap::from([
+            ("project".to_string(), "new-project".to_string()),
+            ("shoot".to_string(), "day1".to_string()),
+        ]));
+        assert!(validate_tags(&(0..=MAX_OBJECT_TAGS).map(|i| (i.to_string(), String::new())).collect()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_deep_archive_objects_get_tags_but_skip_metadata() {
+        let keys = vec!["a.mov".to_string(), "b.mov".to_string()];
+        let metadata = changes(&[("project-id", Some("p-42"))]);
+        let tags = changes(&[("project", Some("new-project"))]);
+
+        let archived = MockS3Client::with_archive_state("DEEP_ARCHIVE", None);
+        let results = update_objects_metadata(&archived, "bucket", &keys, &metadata, &tags, &CancellationToken::new(), |_, _| {}).await;
+        assert_eq!(results.iter().map(|result| result.key.as_str()).collect::<Vec<_>>(), ["a.mov", "b.mov"]);
+        assert!(results.iter().all(|result| matches!(result.metadata, FieldUpdateOutcome::Skipped { .. })));
+        assert!(results.iter().all(|result| result.tags == FieldUpdateOutcome::Updated));
+        assert!(archived.calls().copied_objects.is_empty());
+        assert_eq!(archived.calls().tagged_objects.len(), 2);
+
+        let standard = MockS3Client::new();
+        let report = ObjectMetadataUpdateReport::from_results(
+            update_objects_metadata(&standard, "bucket", &keys, &metadata, &tags, &CancellationToken::new(), |_, _| {}).await,
+        );
+        assert_eq!((report.updated, report.skipped, report.failed), (2, 0, 0));
+        assert_eq!(standard.calls().copied_objects[0].1.get("project-id").map(String::as_str), Some("p-42"));
+    }
+}
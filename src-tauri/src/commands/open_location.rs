@@ -0,0 +1,107 @@
+use std::path::Path;
+use serde::Serialize;
+use tauri::{command, AppHandle};
+use tauri_plugin_shell::ShellExt;
+
+use crate::commands::aws_operations::AwsConfig;
+use crate::commands::file_operations::reveal_path_in_finder;
+use crate::internal::{AppError, InternalError};
+
+/// open_in_finderの結果
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct OpenInFinderResult {
+    pub path: String,
+    /// Finderで表示したパス（ファイルも親フォルダも無い場合はNone）
+    pub opened_path: Option<String>,
+    /// ローカルの実体が無い（フロントは復元フローへ誘導する）
+    pub file_missing: bool,
+}
+
+/// RFC 3986の非予約文字とパス区切り以外をUTF-8のバイト単位でパーセントエンコード
+pub(crate) fn encode_s3_key(key: &str) -> String {
+    let mut encoded = String::with_capacity(key.len());
+    for byte in key.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// リージョンに対応するAWSコンソールのホスト（中国・GovCloudは別ドメイン）
+fn console_host(region: &str) -> &'static str {
+    if region.starts_with("cn-") {
+        "console.amazonaws.cn"
+    } else if region.starts_with("us-gov-") {
+        "console.amazonaws-us-gov.com"
+    } else {
+        "s3.console.aws.amazon.com"
+    }
+}
+
+/// S3コンソールのオブジェクトページのURLを組み立てる
+pub fn build_s3_console_url(region: &str, bucket: &str, key: &str) -> Result<String, InternalError> {
+    if bucket.is_empty() || key.is_empty() {
+        return Err(InternalError::AwsConfig("Bucket name and S3 key are required to open the console".to_string()));
+    }
+    Ok(format!(
+        "https://{}/s3/object/{}?region={}&bucketType=general&prefix={}",
+        console_host(region),
+        encode_s3_key(bucket),
+        encode_s3_key(region),
+        encode_s3_key(key),
+    ))
+}
+
+#[allow(deprecated)] // tauri-plugin-shellのopenはtauri-plugin-openerへの移行予定で非推奨
+pub(crate) fn open_with_shell(app: &AppHandle, target: &str) -> Result<(), InternalError> {
+    app.shell().open(target, None).map_err(|e| InternalError::Other(format!("Failed to open {}: {}", target, e)))
+}
+
+/// ローカルのファイルをFinderで表示（無い場合は親フォルダを開き、欠損を返す）
+#[command]
+pub async fn open_in_finder(app: AppHandle, path: String) -> Result<OpenInFinderResult, AppError> {
+    let file = Path::new(&path);
+    if file.exists() {
+        reveal_path_in_finder(file)?;
+        return Ok(OpenInFinderResult { opened_path: Some(path.clone()), path, file_missing: false });
+    }
+
+    let parent = file.parent().filter(|parent| !parent.as_os_str().is_empty() && parent.is_dir());
+    let opened_path = match parent {
+        Some(parent) => {
+            let parent = parent.to_string_lossy().to_string();
+            open_with_shell(&app, &parent)?;
+            Some(parent)
+        }
+        None => None,
+    };
+    log::info!("Local file is missing: {} (opened: {:?})", path, opened_path);
+    Ok(OpenInFinderResult { path, opened_path, file_missing: true })
+}
+
+/// S3コンソールの該当オブジェクトのページをブラウザで開き、開いたURLを返す
+#[command]
+pub async fn open_s3_console_url(app: AppHandle, config: AwsConfig, key: String) -> Result<String, AppError> {
+    let url = build_s3_console_url(&config.region, &config.bucket_name, &key)?;
+    open_with_shell(&app, &url)?;
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_console_url_encodes_japanese_and_spaces() {
+        let url = build_s3_console_url("ap-northeast-1", "footage", "2024/撮影 素材/A+B.mov").unwrap();
+        assert_eq!(
+            url,
+            "https://s3.console.aws.amazon.com/s3/object/footage?region=ap-northeast-1&bucketType=general\
+             &prefix=2024/%E6%92%AE%E5%BD%B1%20%E7%B4%A0%E6%9D%90/A%2BB.mov"
+        );
+        assert!(build_s3_console_url("cn-north-1", "footage", "a.mov").unwrap().starts_with("https://console.amazonaws.cn/"));
+        assert!(build_s3_console_url("ap-northeast-1", "footage", "").is_err());
+    }
+}
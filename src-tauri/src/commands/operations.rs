@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use serde::Serialize;
+use tauri::{command, AppHandle, Emitter, Manager, State};
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+use crate::internal::{AppError, InternalError};
+
+/// 長時間の操作を開始したときに操作IDを通知するイベント
+pub const OPERATION_STARTED_EVENT: &str = "operation-started";
+/// 操作が完了・中断してレジストリから消えたときのイベント
+pub const OPERATION_FINISHED_EVENT: &str = "operation-finished";
+
+#[derive(Debug, Default)]
+struct TokenState {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+/// 長時間の操作を中断するためのトークン（クローンは同じ状態を共有する）
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<TokenState>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::SeqCst);
+        self.0.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// 中断されるまで待つ
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.0.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// 中断済みならエラーを返す（同期処理のループ内で確認する用）
+    pub fn check(&self) -> Result<(), InternalError> {
+        if self.is_cancelled() {
+            return Err(InternalError::Cancelled("operation was cancelled".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// 中断できる操作の種類
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationKind {
+    Download,
+    ListObjects,
+    HashCalculation,
+    QueueAdd,
+    MetadataUpdate,
+}
+
+/// 実行中の操作（operation-startedイベントのペイロード）
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct OperationInfo {
+    pub operation_id: String,
+    pub kind: OperationKind,
+    /// 対象を示す表示用の文字列（S3キー・ファイルパスなど）
+    pub label: String,
+    pub started_at: String,
+}
+
+/// operation-finishedイベントのペイロード
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationFinished {
+    pub operation_id: String,
+    pub kind: OperationKind,
+    pub cancelled: bool,
+}
+
+struct RegisteredOperation {
+    info: OperationInfo,
+    token: CancellationToken,
+}
+
+/// 操作IDとキャンセルトークンのレジストリ（Tauriのmanage対象）
+#[derive(Clone, Default)]
+pub struct OperationRegistry {
+    operations: Arc<Mutex<HashMap<String, RegisteredOperation>>>,
+}
+
+impl OperationRegistry {
+    /// 操作を登録（戻り値のガードを破棄するとレジストリから消える）
+    pub fn register(&self, kind: OperationKind, label: impl Into<String>) -> OperationGuard {
+        let info = OperationInfo {
+            operation_id: Uuid::new_v4().to_string(),
+            kind,
+            label: label.into(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+        };
+        let token = CancellationToken::new();
+        self.operations.lock().unwrap_or_else(|e| e.into_inner()).insert(
+            info.operation_id.clone(),
+            RegisteredOperation { info: info.clone(), token: token.clone() },
+        );
+        OperationGuard { registry: self.clone(), info, token, app: None }
+    }
+
+    /// 操作を中断（未知のIDや完了済みの操作はfalse）
+    pub fn cancel(&self, operation_id: &str) -> bool {
+        let operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        match operations.get(operation_id) {
+            Some(operation) => {
+                operation.token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn list(&self) -> Vec<OperationInfo> {
+        let operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        let mut list: Vec<OperationInfo> = operations.values().map(|operation| operation.info.clone()).collect();
+        list.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+        list
+    }
+
+    fn remove(&self, operation_id: &str) {
+        self.operations.lock().unwrap_or_else(|e| e.into_inner()).remove(operation_id);
+    }
+}
+
+/// 登録済みの操作（破棄時にレジストリから消し、終了を通知する）
+pub struct OperationGuard {
+    registry: OperationRegistry,
+    info: OperationInfo,
+    token: CancellationToken,
+    app: Option<AppHandle>,
+}
+
+impl OperationGuard {
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    pub fn operation_id(&self) -> &str {
+        &self.info.operation_id
+    }
+
+    /// 中断されたら`future`を破棄してエラーを返す
+    pub async fn run<F: Future>(&self, future: F) -> Result<F::Output, InternalError> {
+        tokio::select! {
+            output = future => Ok(output),
+            _ = self.token.cancelled() => {
+                log::info!("Operation {} ({}) cancelled", self.info.operation_id, self.info.label);
+                Err(InternalError::Cancelled(self.info.label.clone()))
+            }
+        }
+    }
+
+    /// `run`に加え、`timeout`を過ぎた場合も中断する
+    pub async fn run_with_timeout<F: Future>(&self, future: F, timeout: Duration) -> Result<F::Output, InternalError> {
+        match tokio::time::timeout(timeout, self.run(future)).await {
+            Ok(result) => result,
+            Err(_) => {
+                self.token.cancel();
+                Err(InternalError::Cancelled(format!("{} timed out after {}s", self.info.label, timeout.as_secs())))
+            }
+        }
+    }
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        self.registry.remove(&self.info.operation_id);
+        if let Some(app) = &self.app {
+            let finished = OperationFinished {
+                operation_id: self.info.operation_id.clone(),
+                kind: self.info.kind,
+                cancelled: self.token.is_cancelled(),
+            };
+            if let Err(e) = app.emit(OPERATION_FINISHED_EVENT, finished) {
+                log::warn!("Failed to emit {}: {}", OPERATION_FINISHED_EVENT, e);
+            }
+        }
+    }
+}
+
+/// 操作を登録し、操作IDをoperation-startedイベントで通知する
+pub fn begin_operation(app: &AppHandle, kind: OperationKind, label: impl Into<String>) -> OperationGuard {
+    let mut guard = app.state::<OperationRegistry>().register(kind, label);
+    guard.app = Some(app.clone());
+    if let Err(e) = app.emit(OPERATION_STARTED_EVENT, &guard.info) {
+        log::warn!("Failed to emit {}: {}", OPERATION_STARTED_EVENT, e);
+    }
+    guard
+}
+
+/// 実行中の操作を中断する（未知のID・完了済みの操作は何もせず成功を返す）
+#[command]
+pub async fn cancel_operation(
+    operation_id: String,
+    registry: State<'_, OperationRegistry>,
+) -> Result<bool, AppError> {
+    let cancelled = registry.cancel(&operation_id);
+    if cancelled {
+        log::info!("Cancellation requested for operation {}", operation_id);
+    }
+    Ok(cancelled)
+}
+
+/// 実行中の操作の一覧を取得
+#[command]
+pub async fn list_operations(registry: State<'_, OperationRegistry>) -> Result<Vec<OperationInfo>, AppError> {
+    Ok(registry.list())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_operation_cancel_is_noop() {
+        let registry = OperationRegistry::default();
+        assert!(!registry.cancel("no-such-operation"));
+
+        let guard = registry.register(OperationKind::Download, "clip.mov");
+        let operation_id = registry.list()[0].operation_id.clone();
+        drop(guard);
+        // 完了済み（レジストリから消えた）操作の中断も何もしない
+        assert!(registry.list().is_empty());
+        assert!(!registry.cancel(&operation_id));
+    }
+
+    #[tokio::test]
+    async fn test_run_stops_when_cancelled() {
+        let registry = OperationRegistry::default();
+        let guard = registry.register(OperationKind::ListObjects, "footage/");
+        let operation_id = registry.list()[0].operation_id.clone();
+
+        assert_eq!(guard.run(async { 42 }).await.unwrap(), 42);
+        assert!(registry.cancel(&operation_id));
+        let result = guard.run(std::future::pending::<()>()).await;
+        assert!(matches!(result, Err(InternalError::Cancelled(_))));
+        assert!(guard.token().check().is_err());
+
+        let timed_out = registry.register(OperationKind::HashCalculation, "a.mov")
+            .run_with_timeout(std::future::pending::<()>(), Duration::from_millis(10))
+            .await;
+        assert!(matches!(timed_out, Err(InternalError::Cancelled(_))));
+    }
+}
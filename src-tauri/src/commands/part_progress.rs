@@ -0,0 +1,147 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use aws_sdk_s3::primitives::ByteStream;
+use aws_smithy_types::body::SdkBody;
+use bytes::Bytes;
+use http_body::{Body, Frame, SizeHint};
+
+/// HTTPクライアントへ一度に渡すバイト数（この単位で送信済みバイト数を通知する）
+const PROGRESS_FRAME_SIZE: usize = 256 * 1024;
+/// パート内の進捗イベントを送る最小の送信量
+pub const PART_PROGRESS_STEP_BYTES: u64 = 4 * 1024 * 1024;
+/// パート内の進捗イベントを送る最小の間隔
+pub const PART_PROGRESS_MIN_INTERVAL: Duration = Duration::from_millis(250);
+
+/// パート内の送信済みバイト数を受け取るフック（リトライ時は0から数え直す）
+pub type PartProgressHook = Arc<dyn Fn(u64) + Send + Sync>;
+
+/// 送信中のパートの位置
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PartPosition {
+    /// 1始まりのパート番号
+    pub current_part: u32,
+    pub total_parts: u32,
+    pub part_uploaded_bytes: u64,
+    pub part_total_bytes: u64,
+}
+
+/// HTTPクライアントが読み取ったバイト数をフックへ通知するリクエストボディ
+pub struct ProgressBody {
+    data: Bytes,
+    position: usize,
+    hook: PartProgressHook,
+}
+
+impl ProgressBody {
+    pub fn new(data: Bytes, hook: PartProgressHook) -> Self {
+        Self { data, position: 0, hook }
+    }
+}
+
+impl Body for ProgressBody {
+    type Data = Bytes;
+    type Error = std::convert::Infallible;
+
+    fn poll_frame(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, Self::Error>>> {
+        let body = self.get_mut();
+        if body.position >= body.data.len() {
+            return Poll::Ready(None);
+        }
+        let end = (body.position + PROGRESS_FRAME_SIZE).min(body.data.len());
+        let frame = body.data.slice(body.position..end);
+        body.position = end;
+        (body.hook)(end as u64);
+        Poll::Ready(Some(Ok(Frame::data(frame))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.position >= self.data.len()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact((self.data.len() - self.position) as u64)
+    }
+}
+
+/// 送信の進捗をフックへ通知するByteStream（SDKのリトライで作り直せるようにする）
+pub fn progress_byte_stream(data: Vec<u8>, hook: PartProgressHook) -> ByteStream {
+    let data = Bytes::from(data);
+    ByteStream::new(SdkBody::retryable(move || {
+        SdkBody::from_body_1_x(ProgressBody::new(data.clone(), hook.clone()))
+    }))
+}
+
+/// パート内の進捗イベントの間引き（一定量かつ一定時間ごと、パートの送信完了は必ず通知）
+#[derive(Debug)]
+pub struct PartProgressThrottle {
+    part_total_bytes: u64,
+    last_reported_bytes: u64,
+    last_reported_at: Option<Instant>,
+}
+
+impl PartProgressThrottle {
+    pub fn new(part_total_bytes: u64) -> Self {
+        Self { part_total_bytes, last_reported_bytes: 0, last_reported_at: None }
+    }
+
+    pub fn should_report(&mut self, sent_bytes: u64, now: Instant) -> bool {
+        // リトライで0から数え直した場合は次の区切りから通知し直す
+        if sent_bytes < self.last_reported_bytes {
+            self.last_reported_bytes = 0;
+        }
+        let finished = sent_bytes >= self.part_total_bytes;
+        let advanced = sent_bytes - self.last_reported_bytes >= PART_PROGRESS_STEP_BYTES;
+        let waited = match self.last_reported_at {
+            Some(at) => now.duration_since(at) >= PART_PROGRESS_MIN_INTERVAL,
+            None => true,
+        };
+        if !finished && !(advanced && waited) {
+            return false;
+        }
+        self.last_reported_bytes = sent_bytes;
+        self.last_reported_at = Some(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_progress_body_reports_bytes_read_by_client() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        let data: Vec<u8> = (0..(PROGRESS_FRAME_SIZE * 2 + 10)).map(|i| i as u8).collect();
+        let mut body = ProgressBody::new(Bytes::from(data.clone()), Arc::new(move |sent| sink.lock().unwrap().push(sent)));
+        assert_eq!(body.size_hint().exact(), Some(data.len() as u64));
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+        let mut received = Vec::new();
+        while let Poll::Ready(Some(frame)) = Pin::new(&mut body).poll_frame(&mut cx) {
+            received.extend_from_slice(frame.unwrap().data_ref().unwrap());
+        }
+
+        assert_eq!(received, data);
+        assert!(body.is_end_stream());
+        let total = data.len() as u64;
+        assert_eq!(*reported.lock().unwrap(), vec![PROGRESS_FRAME_SIZE as u64, PROGRESS_FRAME_SIZE as u64 * 2, total]);
+    }
+
+    #[test]
+    fn test_throttle_reports_every_few_megabytes_and_part_completion() {
+        let part = PART_PROGRESS_STEP_BYTES * 3;
+        let mut throttle = PartProgressThrottle::new(part);
+        let start = Instant::now();
+        assert!(!throttle.should_report(PART_PROGRESS_STEP_BYTES / 2, start));
+        assert!(throttle.should_report(PART_PROGRESS_STEP_BYTES, start));
+        // 量が足りていても間隔が短ければ送らない
+        assert!(!throttle.should_report(PART_PROGRESS_STEP_BYTES * 2, start + Duration::from_millis(10)));
+        assert!(throttle.should_report(PART_PROGRESS_STEP_BYTES * 2, start + PART_PROGRESS_MIN_INTERVAL));
+        // パートの送信完了は間隔に関係なく送る
+        assert!(throttle.should_report(part, start + PART_PROGRESS_MIN_INTERVAL));
+    }
+}
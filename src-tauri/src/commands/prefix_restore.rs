@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use serde::Serialize;
+use tauri::{command, AppHandle, Emitter};
+
+use crate::commands::api_usage::{estimated_cost_per_request, S3ApiKind};
+use crate::commands::aws_operations::{
+    check_restore_status_internal, create_real_s3_client, request_restore_audited, resolve_restore_days,
+    validate_restore_days, validate_restore_tier, AwsConfig, BulkRestoreFailure, S3Object,
+};
+use crate::commands::bucket_region::{check_cross_region_transfer_for_config, correct_config_region, CrossRegionWarning};
+use crate::internal::{AppError, InternalError};
+
+/// プレフィックス配下の復元がすべて完了したときに送るイベント
+pub const RESTORE_PREFIX_COMPLETED_EVENT: &str = "restore-prefix-completed";
+/// 発行済みの復元リクエストの状態を確認する間隔
+const PREFIX_RESTORE_POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// プレフィックス復元の対象にするストレージクラス
+const PREFIX_RESTORE_STORAGE_CLASS: &str = "DEEP_ARCHIVE";
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// DEEP_ARCHIVEの取り出し料金（USD / GB、ExpeditedはDEEP_ARCHIVEでは使えない）
+fn deep_archive_retrieval_price_per_gb(tier: &str) -> Option<f64> {
+    match tier {
+        "Standard" => Some(0.02),
+        "Bulk" => Some(0.0025),
+        _ => None,
+    }
+}
+
+/// プレフィックス復元の見積もり
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PrefixRestoreEstimate {
+    pub prefix: String,
+    pub tier: String,
+    pub days: u32,
+    pub object_count: usize,
+    pub total_size: u64,
+    /// 取り出し料金とリクエスト料金の合計（USD）
+    pub estimated_cost_usd: f64,
+    /// 復元不要のため対象外にしたオブジェクト数（STANDARDなど）
+    pub skipped_count: usize,
+    /// 復元後のダウンロードでクロスリージョン転送が発生する場合の警告
+    pub cross_region_warning: Option<CrossRegionWarning>,
+}
+
+/// 一覧からDEEP_ARCHIVEのオブジェクトを選び、見積もりと復元対象のキーを返す
+pub fn estimate_prefix_restore(
+    prefix: &str,
+    objects: &[S3Object],
+    tier: &str,
+    days: u32,
+    region: &str,
+) -> Result<(PrefixRestoreEstimate, Vec<String>), InternalError> {
+    let price_per_gb = deep_archive_retrieval_price_per_gb(tier).ok_or_else(|| {
+        InternalError::AwsConfig(format!("{} retrieval is not available for DEEP_ARCHIVE objects", tier))
+    })?;
+    let (archived, skipped): (Vec<&S3Object>, Vec<&S3Object>) = objects.iter()
+        .partition(|object| object.storage_class == PREFIX_RESTORE_STORAGE_CLASS);
+    let total_size: u64 = archived.iter().map(|object| object.size).sum();
+    let estimated_cost_usd = total_size as f64 / BYTES_PER_GB * price_per_gb
+        + archived.len() as f64 * estimated_cost_per_request(S3ApiKind::RestoreObject, region);
+
+    let estimate = PrefixRestoreEstimate {
+        prefix: prefix.to_string(),
+        tier: tier.to_string(),
+        days,
+        object_count: archived.len(),
+        total_size,
+        estimated_cost_usd,
+        skipped_count: skipped.len(),
+        cross_region_warning: None,
+    };
+    Ok((estimate, archived.into_iter().map(|object| object.key.clone()).collect()))
+}
+
+/// プレフィックス復元ジョブの段階
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PrefixRestoreStatus {
+    /// 復元リクエストを順に発行中
+    Requesting,
+    /// 発行済みの復元の完了待ち
+    Waiting,
+    Completed,
+}
+
+/// プレフィックス単位の復元ジョブ
+#[derive(Debug, Clone, Serialize)]
+pub struct PrefixRestoreJob {
+    pub prefix: String,
+    pub bucket: String,
+    pub tier: String,
+    pub days: u32,
+    pub status: PrefixRestoreStatus,
+    pub total: usize,
+    /// 復元リクエストを発行したキー
+    pub requested: Vec<String>,
+    pub failed: Vec<BulkRestoreFailure>,
+    /// 復元が完了したオブジェクト数
+    pub completed: usize,
+    /// キャンセルにより発行しなかったオブジェクト数
+    pub not_issued: usize,
+    pub cancelled: bool,
+    pub started_at: String,
+    pub completed_at: Option<String>,
+}
+
+impl PrefixRestoreJob {
+    fn new(estimate: &PrefixRestoreEstimate, bucket: &str) -> Self {
+        Self {
+            prefix: estimate.prefix.clone(),
+            bucket: bucket.to_string(),
+            tier: estimate.tier.clone(),
+            days: estimate.days,
+            status: PrefixRestoreStatus::Requesting,
+            total: estimate.object_count,
+            requested: Vec::new(),
+            failed: Vec::new(),
+            completed: 0,
+            not_issued: 0,
+            cancelled: false,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            completed_at: None,
+        }
+    }
+
+    fn record_request(&mut self, key: String, result: Result<(), String>) {
+        match result {
+            Ok(()) => self.requested.push(key),
+            Err(error) => self.failed.push(BulkRestoreFailure { key, error }),
+        }
+    }
+
+    /// 発行を終えて完了待ちへ移る（キャンセルで発行しなかった分を数える）
+    fn finish_requesting(&mut self) {
+        self.not_issued = self.total - self.requested.len() - self.failed.len();
+        self.status = PrefixRestoreStatus::Waiting;
+    }
+
+    /// 発行済みの状態を反映し、すべて終わっていればtrue
+    fn update_progress(&mut self, completed: usize, pending: usize) -> bool {
+        self.completed = completed;
+        if pending > 0 {
+            return false;
+        }
+        self.status = PrefixRestoreStatus::Completed;
+        self.completed_at = Some(chrono::Utc::now().to_rfc3339());
+        true
+    }
+}
+
+/// 見積もりと、confirm時に開始したジョブ
+#[derive(Debug, Clone, Serialize)]
+pub struct PrefixRestoreResponse {
+    pub estimate: PrefixRestoreEstimate,
+    pub job: Option<PrefixRestoreJob>,
+}
+
+lazy_static::lazy_static! {
+    static ref PREFIX_RESTORE_JOBS: Mutex<HashMap<String, PrefixRestoreJob>> = Mutex::new(HashMap::new());
+}
+
+fn with_job<T>(prefix: &str, f: impl FnOnce(&mut PrefixRestoreJob) -> T) -> Option<T> {
+    PREFIX_RESTORE_JOBS.lock().unwrap_or_else(|e| e.into_inner()).get_mut(prefix).map(f)
+}
+
+/// 復元リクエストを順に発行し、発行済み分の完了まで見届ける
+async fn run_prefix_restore(app: AppHandle, config: AwsConfig, prefix: String, keys: Vec<String>) {
+    let (tier, days) = match with_job(&prefix, |job| (job.tier.clone(), job.days)) {
+        Some(settings) => settings,
+        None => return,
+    };
+    let s3_client = create_real_s3_client(&app, &config).await.map_err(|e| e.to_string());
+    for key in keys {
+        // キャンセル後は未発行の分だけを止め、発行済みの復元はそのまま進める
+        if with_job(&prefix, |job| job.cancelled).unwrap_or(true) {
+            break;
+        }
+        let result = match &s3_client {
+            Ok(s3_client) => request_restore_audited(&app, s3_client.as_ref(), &key, &config.bucket_name, &tier, days).await
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.clone()),
+        };
+        with_job(&prefix, |job| job.record_request(key, result));
+    }
+    let Some(requested) = with_job(&prefix, |job| {
+        job.finish_requesting();
+        job.requested.clone()
+    }) else {
+        return;
+    };
+    log::info!("Prefix restore requested for {}: {} object(s)", prefix, requested.len());
+
+    loop {
+        let mut completed = 0;
+        let mut pending = 0;
+        for key in &requested {
+            match check_restore_status_internal(key.clone(), config.clone()).await {
+                Ok((status, _)) if status.is_restored => completed += 1,
+                Ok((status, _)) if status.restore_status == "in-progress" => pending += 1,
+                Ok(_) => {}
+                Err(e) => {
+                    log::warn!("Failed to check restore status of {}: {}", key, e);
+                    pending += 1;
+                }
+            }
+        }
+        let finished = with_job(&prefix, |job| job.update_progress(completed, pending).then(|| job.clone()));
+        match finished {
+            // 履歴から消された場合は追跡をやめる
+            None => return,
+            Some(Some(job)) => {
+                log::info!("Prefix restore completed for {}: {}/{} object(s)", prefix, job.completed, job.total);
+                if let Err(e) = app.emit(RESTORE_PREFIX_COMPLETED_EVENT, &job) {
+                    log::warn!("Failed to emit {}: {}", RESTORE_PREFIX_COMPLETED_EVENT, e);
+                }
+                return;
+            }
+            Some(None) => tokio::time::sleep(PREFIX_RESTORE_POLL_INTERVAL).await,
+        }
+    }
+}
+
+/// プレフィックス（S3上のフォルダ）配下のDEEP_ARCHIVEオブジェクトをまとめて復元する
+///
+/// `confirm`がfalseの場合は件数・合計サイズ・概算コストの見積もりだけを返す。
+/// trueで再度呼び出すと復元リクエストをバックグラウンドで発行し、プレフィックス単位のジョブとして進捗を追跡する
+#[command]
+pub async fn restore_prefix(
+    app: AppHandle,
+    mut config: AwsConfig,
+    prefix: String,
+    tier: String,
+    days: Option<u32>,
+    confirm: bool,
+) -> Result<PrefixRestoreResponse, AppError> {
+    let days = resolve_restore_days(&app, days);
+    validate_restore_tier(&tier)?;
+    validate_restore_days(days)?;
+
+    correct_config_region(&app, &mut config).await;
+    let s3_client = create_real_s3_client(&app, &config).await?;
+    let objects = s3_client.list_objects(&config.bucket_name, Some(&prefix)).await?;
+    let (mut estimate, keys) = estimate_prefix_restore(&prefix, &objects, &tier, days, &config.region)?;
+    estimate.cross_region_warning = check_cross_region_transfer_for_config(&app, &config, Some(estimate.total_size)).await;
+    if !confirm || keys.is_empty() {
+        return Ok(PrefixRestoreResponse { estimate, job: None });
+    }
+
+    let job = {
+        let mut jobs = PREFIX_RESTORE_JOBS.lock().unwrap_or_else(|e| e.into_inner());
+        if jobs.get(&prefix).is_some_and(|job| job.status != PrefixRestoreStatus::Completed) {
+            return Err(InternalError::Other(format!("Restore of prefix {} is already running", prefix)).into());
+        }
+        let job = PrefixRestoreJob::new(&estimate, &config.bucket_name);
+        jobs.insert(prefix.clone(), job.clone());
+        job
+    };
+    tauri::async_runtime::spawn(run_prefix_restore(app, config, prefix, keys));
+    Ok(PrefixRestoreResponse { estimate, job: Some(job) })
+}
+
+/// プレフィックス復元ジョブの一覧を取得
+#[command]
+pub async fn list_prefix_restore_jobs() -> Result<Vec<PrefixRestoreJob>, AppError> {
+    let jobs = PREFIX_RESTORE_JOBS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut jobs: Vec<PrefixRestoreJob> = jobs.values().cloned().collect();
+    jobs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+    Ok(jobs)
+}
+
+/// プレフィックス復元の未発行分を止める（発行済みの復元は取り消されない）
+#[command]
+pub async fn cancel_prefix_restore(prefix: String) -> Result<PrefixRestoreJob, AppError> {
+    let cancelled = with_job(&prefix, |job| {
+        if job.status != PrefixRestoreStatus::Requesting {
+            return Err(InternalError::Other(format!(
+                "All restore requests for prefix {} have already been issued", prefix
+            )));
+        }
+        job.cancelled = true;
+        Ok(job.clone())
+    });
+    let job = cancelled.ok_or_else(|| InternalError::Other(format!("Prefix restore job not found for: {}", prefix)))??;
+    log::info!("Prefix restore cancelled for {} after {} request(s)", prefix, job.requested.len());
+    Ok(job)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(key: &str, size: u64, storage_class: &str) -> S3Object {
+        S3Object {
+            key: key.to_string(),
+            size,
+            last_modified: "2024-05-01T00:00:00Z".to_string(),
+            storage_class: storage_class.to_string(),
+            etag: "\"etag\"".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_estimate_counts_only_deep_archive_objects() {
+        let gb = BYTES_PER_GB as u64;
+        let objects = vec![
+            object("DAY01/a.mov", 2 * gb, "DEEP_ARCHIVE"),
+            object("DAY01/b.mov", gb, "DEEP_ARCHIVE"),
+            object("DAY01/proxy.mp4", gb, "STANDARD"),
+        ];
+        let (estimate, keys) = estimate_prefix_restore("DAY01/", &objects, "Bulk", 7, "us-east-1").unwrap();
+
+        assert_eq!(keys, vec!["DAY01/a.mov".to_string(), "DAY01/b.mov".to_string()]);
+        assert_eq!((estimate.object_count, estimate.total_size, estimate.skipped_count), (2, 3 * gb, 1));
+        let expected = 3.0 * 0.0025 + 2.0 * estimated_cost_per_request(S3ApiKind::RestoreObject, "us-east-1");
+        assert!((estimate.estimated_cost_usd - expected).abs() < 1e-9);
+
+        assert!(estimate_prefix_restore("DAY01/", &objects, "Expedited", 7, "us-east-1").is_err());
+    }
+
+    #[test]
+    fn test_job_counts_unissued_objects_after_cancel() {
+        let objects = vec![
+            object("DAY01/a.mov", 1, "DEEP_ARCHIVE"),
+            object("DAY01/b.mov", 1, "DEEP_ARCHIVE"),
+            object("DAY01/c.mov", 1, "DEEP_ARCHIVE"),
+        ];
+        let (estimate, _) = estimate_prefix_restore("DAY01/", &objects, "Standard", 3, "us-east-1").unwrap();
+        let mut job = PrefixRestoreJob::new(&estimate, "footage");
+        job.record_request("DAY01/a.mov".to_string(), Ok(()));
+        job.record_request("DAY01/b.mov".to_string(), Err("throttled".to_string()));
+        job.cancelled = true;
+        job.finish_requesting();
+
+        assert_eq!((job.requested.len(), job.failed.len(), job.not_issued), (1, 1, 1));
+        assert!(!job.update_progress(0, 1));
+        assert_eq!(job.status, PrefixRestoreStatus::Waiting);
+        assert!(job.update_progress(1, 0));
+        assert!(job.completed_at.is_some());
+    }
+}
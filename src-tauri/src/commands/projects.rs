@@ -0,0 +1,282 @@
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle};
+use uuid::Uuid;
+
+use crate::commands::metadata::{metadata_db_path, MetadataDatabase};
+use crate::commands::upload_system::{normalize_project_id, S3KeyConfig};
+use crate::internal::{AppError, InternalError};
+
+/// 素材をまとめるプロジェクト（案件）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Project {
+    pub id: String,
+    pub name: String,
+    pub client: Option<String>,
+    pub description: Option<String>,
+    /// キュー追加時にS3キーの先頭へ付けるプレフィックス
+    pub default_s3_prefix: Option<String>,
+    pub created_at: String,
+}
+
+/// create_projectの入力（IDを省略した場合はUUIDを採番）
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewProject {
+    pub id: Option<String>,
+    pub name: String,
+    pub client: Option<String>,
+    pub description: Option<String>,
+    pub default_s3_prefix: Option<String>,
+}
+
+/// ストレージクラス別のファイル数・合計サイズ
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StorageClassUsage {
+    /// アップロード検証時のストレージクラス（不明な場合は"UNKNOWN"）
+    pub storage_class: String,
+    pub file_count: u64,
+    pub total_bytes: u64,
+}
+
+/// プロジェクトの集計
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectSummary {
+    pub project: Project,
+    pub file_count: u64,
+    pub total_bytes: u64,
+    pub uploaded_count: u64,
+    pub uploaded_bytes: u64,
+    /// アップロード済みの割合（ファイル数基準、0.0〜1.0）
+    pub uploaded_ratio: f64,
+    pub storage_classes: Vec<StorageClassUsage>,
+}
+
+/// assign_files_to_projectの結果
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectAssignmentResult {
+    pub assigned: usize,
+    /// メタデータ・アップロード記録のどちらにもなかったパス
+    pub not_found: Vec<String>,
+}
+
+fn open_metadata_db(app: &AppHandle) -> Result<MetadataDatabase, InternalError> {
+    let db_path = metadata_db_path(app)?;
+    MetadataDatabase::new(&db_path.to_string_lossy())
+        .map_err(|e| InternalError::Database(format!("Failed to create database connection: {}", e)))
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+fn find_project(db: &MetadataDatabase, project_id: &str) -> Result<Project, InternalError> {
+    db.list_projects(Some(project_id))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| InternalError::Config(format!("Project not found: {}", project_id)))
+}
+
+/// プロジェクトの既定プレフィックスをキー設定のプレフィックスの前に付ける
+pub fn apply_project_prefix(config: &mut S3KeyConfig, project_prefix: &str) {
+    let project_prefix = project_prefix.trim_matches('/');
+    if project_prefix.is_empty() {
+        return;
+    }
+    config.prefix = Some(match config.prefix.as_deref().map(|prefix| prefix.trim_matches('/')) {
+        Some(prefix) if !prefix.is_empty() => format!("{}/{}", project_prefix, prefix),
+        _ => project_prefix.to_string(),
+    });
+}
+
+/// 登録済みプロジェクトの既定プレフィックス（未登録の案件IDや読み込み失敗時はNone）
+pub fn project_s3_prefix(app: &AppHandle, project_id: &str) -> Option<String> {
+    let db = open_metadata_db(app).ok()?;
+    match find_project(&db, project_id) {
+        Ok(project) => project.default_s3_prefix,
+        Err(_) => None,
+    }
+}
+
+fn create(db: &MetadataDatabase, input: NewProject, created_at: String) -> Result<Project, InternalError> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err(InternalError::Config("Project name must not be empty".to_string()));
+    }
+    // 案件IDはS3メタデータ（project-id）にも載るため、キュー追加時と同じ規則で検証する
+    let id = normalize_project_id(input.id)?.unwrap_or_else(|| Uuid::new_v4().to_string());
+    if !db.list_projects(Some(&id))?.is_empty() {
+        return Err(InternalError::Config(format!("Project already exists: {}", id)));
+    }
+    let project = Project {
+        id,
+        name,
+        client: non_empty(input.client),
+        description: non_empty(input.description),
+        default_s3_prefix: non_empty(input.default_s3_prefix)
+            .map(|prefix| prefix.trim_matches('/').to_string())
+            .filter(|prefix| !prefix.is_empty()),
+        created_at,
+    };
+    db.insert_project(&project)?;
+    Ok(project)
+}
+
+fn summarize(db: &MetadataDatabase, project_id: &str) -> Result<ProjectSummary, InternalError> {
+    let project = find_project(db, project_id)?;
+    let (file_count, total_bytes) = db.project_file_totals(project_id)?;
+    let storage_classes = db.project_storage_classes(project_id)?;
+    let uploaded_count: u64 = storage_classes.iter().map(|usage| usage.file_count).sum();
+    let uploaded_bytes: u64 = storage_classes.iter().map(|usage| usage.total_bytes).sum();
+    let uploaded_ratio = if file_count == 0 { 0.0 } else { uploaded_count as f64 / file_count as f64 };
+    Ok(ProjectSummary {
+        project,
+        file_count,
+        total_bytes,
+        uploaded_count,
+        uploaded_bytes,
+        uploaded_ratio,
+        storage_classes,
+    })
+}
+
+/// プロジェクトを作成
+#[command]
+pub async fn create_project(app: AppHandle, project: NewProject) -> Result<Project, AppError> {
+    let db = open_metadata_db(&app)?;
+    let project = create(&db, project, chrono::Utc::now().to_rfc3339())?;
+    log::info!("Created project {} ({})", project.id, project.name);
+    Ok(project)
+}
+
+/// プロジェクトを作成日時の新しい順に取得
+#[command]
+pub async fn list_projects(app: AppHandle) -> Result<Vec<Project>, AppError> {
+    let db = open_metadata_db(&app)?;
+    Ok(db.list_projects(None).map_err(InternalError::from)?)
+}
+
+/// ファイルをプロジェクトへ割り当てる（project_idがNoneの場合は割り当て解除）
+#[command]
+pub async fn assign_files_to_project(
+    app: AppHandle,
+    project_id: Option<String>,
+    file_paths: Vec<String>,
+) -> Result<ProjectAssignmentResult, AppError> {
+    let db = open_metadata_db(&app)?;
+    let project_id = normalize_project_id(project_id)?;
+    if let Some(project_id) = &project_id {
+        find_project(&db, project_id)?;
+    }
+    let not_found = db.assign_project(project_id.as_deref(), &file_paths).map_err(InternalError::from)?;
+    Ok(ProjectAssignmentResult { assigned: file_paths.len() - not_found.len(), not_found })
+}
+
+/// プロジェクトのファイル数・合計サイズ・アップロード済み割合・ストレージクラス別内訳を取得
+#[command]
+pub async fn get_project_summary(app: AppHandle, project_id: String) -> Result<ProjectSummary, AppError> {
+    let db = open_metadata_db(&app)?;
+    Ok(summarize(&db, &project_id)?)
+}
+
+/// プロジェクトを削除
+///
+/// 割り当て済みのファイルは未割り当てに戻すだけで、ローカルファイルやS3オブジェクトは削除しない
+#[command]
+pub async fn delete_project(app: AppHandle, project_id: String) -> Result<(), AppError> {
+    let db = open_metadata_db(&app)?;
+    find_project(&db, &project_id)?;
+    db.delete_project(&project_id).map_err(InternalError::from)?;
+    log::info!("Deleted project {} (files unassigned)", project_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::metadata::UploadRecord;
+
+    fn new_project(id: Option<&str>, name: &str) -> NewProject {
+        NewProject {
+            id: id.map(String::from),
+            name: name.to_string(),
+            client: Some("  ".to_string()),
+            description: None,
+            default_s3_prefix: Some("/clients/acme/".to_string()),
+        }
+    }
+
+    fn upload_record(file_path: &str, file_size: u64, verified: bool, storage_class: Option<&str>) -> UploadRecord {
+        UploadRecord {
+            file_path: file_path.to_string(),
+            bucket: "archive".to_string(),
+            s3_key: format!("uploads{}", file_path),
+            file_size,
+            uploaded_at: "2024-06-01T00:00:00Z".to_string(),
+            verified,
+            local_removed_at: None,
+            project_id: None,
+            note: None,
+            storage_class: storage_class.map(String::from),
+            source_modified_at: None,
+            file_hash: None,
+        }
+    }
+
+    #[test]
+    fn test_create_project_validates_and_normalizes() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = MetadataDatabase::new(dir.path().join("metadata.db").to_str().unwrap()).unwrap();
+
+        let project = create(&db, new_project(Some(" PRJ-001 "), "番組A"), "2024-06-01T00:00:00Z".to_string()).unwrap();
+        assert_eq!(project.id, "PRJ-001");
+        assert_eq!(project.client, None);
+        assert_eq!(project.default_s3_prefix.as_deref(), Some("clients/acme"));
+        assert_eq!(db.list_projects(None).unwrap(), vec![project]);
+
+        assert!(create(&db, new_project(Some("PRJ-001"), "重複"), String::new()).is_err());
+        assert!(create(&db, new_project(None, "  "), String::new()).is_err());
+        assert!(create(&db, new_project(Some("案件 A"), "番組B"), String::new()).is_err());
+    }
+
+    #[test]
+    fn test_summary_and_delete_only_unassigns() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = MetadataDatabase::new(dir.path().join("metadata.db").to_str().unwrap()).unwrap();
+        create(&db, new_project(Some("PRJ-001"), "番組A"), "2024-06-01T00:00:00Z".to_string()).unwrap();
+        db.record_upload(&upload_record("/footage/a.mov", 100, true, Some("DEEP_ARCHIVE"))).unwrap();
+        db.record_upload(&upload_record("/footage/b.mov", 50, true, None)).unwrap();
+        db.record_upload(&upload_record("/footage/c.mov", 50, false, None)).unwrap();
+
+        let paths: Vec<String> = ["/footage/a.mov", "/footage/b.mov", "/footage/c.mov", "/footage/missing.mov"]
+            .iter().map(|path| path.to_string()).collect();
+        assert_eq!(db.assign_project(Some("PRJ-001"), &paths).unwrap(), vec!["/footage/missing.mov".to_string()]);
+
+        let summary = summarize(&db, "PRJ-001").unwrap();
+        assert_eq!((summary.file_count, summary.total_bytes), (3, 200));
+        assert_eq!((summary.uploaded_count, summary.uploaded_bytes), (2, 150));
+        assert!((summary.uploaded_ratio - 2.0 / 3.0).abs() < 1e-9);
+        let classes: Vec<&str> = summary.storage_classes.iter().map(|usage| usage.storage_class.as_str()).collect();
+        assert_eq!(classes, vec!["DEEP_ARCHIVE", "UNKNOWN"]);
+
+        assert_eq!(db.delete_project("PRJ-001").unwrap(), 1);
+        assert!(summarize(&db, "PRJ-001").is_err());
+        assert_eq!(db.project_file_totals("PRJ-001").unwrap(), (0, 0));
+        // アップロード記録自体は残る
+        assert_eq!(db.find_retention_candidates("2100-01-01T00:00:00Z").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_apply_project_prefix_prepends_to_key_prefix() {
+        let mut config = S3KeyConfig {
+            prefix: Some("raw/".to_string()),
+            use_date_folder: false,
+            preserve_directory_structure: false,
+            custom_naming_pattern: None,
+        };
+        apply_project_prefix(&mut config, "clients/acme/");
+        assert_eq!(config.prefix.as_deref(), Some("clients/acme/raw"));
+
+        let mut config = S3KeyConfig { prefix: None, ..config };
+        apply_project_prefix(&mut config, "clients/acme");
+        assert_eq!(config.prefix.as_deref(), Some("clients/acme"));
+    }
+}
@@ -0,0 +1,352 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter, State};
+
+use crate::commands::metadata::{calculate_file_hash_cancellable, metadata_db_path, MetadataDatabase};
+use crate::commands::operations::{begin_operation, CancellationToken, OperationKind};
+use crate::commands::upload_system::{
+    emit_queue_changed, queue_lock_error, BatchOperationResult, UploadItem, UploadQueueState, UploadStatus,
+};
+use crate::internal::{AppError, InternalError};
+
+/// 重複チェックのハッシュ計算の進捗を通知するイベント
+pub const QUEUE_DUPLICATE_PROGRESS_EVENT: &str = "queue-duplicate-progress";
+
+/// queue-duplicate-progressイベントのペイロード（operation_idをcancel_operationへ渡すと中断できる）
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueDuplicateProgress {
+    pub operation_id: String,
+    pub processed: usize,
+    pub total: usize,
+    pub file_path: String,
+}
+
+/// ハッシュを求めたキュー内のアイテム
+#[derive(Debug, Clone, PartialEq)]
+pub struct HashedQueueItem {
+    pub item_id: String,
+    pub file_path: String,
+    pub file_size: u64,
+    pub file_hash: String,
+}
+
+/// キュー内で内容が同じアイテムの組（先に追加されたものを残す）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QueueDuplicateGroup {
+    pub file_hash: String,
+    pub file_size: u64,
+    pub keep_item_id: String,
+    pub keep_file_path: String,
+    /// 除外できるアイテム（keep以外）
+    pub duplicate_item_ids: Vec<String>,
+}
+
+/// 同じ内容のファイルのアップロード記録
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UploadedCopy {
+    pub file_path: String,
+    pub bucket: String,
+    pub s3_key: String,
+    pub uploaded_at: String,
+}
+
+/// 既にアップロード済みの内容と同じアイテム
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UploadedDuplicate {
+    pub item_id: String,
+    pub file_path: String,
+    pub file_size: u64,
+    pub file_hash: String,
+    pub uploaded: UploadedCopy,
+}
+
+/// ハッシュを計算できなかったアイテム（重複の判定から外す）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DuplicateHashFailure {
+    pub item_id: String,
+    pub file_path: String,
+    pub error: String,
+}
+
+/// アップロード前の重複チェックの結果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QueueDuplicateReport {
+    /// 対象にした待機中・一時停止中のアイテム数
+    pub analyzed_items: usize,
+    /// このチェックでハッシュを計算した件数（残りはメタデータのハッシュを使用）
+    pub hashed_items: usize,
+    pub queue_groups: Vec<QueueDuplicateGroup>,
+    pub uploaded_duplicates: Vec<UploadedDuplicate>,
+    pub hash_failures: Vec<DuplicateHashFailure>,
+    /// 除外するとアップロードしなくて済むサイズ
+    pub estimated_savings_bytes: u64,
+    pub analyzed_at: String,
+}
+
+impl QueueDuplicateReport {
+    /// 除外するアイテムと理由（アップロード済みの重複を優先して記録する）
+    pub fn exclusions(&self) -> Vec<(String, String)> {
+        let mut exclusions: Vec<(String, String)> = self.uploaded_duplicates.iter()
+            .map(|duplicate| (
+                duplicate.item_id.clone(),
+                format!("Already uploaded as s3://{}/{} ({})", duplicate.uploaded.bucket, duplicate.uploaded.s3_key, duplicate.uploaded.file_path),
+            ))
+            .collect();
+        for group in &self.queue_groups {
+            for item_id in &group.duplicate_item_ids {
+                if !exclusions.iter().any(|(id, _)| id == item_id) {
+                    exclusions.push((item_id.clone(), format!("Duplicate of {} in the queue", group.keep_file_path)));
+                }
+            }
+        }
+        exclusions
+    }
+}
+
+/// 待機中・一時停止中のアイテム（開始済みのものは重複でも止めない）
+fn is_analysis_target(item: &UploadItem) -> bool {
+    matches!(item.status, UploadStatus::Pending | UploadStatus::Paused)
+}
+
+/// ハッシュ済みのアイテムとアップロード記録から重複を集計
+///
+/// `items`はキューの順序（先頭ほど先に追加）で渡す
+pub fn build_duplicate_report(
+    items: &[HashedQueueItem],
+    uploaded: &HashMap<String, UploadedCopy>,
+    hashed_items: usize,
+    hash_failures: Vec<DuplicateHashFailure>,
+) -> QueueDuplicateReport {
+    let mut by_hash: Vec<(&str, Vec<&HashedQueueItem>)> = Vec::new();
+    for item in items {
+        match by_hash.iter_mut().find(|(hash, _)| *hash == item.file_hash) {
+            Some((_, members)) => members.push(item),
+            None => by_hash.push((&item.file_hash, vec![item])),
+        }
+    }
+    let queue_groups: Vec<QueueDuplicateGroup> = by_hash.iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|(hash, members)| QueueDuplicateGroup {
+            file_hash: hash.to_string(),
+            file_size: members[0].file_size,
+            keep_item_id: members[0].item_id.clone(),
+            keep_file_path: members[0].file_path.clone(),
+            duplicate_item_ids: members[1..].iter().map(|item| item.item_id.clone()).collect(),
+        })
+        .collect();
+    let uploaded_duplicates: Vec<UploadedDuplicate> = items.iter()
+        .filter_map(|item| {
+            let copy = uploaded.get(&item.file_hash)?;
+            Some(UploadedDuplicate {
+                item_id: item.item_id.clone(),
+                file_path: item.file_path.clone(),
+                file_size: item.file_size,
+                file_hash: item.file_hash.clone(),
+                uploaded: copy.clone(),
+            })
+        })
+        .collect();
+
+    let mut report = QueueDuplicateReport {
+        analyzed_items: items.len() + hash_failures.len(),
+        hashed_items,
+        queue_groups,
+        uploaded_duplicates,
+        hash_failures,
+        estimated_savings_bytes: 0,
+        analyzed_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let sizes: HashMap<&str, u64> = items.iter().map(|item| (item.item_id.as_str(), item.file_size)).collect();
+    report.estimated_savings_bytes = report.exclusions().iter()
+        .filter_map(|(item_id, _)| sizes.get(item_id.as_str()))
+        .sum();
+    report
+}
+
+/// メタデータに記録済みのハッシュ（サイズが一致する場合のみ）
+fn known_hash(item: &UploadItem, db: Option<&MetadataDatabase>) -> Option<String> {
+    db.and_then(|db| db.get_metadata_by_path(&item.file_path).ok())
+        .filter(|metadata| metadata.file_size == item.file_size)
+        .map(|metadata| metadata.file_hash)
+}
+
+/// 記録済みのハッシュを使い、なければファイルを読んで計算
+///
+/// DB接続はSyncでないため、awaitをまたいで借用しないよう記録済みの値を先に取り出して渡す
+async fn hash_queue_item(
+    item: &UploadItem,
+    known: Option<String>,
+    token: &CancellationToken,
+) -> Result<(String, bool), InternalError> {
+    if let Some(hash) = known {
+        return Ok((hash, false));
+    }
+    let path = PathBuf::from(&item.file_path);
+    let token = token.clone();
+    let hash = tokio::task::spawn_blocking(move || calculate_file_hash_cancellable(&path, &token))
+        .await
+        .map_err(|e| InternalError::Other(format!("Hash task failed: {}", e)))??;
+    Ok((hash, true))
+}
+
+/// キュー内の待機中アイテムの重複（キュー内・アップロード済み）と削減できるサイズを確認
+///
+/// 未計算のハッシュはここで計算し、アイテムごとにqueue-duplicate-progressで進捗を通知する
+#[command]
+pub async fn analyze_queue_duplicates(
+    app: AppHandle,
+    queue_state: State<'_, UploadQueueState>,
+) -> Result<QueueDuplicateReport, AppError> {
+    let targets: Vec<UploadItem> = queue_state.lock()
+        .map_err(queue_lock_error)?
+        .items.iter()
+        .filter(|item| is_analysis_target(item))
+        .cloned()
+        .collect();
+    let db = metadata_db_path(&app).ok()
+        .and_then(|db_path| MetadataDatabase::new(&db_path.to_string_lossy()).ok());
+
+    let operation = begin_operation(&app, OperationKind::HashCalculation, format!("Duplicate check ({} item(s))", targets.len()));
+    let mut hashed = Vec::with_capacity(targets.len());
+    let mut hash_failures = Vec::new();
+    let mut hashed_items = 0;
+    for (index, item) in targets.iter().enumerate() {
+        let known = known_hash(item, db.as_ref());
+        match hash_queue_item(item, known, operation.token()).await {
+            Ok((file_hash, computed)) => {
+                hashed_items += usize::from(computed);
+                hashed.push(HashedQueueItem {
+                    item_id: item.id.clone(),
+                    file_path: item.file_path.clone(),
+                    file_size: item.file_size,
+                    file_hash,
+                });
+            }
+            Err(e @ InternalError::Cancelled(_)) => return Err(e.into()),
+            Err(e) => hash_failures.push(DuplicateHashFailure {
+                item_id: item.id.clone(),
+                file_path: item.file_path.clone(),
+                error: e.to_string(),
+            }),
+        }
+        let progress = QueueDuplicateProgress {
+            operation_id: operation.operation_id().to_string(),
+            processed: index + 1,
+            total: targets.len(),
+            file_path: item.file_path.clone(),
+        };
+        if let Err(e) = app.emit(QUEUE_DUPLICATE_PROGRESS_EVENT, &progress) {
+            log::warn!("Failed to emit {}: {}", QUEUE_DUPLICATE_PROGRESS_EVENT, e);
+        }
+    }
+
+    let mut uploaded = HashMap::new();
+    if let Some(db) = &db {
+        for item in &hashed {
+            if uploaded.contains_key(&item.file_hash) {
+                continue;
+            }
+            match db.find_uploaded_copy(&item.file_hash) {
+                Ok(Some(copy)) => {
+                    uploaded.insert(item.file_hash.clone(), copy);
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("Failed to look up uploaded copies of {}: {}", item.file_path, e),
+            }
+        }
+    }
+    let report = build_duplicate_report(&hashed, &uploaded, hashed_items, hash_failures);
+    log::info!(
+        "Duplicate check: {} item(s), {} queue group(s), {} already uploaded, {} byte(s) can be saved",
+        report.analyzed_items, report.queue_groups.len(), report.uploaded_duplicates.len(), report.estimated_savings_bytes
+    );
+    Ok(report)
+}
+
+/// 重複チェックの結果から重複分をアップロードせずに除外する
+///
+/// 除外したアイテムはSkippedとしてキューに残り、完了アイテムと同様にアップロード履歴へ移る
+#[command]
+pub async fn exclude_queue_duplicates(
+    app: AppHandle,
+    report: QueueDuplicateReport,
+    queue_state: State<'_, UploadQueueState>,
+) -> Result<BatchOperationResult, AppError> {
+    let results = queue_state.lock()
+        .map_err(queue_lock_error)?
+        .skip_items(&report.exclusions());
+    let result = BatchOperationResult::new(results);
+    crate::badge::update_badge(&app, queue_state.inner());
+    log::info!("Excluded {} duplicate upload item(s), {} failed", result.succeeded, result.failed);
+    emit_queue_changed(&app, "skipped", &result);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::upload_system::UploadQueue;
+
+    fn hashed(item_id: &str, file_hash: &str, file_size: u64) -> HashedQueueItem {
+        HashedQueueItem {
+            item_id: item_id.to_string(),
+            file_path: format!("/Volumes/Card/{}.mov", item_id),
+            file_size,
+            file_hash: file_hash.to_string(),
+        }
+    }
+
+    fn uploaded_copy() -> UploadedCopy {
+        UploadedCopy {
+            file_path: "/Volumes/Old/a.mov".to_string(),
+            bucket: "footage".to_string(),
+            s3_key: "2024/a.mov".to_string(),
+            uploaded_at: "2024-06-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_report_groups_queue_and_uploaded_duplicates() {
+        let items = vec![
+            hashed("a1", "aaa", 100),
+            hashed("b1", "bbb", 10),
+            hashed("a2", "aaa", 100),
+            hashed("b2", "bbb", 10),
+            hashed("c1", "ccc", 5),
+        ];
+        let uploaded = HashMap::from([("aaa".to_string(), uploaded_copy())]);
+        let report = build_duplicate_report(&items, &uploaded, 3, Vec::new());
+
+        assert_eq!(report.analyzed_items, 5);
+        assert_eq!(report.queue_groups.len(), 2);
+        assert_eq!(report.queue_groups[0].keep_item_id, "a1");
+        assert_eq!(report.queue_groups[0].duplicate_item_ids, vec!["a2"]);
+        assert_eq!(report.uploaded_duplicates.iter().map(|d| d.item_id.as_str()).collect::<Vec<_>>(), vec!["a1", "a2"]);
+        // アップロード済みの内容はキュー内の1件目も不要、bbbは2件目のみ
+        let excluded: Vec<String> = report.exclusions().into_iter().map(|(id, _)| id).collect();
+        assert_eq!(excluded, vec!["a1", "a2", "b2"]);
+        assert_eq!(report.estimated_savings_bytes, 210);
+    }
+
+    #[test]
+    fn test_excluded_items_are_skipped_and_archivable() {
+        let mut queue = UploadQueue::new();
+        for (id, status) in [("a1", UploadStatus::Pending), ("a2", UploadStatus::Pending), ("a3", UploadStatus::InProgress)] {
+            queue.items.push(UploadItem {
+                id: id.to_string(),
+                ..UploadItem::for_test(&format!("/Volumes/Card/{}.mov", id), status)
+            });
+        }
+        let reasons = vec![
+            ("a2".to_string(), "Duplicate of /Volumes/Card/a1.mov in the queue".to_string()),
+            ("a3".to_string(), "Duplicate of /Volumes/Card/a1.mov in the queue".to_string()),
+        ];
+        let result = BatchOperationResult::new(queue.skip_items(&reasons));
+        assert_eq!((result.succeeded, result.failed), (1, 1));
+        assert_eq!(queue.items[1].status, UploadStatus::Skipped);
+        assert!(queue.items[1].status.is_finished());
+        assert!(queue.items[1].error_message.as_deref().unwrap().contains("a1.mov"));
+        assert_eq!(queue.items[2].status, UploadStatus::InProgress);
+    }
+}
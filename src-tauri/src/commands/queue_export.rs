@@ -0,0 +1,237 @@
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, State};
+use uuid::Uuid;
+
+use crate::commands::upload_system::{queue_lock_error, UploadItem, UploadQueue, UploadQueueState, UploadStatus, UploadTier};
+use crate::internal::{AppError, InternalError};
+
+/// エクスポートファイルのスキーマバージョン（互換性のない変更時に上げる）
+pub const QUEUE_EXPORT_VERSION: u32 = 1;
+
+/// エクスポート時点のアップロード設定の概要（認証情報は含めない）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueConfigSummary {
+    pub bucket_name: String,
+    pub region: String,
+    pub tier: UploadTier,
+    pub chunk_size_mb: u64,
+    pub max_concurrent_uploads: usize,
+    pub s3_key_prefix: Option<String>,
+}
+
+/// キューのエクスポートファイル
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueExport {
+    pub version: u32,
+    pub exported_at: String,
+    pub config_summary: Option<QueueConfigSummary>,
+    pub items: Vec<UploadItem>,
+}
+
+/// インポート方法
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum QueueImportMode {
+    /// 既存のキューの末尾に追加
+    Append,
+    /// 既存のキューを置き換え
+    Replace,
+}
+
+/// インポート結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueImportResult {
+    pub mode: QueueImportMode,
+    pub imported_count: usize,
+    pub skipped_count: usize,
+    /// ファイルが存在しないためスキップしたパス
+    pub skipped_files: Vec<String>,
+    pub config_summary: Option<QueueConfigSummary>,
+}
+
+/// キューの内容からエクスポートデータを作成
+fn build_queue_export(queue: &UploadQueue) -> QueueExport {
+    QueueExport {
+        version: QUEUE_EXPORT_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        config_summary: queue.config.as_ref().map(|config| QueueConfigSummary {
+            bucket_name: config.bucket_name.clone(),
+            region: config.region.clone(),
+            tier: config.tier,
+            chunk_size_mb: config.chunk_size_mb,
+            max_concurrent_uploads: config.max_concurrent_uploads,
+            s3_key_prefix: config.s3_key_prefix.clone(),
+        }),
+        items: queue.items.clone(),
+    }
+}
+
+/// 取り込むアイテムを正規化（新しいIDを振り、進行中だったものは待機中へ戻す）
+fn normalize_imported_item(mut item: UploadItem) -> UploadItem {
+    item.id = Uuid::new_v4().to_string();
+    if item.status == UploadStatus::InProgress {
+        item.status = UploadStatus::Pending;
+        item.progress = 0.0;
+        item.uploaded_bytes = 0;
+        item.speed_mbps = 0.0;
+        item.eta_seconds = None;
+        item.started_at = None;
+    }
+    item
+}
+
+/// エクスポートデータを検証し、取り込むアイテムとスキップしたパスに振り分け
+fn prepare_import(export: QueueExport) -> Result<(Vec<UploadItem>, Vec<String>), InternalError> {
+    if export.version > QUEUE_EXPORT_VERSION {
+        return Err(InternalError::Config(format!(
+            "Unsupported upload queue export version: {} (supported: {})",
+            export.version, QUEUE_EXPORT_VERSION
+        )));
+    }
+
+    let mut items = Vec::new();
+    let mut skipped = Vec::new();
+    for item in export.items {
+        if Path::new(&item.file_path).is_file() {
+            items.push(normalize_imported_item(item));
+        } else {
+            log::warn!("Skipping import of missing file: {}", item.file_path);
+            skipped.push(item.file_path);
+        }
+    }
+    Ok((items, skipped))
+}
+
+/// 取り込むアイテムをキューへ反映
+fn apply_import(queue: &mut UploadQueue, items: Vec<UploadItem>, mode: QueueImportMode) -> Result<(), InternalError> {
+    if mode == QueueImportMode::Replace {
+        if queue.in_progress_count() > 0 {
+            return Err(InternalError::Other(
+                "アップロード中のファイルがあるため、キューを置き換えできません".to_string(),
+            ));
+        }
+        let previous = std::mem::take(&mut queue.items);
+        if let Err(e) = queue.check_tier_limits(items.len()) {
+            queue.items = previous;
+            return Err(e);
+        }
+        queue.active_uploads.clear();
+        queue.network_paused_items.clear();
+    } else {
+        queue.check_tier_limits(items.len())?;
+    }
+    queue.items.extend(items);
+    Ok(())
+}
+
+/// アップロードキューの全アイテムをJSONファイルへ書き出し
+#[command]
+pub async fn export_upload_queue(
+    output_path: String,
+    queue_state: State<'_, UploadQueueState>,
+) -> Result<usize, AppError> {
+    let export = {
+        let queue = queue_state.lock()
+            .map_err(|e| AppError::from(queue_lock_error(e)))?;
+        build_queue_export(&queue)
+    };
+
+    let content = serde_json::to_string_pretty(&export).map_err(InternalError::from)?;
+    std::fs::write(&output_path, content).map_err(InternalError::from)?;
+
+    log::info!("Exported {} upload item(s) to {}", export.items.len(), output_path);
+    Ok(export.items.len())
+}
+
+/// エクスポートしたJSONファイルからアップロードキューを取り込み
+#[command]
+pub async fn import_upload_queue(
+    app_handle: AppHandle,
+    input_path: String,
+    mode: QueueImportMode,
+    queue_state: State<'_, UploadQueueState>,
+) -> Result<QueueImportResult, AppError> {
+    let content = std::fs::read_to_string(&input_path)
+        .map_err(|e| InternalError::File(format!("Failed to read {}: {}", input_path, e)))?;
+    let export: QueueExport = serde_json::from_str(&content)
+        .map_err(|e| InternalError::Config(format!("Invalid upload queue export file: {}", e)))?;
+    let config_summary = export.config_summary.clone();
+
+    let (items, skipped_files) = prepare_import(export)?;
+    let imported_count = items.len();
+    {
+        let mut queue = queue_state.lock()
+            .map_err(|e| AppError::from(queue_lock_error(e)))?;
+        apply_import(&mut queue, items, mode)?;
+    }
+    crate::badge::update_badge(&app_handle, queue_state.inner());
+
+    log::info!(
+        "Imported {} upload item(s) from {} ({:?}), skipped {}",
+        imported_count, input_path, mode, skipped_files.len()
+    );
+    Ok(QueueImportResult {
+        mode,
+        imported_count,
+        skipped_count: skipped_files.len(),
+        skipped_files,
+        config_summary,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_item(file_path: &str, status: UploadStatus) -> UploadItem {
+        UploadItem {
+            s3_key: "uploads/test.mov".to_string(),
+            progress: 40.0,
+            uploaded_bytes: 40,
+            speed_mbps: 1.0,
+            eta_seconds: Some(10),
+            started_at: Some(chrono::Utc::now().to_rfc3339()),
+            ..UploadItem::for_test(file_path, status)
+        }
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let file_path = file.path().to_string_lossy().to_string();
+        let mut queue = UploadQueue::new();
+        queue.items.push(create_item(&file_path, UploadStatus::InProgress));
+        queue.items.push(create_item("/nonexistent/missing.mov", UploadStatus::Pending));
+
+        let json = serde_json::to_string(&build_queue_export(&queue)).unwrap();
+        let export: QueueExport = serde_json::from_str(&json).unwrap();
+        assert_eq!(export.version, QUEUE_EXPORT_VERSION);
+
+        let (items, skipped) = prepare_import(export).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].status, UploadStatus::Pending);
+        assert_eq!(items[0].uploaded_bytes, 0);
+        assert_ne!(items[0].id, queue.items[0].id);
+        assert_eq!(skipped, vec!["/nonexistent/missing.mov".to_string()]);
+
+        // 進行中のアイテムがある間は置き換えできない
+        assert!(apply_import(&mut queue, items.clone(), QueueImportMode::Replace).is_err());
+        assert_eq!(queue.items.len(), 2);
+        queue.items[0].status = UploadStatus::Completed;
+        apply_import(&mut queue, items, QueueImportMode::Replace).unwrap();
+        assert_eq!(queue.items.len(), 1);
+        assert_eq!(queue.items[0].s3_key, "uploads/test.mov");
+    }
+
+    #[test]
+    fn test_import_rejects_newer_version() {
+        let export = QueueExport {
+            version: QUEUE_EXPORT_VERSION + 1,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            config_summary: None,
+            items: Vec::new(),
+        };
+        assert!(prepare_import(export).is_err());
+    }
+}
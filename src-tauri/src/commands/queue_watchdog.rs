@@ -0,0 +1,142 @@
+use std::time::{Duration, Instant};
+use serde::Serialize;
+
+/// キュー処理の停滞を通知するイベント名
+pub const UPLOAD_STALLED_EVENT: &str = "upload-stalled";
+/// 開始できる待機中アイテムがあるのに新規開始も進捗も無いまま、停滞とみなすまでの時間
+pub const QUEUE_STALL_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// 内部状態を修正してから、解消しなければキュー処理を再起動するまでの時間
+pub const QUEUE_STALL_RECOVERY_GRACE: Duration = Duration::from_secs(60);
+/// キューに保持する停滞の記録の上限
+pub const MAX_STALL_HISTORY: usize = 20;
+
+/// ウォッチドッグの判定結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogAction {
+    None,
+    /// active_uploadsとitemsを突き合わせてカウンタを修正する
+    Reconcile { stalled_for: Duration },
+    /// 修正しても解消しないためキュー処理を再起動する
+    Restart { stalled_for: Duration },
+}
+
+/// キュー処理ループの停滞を検出するウォッチドッグ
+#[derive(Debug)]
+pub struct QueueWatchdog {
+    last_activity: Instant,
+    reconciled_at: Option<Instant>,
+}
+
+impl QueueWatchdog {
+    pub fn new(now: Instant) -> Self {
+        Self { last_activity: now, reconciled_at: None }
+    }
+
+    /// 新規開始または進捗があった
+    pub fn record_activity(&mut self, now: Instant) {
+        self.last_activity = now;
+        self.reconciled_at = None;
+    }
+
+    /// `waiting`は開始できる待機中アイテムがある（一時停止・オフライン・上限到達中はfalse）
+    pub fn check(&mut self, waiting: bool, now: Instant) -> WatchdogAction {
+        if !waiting {
+            self.record_activity(now);
+            return WatchdogAction::None;
+        }
+        let stalled_for = now.duration_since(self.last_activity);
+        match self.reconciled_at {
+            None if stalled_for >= QUEUE_STALL_TIMEOUT => {
+                self.reconciled_at = Some(now);
+                WatchdogAction::Reconcile { stalled_for }
+            }
+            Some(reconciled_at) if now.duration_since(reconciled_at) >= QUEUE_STALL_RECOVERY_GRACE => {
+                self.record_activity(now);
+                WatchdogAction::Restart { stalled_for }
+            }
+            _ => WatchdogAction::None,
+        }
+    }
+}
+
+/// 内部状態の突き合わせ結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueueReconcileReport {
+    /// 修正前のactive_upload_count
+    pub previous_count: usize,
+    /// 修正後のactive_upload_count
+    pub corrected_count: usize,
+    /// 進行中でないアイテムに残っていたactive_uploadsの件数
+    pub stale_progress: usize,
+    /// タスクが終了しているのに進行中のまま残り、待機中へ戻したアイテム
+    pub requeued_items: Vec<String>,
+}
+
+impl QueueReconcileReport {
+    pub fn changed(&self) -> bool {
+        self.previous_count != self.corrected_count || self.stale_progress > 0 || !self.requeued_items.is_empty()
+    }
+}
+
+/// upload-stalledイベントのペイロード（エラー履歴にも記録する）
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct UploadStalledEvent {
+    pub detected_at: String,
+    pub stalled_secs: u64,
+    pub pending_count: usize,
+    pub in_progress_count: usize,
+    pub active_upload_count: usize,
+    /// 再起動のため待機中へ戻したアイテム数
+    pub requeued_count: usize,
+}
+
+impl UploadStalledEvent {
+    pub fn message(&self) -> String {
+        format!(
+            "Upload queue stalled for {}s with {} pending item(s) (in progress: {}, active count: {}); restarted queue processing",
+            self.stalled_secs, self.pending_count, self.in_progress_count, self.active_upload_count
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watchdog_reconciles_then_restarts() {
+        let start = Instant::now();
+        let mut watchdog = QueueWatchdog::new(start);
+        assert_eq!(watchdog.check(true, start + Duration::from_secs(60)), WatchdogAction::None);
+
+        let detected = start + QUEUE_STALL_TIMEOUT;
+        assert_eq!(watchdog.check(true, detected), WatchdogAction::Reconcile { stalled_for: QUEUE_STALL_TIMEOUT });
+        assert_eq!(watchdog.check(true, detected + Duration::from_secs(1)), WatchdogAction::None);
+
+        let restart_at = detected + QUEUE_STALL_RECOVERY_GRACE;
+        assert_eq!(
+            watchdog.check(true, restart_at),
+            WatchdogAction::Restart { stalled_for: QUEUE_STALL_TIMEOUT + QUEUE_STALL_RECOVERY_GRACE }
+        );
+        // 再起動後は改めて計測する
+        assert_eq!(watchdog.check(true, restart_at + Duration::from_secs(1)), WatchdogAction::None);
+    }
+
+    #[test]
+    fn test_watchdog_resets_on_activity_or_nothing_to_start() {
+        let start = Instant::now();
+        let mut watchdog = QueueWatchdog::new(start);
+        watchdog.record_activity(start + Duration::from_secs(200));
+        assert_eq!(watchdog.check(true, start + QUEUE_STALL_TIMEOUT), WatchdogAction::None);
+
+        // 一時停止中などで待機していない間は停滞とみなさない
+        assert_eq!(watchdog.check(false, start + QUEUE_STALL_TIMEOUT * 2), WatchdogAction::None);
+        assert_eq!(watchdog.check(true, start + QUEUE_STALL_TIMEOUT * 2 + Duration::from_secs(1)), WatchdogAction::None);
+
+        // 修正後に開始できれば再起動しない
+        let detected = start + QUEUE_STALL_TIMEOUT * 3;
+        assert!(matches!(watchdog.check(true, detected), WatchdogAction::Reconcile { .. }));
+        watchdog.record_activity(detected + Duration::from_secs(1));
+        assert_eq!(watchdog.check(true, detected + QUEUE_STALL_RECOVERY_GRACE), WatchdogAction::None);
+    }
+}
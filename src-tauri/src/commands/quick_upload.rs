@@ -0,0 +1,242 @@
+use std::path::Path;
+use std::sync::Mutex;
+use serde::Serialize;
+use tauri::{command, AppHandle, Emitter, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::commands::config::{load_config, set_config, AppConfig, QuickUploadSettings, QuickUploadSource};
+use crate::commands::upload_system::{add_files_to_upload_queue, open_file_dialog, S3KeyConfig, UploadQueueState};
+use crate::internal::i18n::{tr, LocalizedMessage, MessageKey};
+use crate::internal::{AppError, ErrorCode, InternalError};
+
+/// クイックアップロードの既定のショートカット
+pub const DEFAULT_QUICK_UPLOAD_SHORTCUT: &str = "CmdOrCtrl+Shift+U";
+/// ショートカットの登録失敗を通知するイベント名
+pub const SHORTCUT_REGISTRATION_FAILED_EVENT: &str = "shortcut-registration-failed";
+/// クイックアップロードでキューへ追加したことを通知するイベント名
+pub const QUICK_UPLOAD_QUEUED_EVENT: &str = "quick-upload-queued";
+
+/// 登録中のショートカット（Tauriのmanage対象）
+#[derive(Default)]
+pub struct QuickUploadShortcutState(Mutex<Option<String>>);
+
+/// クイックアップロードの状態
+#[derive(Debug, Clone, Serialize)]
+pub struct QuickUploadStatus {
+    pub settings: QuickUploadSettings,
+    /// OSに登録済みのショートカット
+    pub registered_shortcut: Option<String>,
+}
+
+/// quick-upload-queuedイベントのペイロード
+#[derive(Debug, Clone, Serialize)]
+pub struct QuickUploadQueued {
+    pub file_count: usize,
+    pub message: String,
+}
+
+/// ショートカット文字列を解析
+pub fn parse_shortcut(accelerator: &str) -> Result<Shortcut, InternalError> {
+    accelerator.parse::<Shortcut>().map_err(|e| {
+        InternalError::localized(
+            ErrorCode::Config,
+            LocalizedMessage::new(MessageKey::QuickUploadShortcutInvalid)
+                .param("shortcut", accelerator)
+                .param("error", e),
+        )
+    })
+}
+
+/// 登録中のショートカットを差し替える（新しいショートカットの登録に失敗した場合は元に戻す）
+pub fn apply_quick_upload_shortcut(app: &AppHandle, accelerator: Option<&str>) -> Result<(), InternalError> {
+    let Some(state) = app.try_state::<QuickUploadShortcutState>() else {
+        return Ok(());
+    };
+    let mut current = state.0.lock()
+        .map_err(|e| InternalError::Other(format!("Failed to lock shortcut state: {}", e)))?;
+    if current.as_deref() == accelerator {
+        return Ok(());
+    }
+
+    let new_shortcut = accelerator.map(parse_shortcut).transpose()?;
+    let global_shortcut = app.global_shortcut();
+    let previous = current.take();
+    if let Some(previous) = &previous {
+        if let Err(e) = parse_shortcut(previous).and_then(|shortcut| {
+            global_shortcut.unregister(shortcut).map_err(|e| InternalError::Other(e.to_string()))
+        }) {
+            log::warn!("Failed to unregister shortcut {}: {}", previous, e);
+        }
+    }
+
+    if let (Some(accelerator), Some(shortcut)) = (accelerator, new_shortcut) {
+        if let Err(e) = global_shortcut.register(shortcut) {
+            // 他アプリと競合した場合は元のショートカットを登録し直す
+            if let Some(previous) = previous {
+                if parse_shortcut(&previous).map(|s| global_shortcut.register(s).is_ok()).unwrap_or(false) {
+                    *current = Some(previous);
+                }
+            }
+            return Err(InternalError::localized(
+                ErrorCode::Config,
+                LocalizedMessage::new(MessageKey::QuickUploadShortcutConflict)
+                    .param("shortcut", accelerator)
+                    .param("error", e),
+            ));
+        }
+        *current = Some(accelerator.to_string());
+        log::info!("Quick upload shortcut registered: {}", accelerator);
+    }
+    Ok(())
+}
+
+/// 設定に合わせてショートカットを登録し直す（失敗はイベントとOS通知で知らせ、内容を返す）
+pub fn refresh_quick_upload_shortcut(app: &AppHandle, config: &AppConfig) -> Result<(), String> {
+    if let Err(e) = apply_quick_upload_shortcut(app, config.quick_upload.shortcut.as_deref()) {
+        let message = e.to_string();
+        log::error!("Failed to register quick upload shortcut: {}", message);
+        if let Err(e) = app.emit(SHORTCUT_REGISTRATION_FAILED_EVENT, &message) {
+            log::warn!("Failed to emit shortcut registration failure: {}", e);
+        }
+        crate::notifications::notify_error(app, &tr(MessageKey::QuickUploadTitle), &message);
+        return Err(message);
+    }
+    Ok(())
+}
+
+/// 起動時に保存済みのショートカットを登録
+pub fn initialize_quick_upload(app: &AppHandle) -> Result<(), String> {
+    app.manage(QuickUploadShortcutState::default());
+    let config = load_config(app).map_err(|e| e.to_string())?;
+    refresh_quick_upload_shortcut(app, &config)
+}
+
+/// グローバルショートカットのハンドラ（押下時のみ処理）
+pub fn handle_shortcut(app: &AppHandle, _shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = run_quick_upload(&app).await {
+            log::warn!("Quick upload failed: {}", e.message);
+            crate::notifications::notify_error(&app, &tr(MessageKey::QuickUploadTitle), &e.message);
+        }
+    });
+}
+
+/// クリップボードのテキストからファイルパスを取り出す（file:// URLにも対応）
+pub fn parse_clipboard_paths(text: &str) -> Vec<String> {
+    let mut paths: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.trim().trim_matches('"');
+        let path = match line.strip_prefix("file://") {
+            Some(url_path) => percent_decode(url_path),
+            None => line.to_string(),
+        };
+        if Path::new(&path).is_absolute() && !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = |b: u8| (b as char).to_digit(16);
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let (Some(high), Some(low)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                decoded.push((high * 16 + low) as u8);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// 設定された取得元からファイルを受け取り、そのままアップロードキューへ追加
+async fn run_quick_upload(app: &AppHandle) -> Result<(), AppError> {
+    let source = load_config(app)?.quick_upload.source;
+    let s3_key_config = S3KeyConfig::from_queue(&app.state::<UploadQueueState>())?;
+
+    let file_paths = match source {
+        // ダイアログをキャンセルした場合（選択なしのエラー）は何もしない
+        QuickUploadSource::Dialog => match open_file_dialog(app.clone(), true, None).await {
+            Ok(selection) => selection.selected_files,
+            Err(_) => return Ok(()),
+        },
+        QuickUploadSource::Clipboard => {
+            let text = app.clipboard().read_text().unwrap_or_default();
+            let paths: Vec<String> = parse_clipboard_paths(&text)
+                .into_iter()
+                .filter(|path| Path::new(path).is_file())
+                .collect();
+            if paths.is_empty() {
+                return Err(InternalError::localized(ErrorCode::File, MessageKey::QuickUploadClipboardEmpty).into());
+            }
+            paths
+        }
+    };
+    if file_paths.is_empty() {
+        return Ok(());
+    }
+
+    let file_count = file_paths.len();
+    let message = add_files_to_upload_queue(app.clone(), file_paths, s3_key_config, None, None, None, app.state()).await?;
+    log::info!("Quick upload queued {} file(s) from {:?}", file_count, source);
+    if let Err(e) = app.emit(QUICK_UPLOAD_QUEUED_EVENT, QuickUploadQueued { file_count, message }) {
+        log::warn!("Failed to emit quick upload result: {}", e);
+    }
+    Ok(())
+}
+
+/// クイックアップロードの設定を変更（ショートカットの登録に失敗した場合は保存しない）
+#[command]
+pub async fn set_quick_upload_settings(app: AppHandle, settings: QuickUploadSettings) -> Result<QuickUploadStatus, AppError> {
+    apply_quick_upload_shortcut(&app, settings.shortcut.as_deref())?;
+
+    let mut config = load_config(&app)?;
+    config.quick_upload = settings;
+    set_config(app.clone(), config).await?;
+    get_quick_upload_status(app).await
+}
+
+/// クイックアップロードの設定と登録状況を取得
+#[command]
+pub async fn get_quick_upload_status(app: AppHandle) -> Result<QuickUploadStatus, AppError> {
+    let registered_shortcut = app.try_state::<QuickUploadShortcutState>()
+        .and_then(|state| state.0.lock().ok().and_then(|current| current.clone()));
+    Ok(QuickUploadStatus {
+        settings: load_config(&app)?.quick_upload,
+        registered_shortcut,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_shortcut() {
+        assert!(parse_shortcut(DEFAULT_QUICK_UPLOAD_SHORTCUT).is_ok());
+        assert!(parse_shortcut("Alt+Shift+F9").is_ok());
+        assert!(parse_shortcut("Cmd+Shift+NotAKey").is_err());
+    }
+
+    #[test]
+    fn test_parse_clipboard_paths() {
+        let text = "/Users/editor/clip.mov\nfile:///Users/editor/My%20Project/take%202.mov\n\n\"/Users/editor/clip.mov\"\nnot a path\n";
+        assert_eq!(
+            parse_clipboard_paths(text),
+            vec!["/Users/editor/clip.mov".to_string(), "/Users/editor/My Project/take 2.mov".to_string()]
+        );
+        assert!(parse_clipboard_paths("").is_empty());
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// ファイルを置いたボリュームの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VolumeClass {
+    /// 内蔵HDD（ランダムリードで速度が大きく落ちる）
+    Hdd,
+    Ssd,
+    /// 外付け・リムーバブル（種類を判定できないことが多い）
+    External,
+}
+
+/// 同一ボリュームから同時にチャンクを読み込む数の上限（UploadConfigで調整）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ReadConcurrencyLimits {
+    pub hdd: usize,
+    pub ssd: usize,
+    pub external: usize,
+    /// HDD上のこのサイズ以上のファイルは、チャンク単位ではなくファイル単位で直列に読む（0で無効）
+    pub sequential_threshold_mb: u64,
+}
+
+impl Default for ReadConcurrencyLimits {
+    fn default() -> Self {
+        Self {
+            hdd: 1,
+            ssd: 4,
+            external: 2,
+            sequential_threshold_mb: 1024,
+        }
+    }
+}
+
+impl ReadConcurrencyLimits {
+    pub fn limit_for(&self, class: VolumeClass) -> usize {
+        let limit = match class {
+            VolumeClass::Hdd => self.hdd,
+            VolumeClass::Ssd => self.ssd,
+            VolumeClass::External => self.external,
+        };
+        limit.max(1)
+    }
+}
+
+/// ファイルの読み込み方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadMode {
+    /// チャンクを読む間だけ枠を確保（他ファイルの読み込みと交互になる）
+    PerChunk,
+    /// ファイル全体を読み終えるまで枠を確保（HDD上の大容量ファイルはシーク競合より直列の方が速い）
+    WholeFile,
+}
+
+/// ボリュームの種類とファイルサイズから読み込み方を選ぶ
+pub fn choose_read_mode(class: VolumeClass, file_size: u64, limits: &ReadConcurrencyLimits) -> ReadMode {
+    let threshold = limits.sequential_threshold_mb * 1024 * 1024;
+    if class == VolumeClass::Hdd && threshold > 0 && file_size >= threshold {
+        ReadMode::WholeFile
+    } else {
+        ReadMode::PerChunk
+    }
+}
+
+struct VolumeSlot {
+    limit: usize,
+    semaphore: Arc<Semaphore>,
+}
+
+/// ボリューム（マウントポイント）ごとのセマフォ
+#[derive(Default)]
+pub struct ReadScheduler {
+    volumes: Mutex<HashMap<PathBuf, VolumeSlot>>,
+}
+
+lazy_static::lazy_static! {
+    pub static ref READ_SCHEDULER: ReadScheduler = ReadScheduler::default();
+}
+
+/// パスを含むボリュームのマウントポイントと種類
+fn detect_volume(path: &Path) -> (PathBuf, VolumeClass) {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks.list().iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().components().count())
+        .map(|disk| {
+            let class = if disk.is_removable() || disk.mount_point().starts_with("/Volumes") {
+                VolumeClass::External
+            } else if matches!(disk.kind(), sysinfo::DiskKind::HDD) {
+                VolumeClass::Hdd
+            } else {
+                VolumeClass::Ssd
+            };
+            (disk.mount_point().to_path_buf(), class)
+        })
+        .unwrap_or_else(|| (PathBuf::from("/"), VolumeClass::Ssd))
+}
+
+impl ReadScheduler {
+    fn semaphore_for(&self, volume: &Path, limit: usize) -> Arc<Semaphore> {
+        let mut volumes = self.volumes.lock().unwrap_or_else(|e| e.into_inner());
+        let slot = volumes.entry(volume.to_path_buf()).or_insert_with(|| VolumeSlot {
+            limit,
+            semaphore: Arc::new(Semaphore::new(limit)),
+        });
+        // 上限が変わった場合は新しいセマフォに切り替える（取得済みの枠は旧セマフォで解放される）
+        if slot.limit != limit {
+            *slot = VolumeSlot { limit, semaphore: Arc::new(Semaphore::new(limit)) };
+        }
+        slot.semaphore.clone()
+    }
+
+    /// ファイルを読むためのガードを取得（ファイル単位で読む場合はここで枠を確保する）
+    pub async fn guard_for(
+        &self,
+        volume: &Path,
+        class: VolumeClass,
+        file_size: u64,
+        limits: &ReadConcurrencyLimits,
+    ) -> VolumeReadGuard {
+        let semaphore = self.semaphore_for(volume, limits.limit_for(class));
+        let mode = choose_read_mode(class, file_size, limits);
+        let whole_file = match mode {
+            ReadMode::WholeFile => semaphore.clone().acquire_owned().await.ok(),
+            ReadMode::PerChunk => None,
+        };
+        VolumeReadGuard { semaphore, mode, _whole_file: whole_file }
+    }
+
+    /// ファイルのボリュームを判定してガードを取得
+    pub async fn guard_for_file(&self, path: &Path, file_size: u64, limits: &ReadConcurrencyLimits) -> VolumeReadGuard {
+        let (volume, class) = detect_volume(path);
+        log::debug!("Read scheduling {} on {} ({:?})", path.display(), volume.display(), class);
+        self.guard_for(&volume, class, file_size, limits).await
+    }
+}
+
+/// 1ファイル分の読み込みガード
+pub struct VolumeReadGuard {
+    semaphore: Arc<Semaphore>,
+    mode: ReadMode,
+    _whole_file: Option<OwnedSemaphorePermit>,
+}
+
+impl VolumeReadGuard {
+    pub fn mode(&self) -> ReadMode {
+        self.mode
+    }
+
+    /// チャンクを読む直前に呼び、読み終えたら返り値を破棄する
+    pub async fn chunk_permit(&self) -> Option<OwnedSemaphorePermit> {
+        match self.mode {
+            ReadMode::PerChunk => self.semaphore.clone().acquire_owned().await.ok(),
+            ReadMode::WholeFile => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_choose_read_mode() {
+        let limits = ReadConcurrencyLimits::default();
+        let gib = 1024 * 1024 * 1024;
+        assert_eq!(choose_read_mode(VolumeClass::Hdd, 2 * gib, &limits), ReadMode::WholeFile);
+        assert_eq!(choose_read_mode(VolumeClass::Hdd, 100 * 1024 * 1024, &limits), ReadMode::PerChunk);
+        assert_eq!(choose_read_mode(VolumeClass::Ssd, 2 * gib, &limits), ReadMode::PerChunk);
+
+        let disabled = ReadConcurrencyLimits { sequential_threshold_mb: 0, ..Default::default() };
+        assert_eq!(choose_read_mode(VolumeClass::Hdd, 2 * gib, &disabled), ReadMode::PerChunk);
+        assert_eq!(ReadConcurrencyLimits { hdd: 0, ..Default::default() }.limit_for(VolumeClass::Hdd), 1);
+    }
+
+    #[tokio::test]
+    async fn test_chunk_permits_are_limited_per_volume() {
+        let scheduler = ReadScheduler::default();
+        let limits = ReadConcurrencyLimits::default();
+        let hdd = scheduler.guard_for(Path::new("/Volumes/HDD"), VolumeClass::Hdd, 10, &limits).await;
+        let other = scheduler.guard_for(Path::new("/"), VolumeClass::Hdd, 10, &limits).await;
+
+        let first = hdd.chunk_permit().await;
+        assert!(first.is_some());
+        // 同一ボリュームの2つ目は待たされ、別ボリュームは待たない
+        let second = tokio::time::timeout(std::time::Duration::from_millis(20), hdd.chunk_permit()).await;
+        assert!(second.is_err());
+        assert!(other.chunk_permit().await.is_some());
+
+        drop(first);
+        assert!(hdd.chunk_permit().await.is_some());
+    }
+}
@@ -0,0 +1,358 @@
+use std::path::Path;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter};
+
+use crate::commands::audit::{AuditLogger, AuditOperation, OperationAuditEntry};
+use crate::commands::aws_operations::{create_real_s3_client, AwsConfig, ObjectHeadInfo, S3ClientTrait};
+use crate::commands::manifest::read_manifest;
+use crate::commands::metadata::{metadata_db_path, MetadataDatabase};
+use crate::commands::s3_etag::local_file_matches_etag;
+use crate::internal::{AppError, InternalError};
+
+/// 検証の進捗を通知するイベント名
+pub const REMOTE_VERIFY_PROGRESS_EVENT: &str = "remote-verify-progress";
+/// アップロード時に付与するSHA-256のユーザーメタデータ名（x-amz-meta-sha256）
+pub const SHA256_METADATA_KEY: &str = "sha256";
+/// head_objectの最小間隔（S3のレート制限に収まるよう最大50リクエスト/秒に抑える）
+const VERIFY_REQUEST_INTERVAL: Duration = Duration::from_millis(20);
+/// 進捗イベントを発行する間隔（件数）
+const PROGRESS_EMIT_EVERY: usize = 25;
+
+/// 検証対象の範囲
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VerifyScope {
+    All,
+    /// 指定タグが付いたファイル
+    Tag { tag: String },
+    /// アップロード日時（RFC3339）の範囲
+    Period { from: Option<String>, to: Option<String> },
+    /// フォルダの.reelvault-manifest.jsonに記録されたファイル（マニフェストがなければアップロード記録から探す）
+    Manifest { folder: String },
+}
+
+/// 検証対象のアップロード記録
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifyTarget {
+    pub file_path: String,
+    pub s3_key: String,
+    pub file_size: u64,
+    /// メタデータに記録されたSHA-256（メタデータ未作成の場合はNone）
+    pub file_hash: Option<String>,
+}
+
+/// 1件ごとの検証結果
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyOutcome {
+    Ok,
+    Missing,
+    Mismatch,
+}
+
+/// OK以外となったファイルの詳細
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteVerifyEntry {
+    pub file_path: String,
+    pub s3_key: String,
+    pub outcome: VerifyOutcome,
+    pub expected_size: u64,
+    pub remote_size: Option<u64>,
+    /// x-amz-meta-sha256（なければETag）でハッシュも照合できたか
+    pub hash_checked: bool,
+    pub detail: Option<String>,
+}
+
+/// 整合性検証のレポート
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteVerifyReport {
+    pub bucket: String,
+    pub started_at: String,
+    pub completed_at: String,
+    pub checked_count: usize,
+    pub ok_count: usize,
+    pub missing_count: usize,
+    pub mismatch_count: usize,
+    /// ハッシュまで照合できた件数（それ以外はサイズのみで判定）
+    pub hash_checked_count: usize,
+    /// Missing・Mismatchのファイル
+    pub problems: Vec<RemoteVerifyEntry>,
+    /// head_object自体が失敗したファイル
+    pub errors: Vec<String>,
+}
+
+/// remote-verify-progressイベントのペイロード
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteVerifyProgress {
+    pub checked: usize,
+    pub total: usize,
+    pub missing_count: usize,
+    pub mismatch_count: usize,
+}
+
+/// head_objectの結果とDBの記録を照合
+pub fn classify_remote_object(target: &VerifyTarget, head: Option<&ObjectHeadInfo>) -> RemoteVerifyEntry {
+    let mut entry = RemoteVerifyEntry {
+        file_path: target.file_path.clone(),
+        s3_key: target.s3_key.clone(),
+        outcome: VerifyOutcome::Ok,
+        expected_size: target.file_size,
+        remote_size: head.map(|head| head.size),
+        hash_checked: false,
+        detail: None,
+    };
+    let Some(head) = head else {
+        entry.outcome = VerifyOutcome::Missing;
+        return entry;
+    };
+
+    if head.size != target.file_size {
+        entry.outcome = VerifyOutcome::Mismatch;
+        entry.detail = Some(format!("size {} != {}", head.size, target.file_size));
+        return entry;
+    }
+
+    if let (Some(expected), Some(remote)) = (&target.file_hash, head.metadata.get(SHA256_METADATA_KEY)) {
+        entry.hash_checked = true;
+        if !expected.eq_ignore_ascii_case(remote) {
+            entry.outcome = VerifyOutcome::Mismatch;
+            entry.detail = Some(format!("sha256 {} != {}", remote, expected));
+        }
+    }
+    entry
+}
+
+/// ハッシュのメタデータがないオブジェクトを、ローカルファイルから再計算したETagで照合
+///
+/// ローカル原本が削除済み・ETagがMD5形式でない場合はサイズのみの判定のまま
+async fn check_etag_fallback(entry: &mut RemoteVerifyEntry, head: Option<&ObjectHeadInfo>) {
+    let Some(etag) = head.and_then(|head| head.etag.clone()) else {
+        return;
+    };
+    if entry.outcome != VerifyOutcome::Ok || entry.hash_checked || !Path::new(&entry.file_path).is_file() {
+        return;
+    }
+    let path = entry.file_path.clone();
+    let expected = etag.clone();
+    let matched = tokio::task::spawn_blocking(move || local_file_matches_etag(Path::new(&path), &expected, None)).await;
+    match matched {
+        Ok(Ok(Some(true))) => entry.hash_checked = true,
+        Ok(Ok(Some(false))) => {
+            entry.hash_checked = true;
+            entry.outcome = VerifyOutcome::Mismatch;
+            entry.detail = Some(format!("etag {} does not match local file", etag));
+        }
+        Ok(Ok(None)) => {}
+        Ok(Err(e)) => log::warn!("Failed to calculate ETag of {}: {}", entry.file_path, e),
+        Err(e) => log::warn!("ETag calculation task failed for {}: {}", entry.file_path, e),
+    }
+}
+
+/// 対象を順にhead_objectで照合（リクエスト間隔を空けてレート制限を守る）
+pub(crate) async fn verify_targets(
+    s3_client: &dyn S3ClientTrait,
+    bucket: &str,
+    targets: &[VerifyTarget],
+    request_interval: Duration,
+    on_progress: &(dyn Fn(&RemoteVerifyProgress) + Send + Sync),
+) -> RemoteVerifyReport {
+    let mut report = RemoteVerifyReport {
+        bucket: bucket.to_string(),
+        started_at: chrono::Utc::now().to_rfc3339(),
+        completed_at: String::new(),
+        checked_count: 0,
+        ok_count: 0,
+        missing_count: 0,
+        mismatch_count: 0,
+        hash_checked_count: 0,
+        problems: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    let mut ticker = tokio::time::interval(request_interval);
+    for target in targets {
+        ticker.tick().await;
+        match s3_client.head_object_info(bucket, &target.s3_key).await {
+            Ok(head) => {
+                let mut entry = classify_remote_object(target, head.as_ref());
+                check_etag_fallback(&mut entry, head.as_ref()).await;
+                if entry.hash_checked {
+                    report.hash_checked_count += 1;
+                }
+                match entry.outcome {
+                    VerifyOutcome::Ok => report.ok_count += 1,
+                    VerifyOutcome::Missing => report.missing_count += 1,
+                    VerifyOutcome::Mismatch => report.mismatch_count += 1,
+                }
+                if entry.outcome != VerifyOutcome::Ok {
+                    report.problems.push(entry);
+                }
+            }
+            Err(e) => report.errors.push(format!("{}: {}", target.s3_key, e)),
+        }
+        report.checked_count += 1;
+
+        if report.checked_count % PROGRESS_EMIT_EVERY == 0 || report.checked_count == targets.len() {
+            on_progress(&RemoteVerifyProgress {
+                checked: report.checked_count,
+                total: targets.len(),
+                missing_count: report.missing_count,
+                mismatch_count: report.mismatch_count,
+            });
+        }
+    }
+
+    report.completed_at = chrono::Utc::now().to_rfc3339();
+    report
+}
+
+/// 検証結果を監査テーブルへ記録（問題のあったファイルごとと、実行全体の集計）
+fn record_verify_audit(logger: &AuditLogger, report: &RemoteVerifyReport, elapsed: Duration) {
+    for problem in &report.problems {
+        let result: Result<(), String> = Err(format!(
+            "{:?}{}",
+            problem.outcome,
+            problem.detail.as_ref().map(|detail| format!(": {}", detail)).unwrap_or_default()
+        ));
+        logger.record(
+            &OperationAuditEntry::new(AuditOperation::RemoteVerify, &result)
+                .local_path(problem.file_path.clone())
+                .s3_object(report.bucket.clone(), problem.s3_key.clone())
+                .size(problem.expected_size),
+        );
+    }
+
+    let summary: Result<(), String> = if report.problems.is_empty() && report.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "checked {}, missing {}, mismatch {}, errors {}",
+            report.checked_count, report.missing_count, report.mismatch_count, report.errors.len()
+        ))
+    };
+    let mut entry = OperationAuditEntry::new(AuditOperation::RemoteVerify, &summary).duration(elapsed);
+    entry.bucket = Some(report.bucket.clone());
+    logger.record(&entry);
+}
+
+/// DBに記録されたアップロード済みファイルがS3に正しいサイズ・ハッシュで存在するか検証
+#[command]
+pub async fn verify_remote_integrity(
+    app: AppHandle,
+    config: AwsConfig,
+    scope: VerifyScope,
+) -> Result<RemoteVerifyReport, AppError> {
+    let started_at = std::time::Instant::now();
+    let db_path = metadata_db_path(&app)?;
+    let manifest = match &scope {
+        VerifyScope::Manifest { folder } => read_manifest(Path::new(folder))?
+            .map(|manifest| manifest.verify_targets(Path::new(folder), &config.bucket_name)),
+        _ => None,
+    };
+    let targets = match manifest {
+        Some(targets) => targets,
+        None => MetadataDatabase::new(&db_path.to_string_lossy())
+            .and_then(|db| db.list_verification_targets(&config.bucket_name, &scope))
+            .map_err(InternalError::from)?,
+    };
+    log::info!("Verifying {} uploaded file(s) in bucket {} ({:?})", targets.len(), config.bucket_name, scope);
+
+    let s3_client = create_real_s3_client(&app, &config).await?;
+    let emit_progress = |progress: &RemoteVerifyProgress| {
+        if let Err(e) = app.emit(REMOTE_VERIFY_PROGRESS_EVENT, progress) {
+            log::warn!("Failed to emit remote verify progress: {}", e);
+        }
+    };
+    let report = verify_targets(
+        s3_client.as_ref(),
+        &config.bucket_name,
+        &targets,
+        VERIFY_REQUEST_INTERVAL,
+        &emit_progress,
+    ).await;
+    log::info!(
+        "Remote verification finished: {} ok, {} missing, {} mismatch, {} error(s)",
+        report.ok_count, report.missing_count, report.mismatch_count, report.errors.len()
+    );
+
+    record_verify_audit(&AuditLogger::new(db_path), &report, started_at.elapsed());
+    if report.mismatch_count > 0 || report.missing_count > 0 {
+        crate::notifications::notify_integrity_problems(&app, report.mismatch_count, report.missing_count);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::commands::aws_operations::MockS3Client;
+
+    fn target(key: &str, size: u64, hash: Option<&str>) -> VerifyTarget {
+        VerifyTarget {
+            file_path: format!("/Volumes/Media/{}", key),
+            s3_key: key.to_string(),
+            file_size: size,
+            file_hash: hash.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_classify_remote_object() {
+        let head = ObjectHeadInfo {
+            size: 10,
+            metadata: HashMap::from([(SHA256_METADATA_KEY.to_string(), "ABC".to_string())]),
+            etag: None,
+        };
+        assert_eq!(classify_remote_object(&target("a.mov", 10, Some("abc")), Some(&head)).outcome, VerifyOutcome::Ok);
+        assert!(classify_remote_object(&target("a.mov", 10, Some("abc")), Some(&head)).hash_checked);
+        assert_eq!(classify_remote_object(&target("a.mov", 10, Some("def")), Some(&head)).outcome, VerifyOutcome::Mismatch);
+        assert_eq!(classify_remote_object(&target("a.mov", 11, None), Some(&head)).outcome, VerifyOutcome::Mismatch);
+        assert_eq!(classify_remote_object(&target("a.mov", 10, None), None).outcome, VerifyOutcome::Missing);
+
+        // ハッシュのメタデータがないオブジェクトはサイズのみで判定
+        let without_hash = ObjectHeadInfo { size: 10, metadata: HashMap::new(), etag: None };
+        let entry = classify_remote_object(&target("a.mov", 10, Some("abc")), Some(&without_hash));
+        assert_eq!(entry.outcome, VerifyOutcome::Ok);
+        assert!(!entry.hash_checked);
+    }
+
+    #[tokio::test]
+    async fn test_verify_targets_builds_report() {
+        let client = MockS3Client::with_remote_objects(HashMap::from([
+            ("ok.mov".to_string(), ObjectHeadInfo { size: 5, metadata: HashMap::new(), etag: None }),
+            ("short.mov".to_string(), ObjectHeadInfo { size: 3, metadata: HashMap::new(), etag: None }),
+        ]));
+        let targets = vec![target("ok.mov", 5, None), target("short.mov", 5, None), target("gone.mov", 5, None)];
+        let progress = std::sync::Mutex::new(Vec::new());
+
+        let report = verify_targets(&client, "footage", &targets, Duration::from_millis(1), &|p| {
+            progress.lock().unwrap().push(p.checked);
+        }).await;
+        assert_eq!(report.checked_count, 3);
+        assert_eq!((report.ok_count, report.missing_count, report.mismatch_count), (1, 1, 1));
+        assert_eq!(report.problems.len(), 2);
+        assert_eq!(*progress.lock().unwrap(), vec![3]);
+    }
+
+    #[tokio::test]
+    async fn test_objects_without_hash_metadata_fall_back_to_etag() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("same.mov"), b"abcde").unwrap();
+        std::fs::write(dir.path().join("edited.mov"), b"abcdf").unwrap();
+        // "abcde"のMD5
+        let etag = Some("\"ab56b4d92b40713acc5af89985d4b786\"".to_string());
+        let client = MockS3Client::with_remote_objects(HashMap::from([
+            ("same.mov".to_string(), ObjectHeadInfo { size: 5, metadata: HashMap::new(), etag: etag.clone() }),
+            ("edited.mov".to_string(), ObjectHeadInfo { size: 5, metadata: HashMap::new(), etag }),
+        ]));
+        let targets: Vec<VerifyTarget> = ["same.mov", "edited.mov"].iter()
+            .map(|key| VerifyTarget { file_path: dir.path().join(key).to_string_lossy().to_string(), ..target(key, 5, None) })
+            .collect();
+
+        let report = verify_targets(&client, "footage", &targets, Duration::from_millis(1), &|_| {}).await;
+        assert_eq!((report.ok_count, report.mismatch_count, report.hash_checked_count), (1, 1, 2));
+        assert_eq!(report.problems[0].s3_key, "edited.mov");
+    }
+}
@@ -0,0 +1,167 @@
+use chrono::{DateTime, Utc};
+use tauri::{command, AppHandle, Emitter};
+
+use crate::commands::aws_operations::{restore_job_notifications, RestoreInfo, RestoreNotification};
+use crate::commands::config::{load_config, DEFAULT_RESTORE_NOTIFICATION_RETENTION_DAYS};
+use crate::commands::metadata::{metadata_db_path, MetadataDatabase};
+use crate::internal::{AppError, InternalError};
+
+/// 新しい復元通知を保存したときのイベント
+pub const RESTORE_NOTIFICATION_EVENT: &str = "restore-notification";
+
+fn open_metadata_db(app: &AppHandle) -> Result<MetadataDatabase, InternalError> {
+    let db_path = metadata_db_path(app)?;
+    MetadataDatabase::new(&db_path.to_string_lossy())
+        .map_err(|e| InternalError::Database(format!("Failed to create database connection: {}", e)))
+}
+
+/// 復元ジョブの結果から通知を作成（完了・失敗以外はNone）
+///
+/// IDは同じ復元リクエストの同じ結果で一致するため、何度作成しても保存されるのは1件
+pub fn notification_for_job(key: &str, info: &RestoreInfo, now: DateTime<Utc>) -> Option<RestoreNotification> {
+    let message = match info.restore_status.as_str() {
+        "completed" => format!("File {} is ready for download", key),
+        "failed" => format!("Restore failed for file {}", key),
+        _ => return None,
+    };
+    Some(RestoreNotification {
+        id: format!("{}:{}:{}", info.restore_status, key, info.request_time),
+        key: key.to_string(),
+        status: info.restore_status.clone(),
+        message,
+        timestamp: info.completion_time.clone().unwrap_or_else(|| now.to_rfc3339()),
+        read: false,
+    })
+}
+
+/// 未保存の通知のみ保存し、新しく保存した通知を返す
+pub fn save_new_notifications(
+    db: &MetadataDatabase,
+    notifications: Vec<RestoreNotification>,
+) -> Result<Vec<RestoreNotification>, InternalError> {
+    let mut created = Vec::new();
+    for notification in notifications {
+        if db.insert_restore_notification(&notification)? {
+            created.push(notification);
+        }
+    }
+    Ok(created)
+}
+
+/// 追跡中の復元ジョブから新しい通知を保存し、restore-notificationイベントで送る
+pub fn publish_restore_notifications(app: &AppHandle) {
+    let created = open_metadata_db(app)
+        .and_then(|db| save_new_notifications(&db, restore_job_notifications(Utc::now())));
+    match created {
+        Ok(created) => {
+            for notification in created {
+                if let Err(e) = app.emit(RESTORE_NOTIFICATION_EVENT, &notification) {
+                    log::warn!("Failed to emit {}: {}", RESTORE_NOTIFICATION_EVENT, e);
+                }
+            }
+        }
+        Err(e) => log::warn!("Failed to save restore notifications: {}", e),
+    }
+}
+
+/// 保持期間を過ぎた通知を削除（既定90日）
+pub fn prune_restore_notifications(app: &AppHandle) {
+    let retention_days = load_config(app)
+        .map(|config| config.user_preferences.restore_notification_retention_days)
+        .unwrap_or(DEFAULT_RESTORE_NOTIFICATION_RETENTION_DAYS);
+    let cutoff = (Utc::now() - chrono::Duration::days(i64::from(retention_days))).to_rfc3339();
+    match open_metadata_db(app).and_then(|db| Ok(db.prune_restore_notifications(&cutoff)?)) {
+        Ok(0) => {}
+        Ok(removed) => log::info!("Removed {} restore notification(s) older than {} days", removed, retention_days),
+        Err(e) => log::warn!("Failed to prune restore notifications: {}", e),
+    }
+}
+
+/// 復元通知を新しい順に取得（`unread_only`で未読のみ）
+#[command]
+pub async fn get_restore_notifications(app: AppHandle, unread_only: Option<bool>) -> Result<Vec<RestoreNotification>, AppError> {
+    publish_restore_notifications(&app);
+    let db = open_metadata_db(&app)?;
+    Ok(db.list_restore_notifications(unread_only.unwrap_or(false)).map_err(InternalError::from)?)
+}
+
+/// 通知を既読にする
+#[command]
+pub async fn mark_notification_read(app: AppHandle, id: String) -> Result<(), AppError> {
+    let db = open_metadata_db(&app)?;
+    if db.mark_restore_notification_read(Some(&id)).map_err(InternalError::from)? == 0 {
+        return Err(InternalError::Other(format!("Notification not found: {}", id)).into());
+    }
+    Ok(())
+}
+
+/// すべての通知を既読にし、既読にした件数を返す
+#[command]
+pub async fn mark_all_notifications_read(app: AppHandle) -> Result<usize, AppError> {
+    let db = open_metadata_db(&app)?;
+    Ok(db.mark_restore_notification_read(None).map_err(InternalError::from)?)
+}
+
+/// 通知を削除（同じ復元結果から再作成されないよう、削除済みとして記録を残す）
+#[command]
+pub async fn delete_notification(app: AppHandle, id: String) -> Result<(), AppError> {
+    let db = open_metadata_db(&app)?;
+    if db.delete_restore_notification(&id).map_err(InternalError::from)? == 0 {
+        return Err(InternalError::Other(format!("Notification not found: {}", id)).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(status: &str) -> RestoreInfo {
+        RestoreInfo {
+            key: "footage/a.mov".to_string(),
+            restore_status: status.to_string(),
+            expiry_date: None,
+            tier: "Standard".to_string(),
+            request_time: "2024-06-01T00:00:00Z".to_string(),
+            completion_time: Some("2024-06-01T05:00:00Z".to_string()),
+            local_path: None,
+            version_id: None,
+            restore_days: None,
+            note: None,
+        }
+    }
+
+    #[test]
+    fn test_notifications_are_saved_once_and_deleted_ones_stay_hidden() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = MetadataDatabase::new(dir.path().join("metadata.db").to_str().unwrap()).unwrap();
+        let now = Utc::now();
+        assert!(notification_for_job("footage/a.mov", &job("in-progress"), now).is_none());
+        let completed = notification_for_job("footage/a.mov", &job("completed"), now).unwrap();
+
+        assert_eq!(save_new_notifications(&db, vec![completed.clone()]).unwrap().len(), 1);
+        // 毎回ジョブから作り直しても同じ通知は増えない
+        assert!(save_new_notifications(&db, vec![completed.clone()]).unwrap().is_empty());
+        assert_eq!(db.list_restore_notifications(true).unwrap(), vec![completed.clone()]);
+
+        assert_eq!(db.mark_restore_notification_read(Some(&completed.id)).unwrap(), 1);
+        assert!(db.list_restore_notifications(true).unwrap().is_empty());
+        assert!(db.list_restore_notifications(false).unwrap()[0].read);
+
+        assert_eq!(db.delete_restore_notification(&completed.id).unwrap(), 1);
+        assert!(save_new_notifications(&db, vec![completed]).unwrap().is_empty());
+        assert!(db.list_restore_notifications(false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_prune_removes_notifications_older_than_cutoff() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = MetadataDatabase::new(dir.path().join("metadata.db").to_str().unwrap()).unwrap();
+        let old = notification_for_job("footage/a.mov", &job("completed"), Utc::now()).unwrap();
+        let recent = RestoreNotification { id: "failed:footage/b.mov:x".to_string(), timestamp: "2024-09-01T00:00:00Z".to_string(), ..old.clone() };
+        save_new_notifications(&db, vec![old, recent.clone()]).unwrap();
+
+        assert_eq!(db.prune_restore_notifications("2024-08-01T00:00:00Z").unwrap(), 1);
+        assert_eq!(db.list_restore_notifications(false).unwrap(), vec![recent]);
+    }
+}
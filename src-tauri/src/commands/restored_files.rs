@@ -0,0 +1,224 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle};
+
+use crate::commands::aws_operations::resolve_download_path;
+use crate::commands::config::{load_config, AppConfig, DEFAULT_RESTORED_RETENTION_DAYS};
+use crate::commands::metadata::{metadata_db_path, MetadataDatabase};
+use crate::internal::{AppError, InternalError};
+
+/// restored_files_dirが未設定の場合にダウンロードフォルダ内へ作るフォルダ名
+pub const DEFAULT_RESTORED_DIR_NAME: &str = "ReelVault Restored";
+
+/// 期限切れの作業コピーの確認間隔
+const RESTORED_CLEANUP_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+/// 起動直後の負荷を避けるための初回確認までの待ち時間
+const INITIAL_CLEANUP_DELAY: Duration = Duration::from_secs(90);
+
+/// Restoredフォルダへダウンロードした作業コピー
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RestoredFile {
+    pub local_path: String,
+    pub s3_key: String,
+    pub bucket: String,
+    pub downloaded_at: String,
+    /// この日時を過ぎると日次タスクでゴミ箱へ移す
+    pub expires_at: String,
+}
+
+/// 期限切れの作業コピーの掃除結果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RestoredCleanupResult {
+    pub trashed: Vec<String>,
+    /// すでにファイルがなく記録だけ消したもの
+    pub missing: usize,
+    /// ゴミ箱へ移せなかったもの（記録を残して次回再試行する）
+    pub failed: Vec<String>,
+}
+
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn expiry_after(base: DateTime<Utc>, days: u32) -> String {
+    timestamp(base + chrono::Duration::days(i64::from(days)))
+}
+
+/// 復元済みの作業コピーの保存先フォルダ
+pub fn restored_files_dir(config: &AppConfig) -> PathBuf {
+    config.restored_files_dir.as_deref()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            dirs::download_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(DEFAULT_RESTORED_DIR_NAME)
+        })
+}
+
+/// download_restored_fileの保存先を解決（ファイル名のみ・相対パスはRestoredフォルダ基準）
+pub fn resolve_restored_download_path(app: &AppHandle, local_path: &str, s3_key: &str) -> String {
+    let config = load_config(app).unwrap_or_default();
+    let dir = restored_files_dir(&config);
+    resolve_download_path(local_path, s3_key, Some(&dir.to_string_lossy()))
+        .to_string_lossy()
+        .to_string()
+}
+
+fn open_metadata_db(app: &AppHandle) -> Result<MetadataDatabase, InternalError> {
+    let db_path = metadata_db_path(app)?;
+    MetadataDatabase::new(&db_path.to_string_lossy())
+        .map_err(|e| InternalError::Database(format!("Failed to create database connection: {}", e)))
+}
+
+/// Restoredフォルダ内へ保存した場合のみ、ダウンロード日時と削除予定日を記録する
+/// （ユーザーが任意の場所へ保存したファイルは自動削除しない）
+pub fn record_restored_download(app: &AppHandle, s3_key: &str, bucket: &str, saved_path: &str) {
+    let config = load_config(app).unwrap_or_default();
+    if !Path::new(saved_path).starts_with(restored_files_dir(&config)) {
+        return;
+    }
+    let now = Utc::now();
+    let file = RestoredFile {
+        local_path: saved_path.to_string(),
+        s3_key: s3_key.to_string(),
+        bucket: bucket.to_string(),
+        downloaded_at: timestamp(now),
+        expires_at: expiry_after(now, config.restored_retention_days),
+    };
+    let result = open_metadata_db(app)
+        .and_then(|db| db.upsert_restored_file(&file).map_err(InternalError::from));
+    if let Err(e) = result {
+        log::warn!("Failed to record restored file {}: {}", saved_path, e);
+    }
+}
+
+/// 期限切れの作業コピーをゴミ箱へ移し、記録を削除する
+pub fn cleanup_expired_restored_files(db: &MetadataDatabase, now: DateTime<Utc>) -> Result<RestoredCleanupResult, InternalError> {
+    let mut result = RestoredCleanupResult::default();
+    for file in db.list_restored_files(Some(&timestamp(now)))? {
+        let path = Path::new(&file.local_path);
+        if path.exists() {
+            if let Err(e) = trash::delete(path) {
+                log::warn!("Failed to move expired restored file {} to trash: {}", file.local_path, e);
+                result.failed.push(file.local_path);
+                continue;
+            }
+            result.trashed.push(file.local_path.clone());
+        } else {
+            result.missing += 1;
+        }
+        db.delete_restored_file(&file.local_path)?;
+    }
+    Ok(result)
+}
+
+/// 復元済みの作業コピーの日次掃除を開始
+pub fn start_restored_files_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(INITIAL_CLEANUP_DELAY).await;
+        loop {
+            match open_metadata_db(&app).and_then(|db| cleanup_expired_restored_files(&db, Utc::now())) {
+                Ok(result) if !result.trashed.is_empty() || !result.failed.is_empty() => {
+                    log::info!(
+                        "Restored files cleanup: {} moved to trash, {} failed",
+                        result.trashed.len(), result.failed.len()
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => log::error!("Restored files cleanup failed: {}", e),
+            }
+            tokio::time::sleep(RESTORED_CLEANUP_INTERVAL).await;
+        }
+    });
+}
+
+/// 削除予定の作業コピーを削除予定日の早い順に取得
+#[command]
+pub async fn list_restored_files(app: AppHandle) -> Result<Vec<RestoredFile>, AppError> {
+    let db = open_metadata_db(&app)?;
+    Ok(db.list_restored_files(None).map_err(InternalError::from)?)
+}
+
+/// 作業コピーの保持期間を延長（日数の指定がない場合は設定の保持日数）
+///
+/// 期限切れ前なら現在の削除予定日から、期限切れ後なら現在時刻から延長する
+#[command]
+pub async fn extend_restored_file_retention(
+    app: AppHandle,
+    local_path: String,
+    days: Option<u32>,
+) -> Result<RestoredFile, AppError> {
+    let days = days
+        .or_else(|| load_config(&app).ok().map(|config| config.restored_retention_days))
+        .unwrap_or(DEFAULT_RESTORED_RETENTION_DAYS);
+    if days == 0 {
+        return Err(InternalError::Config("Retention extension must be at least 1 day".to_string()).into());
+    }
+    let db = open_metadata_db(&app)?;
+    Ok(extend_retention(&db, &local_path, days, Utc::now())?)
+}
+
+fn extend_retention(db: &MetadataDatabase, local_path: &str, days: u32, now: DateTime<Utc>) -> Result<RestoredFile, InternalError> {
+    let mut file = db.list_restored_files(None)?
+        .into_iter()
+        .find(|file| file.local_path == local_path)
+        .ok_or_else(|| InternalError::File(format!("Restored file is not tracked: {}", local_path)))?;
+    let current = DateTime::parse_from_rfc3339(&file.expires_at)
+        .map(|time| time.with_timezone(&Utc))
+        .unwrap_or(now)
+        .max(now);
+    file.expires_at = expiry_after(current, days);
+    db.update_restored_file_expiry(&file.local_path, &file.expires_at)?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn restored(local_path: &str, expires_at: &str) -> RestoredFile {
+        RestoredFile {
+            local_path: local_path.to_string(),
+            s3_key: "footage/a.mov".to_string(),
+            bucket: "archive".to_string(),
+            downloaded_at: "2024-06-01T00:00:00Z".to_string(),
+            expires_at: expires_at.to_string(),
+        }
+    }
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_cleanup_removes_only_expired_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = MetadataDatabase::new(dir.path().join("metadata.db").to_str().unwrap()).unwrap();
+        let missing_path = dir.path().join("gone.mov").to_string_lossy().to_string();
+        db.upsert_restored_file(&restored(&missing_path, "2024-06-10T00:00:00Z")).unwrap();
+        db.upsert_restored_file(&restored("/Restored/keep.mov", "2024-06-20T00:00:00Z")).unwrap();
+
+        let result = cleanup_expired_restored_files(&db, at("2024-06-15T00:00:00Z")).unwrap();
+        assert_eq!((result.trashed.len(), result.missing, result.failed.len()), (0, 1, 0));
+        let remaining = db.list_restored_files(None).unwrap();
+        assert_eq!(remaining, vec![restored("/Restored/keep.mov", "2024-06-20T00:00:00Z")]);
+    }
+
+    #[test]
+    fn test_extend_retention_from_later_of_expiry_and_now() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = MetadataDatabase::new(dir.path().join("metadata.db").to_str().unwrap()).unwrap();
+        db.upsert_restored_file(&restored("/Restored/a.mov", "2024-06-20T00:00:00Z")).unwrap();
+
+        let extended = extend_retention(&db, "/Restored/a.mov", 7, at("2024-06-15T00:00:00Z")).unwrap();
+        assert_eq!(extended.expires_at, "2024-06-27T00:00:00Z");
+        let extended = extend_retention(&db, "/Restored/a.mov", 7, at("2024-07-01T00:00:00Z")).unwrap();
+        assert_eq!(extended.expires_at, "2024-07-08T00:00:00Z");
+        assert_eq!(db.list_restored_files(None).unwrap()[0].expires_at, "2024-07-08T00:00:00Z");
+
+        assert!(extend_retention(&db, "/Restored/unknown.mov", 7, at("2024-07-01T00:00:00Z")).is_err());
+    }
+}
@@ -0,0 +1,551 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter, Manager, State};
+
+use crate::commands::audit::{AuditOperation, OperationAuditEntry};
+use crate::commands::aws_operations::S3ClientTrait;
+use crate::commands::config::{load_config, RetentionAction, RetentionMode, RetentionSettings};
+use crate::commands::local_file_lock::{is_local_file_locked, unlock_local_file_at};
+use crate::commands::metadata::{calculate_file_hash, metadata_db_path, MetadataDatabase, UploadRecord};
+use crate::commands::upload_system::{UploadItemLabels, UploadOutcome, UploadQueueState};
+use crate::internal::time::file_time_to_rfc3339;
+use crate::internal::{AppError, InternalError};
+
+/// 確認モードで対象一覧を通知するイベント名
+pub const RETENTION_CLEANUP_PENDING_EVENT: &str = "retention-cleanup-pending";
+/// 削除・移動の完了を通知するイベント名
+pub const RETENTION_CLEANUP_COMPLETED_EVENT: &str = "retention-cleanup-completed";
+
+/// リテンションの確認間隔
+const RETENTION_CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+/// 起動直後の負荷を避けるための初回確認までの待ち時間
+const INITIAL_CHECK_DELAY: Duration = Duration::from_secs(60);
+
+/// 対象ファイルごとの処理結果（監査記録はoperations_auditへ書き込む）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RetentionCleanupEntry {
+    pub file_path: String,
+    pub s3_key: String,
+    /// アーカイブフォルダへ移動した場合の移動先
+    pub destination: Option<String>,
+    pub success: bool,
+    pub error_message: Option<String>,
+}
+
+/// retention-cleanup-pendingイベントのペイロード
+#[derive(Debug, Clone, Serialize)]
+pub struct RetentionCleanupPending {
+    pub retention_days: u32,
+    pub action: RetentionAction,
+    pub candidates: Vec<UploadRecord>,
+}
+
+/// リテンション実行結果
+#[derive(Debug, Clone, Serialize)]
+pub struct RetentionCleanupResult {
+    pub processed_count: usize,
+    pub failed_count: usize,
+    /// 確認後に変更・再アップロードされ、実行時の再確認で対象から外れた件数
+    pub skipped_count: usize,
+    pub entries: Vec<RetentionCleanupEntry>,
+}
+
+/// 確認待ちのリテンション対象（Tauriのmanage対象）
+#[derive(Default)]
+pub struct RetentionState(Mutex<Vec<UploadRecord>>);
+
+fn open_metadata_db(app: &AppHandle) -> Result<MetadataDatabase, InternalError> {
+    let db_path = metadata_db_path(app)?;
+    MetadataDatabase::new(&db_path.to_string_lossy())
+        .map_err(|e| InternalError::Database(format!("Failed to create database connection: {}", e)))
+}
+
+/// アップロード完了をメタデータDBへ記録（S3上のサイズが一致すれば検証済み、検証結果を返す）
+pub async fn record_completed_upload(
+    app: &AppHandle,
+    s3_client: &dyn S3ClientTrait,
+    bucket: &str,
+    file_path: &str,
+    outcome: &UploadOutcome,
+    labels: &UploadItemLabels,
+) -> bool {
+    // 検証で見つかったオブジェクトのストレージクラスも記録（プロジェクトの内訳集計用）
+    let uploaded_object = match s3_client.list_objects(bucket, Some(&outcome.s3_key)).await {
+        Ok(objects) => objects.into_iter()
+            .find(|object| object.key == outcome.s3_key && object.size == outcome.uploaded_bytes),
+        Err(e) => {
+            log::warn!("Failed to verify uploaded object {}: {}", outcome.s3_key, e);
+            None
+        }
+    };
+    let verified = uploaded_object.is_some();
+    let db = match open_metadata_db(app) {
+        Ok(db) => db,
+        Err(e) => {
+            log::warn!("Failed to record completed upload {}: {}", file_path, e);
+            return verified;
+        }
+    };
+    // リテンションでローカル原本の変更を検出できるよう、アップロード時点の更新日時とハッシュを残す
+    let local_metadata = std::fs::metadata(file_path).ok();
+    let source_modified_at = local_metadata.as_ref().and_then(|m| file_time_to_rfc3339(m.modified()));
+    let file_hash = db.get_metadata_by_path(file_path).ok()
+        .filter(|metadata| {
+            // メタデータ作成後に書き換えられていれば、そのハッシュは現在の内容と一致しない
+            source_modified_at.as_deref() == Some(metadata.modified_at.as_str())
+                && local_metadata.as_ref().map(|m| m.len()) == Some(metadata.file_size)
+        })
+        .map(|metadata| metadata.file_hash);
+
+    let record = UploadRecord {
+        file_path: file_path.to_string(),
+        bucket: bucket.to_string(),
+        s3_key: outcome.s3_key.clone(),
+        file_size: outcome.original_size,
+        uploaded_at: chrono::Utc::now().to_rfc3339(),
+        verified,
+        local_removed_at: None,
+        project_id: labels.project_id.clone(),
+        note: labels.note.clone(),
+        storage_class: uploaded_object.map(|object| object.storage_class),
+        source_modified_at,
+        file_hash,
+    };
+    if let Err(e) = db.record_upload(&record) {
+        log::warn!("Failed to record completed upload {}: {}", file_path, e);
+    }
+    verified
+}
+
+/// ローカル原本がアップロード時から変わっていないか（サイズ・更新日時、記録があればハッシュも比較）
+///
+/// 更新日時を記録していない旧レコードは変更の有無を判断できないため、変更ありとして扱う
+pub fn is_unchanged_since_upload(record: &UploadRecord) -> bool {
+    let Some(recorded_modified_at) = record.source_modified_at.as_deref() else {
+        return false;
+    };
+    let Ok(metadata) = std::fs::metadata(&record.file_path) else {
+        return false;
+    };
+    if !metadata.is_file()
+        || metadata.len() != record.file_size
+        || file_time_to_rfc3339(metadata.modified()).as_deref() != Some(recorded_modified_at)
+    {
+        return false;
+    }
+    match &record.file_hash {
+        Some(hash) => calculate_file_hash(&PathBuf::from(&record.file_path))
+            .map(|current| &current == hash)
+            .unwrap_or(false),
+        None => true,
+    }
+}
+
+/// 保持期間を過ぎた検証済みのアップロードのうち、ローカル原本が変更されずに残っているものを選ぶ
+pub fn select_candidates(
+    db: &MetadataDatabase,
+    retention_days: u32,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<UploadRecord>, InternalError> {
+    let cutoff = now - chrono::Duration::days(retention_days as i64);
+    let records = db.find_retention_candidates(&cutoff.to_rfc3339())
+        .map_err(|e| InternalError::Database(format!("Failed to query retention candidates: {}", e)))?;
+
+    // アップロード後に書き換えられたファイルはS3と内容が異なるため対象外
+    Ok(records.into_iter().filter(is_unchanged_since_upload).collect())
+}
+
+/// 移動先に同名ファイルがある場合は日時を付けて重複を避ける
+fn unique_destination(archive_dir: &Path, file_name: &str) -> PathBuf {
+    let destination = archive_dir.join(file_name);
+    if !destination.exists() {
+        return destination;
+    }
+    let path = Path::new(file_name);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or(file_name);
+    let timestamp = chrono::Utc::now().format("%Y%m%d%H%M%S");
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => archive_dir.join(format!("{}-{}.{}", stem, timestamp, ext)),
+        None => archive_dir.join(format!("{}-{}", stem, timestamp)),
+    }
+}
+
+/// ファイルをゴミ箱またはアーカイブフォルダへ移動（移動した場合は移動先を返す）
+///
+/// アップロード後に読み取り専用・uchgにしたファイルは移動・削除できないため、先に解除する
+pub fn apply_retention_action(path: &Path, settings: &RetentionSettings) -> Result<Option<PathBuf>, InternalError> {
+    if is_local_file_locked(path)? {
+        unlock_local_file_at(path)?;
+    }
+    match settings.action {
+        RetentionAction::Trash => {
+            trash::delete(path)
+                .map_err(|e| InternalError::File(format!("Failed to move {} to trash: {}", path.display(), e)))?;
+            Ok(None)
+        }
+        RetentionAction::MoveToArchive => {
+            let archive_dir = settings.archive_dir.as_deref()
+                .filter(|dir| !dir.is_empty())
+                .ok_or_else(|| InternalError::Config("Archive folder is not configured".to_string()))?;
+            let archive_dir = Path::new(archive_dir);
+            std::fs::create_dir_all(archive_dir)?;
+
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .ok_or_else(|| InternalError::File(format!("Invalid file name: {}", path.display())))?;
+            let destination = unique_destination(archive_dir, file_name);
+            // 別ボリュームへの移動はrenameできないため、コピーしてから削除する
+            if std::fs::rename(path, &destination).is_err() {
+                std::fs::copy(path, &destination)?;
+                std::fs::remove_file(path)?;
+            }
+            Ok(Some(destination))
+        }
+    }
+}
+
+/// アップロード時に付与したFinderタグ（アップロードキューの設定から取得）
+fn uploaded_finder_tag(app: &AppHandle) -> Option<String> {
+    let queue_state = app.try_state::<UploadQueueState>()?;
+    let queue = queue_state.lock().ok()?;
+    queue.config.as_ref()?.set_finder_tag_on_complete.clone()
+}
+
+/// 対象ファイルを処理し、結果を操作の監査ログへ記録
+///
+/// `finder_tag`はアップロード時に付与したタグで、移動前に外す（アーカイブフォルダ側に残さない）
+fn execute_cleanup(
+    db: &MetadataDatabase,
+    candidates: &[UploadRecord],
+    settings: &RetentionSettings,
+    finder_tag: Option<&str>,
+) -> RetentionCleanupResult {
+    let mut entries = Vec::with_capacity(candidates.len());
+
+    for record in candidates {
+        if let Some(tag) = finder_tag {
+            if let Err(e) = crate::finder_tags::remove_finder_tag(Path::new(&record.file_path), tag) {
+                log::warn!("Failed to remove Finder tag from {}: {}", record.file_path, e);
+            }
+        }
+        let result = apply_retention_action(Path::new(&record.file_path), settings);
+        let entry = RetentionCleanupEntry {
+            file_path: record.file_path.clone(),
+            s3_key: record.s3_key.clone(),
+            destination: result.as_ref().ok().and_then(|d| d.as_ref()).map(|d| d.to_string_lossy().to_string()),
+            success: result.is_ok(),
+            error_message: result.as_ref().err().map(|e| e.to_string()),
+        };
+
+        match &result {
+            Ok(destination) => {
+                match destination {
+                    Some(destination) => log::info!("Retention moved local original: {} -> {} (s3://{}/{})", record.file_path, destination.display(), record.bucket, record.s3_key),
+                    None => log::info!("Retention removed local original: {} (s3://{}/{})", record.file_path, record.bucket, record.s3_key),
+                }
+                if let Err(e) = db.mark_local_removed(&record.file_path, &chrono::Utc::now().to_rfc3339()) {
+                    log::error!("Failed to mark {} as removed: {}", record.file_path, e);
+                }
+            }
+            Err(e) => log::error!("Retention failed for {}: {}", record.file_path, e),
+        }
+        let operation_entry = OperationAuditEntry::new(AuditOperation::LocalDelete, &result)
+            .local_path(record.file_path.clone())
+            .s3_object(record.bucket.clone(), record.s3_key.clone())
+            .size(record.file_size);
+        if let Err(e) = db.insert_operation_audit(&operation_entry) {
+            log::error!("Failed to write operation audit for {}: {}", record.file_path, e);
+        }
+        entries.push(entry);
+    }
+
+    let failed_count = entries.iter().filter(|e| !e.success).count();
+    RetentionCleanupResult {
+        processed_count: entries.len() - failed_count,
+        failed_count,
+        skipped_count: 0,
+        entries,
+    }
+}
+
+fn emit_completed(app: &AppHandle, result: &RetentionCleanupResult) {
+    if let Err(e) = app.emit(RETENTION_CLEANUP_COMPLETED_EVENT, result) {
+        log::error!("Failed to emit retention cleanup result: {}", e);
+    }
+}
+
+/// 保持期間を過ぎたファイルを確認し、設定に応じて通知または実行
+pub fn run_retention_check(app: &AppHandle) -> Result<(), InternalError> {
+    let config = load_config(app)?;
+    let Some(retention_days) = config.local_retention_days else {
+        return Ok(());
+    };
+
+    let db = open_metadata_db(app)?;
+    let candidates = select_candidates(&db, retention_days, chrono::Utc::now())?;
+    if candidates.is_empty() {
+        log::debug!("No local originals past the {} day retention period", retention_days);
+        return Ok(());
+    }
+
+    match config.retention_settings.mode {
+        RetentionMode::Confirm => {
+            log::info!("{} local original(s) are waiting for retention confirmation", candidates.len());
+            if let Ok(mut pending) = app.state::<RetentionState>().0.lock() {
+                *pending = candidates.clone();
+            }
+            let payload = RetentionCleanupPending {
+                retention_days,
+                action: config.retention_settings.action,
+                candidates,
+            };
+            if let Err(e) = app.emit(RETENTION_CLEANUP_PENDING_EVENT, &payload) {
+                log::error!("Failed to emit retention cleanup pending: {}", e);
+            }
+        }
+        RetentionMode::Auto => {
+            let finder_tag = uploaded_finder_tag(app);
+            let result = execute_cleanup(&db, &candidates, &config.retention_settings, finder_tag.as_deref());
+            emit_completed(app, &result);
+        }
+    }
+    Ok(())
+}
+
+/// 日次のリテンション確認を開始
+pub fn start_retention_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(INITIAL_CHECK_DELAY).await;
+        loop {
+            if let Err(e) = run_retention_check(&app) {
+                log::error!("Retention check failed: {}", e);
+            }
+            tokio::time::sleep(RETENTION_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// 確認待ちのリテンション対象を取得
+#[command]
+pub async fn get_pending_retention_cleanup(
+    state: State<'_, RetentionState>,
+) -> Result<Vec<UploadRecord>, AppError> {
+    let pending = state.0.lock()
+        .map_err(|e| InternalError::Other(format!("Failed to lock retention state: {}", e)))?;
+    Ok(pending.clone())
+}
+
+/// 確認待ちの対象のうち、指定したファイルのリテンションを実行
+#[command]
+pub async fn confirm_retention_cleanup(
+    app_handle: AppHandle,
+    file_paths: Vec<String>,
+    state: State<'_, RetentionState>,
+) -> Result<RetentionCleanupResult, AppError> {
+    let confirmed: Vec<UploadRecord> = {
+        let mut pending = state.0.lock()
+            .map_err(|e| InternalError::Other(format!("Failed to lock retention state: {}", e)))?;
+        let (confirmed, remaining) = std::mem::take(&mut *pending)
+            .into_iter()
+            .partition(|record| file_paths.contains(&record.file_path));
+        *pending = remaining;
+        confirmed
+    };
+
+    let config = load_config(&app_handle)?;
+    let db = open_metadata_db(&app_handle)?;
+    let still_eligible = match config.local_retention_days {
+        Some(retention_days) => recheck_candidates(&db, &confirmed, retention_days, chrono::Utc::now())?,
+        None => Vec::new(),
+    };
+    let finder_tag = uploaded_finder_tag(&app_handle);
+    let mut result = execute_cleanup(&db, &still_eligible, &config.retention_settings, finder_tag.as_deref());
+    result.skipped_count = confirmed.len() - still_eligible.len();
+    emit_completed(&app_handle, &result);
+    Ok(result)
+}
+
+/// 確認時点の一覧は最大で1日前のものなので、実行直前にDBとローカル原本から対象を選び直す
+///
+/// 確認後に書き換え・再アップロードされたファイルや、保持期間の設定を外したファイルは対象から外れる
+fn recheck_candidates(
+    db: &MetadataDatabase,
+    confirmed: &[UploadRecord],
+    retention_days: u32,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<UploadRecord>, InternalError> {
+    let current = select_candidates(db, retention_days, now)?;
+    let eligible: Vec<UploadRecord> = current.into_iter()
+        .filter(|record| confirmed.iter().any(|c| c.file_path == record.file_path && c.s3_key == record.s3_key))
+        .collect();
+    for record in confirmed.iter().filter(|c| !eligible.iter().any(|e| e.file_path == c.file_path)) {
+        log::warn!("Skipped retention for {}: changed since it was confirmed", record.file_path);
+    }
+    Ok(eligible)
+}
+
+/// 確認待ちの対象を破棄（次回の確認で再度通知される）
+#[command]
+pub async fn dismiss_retention_cleanup(state: State<'_, RetentionState>) -> Result<usize, AppError> {
+    let mut pending = state.0.lock()
+        .map_err(|e| InternalError::Other(format!("Failed to lock retention state: {}", e)))?;
+    let count = pending.len();
+    pending.clear();
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::audit::AuditLogFilter;
+
+    /// 現在のローカルファイルの更新日時でアップロード記録を作成
+    fn create_record(file_path: &Path, file_size: u64, uploaded_at: &str, verified: bool) -> UploadRecord {
+        let source_modified_at = std::fs::metadata(file_path).ok().and_then(|m| file_time_to_rfc3339(m.modified()));
+        UploadRecord {
+            file_path: file_path.to_string_lossy().to_string(),
+            bucket: "test-bucket".to_string(),
+            s3_key: "uploads/clip.mov".to_string(),
+            file_size,
+            uploaded_at: uploaded_at.to_string(),
+            verified,
+            local_removed_at: None,
+            project_id: None,
+            note: None,
+            storage_class: None,
+            source_modified_at,
+            file_hash: None,
+        }
+    }
+
+    fn set_modified(path: &Path, modified: std::time::SystemTime) {
+        std::fs::File::options().write(true).open(path).unwrap().set_modified(modified).unwrap();
+    }
+
+    #[test]
+    fn test_select_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = MetadataDatabase::new(dir.path().join("metadata.db").to_str().unwrap()).unwrap();
+        let now = chrono::Utc::now();
+        let old = (now - chrono::Duration::days(40)).to_rfc3339();
+        let recent = (now - chrono::Duration::days(5)).to_rfc3339();
+
+        let expired = dir.path().join("expired.mov");
+        let unverified = dir.path().join("unverified.mov");
+        let fresh = dir.path().join("fresh.mov");
+        let modified = dir.path().join("modified.mov");
+        for path in [&expired, &unverified, &fresh, &modified] {
+            std::fs::write(path, b"footage").unwrap();
+        }
+        db.record_upload(&create_record(&expired, 7, &old, true)).unwrap();
+        db.record_upload(&create_record(&unverified, 7, &old, false)).unwrap();
+        db.record_upload(&create_record(&fresh, 7, &recent, true)).unwrap();
+        db.record_upload(&create_record(&modified, 3, &old, true)).unwrap();
+
+        let candidates = select_candidates(&db, 30, now).unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].file_path, expired.to_string_lossy());
+    }
+
+    #[test]
+    fn test_select_candidates_skips_same_size_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = MetadataDatabase::new(dir.path().join("metadata.db").to_str().unwrap()).unwrap();
+        let now = chrono::Utc::now();
+        let old = (now - chrono::Duration::days(40)).to_rfc3339();
+
+        // 同じサイズで書き直され、更新日時が変わったファイル
+        let retouched = dir.path().join("retouched.mov");
+        std::fs::write(&retouched, b"footage").unwrap();
+        db.record_upload(&create_record(&retouched, 7, &old, true)).unwrap();
+        std::fs::write(&retouched, b"FOOTAGE").unwrap();
+        set_modified(&retouched, std::time::SystemTime::now() + Duration::from_secs(60));
+
+        // 更新日時を戻されたが内容が異なるファイルはハッシュで検出する
+        let restamped = dir.path().join("restamped.xmp");
+        std::fs::write(&restamped, b"sidecar").unwrap();
+        let mut record = create_record(&restamped, 7, &old, true);
+        record.file_hash = Some(calculate_file_hash(&restamped).unwrap());
+        db.record_upload(&record).unwrap();
+        let modified = std::fs::metadata(&restamped).unwrap().modified().unwrap();
+        std::fs::write(&restamped, b"SIDECAR").unwrap();
+        set_modified(&restamped, modified);
+
+        // 更新日時を記録していない旧レコード
+        let legacy = dir.path().join("legacy.mov");
+        std::fs::write(&legacy, b"footage").unwrap();
+        let mut record = create_record(&legacy, 7, &old, true);
+        record.source_modified_at = None;
+        db.record_upload(&record).unwrap();
+
+        assert!(select_candidates(&db, 30, now).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_recheck_candidates_drops_files_changed_after_confirmation() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = MetadataDatabase::new(dir.path().join("metadata.db").to_str().unwrap()).unwrap();
+        let now = chrono::Utc::now();
+        let old = (now - chrono::Duration::days(40)).to_rfc3339();
+
+        let kept = dir.path().join("kept.mov");
+        let edited = dir.path().join("edited.mov");
+        for path in [&kept, &edited] {
+            std::fs::write(path, b"footage").unwrap();
+            db.record_upload(&create_record(path, 7, &old, true)).unwrap();
+        }
+        let confirmed = select_candidates(&db, 30, now).unwrap();
+        assert_eq!(confirmed.len(), 2);
+
+        // 確認待ちの間に編集されたファイルは実行時に外す
+        set_modified(&edited, std::time::SystemTime::now() + Duration::from_secs(60));
+        let eligible = recheck_candidates(&db, &confirmed, 30, now).unwrap();
+        assert_eq!(eligible.len(), 1);
+        assert_eq!(eligible[0].file_path, kept.to_string_lossy());
+    }
+
+    #[test]
+    fn test_move_to_archive_records_audit() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = MetadataDatabase::new(dir.path().join("metadata.db").to_str().unwrap()).unwrap();
+        let source = dir.path().join("clip.mov");
+        std::fs::write(&source, b"footage").unwrap();
+        // アップロード後に読み取り専用にしたファイルも移動できる
+        crate::commands::local_file_lock::lock_local_file(&source, false).unwrap();
+        let record = create_record(&source, 7, &chrono::Utc::now().to_rfc3339(), true);
+        db.record_upload(&record).unwrap();
+
+        let archive_dir = dir.path().join("archive");
+        let settings = RetentionSettings {
+            mode: RetentionMode::Auto,
+            action: RetentionAction::MoveToArchive,
+            archive_dir: Some(archive_dir.to_string_lossy().to_string()),
+        };
+        let result = execute_cleanup(&db, &[record], &settings, Some("アーカイブ済み:green"));
+
+        assert_eq!(result.processed_count, 1);
+        assert!(!source.exists());
+        assert!(archive_dir.join("clip.mov").exists());
+        assert!(!is_local_file_locked(&archive_dir.join("clip.mov")).unwrap());
+        assert_eq!(result.entries[0].destination.as_deref(), Some(archive_dir.join("clip.mov").to_string_lossy().as_ref()));
+        let filter = AuditLogFilter { operation: Some(AuditOperation::LocalDelete), ..Default::default() };
+        let audit = db.query_operation_audit(&filter, 10, 0).unwrap();
+        assert_eq!(audit.len(), 1);
+        assert!(audit[0].success);
+        assert_eq!(audit[0].local_path.as_deref(), Some(source.to_string_lossy().as_ref()));
+        assert!(select_candidates(&db, 0, chrono::Utc::now()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_unique_destination() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(unique_destination(dir.path(), "clip.mov"), dir.path().join("clip.mov"));
+
+        std::fs::write(dir.path().join("clip.mov"), b"x").unwrap();
+        let destination = unique_destination(dir.path(), "clip.mov");
+        assert_ne!(destination, dir.path().join("clip.mov"));
+        assert_eq!(destination.extension().unwrap(), "mov");
+    }
+}
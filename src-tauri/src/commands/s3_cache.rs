@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle};
+
+use crate::commands::aws_operations::{fetch_s3_listing, AwsConfig, S3Object};
+use crate::commands::metadata::{metadata_db_path, MetadataDatabase};
+use crate::internal::{AppError, InternalError};
+
+/// キャッシュ済みのS3オブジェクト（アップロード記録と対応付けたローカルファイル付き）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CachedS3Object {
+    pub key: String,
+    pub size: u64,
+    pub storage_class: String,
+    pub last_modified: String,
+    pub etag: String,
+    /// 一覧で最後に確認した日時
+    pub fetched_at: String,
+    /// このキーへアップロードしたローカルファイル
+    pub local_path: Option<String>,
+    /// リテンションでローカルを削除した日時
+    pub local_removed_at: Option<String>,
+}
+
+/// キャッシュの検索条件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct S3CacheFilter {
+    /// キーの部分一致（大文字小文字を区別しない）
+    pub query: Option<String>,
+    pub storage_class: Option<String>,
+    /// trueはローカルファイルが残っているもの、falseはローカルにないもののみ
+    pub has_local_copy: Option<bool>,
+    pub limit: Option<usize>,
+}
+
+/// ネットワークなしでの閲覧結果
+#[derive(Debug, Clone, Serialize)]
+pub struct S3CacheBrowseResult {
+    pub bucket: String,
+    pub prefix: String,
+    pub objects: Vec<CachedS3Object>,
+    /// このプレフィックスを含む範囲を最後に同期した日時（未同期はNone）
+    pub last_synced_at: Option<String>,
+}
+
+/// 差分更新の結果
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct S3CacheRefreshResult {
+    pub bucket: String,
+    pub prefix: String,
+    pub added: usize,
+    /// サイズ・ETag・ストレージクラスのいずれかが変わったもの
+    pub updated: usize,
+    pub removed: usize,
+    pub total_objects: usize,
+    pub last_synced_at: String,
+}
+
+/// キャッシュと一覧の突き合わせ結果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct S3CacheDiff {
+    pub added: usize,
+    pub updated: usize,
+    /// 一覧になくなったキー
+    pub removed: Vec<String>,
+}
+
+/// キャッシュ済みのオブジェクトとListObjectsV2の結果を突き合わせる
+pub fn diff_s3_listing(cached: &[S3Object], listed: &[S3Object]) -> S3CacheDiff {
+    let mut remaining: HashMap<&str, &S3Object> = cached.iter().map(|object| (object.key.as_str(), object)).collect();
+    let mut diff = S3CacheDiff::default();
+    for object in listed {
+        match remaining.remove(object.key.as_str()) {
+            None => diff.added += 1,
+            Some(previous) => {
+                if previous.size != object.size || previous.etag != object.etag || previous.storage_class != object.storage_class {
+                    diff.updated += 1;
+                }
+            }
+        }
+    }
+    diff.removed = remaining.into_keys().map(str::to_string).collect();
+    diff.removed.sort();
+    diff
+}
+
+fn open_metadata_db(app: &AppHandle) -> Result<MetadataDatabase, InternalError> {
+    let db_path = metadata_db_path(app)?;
+    MetadataDatabase::new(&db_path.to_string_lossy())
+        .map_err(|e| InternalError::Database(format!("Failed to create database connection: {}", e)))
+}
+
+/// 一覧の取得結果でプレフィックス配下のキャッシュを置き換える
+pub fn store_s3_listing(
+    db: &MetadataDatabase,
+    bucket: &str,
+    prefix: &str,
+    listed: &[S3Object],
+) -> Result<S3CacheRefreshResult, InternalError> {
+    let cached = db.cached_s3_objects_under(bucket, prefix)?;
+    let diff = diff_s3_listing(&cached, listed);
+    let synced_at = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    db.apply_s3_cache_update(bucket, prefix, listed, &diff.removed, &synced_at)?;
+    Ok(S3CacheRefreshResult {
+        bucket: bucket.to_string(),
+        prefix: prefix.to_string(),
+        added: diff.added,
+        updated: diff.updated,
+        removed: diff.removed.len(),
+        total_objects: listed.len(),
+        last_synced_at: synced_at,
+    })
+}
+
+/// list_s3_objectsの結果をキャッシュへ反映（失敗しても一覧の取得は成功扱い）
+pub fn cache_s3_listing(app: &AppHandle, bucket: &str, prefix: Option<&str>, listed: &[S3Object]) {
+    let stored = open_metadata_db(app)
+        .and_then(|db| store_s3_listing(&db, bucket, prefix.unwrap_or(""), listed));
+    if let Err(e) = stored {
+        log::warn!("Failed to update S3 object cache for {}: {}", bucket, e);
+    }
+}
+
+/// キャッシュからオブジェクトを閲覧・検索（ネットワークに接続しない）
+#[command]
+pub async fn browse_s3_cached(
+    app: AppHandle,
+    bucket: String,
+    prefix: Option<String>,
+    filter: Option<S3CacheFilter>,
+) -> Result<S3CacheBrowseResult, AppError> {
+    let prefix = prefix.unwrap_or_default();
+    let db = open_metadata_db(&app)?;
+    let objects = db.list_cached_s3_objects(&bucket, &prefix, &filter.unwrap_or_default())
+        .map_err(InternalError::from)?;
+    let last_synced_at = db.s3_cache_synced_at(&bucket, &prefix).map_err(InternalError::from)?;
+    Ok(S3CacheBrowseResult { bucket, prefix, objects, last_synced_at })
+}
+
+/// ListObjectsV2でプレフィックス配下を取得し直し、追加・更新・削除をキャッシュへ反映
+#[command]
+pub async fn refresh_s3_cache(
+    app: AppHandle,
+    config: AwsConfig,
+    prefix: Option<String>,
+) -> Result<S3CacheRefreshResult, AppError> {
+    let listed = fetch_s3_listing(&app, &config, prefix.as_deref()).await?;
+    let db = open_metadata_db(&app)?;
+    let result = store_s3_listing(&db, &config.bucket_name, prefix.as_deref().unwrap_or(""), &listed)?;
+    log::info!(
+        "Refreshed S3 object cache for s3://{}/{}: +{} ~{} -{}",
+        result.bucket, result.prefix, result.added, result.updated, result.removed
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::metadata::UploadRecord;
+
+    fn object(key: &str, size: u64, storage_class: &str) -> S3Object {
+        S3Object {
+            key: key.to_string(),
+            size,
+            last_modified: "2024-06-01T00:00:00Z".to_string(),
+            storage_class: storage_class.to_string(),
+            etag: format!("\"{}\"", size),
+        }
+    }
+
+    #[test]
+    fn test_diff_detects_added_updated_and_removed_keys() {
+        let cached = vec![object("a.mov", 1, "STANDARD"), object("b.mov", 2, "STANDARD"), object("c.mov", 3, "STANDARD")];
+        let listed = vec![object("a.mov", 1, "STANDARD"), object("b.mov", 2, "DEEP_ARCHIVE"), object("d.mov", 4, "STANDARD")];
+        let diff = diff_s3_listing(&cached, &listed);
+        assert_eq!(diff, S3CacheDiff { added: 1, updated: 1, removed: vec!["c.mov".to_string()] });
+    }
+
+    #[test]
+    fn test_refresh_replaces_prefix_and_browse_joins_upload_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = MetadataDatabase::new(dir.path().join("metadata.db").to_str().unwrap()).unwrap();
+        store_s3_listing(&db, "footage", "", &[object("raw/a.mov", 1, "STANDARD"), object("raw/b.mov", 2, "STANDARD"), object("docs/x.pdf", 3, "STANDARD")]).unwrap();
+        let result = store_s3_listing(&db, "footage", "raw/", &[object("raw/a.mov", 1, "DEEP_ARCHIVE")]).unwrap();
+        assert_eq!((result.added, result.updated, result.removed), (0, 1, 1));
+
+        db.record_upload(&UploadRecord {
+            file_path: "/footage/a.mov".to_string(),
+            bucket: "footage".to_string(),
+            s3_key: "raw/a.mov".to_string(),
+            file_size: 1,
+            uploaded_at: "2024-06-01T00:00:00Z".to_string(),
+            verified: true,
+            local_removed_at: None,
+            project_id: None,
+            note: None,
+            storage_class: None,
+            source_modified_at: None,
+            file_hash: None,
+        }).unwrap();
+
+        let all = db.list_cached_s3_objects("footage", "", &S3CacheFilter::default()).unwrap();
+        assert_eq!(all.iter().map(|o| o.key.as_str()).collect::<Vec<_>>(), vec!["docs/x.pdf", "raw/a.mov"]);
+        let local = db.list_cached_s3_objects("footage", "", &S3CacheFilter { has_local_copy: Some(true), ..Default::default() }).unwrap();
+        assert_eq!(local.len(), 1);
+        assert_eq!(local[0].local_path.as_deref(), Some("/footage/a.mov"));
+        let found = db.list_cached_s3_objects("footage", "", &S3CacheFilter { query: Some("X.PDF".to_string()), ..Default::default() }).unwrap();
+        assert_eq!(found.len(), 1);
+
+        assert!(db.s3_cache_synced_at("footage", "raw/sub/").unwrap().is_some());
+        assert!(db.s3_cache_synced_at("other", "").unwrap().is_none());
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::commands::aws_auth::AwsCredentials;
+use crate::commands::aws_operations::create_s3_client_for_credentials;
+use crate::commands::config::load_config;
+
+/// キャッシュするS3クライアントの上限（超えたら作り直す）
+const MAX_CACHED_CLIENTS: usize = 8;
+
+/// S3クライアントを使い回せる条件（シークレットは平文で保持せずハッシュで比較する）
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct S3ClientKey {
+    access_key_id: String,
+    secret_fingerprint: u64,
+    region: String,
+    endpoint: Option<String>,
+}
+
+impl S3ClientKey {
+    pub fn new(
+        access_key_id: &str,
+        secret_access_key: &str,
+        session_token: Option<&str>,
+        region: &str,
+        endpoint: Option<&str>,
+    ) -> Self {
+        let mut hasher = DefaultHasher::new();
+        secret_access_key.hash(&mut hasher);
+        session_token.hash(&mut hasher);
+        Self {
+            access_key_id: access_key_id.to_string(),
+            secret_fingerprint: hasher.finish(),
+            region: region.to_string(),
+            endpoint: endpoint.map(str::to_string),
+        }
+    }
+}
+
+/// 認証情報・リージョン・エンドポイントが同じ間はS3クライアント（接続プール）を使い回すキャッシュ
+pub struct S3ClientCache<C> {
+    clients: Mutex<HashMap<S3ClientKey, C>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<C: Clone> S3ClientCache<C> {
+    pub fn new() -> Self {
+        Self { clients: Mutex::new(HashMap::new()), hits: AtomicU64::new(0), misses: AtomicU64::new(0) }
+    }
+
+    /// キャッシュ済みのクライアントを返し、無ければ`build`で作って登録する
+    pub fn get_or_insert_with(&self, key: S3ClientKey, build: impl FnOnce() -> C) -> C {
+        let mut clients = self.clients.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(client) = clients.get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return client.clone();
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        if clients.len() >= MAX_CACHED_CLIENTS {
+            clients.clear();
+        }
+        let client = build();
+        clients.insert(key, client.clone());
+        client
+    }
+
+    /// 全てのクライアントを破棄する（認証プロファイルの変更・ロック時）
+    pub fn invalidate(&self) -> usize {
+        let mut clients = self.clients.lock().unwrap_or_else(|e| e.into_inner());
+        let removed = clients.len();
+        clients.clear();
+        removed
+    }
+
+    /// (再利用した回数, 作成した回数)
+    pub fn stats(&self) -> (u64, u64) {
+        (self.hits.load(Ordering::Relaxed), self.misses.load(Ordering::Relaxed))
+    }
+}
+
+impl<C: Clone> Default for S3ClientCache<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+lazy_static::lazy_static! {
+    /// アップロード・一覧・ライフサイクルなど全経路で共有するS3クライアント
+    pub static ref S3_CLIENT_CACHE: S3ClientCache<aws_sdk_s3::Client> = S3ClientCache::new();
+}
+
+/// キャッシュ済みのS3クライアントを破棄する
+pub fn invalidate_s3_client_cache(reason: &str) {
+    let removed = S3_CLIENT_CACHE.invalidate();
+    if removed > 0 {
+        let (hits, misses) = S3_CLIENT_CACHE.stats();
+        log::info!("Invalidated {} cached S3 client(s): {} (reused {} / created {})", removed, reason, hits, misses);
+    }
+}
+
+/// 認証成功後、初回のS3操作がTLSハンドシェイクを待たないようバックグラウンドで接続しておく
+pub fn spawn_s3_warm_up(app: &AppHandle, credentials: AwsCredentials) {
+    let Ok(config) = load_config(app) else {
+        return;
+    };
+    if !config.aws_settings.warm_up_s3_connection {
+        return;
+    }
+    let Some(bucket) = config.user_preferences.default_bucket_name.filter(|bucket| !bucket.is_empty()) else {
+        return;
+    };
+    tauri::async_runtime::spawn(async move {
+        let started_at = std::time::Instant::now();
+        let client = match create_s3_client_for_credentials(&credentials).await {
+            Ok(client) => client,
+            Err(e) => {
+                log::debug!("Skipped S3 warm-up: {}", e);
+                return;
+            }
+        };
+        match client.head_bucket(&bucket).await {
+            Ok(()) => log::info!("S3 connection warmed up for {} in {}ms", bucket, started_at.elapsed().as_millis()),
+            Err(e) => log::debug!("S3 warm-up for {} failed: {}", bucket, e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(secret: &str, region: &str) -> S3ClientKey {
+        S3ClientKey::new("AKIAEXAMPLE", secret, None, region, None)
+    }
+
+    #[test]
+    fn test_cache_reuses_until_key_changes_or_invalidated() {
+        let cache: S3ClientCache<u32> = S3ClientCache::new();
+        let mut built = 0;
+        for _ in 0..3 {
+            cache.get_or_insert_with(key("secret", "ap-northeast-1"), || { built += 1; built });
+        }
+        assert_eq!(built, 1);
+        assert_eq!(cache.stats(), (2, 1));
+
+        // シークレット・リージョンが変わったら別のクライアント
+        assert_eq!(cache.get_or_insert_with(key("rotated", "ap-northeast-1"), || 10), 10);
+        assert_eq!(cache.get_or_insert_with(key("secret", "us-east-1"), || 20), 20);
+        assert_eq!(cache.get_or_insert_with(key("secret", "ap-northeast-1"), || 30), 1);
+
+        assert_eq!(cache.invalidate(), 3);
+        assert_eq!(cache.get_or_insert_with(key("secret", "ap-northeast-1"), || 40), 40);
+    }
+
+    /// 小ファイルを連続アップロードする想定で、クライアントが一度だけ作成されることを確認する
+    #[test]
+    fn test_many_small_uploads_build_the_client_once() {
+        use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+
+        const UPLOADS: u32 = 200;
+        let build = || {
+            let config = aws_sdk_s3::Config::builder()
+                .behavior_version(BehaviorVersion::latest())
+                .region(Region::new("ap-northeast-1"))
+                .credentials_provider(Credentials::new("AKIAEXAMPLE", "secret", None, None, "test"))
+                .build();
+            aws_sdk_s3::Client::from_conf(config)
+        };
+
+        let cache = S3ClientCache::new();
+        for _ in 0..UPLOADS {
+            cache.get_or_insert_with(key("secret", "ap-northeast-1"), build);
+        }
+
+        assert_eq!(cache.stats(), (u64::from(UPLOADS) - 1, 1));
+    }
+}
@@ -0,0 +1,194 @@
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use md5::{Digest, Md5};
+
+use crate::internal::InternalError;
+
+const MIB: u64 = 1024 * 1024;
+/// S3のマルチパートアップロードの最小パートサイズ（最終パートを除く）
+const MIN_PART_SIZE: u64 = 5 * MIB;
+/// チャンクサイズが分からない場合に試す一般的なパートサイズ
+/// （ReelVaultの既定16MB・無料版5MB、AWS CLIの既定8MBなど）
+const COMMON_PART_SIZES_MIB: [u64; 12] = [16, 5, 8, 10, 15, 32, 50, 64, 100, 128, 256, 512];
+const READ_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// 解釈したS3のETag
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct S3Etag {
+    /// 16進のMD5（マルチパートの場合は各パートのMD5を連結したもののMD5）
+    pub md5: String,
+    /// マルチパートの場合のパート数（"-N"の部分）
+    pub part_count: Option<u64>,
+}
+
+/// ETagを解釈（引用符は除く）。MD5形式でないもの（SSE-KMSで暗号化したオブジェクトなど）はNone
+pub fn parse_s3_etag(etag: &str) -> Option<S3Etag> {
+    let etag = etag.trim().trim_matches('"');
+    let (md5, part_count) = match etag.split_once('-') {
+        Some((md5, parts)) => (md5, Some(parts.parse::<u64>().ok().filter(|parts| *parts > 0)?)),
+        None => (etag, None),
+    };
+    if md5.len() != 32 || !md5.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(S3Etag { md5: md5.to_ascii_lowercase(), part_count })
+}
+
+fn open(path: &Path) -> Result<BufReader<File>, InternalError> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| InternalError::File(format!("Failed to open file: {}", e)))
+}
+
+/// 最大`limit`バイトを読んでハッシュに加え、読んだバイト数を返す
+fn hash_part(reader: &mut impl Read, hasher: &mut Md5, limit: u64, buffer: &mut [u8]) -> Result<u64, InternalError> {
+    let mut read = 0;
+    while read < limit {
+        let want = (limit - read).min(buffer.len() as u64) as usize;
+        let count = reader.read(&mut buffer[..want])
+            .map_err(|e| InternalError::File(format!("Failed to read file: {}", e)))?;
+        if count == 0 {
+            break;
+        }
+        hasher.update(&buffer[..count]);
+        read += count as u64;
+    }
+    Ok(read)
+}
+
+/// シングルパートでアップロードしたオブジェクトのETag（ファイル全体のMD5）
+pub fn calculate_md5_etag(path: &Path) -> Result<String, InternalError> {
+    let mut reader = open(path)?;
+    let mut hasher = Md5::new();
+    let mut buffer = vec![0; READ_BUFFER_SIZE];
+    hash_part(&mut reader, &mut hasher, u64::MAX, &mut buffer)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// `chunk_size`ごとにマルチパートでアップロードした場合のETag（"各パートのMD5を連結したもののMD5-パート数"）
+pub fn calculate_multipart_etag(path: &Path, chunk_size: u64) -> Result<String, InternalError> {
+    if chunk_size == 0 {
+        return Err(InternalError::Other("Chunk size must be greater than 0".to_string()));
+    }
+    let mut reader = open(path)?;
+    let mut buffer = vec![0; READ_BUFFER_SIZE];
+    let mut part_digests = Md5::new();
+    let mut part_count = 0u64;
+    loop {
+        let mut part = Md5::new();
+        let read = hash_part(&mut reader, &mut part, chunk_size, &mut buffer)?;
+        // 空ファイルも1パートとして扱う
+        if read == 0 && part_count > 0 {
+            break;
+        }
+        part_digests.update(part.finalize());
+        part_count += 1;
+        if read < chunk_size {
+            break;
+        }
+    }
+    Ok(format!("{:x}-{}", part_digests.finalize(), part_count))
+}
+
+/// パート数から考えられるチャンクサイズを推定（候補が多い順ではなく、可能性の高い順）
+///
+/// `hint`（アップロード時の設定値など）を最優先し、一般的なパートサイズ、
+/// 均等割りをMB単位に切り上げたサイズの順に、そのパート数になるものだけを返す
+pub fn estimate_chunk_sizes(file_size: u64, part_count: u64, hint: Option<u64>) -> Vec<u64> {
+    if part_count == 0 {
+        return Vec::new();
+    }
+    if part_count == 1 {
+        // 1パートならチャンクサイズによらず同じ値になる
+        return vec![file_size.max(1)];
+    }
+    let produces = |chunk: u64| chunk > 0 && file_size.div_ceil(chunk) == part_count;
+    let even_split = file_size.div_ceil(part_count).div_ceil(MIB) * MIB;
+    let mut candidates = Vec::new();
+    let ordered = hint.into_iter()
+        .chain(COMMON_PART_SIZES_MIB.iter().map(|size| size * MIB))
+        .chain([even_split, file_size.div_ceil(part_count)]);
+    for chunk in ordered {
+        // アップロード時の設定値はS3の最小パートサイズ未満でもそのまま試す（S3互換ストレージ向け）
+        let allowed = Some(chunk) == hint || chunk >= MIN_PART_SIZE.min(file_size);
+        if produces(chunk) && allowed && !candidates.contains(&chunk) {
+            candidates.push(chunk);
+        }
+    }
+    candidates
+}
+
+/// ローカルファイルがETagと一致するか（シングルパート・マルチパートを自動判別）
+///
+/// ETagがMD5形式でない場合や、パート数に合うチャンクサイズが見つからない場合は判定できないためNone
+pub fn local_file_matches_etag(path: &Path, etag: &str, chunk_size_hint: Option<u64>) -> Result<Option<bool>, InternalError> {
+    let Some(expected) = parse_s3_etag(etag) else {
+        return Ok(None);
+    };
+    let Some(part_count) = expected.part_count else {
+        return Ok(Some(calculate_md5_etag(path)? == expected.md5));
+    };
+    let file_size = std::fs::metadata(path)
+        .map_err(|e| InternalError::File(format!("Failed to read file metadata: {}", e)))?
+        .len();
+    let candidates = estimate_chunk_sizes(file_size, part_count, chunk_size_hint);
+    if candidates.is_empty() {
+        return Ok(None);
+    }
+    let expected = format!("{}-{}", expected.md5, part_count);
+    for chunk_size in candidates {
+        if calculate_multipart_etag(path, chunk_size)? == expected {
+            log::debug!("ETag of {} matched with {} byte parts", path.display(), chunk_size);
+            return Ok(Some(true));
+        }
+    }
+    Ok(Some(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(content: &[u8]) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, content).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_etag_test_vectors() {
+        let file = temp_file(b"abcde");
+        assert_eq!(calculate_md5_etag(file.path()).unwrap(), "ab56b4d92b40713acc5af89985d4b786");
+        // md5(md5("ab") + md5("cd") + md5("e"))
+        assert_eq!(calculate_multipart_etag(file.path(), 2).unwrap(), "b5d55eef9b6106b14530433c1a359920-3");
+        assert_eq!(calculate_multipart_etag(file.path(), 5).unwrap(), "d4600bfd5be6d65a1d7158b29d2908db-1");
+        assert_eq!(calculate_multipart_etag(temp_file(b"").path(), 5).unwrap(), "59adb24ef3cdbe0297f05b395827453f-1");
+    }
+
+    #[test]
+    fn test_parse_and_auto_detect_etag() {
+        assert_eq!(
+            parse_s3_etag("\"B5D55EEF9B6106B14530433C1A359920-3\""),
+            Some(S3Etag { md5: "b5d55eef9b6106b14530433c1a359920".to_string(), part_count: Some(3) })
+        );
+        assert_eq!(parse_s3_etag("not-an-md5"), None);
+
+        let file = temp_file(b"abcde");
+        assert_eq!(local_file_matches_etag(file.path(), "\"ab56b4d92b40713acc5af89985d4b786\"", None).unwrap(), Some(true));
+        assert_eq!(local_file_matches_etag(file.path(), "b5d55eef9b6106b14530433c1a359920-3", Some(2)).unwrap(), Some(true));
+        assert_eq!(local_file_matches_etag(file.path(), "00000000000000000000000000000000", None).unwrap(), Some(false));
+    }
+
+    #[test]
+    fn test_estimate_chunk_sizes_from_part_count() {
+        let size = 100 * MIB + 1;
+        // 16MB×7パート、8MB×13パート
+        assert_eq!(estimate_chunk_sizes(size, 7, None)[0], 16 * MIB);
+        assert!(estimate_chunk_sizes(size, 13, None).contains(&(8 * MIB)));
+        assert!(estimate_chunk_sizes(size, 13, None).iter().all(|chunk| size.div_ceil(*chunk) == 13));
+        // 設定値を優先
+        assert_eq!(estimate_chunk_sizes(size, 11, Some(10 * MIB))[0], 10 * MIB);
+        assert_eq!(estimate_chunk_sizes(size, 1, None), vec![size]);
+    }
+}
@@ -0,0 +1,376 @@
+use std::path::{Path, PathBuf};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter, Manager, State};
+
+use crate::commands::config::{get_config_path, record_config_recovery, recover_config_file, ConfigRecovery, ConfigRecoveryAction};
+use crate::commands::metadata::metadata_db_path;
+use crate::commands::metadata_db_repair::{record_metadata_db_recovery, repair_metadata_db};
+use crate::commands::state_management::AppStateManager;
+use crate::internal::{AppError, InternalError};
+
+/// いずれかの項目に問題があったときに項目別の詳細を送るイベント
+pub const STARTUP_DIAGNOSTICS_FAILED_EVENT: &str = "startup-diagnostics-failed";
+/// 整合性確認に通ったメタデータDBの複製（破損時の復旧元）
+const METADATA_DB_BACKUP_SUFFIX: &str = "bak";
+/// 書込権限の確認に使う一時ファイル名
+const WRITE_PROBE_FILE_NAME: &str = ".reelvault-write-probe";
+/// Keychainへアクセスできるかの確認に使うエントリ
+const KEYCHAIN_SERVICE: &str = "ReelVault-AWS";
+const KEYCHAIN_PROBE_ACCOUNT: &str = "__startup_probe__";
+
+/// 起動時に検査する項目
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupCheckKind {
+    Logger,
+    Config,
+    MetadataDb,
+    AppDataDir,
+    Keychain,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupCheckStatus {
+    Ok,
+    /// 問題があったが自動で復旧した
+    Recovered,
+    Failed,
+}
+
+/// 問題に対する復旧（実施済み、または提案）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case", tag = "action")]
+pub enum StartupRecovery {
+    /// 壊れた設定ファイルを退避し、最新のバックアップからリストアした
+    ConfigRestored { backup_path: String, moved_to: Option<String> },
+    /// 使えるバックアップがなく、デフォルト設定のセーフモードで起動した
+    ConfigSafeMode { moved_to: Option<String> },
+    /// バックアップからのリストアを提案（restore_metadata_db_backupで実行）
+    RestoreMetadataDbBackup { backup_path: String, backup_modified_at: Option<String> },
+    /// 壊れたメタデータDBを自動で修復した（詳細はmetadata-db-recoveredイベント）
+    MetadataDbRepaired { corrupted_path: String, lost_records: Option<u64> },
+}
+
+/// 項目別の検査結果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StartupCheck {
+    pub kind: StartupCheckKind,
+    pub status: StartupCheckStatus,
+    pub message: String,
+    pub recovery: Option<StartupRecovery>,
+}
+
+impl StartupCheck {
+    fn ok(kind: StartupCheckKind, message: impl Into<String>) -> Self {
+        Self { kind, status: StartupCheckStatus::Ok, message: message.into(), recovery: None }
+    }
+
+    fn failed(kind: StartupCheckKind, message: impl Into<String>) -> Self {
+        Self { kind, status: StartupCheckStatus::Failed, message: message.into(), recovery: None }
+    }
+}
+
+/// 起動時セルフチェックの結果（AppStateに保持する）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StartupDiagnostics {
+    pub checks: Vec<StartupCheck>,
+    pub checked_at: String,
+}
+
+impl StartupDiagnostics {
+    /// 復旧済みも含め、問題があった項目
+    pub fn problems(&self) -> Vec<&StartupCheck> {
+        self.checks.iter().filter(|check| check.status != StartupCheckStatus::Ok).collect()
+    }
+}
+
+/// 退避先のパス（例: config.json → config.json.corrupt-20240601-120000）
+pub(crate) fn quarantine_path(path: &Path) -> PathBuf {
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".corrupt-{}", timestamp));
+    path.with_file_name(name)
+}
+
+pub(crate) fn metadata_db_backup_path(db_path: &Path) -> PathBuf {
+    let mut name = db_path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}", METADATA_DB_BACKUP_SUFFIX));
+    db_path.with_file_name(name)
+}
+
+/// ディレクトリに書き込めるか（一時ファイルを作って消す）
+pub fn check_dir_writable(kind: StartupCheckKind, dir: &Path) -> StartupCheck {
+    let probe = dir.join(WRITE_PROBE_FILE_NAME);
+    let result = std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(&probe, b"probe"))
+        .and_then(|_| std::fs::remove_file(&probe));
+    match result {
+        Ok(()) => StartupCheck::ok(kind, format!("{} is writable", dir.display())),
+        Err(e) => StartupCheck::failed(kind, format!("Cannot write to {}: {}", dir.display(), e)),
+    }
+}
+
+/// 設定ファイルを読み込めるか（壊れている場合はバックアップからのリストア、なければセーフモード）
+pub fn check_config_file(config_path: &Path) -> (StartupCheck, Option<ConfigRecovery>) {
+    const KIND: StartupCheckKind = StartupCheckKind::Config;
+    let Some(recovery) = recover_config_file(config_path) else {
+        let message = if config_path.exists() { "Config file loaded" } else { "Config file not created yet (using defaults)" };
+        return (StartupCheck::ok(KIND, message), None);
+    };
+    let moved_to = recovery.corrupted_path.clone();
+    let (message, action) = match &recovery.action {
+        ConfigRecoveryAction::RestoredFromBackup { backup_path } => (
+            format!("{} (restored from {})", recovery.error, backup_path),
+            StartupRecovery::ConfigRestored { backup_path: backup_path.clone(), moved_to },
+        ),
+        ConfigRecoveryAction::SafeMode => (
+            format!("{} (started in safe mode with default settings)", recovery.error),
+            StartupRecovery::ConfigSafeMode { moved_to },
+        ),
+    };
+    let check = StartupCheck { kind: KIND, status: StartupCheckStatus::Recovered, message, recovery: Some(action) };
+    (check, Some(recovery))
+}
+
+pub(crate) fn integrity_check(db_path: &Path) -> Result<(), String> {
+    let connection = Connection::open(db_path).map_err(|e| e.to_string())?;
+    let result: String = connection
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    if result == "ok" {
+        Ok(())
+    } else {
+        Err(result)
+    }
+}
+
+/// メタデータDBの整合性確認（正常ならバックアップを更新し、破損時はバックアップからのリストアを提案）
+pub fn check_metadata_db(db_path: &Path) -> StartupCheck {
+    const KIND: StartupCheckKind = StartupCheckKind::MetadataDb;
+    if !db_path.exists() {
+        return StartupCheck::ok(KIND, "Metadata database not created yet");
+    }
+    let backup_path = metadata_db_backup_path(db_path);
+    match integrity_check(db_path) {
+        Ok(()) => {
+            if let Err(e) = std::fs::copy(db_path, &backup_path) {
+                log::warn!("Failed to back up metadata database: {}", e);
+            }
+            StartupCheck::ok(KIND, "Metadata database integrity check passed")
+        }
+        Err(e) => {
+            log::error!("Metadata database integrity check failed: {}", e);
+            let mut check = StartupCheck::failed(KIND, format!("Metadata database is corrupted: {}", e));
+            if backup_path.exists() {
+                let backup_modified_at = std::fs::metadata(&backup_path)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+                    .map(|modified| chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339());
+                check.recovery = Some(StartupRecovery::RestoreMetadataDbBackup {
+                    backup_path: backup_path.to_string_lossy().to_string(),
+                    backup_modified_at,
+                });
+            }
+            check
+        }
+    }
+}
+
+/// 壊れたメタデータDBを退避し、バックアップで置き換える
+pub fn restore_metadata_db_from_backup(db_path: &Path) -> Result<StartupCheck, InternalError> {
+    let backup_path = metadata_db_backup_path(db_path);
+    if !backup_path.exists() {
+        return Err(InternalError::Database("No metadata database backup found".to_string()));
+    }
+    integrity_check(&backup_path)
+        .map_err(|e| InternalError::Database(format!("Metadata database backup is also corrupted: {}", e)))?;
+    if db_path.exists() {
+        let moved_to = quarantine_path(db_path);
+        std::fs::rename(db_path, &moved_to)?;
+        log::warn!("Corrupted metadata database moved to {}", moved_to.display());
+    }
+    std::fs::copy(&backup_path, db_path)?;
+    Ok(check_metadata_db(db_path))
+}
+
+/// 整合性確認で破損が見つかった場合は段階的な修復まで行う（修復できなければリストアの提案を残す）
+fn check_and_repair_metadata_db(app: &AppHandle, db_path: &Path) -> StartupCheck {
+    let check = check_metadata_db(db_path);
+    if check.status != StartupCheckStatus::Failed {
+        return check;
+    }
+    match repair_metadata_db(db_path) {
+        Ok(Some(recovery)) => {
+            record_metadata_db_recovery(app, &recovery);
+            StartupCheck {
+                kind: StartupCheckKind::MetadataDb,
+                status: StartupCheckStatus::Recovered,
+                message: format!("{} ({})", check.message, recovery.summary()),
+                recovery: Some(StartupRecovery::MetadataDbRepaired {
+                    corrupted_path: recovery.corrupted_path.clone(),
+                    lost_records: recovery.lost_records,
+                }),
+            }
+        }
+        Ok(None) => check_metadata_db(db_path),
+        Err(e) => {
+            log::error!("Failed to repair metadata database: {}", e);
+            StartupCheck { message: format!("{} (automatic repair failed: {})", check.message, e), ..check }
+        }
+    }
+}
+
+/// Keychainへアクセスできるか（存在しないエントリの読み込みが「未登録」で返れば正常）
+fn check_keychain() -> StartupCheck {
+    const KIND: StartupCheckKind = StartupCheckKind::Keychain;
+    let result = keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_PROBE_ACCOUNT)
+        .and_then(|entry| entry.get_password().map(|_| ()));
+    match result {
+        Ok(()) | Err(keyring::Error::NoEntry) => StartupCheck::ok(KIND, "Keychain is accessible"),
+        Err(e) => StartupCheck::failed(KIND, format!("Cannot access keychain: {}", e)),
+    }
+}
+
+fn check_logger(app: &AppHandle, logger_result: Result<(), String>) -> StartupCheck {
+    if let Err(e) = logger_result {
+        return StartupCheck::failed(StartupCheckKind::Logger, format!("Failed to initialize logger: {}", e));
+    }
+    match app.path().app_log_dir() {
+        Ok(log_dir) => check_dir_writable(StartupCheckKind::Logger, &log_dir),
+        Err(e) => StartupCheck::failed(StartupCheckKind::Logger, format!("Failed to get log directory: {}", e)),
+    }
+}
+
+/// 起動時のセルフチェック（ロガー→設定→メタデータDB→app_data_dir→Keychainの順に検査）
+///
+/// 結果はAppStateに保持し、問題があればstartup-diagnostics-failedイベントで通知する。
+/// イベントはフロントの購読前に送られる場合があるため、フロントはget_startup_diagnosticsでも取得する
+pub fn run_startup_diagnostics(app: &AppHandle, logger_result: Result<(), String>) -> StartupDiagnostics {
+    let mut checks = vec![check_logger(app, logger_result)];
+    checks.push(match get_config_path(app) {
+        Ok(config_path) => {
+            let (check, recovery) = check_config_file(&config_path);
+            if let Some(recovery) = &recovery {
+                record_config_recovery(app, recovery);
+            }
+            check
+        }
+        Err(e) => StartupCheck::failed(StartupCheckKind::Config, e.to_string()),
+    });
+    checks.push(match metadata_db_path(app) {
+        Ok(db_path) => check_and_repair_metadata_db(app, &db_path),
+        Err(e) => StartupCheck::failed(StartupCheckKind::MetadataDb, e.to_string()),
+    });
+    checks.push(match app.path().app_data_dir() {
+        Ok(app_data_dir) => check_dir_writable(StartupCheckKind::AppDataDir, &app_data_dir),
+        Err(e) => StartupCheck::failed(StartupCheckKind::AppDataDir, format!("Failed to get app data directory: {}", e)),
+    });
+    checks.push(check_keychain());
+
+    let diagnostics = StartupDiagnostics { checks, checked_at: chrono::Utc::now().to_rfc3339() };
+    store_startup_diagnostics(app, &diagnostics);
+
+    let problems = diagnostics.problems();
+    if problems.is_empty() {
+        log::info!("Startup diagnostics passed");
+    } else {
+        for check in &problems {
+            log::warn!("Startup diagnostics: {:?} {:?}: {}", check.kind, check.status, check.message);
+        }
+        if let Err(e) = app.emit(STARTUP_DIAGNOSTICS_FAILED_EVENT, &diagnostics) {
+            log::warn!("Failed to emit {}: {}", STARTUP_DIAGNOSTICS_FAILED_EVENT, e);
+        }
+    }
+    diagnostics
+}
+
+fn store_startup_diagnostics(app: &AppHandle, diagnostics: &StartupDiagnostics) {
+    if let Some(app_state) = app.try_state::<AppStateManager>() {
+        match app_state.lock() {
+            Ok(mut state) => state.startup_diagnostics = Some(diagnostics.clone()),
+            Err(e) => log::warn!("Failed to store startup diagnostics: {}", e),
+        }
+    }
+}
+
+/// 起動時セルフチェックの結果を取得
+#[command]
+pub async fn get_startup_diagnostics(state: State<'_, AppStateManager>) -> Result<Option<StartupDiagnostics>, AppError> {
+    let state = state.lock()
+        .map_err(|e| InternalError::Other(format!("Failed to lock app state: {}", e)))?;
+    Ok(state.startup_diagnostics.clone())
+}
+
+/// 破損したメタデータDBをバックアップからリストア（AppStateの検査結果も更新する）
+#[command]
+pub async fn restore_metadata_db_backup(app: AppHandle) -> Result<StartupCheck, AppError> {
+    let db_path = metadata_db_path(&app)?;
+    let check = restore_metadata_db_from_backup(&db_path)?;
+    log::info!("Metadata database restored from backup: {}", check.message);
+    if let Some(app_state) = app.try_state::<AppStateManager>() {
+        if let Ok(mut state) = app_state.lock() {
+            if let Some(diagnostics) = state.startup_diagnostics.as_mut() {
+                for existing in diagnostics.checks.iter_mut().filter(|existing| existing.kind == StartupCheckKind::MetadataDb) {
+                    *existing = check.clone();
+                }
+            }
+        }
+    }
+    Ok(check)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_broken_config_without_backup_starts_safe_mode() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+        assert_eq!(check_config_file(&config_path).0.status, StartupCheckStatus::Ok);
+
+        std::fs::write(&config_path, "{ not json").unwrap();
+        let (check, recovery) = check_config_file(&config_path);
+        assert_eq!(check.status, StartupCheckStatus::Recovered);
+        let Some(StartupRecovery::ConfigSafeMode { moved_to: Some(moved_to) }) = check.recovery else {
+            panic!("expected safe mode");
+        };
+        assert!(recovery.unwrap().is_safe_mode());
+        assert_eq!(std::fs::read_to_string(moved_to).unwrap(), "{ not json");
+        assert_eq!(check_config_file(&config_path).0.status, StartupCheckStatus::Ok);
+    }
+
+    #[test]
+    fn test_corrupted_db_proposes_and_restores_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("metadata.db");
+        Connection::open(&db_path).unwrap()
+            .execute_batch("CREATE TABLE t (id INTEGER); INSERT INTO t VALUES (1);")
+            .unwrap();
+        // 正常時はバックアップを作る
+        assert_eq!(check_metadata_db(&db_path).status, StartupCheckStatus::Ok);
+
+        std::fs::write(&db_path, b"this is not a sqlite database at all, just garbage bytes").unwrap();
+        let check = check_metadata_db(&db_path);
+        assert_eq!(check.status, StartupCheckStatus::Failed);
+        assert!(matches!(check.recovery, Some(StartupRecovery::RestoreMetadataDbBackup { .. })));
+
+        let restored = restore_metadata_db_from_backup(&db_path).unwrap();
+        assert_eq!(restored.status, StartupCheckStatus::Ok);
+        let count: i64 = Connection::open(&db_path).unwrap()
+            .query_row("SELECT COUNT(*) FROM t", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_unwritable_dir_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("not-a-dir");
+        std::fs::write(&file, b"").unwrap();
+        assert_eq!(check_dir_writable(StartupCheckKind::AppDataDir, dir.path()).status, StartupCheckStatus::Ok);
+        assert_eq!(check_dir_writable(StartupCheckKind::AppDataDir, &file).status, StartupCheckStatus::Failed);
+    }
+}
@@ -0,0 +1,180 @@
+use std::sync::Mutex;
+use std::time::Instant;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter, Manager};
+
+use crate::commands::autostart::{resume_after_autostart, LaunchContext};
+use crate::internal::{AppError, InternalError};
+
+/// 遅延初期化の各段階が完了したときのイベント
+pub const STARTUP_PHASE_COMPLETED_EVENT: &str = "startup-phase-completed";
+
+/// ウィンドウ表示後に実行する初期化の段階（この順に実行する）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupPhase {
+    /// 前回終了時の復元トラッカー・メタデータ作成ジョブ・統計の復元
+    RestoreState,
+    /// 保存済みライセンスの検証
+    License,
+    /// クイックアップロードのグローバルショートカットの登録
+    Shortcuts,
+    /// リテンション・アップロード前安全確認・ヘルスレポートなどの定期タスクの開始
+    Schedulers,
+}
+
+pub const STARTUP_PHASES: [StartupPhase; 4] = [
+    StartupPhase::RestoreState,
+    StartupPhase::License,
+    StartupPhase::Shortcuts,
+    StartupPhase::Schedulers,
+];
+
+/// 段階ごとの結果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StartupPhaseResult {
+    pub phase: StartupPhase,
+    pub success: bool,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+    pub completed_at: String,
+}
+
+/// 遅延初期化の進行状況
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StartupProgress {
+    /// 完了した段階（実行順）
+    pub phases: Vec<StartupPhaseResult>,
+    pub total_phases: usize,
+    pub finished: bool,
+    /// 最初に失敗した段階
+    pub failed_phase: Option<StartupPhase>,
+}
+
+impl StartupProgress {
+    pub fn from_results(phases: Vec<StartupPhaseResult>) -> Self {
+        Self {
+            finished: phases.len() >= STARTUP_PHASES.len(),
+            failed_phase: phases.iter().find(|result| !result.success).map(|result| result.phase),
+            total_phases: STARTUP_PHASES.len(),
+            phases,
+        }
+    }
+}
+
+/// startup-phase-completedイベントのペイロード
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupPhaseCompleted {
+    pub result: StartupPhaseResult,
+    pub progress: StartupProgress,
+}
+
+/// イベントはフロントの購読前に送られる場合があるため、完了した段階を保持する
+static STARTUP_RESULTS: Mutex<Vec<StartupPhaseResult>> = Mutex::new(Vec::new());
+
+fn run_phase(app: &AppHandle, phase: StartupPhase) -> Result<(), String> {
+    match phase {
+        StartupPhase::RestoreState => crate::shutdown::restore_interrupted_state(app),
+        StartupPhase::License => {
+            crate::commands::license::initialize_license(app);
+            Ok(())
+        }
+        StartupPhase::Shortcuts => crate::commands::quick_upload::initialize_quick_upload(app),
+        StartupPhase::Schedulers => {
+            crate::commands::restored_files::start_restored_files_scheduler(app.clone());
+            crate::commands::api_usage::start_api_usage_scheduler(app.clone());
+            crate::commands::transfer_cap::start_transfer_usage_scheduler(app.clone());
+            crate::commands::upload_history::start_upload_history_scheduler(app.clone());
+            crate::commands::app_data_usage::start_app_data_cleanup_scheduler(app.clone());
+            // セーフモード（設定を復旧できずデフォルト設定）ではAWSへアクセスする・原本を消す定期処理を始めない
+            if crate::commands::config::is_safe_mode() {
+                log::warn!("Safe mode: retention, restore expiry, upload readiness and health report schedulers are not started");
+                return Ok(());
+            }
+            crate::commands::retention::start_retention_scheduler(app.clone());
+            crate::commands::aws_operations::start_restore_expiry_scheduler(app.clone());
+            crate::commands::lifecycle::start_upload_readiness_scheduler(app.clone());
+            crate::commands::health_report::start_health_report_scheduler(app.clone());
+            Ok(())
+        }
+    }
+}
+
+fn record_phase(result: StartupPhaseResult) -> StartupProgress {
+    let mut results = STARTUP_RESULTS.lock().unwrap_or_else(|e| e.into_inner());
+    results.push(result);
+    StartupProgress::from_results(results.clone())
+}
+
+/// setupの後（ウィンドウ表示後）に重い初期化を段階的に実行
+///
+/// 1つの段階が失敗しても後続の段階は実行し、結果を段階ごとにイベントで通知する
+pub fn start_deferred_startup(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let started = Instant::now();
+        for phase in STARTUP_PHASES {
+            let phase_started = Instant::now();
+            let outcome = run_phase(&app, phase);
+            let result = StartupPhaseResult {
+                phase,
+                success: outcome.is_ok(),
+                error: outcome.err(),
+                duration_ms: phase_started.elapsed().as_millis() as u64,
+                completed_at: chrono::Utc::now().to_rfc3339(),
+            };
+            match &result.error {
+                None => log::info!("Startup phase {:?} completed in {}ms", phase, result.duration_ms),
+                Some(e) => log::warn!("Startup phase {:?} failed in {}ms: {}", phase, result.duration_ms, e),
+            }
+            let progress = record_phase(result.clone());
+            if let Err(e) = app.emit(STARTUP_PHASE_COMPLETED_EVENT, StartupPhaseCompleted { result, progress }) {
+                log::warn!("Failed to emit {}: {}", STARTUP_PHASE_COMPLETED_EVENT, e);
+            }
+        }
+        log::info!("Deferred startup finished in {}ms", started.elapsed().as_millis());
+
+        // ログイン時の自動起動ではウィンドウを開かないため、前回の監視とアップロードをここで再開する
+        let launched_hidden = app.try_state::<LaunchContext>().is_some_and(|context| context.launched_hidden);
+        if launched_hidden {
+            resume_after_autostart(&app).await;
+        }
+    });
+}
+
+/// 遅延初期化の進行状況を取得
+#[command]
+pub async fn get_startup_progress() -> Result<StartupProgress, AppError> {
+    let results = STARTUP_RESULTS.lock()
+        .map_err(|e| InternalError::Other(format!("Failed to lock startup progress: {}", e)))?;
+    Ok(StartupProgress::from_results(results.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(phase: StartupPhase, error: Option<&str>) -> StartupPhaseResult {
+        StartupPhaseResult {
+            phase,
+            success: error.is_none(),
+            error: error.map(String::from),
+            duration_ms: 1,
+            completed_at: "2024-06-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_progress_reports_first_failed_phase_and_completion() {
+        let progress = StartupProgress::from_results(vec![
+            result(StartupPhase::RestoreState, None),
+            result(StartupPhase::License, Some("invalid")),
+        ]);
+        assert!(!progress.finished);
+        assert_eq!(progress.failed_phase, Some(StartupPhase::License));
+
+        let progress = StartupProgress::from_results(STARTUP_PHASES.iter().map(|phase| result(*phase, None)).collect());
+        assert!(progress.finished);
+        assert_eq!(progress.failed_phase, None);
+        assert_eq!(serde_json::to_value(StartupPhase::RestoreState).unwrap(), "restore_state");
+    }
+}
@@ -2,6 +2,9 @@ use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 use tauri::{command, State};
 use crate::internal::{InternalError, standardize_error};
+use crate::commands::license::LicenseStatus;
+use crate::commands::aws_operations::LifecycleRule;
+use crate::commands::startup_diagnostics::StartupDiagnostics;
 
 /// アプリケーションのグローバル状態
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -12,6 +15,15 @@ pub struct AppState {
     pub statistics: AppStatistics,
     pub last_error: Option<String>,
     pub system_status: SystemStatus,
+    /// 自動アーカイブ（監視→アップロード→ライフサイクル移行）が有効
+    #[serde(default)]
+    pub auto_archive_enabled: bool,
+    /// 検証済みのライセンス状況（バックエンドのみが更新する）
+    #[serde(default)]
+    pub license: LicenseStatus,
+    /// 起動時セルフチェックの結果
+    #[serde(default)]
+    pub startup_diagnostics: Option<StartupDiagnostics>,
 }
 
 /// アップロードキューのアイテム
@@ -67,6 +79,41 @@ pub struct SystemStatus {
     pub cpu_usage_percent: f64,
     pub network_available: bool,
     pub last_heartbeat: String,
+    /// 転送中のためシステムスリープを抑止している
+    #[serde(default)]
+    pub sleep_prevented: bool,
+    /// 直近のアップロード前安全確認の結果
+    #[serde(default)]
+    pub upload_readiness: Option<UploadReadinessSnapshot>,
+}
+
+/// アップロード前の安全確認（check_upload_readiness）の結果キャッシュ
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UploadReadinessSnapshot {
+    pub bucket: String,
+    pub safe: bool,
+    pub lifecycle_healthy: bool,
+    pub message: String,
+    /// 判定時刻（RFC3339）
+    pub checked_at: String,
+    /// キャッシュ応答時にプレフィックス警告を再計算するためのルール（フロントには返さない）
+    #[serde(skip)]
+    pub lifecycle_rules: Vec<LifecycleRule>,
+}
+
+impl UploadReadinessSnapshot {
+    /// 判定からTTLが経過していないか
+    pub fn is_fresh(&self, now: chrono::DateTime<chrono::Utc>, ttl: chrono::Duration) -> bool {
+        chrono::DateTime::parse_from_rfc3339(&self.checked_at)
+            .map(|checked_at| now.signed_duration_since(checked_at) < ttl)
+            .unwrap_or(false)
+    }
+
+    /// 同じバケットの前回の結果から悪化したか
+    pub fn is_worse_than(&self, previous: &UploadReadinessSnapshot) -> bool {
+        self.bucket == previous.bucket
+            && ((previous.safe && !self.safe) || (previous.lifecycle_healthy && !self.lifecycle_healthy))
+    }
 }
 
 /// 状態更新リクエスト
@@ -98,7 +145,12 @@ impl Default for AppState {
                 cpu_usage_percent: 0.0,
                 network_available: false,
                 last_heartbeat: chrono::Utc::now().to_rfc3339(),
+                sleep_prevented: false,
+                upload_readiness: None,
             },
+            auto_archive_enabled: false,
+            license: LicenseStatus::default(),
+            startup_diagnostics: None,
         }
     }
 }
@@ -125,7 +177,10 @@ pub async fn set_app_state(
     let mut app_state = state.lock()
         .map_err(|e| standardize_error(InternalError::Other(format!("Failed to lock state: {}", e))))?;
     
+    // ライセンス状況はフロントから書き換えさせない
+    let license = std::mem::take(&mut app_state.license);
     *app_state = new_state;
+    app_state.license = license;
     
     log::info!("App state updated");
     Ok("Application state updated successfully".to_string())
@@ -247,12 +302,46 @@ pub async fn reset_app_state(
     let mut app_state = state.lock()
         .map_err(|e| standardize_error(InternalError::Other(format!("Failed to lock state: {}", e))))?;
     
+    let license = std::mem::take(&mut app_state.license);
     *app_state = AppState::default();
+    app_state.license = license;
     
     log::info!("App state reset to default");
     Ok("Application state reset successfully".to_string())
 }
 
+/// 終了時にAppStateを保存するファイル名
+pub(crate) const APP_STATE_FILE: &str = "app_state.json";
+
+/// AppStateをファイルへ保存（終了時のシャットダウンシーケンスから呼び出す）
+pub fn persist_app_state(path: &std::path::Path, state: &AppState) -> Result<(), InternalError> {
+    std::fs::write(path, serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+/// 前回終了時に保存したAppStateから統計と最後のエラーを引き継ぐ
+///
+/// 監視中・転送中などの実行時の状態は引き継がない。読み込んだ場合はファイルを削除してtrueを返す
+pub fn restore_persisted_app_state(path: &std::path::Path, state: &mut AppState) -> bool {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return false;
+    };
+    if let Err(e) = std::fs::remove_file(path) {
+        log::warn!("Failed to remove persisted app state: {}", e);
+    }
+    match serde_json::from_str::<AppState>(&content) {
+        Ok(saved) => {
+            state.statistics = saved.statistics;
+            state.last_error = saved.last_error;
+            true
+        }
+        Err(e) => {
+            log::warn!("Ignoring invalid persisted app state: {}", e);
+            false
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -272,6 +361,7 @@ mod tests {
         assert_eq!(state.statistics.failed_uploads, 0);
         assert_eq!(state.statistics.average_upload_speed_mbps, 0.0);
         assert_eq!(state.last_error, None);
+        assert!(!state.auto_archive_enabled);
         assert_eq!(state.system_status.aws_connected, false);
         assert_eq!(state.system_status.disk_space_gb, 0.0);
         assert_eq!(state.system_status.memory_usage_mb, 0.0);
@@ -347,6 +437,8 @@ mod tests {
             cpu_usage_percent: 25.5,
             network_available: true,
             last_heartbeat: "2024-01-01T00:00:00Z".to_string(),
+            sleep_prevented: false,
+            upload_readiness: None,
         };
         
         assert_eq!(status.aws_connected, true);
@@ -494,6 +586,8 @@ mod tests {
             cpu_usage_percent: 0.0,
             network_available: false,
             last_heartbeat: "2024-01-01T00:00:00Z".to_string(),
+            sleep_prevented: false,
+            upload_readiness: None,
         };
         
         // システム状態の更新をシミュレート
@@ -530,4 +624,47 @@ mod tests {
         assert_eq!(initial_state.last_error, None);
         assert_eq!(initial_state.system_status.aws_connected, false);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_persisted_app_state_restores_statistics_only() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(APP_STATE_FILE);
+        let mut saved = AppState::default();
+        saved.is_watching = true;
+        saved.statistics.total_files_uploaded = 42;
+        saved.last_error = Some("network down".to_string());
+        persist_app_state(&path, &saved).unwrap();
+
+        let mut state = AppState::default();
+        assert!(restore_persisted_app_state(&path, &mut state));
+        assert_eq!(state.statistics.total_files_uploaded, 42);
+        assert_eq!(state.last_error.as_deref(), Some("network down"));
+        assert!(!state.is_watching);
+
+        // 一度読み込んだら二重に復元しない
+        assert!(!path.exists());
+        assert!(!restore_persisted_app_state(&path, &mut AppState::default()));
+    }
+
+    #[test]
+    fn test_upload_readiness_snapshot_freshness_and_degradation() {
+        let now = chrono::Utc::now();
+        let healthy = UploadReadinessSnapshot {
+            bucket: "footage".to_string(),
+            safe: true,
+            lifecycle_healthy: true,
+            message: "ok".to_string(),
+            checked_at: (now - chrono::Duration::seconds(30)).to_rfc3339(),
+            lifecycle_rules: Vec::new(),
+        };
+        assert!(healthy.is_fresh(now, chrono::Duration::seconds(60)));
+        assert!(!healthy.is_fresh(now, chrono::Duration::seconds(10)));
+
+        let degraded = UploadReadinessSnapshot { safe: false, lifecycle_healthy: false, ..healthy.clone() };
+        assert!(degraded.is_worse_than(&healthy));
+        assert!(!healthy.is_worse_than(&degraded));
+        // バケットが変わった場合は悪化とみなさない
+        let other_bucket = UploadReadinessSnapshot { bucket: "other".to_string(), ..degraded.clone() };
+        assert!(!other_bucket.is_worse_than(&healthy));
+    }
+}
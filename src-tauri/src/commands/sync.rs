@@ -0,0 +1,612 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, State};
+
+use crate::commands::audit::{AuditLogger, AuditOperation, OperationAuditEntry};
+use crate::commands::aws_operations::{create_real_s3_client, AwsConfig, S3ClientTrait, S3Object};
+use crate::commands::file_operations::matches_pattern;
+use crate::commands::s3_etag::local_file_matches_etag;
+use crate::commands::upload_system::{new_upload_item, queue_lock_error, UploadQueueState, UploadStatus};
+use crate::internal::{AppError, InternalError};
+use crate::internal::unicode::{normalization_variants, to_nfc};
+
+/// 同期オプション
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncOptions {
+    /// ローカルに存在しないS3オブジェクトを削除する（デフォルトoff）
+    #[serde(default)]
+    pub delete_remote: bool,
+    /// 差分の算出のみ行い、キュー投入・削除を行わない
+    #[serde(default)]
+    pub dry_run: bool,
+    /// 対象とするファイルパターン（空なら全ファイル、例: "*.mp4"）
+    #[serde(default)]
+    pub include_patterns: Vec<String>,
+    /// 除外するファイルパターン
+    #[serde(default)]
+    pub exclude_patterns: Vec<String>,
+}
+
+/// ローカルとS3の比較結果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum FileSyncStatus {
+    /// S3に未アップロード
+    New,
+    /// サイズまたは更新日時が異なる
+    Modified,
+    /// 同期済み
+    Unchanged,
+    /// S3にのみ存在する（削除候補）
+    RemoteOnly,
+}
+
+/// ファイル単位の比較結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileComparison {
+    pub relative_path: String,
+    pub local_path: Option<String>,
+    pub s3_key: String,
+    pub local_size: Option<u64>,
+    pub remote_size: Option<u64>,
+    pub status: FileSyncStatus,
+}
+
+/// 同期結果のサマリ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncSummary {
+    pub dry_run: bool,
+    /// アップロードキューへ追加した（dry_runでは追加予定の）件数
+    pub queued_count: usize,
+    /// 同期済み・キュー投入済みのためスキップした件数
+    pub skipped_count: usize,
+    /// ローカルに存在しないS3オブジェクトの件数
+    pub delete_candidate_count: usize,
+    /// 実際に削除した件数（delete_remote有効時のみ）
+    pub deleted_count: usize,
+    pub queued_keys: Vec<String>,
+    pub delete_candidates: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// 走査したローカルファイル
+#[derive(Debug, Clone)]
+struct LocalFile {
+    relative_path: String,
+    local_path: PathBuf,
+    size: u64,
+    modified: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// プレフィックスと相対パスからS3キーを生成
+fn remote_key(s3_prefix: &str, relative_path: &str) -> String {
+    let prefix = s3_prefix.trim_matches('/');
+    if prefix.is_empty() {
+        to_nfc(relative_path)
+    } else {
+        to_nfc(&format!("{}/{}", prefix, relative_path))
+    }
+}
+
+/// パターンフィルタの判定（ファイル名で判定）
+fn passes_filters(relative_path: &str, options: &SyncOptions) -> bool {
+    let path = PathBuf::from(relative_path);
+    if options.exclude_patterns.iter().any(|pattern| matches_pattern(&path, pattern)) {
+        return false;
+    }
+    options.include_patterns.is_empty()
+        || options.include_patterns.iter().any(|pattern| matches_pattern(&path, pattern))
+}
+
+/// ディレクトリを再帰的に走査（隠しファイルは対象外）
+fn collect_local_files(root: &Path, options: &SyncOptions) -> Result<Vec<LocalFile>, InternalError> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let entries = std::fs::read_dir(&dir)
+            .map_err(|e| InternalError::File(format!("Failed to read directory {}: {}", dir.display(), e)))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_hidden = path.file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| n.starts_with('.'))
+                .unwrap_or(true);
+            if is_hidden {
+                continue;
+            }
+
+            let metadata = match entry.metadata() {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    log::warn!("Skipping {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            if metadata.is_dir() {
+                pending.push(path);
+                continue;
+            }
+
+            let relative_path = path.strip_prefix(root)
+                .map_err(|e| InternalError::File(format!("Invalid path {}: {}", path.display(), e)))?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/");
+            // macOSが返すNFDのファイル名はNFCへ揃えてから照合する
+            let relative_path = to_nfc(&relative_path);
+            if !passes_filters(&relative_path, options) {
+                continue;
+            }
+
+            files.push(LocalFile {
+                relative_path,
+                local_path: path,
+                size: metadata.len(),
+                modified: metadata.modified().ok().map(chrono::DateTime::<chrono::Utc>::from),
+            });
+        }
+    }
+
+    files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    Ok(files)
+}
+
+/// ローカルファイルとS3オブジェクトを突き合わせて比較結果を作成
+fn compare_entries(
+    local_files: &[LocalFile],
+    remote_objects: &[S3Object],
+    s3_prefix: &str,
+    options: &SyncOptions,
+) -> Vec<FileComparison> {
+    let remote_by_key: HashMap<&str, &S3Object> = remote_objects
+        .iter()
+        .map(|object| (object.key.as_str(), object))
+        .collect();
+    let mut local_keys = HashSet::new();
+    let mut comparisons = Vec::new();
+
+    for file in local_files {
+        // NFC正規化を導入する前にNFDのキーでアップロード済みのオブジェクトとも照合する
+        let remote = normalization_variants(&remote_key(s3_prefix, &file.relative_path))
+            .iter()
+            .find_map(|key| remote_by_key.get(key.as_str()).copied());
+        let s3_key = remote
+            .map(|object| object.key.clone())
+            .unwrap_or_else(|| remote_key(s3_prefix, &file.relative_path));
+        let status = match remote {
+            None => FileSyncStatus::New,
+            Some(object) if object.size != file.size => FileSyncStatus::Modified,
+            Some(object) => {
+                let remote_modified = chrono::DateTime::parse_from_rfc3339(&object.last_modified)
+                    .ok()
+                    .map(|dt| dt.with_timezone(&chrono::Utc));
+                match (file.modified, remote_modified) {
+                    (Some(local), Some(remote)) if local > remote => FileSyncStatus::Modified,
+                    _ => FileSyncStatus::Unchanged,
+                }
+            }
+        };
+
+        local_keys.insert(s3_key.clone());
+        comparisons.push(FileComparison {
+            relative_path: file.relative_path.clone(),
+            local_path: Some(file.local_path.to_string_lossy().to_string()),
+            s3_key,
+            local_size: Some(file.size),
+            remote_size: remote.map(|object| object.size),
+            status,
+        });
+    }
+
+    let prefix = s3_prefix.trim_matches('/');
+    for object in remote_objects {
+        if object.key.ends_with('/') || local_keys.contains(&object.key) {
+            continue;
+        }
+        let relative_path = if prefix.is_empty() {
+            object.key.as_str()
+        } else {
+            match object.key.strip_prefix(prefix).and_then(|rest| rest.strip_prefix('/')) {
+                Some(rest) => rest,
+                None => continue,
+            }
+        };
+        // フィルタ対象外のオブジェクトは削除候補にしない
+        if !passes_filters(relative_path, options) {
+            continue;
+        }
+        comparisons.push(FileComparison {
+            relative_path: relative_path.to_string(),
+            local_path: None,
+            s3_key: object.key.clone(),
+            local_size: None,
+            remote_size: Some(object.size),
+            status: FileSyncStatus::RemoteOnly,
+        });
+    }
+
+    comparisons
+}
+
+/// サイズが同じで更新日時だけ新しいファイルは、ETagが一致すれば内容は同じとして同期済みに戻す
+/// （保存し直しただけのファイルを再アップロードしないため）
+fn confirm_unchanged_by_etag(comparisons: &mut [FileComparison], remote_objects: &[S3Object]) {
+    let etags: HashMap<&str, &str> = remote_objects.iter()
+        .map(|object| (object.key.as_str(), object.etag.as_str()))
+        .collect();
+    for comparison in comparisons.iter_mut() {
+        if comparison.status != FileSyncStatus::Modified || comparison.local_size != comparison.remote_size {
+            continue;
+        }
+        let (Some(local_path), Some(etag)) = (&comparison.local_path, etags.get(comparison.s3_key.as_str())) else {
+            continue;
+        };
+        match local_file_matches_etag(Path::new(local_path), etag, None) {
+            Ok(Some(true)) => comparison.status = FileSyncStatus::Unchanged,
+            Ok(_) => {}
+            Err(e) => log::warn!("Failed to calculate ETag of {}: {}", local_path, e),
+        }
+    }
+}
+
+/// S3ClientTraitを使ってローカルディレクトリとS3プレフィックスを比較
+async fn compare_with_client(
+    s3_client: &dyn S3ClientTrait,
+    bucket: &str,
+    local_dir: &Path,
+    s3_prefix: &str,
+    options: &SyncOptions,
+) -> Result<Vec<FileComparison>, InternalError> {
+    if !local_dir.is_dir() {
+        return Err(InternalError::File(format!("Directory not found: {}", local_dir.display())));
+    }
+
+    let local_files = collect_local_files(local_dir, options)?;
+    let prefix = s3_prefix.trim_matches('/');
+    let list_prefix = if prefix.is_empty() { None } else { Some(format!("{}/", prefix)) };
+    let remote_objects = s3_client
+        .list_objects(bucket, list_prefix.as_deref())
+        .await
+        .map_err(InternalError::s3)?;
+
+    let mut comparisons = compare_entries(&local_files, &remote_objects, s3_prefix, options);
+    confirm_unchanged_by_etag(&mut comparisons, &remote_objects);
+    Ok(comparisons)
+}
+
+/// 比較結果に基づいてキュー投入・削除を実行（dry_runでは何も変更しない）
+async fn run_sync(
+    s3_client: &dyn S3ClientTrait,
+    bucket: &str,
+    local_dir: &Path,
+    s3_prefix: &str,
+    options: &SyncOptions,
+    queue_state: &UploadQueueState,
+    audit: Option<&AuditLogger>,
+) -> Result<SyncSummary, InternalError> {
+    let comparisons = compare_with_client(s3_client, bucket, local_dir, s3_prefix, options).await?;
+
+    let mut summary = SyncSummary {
+        dry_run: options.dry_run,
+        queued_count: 0,
+        skipped_count: 0,
+        delete_candidate_count: 0,
+        deleted_count: 0,
+        queued_keys: Vec::new(),
+        delete_candidates: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    let mut uploads = Vec::new();
+    for comparison in &comparisons {
+        match comparison.status {
+            FileSyncStatus::New | FileSyncStatus::Modified => uploads.push(comparison),
+            FileSyncStatus::Unchanged => summary.skipped_count += 1,
+            FileSyncStatus::RemoteOnly => summary.delete_candidates.push(comparison.s3_key.clone()),
+        }
+    }
+    summary.delete_candidate_count = summary.delete_candidates.len();
+
+    if options.dry_run {
+        summary.queued_count = uploads.len();
+        summary.queued_keys = uploads.iter().map(|c| c.s3_key.clone()).collect();
+        log::info!(
+            "Sync dry run for {}: {} to upload, {} unchanged, {} delete candidate(s)",
+            local_dir.display(), summary.queued_count, summary.skipped_count, summary.delete_candidate_count
+        );
+        return Ok(summary);
+    }
+
+    {
+        let mut queue = queue_state.lock()
+            .map_err(queue_lock_error)?;
+        let config = queue.config.as_ref()
+            .ok_or_else(|| InternalError::Config("Upload configuration not initialized".to_string()))?;
+        if config.bucket_name != bucket {
+            return Err(InternalError::Config(format!(
+                "Sync bucket {} does not match upload queue bucket {}",
+                bucket, config.bucket_name
+            )));
+        }
+
+        // 既にキューにある（未完了の）キーは重複投入しない
+        let queued_keys: HashSet<String> = queue.items
+            .iter()
+            .filter(|item| !item.status.is_primary_completed())
+            .map(|item| item.s3_key.clone())
+            .collect();
+        let (already_queued, uploads): (Vec<_>, Vec<_>) = uploads
+            .into_iter()
+            .partition(|c| queued_keys.contains(&c.s3_key));
+        summary.skipped_count += already_queued.len();
+
+        queue.check_tier_limits(uploads.len())?;
+
+        for comparison in uploads {
+            let Some(local_path) = &comparison.local_path else {
+                continue;
+            };
+            match new_upload_item(local_path, comparison.s3_key.clone()) {
+                Ok(item) => {
+                    queue.items.push(item);
+                    summary.queued_keys.push(comparison.s3_key.clone());
+                }
+                Err(e) => summary.errors.push(format!("{}: {}", comparison.relative_path, e)),
+            }
+        }
+        summary.queued_count = summary.queued_keys.len();
+    }
+
+    if options.delete_remote {
+        for key in &summary.delete_candidates {
+            let started_at = std::time::Instant::now();
+            let result = s3_client.delete_object(bucket, key).await;
+            if let Some(audit) = audit {
+                audit.record(
+                    &OperationAuditEntry::new(AuditOperation::S3Delete, &result)
+                        .s3_object(bucket, key.as_str())
+                        .duration(started_at.elapsed()),
+                );
+            }
+            match result {
+                Ok(()) => summary.deleted_count += 1,
+                Err(e) => summary.errors.push(format!("Failed to delete {}: {}", key, e)),
+            }
+        }
+    }
+
+    log::info!(
+        "Sync completed for {}: {} queued, {} skipped, {} delete candidate(s), {} deleted",
+        local_dir.display(), summary.queued_count, summary.skipped_count,
+        summary.delete_candidate_count, summary.deleted_count
+    );
+    Ok(summary)
+}
+
+/// ローカルディレクトリとS3プレフィックスの差分を取得
+#[command]
+pub async fn compare_local_with_s3(
+    app: AppHandle,
+    local_dir: String,
+    config: AwsConfig,
+    s3_prefix: String,
+) -> Result<Vec<FileComparison>, AppError> {
+    let s3_client = create_real_s3_client(&app, &config).await?;
+    let comparisons = compare_with_client(
+        s3_client.as_ref(),
+        &config.bucket_name,
+        Path::new(&local_dir),
+        &s3_prefix,
+        &SyncOptions::default(),
+    ).await?;
+    Ok(comparisons)
+}
+
+/// ローカルディレクトリをS3プレフィックスへ同期（未アップロード・変更ありのファイルをキューへ投入）
+#[command]
+pub async fn sync_directory_to_s3(
+    app_handle: AppHandle,
+    local_dir: String,
+    config: AwsConfig,
+    s3_prefix: String,
+    options: SyncOptions,
+    queue_state: State<'_, UploadQueueState>,
+) -> Result<SyncSummary, AppError> {
+    let s3_client = create_real_s3_client(&app_handle, &config).await?;
+    let summary = run_sync(
+        s3_client.as_ref(),
+        &config.bucket_name,
+        Path::new(&local_dir),
+        &s3_prefix,
+        &options,
+        queue_state.inner(),
+        AuditLogger::for_app(&app_handle).as_ref(),
+    ).await?;
+
+    if !summary.dry_run && summary.queued_count > 0 {
+        crate::badge::update_badge(&app_handle, queue_state.inner());
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use crate::commands::aws_operations::MockS3Client;
+    use crate::commands::upload_system::{UploadConfig, UploadQueue};
+
+    fn create_queue_state(bucket: &str) -> UploadQueueState {
+        let mut queue = UploadQueue::new();
+        queue.config = Some(UploadConfig::new(bucket.to_string()));
+        Arc::new(Mutex::new(queue))
+    }
+
+    /// MockS3Clientは"mock/file.txt"（123バイト）を返す
+    fn create_sync_dir() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("file.txt"), b"changed").unwrap();
+        std::fs::create_dir(dir.path().join("clips")).unwrap();
+        std::fs::write(dir.path().join("clips").join("a.mp4"), b"video").unwrap();
+        std::fs::write(dir.path().join(".DS_Store"), b"").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_remote_key() {
+        assert_eq!(remote_key("", "a/b.mp4"), "a/b.mp4");
+        assert_eq!(remote_key("projects/", "a/b.mp4"), "projects/a/b.mp4");
+        assert_eq!(remote_key("/projects", "b.mp4"), "projects/b.mp4");
+    }
+
+    #[test]
+    fn test_compare_entries() {
+        let modified = chrono::DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let local = |path: &str, size: u64| LocalFile {
+            relative_path: path.to_string(),
+            local_path: PathBuf::from("/tmp").join(path),
+            size,
+            modified: Some(modified),
+        };
+        let remote = |key: &str, size: u64| S3Object {
+            key: key.to_string(),
+            size,
+            last_modified: "2024-02-01T00:00:00Z".to_string(),
+            storage_class: "STANDARD".to_string(),
+            etag: String::new(),
+        };
+
+        let comparisons = compare_entries(
+            &[local("new.mp4", 10), local("same.mp4", 20), local("changed.mp4", 30)],
+            &[
+                remote("p/same.mp4", 20),
+                remote("p/changed.mp4", 31),
+                remote("p/gone.mp4", 40),
+                remote("p/gone.txt", 1),
+            ],
+            "p",
+            &SyncOptions {
+                exclude_patterns: vec!["*.txt".to_string()],
+                ..Default::default()
+            },
+        );
+
+        let status_of = |path: &str| comparisons.iter().find(|c| c.relative_path == path).map(|c| c.status.clone());
+        assert_eq!(status_of("new.mp4"), Some(FileSyncStatus::New));
+        assert_eq!(status_of("same.mp4"), Some(FileSyncStatus::Unchanged));
+        assert_eq!(status_of("changed.mp4"), Some(FileSyncStatus::Modified));
+        assert_eq!(status_of("gone.mp4"), Some(FileSyncStatus::RemoteOnly));
+        assert_eq!(status_of("gone.txt"), None);
+    }
+
+    #[test]
+    fn test_compare_entries_matches_japanese_names_across_normalization_forms() {
+        let local = |path: &str| LocalFile {
+            relative_path: path.to_string(),
+            local_path: PathBuf::from("/tmp").join(path),
+            size: 10,
+            modified: None,
+        };
+        let remote = |key: &str| S3Object {
+            key: key.to_string(),
+            size: 10,
+            last_modified: "2024-02-01T00:00:00Z".to_string(),
+            storage_class: "DEEP_ARCHIVE".to_string(),
+            etag: String::new(),
+        };
+        // NFC導入前にNFDでアップロードされた「ポートフォリオ.mp4」
+        let legacy_nfd_key = "p/\u{30DB}\u{309A}\u{30FC}\u{30C8}\u{30D5}\u{30A9}\u{30EA}\u{30AA}.mp4";
+
+        let comparisons = compare_entries(
+            &[local("ポートフォリオ.mp4"), local("撮影データ.mov")],
+            &[remote(legacy_nfd_key), remote("p/撮影データ.mov")],
+            "p",
+            &SyncOptions::default(),
+        );
+
+        assert_eq!(comparisons.len(), 2);
+        assert!(comparisons.iter().all(|c| c.status == FileSyncStatus::Unchanged));
+        let portfolio = comparisons.iter().find(|c| c.relative_path == "ポートフォリオ.mp4").unwrap();
+        // 既存オブジェクトのキーを維持し、削除候補にもしない
+        assert_eq!(portfolio.s3_key, legacy_nfd_key);
+    }
+
+    #[test]
+    fn test_touched_file_with_matching_etag_is_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("same.mov"), b"abcde").unwrap();
+        std::fs::write(dir.path().join("edited.mov"), b"abcdf").unwrap();
+        let local_files = collect_local_files(dir.path(), &SyncOptions::default()).unwrap();
+        // アップロード後に保存し直した（ローカルの更新日時のほうが新しい）
+        let remote = |key: &str| S3Object {
+            key: key.to_string(),
+            size: 5,
+            last_modified: "2024-02-01T00:00:00Z".to_string(),
+            storage_class: "STANDARD".to_string(),
+            etag: "\"ab56b4d92b40713acc5af89985d4b786\"".to_string(),
+        };
+        let remote_objects = vec![remote("same.mov"), remote("edited.mov")];
+        let mut comparisons = compare_entries(&local_files, &remote_objects, "", &SyncOptions::default());
+        assert!(comparisons.iter().all(|c| c.status == FileSyncStatus::Modified));
+
+        confirm_unchanged_by_etag(&mut comparisons, &remote_objects);
+        let status_of = |path: &str| comparisons.iter().find(|c| c.relative_path == path).map(|c| c.status.clone());
+        assert_eq!(status_of("same.mov"), Some(FileSyncStatus::Unchanged));
+        assert_eq!(status_of("edited.mov"), Some(FileSyncStatus::Modified));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_makes_no_changes() {
+        let dir = create_sync_dir();
+        // ローカルのfile.txtを消し、S3側のmock/file.txtを削除候補にする
+        std::fs::remove_file(dir.path().join("file.txt")).unwrap();
+        let queue_state = create_queue_state("bucket");
+        let options = SyncOptions {
+            delete_remote: true,
+            dry_run: true,
+            ..Default::default()
+        };
+        let client = MockS3Client::new();
+
+        let summary = run_sync(&client, "bucket", dir.path(), "mock", &options, &queue_state, None)
+            .await
+            .unwrap();
+
+        // clips/a.mp4は未アップロード、mock/file.txtはローカルに存在しない
+        assert!(summary.dry_run);
+        assert_eq!(summary.queued_count, 1);
+        assert_eq!(summary.delete_candidates, vec!["mock/file.txt".to_string()]);
+        assert_eq!(summary.deleted_count, 0);
+        assert!(client.calls().deleted_objects.is_empty());
+        assert!(queue_state.lock().unwrap().items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sync_queues_changed_files() {
+        let dir = create_sync_dir();
+        let queue_state = create_queue_state("bucket");
+        let options = SyncOptions {
+            include_patterns: vec!["*.mp4".to_string()],
+            ..Default::default()
+        };
+
+        let summary = run_sync(&MockS3Client::new(), "bucket", dir.path(), "mock", &options, &queue_state, None)
+            .await
+            .unwrap();
+
+        assert_eq!(summary.queued_keys, vec!["mock/clips/a.mp4".to_string()]);
+        assert_eq!(summary.delete_candidate_count, 0);
+        assert_eq!(queue_state.lock().unwrap().items.len(), 1);
+
+        // 再実行しても同じファイルを重複投入しない
+        let summary = run_sync(&MockS3Client::new(), "bucket", dir.path(), "mock", &options, &queue_state, None)
+            .await
+            .unwrap();
+        assert_eq!(summary.queued_count, 0);
+        assert_eq!(summary.skipped_count, 1);
+    }
+}
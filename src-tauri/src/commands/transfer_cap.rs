@@ -0,0 +1,464 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Mutex, OnceLock};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter};
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::commands::aws_operations::S3ObjectStream;
+use crate::commands::config::load_config;
+use crate::commands::metadata::{metadata_db_path, MetadataDatabase};
+use crate::commands::mock_aws::is_mock_aws_mode;
+use crate::internal::{AppError, ErrorCode, InternalError};
+use crate::internal::i18n::{LocalizedMessage, MessageKey};
+
+/// 転送量が上限の80%・100%に達したときのイベント
+pub const TRANSFER_CAP_WARNING_EVENT: &str = "transfer-cap-warning";
+/// 警告を出す使用率
+pub const TRANSFER_CAP_WARNING_RATIO: f64 = 0.8;
+/// 月次上限の単位（回線の契約に合わせて10進のGB）
+const BYTES_PER_GB: f64 = 1_000_000_000.0;
+/// 転送量をSQLiteへ書き出し、トレイの表示を更新する間隔
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+/// 月の保存形式
+const MONTH_FORMAT: &str = "%Y-%m";
+
+/// 転送の方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferDirection {
+    Upload,
+    Download,
+}
+
+impl TransferDirection {
+    /// DBに保存する文字列表現
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferDirection::Upload => "upload",
+            TransferDirection::Download => "download",
+        }
+    }
+}
+
+/// 上限に達したときの挙動
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferCapAction {
+    /// 新しい転送を始めない（進行中の転送は完了させる）
+    #[default]
+    Stop,
+    /// 警告のみで転送は続ける
+    WarnOnly,
+}
+
+/// 上限に対する段階
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferCapLevel {
+    /// 上限の80%以上
+    Warning,
+    /// 上限以上
+    Reached,
+}
+
+/// 今月の転送量（get_transfer_usageの結果）
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TransferUsage {
+    /// 集計対象の月（"YYYY-MM"）
+    pub month: String,
+    pub uploaded_bytes: u64,
+    pub downloaded_bytes: u64,
+    pub total_bytes: u64,
+    /// 月次上限（未設定はNone）
+    pub cap_bytes: Option<u64>,
+    pub remaining_bytes: Option<u64>,
+    /// 上限に対する使用率（1.0で上限）
+    pub used_ratio: Option<f64>,
+    pub action: TransferCapAction,
+    /// 今月分の上限を手動で解除した
+    pub overridden: bool,
+    /// 上限に達したため新しい転送を止めている
+    pub blocked: bool,
+}
+
+/// transfer-cap-warningイベントのペイロード
+#[derive(Debug, Clone, Serialize)]
+pub struct TransferCapAlert {
+    pub level: TransferCapLevel,
+    pub usage: TransferUsage,
+}
+
+#[derive(Debug, Default)]
+struct TransferCapState {
+    cap_bytes: Option<u64>,
+    action: TransferCapAction,
+    month: String,
+    uploaded: u64,
+    downloaded: u64,
+    overridden: bool,
+    /// 今月通知済みの段階（段階が上がったときだけ通知する）
+    notified: Option<TransferCapLevel>,
+    /// SQLiteへ未書き出しの月別・方向別の転送量
+    pending: HashMap<(String, TransferDirection), u64>,
+}
+
+impl TransferCapState {
+    /// 月が変わっていれば今月の集計をやり直す
+    fn roll_month(&mut self, month: &str) {
+        if self.month != month {
+            self.month = month.to_string();
+            self.uploaded = 0;
+            self.downloaded = 0;
+            self.overridden = false;
+            self.notified = None;
+        }
+    }
+
+    fn record(&mut self, direction: TransferDirection, bytes: u64, month: &str) -> Option<TransferCapAlert> {
+        self.roll_month(month);
+        match direction {
+            TransferDirection::Upload => self.uploaded += bytes,
+            TransferDirection::Download => self.downloaded += bytes,
+        }
+        *self.pending.entry((month.to_string(), direction)).or_insert(0) += bytes;
+        self.take_alert()
+    }
+
+    fn total(&self) -> u64 {
+        self.uploaded + self.downloaded
+    }
+
+    fn level(&self) -> Option<TransferCapLevel> {
+        let cap = self.cap_bytes.filter(|cap| *cap > 0)?;
+        let ratio = self.total() as f64 / cap as f64;
+        if ratio >= 1.0 {
+            Some(TransferCapLevel::Reached)
+        } else if ratio >= TRANSFER_CAP_WARNING_RATIO {
+            Some(TransferCapLevel::Warning)
+        } else {
+            None
+        }
+    }
+
+    /// 通知済みより上の段階に達していれば通知内容を返す
+    fn take_alert(&mut self) -> Option<TransferCapAlert> {
+        let level = self.level()?;
+        if self.overridden || self.notified.is_some_and(|notified| notified >= level) {
+            return None;
+        }
+        self.notified = Some(level);
+        Some(TransferCapAlert { level, usage: self.usage() })
+    }
+
+    fn blocked(&self) -> bool {
+        self.action == TransferCapAction::Stop && !self.overridden && self.level() == Some(TransferCapLevel::Reached)
+    }
+
+    fn usage(&self) -> TransferUsage {
+        let total_bytes = self.total();
+        let cap_bytes = self.cap_bytes.filter(|cap| *cap > 0);
+        TransferUsage {
+            month: self.month.clone(),
+            uploaded_bytes: self.uploaded,
+            downloaded_bytes: self.downloaded,
+            total_bytes,
+            cap_bytes,
+            remaining_bytes: cap_bytes.map(|cap| cap.saturating_sub(total_bytes)),
+            used_ratio: cap_bytes.map(|cap| total_bytes as f64 / cap as f64),
+            action: self.action,
+            overridden: self.overridden,
+            blocked: self.blocked(),
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref TRANSFER_CAP: Mutex<TransferCapState> = Mutex::new(TransferCapState::default());
+}
+
+/// transfer-cap-warningイベントの送信に使う（setup時に設定）
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+fn state() -> std::sync::MutexGuard<'static, TransferCapState> {
+    TRANSFER_CAP.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn current_month() -> String {
+    Local::now().format(MONTH_FORMAT).to_string()
+}
+
+/// 月次上限（GB）をバイト数へ変換
+pub fn cap_gb_to_bytes(cap_gb: Option<f64>) -> Option<u64> {
+    cap_gb.filter(|gb| gb.is_finite() && *gb > 0.0).map(|gb| (gb * BYTES_PER_GB) as u64)
+}
+
+fn format_gb(bytes: u64) -> String {
+    format!("{:.1} GB", bytes as f64 / BYTES_PER_GB)
+}
+
+/// トレイに表示する今月の転送量
+pub fn format_transfer_usage(usage: &TransferUsage) -> String {
+    match (usage.cap_bytes, usage.remaining_bytes) {
+        (Some(cap), Some(remaining)) => format!(
+            "今月の転送量: {} / {}（残り{}）",
+            format_gb(usage.total_bytes),
+            format_gb(cap),
+            format_gb(remaining),
+        ),
+        _ => format!("今月の転送量: {}", format_gb(usage.total_bytes)),
+    }
+}
+
+fn notify(alert: &TransferCapAlert) {
+    log::warn!(
+        "Monthly transfer usage {:?}: {} of {:?} bytes (action: {:?})",
+        alert.level, alert.usage.total_bytes, alert.usage.cap_bytes, alert.usage.action,
+    );
+    if let Some(app) = APP_HANDLE.get() {
+        if let Err(e) = app.emit(TRANSFER_CAP_WARNING_EVENT, alert) {
+            log::warn!("Failed to emit {}: {}", TRANSFER_CAP_WARNING_EVENT, e);
+        }
+        crate::tray::update_transfer_usage(app, &format_transfer_usage(&alert.usage));
+    }
+}
+
+/// 転送したバイト数を記録（モックAWSモードでは実際に転送しないため記録しない）
+pub fn record_transfer(direction: TransferDirection, bytes: u64) {
+    if bytes == 0 || is_mock_aws_mode() {
+        return;
+    }
+    let alert = state().record(direction, bytes, &current_month());
+    if let Some(alert) = alert {
+        notify(&alert);
+    }
+}
+
+/// 月次上限に達して新しい転送を止めているか
+pub fn is_transfer_blocked() -> bool {
+    let mut state = state();
+    state.roll_month(&current_month());
+    state.blocked()
+}
+
+/// 新しい転送を始めてよいか（上限到達で停止中ならエラー）
+pub fn ensure_transfer_allowed() -> Result<(), InternalError> {
+    let usage = {
+        let mut state = state();
+        state.roll_month(&current_month());
+        if !state.blocked() {
+            return Ok(());
+        }
+        state.usage()
+    };
+    Err(InternalError::localized(
+        ErrorCode::Unknown,
+        LocalizedMessage::new(MessageKey::TransferCapReached).param("cap", format_gb(usage.cap_bytes.unwrap_or(0))),
+    ))
+}
+
+/// 読み出したバイト数をダウンロードの転送量として記録するリーダー
+struct CountingReader {
+    inner: Pin<Box<dyn AsyncRead + Send>>,
+}
+
+impl AsyncRead for CountingReader {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let before = buf.filled().len();
+        let result = self.inner.as_mut().poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = &result {
+            record_transfer(TransferDirection::Download, (buf.filled().len() - before) as u64);
+        }
+        result
+    }
+}
+
+/// 本体を読み出した分だけダウンロードの転送量として数えるストリームにする
+pub fn count_download(stream: S3ObjectStream) -> S3ObjectStream {
+    S3ObjectStream::new(stream.content_length, CountingReader { inner: stream.body })
+}
+
+fn open_metadata_db(app: &AppHandle) -> Result<MetadataDatabase, InternalError> {
+    let db_path = metadata_db_path(app)?;
+    MetadataDatabase::new(&db_path.to_string_lossy())
+        .map_err(|e| InternalError::Database(format!("Failed to create database connection: {}", e)))
+}
+
+/// 未書き出しの転送量をSQLiteへ加算（失敗した場合は次回に持ち越す）
+pub fn flush_transfer_usage(db: &MetadataDatabase) -> Result<usize, InternalError> {
+    let pending = std::mem::take(&mut state().pending);
+    let mut failed = HashMap::new();
+    let mut result = Ok(pending.len());
+    for ((month, direction), bytes) in pending {
+        if let Err(e) = db.add_transfer_usage(&month, direction.as_str(), bytes) {
+            result = Err(InternalError::Database(format!("Failed to persist transfer usage: {}", e)));
+            failed.insert((month, direction), bytes);
+        }
+    }
+    if !failed.is_empty() {
+        let mut state = state();
+        for (key, bytes) in failed {
+            *state.pending.entry(key).or_insert(0) += bytes;
+        }
+    }
+    result
+}
+
+/// 未書き出しの転送量をアプリのメタデータDBへ書き出す（終了時用）
+pub fn persist_transfer_usage(app: &AppHandle) -> Result<usize, InternalError> {
+    flush_transfer_usage(&open_metadata_db(app)?)
+}
+
+/// 設定ファイルの月次上限・上限到達時の挙動を反映
+pub fn refresh_transfer_cap_settings(app: &AppHandle) {
+    let config = match load_config(app) {
+        Ok(config) => config,
+        Err(e) => {
+            log::warn!("Failed to load transfer cap settings: {}", e);
+            return;
+        }
+    };
+    let alert = {
+        let mut state = state();
+        state.cap_bytes = cap_gb_to_bytes(config.monthly_transfer_cap_gb);
+        state.action = config.transfer_cap_action;
+        // 上限を変えたら新しい上限に対して通知し直す
+        state.notified = None;
+        state.take_alert()
+    };
+    if let Some(alert) = alert {
+        notify(&alert);
+    }
+}
+
+/// 今月の転送量と解除状態をDBから読み込み、設定を反映する（setup時に呼ぶ）
+pub fn initialize_transfer_cap(app: &AppHandle) {
+    let _ = APP_HANDLE.set(app.clone());
+    let month = current_month();
+    let loaded = open_metadata_db(app).and_then(|db| {
+        let usage = db.list_transfer_usage(&month)?;
+        let overridden = db.is_transfer_cap_overridden(&month)?;
+        Ok((usage, overridden))
+    });
+    match loaded {
+        Ok((usage, overridden)) => {
+            let mut state = state();
+            state.roll_month(&month);
+            for (direction, bytes) in usage {
+                match direction.as_str() {
+                    "upload" => state.uploaded += bytes,
+                    "download" => state.downloaded += bytes,
+                    _ => {}
+                }
+            }
+            state.overridden = overridden;
+        }
+        Err(e) => log::warn!("Failed to load transfer usage: {}", e),
+    }
+    refresh_transfer_cap_settings(app);
+}
+
+/// 定期的に転送量をSQLiteへ書き出し、トレイの今月の転送量を更新
+pub fn start_transfer_usage_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            match persist_transfer_usage(&app) {
+                Ok(_) => {
+                    let usage = {
+                        let mut state = state();
+                        state.roll_month(&current_month());
+                        state.usage()
+                    };
+                    crate::tray::update_transfer_usage(&app, &format_transfer_usage(&usage));
+                }
+                Err(e) => log::warn!("Failed to persist transfer usage: {}", e),
+            }
+            tokio::time::sleep(FLUSH_INTERVAL).await;
+        }
+    });
+}
+
+/// 今月の転送量と上限までの残量を取得
+#[command]
+pub async fn get_transfer_usage(app: AppHandle) -> Result<TransferUsage, AppError> {
+    if let Err(e) = persist_transfer_usage(&app) {
+        log::warn!("Failed to persist transfer usage: {}", e);
+    }
+    let mut state = state();
+    state.roll_month(&current_month());
+    Ok(state.usage())
+}
+
+/// 今月分の上限を解除し、止めていた転送を再開できるようにする（翌月は再び上限を適用する）
+#[command]
+pub async fn override_transfer_cap(app: AppHandle) -> Result<TransferUsage, AppError> {
+    let month = current_month();
+    open_metadata_db(&app)?
+        .set_transfer_cap_override(&month, &chrono::Utc::now().to_rfc3339())
+        .map_err(|e| InternalError::Database(format!("Failed to save transfer cap override: {}", e)))?;
+    let usage = {
+        let mut state = state();
+        state.roll_month(&month);
+        state.overridden = true;
+        state.usage()
+    };
+    log::info!("Monthly transfer cap overridden for {}", month);
+    crate::tray::update_transfer_usage(&app, &format_transfer_usage(&usage));
+    Ok(usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1_000_000_000;
+
+    fn capped_state(cap_gb: f64, action: TransferCapAction) -> TransferCapState {
+        TransferCapState { cap_bytes: cap_gb_to_bytes(Some(cap_gb)), action, ..TransferCapState::default() }
+    }
+
+    #[test]
+    fn test_alerts_at_80_and_100_percent_then_blocks_until_override() {
+        let mut state = capped_state(10.0, TransferCapAction::Stop);
+        assert!(state.record(TransferDirection::Upload, 7 * GB, "2024-05").is_none());
+        let warning = state.record(TransferDirection::Download, GB, "2024-05").unwrap();
+        assert_eq!(warning.level, TransferCapLevel::Warning);
+        assert_eq!(warning.usage.remaining_bytes, Some(2 * GB));
+        // 同じ段階は繰り返し通知しない
+        assert!(state.record(TransferDirection::Upload, GB / 2, "2024-05").is_none());
+        assert!(!state.blocked());
+
+        let reached = state.record(TransferDirection::Upload, 2 * GB, "2024-05").unwrap();
+        assert_eq!(reached.level, TransferCapLevel::Reached);
+        assert!(reached.usage.blocked);
+        assert_eq!(reached.usage.remaining_bytes, Some(0));
+
+        state.overridden = true;
+        assert!(!state.blocked());
+        assert!(state.record(TransferDirection::Upload, GB, "2024-05").is_none());
+
+        // 翌月は集計も解除もやり直す
+        assert!(state.record(TransferDirection::Upload, GB, "2024-06").is_none());
+        assert_eq!(state.usage().total_bytes, GB);
+        assert!(!state.usage().overridden);
+        assert_eq!(state.pending.get(&("2024-05".to_string(), TransferDirection::Upload)), Some(&(10 * GB + GB / 2)));
+    }
+
+    #[test]
+    fn test_warn_only_and_uncapped_never_block() {
+        let mut state = capped_state(1.0, TransferCapAction::WarnOnly);
+        let alert = state.record(TransferDirection::Upload, 2 * GB, "2024-05").unwrap();
+        assert_eq!(alert.level, TransferCapLevel::Reached);
+        assert!(!state.blocked());
+
+        let mut uncapped = TransferCapState::default();
+        assert!(uncapped.record(TransferDirection::Download, 100 * GB, "2024-05").is_none());
+        assert!(!uncapped.blocked());
+        assert_eq!(uncapped.usage().remaining_bytes, None);
+        assert_eq!(cap_gb_to_bytes(Some(0.0)), None);
+        assert_eq!(format_transfer_usage(&capped_state(50.0, TransferCapAction::Stop).usage()), "今月の転送量: 0.0 GB / 50.0 GB（残り50.0 GB）");
+    }
+}
@@ -0,0 +1,151 @@
+use std::time::Instant;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::commands::aws_operations::S3ClientTrait;
+use crate::commands::metrics::METRICS;
+
+/// S3に保存されたチェックサムがローカルで計算した値と一致しないときのエラー
+pub const CHECKSUM_MISMATCH_ERROR: &str = "uploaded object checksum mismatch";
+
+/// アップロード時にS3へ送るチェックサムのアルゴリズム
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksumAlgorithm {
+    /// 付与しない（S3のMD5 ETagのみ）
+    #[default]
+    None,
+    /// CPUのCRC命令（SSE4.2・ARMv8）で計算するため大容量でも負荷が小さい
+    Crc32c,
+    Sha256,
+}
+
+impl ChecksumAlgorithm {
+    pub fn is_enabled(self) -> bool {
+        self != Self::None
+    }
+
+    /// S3のChecksumAlgorithmの表記
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "NONE",
+            Self::Crc32c => "CRC32C",
+            Self::Sha256 => "SHA256",
+        }
+    }
+
+    /// S3と同じバイト列のダイジェスト（CRC32Cはビッグエンディアン）
+    fn digest(self, data: &[u8]) -> Option<Vec<u8>> {
+        match self {
+            Self::None => None,
+            Self::Crc32c => Some(crc32c::crc32c(data).to_be_bytes().to_vec()),
+            Self::Sha256 => Some(Sha256::digest(data).to_vec()),
+        }
+    }
+}
+
+/// S3へ送るチェックサム（x-amz-checksum-*と同じBase64）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadChecksum {
+    pub algorithm: ChecksumAlgorithm,
+    pub value: String,
+}
+
+/// upload_part_with_checksumで送信するパート
+#[derive(Debug, Clone)]
+pub struct PartUpload {
+    pub part_number: i32,
+    pub data: Vec<u8>,
+    /// S3が受信データの検証に使う（Noneは付与しない）
+    pub checksum: Option<UploadChecksum>,
+}
+
+/// データのチェックサム（計測しない。Noneは計算しない）
+pub fn checksum_of(algorithm: ChecksumAlgorithm, data: &[u8]) -> Option<UploadChecksum> {
+    algorithm.digest(data).map(|digest| UploadChecksum { algorithm, value: STANDARD.encode(digest) })
+}
+
+/// 送信データのチェックサムを計算し、アルゴリズムごとの計算時間と処理量をメトリクスに記録する
+pub fn compute_checksum(algorithm: ChecksumAlgorithm, data: &[u8]) -> Option<UploadChecksum> {
+    let started = Instant::now();
+    let checksum = checksum_of(algorithm, data)?;
+    METRICS.record_checksum(algorithm, data.len() as u64, started.elapsed());
+    Some(checksum)
+}
+
+/// マルチパートのオブジェクト全体のチェックサム
+///
+/// S3のCOMPOSITE形式（各パートのダイジェストを連結したもののチェックサムに"-パート数"を付ける）
+pub fn composite_checksum(algorithm: ChecksumAlgorithm, parts: &[UploadChecksum]) -> Result<String, String> {
+    let mut digests = Vec::new();
+    for part in parts {
+        let digest = STANDARD.decode(&part.value)
+            .map_err(|e| format!("Invalid {} part checksum {}: {}", part.algorithm.as_str(), part.value, e))?;
+        digests.extend_from_slice(&digest);
+    }
+    let combined = checksum_of(algorithm, &digests)
+        .ok_or_else(|| "Checksum algorithm is not enabled".to_string())?;
+    Ok(format!("{}-{}", combined.value, parts.len()))
+}
+
+/// S3に保存されたチェックサムをローカルで計算した値と照合する
+///
+/// S3は送信時のチェックサムで受信データを検証済みのため、チェックサムを返さない場合（モックAWSモードなど）は警告のみ
+pub async fn verify_uploaded_checksum(
+    s3_client: &dyn S3ClientTrait,
+    bucket: &str,
+    key: &str,
+    algorithm: ChecksumAlgorithm,
+    expected: &str,
+) -> Result<(), String> {
+    match s3_client.head_object_checksum(bucket, key, algorithm).await? {
+        Some(actual) if actual == expected => {
+            tracing::info!("Verified {} checksum of s3://{}/{}: {}", algorithm.as_str(), bucket, key, actual);
+            Ok(())
+        }
+        Some(actual) => Err(format!(
+            "{} ({} {} != {})", CHECKSUM_MISMATCH_ERROR, algorithm.as_str(), actual, expected
+        )),
+        None => {
+            tracing::warn!("S3 returned no {} checksum for s3://{}/{}; relying on upload-time validation", algorithm.as_str(), bucket, key);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksums_match_s3_encoding() {
+        assert_eq!(checksum_of(ChecksumAlgorithm::None, b"data"), None);
+        // RFC 3720のCRC32Cテストベクタ（32バイトの0は0x8A9136AA）
+        let crc = checksum_of(ChecksumAlgorithm::Crc32c, &[0u8; 32]).unwrap();
+        assert_eq!(crc.value, STANDARD.encode([0x8A, 0x91, 0x36, 0xAA]));
+        let sha = checksum_of(ChecksumAlgorithm::Sha256, b"").unwrap();
+        assert_eq!(sha.value, "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=");
+
+        // 計算時間はアルゴリズムごとに記録する
+        let before = METRICS.snapshot().checksum_crc32c.bytes;
+        compute_checksum(ChecksumAlgorithm::Crc32c, &[1u8; 1024]).unwrap();
+        assert!(METRICS.snapshot().checksum_crc32c.bytes >= before + 1024);
+    }
+
+    #[test]
+    fn test_composite_checksum_combines_part_digests() {
+        for algorithm in [ChecksumAlgorithm::Crc32c, ChecksumAlgorithm::Sha256] {
+            let parts: Vec<_> = [b"part-1".as_slice(), b"part-2".as_slice()].iter()
+                .map(|data| checksum_of(algorithm, data).unwrap())
+                .collect();
+            let mut digests = algorithm.digest(b"part-1").unwrap();
+            digests.extend(algorithm.digest(b"part-2").unwrap());
+            let expected = format!("{}-2", checksum_of(algorithm, &digests).unwrap().value);
+            assert_eq!(composite_checksum(algorithm, &parts).unwrap(), expected);
+        }
+        let invalid = UploadChecksum { algorithm: ChecksumAlgorithm::Crc32c, value: "not base64!".to_string() };
+        assert!(composite_checksum(ChecksumAlgorithm::Crc32c, &[invalid]).is_err());
+    }
+}
@@ -0,0 +1,183 @@
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Manager, State};
+
+use crate::commands::config::{load_config, DEFAULT_UPLOAD_HISTORY_ARCHIVE_LIMIT, DEFAULT_UPLOAD_HISTORY_RETENTION_DAYS};
+use crate::commands::metadata::{metadata_db_path, MetadataDatabase};
+use crate::commands::state_management::{AppStateManager, AppStatistics};
+use crate::commands::upload_system::{queue_lock_error, UploadItem, UploadQueueState};
+use crate::internal::{AppError, InternalError};
+
+/// 完了アイテムのアーカイブ間隔
+const HISTORY_ARCHIVE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+/// 起動直後の負荷を避けるための初回アーカイブまでの待ち時間
+const INITIAL_ARCHIVE_DELAY: Duration = Duration::from_secs(120);
+/// 履歴の既定の取得件数
+const DEFAULT_HISTORY_LIMIT: usize = 200;
+
+/// キューからアップロード履歴へ移したアイテム
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UploadHistoryEntry {
+    pub item: UploadItem,
+    /// 完了・失敗した日時（失敗時は開始日時）
+    pub finished_at: String,
+    pub archived_at: String,
+}
+
+/// アーカイブの実行結果
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadHistoryArchiveResult {
+    pub archived: usize,
+    pub remaining_in_queue: usize,
+    pub retention_days: u32,
+}
+
+/// アップロード履歴（アーカイブ済みのアイテムと累計の統計）
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadHistory {
+    pub entries: Vec<UploadHistoryEntry>,
+    pub total_archived: u64,
+    pub statistics: AppStatistics,
+}
+
+/// アイテムが完了・失敗した日時
+pub fn finished_at(item: &UploadItem) -> &str {
+    item.completed_at.as_deref()
+        .or(item.started_at.as_deref())
+        .unwrap_or(&item.created_at)
+}
+
+fn open_metadata_db(app: &AppHandle) -> Result<MetadataDatabase, InternalError> {
+    let db_path = metadata_db_path(app)?;
+    MetadataDatabase::new(&db_path.to_string_lossy())
+        .map_err(|e| InternalError::Database(format!("Failed to create database connection: {}", e)))
+}
+
+/// 保持日数を過ぎた完了・失敗アイテムをキューからupload_historyテーブルへ移す
+///
+/// DBへの書き込みに失敗した場合はアイテムをキューへ戻す
+pub fn archive_queue_history(
+    app: &AppHandle,
+    queue_state: &UploadQueueState,
+    retention_days: u32,
+    limit: usize,
+) -> Result<UploadHistoryArchiveResult, InternalError> {
+    let now = chrono::Utc::now();
+    let cutoff = now - chrono::Duration::days(i64::from(retention_days));
+    let items = queue_state.lock().map_err(queue_lock_error)?.take_archivable_items(cutoff, limit);
+
+    if !items.is_empty() {
+        let archived_at = now.to_rfc3339();
+        let entries: Vec<UploadHistoryEntry> = items.iter()
+            .map(|item| UploadHistoryEntry {
+                item: item.clone(),
+                finished_at: finished_at(item).to_string(),
+                archived_at: archived_at.clone(),
+            })
+            .collect();
+        let result = open_metadata_db(app).and_then(|db| {
+            db.insert_upload_history(&entries)
+                .map_err(|e| InternalError::Database(format!("Failed to archive upload history: {}", e)))
+        });
+        if let Err(e) = result {
+            queue_state.lock().map_err(queue_lock_error)?.items.extend(items);
+            return Err(e);
+        }
+        log::info!("Archived {} finished upload(s) older than {} day(s)", entries.len(), retention_days);
+    }
+
+    let remaining_in_queue = queue_state.lock().map_err(queue_lock_error)?.items.len();
+    Ok(UploadHistoryArchiveResult { archived: items.len(), remaining_in_queue, retention_days })
+}
+
+fn archive_with_settings(app: &AppHandle, retention_days: Option<u32>) -> Result<UploadHistoryArchiveResult, InternalError> {
+    let (default_days, limit) = load_config(app)
+        .map(|config| (config.app_settings.upload_history_retention_days, config.app_settings.upload_history_archive_limit))
+        .unwrap_or((DEFAULT_UPLOAD_HISTORY_RETENTION_DAYS, DEFAULT_UPLOAD_HISTORY_ARCHIVE_LIMIT));
+    let queue_state = app.state::<UploadQueueState>();
+    archive_queue_history(app, queue_state.inner(), retention_days.unwrap_or(default_days), limit)
+}
+
+/// 日次の完了アイテムのアーカイブを開始
+pub fn start_upload_history_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(INITIAL_ARCHIVE_DELAY).await;
+        loop {
+            if let Err(e) = archive_with_settings(&app, None) {
+                log::error!("Upload history archive failed: {}", e);
+            }
+            tokio::time::sleep(HISTORY_ARCHIVE_INTERVAL).await;
+        }
+    });
+}
+
+/// 完了アイテムのアーカイブを手動で実行（保持日数の指定がない場合は設定値）
+#[command]
+pub async fn archive_upload_history(
+    app: AppHandle,
+    retention_days: Option<u32>,
+) -> Result<UploadHistoryArchiveResult, AppError> {
+    Ok(archive_with_settings(&app, retention_days)?)
+}
+
+/// アーカイブ済みのアップロード履歴を新しい順に取得
+#[command]
+pub async fn get_upload_history(
+    app: AppHandle,
+    project_id: Option<String>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    state: State<'_, AppStateManager>,
+) -> Result<UploadHistory, AppError> {
+    let db = open_metadata_db(&app)?;
+    let entries = db.list_upload_history(project_id.as_deref(), limit.unwrap_or(DEFAULT_HISTORY_LIMIT), offset.unwrap_or(0))
+        .map_err(InternalError::from)?;
+    let total_archived = db.count_upload_history().map_err(InternalError::from)?;
+    let statistics = state.lock()
+        .map_err(|e| InternalError::Other(format!("Failed to lock app state: {}", e)))?
+        .statistics
+        .clone();
+    Ok(UploadHistory { entries, total_archived, statistics })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::upload_system::UploadStatus;
+
+    fn history_item(id: &str, project_id: Option<&str>, completed_at: &str) -> UploadItem {
+        UploadItem {
+            id: id.to_string(),
+            file_size: 10,
+            progress: 100.0,
+            uploaded_bytes: 10,
+            created_at: "2024-01-01T00:00:00+00:00".to_string(),
+            completed_at: Some(completed_at.to_string()),
+            project_id: project_id.map(String::from),
+            ..UploadItem::for_test(&format!("/footage/{}.mov", id), UploadStatus::Completed)
+        }
+    }
+
+    #[test]
+    fn test_history_roundtrip_filters_by_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = MetadataDatabase::new(dir.path().join("metadata.db").to_str().unwrap()).unwrap();
+        let entries: Vec<UploadHistoryEntry> = [
+            history_item("a", Some("PRJ-001"), "2024-01-02T00:00:00+00:00"),
+            history_item("b", None, "2024-01-03T00:00:00+00:00"),
+        ].into_iter()
+            .map(|item| UploadHistoryEntry {
+                finished_at: finished_at(&item).to_string(),
+                item,
+                archived_at: "2024-02-10T00:00:00+00:00".to_string(),
+            })
+            .collect();
+        assert_eq!(db.insert_upload_history(&entries).unwrap(), 2);
+
+        let all = db.list_upload_history(None, 10, 0).unwrap();
+        assert_eq!(all.iter().map(|e| e.item.id.as_str()).collect::<Vec<_>>(), vec!["b", "a"]);
+        let project = db.list_upload_history(Some("PRJ-001"), 10, 0).unwrap();
+        assert_eq!(project, vec![entries[0].clone()]);
+        assert_eq!(db.count_upload_history().unwrap(), 2);
+    }
+}
@@ -0,0 +1,194 @@
+use std::path::Path;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::commands::credential_cache::cached_credentials_expires_at;
+use crate::commands::health_report::{disk_space_for, LOW_DISK_SPACE_BYTES};
+use crate::commands::license::TierPolicy;
+use crate::commands::lifecycle::last_upload_readiness;
+use crate::commands::state_management::UploadReadinessSnapshot;
+use crate::commands::upload_system::UploadTier;
+use crate::internal::i18n::{LocalizedMessage, MessageKey};
+use crate::notifications::format_bytes;
+
+/// 認証情報の有効期限がこの分数以内なら警告する
+const CREDENTIAL_EXPIRY_WARNING_MINUTES: i64 = 30;
+
+/// 現在のティアの制限と残量（Noneは無制限）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UploadLimitInfo {
+    pub tier: UploadTier,
+    pub max_files: Option<usize>,
+    pub remaining_files: Option<usize>,
+    pub max_total_size_bytes: Option<u64>,
+    pub remaining_size_bytes: Option<u64>,
+    /// 現在適用中の帯域制限（Mbps）
+    pub bandwidth_limit_mbps: Option<f64>,
+    /// 設定値とティア上限の小さい方
+    pub max_concurrent_uploads: usize,
+}
+
+impl UploadLimitInfo {
+    /// `queued_files`・`used_bytes`はティア制限の判定と同じく、キュー内のファイル数とアップロード済み+キュー内の合計サイズ
+    pub fn new(
+        policy: &TierPolicy,
+        queued_files: usize,
+        used_bytes: u64,
+        bandwidth_limit_mbps: Option<f64>,
+        configured_concurrency: Option<usize>,
+    ) -> Self {
+        let max_total_size_bytes = policy.max_total_size_mb.map(|mb| mb * 1024 * 1024);
+        Self {
+            tier: policy.tier,
+            max_files: policy.max_queue_files,
+            remaining_files: policy.max_queue_files.map(|max| max.saturating_sub(queued_files)),
+            max_total_size_bytes,
+            remaining_size_bytes: max_total_size_bytes.map(|max| max.saturating_sub(used_bytes)),
+            bandwidth_limit_mbps: bandwidth_limit_mbps.filter(|_| policy.bandwidth_limit),
+            max_concurrent_uploads: configured_concurrency
+                .map_or(policy.max_concurrent_uploads, |configured| configured.min(policy.max_concurrent_uploads)),
+        }
+    }
+}
+
+/// アップロード画面に表示する警告の種類
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadWarningCode {
+    LifecycleNotConfigured,
+    CredentialsExpiringSoon,
+    LowDiskSpace,
+}
+
+/// 現在アクティブな警告
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UploadStatusWarning {
+    pub code: UploadWarningCode,
+    pub message: String,
+}
+
+impl UploadStatusWarning {
+    fn new(code: UploadWarningCode, message: LocalizedMessage) -> Self {
+        Self { code, message: message.render() }
+    }
+}
+
+/// 警告の判定に使う現在の状態
+#[derive(Debug, Clone, Default)]
+pub struct UploadWarningInputs<'a> {
+    /// アップロード先バケットの最後の安全確認結果
+    pub readiness: Option<&'a UploadReadinessSnapshot>,
+    pub credentials_expires_at: Option<DateTime<Utc>>,
+    /// (確認したパス, 空き容量)
+    pub disk: Option<(&'a Path, u64)>,
+}
+
+/// 状態から警告を作成する
+pub fn build_upload_warnings(inputs: &UploadWarningInputs, now: DateTime<Utc>) -> Vec<UploadStatusWarning> {
+    let mut warnings = Vec::new();
+    if let Some(readiness) = inputs.readiness.filter(|readiness| !readiness.lifecycle_healthy) {
+        warnings.push(UploadStatusWarning::new(
+            UploadWarningCode::LifecycleNotConfigured,
+            LocalizedMessage::new(MessageKey::UploadWarningLifecycleNotConfigured).param("bucket", &readiness.bucket),
+        ));
+    }
+    // 期限切れ後はロック中として扱われるため、期限前のみ警告する
+    let remaining = inputs.credentials_expires_at.map(|expires_at| expires_at - now)
+        .filter(|remaining| *remaining > Duration::zero()
+            && *remaining <= Duration::minutes(CREDENTIAL_EXPIRY_WARNING_MINUTES));
+    if let Some(remaining) = remaining {
+        warnings.push(UploadStatusWarning::new(
+            UploadWarningCode::CredentialsExpiringSoon,
+            LocalizedMessage::new(MessageKey::UploadWarningCredentialsExpiring).param("minutes", remaining.num_minutes().max(1)),
+        ));
+    }
+    if let Some((path, available)) = inputs.disk.filter(|(_, available)| *available < LOW_DISK_SPACE_BYTES) {
+        warnings.push(UploadStatusWarning::new(
+            UploadWarningCode::LowDiskSpace,
+            LocalizedMessage::new(MessageKey::HealthLowDiskSpace)
+                .param("path", path.to_string_lossy())
+                .param("available", format_bytes(available)),
+        ));
+    }
+    warnings
+}
+
+/// アップロード先バケット・認証情報キャッシュ・アプリのデータ領域から現在の警告を集める
+pub fn collect_upload_warnings(app: &AppHandle, bucket: Option<&str>) -> Vec<UploadStatusWarning> {
+    let readiness = bucket.and_then(|bucket| last_upload_readiness(app, bucket));
+    let data_dir = app.path().app_data_dir().ok();
+    let disk = data_dir.as_deref()
+        .and_then(|path| disk_space_for(path).0.map(|available| (path, available)));
+    let inputs = UploadWarningInputs {
+        readiness: readiness.as_ref(),
+        credentials_expires_at: cached_credentials_expires_at(app),
+        disk,
+    };
+    build_upload_warnings(&inputs, Utc::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_info_reports_remaining_or_unlimited() {
+        let free = TierPolicy::for_tier(UploadTier::Free);
+        let max_files = free.max_queue_files.unwrap();
+        let max_bytes = free.max_total_size_mb.unwrap() * 1024 * 1024;
+        let info = UploadLimitInfo::new(&free, 2, 45 * 1024 * 1024, Some(50.0), Some(4));
+        assert_eq!(info.tier, UploadTier::Free);
+        assert_eq!(info.remaining_files, Some(max_files - 2));
+        assert_eq!(info.remaining_size_bytes, Some(max_bytes - 45 * 1024 * 1024));
+        // 無料版は帯域制限・並列数を使えない
+        assert_eq!(info.bandwidth_limit_mbps, None);
+        assert_eq!(info.max_concurrent_uploads, 1);
+        // 上限を超えていても残量は0
+        assert_eq!(UploadLimitInfo::new(&free, max_files + 1, max_bytes * 2, None, None).remaining_size_bytes, Some(0));
+
+        let premium = UploadLimitInfo::new(&TierPolicy::for_tier(UploadTier::Premium), 500, u64::MAX, Some(50.0), Some(4));
+        assert_eq!((premium.max_files, premium.remaining_files), (None, None));
+        assert_eq!((premium.max_total_size_bytes, premium.remaining_size_bytes), (None, None));
+        assert_eq!(premium.bandwidth_limit_mbps, Some(50.0));
+        assert_eq!(premium.max_concurrent_uploads, 4);
+    }
+
+    #[test]
+    fn test_build_upload_warnings() {
+        let now = Utc::now();
+        assert!(build_upload_warnings(&UploadWarningInputs::default(), now).is_empty());
+
+        let readiness = UploadReadinessSnapshot {
+            bucket: "archive".to_string(),
+            safe: false,
+            lifecycle_healthy: false,
+            message: "not configured".to_string(),
+            checked_at: now.to_rfc3339(),
+            lifecycle_rules: Vec::new(),
+        };
+        let path = Path::new("/Volumes/Media");
+        let inputs = UploadWarningInputs {
+            readiness: Some(&readiness),
+            credentials_expires_at: Some(now + Duration::minutes(10)),
+            disk: Some((path, 1024)),
+        };
+        let codes: Vec<_> = build_upload_warnings(&inputs, now).into_iter().map(|warning| warning.code).collect();
+        assert_eq!(codes, vec![
+            UploadWarningCode::LifecycleNotConfigured,
+            UploadWarningCode::CredentialsExpiringSoon,
+            UploadWarningCode::LowDiskSpace,
+        ]);
+
+        // 期限まで余裕がある・期限切れ・空き容量が十分な場合は警告しない
+        let healthy = UploadReadinessSnapshot { lifecycle_healthy: true, safe: true, ..readiness.clone() };
+        for expires_at in [now + Duration::hours(2), now - Duration::minutes(1)] {
+            let inputs = UploadWarningInputs {
+                readiness: Some(&healthy),
+                credentials_expires_at: Some(expires_at),
+                disk: Some((path, LOW_DISK_SPACE_BYTES)),
+            };
+            assert!(build_upload_warnings(&inputs, now).is_empty());
+        }
+    }
+}
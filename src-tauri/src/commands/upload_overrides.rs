@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+
+use crate::commands::license::TierPolicy;
+use crate::commands::upload_system::{UploadConfig, UploadItem};
+use crate::internal::InternalError;
+
+/// アップロード時に指定できるストレージクラス
+const UPLOAD_STORAGE_CLASSES: &[&str] = &[
+    "STANDARD",
+    "STANDARD_IA",
+    "ONEZONE_IA",
+    "INTELLIGENT_TIERING",
+    "GLACIER_IR",
+    "GLACIER",
+    "DEEP_ARCHIVE",
+];
+
+/// ファイル名パターンごとのアップロード設定の上書き（未設定の項目はUploadConfigの値を使う）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UploadTypeOverride {
+    /// ファイル名のglob（"*"・"?"が使える。大文字小文字は区別しない。例: "*.mov"、"*_proxy.*"）
+    pub pattern: String,
+    #[serde(default)]
+    pub chunk_size_mb: Option<u64>,
+    #[serde(default)]
+    pub priority: Option<i32>,
+    #[serde(default)]
+    pub storage_class: Option<String>,
+}
+
+/// キュー追加時にアイテムへ焼き込んだ上書き設定
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AppliedUploadOverride {
+    /// 適用したルールのper_type_overrides内での位置
+    pub rule_index: usize,
+    pub pattern: String,
+    /// ティアの上限に合わせた後のチャンクサイズ
+    pub chunk_size_mb: Option<u64>,
+    pub storage_class: Option<String>,
+    /// ティアの上限・未対応の値のため補正した項目
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// globを大文字小文字を区別しない正規表現へ変換する
+fn glob_regex(pattern: &str) -> Result<regex::Regex, InternalError> {
+    let mut expression = String::from("(?i)^");
+    for c in pattern.chars() {
+        match c {
+            '*' => expression.push_str(".*"),
+            '?' => expression.push('.'),
+            _ => expression.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    expression.push('$');
+    regex::Regex::new(&expression)
+        .map_err(|e| InternalError::Config(format!("Invalid file pattern {}: {}", pattern, e)))
+}
+
+/// ファイル名に最初にマッチしたルール（不正なパターンは読み飛ばす）
+pub fn match_override<'a>(overrides: &'a [UploadTypeOverride], file_name: &str) -> Option<(usize, &'a UploadTypeOverride)> {
+    overrides.iter().enumerate().find(|(_, rule)| match glob_regex(rule.pattern.trim()) {
+        Ok(regex) => regex.is_match(file_name),
+        Err(e) => {
+            tracing::warn!("Skipped upload override: {}", e);
+            false
+        }
+    })
+}
+
+/// ルールの値をティアの上限に収める
+fn resolve_override(rule_index: usize, rule: &UploadTypeOverride, policy: &TierPolicy) -> AppliedUploadOverride {
+    let mut warnings = Vec::new();
+    let chunk_size_mb = rule.chunk_size_mb.map(|requested| {
+        let clamped = requested.clamp(policy.min_chunk_size_mb, policy.max_chunk_size_mb);
+        if clamped != requested {
+            warnings.push(format!(
+                "chunk_size_mb {} is outside the {:?} tier range ({}-{} MB); using {} MB",
+                requested, policy.tier, policy.min_chunk_size_mb, policy.max_chunk_size_mb, clamped
+            ));
+        }
+        clamped
+    });
+    let storage_class = rule.storage_class.as_deref().map(str::trim).filter(|class| !class.is_empty()).and_then(|class| {
+        let normalized = class.to_ascii_uppercase();
+        if UPLOAD_STORAGE_CLASSES.contains(&normalized.as_str()) {
+            Some(normalized)
+        } else {
+            warnings.push(format!("Unsupported storage class {}; using the upload default", class));
+            None
+        }
+    });
+    AppliedUploadOverride { rule_index, pattern: rule.pattern.clone(), chunk_size_mb, storage_class, warnings }
+}
+
+/// 最初にマッチしたルールをアイテムへ焼き込む（優先度はアイテムに直接反映する）
+pub fn apply_type_override(item: &mut UploadItem, config: &UploadConfig) {
+    let Some((rule_index, rule)) = match_override(&config.per_type_overrides, &item.file_name) else {
+        return;
+    };
+    let applied = resolve_override(rule_index, rule, &TierPolicy::for_tier(config.tier));
+    for warning in &applied.warnings {
+        tracing::warn!("Upload override {} for {}: {}", applied.pattern, item.file_name, warning);
+    }
+    if let Some(priority) = rule.priority {
+        item.priority = priority;
+    }
+    item.applied_override = Some(applied);
+}
+
+/// アイテムに焼き込んだ上書きを反映したアップロード設定
+pub fn config_for_item(config: &UploadConfig, item: &UploadItem) -> UploadConfig {
+    let mut config = config.clone();
+    if let Some(applied) = &item.applied_override {
+        if let Some(chunk_size_mb) = applied.chunk_size_mb {
+            config.chunk_size_mb = chunk_size_mb;
+        }
+        if let Some(storage_class) = &applied.storage_class {
+            config.storage_class = Some(storage_class.clone());
+        }
+    }
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::upload_system::{new_upload_item, UploadTier};
+
+    fn rule(pattern: &str, chunk_size_mb: Option<u64>, priority: Option<i32>, storage_class: Option<&str>) -> UploadTypeOverride {
+        UploadTypeOverride {
+            pattern: pattern.to_string(),
+            chunk_size_mb,
+            priority,
+            storage_class: storage_class.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_first_matching_rule_wins() {
+        let overrides = vec![
+            rule("*_proxy.*", Some(8), None, None),
+            rule("*.MOV", Some(256), Some(-1), Some("deep_archive")),
+            rule("*.xml", None, Some(10), None),
+            rule("clip??.mxf", None, Some(5), None),
+        ];
+        assert_eq!(match_override(&overrides, "A001_proxy.mov").map(|(i, _)| i), Some(0));
+        assert_eq!(match_override(&overrides, "A001.mov").map(|(i, _)| i), Some(1));
+        assert_eq!(match_override(&overrides, "A001.xml").map(|(i, _)| i), Some(2));
+        assert_eq!(match_override(&overrides, "clip01.mxf").map(|(i, _)| i), Some(3));
+        assert!(match_override(&overrides, "clip001.mxf").is_none());
+        // 正規表現の記号はそのまま比較する
+        assert!(match_override(&[rule("A+B.mov", None, None, None)], "AAB.mov").is_none());
+    }
+
+    #[test]
+    fn test_override_is_clamped_to_tier_and_baked_into_item() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("A001.mov");
+        std::fs::write(&path, b"prores").unwrap();
+
+        let mut config = UploadConfig::new("archive".to_string());
+        config.tier = UploadTier::Free;
+        config.per_type_overrides = vec![rule("*.mov", Some(256), Some(3), Some("deep_archive"))];
+        let mut item = new_upload_item(&path.to_string_lossy(), "footage/A001.mov".to_string()).unwrap();
+        apply_type_override(&mut item, &config);
+
+        let applied = item.applied_override.clone().unwrap();
+        assert_eq!((applied.rule_index, applied.pattern.as_str()), (0, "*.mov"));
+        // 無料版はチャンクサイズが5MB固定
+        assert_eq!(applied.chunk_size_mb, Some(5));
+        assert_eq!(applied.storage_class.as_deref(), Some("DEEP_ARCHIVE"));
+        assert_eq!(applied.warnings.len(), 1);
+        assert_eq!(item.priority, 3);
+
+        let item_config = config_for_item(&config, &item);
+        assert_eq!(item_config.chunk_size_mb, 5);
+        assert_eq!(item_config.storage_class.as_deref(), Some("DEEP_ARCHIVE"));
+
+        let applied = resolve_override(0, &rule("*", Some(64), None, Some("REDUCED")), &TierPolicy::for_tier(UploadTier::Premium));
+        assert_eq!(applied.chunk_size_mb, Some(64));
+        assert_eq!(applied.storage_class, None);
+        assert_eq!(applied.warnings.len(), 1);
+    }
+}
@@ -1,17 +1,57 @@
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
-use tauri::{command, State, AppHandle, Emitter};
+use tauri::{command, State, AppHandle, Emitter, Manager};
 use tokio::sync::mpsc;
 use tokio::time::sleep;
+use tracing::Instrument;
 use uuid::Uuid;
 
 use crate::commands::aws_auth::AwsCredentials;
-use crate::commands::metadata::create_file_metadata;
-use crate::internal::{InternalError, standardize_error};
-use crate::commands::aws_operations::{S3ClientTrait, MockS3Client, RealS3Client, create_s3_client};
+use crate::commands::credential_cache::{cached_credentials, cached_credentials_in_region};
+use crate::commands::bundle::{bundle_staging_dir, complete_queued_bundle_file, discard_queued_bundle, is_queued_bundle_file, stage_bundle_async, BundleOptions, MANIFEST_SUFFIX};
+use crate::commands::compression::{compress_file_async, compression_metadata, default_compression_level, should_compress, ZSTD_EXTENSION};
+use crate::commands::manifest::{upload_manifest, write_manifest, ManifestEntry};
+use crate::commands::upload_history::finished_at;
+use crate::commands::mirror_upload::{initial_mirrors, run_mirror_uploads, MirrorResult, MirrorStatus, MirrorTarget};
+use crate::commands::metadata::{build_file_metadata, metadata_db_path, MetadataDatabase};
+use crate::commands::bucket_region::correct_credentials_region;
+use crate::commands::file_links::{resolve_queue_paths, ResolvedPaths};
+use crate::commands::projects::{apply_project_prefix, project_s3_prefix};
+use crate::commands::operations::{begin_operation, CancellationToken, OperationKind};
+use crate::commands::bandwidth::{BandwidthProfile, BANDWIDTH_LIMITER};
+use crate::commands::read_scheduler::{ReadConcurrencyLimits, ReadMode, READ_SCHEDULER};
+use crate::commands::naming::{expand_naming_pattern, pattern_uses_hash, validate_naming_pattern, NamingContext};
+use crate::commands::audit::{record_operation, AuditOperation, OperationAuditEntry};
+use crate::commands::retention::record_completed_upload;
+use crate::internal::{AppError, ErrorCode, InternalError};
+use crate::internal::i18n::{tr, LocalizedMessage, MessageKey};
+use crate::internal::error_mapper::is_network_error_message;
+use crate::internal::unicode::to_nfc;
+use crate::commands::aws_operations::{S3ClientTrait, create_cached_s3_client, create_s3_client_for_credentials};
+use crate::commands::mock_aws::{is_mock_aws_mode, label_message};
+use crate::commands::lifecycle::{check_lifecycle_prefix_coverage, refresh_upload_readiness, LifecyclePrefixWarning};
+use crate::commands::license::{current_policy, TierPolicy};
+use crate::commands::metrics::{timed_lock, MetricsRegistry, METRICS};
+use crate::commands::part_progress::{PartPosition, PartProgressHook, PartProgressThrottle};
+use crate::commands::transfer_cap::is_transfer_blocked;
+use crate::commands::file_in_use::{check_file_in_use, check_files_in_use, FileInUseHold, FILE_IN_USE_TIMEOUT};
+use crate::commands::local_file_lock::lock_local_file;
+use crate::commands::upload_overrides::{apply_type_override, config_for_item, AppliedUploadOverride, UploadTypeOverride};
+use crate::commands::queue_watchdog::{
+    QueueReconcileReport, QueueWatchdog, UploadStalledEvent, WatchdogAction, MAX_STALL_HISTORY, UPLOAD_STALLED_EVENT,
+};
+use crate::commands::hot_folder::{finish_hot_folder_upload, record_hot_folder_upload_failure};
+use crate::commands::upload_limits::{collect_upload_warnings, UploadLimitInfo, UploadStatusWarning};
+use crate::commands::upload_checksum::{composite_checksum, compute_checksum, verify_uploaded_checksum, ChecksumAlgorithm, PartUpload};
+
+/// パート送信失敗時の再送待ち時間の基準（試行回数に比例して延ばす）
+const PART_RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// キュー追加時にS3キーがライフサイクルルールの対象外だった場合の通知イベント
+pub const LIFECYCLE_PREFIX_WARNING_EVENT: &str = "lifecycle-prefix-warning";
 
 /// アップロードアイテムの状態
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -22,10 +62,26 @@ pub enum UploadStatus {
     Failed,
     Paused,
     Cancelled,
+    /// プライマリは完了したが、ミラー先へのアップロードが失敗している（retry_mirrorで再実行）
+    Warning,
+    /// 重複のためアップロードせずに除外した（理由はerror_message）
+    Skipped,
+}
+
+impl UploadStatus {
+    /// プライマリのアップロードが完了している（ミラーの失敗を含む）
+    pub fn is_primary_completed(&self) -> bool {
+        matches!(self, Self::Completed | Self::Warning)
+    }
+
+    /// これ以上自動では進まない状態
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled | Self::Warning | Self::Skipped)
+    }
 }
 
 /// アップロードアイテム
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct UploadItem {
     pub id: String,
     pub file_path: String,
@@ -42,6 +98,134 @@ pub struct UploadItem {
     pub completed_at: Option<String>,
     pub error_message: Option<String>,
     pub retry_count: u32,
+    /// 任意のメモ
+    #[serde(default)]
+    pub note: Option<String>,
+    /// 素材を紐付ける案件ID（x-amz-meta-project-idとして付与）
+    #[serde(default)]
+    pub project_id: Option<String>,
+    /// 待機中のアイテムを開始する優先度（大きいほど先に開始、同じ値は追加順）
+    #[serde(default)]
+    pub priority: i32,
+    /// ミラー先ごとのアップロード結果（ミラー未設定時は空）
+    #[serde(default)]
+    pub mirrors: Vec<MirrorResult>,
+    /// キュー追加時に適用したper_type_overridesのルール（マッチしなければNone）
+    #[serde(default)]
+    pub applied_override: Option<AppliedUploadOverride>,
+}
+
+#[cfg(test)]
+impl UploadItem {
+    /// テスト用のアイテム（ファイル名はパスから取り、S3キーはuploads/配下、サイズは100バイト）
+    pub fn for_test(file_path: &str, status: UploadStatus) -> Self {
+        let file_name = Path::new(file_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            file_path: file_path.to_string(),
+            s3_key: format!("uploads/{}", file_name),
+            file_name,
+            file_size: 100,
+            status,
+            progress: 0.0,
+            uploaded_bytes: 0,
+            speed_mbps: 0.0,
+            eta_seconds: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            started_at: None,
+            completed_at: None,
+            error_message: None,
+            retry_count: 0,
+            note: None,
+            project_id: None,
+            priority: 0,
+            mirrors: Vec::new(),
+            applied_override: None,
+        }
+    }
+}
+
+/// 案件IDを付与するS3オブジェクトメタデータのキー（x-amz-meta-project-id）
+pub const META_PROJECT_ID: &str = "project-id";
+/// 案件IDの最大長
+const MAX_PROJECT_ID_LEN: usize = 128;
+
+/// 空白のみの入力はNoneにし、S3メタデータに載せられない案件IDを拒否する
+pub fn normalize_project_id(project_id: Option<String>) -> Result<Option<String>, InternalError> {
+    let Some(project_id) = project_id.map(|id| id.trim().to_string()).filter(|id| !id.is_empty()) else {
+        return Ok(None);
+    };
+    if project_id.len() > MAX_PROJECT_ID_LEN || !project_id.chars().all(|c| c.is_ascii_graphic()) {
+        return Err(InternalError::Config(format!(
+            "Invalid project ID: {} (use up to {} ASCII characters without spaces)",
+            project_id, MAX_PROJECT_ID_LEN
+        )));
+    }
+    Ok(Some(project_id))
+}
+
+fn normalize_note(note: Option<String>) -> Option<String> {
+    note.map(|note| note.trim().to_string()).filter(|note| !note.is_empty())
+}
+
+/// S3キーの最大長（UTF-8のバイト数）
+const MAX_S3_KEY_BYTES: usize = 1024;
+
+/// 手入力のS3キーを正規化（前後の空白・先頭の"/"・連続する"/"を除き、NFCへ揃える）
+pub fn normalize_s3_key(key: &str) -> Result<String, InternalError> {
+    let key = to_nfc(key.trim());
+    let normalized = key.split('/').filter(|segment| !segment.is_empty()).collect::<Vec<_>>().join("/");
+    if normalized.is_empty() || key.ends_with('/') {
+        return Err(InternalError::Config(format!("Invalid S3 key (must name a file): {}", key)));
+    }
+    if normalized.len() > MAX_S3_KEY_BYTES {
+        return Err(InternalError::Config(format!("S3 key exceeds {} bytes: {}", MAX_S3_KEY_BYTES, normalized)));
+    }
+    Ok(normalized)
+}
+
+/// update_upload_itemで変更する項目（指定しない項目はそのまま）
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UploadItemChanges {
+    pub s3_key: Option<String>,
+    pub priority: Option<i32>,
+    /// 空文字でメモを消す
+    pub note: Option<String>,
+}
+
+/// アップロード先へ伝播させるアイテムのメモ・案件ID
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UploadItemLabels {
+    pub note: Option<String>,
+    pub project_id: Option<String>,
+}
+
+impl UploadItemLabels {
+    pub fn from_item(item: &UploadItem) -> Self {
+        Self { note: item.note.clone(), project_id: item.project_id.clone() }
+    }
+
+    /// S3オブジェクトメタデータ（メモは非ASCIIを含みうるため付与しない）
+    fn object_metadata(&self) -> HashMap<String, String> {
+        self.project_id.iter()
+            .map(|project_id| (META_PROJECT_ID.to_string(), project_id.clone()))
+            .collect()
+    }
+
+    /// メタデータDBのcustom_fields
+    fn custom_fields(&self) -> HashMap<String, String> {
+        let mut fields = HashMap::new();
+        if let Some(project_id) = &self.project_id {
+            fields.insert("project_id".to_string(), project_id.clone());
+        }
+        if let Some(note) = &self.note {
+            fields.insert("note".to_string(), note.clone());
+        }
+        fields
+    }
 }
 
 /// アップロード進捗情報
@@ -54,12 +238,91 @@ pub struct UploadProgress {
     pub speed_mbps: f64,
     pub eta_seconds: Option<u64>,
     pub status: UploadStatus,
+    /// 送信中のパート番号（1始まり、単純アップロードではNone）
+    #[serde(default)]
+    pub current_part: Option<u32>,
+    #[serde(default)]
+    pub total_parts: Option<u32>,
+    /// 送信中のパートのうち送信済みのバイト数
+    #[serde(default)]
+    pub part_uploaded_bytes: Option<u64>,
+    #[serde(default)]
+    pub part_total_bytes: Option<u64>,
+}
+
+/// 送信済みバイト数からアップロード進捗を組み立てる
+fn build_upload_progress(item_id: &str, uploaded: u64, total: u64, speed_mbps: f64, part: Option<PartPosition>) -> UploadProgress {
+    let percentage = if total > 0 {
+        (uploaded as f64 / total as f64) * 100.0
+    } else {
+        0.0
+    };
+    
+    let eta_seconds = if speed_mbps > 0.0 {
+        let remaining_mb = total.saturating_sub(uploaded) as f64 / (1024.0 * 1024.0);
+        Some((remaining_mb / speed_mbps) as u64)
+    } else {
+        None
+    };
+    
+    UploadProgress {
+        item_id: item_id.to_string(),
+        uploaded_bytes: uploaded,
+        total_bytes: total,
+        percentage,
+        speed_mbps,
+        eta_seconds,
+        status: if uploaded >= total {
+            UploadStatus::Completed
+        } else {
+            UploadStatus::InProgress
+        },
+        current_part: part.map(|part| part.current_part),
+        total_parts: part.map(|part| part.total_parts),
+        part_uploaded_bytes: part.map(|part| part.part_uploaded_bytes),
+        part_total_bytes: part.map(|part| part.part_total_bytes),
+    }
+}
+
+/// パート内の送信進捗を間引いてUploadProgressとして送るフック
+fn part_progress_hook(
+    item_id: &str,
+    progress_tx: &mpsc::Sender<UploadProgress>,
+    started: Instant,
+    uploaded_before: u64,
+    file_size: u64,
+    part: PartPosition,
+) -> PartProgressHook {
+    let item_id = item_id.to_string();
+    let progress_tx = progress_tx.clone();
+    let throttle = Mutex::new(PartProgressThrottle::new(part.part_total_bytes));
+    Arc::new(move |sent| {
+        if !throttle.lock().unwrap_or_else(|e| e.into_inner()).should_report(sent, Instant::now()) {
+            return;
+        }
+        let uploaded = uploaded_before + sent;
+        let elapsed = started.elapsed().as_secs_f64();
+        let speed_mbps = if elapsed > 0.0 {
+            (uploaded as f64 / (1024.0 * 1024.0)) / elapsed
+        } else {
+            0.0
+        };
+        let mut progress = build_upload_progress(&item_id, uploaded, file_size, speed_mbps, Some(PartPosition { part_uploaded_bytes: sent, ..part }));
+        // 最後のパートを送り切っても完了はマルチパートの完了後に判定する
+        progress.status = UploadStatus::InProgress;
+        // 途中経過のため、チャネルが詰まっている場合は捨てる
+        let _ = progress_tx.try_send(progress);
+    })
 }
 
 /// アップロード設定
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UploadConfig {
-    pub aws_credentials: AwsCredentials,
+    /// 接続先のリージョン（フロントからは受け取らず、キューの初期化時にロック解除済みの認証情報から設定する）
+    ///
+    /// 認証情報そのものは保持せず、S3クライアントの作成時にcredential_cacheから取得する
+    #[serde(skip)]
+    pub region: String,
     pub bucket_name: String,
     pub max_concurrent_uploads: usize,
     pub chunk_size_mb: u64,
@@ -76,6 +339,55 @@ pub struct UploadConfig {
     pub bandwidth_limit_mbps: Option<f64>,  // 帯域制限（無料版: なし, プレミアム版: 設定可能）
     pub enable_resume: bool,                // 中断・再開機能（無料版: false, プレミアム版: true）
     pub tier: UploadTier,                   // 機能ティア
+    /// 動画以外のファイルをzstd圧縮してからアップロード
+    #[serde(default)]
+    pub compress_non_video: bool,
+    /// zstdの圧縮レベル
+    #[serde(default = "default_compression_level")]
+    pub compression_level: i32,
+    /// ライフサイクルルールの対象外となるS3キーのアップロードを拒否する（既定は警告のみ）
+    #[serde(default)]
+    pub block_uncovered_lifecycle_prefix: bool,
+    /// 同一ボリュームから同時に読み込むファイル数の上限（HDD・SSD・外部ボリューム別）
+    #[serde(default)]
+    pub read_concurrency: ReadConcurrencyLimits,
+    /// アップロードの検証が完了したローカルファイルに付与するFinderタグ（「名前」または「名前:色」）
+    #[serde(default)]
+    pub set_finder_tag_on_complete: Option<String>,
+    /// アップロードの検証が完了したローカルファイルの書き込みビットを落とし、誤って編集・上書きされないようにする
+    #[serde(default)]
+    pub set_readonly_after_upload: bool,
+    /// 読み取り専用化の際にuchgフラグも付ける（macOSのみ。Finderからの削除・名前変更も防ぐ）
+    #[serde(default)]
+    pub set_immutable_flag_after_upload: bool,
+    /// 同じフォルダのアイテムがすべて完了したら、フォルダに.reelvault-manifest.jsonを書き出す
+    #[serde(default)]
+    pub write_local_manifest: bool,
+    /// 書き出したマニフェストをS3の.reelvault/manifests/にもアップロードする
+    #[serde(default)]
+    pub upload_manifest_to_s3: bool,
+    /// キューへ追加したシンボリックリンクを実体のパス・サイズで登録する（無効時はスキップ）
+    #[serde(default = "default_follow_symlinks")]
+    pub follow_symlinks: bool,
+    /// アップロード中に元ファイルが変更されて失敗した場合、この秒数変化しなくなってから自動で再キューする（未設定は再キューしない）
+    #[serde(default)]
+    pub requeue_after_source_change_secs: Option<u64>,
+    /// プライマリの完了後に同じファイルをアップロードするバックアップ先（別リージョン・別アカウント）
+    #[serde(default)]
+    pub mirror_targets: Vec<MirrorTarget>,
+    /// アップロード時に指定するストレージクラス（未設定はSTANDARD）
+    #[serde(default)]
+    pub storage_class: Option<String>,
+    /// ファイル名パターンごとのチャンクサイズ・優先度・ストレージクラスの上書き（最初にマッチしたルールを使う）
+    #[serde(default)]
+    pub per_type_overrides: Vec<UploadTypeOverride>,
+    /// S3へ送って受信データを検証させ、完了後に保存値と照合するチェックサム（Noneは従来どおりETagのみ）
+    #[serde(default)]
+    pub checksum_algorithm: ChecksumAlgorithm,
+}
+
+fn default_follow_symlinks() -> bool {
+    true
 }
 
 /// アップロード機能ティア
@@ -86,12 +398,43 @@ pub enum UploadTier {
 }
 
 impl UploadConfig {
-
-
+    /// バケットから標準設定を作成（CLIなどUI以外からのアップロード用）
+    pub fn new(bucket_name: String) -> Self {
+        Self {
+            region: String::new(),
+            bucket_name,
+            max_concurrent_uploads: 1,
+            chunk_size_mb: 16,
+            retry_attempts: 3,
+            timeout_seconds: 300,
+            auto_create_metadata: false,
+            s3_key_prefix: None,
+            max_concurrent_parts: 1,
+            adaptive_chunk_size: false,
+            min_chunk_size_mb: 5,
+            max_chunk_size_mb: 16,
+            bandwidth_limit_mbps: None,
+            enable_resume: false,
+            tier: UploadTier::Premium,
+            compress_non_video: false,
+            compression_level: default_compression_level(),
+            block_uncovered_lifecycle_prefix: false,
+            read_concurrency: ReadConcurrencyLimits::default(),
+            set_finder_tag_on_complete: None,
+            set_readonly_after_upload: false,
+            set_immutable_flag_after_upload: false,
+            write_local_manifest: false,
+            upload_manifest_to_s3: false,
+            follow_symlinks: true,
+            requeue_after_source_change_secs: None,
+            mirror_targets: Vec::new(),
+            storage_class: None,
+            per_type_overrides: Vec::new(),
+            checksum_algorithm: ChecksumAlgorithm::None,
+        }
+    }
 }
 
-
-
 /// アップロードキューの管理
 #[derive(Debug)]
 pub struct UploadQueue {
@@ -103,6 +446,24 @@ pub struct UploadQueue {
     pub total_files_uploaded: u64,
     /// 厳格な同時実行制御のための専用カウンター
     pub active_upload_count: usize,
+    /// キュー全体の一時停止中フラグ（新規アップロードを開始しない）
+    pub is_paused: bool,
+    /// 実行中のアップロードタスク（終了時に中断するため）
+    pub upload_tasks: HashMap<String, tokio::task::AbortHandle>,
+    /// ネットワークがオフラインと判定されている（新規アップロードを開始しない）
+    pub network_offline: bool,
+    /// ネットワーク起因で一時停止したアイテム（オンライン復帰時に自動再開する）
+    pub network_paused_items: HashSet<String>,
+    /// ネットワーク起因の失敗が発生し、到達性の再確認が必要
+    pub network_check_requested: bool,
+    /// 事前圧縮によって削減した転送量の合計
+    pub compression_saved_bytes: u64,
+    /// 他のアプリが書き込み中のため待機中のまま保留しているアイテム
+    pub file_in_use_holds: HashMap<String, FileInUseHold>,
+    /// ウォッチドッグが検出したキュー処理の停滞（診断情報のエラー履歴に含める）
+    pub stall_history: Vec<UploadStalledEvent>,
+    /// 中断して先頭からやり直すため、未完了パーツの破棄を待っているマルチパートアップロード
+    pub stale_multipart_uploads: Vec<ActiveMultipartUpload>,
 }
 
 impl UploadQueue {
@@ -115,9 +476,25 @@ impl UploadQueue {
             total_uploaded_bytes: 0,
             total_files_uploaded: 0,
             active_upload_count: 0,
+            is_paused: false,
+            upload_tasks: HashMap::new(),
+            network_offline: false,
+            network_paused_items: HashSet::new(),
+            network_check_requested: false,
+            compression_saved_bytes: 0,
+            file_in_use_holds: HashMap::new(),
+            stall_history: Vec::new(),
+            stale_multipart_uploads: Vec::new(),
         }
     }
     
+    /// 進行中のアップロード件数
+    pub fn in_progress_count(&self) -> usize {
+        self.items.iter()
+            .filter(|item| item.status == UploadStatus::InProgress)
+            .count()
+    }
+    
     /// 安全な同時実行数取得
     pub fn get_active_upload_count(&self) -> usize {
         // 複数の状態を確認して最も正確な値を返す
@@ -136,64 +513,45 @@ impl UploadQueue {
     /// アップロード開始時の状態更新
     pub fn start_upload(&mut self, item_id: &str) -> Result<(), InternalError> {
         if let Some(config) = &self.config {
-            let current_active = self.get_active_upload_count();
-            
-            // 無料版の厳格な制限チェック
-            if config.max_concurrent_uploads == 1 && current_active > 0 {
-                return Err(InternalError::Other(format!("無料版では同時アップロードは1つまでです。現在アクティブ: {}", current_active)));
-            }
-            
-            if current_active >= config.max_concurrent_uploads {
-                return Err(InternalError::Other(format!("同時アップロード数の上限に達しています: {}/{}", 
-                                 current_active, config.max_concurrent_uploads)));
-            }
+            TierPolicy::for_tier(config.tier)
+                .check_concurrent_uploads(self.get_active_upload_count(), config.max_concurrent_uploads)?;
         }
         
-        // 状態を更新
+        // 状態を更新（待機中以外から開始するとカウントが二重に増えるため拒否）
         if let Some(item) = self.items.iter_mut().find(|i| i.id == item_id) {
+            if item.status != UploadStatus::Pending {
+                return Err(InternalError::localized(ErrorCode::Unknown, LocalizedMessage::new(MessageKey::UploadItemNotPending)
+                    .param("item_id", item_id)
+                    .param("status", format!("{:?}", item.status))));
+            }
+            let now = chrono::Utc::now();
+            if let Ok(created_at) = chrono::DateTime::parse_from_rfc3339(&item.created_at) {
+                if let Ok(wait) = (now - created_at.with_timezone(&chrono::Utc)).to_std() {
+                    METRICS.queue_wait.record(wait);
+                }
+            }
             item.status = UploadStatus::InProgress;
-            item.started_at = Some(chrono::Utc::now().to_rfc3339());
+            item.started_at = Some(now.to_rfc3339());
             self.active_upload_count += 1;
             
-            log::info!("Upload started: {} (active count: {})", item_id, self.active_upload_count);
+            tracing::info!("Upload started: {} (active count: {})", item_id, self.active_upload_count);
             Ok(())
         } else {
-            Err(InternalError::Other(format!("Upload item not found: {}", item_id)))
+            Err(item_not_found(item_id))
         }
     }
     
     /// アップロード完了時の状態更新
     pub fn complete_upload(&mut self, item_id: &str, success: bool, error_msg: Option<String>) {
-        log::info!("🔧 complete_upload called: {} (success: {})", item_id, success);
-        
-        // アイテムの現在の状態をチェック
-        let current_status = self.items.iter()
-            .find(|i| i.id == item_id)
-            .map(|i| i.status.clone());
-        
-        if let Some(status) = &current_status {
-            if *status == UploadStatus::Completed {
-                log::info!("⚠️  Upload already completed, skipping duplicate cleanup: {}", item_id);
-                return;
-            }
-        }
-        
-        // アクティブカウントを減らす（重複減算を防ぐ）
-        let was_active = self.active_uploads.contains_key(item_id) || 
-                        current_status == Some(UploadStatus::InProgress);
+        tracing::info!("🔧 complete_upload called: {} (success: {})", item_id, success);
         
-        log::info!("🔍 Cleanup state check - was_active: {}, active_uploads contains: {}, current_status: {:?}", 
-                   was_active, self.active_uploads.contains_key(item_id), current_status);
-        
-        if was_active && self.active_upload_count > 0 {
-            self.active_upload_count -= 1;
-            log::info!("🔽 Active upload count decreased: {} -> {}", 
-                       self.active_upload_count + 1, self.active_upload_count);
+        // アクティブ枠を解放（進行中からの遷移時のみ減算し、重複減算を防ぐ）
+        // 進行中でなければ、遅れて届いた完了や重複した完了で失敗・待機中・一時停止の状態や累計を上書きしない
+        if !self.release_active_slot(item_id) {
+            tracing::info!("⚠️  Upload is not in progress, ignoring completion: {}", item_id);
+            return;
         }
-        
-        // active_uploadsから削除（成功・失敗に関わらず必ず削除）
-        let removed = self.active_uploads.remove(item_id);
-        log::info!("🗑️  Removing from active_uploads: {} (was present: {})", item_id, removed.is_some());
+        self.upload_tasks.remove(item_id);
         
         // アイテムの状態を更新
         if let Some(item) = self.items.iter_mut().find(|i| i.id == item_id) {
@@ -203,43 +561,515 @@ impl UploadQueue {
                 item.progress = 100.0;
                 self.total_files_uploaded += 1;
                 self.total_uploaded_bytes += item.file_size;
-                log::info!("✅ Upload marked as completed: {} ({})", item.file_name, item_id);
+                tracing::info!("✅ Upload marked as completed: {} ({})", item.file_name, item_id);
             } else {
                 item.status = UploadStatus::Failed;
                 item.error_message = error_msg;
-                log::error!("❌ Upload marked as failed: {} ({})", item.file_name, item_id);
+                tracing::error!("❌ Upload marked as failed: {} ({})", item.file_name, item_id);
             }
         }
         
-        log::info!("📊 Upload completion summary - Active count: {}, Active uploads: {}, Items in progress: {}", 
+        tracing::info!("📊 Upload completion summary - Active count: {}, Active uploads: {}, Items in progress: {}", 
                    self.active_upload_count, 
                    self.active_uploads.len(),
                    self.items.iter().filter(|i| i.status == UploadStatus::InProgress).count());
     }
     
-    /// 無料版制限チェック
-    pub fn check_free_tier_limits(&self, new_files_count: usize) -> Result<(), InternalError> {
+    /// アクティブ枠を解放する
+    ///
+    /// カウントの減算は進行中のアイテムに対してだけ行う。呼び出し元は直後に状態を
+    /// 進行中以外へ変えるため、同じアイテムで二重に減算されることはない
+    fn release_active_slot(&mut self, item_id: &str) -> bool {
+        let was_in_progress = self.items.iter()
+            .any(|i| i.id == item_id && i.status == UploadStatus::InProgress);
+        self.active_uploads.remove(item_id);
+        if was_in_progress {
+            let old_count = self.active_upload_count;
+            self.active_upload_count = self.active_upload_count.saturating_sub(1);
+            tracing::info!("🔽 Active upload count decreased: {} -> {}", old_count, self.active_upload_count);
+        }
+        was_in_progress
+    }
+    
+    /// ネットワーク起因で失敗・中断したアイテムを一時停止へ移行（retry_countは消費しない）
+    pub fn pause_for_network(&mut self, item_id: &str) {
+        self.release_active_slot(item_id);
+        if let Some(handle) = self.upload_tasks.remove(item_id) {
+            handle.abort();
+        }
+        // オフライン中は破棄できないため、接続の回復後に未完了パーツを破棄する
+        self.discard_multipart_upload(item_id);
+        
+        if let Some(item) = self.items.iter_mut().find(|i| i.id == item_id) {
+            // 再開時は先頭からアップロードし直す
+            item.status = UploadStatus::Paused;
+            item.progress = 0.0;
+            item.uploaded_bytes = 0;
+            item.speed_mbps = 0.0;
+            item.eta_seconds = None;
+            item.error_message = Some("ネットワーク切断のため一時停止中".to_string());
+            self.network_paused_items.insert(item_id.to_string());
+            tracing::warn!("⏸️ Upload paused due to network error: {} ({})", item.file_name, item_id);
+        }
+    }
+    
+    /// オフラインへ移行し、進行中のアイテムを全て一時停止（一時停止した件数を返す）
+    pub fn go_offline(&mut self) -> usize {
+        self.network_offline = true;
+        let in_progress_ids: Vec<String> = self.items.iter()
+            .filter(|item| item.status == UploadStatus::InProgress)
+            .map(|item| item.id.clone())
+            .collect();
+        for item_id in &in_progress_ids {
+            self.pause_for_network(item_id);
+        }
+        in_progress_ids.len()
+    }
+    
+    /// 進行中のマルチパートアップロードの登録を外し、未完了パーツの破棄待ちへ回す
+    fn discard_multipart_upload(&mut self, item_id: &str) {
+        if let Some(upload) = take_multipart_upload(item_id) {
+            self.stale_multipart_uploads.push(upload);
+        }
+    }
+    
+    /// 破棄待ちのマルチパートアップロードと、破棄に使うリージョンを取り出す（設定前は取り出さない）
+    pub fn take_stale_multipart_uploads(&mut self) -> Option<(Vec<ActiveMultipartUpload>, String)> {
+        if self.stale_multipart_uploads.is_empty() {
+            return None;
+        }
+        let region = self.config.as_ref()?.region.clone();
+        Some((std::mem::take(&mut self.stale_multipart_uploads), region))
+    }
+    
+    /// オンライン復帰時にネットワーク起因で一時停止したアイテムを待機中へ戻す（再開した件数を返す）
+    pub fn resume_from_network(&mut self) -> usize {
+        self.network_offline = false;
+        let paused_ids = std::mem::take(&mut self.network_paused_items);
+        let mut resumed = 0;
+        for item in self.items.iter_mut().filter(|i| paused_ids.contains(&i.id)) {
+            if item.status == UploadStatus::Paused {
+                item.status = UploadStatus::Pending;
+                item.error_message = None;
+                resumed += 1;
+            }
+        }
+        resumed
+    }
+    
+    /// ティア制限の対象となる(キュー内のファイル数, アップロード済みとキュー内の合計サイズ)
+    pub fn tier_usage(&self) -> (usize, u64) {
+        let total_size = self.total_uploaded_bytes + self.items.iter().map(|i| i.file_size).sum::<u64>();
+        (self.items.len(), total_size)
+    }
+
+    /// ティアによるキューのファイル数・合計サイズの制限チェック
+    pub fn check_tier_limits(&self, new_files_count: usize) -> Result<(), InternalError> {
         if let Some(config) = &self.config {
-            if config.tier == UploadTier::Free {
-                // 無料版の制限チェック
-                let total_files = self.items.len() + new_files_count;
-                if total_files > 10 {
-                    return Err(InternalError::Other("無料版では最大10ファイルまでアップロードできます".to_string()));
-                }
-                
-                let total_size_mb = (self.total_uploaded_bytes + 
-                    self.items.iter().map(|i| i.file_size).sum::<u64>()) / 1024 / 1024;
-                if total_size_mb > 100 {
-                    return Err(InternalError::Other("無料版では最大100MBまでアップロードできます".to_string()));
+            let (queued_files, total_size) = self.tier_usage();
+            TierPolicy::for_tier(config.tier)
+                .check_queue_limits(queued_files + new_files_count, total_size)?;
+        }
+        Ok(())
+    }
+    
+    /// 完了・失敗・キャンセル・警告から`cutoff`以前のアイテムを古い順に最大`limit`件キューから取り出す
+    pub fn take_archivable_items(&mut self, cutoff: chrono::DateTime<chrono::Utc>, limit: usize) -> Vec<UploadItem> {
+        let mut candidates: Vec<(chrono::DateTime<chrono::FixedOffset>, String)> = self.items.iter()
+            .filter(|item| item.status.is_finished())
+            .filter_map(|item| {
+                let finished = chrono::DateTime::parse_from_rfc3339(finished_at(item)).ok()?;
+                (finished <= cutoff).then(|| (finished, item.id.clone()))
+            })
+            .collect();
+        candidates.sort();
+        candidates.truncate(limit);
+        let ids: HashSet<String> = candidates.into_iter().map(|(_, id)| id).collect();
+        
+        let (archived, remaining): (Vec<UploadItem>, Vec<UploadItem>) = std::mem::take(&mut self.items)
+            .into_iter()
+            .partition(|item| ids.contains(&item.id));
+        self.items = remaining;
+        archived
+    }
+    
+    /// フォルダ直下のアイテムがすべて終わっていれば完了したアイテムを返す（未完了があればNone）
+    pub fn folder_manifest_items(&self, folder: &Path) -> Option<Vec<UploadItem>> {
+        let in_folder: Vec<&UploadItem> = self.items.iter()
+            .filter(|item| Path::new(&item.file_path).parent() == Some(folder))
+            .collect();
+        let unfinished = in_folder.iter()
+            .any(|item| matches!(item.status, UploadStatus::Pending | UploadStatus::InProgress | UploadStatus::Paused));
+        if unfinished {
+            return None;
+        }
+        Some(in_folder.into_iter().filter(|item| item.status.is_primary_completed()).cloned().collect())
+    }
+    
+    /// 複数のアイテムを削除（進行中のアイテムはアップロードを中断してから削除する）
+    ///
+    /// 中断したマルチパートアップロードを返すため、呼び出し元はロックを外してから破棄すること
+    pub fn remove_items(&mut self, item_ids: &[String]) -> (Vec<BatchItemResult>, Vec<ActiveMultipartUpload>) {
+        let mut results = Vec::new();
+        let mut aborted_uploads = Vec::new();
+        for item_id in unique_ids(item_ids) {
+            let Some(status) = self.items.iter().find(|i| i.id == item_id).map(|i| i.status.clone()) else {
+                results.push(BatchItemResult::failed(item_id, BatchItemOutcome::NotFound));
+                continue;
+            };
+            let outcome = if status == UploadStatus::InProgress {
+                self.release_active_slot(item_id);
+                if let Some(handle) = self.upload_tasks.remove(item_id) {
+                    handle.abort();
                 }
+                aborted_uploads.extend(take_multipart_upload(item_id));
+                BatchItemOutcome::RemovedWithAbort
+            } else {
+                BatchItemOutcome::Removed
+            };
+            discard_removed_bundle_files(self.items.iter().filter(|i| i.id == item_id));
+            self.items.retain(|i| i.id != item_id);
+            self.active_uploads.remove(item_id);
+            self.network_paused_items.remove(item_id);
+            results.push(BatchItemResult::succeeded(item_id, outcome));
+        }
+        (results, aborted_uploads)
+    }
+    
+    /// 待機中のアイテムを編集できるか確認（`s3_key`は正規化済みであること）
+    ///
+    /// 他のアイテムと同じS3キーへの変更は上書きになるため拒否する
+    pub fn check_pending_update(&self, item_id: &str, changes: &UploadItemChanges) -> Result<(), InternalError> {
+        let item = self.items.iter()
+            .find(|i| i.id == item_id)
+            .ok_or_else(|| item_not_found(item_id))?;
+        if item.status != UploadStatus::Pending {
+            return Err(InternalError::localized(ErrorCode::Config, LocalizedMessage::new(MessageKey::UploadItemNotEditable)
+                .param("item_id", item_id)
+                .param("status", format!("{:?}", item.status))));
+        }
+        if let Some(s3_key) = &changes.s3_key {
+            if let Some(other) = self.items.iter().find(|i| i.id != item_id && &i.s3_key == s3_key) {
+                return Err(InternalError::Config(format!(
+                    "S3 key {} is already used by {} in the upload queue", s3_key, other.file_name
+                )));
             }
         }
         Ok(())
     }
+
+    /// 待機中のアイテムへ変更を反映
+    pub fn update_pending_item(&mut self, item_id: &str, changes: &UploadItemChanges) -> Result<UploadItem, InternalError> {
+        self.check_pending_update(item_id, changes)?;
+        let item = self.items.iter_mut()
+            .find(|i| i.id == item_id)
+            .ok_or_else(|| item_not_found(item_id))?;
+        if let Some(s3_key) = &changes.s3_key {
+            item.s3_key = s3_key.clone();
+        }
+        if let Some(priority) = changes.priority {
+            item.priority = priority;
+        }
+        if changes.note.is_some() {
+            item.note = normalize_note(changes.note.clone());
+        }
+        Ok(item.clone())
+    }
+
+    /// 開始する待機中アイテムのID（優先度の高い順、同じ優先度は追加順）
+    ///
+    /// 使用中で保留しているアイテムは再確認の時刻まで対象外
+    fn next_pending_ids(&self, limit: usize) -> Vec<String> {
+        let now = Instant::now();
+        let mut pending: Vec<&UploadItem> = self.items.iter()
+            .filter(|item| item.status == UploadStatus::Pending)
+            .filter(|item| !matches!(self.file_in_use_holds.get(&item.id), Some(hold) if !hold.is_due(now)))
+            .collect();
+        pending.sort_by_key(|item| std::cmp::Reverse(item.priority));
+        pending.into_iter().take(limit).map(|item| item.id.clone()).collect()
+    }
+    
+    /// 他のアプリが書き込み中のアイテムを待機中のまま保留する
+    ///
+    /// 解放を待つ上限を過ぎた場合は理由付きでFailedにしてtrueを返す
+    pub fn hold_in_use_item(&mut self, item_id: &str, now: Instant) -> bool {
+        let timed_out = {
+            let hold = self.file_in_use_holds.entry(item_id.to_string()).or_insert_with(|| FileInUseHold::new(now));
+            hold.defer(now);
+            hold.is_timed_out(now)
+        };
+        if !timed_out {
+            return false;
+        }
+        self.file_in_use_holds.remove(item_id);
+        // 開始前のアイテムのため、完了処理を通さずに失敗させる
+        if let Some(item) = self.items.iter_mut().find(|i| i.id == item_id) {
+            item.status = UploadStatus::Failed;
+            item.error_message = Some(format!(
+                "File is still being written by another application after {} minutes",
+                FILE_IN_USE_TIMEOUT.as_secs() / 60
+            ));
+        }
+        true
+    }
+
+    /// 待機中でなくなったアイテムの保留を解除する
+    fn prune_in_use_holds(&mut self) {
+        let pending: HashSet<&str> = self.items.iter()
+            .filter(|item| item.status == UploadStatus::Pending)
+            .map(|item| item.id.as_str())
+            .collect();
+        self.file_in_use_holds.retain(|item_id, _| pending.contains(item_id.as_str()));
+    }
+    
+    /// 元ファイルの変更で失敗したアイテムを変更後のサイズで待機中に戻す
+    ///
+    /// 安定化待ちの間に削除・再試行されたアイテムは対象外
+    pub fn requeue_modified_item(&mut self, item_id: &str, file_size: u64) -> bool {
+        let Some(item) = self.items.iter_mut().find(|i| i.id == item_id) else {
+            return false;
+        };
+        let failed_by_change = item.error_message.as_deref().map(is_source_modified_error).unwrap_or(false);
+        if item.status != UploadStatus::Failed || !failed_by_change {
+            return false;
+        }
+        item.status = UploadStatus::Pending;
+        item.file_size = file_size;
+        item.progress = 0.0;
+        item.uploaded_bytes = 0;
+        item.error_message = None;
+        item.retry_count += 1;
+        MetricsRegistry::increment(&METRICS.upload_retries);
+        true
+    }
+    
+    /// 複数のアイテムを待機中に戻す（進行中のアイテムは対象外）
+    pub fn retry_items(&mut self, item_ids: &[String]) -> Vec<BatchItemResult> {
+        unique_ids(item_ids).into_iter().map(|item_id| {
+            match self.items.iter_mut().find(|i| i.id == item_id) {
+                None => BatchItemResult::failed(item_id, BatchItemOutcome::NotFound),
+                Some(item) if item.status == UploadStatus::InProgress => {
+                    BatchItemResult::failed(item_id, BatchItemOutcome::InProgress)
+                }
+                Some(item) => {
+                    item.status = UploadStatus::Pending;
+                    item.progress = 0.0;
+                    item.uploaded_bytes = 0;
+                    item.error_message = None;
+                    item.mirrors.clear();
+                    item.retry_count += 1;
+                    MetricsRegistry::increment(&METRICS.upload_retries);
+                    BatchItemResult::succeeded(item_id, BatchItemOutcome::Queued)
+                }
+            }
+        }).collect()
+    }
+    
+    /// 待機中・一時停止中のアイテムをアップロードせずにSkippedにする（`reasons`は(アイテムID, 理由)）
+    pub fn skip_items(&mut self, reasons: &[(String, String)]) -> Vec<BatchItemResult> {
+        let mut seen = HashSet::new();
+        let now = chrono::Utc::now().to_rfc3339();
+        reasons.iter().filter(|(item_id, _)| seen.insert(item_id.as_str())).map(|(item_id, reason)| {
+            match self.items.iter_mut().find(|i| &i.id == item_id) {
+                None => BatchItemResult::failed(item_id, BatchItemOutcome::NotFound),
+                Some(item) if !matches!(item.status, UploadStatus::Pending | UploadStatus::Paused) => {
+                    BatchItemResult::failed(item_id, BatchItemOutcome::NotPending)
+                }
+                Some(item) => {
+                    item.status = UploadStatus::Skipped;
+                    item.error_message = Some(reason.clone());
+                    item.completed_at = Some(now.clone());
+                    BatchItemResult::succeeded(item_id, BatchItemOutcome::Skipped)
+                }
+            }
+        }).collect()
+    }
+    
+    /// 進行中のアイテムを待機中へ戻す（ウォッチドッグによる復旧用。retry_countは消費しない）
+    fn requeue_in_progress_item(&mut self, item_id: &str) {
+        self.active_uploads.remove(item_id);
+        self.discard_multipart_upload(item_id);
+        if let Some(item) = self.items.iter_mut().find(|i| i.id == item_id) {
+            item.status = UploadStatus::Pending;
+            item.progress = 0.0;
+            item.uploaded_bytes = 0;
+            item.speed_mbps = 0.0;
+            item.eta_seconds = None;
+            item.started_at = None;
+        }
+    }
+    
+    /// active_uploads・実行中のタスクとitemsを突き合わせ、アクティブ数のずれを修正する
+    ///
+    /// タスクが終了しているのに進行中のまま残ったアイテムは待機中へ戻す
+    pub fn reconcile_active_state(&mut self) -> QueueReconcileReport {
+        let previous_count = self.active_upload_count;
+        let orphaned: Vec<String> = self.items.iter()
+            .filter(|item| item.status == UploadStatus::InProgress)
+            .filter(|item| self.upload_tasks.get(&item.id).map(|task| task.is_finished()).unwrap_or(true))
+            .map(|item| item.id.clone())
+            .collect();
+        for item_id in &orphaned {
+            self.upload_tasks.remove(item_id);
+            self.requeue_in_progress_item(item_id);
+        }
+        
+        let in_progress: HashSet<String> = self.items.iter()
+            .filter(|item| item.status == UploadStatus::InProgress)
+            .map(|item| item.id.clone())
+            .collect();
+        let stale_progress = self.active_uploads.len();
+        self.active_uploads.retain(|item_id, _| in_progress.contains(item_id));
+        self.active_upload_count = in_progress.len();
+        
+        QueueReconcileReport {
+            previous_count,
+            corrected_count: self.active_upload_count,
+            stale_progress: stale_progress - self.active_uploads.len(),
+            requeued_items: orphaned,
+        }
+    }
+    
+    /// キュー処理の再起動に備えて進行中のアップロードを中断し、待機中へ戻す
+    ///
+    /// 中断したマルチパートアップロードを返すため、呼び出し元はロックを外してから破棄すること
+    pub fn reset_for_restart(&mut self) -> (usize, Vec<ActiveMultipartUpload>) {
+        let in_progress_ids: Vec<String> = self.items.iter()
+            .filter(|item| item.status == UploadStatus::InProgress)
+            .map(|item| item.id.clone())
+            .collect();
+        let mut aborted_uploads = Vec::new();
+        for item_id in &in_progress_ids {
+            if let Some(handle) = self.upload_tasks.remove(item_id) {
+                handle.abort();
+            }
+            aborted_uploads.extend(take_multipart_upload(item_id));
+            self.requeue_in_progress_item(item_id);
+        }
+        self.active_uploads.clear();
+        self.active_upload_count = 0;
+        (in_progress_ids.len(), aborted_uploads)
+    }
+    
+    /// 停滞をエラー履歴に記録する（古いものから捨てる）
+    pub fn record_stall(&mut self, event: UploadStalledEvent) {
+        if self.stall_history.len() >= MAX_STALL_HISTORY {
+            self.stall_history.remove(0);
+        }
+        self.stall_history.push(event);
+    }
+}
+
+/// 重複を除いたID（指定順を保つ）
+fn unique_ids(item_ids: &[String]) -> Vec<&str> {
+    let mut seen = HashSet::new();
+    item_ids.iter().map(String::as_str).filter(|id| seen.insert(*id)).collect()
+}
+
+/// キューのバッチ操作の結果（アイテムごと）
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchItemOutcome {
+    Removed,
+    /// 進行中だったためアップロードを中断して削除した
+    RemovedWithAbort,
+    /// 待機中に戻した
+    Queued,
+    NotFound,
+    /// 進行中のためリトライできない
+    InProgress,
+    /// アップロードせずに除外した
+    Skipped,
+    /// 開始済み・終了済みのため除外できない
+    NotPending,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BatchItemResult {
+    pub item_id: String,
+    pub success: bool,
+    pub outcome: BatchItemOutcome,
+}
+
+impl BatchItemResult {
+    fn succeeded(item_id: &str, outcome: BatchItemOutcome) -> Self {
+        Self { item_id: item_id.to_string(), success: true, outcome }
+    }
+
+    fn failed(item_id: &str, outcome: BatchItemOutcome) -> Self {
+        Self { item_id: item_id.to_string(), success: false, outcome }
+    }
+}
+
+/// remove_upload_items・retry_upload_items・exclude_queue_duplicatesの結果
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchOperationResult {
+    pub results: Vec<BatchItemResult>,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+impl BatchOperationResult {
+    pub(crate) fn new(results: Vec<BatchItemResult>) -> Self {
+        let succeeded = results.iter().filter(|result| result.success).count();
+        Self { failed: results.len() - succeeded, succeeded, results }
+    }
+}
+
+/// キューの内容がまとめて変わったことを通知するイベント名
+pub const UPLOAD_QUEUE_CHANGED_EVENT: &str = "upload-queue-changed";
+
+/// upload-queue-changedイベントのペイロード
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadQueueChanged {
+    /// 変更の種類（"removed"・"retried"・"updated"・"mirrored"・"skipped"）
+    pub reason: &'static str,
+    pub item_ids: Vec<String>,
 }
 
 pub type UploadQueueState = Arc<Mutex<UploadQueue>>;
 
+pub(crate) fn queue_lock_error(e: impl std::fmt::Display) -> InternalError {
+    InternalError::localized(ErrorCode::Unknown, LocalizedMessage::new(MessageKey::UploadQueueLockFailed).param("error", e))
+}
+
+pub(crate) fn item_not_found(item_id: &str) -> InternalError {
+    InternalError::localized(ErrorCode::Unknown, LocalizedMessage::new(MessageKey::UploadItemNotFound).param("item_id", item_id))
+}
+
+/// 終了時に永続化するキュー状態のファイル名（app_data_dir直下）
+pub(crate) const QUEUE_STATE_FILE: &str = "upload_queue_state.json";
+
+/// 進行中のマルチパートアップロード（終了時に未完了パーツを破棄するため）
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveMultipartUpload {
+    pub bucket: String,
+    pub key: String,
+    pub upload_id: String,
+}
+
+lazy_static::lazy_static! {
+    static ref ACTIVE_MULTIPART_UPLOADS: Mutex<HashMap<String, ActiveMultipartUpload>> =
+        Mutex::new(HashMap::new());
+}
+
+fn register_multipart_upload(item_id: &str, upload: ActiveMultipartUpload) {
+    if let Ok(mut uploads) = ACTIVE_MULTIPART_UPLOADS.lock() {
+        uploads.insert(item_id.to_string(), upload);
+    }
+}
+
+fn unregister_multipart_upload(item_id: &str) {
+    if let Ok(mut uploads) = ACTIVE_MULTIPART_UPLOADS.lock() {
+        uploads.remove(item_id);
+    }
+}
+
+/// 登録を外して返す（未完了パーツを呼び出し元で破棄する）
+fn take_multipart_upload(item_id: &str) -> Option<ActiveMultipartUpload> {
+    ACTIVE_MULTIPART_UPLOADS.lock().ok().and_then(|mut uploads| uploads.remove(item_id))
+}
+
 /// アップロード統計
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UploadStatistics {
@@ -252,6 +1082,32 @@ pub struct UploadStatistics {
     pub uploaded_bytes: u64,
     pub average_speed_mbps: f64,
     pub estimated_time_remaining: Option<u64>,
+    pub is_paused: bool,
+    /// 事前圧縮によって削減した転送量
+    pub compression_saved_bytes: u64,
+    /// モックAWSモードで実行中（実際にはS3へアップロードされない）
+    pub mock_aws_mode: bool,
+    /// 現在適用中の帯域制限（未初期化のキューではNone）
+    pub bandwidth_profile: Option<BandwidthProfile>,
+    /// プライマリは完了したがミラーが失敗しているファイル数（completed_filesには含めない）
+    #[serde(default)]
+    pub warning_files: u64,
+    /// ミラー先へのアップロードの成功・失敗件数（ミラー先ごとに数える）
+    #[serde(default)]
+    pub mirror_completed: u64,
+    #[serde(default)]
+    pub mirror_failed: u64,
+    /// 現在のティアの制限と残量
+    pub limits: UploadLimitInfo,
+    /// ライフサイクル未設定・認証期限接近・ディスク残量低下などの警告
+    #[serde(default)]
+    pub warnings: Vec<UploadStatusWarning>,
+}
+
+/// キュー全体の一時停止状態（upload-queue-pause-changedイベントのペイロード）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadQueuePauseState {
+    pub is_paused: bool,
 }
 
 /// ファイル選択ダイアログの結果
@@ -271,14 +1127,41 @@ pub struct S3KeyConfig {
     pub custom_naming_pattern: Option<String>,
 }
 
+impl S3KeyConfig {
+    /// アップロード画面と同じ既定のキー設定（キュー設定のプレフィックス＋日付フォルダ）
+    pub(crate) fn from_queue(queue_state: &UploadQueueState) -> Result<Self, InternalError> {
+        let queue = queue_state.lock().map_err(queue_lock_error)?;
+        let config = queue.config.as_ref()
+            .ok_or_else(|| InternalError::localized(ErrorCode::Config, MessageKey::UploadQueueNotInitialized))?;
+        Ok(Self {
+            prefix: config.s3_key_prefix.clone(),
+            use_date_folder: true,
+            preserve_directory_structure: false,
+            custom_naming_pattern: None,
+        })
+    }
+}
+
 /// アップロードキューを初期化
 #[command]
 pub async fn initialize_upload_queue(
-    config: UploadConfig,
+    app_handle: AppHandle,
+    mut config: UploadConfig,
     queue_state: State<'_, UploadQueueState>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
+    // キューにはリージョンだけを保持し、認証情報はS3クライアントの作成時にキャッシュから取得する
+    let mut credentials = cached_credentials_in_region(&app_handle, "")?;
+    correct_credentials_region(&app_handle, &mut credentials, &config.bucket_name).await;
+    config.region = credentials.region;
+
+    // ティアはフロントの指定ではなく検証済みライセンスから決める
+    let adjusted = current_policy(&app_handle).apply(&mut config);
+    if !adjusted.is_empty() {
+        tracing::info!("Upload config adjusted for {:?} tier: {}", config.tier, adjusted.join(", "));
+    }
+
     let mut queue = queue_state.lock()
-        .map_err(|e| standardize_error(InternalError::Other(format!("Failed to lock upload queue: {}", e))))?;
+        .map_err(|e| AppError::from(queue_lock_error(e)))?;
     
     queue.config = Some(config);
     queue.is_processing = false;
@@ -288,8 +1171,15 @@ pub async fn initialize_upload_queue(
     queue.total_files_uploaded = 0;
     queue.active_upload_count = 0;
     
-    log::info!("Upload queue initialized with configuration");
-    Ok("Upload queue initialized successfully".to_string())
+    // 前回終了時に中断したアップロードがあればキューに戻す
+    let restored_items = take_persisted_queue_items(&app_handle);
+    if !restored_items.is_empty() {
+        tracing::info!("Restored {} interrupted upload(s) from previous session", restored_items.len());
+        queue.items = restored_items;
+    }
+    
+    tracing::info!("Upload queue initialized with configuration");
+    Ok(tr(MessageKey::UploadQueueInitialized))
 }
 
 /// ファイル選択ダイアログを開く
@@ -298,7 +1188,7 @@ pub async fn open_file_dialog(
     _app_handle: AppHandle,
     multiple: bool,
     _file_types: Option<Vec<String>>,
-) -> Result<FileSelection, String> {
+) -> Result<FileSelection, AppError> {
     let files = if multiple {
         rfd::FileDialog::new()
             .set_title("Select files to upload")
@@ -326,92 +1216,327 @@ pub async fn open_file_dialog(
                 file_count: selected_files.len() as u32,
             })
         }
-        None => Err(standardize_error(InternalError::Other("No files selected".to_string())))
+        None => Err(AppError::localized(ErrorCode::Unknown, MessageKey::UploadNoFilesSelected))
+    }
+}
+
+/// add_files_to_upload_queueで一度に処理する件数（進捗の通知・キューのロックはバッチ単位）
+const QUEUE_ADD_BATCH_SIZE: usize = 500;
+/// 追加できなかったファイルをメッセージに列挙する上限
+const QUEUE_ADD_FAILURES_SHOWN: usize = 10;
+/// アイテム作成の進捗を通知するイベント
+pub const QUEUE_ADD_PROGRESS_EVENT: &str = "queue-add-progress";
+
+/// queue-add-progressイベントのペイロード（operation_idをcancel_operationへ渡すと中断できる）
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueAddProgress {
+    pub operation_id: String,
+    pub processed: usize,
+    pub total: usize,
+    pub failed: usize,
+}
+
+/// キューへ追加できなかったファイル
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct QueueAddFailure {
+    pub file_path: String,
+    pub error: String,
+}
+
+/// バッチ内のファイルからアイテムを作成（失敗したファイルはスキップして理由を返す）
+///
+/// `first_index`は連番（{n}）に使うファイルリスト全体での位置
+pub(crate) fn prepare_queue_batch(
+    file_paths: &[String],
+    first_index: usize,
+    s3_key_config: &S3KeyConfig,
+    metadata_db: Option<&MetadataDatabase>,
+) -> (Vec<UploadItem>, Vec<QueueAddFailure>) {
+    let mut items = Vec::with_capacity(file_paths.len());
+    let mut failures = Vec::new();
+    for (offset, file_path) in file_paths.iter().enumerate() {
+        let item = (|| {
+            if !Path::new(file_path).exists() {
+                return Err(InternalError::File(format!("File not found: {}", file_path)));
+            }
+            // {hash8}はメタデータ作成済みのハッシュを優先する
+            let known_hash = metadata_db
+                .and_then(|db| db.get_metadata_by_path(file_path).ok())
+                .map(|metadata| metadata.file_hash);
+            let context = NamingContext::new(first_index + offset + 1).with_hash(known_hash);
+            let s3_key = generate_s3_key_with_context(file_path, s3_key_config, &context)?;
+            new_upload_item(file_path, s3_key)
+        })();
+        match item {
+            Ok(item) => items.push(item),
+            Err(e) => failures.push(QueueAddFailure { file_path: file_path.clone(), error: e.to_string() }),
+        }
     }
+    (items, failures)
+}
+
+/// 生成したキーがアーカイブ移行ルールの対象か確認（認証情報がロック中の場合は確認しない）
+async fn check_queue_lifecycle_coverage(
+    app_handle: &AppHandle,
+    config: &UploadConfig,
+    s3_keys: &[String],
+) -> Option<LifecyclePrefixWarning> {
+    let credentials = cached_credentials_in_region(app_handle, &config.region).ok()?;
+    check_lifecycle_prefix_coverage(&credentials, &config.bucket_name, s3_keys).await
 }
 
 /// ファイルをアップロードキューに追加
+///
+/// 大量のファイルはバッチに分けて処理し、バッチごとにqueue-add-progressで進捗を通知する。
+/// 追加できなかったファイルはスキップして最後にまとめて報告し、cancel_operationで全体を中断できる
 #[command]
 pub async fn add_files_to_upload_queue(
+    app_handle: AppHandle,
     file_paths: Vec<String>,
-    s3_key_config: S3KeyConfig,
+    mut s3_key_config: S3KeyConfig,
+    bundle_options: Option<BundleOptions>,
+    note: Option<String>,
+    project_id: Option<String>,
     queue_state: State<'_, UploadQueueState>,
-) -> Result<String, String> {
-    let mut queue = queue_state.lock()
-        .map_err(|e| standardize_error(InternalError::Other(format!("Failed to lock upload queue: {}", e))))?;
+) -> Result<String, AppError> {
+    let note = normalize_note(note);
+    let project_id = normalize_project_id(project_id)?;
+    // シンボリックリンクは設定に従って実体へ置き換えるかスキップし、同じ実体は1件にまとめる
+    let follow_symlinks = queue_state.lock()
+        .map_err(|e| AppError::from(queue_lock_error(e)))?
+        .config.as_ref()
+        .map(|config| config.follow_symlinks)
+        .unwrap_or(true);
+    let ResolvedPaths { files: file_paths, skipped } = resolve_queue_paths(file_paths, follow_symlinks)?;
+    // 登録済みプロジェクトの既定プレフィックスをキーの先頭に付ける
+    if let Some(project_prefix) = project_id.as_deref().and_then(|id| project_s3_prefix(&app_handle, id)) {
+        apply_project_prefix(&mut s3_key_config, &project_prefix);
+    }
+    // 小ファイルをtarにまとめる場合は、キューをロックする前にアーカイブを作成
+    let (file_paths, bundle) = match &bundle_options {
+        Some(options) => {
+            let staging_dir = bundle_staging_dir(&app_handle)?;
+            let staged = stage_bundle_async(file_paths, options.clone(), staging_dir).await?;
+            (staged.individual_files, staged.archive)
+        }
+        None => (file_paths, None),
+    };
     
-    // 無料版の制限チェック
-    queue.check_free_tier_limits(file_paths.len())
-        .map_err(|e| standardize_error(e))?;
+    // ライフサイクル確認でS3へ問い合わせるため、ロックは制限チェックと追加時のみ取得する
+    let item_count = file_paths.len() + if bundle.is_some() { 2 } else { 0 };
+    let config = {
+        let queue = queue_state.lock()
+            .map_err(|e| AppError::from(queue_lock_error(e)))?;
+        
+        // ティアの制限チェック（バンドルはアーカイブと目録の2アイテム）
+        queue.check_tier_limits(item_count)
+            .map_err(|e| AppError::from(e))?;
+        
+        queue.config.clone()
+            .ok_or_else(|| AppError::from(InternalError::Config("Upload configuration not initialized".to_string())))?
+    };
     
-    let _config = queue.config.as_ref()
-        .ok_or_else(|| standardize_error(InternalError::Config("Upload configuration not initialized".to_string())))?;
+    // 命名パターンの誤りはファイルごとではなく追加前にまとめて検出する
+    let naming_pattern = s3_key_config.custom_naming_pattern.as_deref();
+    if let Some(pattern) = naming_pattern {
+        validate_naming_pattern(pattern)?;
+    }
+    // {hash8}はメタデータ作成済みのハッシュを優先する
+    let metadata_db = naming_pattern
+        .filter(|pattern| pattern_uses_hash(pattern))
+        .and_then(|_| metadata_db_path(&app_handle).ok())
+        .and_then(|db_path| MetadataDatabase::new(&db_path.to_string_lossy()).ok());
     
-    for file_path in &file_paths {
-        // ファイルの存在確認
-        if !Path::new(&file_path).exists() {
-            return Err(standardize_error(InternalError::File(format!("File not found: {}", file_path))));
+    let operation = begin_operation(&app_handle, OperationKind::QueueAdd, format!("{} file(s)", file_paths.len()));
+    let mut new_items = Vec::with_capacity(item_count);
+    let mut failures = Vec::new();
+    for (batch_index, batch) in file_paths.chunks(QUEUE_ADD_BATCH_SIZE).enumerate() {
+        operation.token().check()?;
+        let (items, failed) = prepare_queue_batch(batch, batch_index * QUEUE_ADD_BATCH_SIZE, &s3_key_config, metadata_db.as_ref());
+        new_items.extend(items);
+        failures.extend(failed);
+        let progress = QueueAddProgress {
+            operation_id: operation.operation_id().to_string(),
+            processed: (batch_index * QUEUE_ADD_BATCH_SIZE + batch.len()).min(file_paths.len()),
+            total: file_paths.len(),
+            failed: failures.len(),
+        };
+        if let Err(e) = app_handle.emit(QUEUE_ADD_PROGRESS_EVENT, &progress) {
+            tracing::warn!("Failed to emit {}: {}", QUEUE_ADD_PROGRESS_EVENT, e);
         }
+        // バッチの合間に他のタスク（進捗更新・UIからのコマンド）を進める
+        tokio::task::yield_now().await;
+    }
+    drop(operation);
+    for failure in &failures {
+        tracing::warn!("Skipped {}: {}", failure.file_path, failure.error);
+    }
+    if new_items.is_empty() && bundle.is_none() {
+        if let Some(failure) = failures.first() {
+            return Err(AppError::from(InternalError::File(failure.error.clone())));
+        }
+    }
+    
+    if let (Some(archive), Some(options)) = (bundle, &bundle_options) {
+        let bundle_key = generate_s3_key(&archive.virtual_path.to_string_lossy(), &s3_key_config)?;
+        let manifest_path = archive.finalize(&bundle_key, &options.db_path)?;
         
-        // S3キーを生成
-        let s3_key = generate_s3_key(&file_path, &s3_key_config)
-            .map_err(|e| standardize_error(InternalError::Other(e.to_string())))?;
-        
-        // ファイル情報を取得
-        let metadata = std::fs::metadata(&file_path)
-            .map_err(|e| standardize_error(InternalError::File(format!("Failed to get file metadata: {}", e))))?;
-        
-        let file_name = Path::new(&file_path)
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown")
-            .to_string();
+        let mut bundle_item = new_upload_item(&archive.archive_path.to_string_lossy(), bundle_key.clone())?;
+        bundle_item.file_name = bundle_key.rsplit('/').next().unwrap_or(&bundle_key).to_string();
+        let manifest_key = format!("{}{}", bundle_key, MANIFEST_SUFFIX);
+        let mut manifest_item = new_upload_item(&manifest_path.to_string_lossy(), manifest_key.clone())?;
+        manifest_item.file_name = manifest_key.rsplit('/').next().unwrap_or(&manifest_key).to_string();
         
-        // アップロードアイテムを作成
-        let item = UploadItem {
-            id: Uuid::new_v4().to_string(),
-            file_path: file_path.clone(),
-            file_name,
-            file_size: metadata.len(),
-            s3_key,
-            status: UploadStatus::Pending,
-            progress: 0.0,
-            uploaded_bytes: 0,
-            speed_mbps: 0.0,
-            eta_seconds: None,
-            created_at: chrono::Utc::now().to_rfc3339(),
-            started_at: None,
-            completed_at: None,
-            error_message: None,
-            retry_count: 0,
-        };
-        
-        queue.items.push(item);
+        tracing::info!("Queued bundle {} with {} file(s)", bundle_key, archive.entries.len());
+        new_items.push(bundle_item);
+        new_items.push(manifest_item);
+    }
+    
+    // 生成したキーがアーカイブ移行ルールの対象か確認（対象外はSTANDARDのまま残る）
+    let s3_keys: Vec<String> = new_items.iter().map(|item| item.s3_key.clone()).collect();
+    let lifecycle_warning = check_queue_lifecycle_coverage(&app_handle, &config, &s3_keys).await;
+    if let Some(warning) = &lifecycle_warning {
+        if config.block_uncovered_lifecycle_prefix {
+            tracing::warn!("Rejected {} upload(s) outside lifecycle rules", warning.uncovered_count);
+            for item in &new_items {
+                discard_queued_bundle(&item.file_path);
+            }
+            return Err(AppError::from(InternalError::Config(warning.message.clone())));
+        }
+        tracing::warn!("{}", warning.message);
+        if let Err(e) = app_handle.emit(LIFECYCLE_PREFIX_WARNING_EVENT, warning) {
+            tracing::warn!("Failed to emit lifecycle prefix warning: {}", e);
+        }
     }
     
-    log::info!("Added {} files to upload queue", file_paths.len());
-    Ok(format!("Added {} files to upload queue", file_paths.len()))
+    for item in &mut new_items {
+        item.note = note.clone();
+        item.project_id = project_id.clone();
+        apply_type_override(item, &config);
+    }
+    
+    // 他のアプリが書き込み中のファイル（書き出し中・コピー途中）は待機中のまま保留する
+    let in_use_paths = check_files_in_use(new_items.iter().map(|item| PathBuf::from(&item.file_path)).collect()).await;
+    let in_use_items: Vec<(String, String)> = new_items.iter()
+        .filter(|item| in_use_paths.contains(Path::new(&item.file_path)))
+        .map(|item| (item.id.clone(), item.file_path.clone()))
+        .collect();
+    
+    let item_count = new_items.len();
+    let mut pending = new_items.into_iter().peekable();
+    while pending.peek().is_some() {
+        let mut queue = queue_state.lock()
+            .map_err(|e| AppError::from(queue_lock_error(e)))?;
+        queue.items.extend(pending.by_ref().take(QUEUE_ADD_BATCH_SIZE));
+    }
+    if !in_use_items.is_empty() {
+        let mut queue = queue_state.lock()
+            .map_err(|e| AppError::from(queue_lock_error(e)))?;
+        let now = Instant::now();
+        for (item_id, file_path) in &in_use_items {
+            tracing::info!("Holding {} until another application finishes writing it", file_path);
+            queue.file_in_use_holds.insert(item_id.clone(), FileInUseHold::new(now));
+        }
+    }
+    crate::badge::update_badge(&app_handle, queue_state.inner());
+    
+    tracing::info!("Added {} items to upload queue ({} failed)", item_count, failures.len());
+    let mut message = match lifecycle_warning {
+        Some(warning) => LocalizedMessage::new(MessageKey::UploadFilesAddedWithWarning)
+            .param("count", item_count)
+            .param("warning", warning.message)
+            .render(),
+        None => LocalizedMessage::new(MessageKey::UploadFilesAdded).param("count", item_count).render(),
+    };
+    if !skipped.is_empty() {
+        let paths: Vec<&str> = skipped.iter().map(|skipped| skipped.path.as_str()).collect();
+        message.push_str(&LocalizedMessage::new(MessageKey::UploadPathsSkipped)
+            .param("count", skipped.len())
+            .param("paths", paths.join(", "))
+            .render());
+    }
+    if !in_use_items.is_empty() {
+        let mut paths: Vec<&str> = in_use_items.iter().take(QUEUE_ADD_FAILURES_SHOWN).map(|(_, path)| path.as_str()).collect();
+        if in_use_items.len() > QUEUE_ADD_FAILURES_SHOWN {
+            paths.push("...");
+        }
+        message.push_str(&LocalizedMessage::new(MessageKey::UploadFilesInUse)
+            .param("count", in_use_items.len())
+            .param("paths", paths.join(", "))
+            .render());
+    }
+    if !failures.is_empty() {
+        let mut paths: Vec<&str> = failures.iter().take(QUEUE_ADD_FAILURES_SHOWN).map(|failure| failure.file_path.as_str()).collect();
+        if failures.len() > QUEUE_ADD_FAILURES_SHOWN {
+            paths.push("...");
+        }
+        message.push_str(&LocalizedMessage::new(MessageKey::UploadFilesFailedToAdd)
+            .param("count", failures.len())
+            .param("paths", paths.join(", "))
+            .render());
+    }
+    Ok(message)
+}
+
+/// ファイル情報から待機中のアップロードアイテムを作成
+pub(crate) fn new_upload_item(file_path: &str, s3_key: String) -> Result<UploadItem, InternalError> {
+    let metadata = std::fs::metadata(file_path)
+        .map_err(|e| InternalError::File(format!("Failed to get file metadata: {}", e)))?;
+    
+    let file_name = Path::new(file_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown")
+        .to_string();
+    
+    Ok(UploadItem {
+        id: Uuid::new_v4().to_string(),
+        file_path: file_path.to_string(),
+        file_name,
+        file_size: metadata.len(),
+        s3_key,
+        status: UploadStatus::Pending,
+        progress: 0.0,
+        uploaded_bytes: 0,
+        speed_mbps: 0.0,
+        eta_seconds: None,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        started_at: None,
+        completed_at: None,
+        error_message: None,
+        retry_count: 0,
+        note: None,
+        project_id: None,
+        priority: 0,
+        mirrors: Vec::new(),
+        applied_override: None,
+    })
 }
 
 /// アップロードアイテムを削除
 #[command]
 pub async fn remove_upload_item(
+    app_handle: AppHandle,
     item_id: String,
     queue_state: State<'_, UploadQueueState>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     let mut queue = queue_state.lock()
-        .map_err(|e| standardize_error(InternalError::Other(format!("Failed to lock upload queue: {}", e))))?;
+        .map_err(|e| AppError::from(queue_lock_error(e)))?;
     
     let initial_count = queue.items.len();
+    discard_removed_bundle_files(queue.items.iter().filter(|item| item.id == item_id));
     queue.items.retain(|item| item.id != item_id);
     queue.active_uploads.remove(&item_id);
     
     let removed_count = initial_count - queue.items.len();
+    drop(queue);
+    crate::badge::update_badge(&app_handle, queue_state.inner());
+    
     if removed_count > 0 {
-        log::info!("Removed upload item: {}", item_id);
-        Ok(format!("Removed {} upload item(s)", removed_count))
+        tracing::info!("Removed upload item: {}", item_id);
+        Ok(LocalizedMessage::new(MessageKey::UploadItemsRemoved).param("count", removed_count).render())
     } else {
-        Err(standardize_error(InternalError::Other(format!("Upload item not found: {}", item_id))))
+        Err(AppError::from(item_not_found(&item_id)))
     }
 }
 
@@ -420,41 +1545,80 @@ pub async fn remove_upload_item(
 pub async fn start_upload_processing(
     app_handle: AppHandle,
     queue_state: State<'_, UploadQueueState>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
+    // 安全確認はキャッシュが古い場合のみ再実行する（悪化時はupload-readiness-changedで通知）
+    let credentials = cached_credentials(&app_handle)?;
+    let bucket = {
+        let queue = queue_state.lock().map_err(queue_lock_error)?;
+        queue.config.as_ref().map(|config| config.bucket_name.clone())
+    };
+    if let Some(bucket) = bucket {
+        let readiness = refresh_upload_readiness(&app_handle, &credentials, &bucket, false).await;
+        if !readiness.safe {
+            tracing::warn!("Starting uploads although readiness check failed: {}", readiness.message);
+        }
+    }
+
+    if !start_queue_processing(&app_handle, queue_state.inner())? {
+        return Err(AppError::localized(ErrorCode::Unknown, MessageKey::UploadAlreadyRunning));
+    }
+    
+    Ok(tr(MessageKey::UploadProcessingStarted))
+}
+
+/// バックグラウンドでキュー処理を開始（既に実行中の場合はfalse）
+pub(crate) fn start_queue_processing(
+    app_handle: &AppHandle,
+    queue_state: &UploadQueueState,
+) -> Result<bool, InternalError> {
     let mut queue = queue_state.lock()
-        .map_err(|e| standardize_error(InternalError::Other(format!("Failed to lock upload queue: {}", e))))?;
+        .map_err(queue_lock_error)?;
     
     if queue.is_processing {
-        return Err(standardize_error(InternalError::Other("Upload processing is already running".to_string())));
+        return Ok(false);
     }
     
-    let config = queue.config.as_ref()
-        .ok_or_else(|| standardize_error(InternalError::Config("Upload configuration not initialized".to_string())))?
-        .clone();
+    let config = queue.config.clone()
+        .ok_or_else(|| InternalError::Config("Upload configuration not initialized".to_string()))?;
+    // ロック中・期限切れの場合は開始しない
+    cached_credentials_in_region(app_handle, &config.region)?;
     
     queue.is_processing = true;
     drop(queue); // ロックを解放
     
     // バックグラウンドでアップロード処理を開始
-    let queue_state_clone = queue_state.inner().clone();
-    let config_clone = config.clone();
+    let queue_state_clone = queue_state.clone();
+    let app_handle = app_handle.clone();
     
-    tokio::spawn(async move {
-        if let Err(e) = process_upload_queue(queue_state_clone, app_handle, config_clone).await {
-            log::error!("Upload processing failed: {}", e);
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = process_upload_queue(queue_state_clone, app_handle, config).await {
+            tracing::error!("Upload processing failed: {}", e);
         }
     });
     
-    Ok("Upload processing started".to_string())
+    Ok(true)
 }
 
 /// アップロード処理を停止
 #[command]
 pub async fn stop_upload_processing(
     queue_state: State<'_, UploadQueueState>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
+    stop_queue_processing(queue_state.inner())?;
+    Ok(tr(MessageKey::UploadProcessingStopped))
+}
+
+/// キューから外すアイテムのうち、アップロードが終わっていないバンドルのステージングファイルを削除
+fn discard_removed_bundle_files<'a>(items: impl Iterator<Item = &'a UploadItem>) {
+    for item in items.filter(|i| !i.status.is_primary_completed()) {
+        discard_queued_bundle(&item.file_path);
+    }
+}
+
+/// キュー処理を停止し、進行中のアップロードをキャンセル扱いにする
+pub(crate) fn stop_queue_processing(queue_state: &UploadQueueState) -> Result<(), InternalError> {
     let mut queue = queue_state.lock()
-        .map_err(|e| standardize_error(InternalError::Other(format!("Failed to lock upload queue: {}", e))))?;
+        .map_err(queue_lock_error)?;
     
     queue.is_processing = false;
     
@@ -462,23 +1626,67 @@ pub async fn stop_upload_processing(
     for item in queue.items.iter_mut() {
         if item.status == UploadStatus::InProgress {
             item.status = UploadStatus::Cancelled;
+            discard_queued_bundle(&item.file_path);
         }
     }
     
     queue.active_uploads.clear();
     queue.active_upload_count = 0;
     
-    log::info!("Upload processing stopped");
-    Ok("Upload processing stopped".to_string())
+    tracing::info!("Upload processing stopped");
+    Ok(())
 }
 
-/// アップロードキューの状態を取得
+/// キュー全体の一時停止状態を変更し、フロントエンドとトレイへ反映
+/// フロントからの操作とトレイからの操作は必ずこの関数を経由させる
+pub fn set_queue_paused(
+    app_handle: &AppHandle,
+    queue_state: &UploadQueueState,
+    paused: bool,
+) -> Result<(), InternalError> {
+    {
+        let mut queue = queue_state.lock()
+            .map_err(queue_lock_error)?;
+        queue.is_paused = paused;
+    }
+    
+    tracing::info!("Upload queue {}", if paused { "paused" } else { "resumed" });
+    if let Err(e) = app_handle.emit("upload-queue-pause-changed", UploadQueuePauseState { is_paused: paused }) {
+        tracing::error!("Failed to emit upload queue pause state: {}", e);
+    }
+    crate::tray::sync_pause_state(app_handle, queue_state, paused);
+    Ok(())
+}
+
+/// アップロードキュー全体を一時停止（進行中のファイルは完了まで継続）
+#[command]
+pub async fn pause_upload_queue(
+    app_handle: AppHandle,
+    queue_state: State<'_, UploadQueueState>,
+) -> Result<String, AppError> {
+    set_queue_paused(&app_handle, queue_state.inner(), true)?;
+    Ok(tr(MessageKey::UploadQueuePaused))
+}
+
+/// アップロードキュー全体を再開
+#[command]
+pub async fn resume_upload_queue(
+    app_handle: AppHandle,
+    queue_state: State<'_, UploadQueueState>,
+) -> Result<String, AppError> {
+    set_queue_paused(&app_handle, queue_state.inner(), false)?;
+    Ok(tr(MessageKey::UploadQueueResumed))
+}
+
+/// アップロードキューの状態を取得（ティアの制限と残量、現在の警告を含む）
 #[command]
 pub async fn get_upload_queue_status(
+    app_handle: AppHandle,
     queue_state: State<'_, UploadQueueState>,
-) -> Result<UploadStatistics, String> {
+) -> Result<UploadStatistics, AppError> {
+    let policy = current_policy(&app_handle);
     let queue = queue_state.lock()
-        .map_err(|e| standardize_error(InternalError::Other(format!("Failed to lock upload queue: {}", e))))?;
+        .map_err(|e| AppError::from(queue_lock_error(e)))?;
     
     let total_files = queue.items.len() as u64;
     let completed_files = queue.items.iter()
@@ -493,6 +1701,13 @@ pub async fn get_upload_queue_status(
     let in_progress_files = queue.items.iter()
         .filter(|item| item.status == UploadStatus::InProgress)
         .count() as u64;
+    let warning_files = queue.items.iter()
+        .filter(|item| item.status == UploadStatus::Warning)
+        .count() as u64;
+    let mirror_count = |status: MirrorStatus| queue.items.iter()
+        .flat_map(|item| item.mirrors.iter())
+        .filter(|mirror| mirror.status == status)
+        .count() as u64;
     
     let total_bytes: u64 = queue.items.iter().map(|item| item.file_size).sum();
     let uploaded_bytes: u64 = queue.items.iter().map(|item| item.uploaded_bytes).sum();
@@ -512,8 +1727,19 @@ pub async fn get_upload_queue_status(
     } else {
         None
     };
+
+    let bandwidth_profile = queue.config.as_ref().map(|config| BANDWIDTH_LIMITER.profile(config));
+    let (queued_files, used_bytes) = queue.tier_usage();
+    let limits = UploadLimitInfo::new(
+        &policy,
+        queued_files,
+        used_bytes,
+        bandwidth_profile.as_ref().and_then(|profile| profile.limit_mbps),
+        queue.config.as_ref().map(|config| config.max_concurrent_uploads),
+    );
+    let bucket = queue.config.as_ref().map(|config| config.bucket_name.clone());
     
-    Ok(UploadStatistics {
+    let statistics = UploadStatistics {
         total_files,
         completed_files,
         failed_files,
@@ -523,45 +1749,278 @@ pub async fn get_upload_queue_status(
         uploaded_bytes,
         average_speed_mbps: average_speed,
         estimated_time_remaining: estimated_time,
+        is_paused: queue.is_paused,
+        compression_saved_bytes: queue.compression_saved_bytes,
+        mock_aws_mode: is_mock_aws_mode(),
+        bandwidth_profile,
+        warning_files,
+        mirror_completed: mirror_count(MirrorStatus::Completed),
+        mirror_failed: mirror_count(MirrorStatus::Failed),
+        limits,
+        warnings: Vec::new(),
+    };
+    // 警告の収集は他の状態をロックするため、キューのロックを解放してから行う
+    drop(queue);
+    Ok(UploadStatistics {
+        warnings: collect_upload_warnings(&app_handle, bucket.as_deref()),
+        ..statistics
     })
 }
 
-/// アップロードキューアイテムを取得
+/// アップロードキューアイテムを取得（案件IDを指定した場合はその案件のみ）
 #[command]
 pub async fn get_upload_queue_items(
+    project_id: Option<String>,
     queue_state: State<'_, UploadQueueState>,
-) -> Result<Vec<UploadItem>, String> {
+) -> Result<Vec<UploadItem>, AppError> {
     let queue = queue_state.lock()
-        .map_err(|e| standardize_error(InternalError::Other(format!("Failed to lock upload queue: {}", e))))?;
+        .map_err(|e| AppError::from(queue_lock_error(e)))?;
     
-    Ok(queue.items.clone())
+    Ok(queue.items.iter()
+        .filter(|item| project_id.is_none() || item.project_id == project_id)
+        .cloned()
+        .collect())
+}
+
+/// アイテムのメモを編集（空にすると削除）
+#[command]
+pub async fn set_upload_item_note(
+    item_id: String,
+    note: Option<String>,
+    queue_state: State<'_, UploadQueueState>,
+) -> Result<UploadItem, AppError> {
+    let mut queue = queue_state.lock()
+        .map_err(|e| AppError::from(queue_lock_error(e)))?;
+    let item = queue.items.iter_mut()
+        .find(|item| item.id == item_id)
+        .ok_or_else(|| AppError::from(item_not_found(&item_id)))?;
+    item.note = normalize_note(note);
+    Ok(item.clone())
 }
 
 /// アップロードアイテムをリトライ
 #[command]
 pub async fn retry_upload_item(
+    app_handle: AppHandle,
     item_id: String,
     queue_state: State<'_, UploadQueueState>,
-) -> Result<String, String> {
-    let mut queue = queue_state.lock()
-        .map_err(|e| standardize_error(InternalError::Other(format!("Failed to lock upload queue: {}", e))))?;
-    
-    if let Some(item) = queue.items.iter_mut().find(|i| i.id == item_id) {
-        item.status = UploadStatus::Pending;
-        item.progress = 0.0;
-        item.uploaded_bytes = 0;
-        item.error_message = None;
-        item.retry_count += 1;
+) -> Result<String, AppError> {
+    let found = {
+        let mut queue = queue_state.lock()
+            .map_err(|e| AppError::from(queue_lock_error(e)))?;
         
-        log::info!("Retrying upload item: {}", item_id);
-        Ok("Upload item queued for retry".to_string())
+        if let Some(item) = queue.items.iter_mut().find(|i| i.id == item_id) {
+            item.status = UploadStatus::Pending;
+            item.progress = 0.0;
+            item.uploaded_bytes = 0;
+            item.error_message = None;
+            item.mirrors.clear();
+            item.retry_count += 1;
+            MetricsRegistry::increment(&METRICS.upload_retries);
+            true
+        } else {
+            false
+        }
+    };
+    
+    if found {
+        crate::badge::update_badge(&app_handle, queue_state.inner());
+        tracing::info!("Retrying upload item: {}", item_id);
+        Ok(tr(MessageKey::UploadRetryQueued))
     } else {
-        Err(standardize_error(InternalError::Other(format!("Upload item not found: {}", item_id))))
+        Err(AppError::from(item_not_found(&item_id)))
+    }
+}
+
+/// マニフェストの書き出し先
+struct ManifestTarget<'a> {
+    bucket: &'a str,
+    upload_to_s3: bool,
+}
+
+/// アップロードしたファイルのフォルダのアイテムがすべて終わっていれば、マニフェストを生成・更新する
+async fn update_folder_manifest(
+    app_handle: &AppHandle,
+    queue_state: &UploadQueueState,
+    s3_client: &dyn S3ClientTrait,
+    source_path: &str,
+    target: ManifestTarget<'_>,
+) {
+    let Some(folder) = Path::new(source_path).parent().map(Path::to_path_buf) else {
+        return;
+    };
+    // バンドルのステージング先は元のフォルダではないため対象外
+    if is_queued_bundle_file(source_path) {
+        return;
+    }
+    let items = match queue_state.lock() {
+        Ok(queue) => queue.folder_manifest_items(&folder),
+        Err(e) => {
+            tracing::error!("Failed to lock upload queue for manifest: {}", e);
+            return;
+        }
+    };
+    let Some(items) = items.filter(|items| !items.is_empty()) else {
+        return;
+    };
+    
+    // ハッシュはメタデータ作成済みのもののみ（未作成ならNoneのまま記録する）
+    let metadata_db = metadata_db_path(app_handle).ok()
+        .and_then(|db_path| MetadataDatabase::new(&db_path.to_string_lossy()).ok());
+    let now = chrono::Utc::now().to_rfc3339();
+    let entries = items.into_iter().map(|item| ManifestEntry {
+        path: Path::new(&item.file_path).strip_prefix(&folder)
+            .map(|relative| relative.to_string_lossy().to_string())
+            .unwrap_or_else(|_| item.file_name.clone()),
+        bucket: target.bucket.to_string(),
+        sha256: metadata_db.as_ref()
+            .and_then(|db| db.get_metadata_by_path(&item.file_path).ok())
+            .map(|metadata| metadata.file_hash),
+        uploaded_at: item.completed_at.clone().unwrap_or_else(|| now.clone()),
+        s3_key: item.s3_key,
+        size: item.file_size,
+    }).collect();
+    
+    let manifest = match write_manifest(&folder, entries) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            tracing::warn!("Failed to write manifest for {}: {}", folder.display(), e);
+            return;
+        }
+    };
+    tracing::info!("Manifest updated for {} ({} file(s))", folder.display(), manifest.entries.len());
+    if target.upload_to_s3 {
+        match upload_manifest(s3_client, target.bucket, &folder, &manifest).await {
+            Ok(key) => tracing::info!("Manifest uploaded to s3://{}/{}", target.bucket, key),
+            Err(e) => tracing::warn!("Failed to upload manifest for {}: {}", folder.display(), e),
+        }
     }
 }
 
+/// バッチ操作で変わったアイテムを1回のイベントで通知
+pub(crate) fn emit_queue_changed(app_handle: &AppHandle, reason: &'static str, result: &BatchOperationResult) {
+    let item_ids: Vec<String> = result.results.iter()
+        .filter(|result| result.success)
+        .map(|result| result.item_id.clone())
+        .collect();
+    if item_ids.is_empty() {
+        return;
+    }
+    if let Err(e) = app_handle.emit(UPLOAD_QUEUE_CHANGED_EVENT, UploadQueueChanged { reason, item_ids }) {
+        tracing::error!("Failed to emit upload queue changed event: {}", e);
+    }
+}
+
+/// 選択した複数のアイテムを一括削除（進行中のアイテムはアップロードを中断する）
+#[command]
+pub async fn remove_upload_items(
+    app_handle: AppHandle,
+    item_ids: Vec<String>,
+    queue_state: State<'_, UploadQueueState>,
+) -> Result<BatchOperationResult, AppError> {
+    let (results, aborted_uploads, region) = {
+        let mut queue = queue_state.lock()
+            .map_err(queue_lock_error)?;
+        let (results, aborted_uploads) = queue.remove_items(&item_ids);
+        (results, aborted_uploads, queue.config.as_ref().map(|config| config.region.clone()))
+    };
+    let result = BatchOperationResult::new(results);
+    crate::badge::update_badge(&app_handle, queue_state.inner());
+    tracing::info!("Removed {} upload item(s), {} failed", result.succeeded, result.failed);
+    emit_queue_changed(&app_handle, "removed", &result);
+    
+    // 中断したマルチパートアップロードの未完了パーツを破棄
+    if let (false, Some(region)) = (aborted_uploads.is_empty(), region) {
+        match create_cached_s3_client(&app_handle, &region).await {
+            Ok(client) => {
+                abort_multipart_uploads(client.as_ref(), &aborted_uploads).await;
+            }
+            Err(e) => tracing::error!("Failed to abort {} multipart upload(s): {}", aborted_uploads.len(), e),
+        }
+    }
+    Ok(result)
+}
+
+/// 選択した複数のアイテムを一括リトライ
+#[command]
+pub async fn retry_upload_items(
+    app_handle: AppHandle,
+    item_ids: Vec<String>,
+    queue_state: State<'_, UploadQueueState>,
+) -> Result<BatchOperationResult, AppError> {
+    let results = queue_state.lock()
+        .map_err(queue_lock_error)?
+        .retry_items(&item_ids);
+    let result = BatchOperationResult::new(results);
+    crate::badge::update_badge(&app_handle, queue_state.inner());
+    tracing::info!("Retrying {} upload item(s), {} failed", result.succeeded, result.failed);
+    emit_queue_changed(&app_handle, "retried", &result);
+    Ok(result)
+}
+
+/// update_upload_itemの結果
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadItemUpdateResult {
+    pub item: UploadItem,
+    /// 変更後のS3キーがライフサイクルルールの対象外の場合の警告
+    pub lifecycle_warning: Option<LifecyclePrefixWarning>,
+}
+
+/// 待機中のアイテムのS3キー・優先度・メモを編集（進行中・完了済みのアイテムは拒否）
+///
+/// S3キーを変更する場合は正規化・キュー内の衝突チェック・ライフサイクルの対象確認をやり直す
+#[command]
+pub async fn update_upload_item(
+    app_handle: AppHandle,
+    item_id: String,
+    mut changes: UploadItemChanges,
+    queue_state: State<'_, UploadQueueState>,
+) -> Result<UploadItemUpdateResult, AppError> {
+    changes.s3_key = changes.s3_key.as_deref().map(normalize_s3_key).transpose()?;
+    let mut lifecycle_warning = None;
+    if let Some(s3_key) = &changes.s3_key {
+        // S3へ問い合わせる前に、編集できない・衝突するキーでないことを確かめる
+        let config = {
+            let queue = queue_state.lock().map_err(queue_lock_error)?;
+            queue.check_pending_update(&item_id, &changes)?;
+            queue.config.clone()
+                .ok_or_else(|| InternalError::localized(ErrorCode::Config, MessageKey::UploadQueueNotInitialized))?
+        };
+        lifecycle_warning = check_queue_lifecycle_coverage(&app_handle, &config, std::slice::from_ref(&s3_key)).await;
+        if let Some(warning) = &lifecycle_warning {
+            if config.block_uncovered_lifecycle_prefix {
+                tracing::warn!("Rejected S3 key change outside lifecycle rules: {}", s3_key);
+                return Err(InternalError::Config(warning.message.clone()).into());
+            }
+            tracing::warn!("{}", warning.message);
+            if let Err(e) = app_handle.emit(LIFECYCLE_PREFIX_WARNING_EVENT, warning) {
+                tracing::warn!("Failed to emit lifecycle prefix warning: {}", e);
+            }
+        }
+    }
+
+    // 確認中に開始・削除された場合に備え、ロックを取り直して再検証してから反映する
+    let item = queue_state.lock()
+        .map_err(queue_lock_error)?
+        .update_pending_item(&item_id, &changes)?;
+    tracing::info!("Updated upload item {} (s3_key: {}, priority: {})", item.id, item.s3_key, item.priority);
+    if let Err(e) = app_handle.emit(UPLOAD_QUEUE_CHANGED_EVENT, UploadQueueChanged { reason: "updated", item_ids: vec![item.id.clone()] }) {
+        tracing::error!("Failed to emit upload queue changed event: {}", e);
+    }
+    Ok(UploadItemUpdateResult { item, lifecycle_warning })
+}
+
 /// S3キーを生成
-fn generate_s3_key(file_path: &str, config: &S3KeyConfig) -> Result<String, InternalError> {
+pub(crate) fn generate_s3_key(file_path: &str, config: &S3KeyConfig) -> Result<String, InternalError> {
+    generate_s3_key_with_context(file_path, config, &NamingContext::new(1))
+}
+
+/// 連番・ハッシュなどバッチ単位の値を指定してS3キーを生成
+pub(crate) fn generate_s3_key_with_context(
+    file_path: &str,
+    config: &S3KeyConfig,
+    context: &NamingContext,
+) -> Result<String, InternalError> {
     let path = Path::new(file_path);
     let file_name = path.file_name()
         .and_then(|n| n.to_str())
@@ -598,17 +2057,13 @@ fn generate_s3_key(file_path: &str, config: &S3KeyConfig) -> Result<String, Inte
     
     // カスタム命名パターンを適用
     if let Some(pattern) = &config.custom_naming_pattern {
-        // 簡易的なパターン置換
-        let custom_name = pattern
-            .replace("{filename}", file_name)
-            .replace("{timestamp}", &chrono::Utc::now().timestamp().to_string())
-            .replace("{uuid}", &Uuid::new_v4().to_string());
-        s3_key.push_str(&custom_name);
+        s3_key.push_str(&expand_naming_pattern(pattern, path, context, chrono::Utc::now())?);
     } else {
         s3_key.push_str(file_name);
     }
     
-    Ok(s3_key)
+    // macOSのNFDファイル名のままだと他OS・Webコンソールから別の文字列に見えるためNFCへ揃える
+    Ok(to_nfc(&s3_key))
 }
 
 /// バックグラウンドでアップロードキューを処理
@@ -617,131 +2072,330 @@ async fn process_upload_queue(
     app_handle: AppHandle,
     config: UploadConfig,
 ) -> Result<(), String> {
-    log::info!("🚀 process_upload_queue started with max_concurrent: {}", config.max_concurrent_uploads);
+    tracing::info!("🚀 process_upload_queue started with max_concurrent: {}", config.max_concurrent_uploads);
     
     // 開始時のテストイベント送信
     if let Err(e) = app_handle.emit("test-event", "process_upload_queue started") {
-        log::error!("Failed to emit process start test event: {}", e);
+        tracing::error!("Failed to emit process start test event: {}", e);
     } else {
-        log::info!("Process start test event emitted successfully");
+        tracing::info!("Process start test event emitted successfully");
     }
     
     let max_concurrent = config.max_concurrent_uploads;
     let (tx, mut rx) = mpsc::channel::<UploadProgress>(100);
+    let mut last_network_check = Instant::now();
+    let mut watchdog = QueueWatchdog::new(Instant::now());
+    let mut restart = false;
     
     loop {
         // 処理停止チェック
-        {
-            let queue = queue_state.lock()
+        let (network_offline, network_check_requested) = {
+            let queue = timed_lock(&queue_state)
+                .map_err(|e| format!("Failed to lock queue: {}", e))?;
+            if !queue.is_processing {
+                break;
+            }
+            (queue.network_offline, queue.network_check_requested)
+        };
+        
+        // ネットワーク到達性の確認（定期的、またはネットワーク起因の失敗時）
+        let check_interval = if network_offline {
+            crate::network::OFFLINE_CHECK_INTERVAL
+        } else {
+            crate::network::ONLINE_CHECK_INTERVAL
+        };
+        if network_check_requested || last_network_check.elapsed() >= check_interval {
+            last_network_check = Instant::now();
+            let online = crate::network::check_connectivity(&config.region).await;
+            crate::network::apply_network_status(&app_handle, &queue_state, online);
+        }
+        
+        // 開始できる待機中アイテムがあるのに新規開始も進捗も無い状態が続いたら自己修復する
+        let waiting = {
+            let queue = timed_lock(&queue_state)
                 .map_err(|e| format!("Failed to lock queue: {}", e))?;
-            if !queue.is_processing {
+            !queue.is_paused && !queue.network_offline && !is_transfer_blocked() && !queue.next_pending_ids(1).is_empty()
+        };
+        match watchdog.check(waiting, Instant::now()) {
+            WatchdogAction::None => {}
+            WatchdogAction::Reconcile { stalled_for } => {
+                let report = timed_lock(&queue_state)
+                    .map_err(|e| format!("Failed to lock queue: {}", e))?
+                    .reconcile_active_state();
+                if report.changed() {
+                    tracing::warn!("Upload queue stalled for {}s; corrected active count {} -> {} (stale progress: {}, requeued: {:?})",
+                        stalled_for.as_secs(), report.previous_count, report.corrected_count, report.stale_progress, report.requeued_items);
+                } else {
+                    tracing::warn!("Upload queue stalled for {}s; internal state is consistent", stalled_for.as_secs());
+                }
+            }
+            WatchdogAction::Restart { stalled_for } => {
+                reset_stalled_queue(&app_handle, &queue_state, &config, stalled_for).await?;
+                restart = true;
                 break;
             }
         }
         
         // 新しいアップロードを開始できるかチェック
-        let (should_wait, pending_items) = {
-            let mut queue = queue_state.lock()
+        let (should_wait, candidates) = {
+            let queue = timed_lock(&queue_state)
                 .map_err(|e| format!("Failed to lock queue: {}", e))?;
             let current_active = queue.get_active_upload_count();
-            if current_active >= max_concurrent {
+            if queue.is_paused || queue.network_offline || is_transfer_blocked() {
+                // 一時停止中・オフライン中・月次の転送量上限に達した場合は新規アップロードを開始せず、進行中の進捗処理のみ継続
+                (false, Vec::new())
+            } else if current_active >= max_concurrent {
                 (true, Vec::new())
             } else {
                 let available_slots = max_concurrent.saturating_sub(current_active);
-                let mut pending = Vec::new();
                 let max_new_uploads = if max_concurrent == 1 {
                     if current_active > 0 { 0 } else { 1 }
                 } else {
                     available_slots
                 };
-                let pending_item_ids: Vec<String> = queue.items.iter()
-                    .filter(|item| item.status == UploadStatus::Pending)
-                    .take(max_new_uploads)
-                    .map(|item| item.id.clone())
+                let candidates: Vec<(String, String)> = queue.next_pending_ids(max_new_uploads).into_iter()
+                    .filter_map(|item_id| {
+                        let file_path = queue.items.iter().find(|i| i.id == item_id)?.file_path.clone();
+                        Some((item_id, file_path))
+                    })
                     .collect();
-                for item_id in pending_item_ids {
-                    match queue.start_upload(&item_id) {
-                        Ok(()) => {
-                            if let Some(item) = queue.items.iter().find(|i| i.id == item_id) {
-                                pending.push(item.clone());
-                                if max_concurrent == 1 {
-                                    break;
-                                }
+                (false, candidates)
+            }
+        };
+        
+        if should_wait {
+            sleep(Duration::from_millis(1000)).await;
+            continue;
+        }
+        
+        // 他のアプリが書き込み中のファイルは開始せずに保留する（lsofはロックの外で実行）
+        let mut released_ids = Vec::new();
+        let mut in_use_ids = Vec::new();
+        for (item_id, file_path) in candidates {
+            if check_file_in_use(PathBuf::from(&file_path)).await {
+                in_use_ids.push((item_id, file_path));
+            } else {
+                released_ids.push(item_id);
+            }
+        }
+        
+        let pending_items = {
+            let mut queue = timed_lock(&queue_state)
+                .map_err(|e| format!("Failed to lock queue: {}", e))?;
+            let now = Instant::now();
+            for (item_id, file_path) in &in_use_ids {
+                if queue.hold_in_use_item(item_id, now) {
+                    tracing::warn!("Gave up waiting for {} to be released by another application", file_path);
+                } else {
+                    tracing::info!("Holding {} until another application finishes writing it", file_path);
+                }
+            }
+            queue.prune_in_use_holds();
+            let mut pending = Vec::new();
+            for item_id in released_ids {
+                match queue.start_upload(&item_id) {
+                    Ok(()) => {
+                        queue.file_in_use_holds.remove(&item_id);
+                        if let Some(item) = queue.items.iter().find(|i| i.id == item_id) {
+                            pending.push(item.clone());
+                            if max_concurrent == 1 {
+                                break;
                             }
                         }
-                        Err(e) => {
-                            log::error!("Failed to start upload for {}: {}", item_id, e);
-                            break;
-                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to start upload for {}: {}", item_id, e);
+                        break;
                     }
                 }
-                (false, pending)
             }
+            pending
         };
         
-        if should_wait {
-            sleep(Duration::from_millis(1000)).await;
-            continue;
+        if !pending_items.is_empty() {
+            watchdog.record_activity(Instant::now());
         }
         
         // 新しいアップロードタスクを開始
         for item in pending_items {
             let queue_state_clone = queue_state.clone();
-            let config_clone = config.clone();
+            // 追加時に焼き込んだファイル種別ごとの上書きを反映
+            let config_clone = config_for_item(&config, &item);
             let tx_clone = tx.clone();
             let item_id = item.id.clone();
             let file_name = item.file_name.clone();
+            let file_size = item.file_size;
+            let app_handle_clone = app_handle.clone();
+            let task_item_id = item.id.clone();
+            let source_path = item.file_path.clone();
+            let bucket_name = config.bucket_name.clone();
+            let finder_tag = config.set_finder_tag_on_complete.clone();
+            let readonly_lock = config.set_readonly_after_upload.then_some(config.set_immutable_flag_after_upload);
+            let write_local_manifest = config.write_local_manifest;
+            let upload_manifest_to_s3 = config.upload_manifest_to_s3;
+            let labels = UploadItemLabels::from_item(&item);
+            let requeue_after_source_change = config.requeue_after_source_change_secs.map(Duration::from_secs);
+            // ミラーはプライマリと同じ元ファイル・圧縮前のキーから作る
+            let mirror = (!config.mirror_targets.is_empty()).then(|| (config_clone.clone(), item.clone()));
+            // 配下のログすべてにアイテムのコンテキストを付け、span終了時に所要時間を記録する
+            let item_span = tracing::info_span!(
+                "upload_item",
+                item_id = %item.id,
+                file_name = %item.file_name,
+                s3_key = %item.s3_key,
+                size = item.file_size,
+            );
             
-            tokio::spawn(async move {
-                log::info!("🔄 Starting upload task for: {} ({})", file_name, item_id);
+            let task = tokio::spawn(async move {
+                tracing::info!("🔄 Starting upload task for: {} ({})", file_name, item_id);
                 
-                // RealS3Clientを作成
-                let s3_client = match create_s3_client(&config_clone.aws_credentials).await {
-                    Ok(client) => RealS3Client::new(client),
+                // S3クライアントを作成（認証情報はキャッシュから取得し、モックAWSモード時はAWSへアクセスしない）
+                let s3_client = match create_cached_s3_client(&app_handle_clone, &config_clone.region).await {
+                    Ok(client) => client,
                     Err(e) => {
-                        log::error!("Failed to create S3 client: {}", e);
+                        tracing::error!("Failed to create S3 client: {}", e);
                         let mut queue = queue_state_clone.lock().unwrap();
                         queue.complete_upload(&item_id, false, Some(format!("Failed to create S3 client: {}", e)));
                         return;
                     }
                 };
                 
-                let result = upload_file_to_s3(
+                let requested_key = item.s3_key.clone();
+                let started_at = std::time::Instant::now();
+                let result = upload_file_to_s3_with_labels(
+                    s3_client.as_ref(),
                     item.file_path,
                     item.s3_key,
                     config_clone,
                     tx_clone,
                     item_id.clone(),
-                    &s3_client,
+                    &labels,
                 ).await;
+                let elapsed = started_at.elapsed();
                 
-                let (success, error_msg) = match result {
-                    Ok(_) => (true, None),
-                    Err(e) => (false, Some(e)),
+                let (success, error_msg, outcome) = match result {
+                    Ok(outcome) => (true, None, Some(outcome)),
+                    Err(e) => (false, Some(e), None),
                 };
                 
+                // ネットワーク起因の失敗はFailedにせず一時停止し、到達性の確認を要求
+                if let Some(e) = error_msg.as_deref().filter(|e| is_network_error_message(e)) {
+                    tracing::warn!("Upload interrupted by network error: {} ({}): {}", file_name, item_id, e);
+                    let mut queue = queue_state_clone.lock().unwrap();
+                    queue.pause_for_network(&item_id);
+                    queue.network_check_requested = true;
+                    return;
+                }
+                
+                // 失敗したマルチパートアップロードの未完了パーツを破棄（成功時は登録解除済み）
+                if let Some(upload) = take_multipart_upload(&item_id) {
+                    abort_multipart_uploads(s3_client.as_ref(), std::slice::from_ref(&upload)).await;
+                }
+                
+                // 成功・最終的な失敗のみ監査ログへ記録（ネットワーク一時停止は再開されるため除外）
+                let uploaded_key = outcome.as_ref().map(|o| o.s3_key.clone()).unwrap_or_else(|| requested_key.clone());
+                let audit_result = match &error_msg {
+                    Some(e) => Err(e.clone()),
+                    None => Ok(()),
+                };
+                record_operation(
+                    &app_handle_clone,
+                    OperationAuditEntry::new(AuditOperation::Upload, &audit_result)
+                        .local_path(source_path.clone())
+                        .s3_object(bucket_name.clone(), uploaded_key)
+                        .size(file_size)
+                        .duration(elapsed)
+                        .project_id(labels.project_id.clone()),
+                );
+                
                 // 新しい状態管理システムを使用してアップロード完了を記録
                 {
                     let mut queue = queue_state_clone.lock().unwrap();
+                    // 圧縮してアップロードした場合は実際のキーと削減量を反映
+                    if let Some(outcome) = &outcome {
+                        queue.compression_saved_bytes += outcome.saved_bytes();
+                        if let Some(item) = queue.items.iter_mut().find(|i| i.id == item_id) {
+                            item.s3_key = outcome.s3_key.clone();
+                        }
+                    }
                     // 既に完了済みかチェック（進捗更新で先に処理された場合）
                     if let Some(item) = queue.items.iter().find(|i| i.id == item_id) {
                         if item.status == UploadStatus::Completed {
-                            log::info!("✅ Upload already completed by progress update, skipping task cleanup: {}", item_id);
+                            tracing::info!("✅ Upload already completed by progress update, skipping task cleanup: {}", item_id);
                         } else {
-                            log::info!("🔄 Task completion: calling complete_upload for {}", item_id);
+                            tracing::info!("🔄 Task completion: calling complete_upload for {}", item_id);
                             queue.complete_upload(&item_id, success, error_msg.clone());
                         }
                     } else {
-                        log::warn!("⚠️  Upload item not found during task completion: {}", item_id);
+                        tracing::warn!("⚠️  Upload item not found during task completion: {}", item_id);
+                    }
+                }
+                
+                if let Some(stable_for) = requeue_after_source_change.filter(|_| error_msg.as_deref().map(is_source_modified_error).unwrap_or(false)) {
+                    requeue_after_stable_source(queue_state_clone.clone(), item_id.clone(), source_path.clone(), stable_for);
+                }
+                
+                let verified = match &outcome {
+                    Some(outcome) => record_completed_upload(&app_handle_clone, s3_client.as_ref(), &bucket_name, &source_path, outcome, &labels).await,
+                    None => false,
+                };
+                // タグ付けに失敗してもアップロードは成功扱い
+                if let Some(tag) = finder_tag.as_deref().filter(|_| verified) {
+                    if let Err(e) = crate::finder_tags::add_finder_tag(std::path::Path::new(&source_path), tag) {
+                        tracing::warn!("Failed to set Finder tag on {}: {}", source_path, e);
+                    }
+                }
+                // 読み取り専用化に失敗してもアップロードは成功扱い
+                if let Some(immutable) = readonly_lock.filter(|_| verified) {
+                    if let Err(e) = lock_local_file(std::path::Path::new(&source_path), immutable) {
+                        tracing::warn!("Failed to make {} read-only: {}", source_path, e);
                     }
                 }
                 
+                if let Some((mirror_config, mirror_item)) = mirror.filter(|_| success) {
+                    let pending = initial_mirrors(&mirror_config.mirror_targets, &requested_key);
+                    run_mirror_uploads(&app_handle_clone, &queue_state_clone, &mirror_config, &mirror_item, pending).await;
+                }
+                
+                if success && write_local_manifest {
+                    let target = ManifestTarget { bucket: &bucket_name, upload_to_s3: upload_manifest_to_s3 };
+                    update_folder_manifest(&app_handle_clone, &queue_state_clone, s3_client.as_ref(), &source_path, target).await;
+                }
+                
+                // 取り込みフォルダから追加したファイルは、ミラー・目録で読み終えてから処理済みとして削除・移動する
+                // （ミラーが失敗した場合は再送できるよう元ファイルを残す）
+                if let Some(outcome) = &outcome {
+                    let mirror_failed = queue_state_clone.lock()
+                        .map(|queue| queue.items.iter().any(|i| i.id == item_id && i.status == UploadStatus::Warning))
+                        .unwrap_or(false);
+                    finish_hot_folder_upload(&app_handle_clone, &item_id, &outcome.s3_key, verified && !mirror_failed);
+                }
+                
                 if success {
-                    log::info!("Upload task completed successfully: {} ({})", file_name, item_id);
+                    tracing::info!("Upload task completed successfully: {} ({})", file_name, item_id);
+                    // バンドルのアーカイブが揃ったら目録を記録し、ステージングしたファイルを削除
+                    complete_queued_bundle_file(&source_path);
+                    crate::notifications::notify_large_file_completed(&app_handle_clone, &file_name, file_size);
+                    crate::tray::schedule_recent_uploads_refresh(&app_handle_clone);
                 } else {
-                    log::error!("Upload task failed: {} ({}), error: {}", file_name, item_id, error_msg.unwrap_or_default());
+                    let error_msg = error_msg.unwrap_or_default();
+                    tracing::error!("Upload task failed: {} ({}), error: {}", file_name, item_id, error_msg);
+                    record_hot_folder_upload_failure(&app_handle_clone, &item_id, &error_msg);
+                    // アーカイブが揃わないバンドルは記録せず、ステージングしたファイルを削除
+                    discard_queued_bundle(&source_path);
+                    crate::notifications::notify_error(
+                        &app_handle_clone,
+                        &tr(MessageKey::UploadFailedNotification),
+                        &format!("{}: {}", file_name, error_msg),
+                    );
                 }
-            });
+            }.instrument(item_span));
+            
+            if let Ok(mut queue) = queue_state.lock() {
+                if !task.is_finished() {
+                    queue.upload_tasks.insert(task_item_id, task.abort_handle());
+                }
+            }
         }
         
         // 進捗更新を処理
@@ -751,139 +2405,388 @@ async fn process_upload_queue(
             {
                 let mut queue = queue_state.lock()
                     .map_err(|e| format!("Failed to lock queue: {}", e))?;
-                queue.active_uploads.insert(progress.item_id.clone(), progress.clone());
-                
-                // キューアイテムの進捗も更新
-                let (should_cleanup, file_name, file_size, is_success) = {
-                    if let Some(item) = queue.items.iter_mut().find(|i| i.id == progress.item_id) {
-                        let was_in_progress = item.status == UploadStatus::InProgress;
-                        
-                        item.progress = progress.percentage;
-                        item.uploaded_bytes = progress.uploaded_bytes;
-                        item.speed_mbps = progress.speed_mbps;
-                        item.eta_seconds = progress.eta_seconds;
-                        item.status = progress.status.clone();
-                        
-                        // 完了時（成功・失敗問わず）の判定と必要な値の取得
-                        let is_completed = matches!(progress.status, UploadStatus::Completed | UploadStatus::Failed);
-                        if is_completed && was_in_progress {
-                            if progress.status == UploadStatus::Completed {
-                                item.completed_at = Some(chrono::Utc::now().to_rfc3339());
-                            }
-                            (true, item.file_name.clone(), item.file_size, progress.status == UploadStatus::Completed)
-                        } else {
-                            (false, String::new(), 0, false)
-                        }
-                    } else {
-                        (false, String::new(), 0, false)
-                    }
+                // オフライン検知で一時停止したアイテムの遅延した進捗は反映しない
+                if queue.network_paused_items.contains(&progress.item_id) {
+                    continue;
+                }
+                // 進行中のアイテムのみ反映（完了後に届いた古い進捗で状態を戻さない）
+                let Some(item) = queue.items.iter_mut()
+                    .find(|i| i.id == progress.item_id && i.status == UploadStatus::InProgress)
+                else {
+                    continue;
                 };
+                item.progress = progress.percentage;
+                item.uploaded_bytes = progress.uploaded_bytes;
+                item.speed_mbps = progress.speed_mbps;
+                item.eta_seconds = progress.eta_seconds;
                 
-                // 借用が終了した後でクリーンアップ処理
-                if should_cleanup {
-                    if is_success {
-                        log::info!("🎉 Upload 100% completed, performing immediate cleanup: {}", progress.item_id);
-                        queue.total_files_uploaded += 1;
-                        queue.total_uploaded_bytes += file_size;
-                    } else {
-                        log::info!("💥 Upload failed, performing immediate cleanup: {}", progress.item_id);
-                    }
-                    
-                    // アクティブカウントを減らす
-                    let old_count = queue.active_upload_count;
-                    if queue.active_upload_count > 0 {
-                        queue.active_upload_count -= 1;
-                        log::info!("🔽 Active upload count decreased: {} -> {}", 
-                                   old_count, queue.active_upload_count);
+                match progress.status {
+                    UploadStatus::Completed | UploadStatus::Failed => {
+                        // 完了処理はcomplete_uploadに一本化（アクティブ枠の解放は1回だけ行われる）
+                        let is_success = progress.status == UploadStatus::Completed;
+                        tracing::info!("Upload finished by progress update, performing immediate cleanup: {} (success: {})",
+                                   progress.item_id, is_success);
+                        queue.complete_upload(&progress.item_id, is_success, None);
                     }
-                    
-                    // active_uploadsから削除
-                    let removed = queue.active_uploads.remove(&progress.item_id);
-                    log::info!("🗑️  Removed from active_uploads: {} (was present: {})", progress.item_id, removed.is_some());
-                    
-                    if is_success {
-                        log::info!("✅ Upload completed and cleaned up: {} ({})", file_name, progress.item_id);
-                    } else {
-                        log::info!("❌ Upload failed and cleaned up: {} ({})", file_name, progress.item_id);
+                    _ => {
+                        queue.active_uploads.insert(progress.item_id.clone(), progress.clone());
                     }
-                    log::info!("📊 Cleanup summary - Active count: {}, Active uploads: {}", 
-                               queue.active_upload_count, queue.active_uploads.len());
                 }
             } // ロックをここで解放
             
             // フロントエンドに進捗を通知
-            log::info!("Emitting progress event to frontend: {:.1}% for {}", 
+            tracing::trace!("Emitting progress event to frontend: {:.1}% for {}", 
                        progress.percentage, progress.item_id);
             if let Err(e) = app_handle.emit("upload-progress", &progress) {
-                log::error!("Failed to emit upload progress: {}", e);
+                tracing::error!("Failed to emit upload progress: {}", e);
             } else {
-                log::info!("Progress event emitted successfully: {:.1}%", progress.percentage);
+                tracing::trace!("Progress event emitted successfully: {:.1}%", progress.percentage);
             }
         }
         
         if progress_received > 0 {
-            log::info!("Processed {} progress updates in this cycle", progress_received);
+            watchdog.record_activity(Instant::now());
+            tracing::info!("Processed {} progress updates in this cycle", progress_received);
         }
         
+        // トレイのステータス表示を更新（間隔はtray側で間引く）
+        crate::tray::update_upload_status(&app_handle, &queue_state);
+        crate::badge::update_badge(&app_handle, &queue_state);
+        
         // アップロード停止検出とリカバリ
-        let (has_pending, has_active, all_completed) = {
+        let (has_pending, has_active, all_completed, is_paused) = {
             let queue = queue_state.lock()
                 .map_err(|e| format!("Failed to lock queue: {}", e))?;
             let pending = queue.items.iter().any(|item| item.status == UploadStatus::Pending);
             let active = queue.get_active_upload_count() > 0;
-            let completed = queue.items.iter().all(|item| 
-                matches!(item.status, UploadStatus::Completed | UploadStatus::Failed | UploadStatus::Cancelled)
-            );
-            (pending, active, completed)
+            let completed = queue.items.iter().all(|item| item.status.is_finished());
+            (pending, active, completed, queue.is_paused)
         };
         
+        // 転送中（または開始待ち）の間はシステムスリープを抑止
+        crate::power::set_upload_active(&app_handle, has_active || (has_pending && !is_paused));
+        
         // 全てのファイルが完了した場合は処理を停止
         if all_completed {
-            log::info!("🎉 All uploads completed! Stopping processing");
+            tracing::info!("🎉 All uploads completed! Stopping processing");
+            let (succeeded, failed, total_bytes) = {
+                let queue = queue_state.lock()
+                    .map_err(|e| format!("Failed to lock queue: {}", e))?;
+                let completed: Vec<&UploadItem> = queue.items.iter()
+                    .filter(|item| item.status.is_primary_completed())
+                    .collect();
+                let failed = queue.items.iter()
+                    .filter(|item| item.status == UploadStatus::Failed)
+                    .count();
+                (completed.len(), failed, completed.iter().map(|item| item.file_size).sum::<u64>())
+            };
+            crate::notifications::notify_queue_completed(&app_handle, succeeded, failed, total_bytes);
             break;
         }
         
         // 待機時間を設定
         if !has_pending && !has_active {
-            log::info!("No pending or active uploads, waiting...");
+            tracing::info!("No pending or active uploads, waiting...");
             sleep(Duration::from_millis(5000)).await;
         } else {
             sleep(Duration::from_millis(100)).await;
         }
     }
     
-    log::info!("🚀 process_upload_queue completed");
+    // 最終状態（待機中など）を確実に反映
+    crate::power::set_upload_active(&app_handle, false);
+    crate::tray::refresh_upload_status(&app_handle, &queue_state);
+    crate::badge::update_badge(&app_handle, &queue_state);
+    
+    tracing::info!("🚀 process_upload_queue completed");
+    
+    if restart {
+        match start_queue_processing(&app_handle, &queue_state) {
+            Ok(true) => tracing::info!("Restarted upload queue processing after stall"),
+            Ok(false) => tracing::info!("Upload queue processing was already restarted"),
+            Err(e) => tracing::error!("Failed to restart upload queue processing: {}", e),
+        }
+    }
+    Ok(())
+}
+
+/// 停滞したキュー処理を記録・通知し、進行中のアップロードを待機中へ戻して処理ループを終える
+async fn reset_stalled_queue(
+    app_handle: &AppHandle,
+    queue_state: &UploadQueueState,
+    config: &UploadConfig,
+    stalled_for: Duration,
+) -> Result<(), String> {
+    let (event, aborted_uploads) = {
+        let mut queue = timed_lock(queue_state)
+            .map_err(|e| format!("Failed to lock queue: {}", e))?;
+        let pending_count = queue.items.iter().filter(|item| item.status == UploadStatus::Pending).count();
+        let in_progress_count = queue.in_progress_count();
+        let active_upload_count = queue.get_active_upload_count();
+        let (requeued_count, aborted_uploads) = queue.reset_for_restart();
+        let event = UploadStalledEvent {
+            detected_at: chrono::Utc::now().to_rfc3339(),
+            stalled_secs: stalled_for.as_secs(),
+            pending_count,
+            in_progress_count,
+            active_upload_count,
+            requeued_count,
+        };
+        queue.record_stall(event.clone());
+        queue.is_processing = false;
+        (event, aborted_uploads)
+    };
+    tracing::error!("{}", event.message());
+    if let Err(e) = app_handle.emit(UPLOAD_STALLED_EVENT, &event) {
+        tracing::error!("Failed to emit upload stalled event: {}", e);
+    }
+    
+    // 中断したマルチパートアップロードの未完了パーツを破棄
+    if !aborted_uploads.is_empty() {
+        match create_cached_s3_client(app_handle, &config.region).await {
+            Ok(client) => {
+                abort_multipart_uploads(client.as_ref(), &aborted_uploads).await;
+            }
+            Err(e) => tracing::error!("Failed to abort {} multipart upload(s): {}", aborted_uploads.len(), e),
+        }
+    }
     Ok(())
 }
 
-/// 単一ファイルのアップロード処理
-async fn upload_file_to_s3(
+/// アップロード結果
+#[derive(Debug, Clone)]
+pub struct UploadOutcome {
+    /// 実際にアップロードしたキー（圧縮時は".zst"付き）
+    pub s3_key: String,
+    /// 転送したバイト数（圧縮時は圧縮後のサイズ）
+    pub uploaded_bytes: u64,
+    /// 元ファイルのサイズ
+    pub original_size: u64,
+}
+
+impl UploadOutcome {
+    /// 圧縮による削減量
+    pub fn saved_bytes(&self) -> u64 {
+        self.original_size.saturating_sub(self.uploaded_bytes)
+    }
+}
+
+/// アップロード中に元ファイルが変更されたときのエラー
+pub const SOURCE_MODIFIED_ERROR: &str = "source file modified during upload";
+/// 安定化待ちでサイズ・更新日時を確認する間隔
+const SOURCE_STABILITY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+pub fn is_source_modified_error(message: &str) -> bool {
+    message.starts_with(SOURCE_MODIFIED_ERROR)
+}
+
+/// 元ファイルの変更検出に使うサイズと更新日時
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SourceFingerprint {
+    size: u64,
+    modified: Option<std::time::SystemTime>,
+}
+
+impl SourceFingerprint {
+    pub(crate) fn capture(path: &Path) -> Result<Self, String> {
+        let metadata = std::fs::metadata(path)
+            .map_err(|e| format!("Failed to get file metadata: {}", e))?;
+        Ok(Self { size: metadata.len(), modified: metadata.modified().ok() })
+    }
+
+    /// 記録時からサイズ・更新日時が変わっていれば（削除された場合も）エラー
+    pub(crate) fn ensure_unchanged(&self, path: &Path) -> Result<(), String> {
+        match Self::capture(path) {
+            Ok(current) if current == *self => Ok(()),
+            Ok(current) => Err(format!(
+                "{} (size {} -> {} bytes)", SOURCE_MODIFIED_ERROR, self.size, current.size
+            )),
+            Err(e) => Err(format!("{} ({})", SOURCE_MODIFIED_ERROR, e)),
+        }
+    }
+}
+
+/// サイズ・更新日時が`stable_for`の間変わらなくなるまで待つ（削除された場合はNone）
+pub(crate) async fn wait_for_stable_source(path: &Path, stable_for: Duration, poll_interval: Duration) -> Option<SourceFingerprint> {
+    let mut last = SourceFingerprint::capture(path).ok()?;
+    let mut stable_since = Instant::now();
+    loop {
+        sleep(poll_interval).await;
+        let current = SourceFingerprint::capture(path).ok()?;
+        if current != last {
+            last = current;
+            stable_since = Instant::now();
+        } else if stable_since.elapsed() >= stable_for {
+            return Some(current);
+        }
+    }
+}
+
+/// 元ファイルの変更で失敗したアイテムを、書き込みが落ち着いてから再キューする
+fn requeue_after_stable_source(queue_state: UploadQueueState, item_id: String, source_path: String, stable_for: Duration) {
+    tokio::spawn(async move {
+        tracing::info!("Waiting for {} to stabilize before requeueing {}", source_path, item_id);
+        let Some(fingerprint) = wait_for_stable_source(Path::new(&source_path), stable_for, SOURCE_STABILITY_POLL_INTERVAL).await else {
+            tracing::warn!("Source file disappeared while waiting to requeue: {}", source_path);
+            return;
+        };
+        let requeued = queue_state.lock()
+            .map(|mut queue| queue.requeue_modified_item(&item_id, fingerprint.size))
+            .unwrap_or(false);
+        if requeued {
+            tracing::info!("Requeued {} after source change: {}", item_id, source_path);
+        }
+    });
+}
+
+/// 元ファイルの変更を検出したら、変更前後のデータが混ざったオブジェクトを作らないようマルチパートを中断する
+async fn abort_on_source_change(s3_client: &dyn S3ClientTrait, item_id: &str, error: String) -> String {
+    tracing::warn!("Aborting upload {}: {}", item_id, error);
+    let uploads: Vec<ActiveMultipartUpload> = take_multipart_upload(item_id).into_iter().collect();
+    abort_multipart_uploads(s3_client, &uploads).await;
+    error
+}
+
+/// 単一ファイルのアップロード処理（Tauriに依存しないため、キュー処理・CLIから共通で使用）
+/// 設定に応じて動画以外のファイルはzstd圧縮してからアップロードする
+pub(crate) async fn upload_file_to_s3(
+    file_path: String,
+    s3_key: String,
+    credentials: &AwsCredentials,
+    config: UploadConfig,
+    progress_tx: mpsc::Sender<UploadProgress>,
+    item_id: String,
+) -> Result<UploadOutcome, String> {
+    let s3_client = create_s3_client_for_credentials(credentials)
+        .await
+        .map_err(|e| format!("Failed to create S3 client: {}", e))?;
+    upload_file_to_s3_internal(s3_client.as_ref(), file_path, s3_key, config, progress_tx, item_id).await
+}
+
+/// 内部実装：S3ClientTraitを使ったアップロード（圧縮・チャンク分割・リトライ・メタデータ作成）
+pub(crate) async fn upload_file_to_s3_internal(
+    s3_client: &dyn S3ClientTrait,
+    file_path: String,
+    s3_key: String,
+    config: UploadConfig,
+    progress_tx: mpsc::Sender<UploadProgress>,
+    item_id: String,
+) -> Result<UploadOutcome, String> {
+    upload_file_to_s3_with_labels(s3_client, file_path, s3_key, config, progress_tx, item_id, &UploadItemLabels::default()).await
+}
+
+/// 内部実装：メモ・案件IDをS3オブジェクトメタデータとメタデータDBへ伝播させてアップロード
+#[tracing::instrument(
+    name = "upload_file",
+    skip_all,
+    fields(item_id = %item_id, s3_key = %s3_key, file_name = tracing::field::Empty, size = tracing::field::Empty),
+)]
+pub(crate) async fn upload_file_to_s3_with_labels(
+    s3_client: &dyn S3ClientTrait,
     file_path: String,
     s3_key: String,
     config: UploadConfig,
     progress_tx: mpsc::Sender<UploadProgress>,
     item_id: String,
+    labels: &UploadItemLabels,
+) -> Result<UploadOutcome, String> {
+    let path = Path::new(&file_path);
+    if !path.exists() {
+        return Err(format!("File does not exist: {}", file_path));
+    }
+    let source = SourceFingerprint::capture(path)?;
+    let original_size = source.size;
+    let span = tracing::Span::current();
+    span.record("file_name", path.file_name().and_then(|n| n.to_str()).unwrap_or(""));
+    span.record("size", original_size);
+    
+    let outcome = if config.compress_non_video && should_compress(path, original_size) {
+        let compressed = compress_file_async(path.to_path_buf(), config.compression_level)
+            .await
+            .map_err(crate::internal::standardize_error)?;
+        // 圧縮中に書き換えられた場合は、変更前後が混ざった圧縮データになっている
+        source.ensure_unchanged(path)?;
+        let compressed_key = format!("{}{}", s3_key, ZSTD_EXTENSION);
+        let file_name = path.file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+        let mut metadata = compression_metadata(file_name, original_size);
+        metadata.extend(labels.object_metadata());
+        
+        upload_file_contents(
+            compressed.path().to_string_lossy().to_string(),
+            compressed_key.clone(),
+            &config,
+            progress_tx,
+            item_id,
+            s3_client,
+            metadata,
+        ).await?;
+        tracing::info!("Compressed upload saved {} bytes: {}", compressed.saved_bytes(), compressed_key);
+        
+        UploadOutcome {
+            s3_key: compressed_key,
+            uploaded_bytes: compressed.compressed_size,
+            original_size,
+        }
+    } else {
+        upload_file_contents(
+            file_path.clone(),
+            s3_key.clone(),
+            &config,
+            progress_tx,
+            item_id,
+            s3_client,
+            labels.object_metadata(),
+        ).await?;
+        
+        UploadOutcome {
+            s3_key,
+            uploaded_bytes: original_size,
+            original_size,
+        }
+    };
+    
+    // メタデータ作成（設定されている場合）
+    if config.auto_create_metadata {
+        let tags = vec!["upload".to_string()];
+        if let Err(e) = build_file_metadata(file_path.clone(), tags, labels.custom_fields(), &CancellationToken::new()).await {
+            tracing::warn!("Failed to create metadata for {}: {}", outcome.s3_key, e);
+        }
+    }
+    
+    Ok(outcome)
+}
+
+/// ファイル内容をS3へ送信（サイズに応じて単純アップロード・マルチパートを切り替え）
+async fn upload_file_contents(
+    file_path: String,
+    s3_key: String,
+    config: &UploadConfig,
+    progress_tx: mpsc::Sender<UploadProgress>,
+    item_id: String,
     s3_client: &dyn S3ClientTrait,
+    object_metadata: HashMap<String, String>,
 ) -> Result<String, String> {
     use std::path::Path;
     use tokio::fs::File;
     use tokio::io::AsyncReadExt;
     
-    log::info!("Starting upload for file: {} -> s3://{}/{}", file_path, config.bucket_name, s3_key);
+    tracing::info!("Starting upload for file: {} -> s3://{}/{}", file_path, config.bucket_name, s3_key);
     
     // 🔍 受信した設定の全詳細をデバッグ出力
-    log::info!("🔧 === アップロード設定詳細 ===");
-    log::info!("🔧 tier: {:?}", config.tier);
-    log::info!("🔧 chunk_size_mb: {}", config.chunk_size_mb);
-    log::info!("🔧 max_concurrent_uploads: {}", config.max_concurrent_uploads);
-    log::info!("🔧 max_concurrent_parts: {}", config.max_concurrent_parts);
-    log::info!("🔧 adaptive_chunk_size: {}", config.adaptive_chunk_size);
-    log::info!("🔧 min_chunk_size_mb: {}", config.min_chunk_size_mb);
-    log::info!("🔧 max_chunk_size_mb: {}", config.max_chunk_size_mb);
-    log::info!("🔧 retry_attempts: {}", config.retry_attempts);
-    log::info!("🔧 timeout_seconds: {}", config.timeout_seconds);
-    log::info!("🔧 enable_resume: {}", config.enable_resume);
-    log::info!("🔧 ========================");
+    tracing::info!("🔧 === アップロード設定詳細 ===");
+    tracing::info!("🔧 tier: {:?}", config.tier);
+    tracing::info!("🔧 chunk_size_mb: {}", config.chunk_size_mb);
+    tracing::info!("🔧 max_concurrent_uploads: {}", config.max_concurrent_uploads);
+    tracing::info!("🔧 max_concurrent_parts: {}", config.max_concurrent_parts);
+    tracing::info!("🔧 adaptive_chunk_size: {}", config.adaptive_chunk_size);
+    tracing::info!("🔧 min_chunk_size_mb: {}", config.min_chunk_size_mb);
+    tracing::info!("🔧 max_chunk_size_mb: {}", config.max_chunk_size_mb);
+    tracing::info!("🔧 retry_attempts: {}", config.retry_attempts);
+    tracing::info!("🔧 timeout_seconds: {}", config.timeout_seconds);
+    tracing::info!("🔧 enable_resume: {}", config.enable_resume);
+    tracing::info!("🔧 ========================");
     
     // ファイル存在確認
     let path = Path::new(&file_path);
@@ -891,44 +2794,25 @@ async fn upload_file_to_s3(
         return Err(format!("File does not exist: {}", file_path));
     }
     
-    let file_size = std::fs::metadata(&path)
-        .map_err(|e| format!("Failed to get file metadata: {}", e))?
-        .len();
+    // 読み込み中の上書き保存を検出するため、開始時のサイズと更新日時を記録
+    let source = SourceFingerprint::capture(path)?;
+    let file_size = source.size;
+    
+    // 同一ボリュームからの同時読み込み数を制限（HDDのシーク競合を避ける）
+    let read_guard = READ_SCHEDULER.guard_for_file(path, file_size, &config.read_concurrency).await;
+    if read_guard.mode() == ReadMode::WholeFile {
+        tracing::info!("Reading {} sequentially to avoid seek contention on the same disk", file_path);
+    }
     
     let start_time = Instant::now();
     let mut uploaded_bytes = 0u64;
     
     // 進捗レポート用のクロージャ
-    let report_progress = |uploaded: u64, total: u64, speed_mbps: f64| {
-        let percentage = if total > 0 {
-            (uploaded as f64 / total as f64) * 100.0
-        } else {
-            0.0
-        };
-        
-        let eta_seconds = if speed_mbps > 0.0 {
-            let remaining_mb = (total - uploaded) as f64 / (1024.0 * 1024.0);
-            Some((remaining_mb / speed_mbps) as u64)
-        } else {
-            None
-        };
-        
-        let progress = UploadProgress {
-            item_id: item_id.clone(),
-            uploaded_bytes: uploaded,
-            total_bytes: total,
-            percentage,
-            speed_mbps,
-            eta_seconds,
-            status: if uploaded >= total {
-                UploadStatus::Completed
-            } else {
-                UploadStatus::InProgress
-            },
-        };
+    let report_progress = |uploaded: u64, total: u64, speed_mbps: f64, part: Option<PartPosition>| {
+        let progress = build_upload_progress(&item_id, uploaded, total, speed_mbps, part);
         
         if let Err(e) = progress_tx.try_send(progress) {
-            log::warn!("Failed to send progress update: {}", e);
+            tracing::warn!("Failed to send progress update: {}", e);
         }
     };
     
@@ -938,29 +2822,33 @@ async fn upload_file_to_s3(
     let effective_chunk_size = std::cmp::max(configured_size, s3_min_size);
     
     // 🔍 チャンクサイズ計算の詳細をデバッグ出力
-    log::info!("🔧 === チャンクサイズ計算 ===");
-    log::info!("🔧 config.chunk_size_mb: {}", config.chunk_size_mb);
-    log::info!("🔧 configured_size (bytes): {}", configured_size);
-    log::info!("🔧 s3_min_size (bytes): {}", s3_min_size);
-    log::info!("🔧 effective_chunk_size (bytes): {}", effective_chunk_size);
-    log::info!("🔧 effective_chunk_size (MB): {}", effective_chunk_size / (1024 * 1024));
-    log::info!("🔧 ========================");
+    tracing::info!("🔧 === チャンクサイズ計算 ===");
+    tracing::info!("🔧 config.chunk_size_mb: {}", config.chunk_size_mb);
+    tracing::info!("🔧 configured_size (bytes): {}", configured_size);
+    tracing::info!("🔧 s3_min_size (bytes): {}", s3_min_size);
+    tracing::info!("🔧 effective_chunk_size (bytes): {}", effective_chunk_size);
+    tracing::info!("🔧 effective_chunk_size (MB): {}", effective_chunk_size / (1024 * 1024));
+    tracing::info!("🔧 ========================");
     
     if effective_chunk_size != configured_size {
-        log::warn!("⚠️ Chunk size adjusted for S3 compliance: {} MB -> {} MB", 
+        tracing::warn!("⚠️ Chunk size adjusted for S3 compliance: {} MB -> {} MB", 
                   configured_size / (1024 * 1024), effective_chunk_size / (1024 * 1024));
     }
     
     // 小さなファイルの場合は単純アップロード（調整後のチャンクサイズで判定）
     if file_size <= effective_chunk_size {
-        log::info!("Using simple upload for small file: {} bytes", file_size);
+        tracing::info!("Using simple upload for small file: {} bytes", file_size);
         
         let mut file = File::open(&path).await
             .map_err(|e| format!("Failed to open file: {}", e))?;
         
         let mut buffer = Vec::new();
+        let read_permit = read_guard.chunk_permit().await;
         file.read_to_end(&mut buffer).await
             .map_err(|e| format!("Failed to read file: {}", e))?;
+        drop(read_permit);
+        source.ensure_unchanged(path)?;
+        BANDWIDTH_LIMITER.acquire(buffer.len() as u64, config).await;
         
         uploaded_bytes = buffer.len() as u64;
         
@@ -972,26 +2860,44 @@ async fn upload_file_to_s3(
             0.0
         };
         
-        report_progress(uploaded_bytes, file_size, speed_mbps);
+        report_progress(uploaded_bytes, file_size, speed_mbps, None);
         
-        s3_client
-            .put_object(&config.bucket_name, &s3_key, buffer)
-            .await?;
+        match compute_checksum(config.checksum_algorithm, &buffer) {
+            Some(checksum) => {
+                s3_client
+                    .put_object_with_checksum(&config.bucket_name, &s3_key, buffer, object_metadata, config.storage_class.as_deref(), &checksum)
+                    .await?;
+                verify_uploaded_checksum(s3_client, &config.bucket_name, &s3_key, checksum.algorithm, &checksum.value).await?;
+            }
+            None => {
+                s3_client
+                    .put_object_with_metadata(&config.bucket_name, &s3_key, buffer, object_metadata, config.storage_class.as_deref())
+                    .await?;
+            }
+        }
         
-        log::info!("Simple upload completed: {} bytes", uploaded_bytes);
+        tracing::info!("Simple upload completed: {} bytes", uploaded_bytes);
         
     } else {
         // マルチパートアップロード
-        log::info!("Using multipart upload for large file: {} bytes", file_size);
+        tracing::info!("Using multipart upload for large file: {} bytes", file_size);
         
+        let checksum_algorithm = config.checksum_algorithm;
         let upload_id = s3_client
-            .create_multipart_upload(&config.bucket_name, &s3_key)
+            .create_multipart_upload_with_checksum(&config.bucket_name, &s3_key, object_metadata, config.storage_class.as_deref(), checksum_algorithm)
             .await?;
+        register_multipart_upload(&item_id, ActiveMultipartUpload {
+            bucket: config.bucket_name.clone(),
+            key: s3_key.clone(),
+            upload_id: upload_id.clone(),
+        });
         
         // 事前計算されたチャンクサイズを使用
         let chunk_size = effective_chunk_size;
+        let total_parts = file_size.div_ceil(chunk_size) as u32;
         let mut part_number = 1;
         let mut completed_parts = Vec::new();
+        let mut part_checksums = Vec::new();
         
         let mut file = File::open(&path).await
             .map_err(|e| format!("Failed to open file: {}", e))?;
@@ -1000,13 +2906,15 @@ async fn upload_file_to_s3(
             let mut buffer = vec![0u8; chunk_size as usize];
             
             // 🔍 バッファサイズをデバッグ出力
-            log::info!("🔧 Reading chunk: buffer_size={} bytes ({} MB)", 
+            tracing::info!("🔧 Reading chunk: buffer_size={} bytes ({} MB)", 
                        buffer.len(), buffer.len() / (1024 * 1024));
             
             // 完全にチャンクサイズを読み込むまでループ
             let mut total_bytes_read = 0;
             let mut temp_buffer = vec![0u8; chunk_size as usize];
             
+            // 読み込み中のみ枠を確保し、パーツ送信中は他ファイルに譲る
+            let read_permit = read_guard.chunk_permit().await;
             while total_bytes_read < chunk_size as usize {
                 let bytes_read = file.read(&mut temp_buffer[total_bytes_read..]).await
                     .map_err(|e| format!("Failed to read file chunk: {}", e))?;
@@ -1018,9 +2926,15 @@ async fn upload_file_to_s3(
                 
                 total_bytes_read += bytes_read;
             }
+            drop(read_permit);
+            
+            // チャンクごとに変更を確認し、送信済みのパーツは中断して破棄する
+            if let Err(e) = source.ensure_unchanged(path) {
+                return Err(abort_on_source_change(s3_client, &item_id, e).await);
+            }
             
             // 🔍 実際の読み込みサイズをデバッグ出力
-            log::info!("🔧 Read result: total_bytes_read={} bytes ({} MB)", 
+            tracing::info!("🔧 Read result: total_bytes_read={} bytes ({} MB)", 
                        total_bytes_read, total_bytes_read / (1024 * 1024));
             
             if total_bytes_read == 0 {
@@ -1031,11 +2945,27 @@ async fn upload_file_to_s3(
             temp_buffer.truncate(total_bytes_read);
             buffer = temp_buffer;
             
-            let etag = s3_client
-                .upload_part(&config.bucket_name, &s3_key, &upload_id, part_number, buffer)
-                .await?;
+            // 時間帯別の帯域制限はパーツ単位で反映（進行中のアップロードも次のパーツから切り替わる）
+            BANDWIDTH_LIMITER.acquire(total_bytes_read as u64, config).await;
+            let part = PartPosition {
+                current_part: part_number as u32,
+                total_parts,
+                part_uploaded_bytes: 0,
+                part_total_bytes: total_bytes_read as u64,
+            };
+            let part_hook = part_progress_hook(&item_id, &progress_tx, start_time, uploaded_bytes, file_size, part);
+            let checksum = compute_checksum(checksum_algorithm, &buffer);
+            let etag = upload_part_with_retry(
+                s3_client,
+                config,
+                &s3_key,
+                &upload_id,
+                PartUpload { part_number, data: buffer, checksum: checksum.clone() },
+                part_hook,
+            ).await?;
             
             completed_parts.push((part_number, etag));
+            part_checksums.extend(checksum);
             
             uploaded_bytes += total_bytes_read as u64;
             part_number += 1;
@@ -1048,23 +2978,28 @@ async fn upload_file_to_s3(
                 0.0
             };
             
-            report_progress(uploaded_bytes, file_size, speed_mbps);
+            report_progress(uploaded_bytes, file_size, speed_mbps, Some(PartPosition { part_uploaded_bytes: part.part_total_bytes, ..part }));
             
-            log::info!("Uploaded part {}: {} bytes (total: {}/{})", 
+            tracing::info!("Uploaded part {}: {} bytes (total: {}/{})", 
                        part_number - 1, total_bytes_read, uploaded_bytes, file_size);
         }
         
+        // 最後のパーツの送信中に変更された場合も完了させない
+        if let Err(e) = source.ensure_unchanged(path) {
+            return Err(abort_on_source_change(s3_client, &item_id, e).await);
+        }
+        
         // マルチパートアップロード完了（エラーハンドリング強化）
-        log::info!("🔧 Completing multipart upload with {} parts", completed_parts.len());
+        tracing::info!("🔧 Completing multipart upload with {} parts", completed_parts.len());
         
         // パーツを部品番号順にソート（重要）
         let mut sorted_parts = completed_parts;
         sorted_parts.sort_by_key(|(part_number, _)| *part_number);
         
         // デバッグ情報（詳細）
-        log::info!("🔧 Preparing to complete multipart upload with {} parts:", sorted_parts.len());
+        tracing::info!("🔧 Preparing to complete multipart upload with {} parts:", sorted_parts.len());
         for (i, (part_number, etag)) in sorted_parts.iter().enumerate() {
-            log::info!("  Part {}: number={}, etag={}", i + 1, part_number, etag);
+            tracing::info!("  Part {}: number={}, etag={}", i + 1, part_number, etag);
         }
         
         let parts_count = sorted_parts.len();
@@ -1073,41 +3008,59 @@ async fn upload_file_to_s3(
         let mut retry_count = 0;
         let max_retries = 3;
         
+        // パートは番号順に送信しているため、チェックサムも同じ順に並んでいる
+        let checksummed_parts: Vec<(i32, String, String)> = sorted_parts.iter().cloned()
+            .zip(&part_checksums)
+            .map(|((part_number, etag), checksum)| (part_number, etag, checksum.value.clone()))
+            .collect();
+        
         loop {
-            match s3_client
-                .complete_multipart_upload(&config.bucket_name, &s3_key, &upload_id, sorted_parts.clone())
-                .await
-            {
+            let completed = if checksum_algorithm.is_enabled() {
+                s3_client
+                    .complete_multipart_upload_with_checksums(&config.bucket_name, &s3_key, &upload_id, checksum_algorithm, checksummed_parts.clone())
+                    .await
+            } else {
+                s3_client
+                    .complete_multipart_upload(&config.bucket_name, &s3_key, &upload_id, sorted_parts.clone())
+                    .await
+            };
+            match completed {
                 Ok(_) => {
-                    log::info!("✅ Multipart upload completed successfully");
+                    tracing::info!("✅ Multipart upload completed successfully");
                     break;
                 }
                 Err(e) => {
                     retry_count += 1;
                     
                     // 詳細なエラー情報をログ出力
-                    log::error!("🔍 Multipart upload completion error details:");
-                    log::error!("  ├─ Error: {:?}", e);
-                    log::error!("  ├─ Bucket: {}", config.bucket_name);
-                    log::error!("  ├─ Key: {}", s3_key);
-                    log::error!("  ├─ Upload ID: {}", upload_id);
-                    log::error!("  ├─ Parts count: {}", parts_count);
-                    log::error!("  └─ Attempt: {}/{}", retry_count, max_retries);
+                    tracing::error!("🔍 Multipart upload completion error details:");
+                    tracing::error!("  ├─ Error: {:?}", e);
+                    tracing::error!("  ├─ Bucket: {}", config.bucket_name);
+                    tracing::error!("  ├─ Key: {}", s3_key);
+                    tracing::error!("  ├─ Upload ID: {}", upload_id);
+                    tracing::error!("  ├─ Parts count: {}", parts_count);
+                    tracing::error!("  └─ Attempt: {}/{}", retry_count, max_retries);
                     
                     if retry_count > max_retries {
-                        log::error!("❌ Multipart upload completion failed after {} retries: {}", max_retries, e);
+                        tracing::error!("❌ Multipart upload completion failed after {} retries: {}", max_retries, e);
                         return Err(format!("Failed to complete multipart upload after {} retries: {}", max_retries, e));
                     }
                     
                     let delay = Duration::from_millis(1000 * retry_count as u64);
-                    log::warn!("⚠️ Multipart upload completion failed (attempt {}), retrying in {:?}: {}", 
+                    tracing::warn!("⚠️ Multipart upload completion failed (attempt {}), retrying in {:?}: {}", 
                               retry_count, delay, e);
                     tokio::time::sleep(delay).await;
                 }
             }
         }
         
-        log::info!("Multipart upload completed: {} bytes in {} parts", uploaded_bytes, part_number - 1);
+        unregister_multipart_upload(&item_id);
+        tracing::info!("Multipart upload completed: {} bytes in {} parts", uploaded_bytes, part_number - 1);
+        
+        if checksum_algorithm.is_enabled() {
+            let expected = composite_checksum(checksum_algorithm, &part_checksums)?;
+            verify_uploaded_checksum(s3_client, &config.bucket_name, &s3_key, checksum_algorithm, &expected).await?;
+        }
     }
     
     // 最終進捗レポート
@@ -1118,98 +3071,245 @@ async fn upload_file_to_s3(
         0.0
     };
     
-    report_progress(uploaded_bytes, file_size, speed_mbps);
+    report_progress(uploaded_bytes, file_size, speed_mbps, None);
     
-    // メタデータ作成（設定されている場合）
-    if config.auto_create_metadata {
-        use std::collections::HashMap;
-        let tags = vec!["upload".to_string()];
-        let custom_fields = HashMap::new();
-        if let Err(e) = create_file_metadata(file_path.clone(), tags, custom_fields).await {
-            log::warn!("Failed to create metadata for {}: {}", s3_key, e);
+    Ok(format!("Upload completed: {} bytes", uploaded_bytes))
+}
+
+/// パートを送信し、失敗時はretry_attempts回まで待機して再送
+async fn upload_part_with_retry(
+    s3_client: &dyn S3ClientTrait,
+    config: &UploadConfig,
+    s3_key: &str,
+    upload_id: &str,
+    part: PartUpload,
+    progress: PartProgressHook,
+) -> Result<String, String> {
+    let part_number = part.part_number;
+    let mut attempt = 0;
+    loop {
+        let started = Instant::now();
+        match s3_client
+            .upload_part_with_checksum(&config.bucket_name, s3_key, upload_id, part.clone(), progress.clone())
+            .await
+        {
+            Ok(etag) => {
+                METRICS.part_upload_duration.record(started.elapsed());
+                return Ok(etag);
+            }
+            Err(e) if attempt < config.retry_attempts => {
+                attempt += 1;
+                MetricsRegistry::increment(&METRICS.part_retries);
+                let delay = PART_RETRY_BASE_DELAY * attempt;
+                tracing::warn!("⚠️ Part {} upload failed (attempt {}/{}), retrying in {:?}: {}",
+                          part_number, attempt, config.retry_attempts, delay, e);
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => {
+                tracing::error!("❌ Part {} upload failed after {} retries: {}", part_number, attempt, e);
+                return Err(e);
+            }
+        }
+    }
+}
+
+/// 終了時に永続化するアイテムを抽出
+/// 完了・失敗・キャンセル済みは除外し、進行中のものは次回起動時に最初からやり直すため待機中に戻す
+pub fn items_to_persist(items: &[UploadItem]) -> Vec<UploadItem> {
+    items.iter()
+        .filter(|item| matches!(
+            item.status,
+            UploadStatus::Pending | UploadStatus::InProgress | UploadStatus::Paused
+        ))
+        .cloned()
+        .map(|mut item| {
+            if item.status == UploadStatus::InProgress {
+                item.status = UploadStatus::Pending;
+                item.progress = 0.0;
+                item.uploaded_bytes = 0;
+                item.speed_mbps = 0.0;
+                item.eta_seconds = None;
+                item.started_at = None;
+            }
+            item
+        })
+        .collect()
+}
+
+fn queue_state_path(app_handle: &AppHandle) -> Result<std::path::PathBuf, InternalError> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| InternalError::Config(format!("Failed to get app data directory: {}", e)))?;
+    std::fs::create_dir_all(&app_data_dir)?;
+    Ok(app_data_dir.join(QUEUE_STATE_FILE))
+}
+
+/// 未完了のアイテムをファイルに保存（次回起動時にinitialize_upload_queueで復元）
+pub fn persist_queue_state(app_handle: &AppHandle, queue_state: &UploadQueueState) -> Result<usize, InternalError> {
+    let items = {
+        let queue = queue_state.lock()
+            .map_err(queue_lock_error)?;
+        items_to_persist(&queue.items)
+    };
+    
+    let path = queue_state_path(app_handle)?;
+    if items.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(&path)?;
         }
+        return Ok(0);
     }
     
-    Ok(format!("Upload completed: {} bytes", uploaded_bytes))
+    std::fs::write(&path, serde_json::to_string_pretty(&items)?)?;
+    tracing::info!("Persisted {} unfinished upload(s) to {}", items.len(), path.display());
+    Ok(items.len())
+}
+
+/// 保存済みのアイテムを読み込み、ファイルを削除（二重に復元しないため）
+fn take_persisted_queue_items(app_handle: &AppHandle) -> Vec<UploadItem> {
+    let Ok(path) = queue_state_path(app_handle) else {
+        return Vec::new();
+    };
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    if let Err(e) = std::fs::remove_file(&path) {
+        tracing::warn!("Failed to remove persisted upload queue state: {}", e);
+    }
+    match serde_json::from_str(&content) {
+        Ok(items) => items,
+        Err(e) => {
+            tracing::warn!("Ignoring invalid persisted upload queue state: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// 未完了のマルチパートアップロードを破棄（失敗はログ出力のみ）
+/// 戻り値は破棄に成功した件数
+pub async fn abort_multipart_uploads(s3_client: &dyn S3ClientTrait, uploads: &[ActiveMultipartUpload]) -> usize {
+    let mut aborted = 0;
+    for upload in uploads {
+        match s3_client.abort_multipart_upload(&upload.bucket, &upload.key, &upload.upload_id).await {
+            Ok(()) => {
+                tracing::info!("Aborted multipart upload: s3://{}/{} ({})", upload.bucket, upload.key, upload.upload_id);
+                aborted += 1;
+            }
+            Err(e) => {
+                tracing::error!("Failed to abort multipart upload s3://{}/{}: {}", upload.bucket, upload.key, e);
+            }
+        }
+    }
+    aborted
+}
+
+/// 破棄待ちのマルチパートアップロードをバックグラウンドで破棄
+pub fn spawn_abort_multipart_uploads(app: &AppHandle, uploads: Vec<ActiveMultipartUpload>, region: String) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match create_cached_s3_client(&app, &region).await {
+            Ok(client) => {
+                let aborted = abort_multipart_uploads(client.as_ref(), &uploads).await;
+                tracing::info!("Aborted {}/{} interrupted multipart upload(s)", aborted, uploads.len());
+            }
+            Err(e) => tracing::error!("Failed to create S3 client to abort interrupted multipart uploads: {}", e),
+        }
+    });
+}
+
+/// アプリ終了に向けて進行中のアップロードを中断し、未完了パーツを破棄
+/// 戻り値は中断した進行中アイテムの件数
+pub async fn abort_active_uploads(app: &AppHandle, queue_state: &UploadQueueState) -> Result<usize, InternalError> {
+    let (in_progress, region) = {
+        let mut queue = queue_state.lock()
+            .map_err(queue_lock_error)?;
+        queue.is_processing = false;
+        for (_, task) in queue.upload_tasks.drain() {
+            task.abort();
+        }
+        queue.active_uploads.clear();
+        queue.active_upload_count = 0;
+        (queue.in_progress_count(), queue.config.as_ref().map(|config| config.region.clone()))
+    };
+    
+    let mut uploads: Vec<ActiveMultipartUpload> = ACTIVE_MULTIPART_UPLOADS.lock()
+        .map(|mut uploads| uploads.drain().map(|(_, upload)| upload).collect())
+        .unwrap_or_default();
+    if let Ok(mut queue) = queue_state.lock() {
+        uploads.append(&mut queue.stale_multipart_uploads);
+    }
+    if uploads.is_empty() {
+        return Ok(in_progress);
+    }
+    
+    let Some(region) = region else {
+        tracing::warn!("Upload configuration not initialized, leaving {} multipart upload(s) unaborted", uploads.len());
+        return Ok(in_progress);
+    };
+    let client = create_cached_s3_client(app, &region).await?;
+    abort_multipart_uploads(client.as_ref(), &uploads).await;
+    
+    Ok(in_progress)
 }
 
 /// アップロードキューをクリア
 #[command]
 pub async fn clear_upload_queue(
+    app_handle: AppHandle,
     queue_state: State<'_, UploadQueueState>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     let mut queue = queue_state.lock()
-        .map_err(|e| format!("Failed to lock upload queue: {}", e))?;
+        .map_err(queue_lock_error)?;
     
     // アクティブなアップロードがある場合は停止
     if !queue.active_uploads.is_empty() {
         queue.is_processing = false;
     }
     
+    discard_removed_bundle_files(queue.items.iter());
     queue.items.clear();
     queue.active_uploads.clear();
+    drop(queue);
+    crate::badge::update_badge(&app_handle, queue_state.inner());
+    crate::tray::refresh_upload_status(&app_handle, queue_state.inner());
     
-    log::info!("Upload queue cleared");
-    Ok("Upload queue cleared".to_string())
+    tracing::info!("Upload queue cleared");
+    Ok(tr(MessageKey::UploadQueueCleared))
 }
 
 /// アップロード設定をテスト
 #[command]
-pub async fn test_upload_config(config: UploadConfig) -> Result<String, String> {
-    use aws_config::{BehaviorVersion, Region};
-    use aws_sdk_s3::Client as S3Client;
-    use aws_credential_types::Credentials;
-    
-    // AWS認証テスト
-    let region = Region::new(config.aws_credentials.region.clone());
-    let mut config_builder = aws_config::defaults(BehaviorVersion::latest())
-        .region(region);
-    
-    let creds = Credentials::new(
-        &config.aws_credentials.access_key_id,
-        &config.aws_credentials.secret_access_key,
-        config.aws_credentials.session_token.clone(),
-        None,
-        "test",
-    );
-    
-    config_builder = config_builder.credentials_provider(creds);
-    let aws_config = config_builder.load().await;
-    let s3_client = S3Client::new(&aws_config);
+pub async fn test_upload_config(app_handle: AppHandle, config: UploadConfig) -> Result<String, AppError> {
+    let mut credentials = cached_credentials_in_region(&app_handle, "")?;
+    correct_credentials_region(&app_handle, &mut credentials, &config.bucket_name).await;
+
+    // AWS認証テスト（カスタムエンドポイント指定時はそちらに接続）
+    let s3_client = create_s3_client_for_credentials(&credentials).await
+        .map_err(InternalError::s3)?;
     
     // バケットアクセステスト
     s3_client
-        .head_bucket()
-        .bucket(&config.bucket_name)
-        .send()
+        .head_bucket(&config.bucket_name)
         .await
-        .map_err(|e| format!("Bucket access test failed: {}", e))?;
+        .map_err(InternalError::s3)?;
     
-    Ok(format!("Upload configuration test successful for bucket: {}", config.bucket_name))
+    Ok(label_message(format!("Upload configuration test successful for bucket: {}", config.bucket_name)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::collections::HashMap;
+    use crate::commands::aws_operations::MockS3Client;
     use tempfile::tempdir;
     use std::fs::File;
     use std::io::Write;
 
-    #[cfg(test)]
-    fn create_test_credentials() -> AwsCredentials {
-        AwsCredentials {
-            access_key_id: "test_access_key".to_string(),
-            secret_access_key: "test_secret_key".to_string(),
-            region: "ap-northeast-1".to_string(),
-            session_token: None,
-        }
-    }
-
     #[cfg(test)]
     fn create_test_upload_config() -> UploadConfig {
         UploadConfig {
-            aws_credentials: create_test_credentials(),
+            region: "ap-northeast-1".to_string(),
             bucket_name: "test-bucket".to_string(),
             max_concurrent_uploads: 8,
             chunk_size_mb: 10,
@@ -1224,6 +3324,21 @@ mod tests {
             bandwidth_limit_mbps: None,
             enable_resume: true,
             tier: UploadTier::Premium,
+            compress_non_video: false,
+            compression_level: 3,
+            block_uncovered_lifecycle_prefix: false,
+            read_concurrency: ReadConcurrencyLimits::default(),
+            set_finder_tag_on_complete: None,
+            set_readonly_after_upload: false,
+            set_immutable_flag_after_upload: false,
+            write_local_manifest: false,
+            upload_manifest_to_s3: false,
+            follow_symlinks: true,
+            requeue_after_source_change_secs: None,
+            mirror_targets: Vec::new(),
+            storage_class: None,
+            per_type_overrides: Vec::new(),
+            checksum_algorithm: ChecksumAlgorithm::None,
         }
     }
 
@@ -1243,6 +3358,7 @@ mod tests {
         assert_eq!(queue.items.len(), 0);
         assert_eq!(queue.active_uploads.len(), 0);
         assert!(!queue.is_processing);
+        assert!(!queue.is_paused);
         assert_eq!(queue.total_uploaded_bytes, 0);
         assert_eq!(queue.total_files_uploaded, 0);
     }
@@ -1291,6 +3407,21 @@ mod tests {
         assert!(result.len() > "test.mp4".len()); // タイムスタンプが追加されている
     }
 
+    #[test]
+    fn test_s3_key_generation_normalizes_japanese_file_name_to_nfc() {
+        let config = S3KeyConfig {
+            prefix: Some("プロジェクト".to_string()),
+            use_date_folder: false,
+            preserve_directory_structure: false,
+            custom_naming_pattern: None,
+        };
+
+        // macOSが返すNFD形式の「ポートフォリオ.mp4」
+        let nfd_path = "/Users/editor/\u{30DB}\u{309A}\u{30FC}\u{30C8}\u{30D5}\u{30A9}\u{30EA}\u{30AA}.mp4";
+        let result = generate_s3_key(nfd_path, &config).unwrap();
+        assert_eq!(result, "プロジェクト/ポートフォリオ.mp4");
+    }
+
     #[tokio::test]
     async fn test_upload_queue_initialization() {
         let queue = Arc::new(Mutex::new(UploadQueue::new()));
@@ -1326,21 +3457,9 @@ mod tests {
         let s3_key = generate_s3_key(&file_path_str, &s3_key_config).unwrap();
 
         let item = UploadItem {
-            id: Uuid::new_v4().to_string(),
-            file_path: file_path_str.clone(),
-            file_name: "test_video.mp4".to_string(),
             file_size: 18, // "test video content".len()
             s3_key,
-            status: UploadStatus::Pending,
-            progress: 0.0,
-            uploaded_bytes: 0,
-            speed_mbps: 0.0,
-            eta_seconds: None,
-            created_at: chrono::Utc::now().to_rfc3339(),
-            started_at: None,
-            completed_at: None,
-            error_message: None,
-            retry_count: 0,
+            ..UploadItem::for_test(&file_path_str, UploadStatus::Pending)
         };
 
         {
@@ -1363,20 +3482,13 @@ mod tests {
         for i in 0..5 {
             let item = UploadItem {
                 id: format!("item_{}", i),
-                file_path: format!("/test/file_{}.mp4", i),
-                file_name: format!("file_{}.mp4", i),
                 file_size: 1000,
-                s3_key: format!("uploads/file_{}.mp4", i),
-                status: if i < 2 { UploadStatus::Completed } else if i < 4 { UploadStatus::Pending } else { UploadStatus::Failed },
                 progress: if i < 2 { 100.0 } else { 0.0 },
                 uploaded_bytes: if i < 2 { 1000 } else { 0 },
-                speed_mbps: 0.0,
-                eta_seconds: None,
-                created_at: chrono::Utc::now().to_rfc3339(),
-                started_at: None,
-                completed_at: None,
-                error_message: None,
-                retry_count: 0,
+                ..UploadItem::for_test(
+                    &format!("/test/file_{}.mp4", i),
+                    if i < 2 { UploadStatus::Completed } else if i < 4 { UploadStatus::Pending } else { UploadStatus::Failed },
+                )
             };
             queue.items.push(item);
         }
@@ -1402,20 +3514,8 @@ mod tests {
     fn test_upload_item_status_transitions() {
         let mut item = UploadItem {
             id: "test_item".to_string(),
-            file_path: "/test/file.mp4".to_string(),
-            file_name: "file.mp4".to_string(),
             file_size: 1000,
-            s3_key: "uploads/file.mp4".to_string(),
-            status: UploadStatus::Pending,
-            progress: 0.0,
-            uploaded_bytes: 0,
-            speed_mbps: 0.0,
-            eta_seconds: None,
-            created_at: chrono::Utc::now().to_rfc3339(),
-            started_at: None,
-            completed_at: None,
-            error_message: None,
-            retry_count: 0,
+            ..UploadItem::for_test("/test/file.mp4", UploadStatus::Pending)
         };
 
         // Pending -> InProgress
@@ -1460,9 +3560,7 @@ mod tests {
     fn test_upload_config_validation() {
         let config = create_test_upload_config();
         
-        assert!(!config.aws_credentials.access_key_id.is_empty());
-        assert!(!config.aws_credentials.secret_access_key.is_empty());
-        assert!(!config.aws_credentials.region.is_empty());
+        assert!(!config.region.is_empty());
         assert!(!config.bucket_name.is_empty());
         assert!(config.max_concurrent_uploads > 0);
         assert!(config.chunk_size_mb > 0);
@@ -1487,7 +3585,6 @@ mod tests {
     
     #[tokio::test]
     async fn test_upload_file_to_s3_with_mock_simple_upload() {
-        let credentials = create_test_credentials();
         let config = create_test_upload_config();
         let (tx, _rx) = mpsc::channel::<UploadProgress>(10);
         let item_id = "test-item-1".to_string();
@@ -1500,25 +3597,26 @@ mod tests {
         let s3_key = "test/upload/test_file.txt".to_string();
         
         // MockS3Clientを使用
-        let mock_client = MockS3Client;
+        let mock_client = MockS3Client::new();
         
-        let result = upload_file_to_s3(
+        let result = upload_file_to_s3_internal(
+            &mock_client,
             test_file_path.to_string_lossy().to_string(),
             s3_key,
             config,
             tx,
             item_id,
-            &mock_client,
         ).await;
         
         assert!(result.is_ok());
-        let result_str = result.unwrap();
-        assert!(result_str.contains("Upload completed"));
+        let outcome = result.unwrap();
+        assert_eq!(outcome.s3_key, "test/upload/test_file.txt");
+        assert_eq!(outcome.uploaded_bytes, 13);
+        assert_eq!(outcome.saved_bytes(), 0);
     }
     
     #[tokio::test]
     async fn test_upload_file_to_s3_with_mock_multipart_upload() {
-        let credentials = create_test_credentials();
         let mut config = create_test_upload_config();
         // チャンクサイズを小さくしてマルチパートアップロードを強制
         config.chunk_size_mb = 1; // 1MB
@@ -1534,25 +3632,49 @@ mod tests {
         let s3_key = "test/upload/large_test_file.bin".to_string();
         
         // MockS3Clientを使用
-        let mock_client = MockS3Client;
+        let mock_client = MockS3Client::new();
         
-        let result = upload_file_to_s3(
+        let result = upload_file_to_s3_internal(
+            &mock_client,
             test_file_path.to_string_lossy().to_string(),
             s3_key,
             config,
             tx,
             item_id,
-            &mock_client,
         ).await;
         
         assert!(result.is_ok());
-        let result_str = result.unwrap();
-        assert!(result_str.contains("Upload completed"));
+        let outcome = result.unwrap();
+        assert_eq!(outcome.uploaded_bytes, 6 * 1024 * 1024);
+    }
+    
+    #[tokio::test]
+    async fn test_upload_file_to_s3_compresses_non_video_files() {
+        let mut config = create_test_upload_config();
+        config.compress_non_video = true;
+        let (tx, _rx) = mpsc::channel::<UploadProgress>(100);
+        
+        let temp_dir = tempfile::tempdir().unwrap();
+        let test_file_path = temp_dir.path().join("render.log");
+        std::fs::write(&test_file_path, "frame rendered\n".repeat(200_000)).unwrap();
+        
+        let result = upload_file_to_s3_internal(
+            &MockS3Client::new(),
+            test_file_path.to_string_lossy().to_string(),
+            "test/upload/render.log".to_string(),
+            config,
+            tx,
+            "test-item-compress".to_string(),
+        ).await;
+        
+        let outcome = result.unwrap();
+        assert_eq!(outcome.s3_key, "test/upload/render.log.zst");
+        assert!(outcome.uploaded_bytes < outcome.original_size);
+        assert!(outcome.saved_bytes() > 0);
     }
     
     #[tokio::test]
     async fn test_upload_file_to_s3_file_not_found() {
-        let credentials = create_test_credentials();
         let config = create_test_upload_config();
         let (tx, _rx) = mpsc::channel::<UploadProgress>(10);
         let item_id = "test-item-3".to_string();
@@ -1561,19 +3683,734 @@ mod tests {
         let s3_key = "test/upload/non_existent.txt".to_string();
         
         // MockS3Clientを使用
-        let mock_client = MockS3Client;
+        let mock_client = MockS3Client::new();
         
-        let result = upload_file_to_s3(
+        let result = upload_file_to_s3_internal(
+            &mock_client,
             non_existent_file,
             s3_key,
             config,
             tx,
             item_id,
-            &mock_client,
         ).await;
         
         assert!(result.is_err());
         let error_msg = result.unwrap_err();
         assert!(error_msg.contains("File does not exist"));
     }
-}
\ No newline at end of file
+    
+    fn create_multipart_test_file(dir: &tempfile::TempDir, size: usize) -> String {
+        let path = dir.path().join("multipart.bin");
+        std::fs::write(&path, vec![7u8; size]).unwrap();
+        path.to_string_lossy().to_string()
+    }
+    
+    #[tokio::test]
+    async fn test_multipart_upload_records_parts_in_order() {
+        let mut config = create_test_upload_config();
+        config.chunk_size_mb = 5;
+        config.auto_create_metadata = false;
+        let (tx, _rx) = mpsc::channel::<UploadProgress>(100);
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = create_multipart_test_file(&temp_dir, 12 * 1024 * 1024);
+        let mock_client = MockS3Client::new();
+        
+        let result = upload_file_to_s3_internal(
+            &mock_client,
+            file_path,
+            "test/upload/multipart.bin".to_string(),
+            config,
+            tx,
+            "test-item-parts".to_string(),
+        ).await;
+        
+        assert!(result.is_ok());
+        let calls = mock_client.calls();
+        let chunk = 5 * 1024 * 1024;
+        // 5MB未満の端数パートは最後にだけ来る
+        assert_eq!(calls.uploaded_parts, vec![(1, chunk), (2, chunk), (3, 2 * 1024 * 1024)]);
+        assert_eq!(calls.completed_parts.len(), 1);
+        let part_numbers: Vec<i32> = calls.completed_parts[0].iter().map(|(n, _)| *n).collect();
+        assert_eq!(part_numbers, vec![1, 2, 3]);
+        assert_eq!(calls.completed_parts[0][2].1, "mock-etag-3");
+        // チェックサム未指定では従来どおりETagのみ
+        assert!(calls.part_checksums.is_empty() && calls.object_checksums.is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_upload_verifies_checksum_for_each_algorithm() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let small_path = temp_dir.path().join("small.txt");
+        std::fs::write(&small_path, "Hello, World!").unwrap();
+        let large_path = create_multipart_test_file(&temp_dir, 12 * 1024 * 1024);
+        
+        for algorithm in [ChecksumAlgorithm::Crc32c, ChecksumAlgorithm::Sha256] {
+            let mut config = create_test_upload_config();
+            config.chunk_size_mb = 5;
+            config.auto_create_metadata = false;
+            config.checksum_algorithm = algorithm;
+            let mock_client = MockS3Client::new();
+            for (path, key) in [(small_path.to_string_lossy().to_string(), "small.txt"), (large_path.clone(), "large.bin")] {
+                let (tx, _rx) = mpsc::channel::<UploadProgress>(100);
+                let result = upload_file_to_s3_internal(&mock_client, path, key.to_string(), config.clone(), tx, "test-item-checksum".to_string()).await;
+                assert!(result.is_ok(), "{:?}: {:?}", algorithm, result);
+            }
+            
+            let calls = mock_client.calls();
+            let expected = crate::commands::upload_checksum::checksum_of(algorithm, b"Hello, World!").unwrap();
+            assert_eq!(calls.object_checksums["small.txt"], expected.value);
+            // マルチパートは各パートのチェックサムを送り、全体はCOMPOSITE形式
+            assert_eq!(calls.part_checksums.len(), 3);
+            assert!(calls.part_checksums.iter().all(|(_, checksum)| checksum.algorithm == algorithm));
+            assert!(calls.object_checksums["large.bin"].ends_with("-3"));
+        }
+        
+        // 保存後の値が一致しなければ失敗させる
+        let mut config = create_test_upload_config();
+        config.checksum_algorithm = ChecksumAlgorithm::Crc32c;
+        let (tx, _rx) = mpsc::channel::<UploadProgress>(10);
+        let result = upload_file_to_s3_internal(
+            &MockS3Client::corrupting_checksums(),
+            small_path.to_string_lossy().to_string(),
+            "small.txt".to_string(),
+            config,
+            tx,
+            "test-item-corrupt".to_string(),
+        ).await;
+        assert!(result.unwrap_err().starts_with(crate::commands::upload_checksum::CHECKSUM_MISMATCH_ERROR));
+    }
+    
+    #[tokio::test]
+    async fn test_multipart_upload_aborts_when_source_modified() {
+        let mut config = create_test_upload_config();
+        config.chunk_size_mb = 5;
+        config.auto_create_metadata = false;
+        let (tx, _rx) = mpsc::channel::<UploadProgress>(100);
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = create_multipart_test_file(&temp_dir, 12 * 1024 * 1024);
+        // 1つ目のパート送信後にユーザーが上書き保存した状況
+        let mock_client = MockS3Client::modifying_file_after_part(Path::new(&file_path), 1);
+        
+        let error = upload_file_to_s3_internal(
+            &mock_client,
+            file_path,
+            "test/upload/multipart.bin".to_string(),
+            config,
+            tx,
+            "test-item-modified".to_string(),
+        ).await.unwrap_err();
+        
+        assert!(is_source_modified_error(&error), "{}", error);
+        let calls = mock_client.calls();
+        assert_eq!(calls.uploaded_parts.len(), 1);
+        assert!(calls.completed_parts.is_empty());
+        assert_eq!(calls.aborted_upload_ids.len(), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_wait_for_stable_source_and_requeue_modified_item() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("edit.mov");
+        std::fs::write(&path, b"v1").unwrap();
+        let source = SourceFingerprint::capture(&path).unwrap();
+        std::fs::write(&path, b"version 2").unwrap();
+        assert!(source.ensure_unchanged(&path).is_err());
+        
+        let stable = wait_for_stable_source(&path, Duration::from_millis(30), Duration::from_millis(10)).await.unwrap();
+        assert_eq!(stable.size, 9);
+        assert!(wait_for_stable_source(&temp_dir.path().join("gone.mov"), Duration::from_millis(30), Duration::from_millis(10)).await.is_none());
+        
+        let mut queue = concurrency_test_queue(2, 2);
+        queue.start_upload("item-0").unwrap();
+        queue.complete_upload("item-0", false, Some(format!("{} (size 2 -> 9 bytes)", SOURCE_MODIFIED_ERROR)));
+        queue.items[1].status = UploadStatus::Failed;
+        queue.items[1].error_message = Some("timeout".to_string());
+        assert!(queue.requeue_modified_item("item-0", stable.size));
+        assert!(!queue.requeue_modified_item("item-1", stable.size));
+        assert_eq!((queue.items[0].status.clone(), queue.items[0].file_size), (UploadStatus::Pending, 9));
+    }
+    
+    #[tokio::test]
+    async fn test_multipart_upload_retries_failed_part() {
+        let mut config = create_test_upload_config();
+        config.chunk_size_mb = 5;
+        config.retry_attempts = 2;
+        config.auto_create_metadata = false;
+        let (tx, _rx) = mpsc::channel::<UploadProgress>(100);
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = create_multipart_test_file(&temp_dir, 6 * 1024 * 1024);
+        let mock_client = MockS3Client::failing_parts(1);
+        
+        let result = upload_file_to_s3_internal(
+            &mock_client,
+            file_path,
+            "test/upload/retry.bin".to_string(),
+            config,
+            tx,
+            "test-item-retry".to_string(),
+        ).await;
+        
+        assert!(result.is_ok());
+        let part_numbers: Vec<i32> = mock_client.calls().uploaded_parts.iter().map(|(n, _)| *n).collect();
+        assert_eq!(part_numbers, vec![1, 1, 2]);
+    }
+    
+    #[tokio::test]
+    async fn test_multipart_upload_fails_after_retries_exhausted() {
+        let mut config = create_test_upload_config();
+        config.chunk_size_mb = 5;
+        config.retry_attempts = 1;
+        config.auto_create_metadata = false;
+        let (tx, _rx) = mpsc::channel::<UploadProgress>(100);
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = create_multipart_test_file(&temp_dir, 6 * 1024 * 1024);
+        let mock_client = MockS3Client::failing_parts(2);
+        
+        let result = upload_file_to_s3_internal(
+            &mock_client,
+            file_path,
+            "test/upload/fail.bin".to_string(),
+            config,
+            tx,
+            "test-item-fail".to_string(),
+        ).await;
+        
+        assert!(result.unwrap_err().contains("Mock upload_part failure"));
+        let calls = mock_client.calls();
+        assert_eq!(calls.uploaded_parts.len(), 2);
+        assert!(calls.completed_parts.is_empty());
+    }
+    
+    #[test]
+    fn test_items_to_persist_resets_in_progress_items() {
+        let statuses = [
+            UploadStatus::Pending,
+            UploadStatus::InProgress,
+            UploadStatus::Completed,
+            UploadStatus::Failed,
+            UploadStatus::Paused,
+            UploadStatus::Cancelled,
+        ];
+        let items: Vec<UploadItem> = statuses.iter().map(|status| UploadItem {
+            progress: 40.0,
+            uploaded_bytes: 40,
+            speed_mbps: 1.0,
+            eta_seconds: Some(10),
+            started_at: Some(chrono::Utc::now().to_rfc3339()),
+            ..UploadItem::for_test("/tmp/test.mov", status.clone())
+        }).collect();
+        
+        let persisted = items_to_persist(&items);
+        assert_eq!(persisted.len(), 3);
+        assert_eq!(persisted[0].status, UploadStatus::Pending);
+        assert_eq!(persisted[1].status, UploadStatus::Pending);
+        assert_eq!(persisted[1].uploaded_bytes, 0);
+        assert!(persisted[1].started_at.is_none());
+        assert_eq!(persisted[2].status, UploadStatus::Paused);
+    }
+    
+    #[test]
+    fn test_network_offline_pauses_and_resumes_items() {
+        let mut queue = UploadQueue::new();
+        for status in [UploadStatus::InProgress, UploadStatus::Pending, UploadStatus::Failed] {
+            queue.items.push(UploadItem {
+                progress: 40.0,
+                uploaded_bytes: 40,
+                speed_mbps: 1.0,
+                eta_seconds: Some(10),
+                retry_count: 1,
+                ..UploadItem::for_test("/tmp/test.mov", status)
+            });
+        }
+        queue.active_upload_count = 1;
+        
+        assert_eq!(queue.go_offline(), 1);
+        assert!(queue.network_offline);
+        assert_eq!(queue.active_upload_count, 0);
+        assert_eq!(queue.items[0].status, UploadStatus::Paused);
+        assert_eq!(queue.items[0].uploaded_bytes, 0);
+        // ネットワーク起因の一時停止ではretry_countを消費しない
+        assert_eq!(queue.items[0].retry_count, 1);
+        
+        assert_eq!(queue.resume_from_network(), 1);
+        assert!(!queue.network_offline);
+        assert_eq!(queue.items[0].status, UploadStatus::Pending);
+        assert!(queue.items[0].error_message.is_none());
+        assert_eq!(queue.items[2].status, UploadStatus::Failed);
+    }
+    
+    #[test]
+    fn test_go_offline_keeps_multipart_upload_for_abort() {
+        let mut queue = UploadQueue::new();
+        let item = UploadItem::for_test("/tmp/offline.mov", UploadStatus::InProgress);
+        let item_id = item.id.clone();
+        let upload = ActiveMultipartUpload {
+            bucket: "test-bucket".to_string(),
+            key: item.s3_key.clone(),
+            upload_id: "offline-upload".to_string(),
+        };
+        queue.items.push(item);
+        register_multipart_upload(&item_id, upload.clone());
+        
+        assert_eq!(queue.go_offline(), 1);
+        assert!(take_multipart_upload(&item_id).is_none());
+        assert_eq!(queue.stale_multipart_uploads, vec![upload.clone()]);
+        
+        // 設定がなければ破棄に使う認証情報がないため保持したままにする
+        assert!(queue.take_stale_multipart_uploads().is_none());
+        queue.config = Some(create_test_upload_config());
+        let (uploads, _) = queue.take_stale_multipart_uploads().unwrap();
+        assert_eq!(uploads, vec![upload]);
+        assert!(queue.stale_multipart_uploads.is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_abort_multipart_uploads_with_mock() {
+        let uploads = vec![
+            ActiveMultipartUpload {
+                bucket: "test-bucket".to_string(),
+                key: "uploads/a.mov".to_string(),
+                upload_id: "upload-a".to_string(),
+            },
+            ActiveMultipartUpload {
+                bucket: "test-bucket".to_string(),
+                key: "uploads/b.mov".to_string(),
+                upload_id: "upload-b".to_string(),
+            },
+        ];
+        assert_eq!(abort_multipart_uploads(&MockS3Client::new(), &uploads).await, 2);
+    }
+
+    fn concurrency_test_item(id: &str) -> UploadItem {
+        UploadItem {
+            id: id.to_string(),
+            ..UploadItem::for_test(&format!("/tmp/{}.mov", id), UploadStatus::Pending)
+        }
+    }
+    
+    fn concurrency_test_queue(item_count: usize, max_concurrent: usize) -> UploadQueue {
+        let mut config = create_test_upload_config();
+        config.max_concurrent_uploads = max_concurrent;
+        let mut queue = UploadQueue::new();
+        queue.config = Some(config);
+        queue.items = (0..item_count).map(|i| concurrency_test_item(&format!("item-{}", i))).collect();
+        queue
+    }
+    
+    #[test]
+    fn test_start_upload_rejects_non_pending_items() {
+        let mut queue = concurrency_test_queue(2, 2);
+        queue.start_upload("item-0").unwrap();
+        // 二重に開始してもカウントは増えない
+        assert!(queue.start_upload("item-0").is_err());
+        assert_eq!(queue.active_upload_count, 1);
+        
+        queue.complete_upload("item-0", false, Some("failed".to_string()));
+        // 遅れて届いた完了は失敗の状態・理由を上書きせず、累計にも数えない
+        queue.complete_upload("item-0", true, None);
+        queue.complete_upload("item-0", false, Some("failed again".to_string()));
+        assert_eq!(queue.active_upload_count, 0);
+        assert_eq!(queue.items[0].status, UploadStatus::Failed);
+        assert_eq!(queue.items[0].error_message.as_deref(), Some("failed"));
+        assert_eq!((queue.total_files_uploaded, queue.total_uploaded_bytes), (0, 0));
+        
+        // 待機中のアイテムへの完了も無視する
+        queue.complete_upload("item-1", true, None);
+        assert_eq!(queue.items[1].status, UploadStatus::Pending);
+        assert_eq!(queue.total_files_uploaded, 0);
+    }
+    
+    #[tokio::test]
+    async fn test_reconcile_fixes_drifted_active_count() {
+        let mut queue = concurrency_test_queue(2, 1);
+        // 完了処理の取りこぼしでカウンタだけが残り、新規アップロードを開始できない状態
+        queue.active_upload_count = 1;
+        assert_eq!(queue.get_active_upload_count(), 1);
+        
+        let report = queue.reconcile_active_state();
+        assert!(report.changed());
+        assert_eq!((report.previous_count, report.corrected_count), (1, 0));
+        assert_eq!(queue.get_active_upload_count(), 0);
+        
+        queue.start_upload("item-0").unwrap();
+        let task = tokio::spawn(std::future::pending::<()>());
+        queue.upload_tasks.insert("item-0".to_string(), task.abort_handle());
+        // 整合している場合は何も変えない
+        assert!(!queue.reconcile_active_state().changed());
+        assert_eq!(queue.get_active_upload_count(), 1);
+        task.abort();
+    }
+    
+    #[tokio::test]
+    async fn test_reconcile_and_restart_recover_orphaned_uploads() {
+        let mut queue = concurrency_test_queue(3, 3);
+        queue.start_upload("item-0").unwrap();
+        queue.start_upload("item-1").unwrap();
+        let live = tokio::spawn(std::future::pending::<()>());
+        queue.upload_tasks.insert("item-1".to_string(), live.abort_handle());
+        // item-0はタスクが無いまま進行中に残り、待機中のitem-2には古い進捗が残っている
+        queue.active_uploads.insert("item-2".to_string(), build_upload_progress("item-2", 10, 100, 1.0, None));
+        
+        let report = queue.reconcile_active_state();
+        assert_eq!(report.requeued_items, vec!["item-0".to_string()]);
+        assert_eq!(report.stale_progress, 1);
+        assert_eq!(report.corrected_count, 1);
+        assert_eq!(queue.items[0].status, UploadStatus::Pending);
+        assert_eq!(queue.items[0].retry_count, 0);
+        
+        // 修正しても解消しない場合は進行中のアップロードを中断して待機中へ戻す
+        let (requeued, _) = queue.reset_for_restart();
+        assert_eq!(requeued, 1);
+        assert!(queue.items.iter().all(|item| item.status == UploadStatus::Pending));
+        assert_eq!(queue.get_active_upload_count(), 0);
+        assert!(queue.upload_tasks.is_empty());
+        assert!(live.await.unwrap_err().is_cancelled());
+        
+        for secs in 0..(MAX_STALL_HISTORY as u64 + 2) {
+            queue.record_stall(UploadStalledEvent {
+                detected_at: chrono::Utc::now().to_rfc3339(),
+                stalled_secs: secs,
+                pending_count: 3,
+                in_progress_count: 0,
+                active_upload_count: 0,
+                requeued_count: 0,
+            });
+        }
+        assert_eq!(queue.stall_history.len(), MAX_STALL_HISTORY);
+        assert_eq!(queue.stall_history[0].stalled_secs, 2);
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_start_and_complete_keeps_counts_consistent() {
+        const ITEM_COUNT: usize = 300;
+        const WORKERS: usize = 16;
+        const MAX_CONCURRENT: usize = 3;
+        let queue_state: UploadQueueState = Arc::new(Mutex::new(concurrency_test_queue(ITEM_COUNT, MAX_CONCURRENT)));
+        
+        let mut workers = Vec::new();
+        for worker in 0..WORKERS {
+            let queue_state = queue_state.clone();
+            workers.push(tokio::spawn(async move {
+                loop {
+                    let started = {
+                        let mut queue = queue_state.lock().unwrap();
+                        let next = queue.items.iter()
+                            .find(|i| i.status == UploadStatus::Pending)
+                            .map(|i| i.id.clone());
+                        match next {
+                            Some(id) => queue.start_upload(&id).ok().map(|_| id),
+                            None => break,
+                        }
+                    };
+                    let Some(item_id) = started else {
+                        tokio::task::yield_now().await;
+                        continue;
+                    };
+                    {
+                        let queue = queue_state.lock().unwrap();
+                        assert!(queue.active_upload_count <= MAX_CONCURRENT);
+                        assert_eq!(queue.active_upload_count, queue.in_progress_count());
+                    }
+                    tokio::task::yield_now().await;
+                    
+                    let success = worker % 4 != 0;
+                    let mut queue = queue_state.lock().unwrap();
+                    // 進捗更新とタスク終了の両方から完了が届くケースを再現
+                    queue.complete_upload(&item_id, success, None);
+                    queue.complete_upload(&item_id, success, None);
+                    assert_eq!(queue.active_upload_count, queue.in_progress_count());
+                }
+            }));
+        }
+        for worker in workers {
+            worker.await.unwrap();
+        }
+        
+        let queue = queue_state.lock().unwrap();
+        assert!(queue.items.iter().all(|i| matches!(i.status, UploadStatus::Completed | UploadStatus::Failed)));
+        assert_eq!(queue.active_upload_count, 0);
+        assert!(queue.active_uploads.is_empty());
+        let completed = queue.items.iter().filter(|i| i.status == UploadStatus::Completed).count();
+        assert_eq!(queue.total_files_uploaded, completed as u64);
+    }
+    
+    /// 性質テスト用のキュー操作
+    #[derive(Debug, Clone)]
+    enum QueueOp {
+        Start(usize),
+        Complete(usize, bool),
+        PauseForNetwork(usize),
+        /// 失敗・一時停止したアイテムを待機中へ戻す（再試行・オンライン復帰）
+        Requeue(usize),
+    }
+    
+    const PROP_ITEM_COUNT: usize = 6;
+    
+    fn queue_op() -> impl proptest::strategy::Strategy<Value = QueueOp> {
+        use proptest::prelude::*;
+        prop_oneof![
+            (0..PROP_ITEM_COUNT).prop_map(QueueOp::Start),
+            (0..PROP_ITEM_COUNT, any::<bool>()).prop_map(|(i, ok)| QueueOp::Complete(i, ok)),
+            (0..PROP_ITEM_COUNT).prop_map(QueueOp::PauseForNetwork),
+            (0..PROP_ITEM_COUNT).prop_map(QueueOp::Requeue),
+        ]
+    }
+    
+    proptest::proptest! {
+        #[test]
+        fn prop_active_count_tracks_in_progress_items(
+            ops in proptest::collection::vec(queue_op(), 1..200),
+            max_concurrent in 1usize..4,
+        ) {
+            let mut queue = concurrency_test_queue(PROP_ITEM_COUNT, max_concurrent);
+            for op in ops {
+                match op {
+                    QueueOp::Start(i) => { let _ = queue.start_upload(&format!("item-{}", i)); }
+                    QueueOp::Complete(i, ok) => queue.complete_upload(&format!("item-{}", i), ok, None),
+                    QueueOp::PauseForNetwork(i) => queue.pause_for_network(&format!("item-{}", i)),
+                    QueueOp::Requeue(i) => {
+                        let item = &mut queue.items[i];
+                        if matches!(item.status, UploadStatus::Failed | UploadStatus::Paused) {
+                            item.status = UploadStatus::Pending;
+                        }
+                    }
+                }
+                proptest::prop_assert_eq!(queue.active_upload_count, queue.in_progress_count());
+                proptest::prop_assert!(queue.active_upload_count <= max_concurrent);
+            }
+            
+            // 残りを全て処理すると、全アイテムがCompleted/Failedに収束する
+            for item in queue.items.iter_mut().filter(|i| i.status == UploadStatus::Paused) {
+                item.status = UploadStatus::Pending;
+            }
+            for i in 0..PROP_ITEM_COUNT {
+                let id = format!("item-{}", i);
+                if queue.items[i].status == UploadStatus::InProgress {
+                    queue.complete_upload(&id, true, None);
+                }
+            }
+            for i in 0..PROP_ITEM_COUNT {
+                let id = format!("item-{}", i);
+                if queue.items[i].status == UploadStatus::Pending {
+                    queue.start_upload(&id).unwrap();
+                    queue.complete_upload(&id, true, None);
+                }
+            }
+            proptest::prop_assert!(queue.items.iter().all(|i| matches!(i.status, UploadStatus::Completed | UploadStatus::Failed)));
+            proptest::prop_assert_eq!(queue.active_upload_count, 0);
+        }
+    }
+
+    #[test]
+    fn test_remove_items_reports_per_item_outcome() {
+        let mut queue = concurrency_test_queue(3, 3);
+        queue.start_upload("item-0").unwrap();
+        register_multipart_upload("item-0", ActiveMultipartUpload {
+            bucket: "footage".to_string(),
+            key: "uploads/item-0.mov".to_string(),
+            upload_id: "upload-0".to_string(),
+        });
+
+        let ids = ["item-0", "item-1", "missing", "item-1"].map(String::from);
+        let (results, aborted) = queue.remove_items(&ids);
+        let outcomes: Vec<_> = results.iter().map(|r| (r.item_id.as_str(), r.outcome)).collect();
+        assert_eq!(outcomes, vec![
+            ("item-0", BatchItemOutcome::RemovedWithAbort),
+            ("item-1", BatchItemOutcome::Removed),
+            ("missing", BatchItemOutcome::NotFound),
+        ]);
+        assert_eq!(aborted.len(), 1);
+        assert_eq!(queue.items.len(), 1);
+        assert_eq!(queue.active_upload_count, 0);
+        assert_eq!(BatchOperationResult::new(results).failed, 1);
+    }
+
+    #[test]
+    fn test_retry_items_skips_in_progress() {
+        let mut queue = concurrency_test_queue(2, 2);
+        queue.start_upload("item-0").unwrap();
+        queue.items[1].status = UploadStatus::Failed;
+        queue.items[1].error_message = Some("timeout".to_string());
+
+        let results = queue.retry_items(&["item-0".to_string(), "item-1".to_string()]);
+        assert_eq!(results[0].outcome, BatchItemOutcome::InProgress);
+        assert!(!results[0].success);
+        assert_eq!(results[1].outcome, BatchItemOutcome::Queued);
+        assert_eq!(queue.items[1].status, UploadStatus::Pending);
+        assert_eq!(queue.items[1].retry_count, 1);
+        assert!(queue.items[1].error_message.is_none());
+    }
+
+    #[test]
+    fn test_normalize_s3_key() {
+        assert_eq!(normalize_s3_key(" /footage//2024/a.mov ").unwrap(), "footage/2024/a.mov");
+        assert!(normalize_s3_key("footage/").is_err());
+        assert!(normalize_s3_key(" / ").is_err());
+        assert!(normalize_s3_key(&"a".repeat(MAX_S3_KEY_BYTES + 1)).is_err());
+    }
+
+    #[test]
+    fn test_update_pending_item_rejects_started_and_conflicting_items() {
+        let mut queue = concurrency_test_queue(3, 3);
+        queue.start_upload("item-0").unwrap();
+        let priority = UploadItemChanges { priority: Some(5), ..Default::default() };
+        assert!(queue.update_pending_item("item-0", &priority).is_err());
+
+        let taken_key = UploadItemChanges { s3_key: Some(queue.items[2].s3_key.clone()), ..Default::default() };
+        assert!(queue.update_pending_item("item-1", &taken_key).is_err());
+
+        let changes = UploadItemChanges {
+            s3_key: Some("footage/renamed.mov".to_string()),
+            priority: Some(5),
+            note: Some("  graded  ".to_string()),
+        };
+        let updated = queue.update_pending_item("item-1", &changes).unwrap();
+        assert_eq!((updated.s3_key.as_str(), updated.priority, updated.note.as_deref()), ("footage/renamed.mov", 5, Some("graded")));
+
+        let clear_note = UploadItemChanges { note: Some(String::new()), ..Default::default() };
+        assert!(queue.update_pending_item("item-1", &clear_note).unwrap().note.is_none());
+    }
+
+    #[test]
+    fn test_next_pending_ids_orders_by_priority_then_insertion() {
+        let mut queue = concurrency_test_queue(4, 4);
+        queue.items[2].priority = 10;
+        queue.items[3].priority = 10;
+        queue.items[0].priority = -1;
+        assert_eq!(queue.next_pending_ids(3), vec!["item-2", "item-3", "item-1"]);
+    }
+
+    #[test]
+    fn test_in_use_item_is_held_then_fails_after_timeout() {
+        let mut queue = concurrency_test_queue(2, 2);
+        let start = Instant::now();
+        assert!(!queue.hold_in_use_item("item-0", start));
+        // 再確認の時刻までは開始対象にしない（待機中のまま）
+        assert_eq!(queue.next_pending_ids(2), vec!["item-1"]);
+        assert_eq!(queue.items[0].status, UploadStatus::Pending);
+
+        assert!(queue.hold_in_use_item("item-0", start + FILE_IN_USE_TIMEOUT));
+        assert_eq!(queue.items[0].status, UploadStatus::Failed);
+        assert!(queue.items[0].error_message.as_deref().unwrap().contains("another application"));
+        assert!(queue.file_in_use_holds.is_empty());
+    }
+
+    #[test]
+    fn test_take_archivable_items_keeps_recent_and_unfinished_work() {
+        let mut queue = UploadQueue::new();
+        for (id, status, completed_at) in [
+            ("old-done", UploadStatus::Completed, Some("2024-01-01T00:00:00+00:00")),
+            ("older-done", UploadStatus::Completed, Some("2023-12-01T00:00:00+00:00")),
+            ("recent-done", UploadStatus::Completed, Some("2024-03-01T00:00:00+00:00")),
+            ("old-pending", UploadStatus::Pending, None),
+        ] {
+            let mut item = concurrency_test_item(id);
+            item.status = status;
+            item.created_at = "2023-11-01T00:00:00+00:00".to_string();
+            item.completed_at = completed_at.map(String::from);
+            queue.items.push(item);
+        }
+        let cutoff = chrono::DateTime::parse_from_rfc3339("2024-02-01T00:00:00+00:00").unwrap().with_timezone(&chrono::Utc);
+
+        let archived = queue.take_archivable_items(cutoff, 1);
+        assert_eq!(archived.iter().map(|i| i.id.as_str()).collect::<Vec<_>>(), vec!["older-done"]);
+        let archived = queue.take_archivable_items(cutoff, 10);
+        assert_eq!(archived.iter().map(|i| i.id.as_str()).collect::<Vec<_>>(), vec!["old-done"]);
+        assert_eq!(queue.items.iter().map(|i| i.id.as_str()).collect::<Vec<_>>(), vec!["recent-done", "old-pending"]);
+    }
+
+    #[test]
+    fn test_project_id_is_normalized_and_propagated() {
+        assert_eq!(normalize_project_id(Some("  PRJ-001 ".to_string())).unwrap().as_deref(), Some("PRJ-001"));
+        assert_eq!(normalize_project_id(Some("   ".to_string())).unwrap(), None);
+        assert!(normalize_project_id(Some("案件 A".to_string())).is_err());
+
+        let labels = UploadItemLabels { note: Some("本編".to_string()), project_id: Some("PRJ-001".to_string()) };
+        assert_eq!(labels.object_metadata(), HashMap::from([(META_PROJECT_ID.to_string(), "PRJ-001".to_string())]));
+        assert_eq!(labels.custom_fields().get("note").map(String::as_str), Some("本編"));
+        assert!(UploadItemLabels::default().object_metadata().is_empty());
+    }
+
+    #[test]
+    fn test_folder_manifest_items_waits_for_whole_folder() {
+        let mut queue = concurrency_test_queue(3, 3);
+        queue.items[2].file_path = "/Volumes/Other/item-2.mov".to_string();
+        queue.items[0].status = UploadStatus::Completed;
+        let folder = Path::new("/tmp");
+        assert!(queue.folder_manifest_items(folder).is_none());
+
+        queue.items[1].status = UploadStatus::Failed;
+        let items = queue.folder_manifest_items(folder).unwrap();
+        assert_eq!(items.iter().map(|i| i.id.as_str()).collect::<Vec<_>>(), vec!["item-0"]);
+    }
+
+    #[test]
+    fn test_prepare_queue_batch_skips_missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("a.mov");
+        std::fs::write(&existing, b"clip").unwrap();
+        let missing = dir.path().join("missing.mov").to_string_lossy().to_string();
+        let paths = vec![existing.to_string_lossy().to_string(), missing.clone()];
+
+        let (items, failures) = prepare_queue_batch(&paths, 0, &create_test_s3_key_config(), None);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].file_name, "a.mov");
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].file_path, missing);
+    }
+
+    #[test]
+    fn test_prepare_queue_batch_scales_to_100k_files() {
+        // 1000ファイルを繰り返して10万件のパスを作り、バッチ処理の時間と1件あたりのメモリを確認
+        let dir = tempfile::tempdir().unwrap();
+        let files: Vec<String> = (0..1000)
+            .map(|i| {
+                let path = dir.path().join(format!("clip-{:04}.mov", i));
+                std::fs::write(&path, b"x").unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+        let paths: Vec<String> = files.iter().cycle().take(100_000).cloned().collect();
+        let config = create_test_s3_key_config();
+
+        let started = Instant::now();
+        let mut items = Vec::with_capacity(paths.len());
+        for (batch_index, batch) in paths.chunks(QUEUE_ADD_BATCH_SIZE).enumerate() {
+            let (batch_items, failures) = prepare_queue_batch(batch, batch_index * QUEUE_ADD_BATCH_SIZE, &config, None);
+            assert!(failures.is_empty());
+            items.extend(batch_items);
+        }
+        let elapsed = started.elapsed();
+        assert_eq!(items.len(), 100_000);
+        assert!(elapsed < Duration::from_secs(30), "took {:?}", elapsed);
+
+        let bytes: usize = items.iter()
+            .map(|item| std::mem::size_of::<UploadItem>()
+                + item.id.capacity() + item.file_path.capacity() + item.file_name.capacity() + item.s3_key.capacity())
+            .sum();
+        assert!(bytes / items.len() < 2048, "{} bytes per item", bytes / items.len());
+    }
+
+    #[test]
+    fn test_part_progress_hook_reports_position_within_part() {
+        use crate::commands::part_progress::PART_PROGRESS_STEP_BYTES;
+        let (tx, mut rx) = mpsc::channel::<UploadProgress>(10);
+        let part_bytes = PART_PROGRESS_STEP_BYTES * 4;
+        let part = PartPosition { current_part: 12, total_parts: 12, part_uploaded_bytes: 0, part_total_bytes: part_bytes };
+        let hook = part_progress_hook("item-1", &tx, Instant::now(), part_bytes * 11, part_bytes * 12, part);
+
+        hook(PART_PROGRESS_STEP_BYTES * 2);
+        hook(PART_PROGRESS_STEP_BYTES * 2 + 1);
+        hook(part_bytes);
+
+        let progress = rx.try_recv().unwrap();
+        assert_eq!((progress.current_part, progress.total_parts), (Some(12), Some(12)));
+        assert_eq!(progress.part_uploaded_bytes, Some(PART_PROGRESS_STEP_BYTES * 2));
+        assert_eq!(progress.uploaded_bytes, part_bytes * 11 + PART_PROGRESS_STEP_BYTES * 2);
+        // 間引かれた途中経過は送らず、最後のパートを送り切っても完了扱いにしない
+        let last = rx.try_recv().unwrap();
+        assert_eq!(last.part_uploaded_bytes, Some(part_bytes));
+        assert_eq!(last.status, UploadStatus::InProgress);
+        assert!(rx.try_recv().is_err());
+    }
+}
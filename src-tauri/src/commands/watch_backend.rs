@@ -0,0 +1,510 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use notify::event::{CreateKind, DataChange, ModifyKind, RemoveKind};
+use notify::{Config, Event, EventHandler, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+
+use crate::commands::file_operations::WatchConfig;
+
+/// FSEventsが届かないネットワークファイルシステム
+const NETWORK_FILE_SYSTEMS: &[&str] = &["smbfs", "nfs", "afpfs", "webdav", "cifs", "smb2", "smb3"];
+/// ポーリング間隔の下限
+const MIN_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// 更新日時の分解能（SMBは秒単位のため、直近に変わったディレクトリは次回も走査する）
+const MTIME_GRANULARITY: Duration = Duration::from_secs(2);
+/// 監視が壊れてから最初に再作成を試すまでの待ち時間（失敗するたびに倍にする）
+const RECOVERY_INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+/// 再作成の待ち時間の上限
+const RECOVERY_MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
+/// 続けてこの回数だけ再作成に失敗したら監視をerror状態にする
+pub const MAX_RECOVERY_ATTEMPTS: u32 = 10;
+
+/// 監視の方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchMode {
+    /// OSのファイル変更通知（macOSではFSEvents）
+    Native,
+    /// ディレクトリの更新日時による差分走査
+    Polling,
+}
+
+/// ファイルシステム名がネットワークボリュームか
+pub fn is_network_file_system(file_system: &str) -> bool {
+    NETWORK_FILE_SYSTEMS.contains(&file_system.to_ascii_lowercase().as_str())
+}
+
+/// パスを含むボリュームのファイルシステム名
+pub fn volume_file_system(path: &Path) -> Option<String> {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks.list().iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().components().count())
+        .map(|disk| disk.file_system().to_string_lossy().to_string())
+}
+
+/// 設定とボリュームの種類から監視方式を選ぶ（ネットワークボリュームは自動でポーリング）
+pub fn choose_watch_mode(force_polling: bool, file_system: Option<&str>) -> WatchMode {
+    if force_polling || file_system.is_some_and(is_network_file_system) {
+        WatchMode::Polling
+    } else {
+        WatchMode::Native
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+#[derive(Debug)]
+struct DirState {
+    modified: Option<SystemTime>,
+    files: HashMap<PathBuf, FileStamp>,
+    /// 前回の走査で変化があった（書き込み中のファイルのサイズ変化を追うため次回も走査する）
+    hot: bool,
+}
+
+struct DirListing {
+    files: HashMap<PathBuf, FileStamp>,
+    dirs: Vec<PathBuf>,
+}
+
+fn list_dir(dir: &Path) -> Option<DirListing> {
+    let mut listing = DirListing { files: HashMap::new(), dirs: Vec::new() };
+    for entry in std::fs::read_dir(dir).ok()?.flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            listing.dirs.push(entry.path());
+        } else if metadata.is_file() {
+            listing.files.insert(entry.path(), FileStamp { size: metadata.len(), modified: metadata.modified().ok() });
+        }
+    }
+    Some(listing)
+}
+
+fn dir_modified(dir: &Path) -> Option<SystemTime> {
+    std::fs::metadata(dir).ok().filter(|m| m.is_dir()).and_then(|m| m.modified().ok())
+}
+
+fn recently_modified(modified: Option<SystemTime>, now: SystemTime) -> bool {
+    modified.is_some_and(|modified| now.duration_since(modified).map_or(true, |age| age < MTIME_GRANULARITY))
+}
+
+/// ディレクトリの更新日時による差分走査
+///
+/// 更新日時が変わったディレクトリだけを読み直すため、ファイル数が多いNASでも負荷が小さい。
+/// 既存ファイルの上書きはディレクトリの更新日時を変えないため、新規作成・リネーム・削除の検知が主となる
+#[derive(Debug)]
+pub struct DirectorySnapshot {
+    recursive: bool,
+    dirs: HashMap<PathBuf, DirState>,
+}
+
+impl DirectorySnapshot {
+    /// 現在の状態を記録（既存のファイルはイベントにしない）
+    pub fn new(root: &Path, recursive: bool) -> Self {
+        let mut snapshot = Self { recursive, dirs: HashMap::new() };
+        snapshot.add_dir(root.to_path_buf(), SystemTime::now(), &mut Vec::new());
+        snapshot
+    }
+
+    /// 新しく見つかったディレクトリを記録し、中のファイルを作成イベントにする
+    fn add_dir(&mut self, dir: PathBuf, now: SystemTime, events: &mut Vec<Event>) {
+        let modified = dir_modified(&dir);
+        let Some(listing) = list_dir(&dir) else {
+            return;
+        };
+        for path in listing.files.keys() {
+            events.push(Event::new(EventKind::Create(CreateKind::File)).add_path(path.clone()));
+        }
+        self.dirs.insert(dir, DirState { modified, files: listing.files, hot: recently_modified(modified, now) });
+        if self.recursive {
+            for sub in listing.dirs {
+                self.add_dir(sub, now, events);
+            }
+        }
+    }
+
+    /// ディレクトリと配下の記録を削除し、含まれていたファイルを削除イベントにする
+    fn remove_dir(&mut self, dir: &Path, events: &mut Vec<Event>) {
+        let removed: Vec<PathBuf> = self.dirs.keys().filter(|path| path.starts_with(dir)).cloned().collect();
+        for path in removed {
+            if let Some(state) = self.dirs.remove(&path) {
+                for file in state.files.into_keys() {
+                    events.push(Event::new(EventKind::Remove(RemoveKind::File)).add_path(file));
+                }
+            }
+        }
+    }
+
+    /// 前回からの変化をイベントとして返す
+    pub fn rescan(&mut self) -> Vec<Event> {
+        let now = SystemTime::now();
+        let mut events = Vec::new();
+        let dirs: Vec<PathBuf> = self.dirs.keys().cloned().collect();
+        for dir in dirs {
+            let Some(state) = self.dirs.get(&dir) else {
+                // 親ディレクトリと一緒に削除済み
+                continue;
+            };
+            let modified = dir_modified(&dir);
+            if modified.is_some() && modified == state.modified && !state.hot {
+                continue;
+            }
+            let Some(listing) = modified.and_then(|_| list_dir(&dir)) else {
+                self.remove_dir(&dir, &mut events);
+                continue;
+            };
+
+            let previous = self.dirs.get(&dir).map(|state| &state.files);
+            let mut changed = false;
+            for (path, stamp) in &listing.files {
+                let kind = match previous.and_then(|files| files.get(path)) {
+                    None => EventKind::Create(CreateKind::File),
+                    Some(prev) if prev != stamp => EventKind::Modify(ModifyKind::Data(DataChange::Any)),
+                    Some(_) => continue,
+                };
+                events.push(Event::new(kind).add_path(path.clone()));
+                changed = true;
+            }
+            for path in previous.into_iter().flat_map(|files| files.keys()) {
+                if !listing.files.contains_key(path) {
+                    events.push(Event::new(EventKind::Remove(RemoveKind::File)).add_path(path.clone()));
+                    changed = true;
+                }
+            }
+
+            let removed_dirs: Vec<PathBuf> = self.dirs.keys()
+                .filter(|path| path.parent() == Some(dir.as_path()) && !listing.dirs.contains(path))
+                .cloned()
+                .collect();
+            for removed in removed_dirs {
+                self.remove_dir(&removed, &mut events);
+            }
+            if self.recursive {
+                for sub in &listing.dirs {
+                    if !self.dirs.contains_key(sub) {
+                        self.add_dir(sub.clone(), now, &mut events);
+                    }
+                }
+            }
+            let hot = changed || recently_modified(modified, now);
+            self.dirs.insert(dir, DirState { modified, files: listing.files, hot });
+        }
+        events
+    }
+}
+
+/// バックグラウンドスレッドで差分走査を行うウォッチャー（破棄すると停止する）
+pub struct PollingWatcher {
+    stop: Arc<AtomicBool>,
+}
+
+impl PollingWatcher {
+    pub fn new<H: EventHandler>(root: &Path, recursive: bool, interval: Duration, mut handler: H) -> std::io::Result<Self> {
+        if !root.is_dir() {
+            return Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("{} is not a directory", root.display())));
+        }
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let root = root.to_path_buf();
+        let interval = interval.max(MIN_POLL_INTERVAL);
+        std::thread::Builder::new()
+            .name("reelvault-poll-watcher".to_string())
+            .spawn(move || {
+                let mut snapshot = DirectorySnapshot::new(&root, recursive);
+                while !thread_stop.load(Ordering::Relaxed) {
+                    std::thread::sleep(interval);
+                    if thread_stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    // ドライブの取り外しなどでルートがなくなったら、エラーを通知して走査をやめる
+                    if !root.is_dir() {
+                        handler.handle_event(Err(notify::Error::path_not_found().add_path(root.clone())));
+                        break;
+                    }
+                    for event in snapshot.rescan() {
+                        handler.handle_event(Ok(event));
+                    }
+                }
+                log::debug!("Polling watcher stopped for {}", root.display());
+            })?;
+        Ok(Self { stop })
+    }
+}
+
+impl Drop for PollingWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// 実行中の監視（破棄すると監視が止まる）
+pub enum WatchHandle {
+    Native(RecommendedWatcher),
+    Polling(PollingWatcher),
+}
+
+impl WatchHandle {
+    pub fn mode(&self) -> WatchMode {
+        match self {
+            WatchHandle::Native(_) => WatchMode::Native,
+            WatchHandle::Polling(_) => WatchMode::Polling,
+        }
+    }
+}
+
+/// 設定とボリュームに応じた方式で監視を開始
+pub fn start_watch<H: EventHandler>(path: &Path, config: &WatchConfig, handler: H) -> Result<WatchHandle, notify::Error> {
+    let file_system = volume_file_system(path);
+    let mode = choose_watch_mode(config.force_polling, file_system.as_deref());
+    if mode == WatchMode::Polling && !config.force_polling {
+        log::info!(
+            "{} is on a network volume ({}), using polling watcher",
+            path.display(),
+            file_system.as_deref().unwrap_or("unknown")
+        );
+    }
+    match mode {
+        WatchMode::Native => {
+            let mut watcher = RecommendedWatcher::new(handler, Config::default())?;
+            let recursive_mode = if config.recursive { RecursiveMode::Recursive } else { RecursiveMode::NonRecursive };
+            watcher.watch(path, recursive_mode)?;
+            Ok(WatchHandle::Native(watcher))
+        }
+        WatchMode::Polling => {
+            let interval = Duration::from_secs(config.poll_interval_secs);
+            let watcher = PollingWatcher::new(path, config.recursive, interval, handler)?;
+            Ok(WatchHandle::Polling(watcher))
+        }
+    }
+}
+
+/// n回目（1始まり）の再作成を試すまでの待ち時間
+pub fn recovery_backoff(attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    RECOVERY_INITIAL_BACKOFF.saturating_mul(factor).min(RECOVERY_MAX_BACKOFF)
+}
+
+/// 監視の再作成を試した結果
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecoveryOutcome {
+    Recovered { attempts: u32, mode: WatchMode },
+    /// `retry_in`がNoneなら上限に達したため再作成をやめた
+    Failed { attempt: u32, error: String, retry_in: Option<Duration> },
+}
+
+/// エラーや対象パスの消失で止まった監視を、指数バックオフで作り直すウォッチャー
+///
+/// 作り直した監視も同じ送信先へイベントを送るため、受信側のループはそのまま使える
+pub struct SelfHealingWatch {
+    path: PathBuf,
+    config: WatchConfig,
+    sender: Sender<notify::Result<Event>>,
+    handle: Option<WatchHandle>,
+    /// 失敗した再作成の回数と次に試す時刻（監視中はNone）
+    recovery: Option<(u32, Instant)>,
+    gave_up: bool,
+}
+
+impl SelfHealingWatch {
+    pub fn start(path: &Path, config: &WatchConfig, sender: Sender<notify::Result<Event>>) -> Result<Self, notify::Error> {
+        let handle = start_watch(path, config, sender.clone())?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            config: config.clone(),
+            sender,
+            handle: Some(handle),
+            recovery: None,
+            gave_up: false,
+        })
+    }
+
+    /// 現在の監視方式（再作成待ちの間はNone）
+    pub fn mode(&self) -> Option<WatchMode> {
+        self.handle.as_ref().map(WatchHandle::mode)
+    }
+
+    /// 再作成の上限に達して監視をやめた
+    pub fn has_given_up(&self) -> bool {
+        self.gave_up
+    }
+
+    /// 監視を破棄して再作成を予約（監視中でなければ何もせずfalse）
+    pub fn mark_broken(&mut self, now: Instant) -> bool {
+        if self.handle.take().is_none() {
+            return false;
+        }
+        self.recovery = Some((0, now + recovery_backoff(1)));
+        true
+    }
+
+    /// 監視中に対象パスが消えていたら監視を破棄して再作成を予約
+    pub fn check_path(&mut self, now: Instant) -> bool {
+        self.handle.is_some() && !self.path.is_dir() && self.mark_broken(now)
+    }
+
+    /// 予定時刻を過ぎていれば監視の再作成を試す
+    pub fn poll_recovery(&mut self, now: Instant) -> Option<RecoveryOutcome> {
+        let (failures, due) = self.recovery?;
+        if now < due {
+            return None;
+        }
+        let attempt = failures + 1;
+        let started = if self.path.is_dir() {
+            start_watch(&self.path, &self.config, self.sender.clone()).map_err(|e| e.to_string())
+        } else {
+            Err(format!("{} is not available", self.path.display()))
+        };
+        match started {
+            Ok(handle) => {
+                let mode = handle.mode();
+                self.handle = Some(handle);
+                self.recovery = None;
+                Some(RecoveryOutcome::Recovered { attempts: attempt, mode })
+            }
+            Err(error) if attempt >= MAX_RECOVERY_ATTEMPTS => {
+                self.recovery = None;
+                self.gave_up = true;
+                Some(RecoveryOutcome::Failed { attempt, error, retry_in: None })
+            }
+            Err(error) => {
+                let retry_in = recovery_backoff(attempt + 1);
+                self.recovery = Some((attempt, now + retry_in));
+                Some(RecoveryOutcome::Failed { attempt, error, retry_in: Some(retry_in) })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths_of(events: &[Event], kind: fn(&EventKind) -> bool) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = events.iter()
+            .filter(|event| kind(&event.kind))
+            .flat_map(|event| event.paths.clone())
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn test_choose_watch_mode_for_network_volumes() {
+        assert_eq!(choose_watch_mode(false, Some("apfs")), WatchMode::Native);
+        assert_eq!(choose_watch_mode(false, Some("smbfs")), WatchMode::Polling);
+        assert_eq!(choose_watch_mode(false, Some("NFS")), WatchMode::Polling);
+        assert_eq!(choose_watch_mode(true, Some("apfs")), WatchMode::Polling);
+        assert_eq!(choose_watch_mode(false, None), WatchMode::Native);
+    }
+
+    #[test]
+    fn test_rescan_reports_new_and_removed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("existing.mov"), b"a").unwrap();
+        let mut snapshot = DirectorySnapshot::new(dir.path(), true);
+
+        std::fs::write(dir.path().join("new.mov"), b"b").unwrap();
+        std::fs::create_dir(dir.path().join("DAY02")).unwrap();
+        std::fs::write(dir.path().join("DAY02").join("clip.mov"), b"c").unwrap();
+        std::fs::remove_file(dir.path().join("existing.mov")).unwrap();
+
+        let events = snapshot.rescan();
+        assert_eq!(
+            paths_of(&events, EventKind::is_create),
+            vec![dir.path().join("DAY02").join("clip.mov"), dir.path().join("new.mov")]
+        );
+        assert_eq!(paths_of(&events, EventKind::is_remove), vec![dir.path().join("existing.mov")]);
+
+        // 変化がなければ何も報告しない（直近に変わったディレクトリは読み直すが差分はない）
+        assert!(snapshot.rescan().is_empty());
+    }
+
+    fn polling_config(path: &Path) -> WatchConfig {
+        serde_json::from_value(serde_json::json!({
+            "path": path.to_string_lossy(),
+            "recursive": true,
+            "file_patterns": ["*.mov"],
+            "max_file_size_mb": null,
+            "auto_upload": false,
+            "exclude_patterns": [],
+            "exclude_directories": [],
+            "auto_metadata": false,
+            "force_polling": true,
+            "poll_interval_secs": 1,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_recovery_backoff_doubles_up_to_limit() {
+        assert_eq!(recovery_backoff(1), Duration::from_secs(2));
+        assert_eq!(recovery_backoff(2), Duration::from_secs(4));
+        assert_eq!(recovery_backoff(4), Duration::from_secs(16));
+        assert_eq!(recovery_backoff(MAX_RECOVERY_ATTEMPTS), RECOVERY_MAX_BACKOFF);
+        assert_eq!(recovery_backoff(u32::MAX), RECOVERY_MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_watch_recovers_after_drive_is_remounted() {
+        let volume = tempfile::tempdir().unwrap();
+        let card = volume.path().join("CARD01");
+        std::fs::create_dir(&card).unwrap();
+        let config = polling_config(&card);
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut watch = SelfHealingWatch::start(&card, &config, tx).unwrap();
+
+        // 取り外し: パスの消失を検知して監視を破棄し、戻るまでは再作成に失敗する
+        std::fs::remove_dir_all(&card).unwrap();
+        let now = Instant::now();
+        assert!(watch.check_path(now));
+        assert_eq!(watch.mode(), None);
+        assert_eq!(watch.poll_recovery(now), None);
+        let outcome = watch.poll_recovery(now + recovery_backoff(1));
+        assert!(matches!(outcome, Some(RecoveryOutcome::Failed { attempt: 1, retry_in: Some(_), .. })));
+
+        // 再マウント: 次の試行で監視が復活し、新しいファイルを検知する
+        std::fs::create_dir(&card).unwrap();
+        let outcome = watch.poll_recovery(now + recovery_backoff(1) + recovery_backoff(2));
+        assert_eq!(outcome, Some(RecoveryOutcome::Recovered { attempts: 2, mode: WatchMode::Polling }));
+        std::thread::sleep(Duration::from_millis(200));
+        std::fs::write(card.join("clip.mov"), b"a").unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut detected = false;
+        while !detected && Instant::now() < deadline {
+            if let Ok(Ok(event)) = rx.recv_timeout(Duration::from_millis(500)) {
+                detected = event.paths.contains(&card.join("clip.mov"));
+            }
+        }
+        assert!(detected);
+    }
+
+    #[test]
+    fn test_watch_gives_up_after_max_attempts() {
+        let volume = tempfile::tempdir().unwrap();
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let mut watch = SelfHealingWatch::start(volume.path(), &polling_config(volume.path()), tx).unwrap();
+        // パスが存在するうちも、明示的に壊れたと扱えば再作成の対象になる
+        let mut now = Instant::now();
+        assert!(watch.mark_broken(now));
+        assert!(!watch.mark_broken(now));
+        watch.path = volume.path().join("ejected");
+
+        let mut last = None;
+        while !watch.has_given_up() {
+            now += RECOVERY_MAX_BACKOFF;
+            last = watch.poll_recovery(now);
+        }
+        assert!(matches!(last, Some(RecoveryOutcome::Failed { attempt: MAX_RECOVERY_ATTEMPTS, retry_in: None, .. })));
+        assert_eq!(watch.poll_recovery(now + RECOVERY_MAX_BACKOFF), None);
+    }
+}
@@ -0,0 +1,128 @@
+use std::path::Path;
+
+/// Finderタグを保持する拡張属性名
+pub const FINDER_TAGS_XATTR: &str = "com.apple.metadata:_kMDItemUserTags";
+
+/// Finderのタグ色（拡張属性には「名前\n色番号」の形で保存される）
+const TAG_COLORS: &[(&str, u8)] = &[
+    ("gray", 1),
+    ("green", 2),
+    ("purple", 3),
+    ("blue", 4),
+    ("yellow", 5),
+    ("red", 6),
+    ("orange", 7),
+];
+
+/// macOSの拡張属性（xattr）への読み書き
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::path::Path;
+    use super::FINDER_TAGS_XATTR;
+
+    pub fn read_tags(path: &Path) -> Result<Vec<String>, String> {
+        match xattr::get(path, FINDER_TAGS_XATTR).map_err(|e| e.to_string())? {
+            Some(bytes) => plist::from_bytes(&bytes).map_err(|e| format!("Invalid Finder tags: {}", e)),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub fn write_tags(path: &Path, tags: &[String]) -> Result<(), String> {
+        if tags.is_empty() {
+            return xattr::remove(path, FINDER_TAGS_XATTR).map_err(|e| e.to_string());
+        }
+        let mut bytes = Vec::new();
+        plist::to_writer_binary(&mut bytes, &tags).map_err(|e| e.to_string())?;
+        xattr::set(path, FINDER_TAGS_XATTR, &bytes).map_err(|e| e.to_string())
+    }
+}
+
+/// macOS以外ではFinderタグを扱わない
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use std::path::Path;
+
+    pub fn read_tags(_path: &Path) -> Result<Vec<String>, String> {
+        Ok(Vec::new())
+    }
+
+    pub fn write_tags(_path: &Path, _tags: &[String]) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// 設定値（「名前」または「名前:色」）を拡張属性に保存する形式へ変換
+pub fn tag_entry(spec: &str) -> String {
+    let colored = spec.rsplit_once(':').and_then(|(name, color)| {
+        TAG_COLORS.iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(color.trim()))
+            .map(|(_, index)| format!("{}\n{}", name.trim(), index))
+    });
+    colored.unwrap_or_else(|| spec.trim().to_string())
+}
+
+fn tag_name(entry: &str) -> &str {
+    entry.split('\n').next().unwrap_or(entry)
+}
+
+/// 既存のタグに追加したタグ一覧（同名のタグが付いていればNone）
+pub fn with_tag(existing: &[String], entry: &str) -> Option<Vec<String>> {
+    if existing.iter().any(|tag| tag_name(tag) == tag_name(entry)) {
+        return None;
+    }
+    let mut tags = existing.to_vec();
+    tags.push(entry.to_string());
+    Some(tags)
+}
+
+/// 既存のタグから外したタグ一覧（付いていなければNone）
+pub fn without_tag(existing: &[String], entry: &str) -> Option<Vec<String>> {
+    if !existing.iter().any(|tag| tag_name(tag) == tag_name(entry)) {
+        return None;
+    }
+    Some(existing.iter().filter(|tag| tag_name(tag) != tag_name(entry)).cloned().collect())
+}
+
+/// ファイルにFinderタグを付与（既存のタグは残す）
+pub fn add_finder_tag(path: &Path, spec: &str) -> Result<(), String> {
+    let existing = platform::read_tags(path)?;
+    match with_tag(&existing, &tag_entry(spec)) {
+        Some(tags) => platform::write_tags(path, &tags),
+        None => Ok(()),
+    }
+}
+
+/// ファイルからFinderタグを外す（他のタグは残す）
+pub fn remove_finder_tag(path: &Path, spec: &str) -> Result<(), String> {
+    let existing = platform::read_tags(path)?;
+    match without_tag(&existing, &tag_entry(spec)) {
+        Some(tags) => platform::write_tags(path, &tags),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_entry_colors() {
+        assert_eq!(tag_entry("アーカイブ済み:green"), "アーカイブ済み\n2");
+        assert_eq!(tag_entry("Archived:Red"), "Archived\n6");
+        assert_eq!(tag_entry("アーカイブ済み"), "アーカイブ済み");
+        // 色名でない場合はコロンを含む名前として扱う
+        assert_eq!(tag_entry("Project:2024"), "Project:2024");
+    }
+
+    #[test]
+    fn test_with_and_without_tag_keep_other_tags() {
+        let existing = vec!["Work\n4".to_string()];
+        let tagged = with_tag(&existing, "アーカイブ済み\n2").unwrap();
+        assert_eq!(tagged, vec!["Work\n4".to_string(), "アーカイブ済み\n2".to_string()]);
+        // 色が異なっても同名なら付与済み扱い
+        assert!(with_tag(&tagged, "アーカイブ済み").is_none());
+
+        assert_eq!(without_tag(&tagged, "アーカイブ済み"), Some(existing.clone()));
+        assert!(without_tag(&existing, "アーカイブ済み").is_none());
+    }
+}
@@ -0,0 +1,317 @@
+use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
+use crate::internal::InternalError;
+
+/// S3エラーコードとユーザー向けメッセージの対応
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct S3ErrorMapping {
+    pub code: &'static str,
+    /// 原因
+    pub cause: &'static str,
+    /// 対処方法
+    pub remedy: &'static str,
+    /// 時間をおいて再試行すれば解消する可能性があるか
+    pub retryable: bool,
+}
+
+/// ネットワーク到達不能時に使用する疑似エラーコード
+pub const DISPATCH_FAILURE_CODE: &str = "DispatchFailure";
+/// リクエストタイムアウト時に使用する疑似エラーコード
+pub const TIMEOUT_CODE: &str = "TimeoutError";
+/// Expedited復元のプロビジョンドキャパシティが不足しているときのエラーコード
+pub const EXPEDITED_UNAVAILABLE_CODE: &str = "GlacierExpeditedRetrievalNotAvailable";
+
+/// 主要なS3エラーコードのマッピングテーブル
+pub const S3_ERROR_MAPPINGS: &[S3ErrorMapping] = &[
+    S3ErrorMapping {
+        code: "NoSuchBucket",
+        cause: "指定されたバケットが存在しません。",
+        remedy: "設定画面でバケット名とリージョンが正しいか確認してください。",
+        retryable: false,
+    },
+    S3ErrorMapping {
+        code: "NoSuchKey",
+        cause: "指定されたファイルがS3上に見つかりません。",
+        remedy: "ファイルが削除または移動されていないか確認してください。",
+        retryable: false,
+    },
+    S3ErrorMapping {
+        code: "NotFound",
+        cause: "指定されたバケットまたはファイルが見つかりません。",
+        remedy: "バケット名とファイルのパスを確認してください。",
+        retryable: false,
+    },
+    S3ErrorMapping {
+        code: "AccessDenied",
+        cause: "S3へのアクセスが拒否されました。",
+        remedy: "IAMユーザーに対象バケットへのアクセス権限（s3:PutObject等）が付与されているか確認してください。",
+        retryable: false,
+    },
+    S3ErrorMapping {
+        code: "Forbidden",
+        cause: "S3へのアクセスが拒否されました。",
+        remedy: "IAMポリシーとバケットポリシーの設定を確認してください。",
+        retryable: false,
+    },
+    S3ErrorMapping {
+        code: "InvalidAccessKeyId",
+        cause: "アクセスキーIDが無効です。",
+        remedy: "設定画面でアクセスキーIDを再入力するか、キーが無効化されていないか確認してください。",
+        retryable: false,
+    },
+    S3ErrorMapping {
+        code: "SignatureDoesNotMatch",
+        cause: "シークレットアクセスキーが一致しません。",
+        remedy: "設定画面でシークレットアクセスキーを再入力してください。",
+        retryable: false,
+    },
+    S3ErrorMapping {
+        code: "ExpiredToken",
+        cause: "一時認証情報の有効期限が切れています。",
+        remedy: "認証情報を更新してから再度実行してください。",
+        retryable: false,
+    },
+    S3ErrorMapping {
+        code: "RequestTimeTooSkewed",
+        cause: "このMacの時刻とAWSの時刻が大きくずれています。",
+        remedy: "システム設定で「日付と時刻を自動的に設定」を有効にしてください。",
+        retryable: false,
+    },
+    S3ErrorMapping {
+        code: "AuthorizationHeaderMalformed",
+        cause: "リクエストのリージョン指定がバケットのリージョンと一致しません。",
+        remedy: "設定画面のリージョンをバケットのリージョンに合わせてください。",
+        retryable: false,
+    },
+    S3ErrorMapping {
+        code: "PermanentRedirect",
+        cause: "バケットが別のリージョンに存在します。",
+        remedy: "設定画面のリージョンをバケットのリージョンに合わせてください。",
+        retryable: false,
+    },
+    S3ErrorMapping {
+        code: "InvalidObjectState",
+        cause: "ファイルがアーカイブ状態のため直接取得できません。",
+        remedy: "復元リクエストを行い、復元完了後にダウンロードしてください。",
+        retryable: false,
+    },
+    S3ErrorMapping {
+        code: EXPEDITED_UNAVAILABLE_CODE,
+        cause: "Expedited復元のキャパシティが不足しています。",
+        remedy: "StandardまたはBulkティアで復元してください。",
+        retryable: false,
+    },
+    S3ErrorMapping {
+        code: "EntityTooLarge",
+        cause: "ファイルサイズがS3の上限を超えています。",
+        remedy: "マルチパートアップロードを使用するか、ファイルを分割してください。",
+        retryable: false,
+    },
+    S3ErrorMapping {
+        code: "NoSuchUpload",
+        cause: "マルチパートアップロードが見つかりません（中断済みまたは期限切れ）。",
+        remedy: "アップロードを最初からやり直してください。",
+        retryable: false,
+    },
+    S3ErrorMapping {
+        code: "SlowDown",
+        cause: "S3へのリクエストが多すぎるため制限されています。",
+        remedy: "しばらく待ってから再試行してください。同時アップロード数を減らすと改善する場合があります。",
+        retryable: true,
+    },
+    S3ErrorMapping {
+        code: "RequestTimeout",
+        cause: "S3との通信がタイムアウトしました。",
+        remedy: "ネットワーク接続を確認し、再試行してください。",
+        retryable: true,
+    },
+    S3ErrorMapping {
+        code: "InternalError",
+        cause: "S3側で一時的なエラーが発生しました。",
+        remedy: "しばらく待ってから再試行してください。",
+        retryable: true,
+    },
+    S3ErrorMapping {
+        code: "ServiceUnavailable",
+        cause: "S3が一時的に利用できません。",
+        remedy: "しばらく待ってから再試行してください。",
+        retryable: true,
+    },
+    S3ErrorMapping {
+        code: DISPATCH_FAILURE_CODE,
+        cause: "AWSに接続できませんでした。",
+        remedy: "インターネット接続とプロキシ設定を確認してください。",
+        retryable: true,
+    },
+    S3ErrorMapping {
+        code: TIMEOUT_CODE,
+        cause: "AWSへのリクエストがタイムアウトしました。",
+        remedy: "ネットワーク接続を確認し、再試行してください。",
+        retryable: true,
+    },
+];
+
+/// エラーコードに対応するマッピングを取得
+pub fn lookup_s3_error(code: &str) -> Option<&'static S3ErrorMapping> {
+    S3_ERROR_MAPPINGS.iter().find(|mapping| mapping.code == code)
+}
+
+/// マッピングテーブル以外で再試行できるAWSのエラーコード（STSなど他サービスのスロットリング・5xx）
+const RETRYABLE_AWS_CODES: &[&str] = &[
+    "Throttling",
+    "ThrottlingException",
+    "RequestThrottled",
+    "TooManyRequestsException",
+    "RequestLimitExceeded",
+    "InternalFailure",
+    "ServiceUnavailableException",
+    "IDPCommunicationError",
+];
+
+/// 再試行で解消する可能性があるAWSエラーコードか（スロットリング・5xx・タイムアウト）
+pub fn is_retryable_s3_code(code: &str) -> bool {
+    match lookup_s3_error(code) {
+        Some(mapping) => mapping.retryable,
+        None => RETRYABLE_AWS_CODES.contains(&code),
+    }
+}
+
+/// ネットワーク到達不能を示す低レベルエラーの文言（SDKを経由しないI/Oエラー用）
+const NETWORK_ERROR_PHRASES: &[&str] = &[
+    "dispatch failure",
+    "connection refused",
+    "connection reset",
+    "network is unreachable",
+    "failed to lookup address",
+    "timed out",
+];
+
+/// 標準化済みのエラーメッセージがネットワーク起因か（オフライン検知に使用）
+pub fn is_network_error_message(message: &str) -> bool {
+    let is_mapped_network_error = [DISPATCH_FAILURE_CODE, TIMEOUT_CODE]
+        .iter()
+        .filter_map(|code| lookup_s3_error(code))
+        .any(|mapping| message.contains(mapping.cause));
+    if is_mapped_network_error {
+        return true;
+    }
+    let lower = message.to_lowercase();
+    NETWORK_ERROR_PHRASES.iter().any(|phrase| lower.contains(phrase))
+}
+
+/// エラーコードと生メッセージから日本語メッセージ付きのInternalErrorを生成
+pub fn map_s3_error(code: Option<&str>, raw_message: Option<&str>) -> InternalError {
+    let message = match code.and_then(lookup_s3_error) {
+        Some(mapping) => format!("{}{}", mapping.cause, mapping.remedy),
+        None => match (code, raw_message) {
+            (_, Some(raw)) => format!("S3でエラーが発生しました: {}", raw),
+            (Some(code), None) => format!("S3でエラーが発生しました（{}）", code),
+            (None, None) => "S3で不明なエラーが発生しました。".to_string(),
+        },
+    };
+
+    InternalError::S3 {
+        code: code.map(|c| c.to_string()),
+        message,
+    }
+}
+
+/// aws_sdk_s3のSdkErrorを日本語メッセージ付きのInternalErrorへ変換
+pub fn from_s3_sdk_error<E, R>(err: &SdkError<E, R>) -> InternalError
+where
+    E: ProvideErrorMetadata + std::error::Error + 'static,
+    R: std::fmt::Debug,
+{
+    // 生のエラー内容は調査用にログへ残す（認証情報はロガー側でマスクされる）
+    log::debug!("S3 SDK error: {:?}", err);
+
+    match err {
+        SdkError::DispatchFailure(_) => map_s3_error(Some(DISPATCH_FAILURE_CODE), None),
+        SdkError::TimeoutError(_) => map_s3_error(Some(TIMEOUT_CODE), None),
+        _ => match err.as_service_error() {
+            Some(service_error) => map_s3_error(service_error.code(), service_error.message()),
+            None => map_s3_error(None, Some(&err.to_string())),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::{AppError, ErrorCode};
+
+    #[test]
+    fn test_major_codes_are_mapped() {
+        for code in [
+            "NoSuchBucket",
+            "AccessDenied",
+            "InvalidAccessKeyId",
+            "RequestTimeTooSkewed",
+            "SlowDown",
+        ] {
+            let mapping = lookup_s3_error(code).expect(code);
+            assert!(!mapping.cause.is_empty());
+            assert!(!mapping.remedy.is_empty());
+        }
+        assert!(lookup_s3_error("UnknownCode").is_none());
+    }
+
+    #[test]
+    fn test_mapping_codes_are_unique() {
+        let mut codes: Vec<&str> = S3_ERROR_MAPPINGS.iter().map(|m| m.code).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), S3_ERROR_MAPPINGS.len());
+    }
+
+    #[test]
+    fn test_map_s3_error_keeps_raw_code() {
+        match map_s3_error(Some("NoSuchBucket"), Some("The specified bucket does not exist")) {
+            InternalError::S3 { code, message } => {
+                assert_eq!(code.as_deref(), Some("NoSuchBucket"));
+                assert!(message.contains("バケットが存在しません"));
+                assert!(!message.contains("does not exist"));
+            }
+            _ => panic!("Expected S3 error"),
+        }
+    }
+
+    #[test]
+    fn test_map_unknown_code_falls_back_to_raw_message() {
+        match map_s3_error(Some("WeirdError"), Some("something broke")) {
+            InternalError::S3 { code, message } => {
+                assert_eq!(code.as_deref(), Some("WeirdError"));
+                assert!(message.contains("something broke"));
+            }
+            _ => panic!("Expected S3 error"),
+        }
+    }
+
+    #[test]
+    fn test_retryable_codes() {
+        assert!(is_retryable_s3_code("SlowDown"));
+        assert!(is_retryable_s3_code(DISPATCH_FAILURE_CODE));
+        assert!(!is_retryable_s3_code("AccessDenied"));
+        assert!(!is_retryable_s3_code("UnknownCode"));
+    }
+
+    #[test]
+    fn test_is_network_error_message() {
+        let dispatch = crate::internal::standardize_error(map_s3_error(Some(DISPATCH_FAILURE_CODE), None));
+        assert!(is_network_error_message(&dispatch));
+        assert!(is_network_error_message("Failed to upload part: Connection refused (os error 61)"));
+        let access_denied = crate::internal::standardize_error(map_s3_error(Some("AccessDenied"), None));
+        assert!(!is_network_error_message(&access_denied));
+    }
+
+    #[test]
+    fn test_app_error_details_contains_raw_code() {
+        let app_error: AppError = map_s3_error(Some("AccessDenied"), None).into();
+        assert_eq!(app_error.code, ErrorCode::AwsS3);
+        assert_eq!(app_error.details.as_deref(), Some("AccessDenied"));
+        assert!(!app_error.retryable);
+        assert!(app_error.message.contains("アクセスが拒否されました"));
+
+        let app_error: AppError = map_s3_error(Some("SlowDown"), None).into();
+        assert!(app_error.retryable);
+    }
+}
@@ -0,0 +1,523 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// バックエンドが返すメッセージの言語（AppConfigのapp_settings.language）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Ja,
+    En,
+}
+
+impl Language {
+    /// 言語コードから判定（未対応の言語は日本語）
+    pub fn from_code(code: &str) -> Self {
+        if code.trim().to_ascii_lowercase().starts_with("en") {
+            Language::En
+        } else {
+            Language::Ja
+        }
+    }
+}
+
+/// 現在の表示言語（0: ja, 1: en）
+static CURRENT_LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_language(language: Language) {
+    let value = match language {
+        Language::Ja => 0,
+        Language::En => 1,
+    };
+    CURRENT_LANGUAGE.store(value, Ordering::Relaxed);
+}
+
+pub fn current_language() -> Language {
+    match CURRENT_LANGUAGE.load(Ordering::Relaxed) {
+        1 => Language::En,
+        _ => Language::Ja,
+    }
+}
+
+/// メッセージキーと各言語の文言を定義する
+///
+/// 文言中の`{name}`はパラメータで置換される
+macro_rules! message_catalog {
+    ($($variant:ident => $key:literal { ja: $ja:literal, en: $en:literal },)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum MessageKey {
+            $($variant,)*
+        }
+
+        impl MessageKey {
+            /// 定義済みの全メッセージキー
+            pub const ALL: &'static [MessageKey] = &[$(MessageKey::$variant,)*];
+
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(MessageKey::$variant => $key,)*
+                }
+            }
+
+            fn template(&self, language: Language) -> &'static str {
+                match (self, language) {
+                    $(
+                        (MessageKey::$variant, Language::Ja) => $ja,
+                        (MessageKey::$variant, Language::En) => $en,
+                    )*
+                }
+            }
+        }
+    };
+}
+
+message_catalog! {
+    // 共通
+    ListSeparator => "common.list_separator" { ja: "、", en: ", " },
+    BucketNameRequired => "common.bucket_name_required" {
+        ja: "バケット名が入力されていません",
+        en: "Bucket name is required" },
+    AwsConfigCreationFailed => "common.aws_config_failed" {
+        ja: "AWS設定の作成に失敗しました: {error}",
+        en: "AWS config creation failed: {error}" },
+    S3ClientCreationFailed => "common.s3_client_failed" {
+        ja: "S3クライアントの作成に失敗しました: {error}",
+        en: "S3 client creation failed: {error}" },
+
+    // アップロード
+    UploadItemNotFound => "upload.item_not_found" {
+        ja: "アップロードアイテムが見つかりません: {item_id}",
+        en: "Upload item not found: {item_id}" },
+    UploadItemNotPending => "upload.item_not_pending" {
+        ja: "待機中ではないため開始できません: {item_id}（{status}）",
+        en: "Upload item is not pending: {item_id} ({status})" },
+    UploadItemNotEditable => "upload.item_not_editable" {
+        ja: "待機中ではないため編集できません: {item_id}（{status}）",
+        en: "Only pending upload items can be edited: {item_id} ({status})" },
+    UploadQueueLockFailed => "upload.queue_lock_failed" {
+        ja: "アップロードキューを取得できませんでした: {error}",
+        en: "Failed to lock upload queue: {error}" },
+    UploadQueueInitialized => "upload.queue_initialized" {
+        ja: "アップロードキューを初期化しました",
+        en: "Upload queue initialized successfully" },
+    UploadNoFilesSelected => "upload.no_files_selected" {
+        ja: "ファイルが選択されていません",
+        en: "No files selected" },
+    UploadQueueNotInitialized => "upload.queue_not_initialized" {
+        ja: "アップロード設定が初期化されていません。アップロード画面を一度開いてください",
+        en: "Upload settings are not initialized. Open the upload screen once first" },
+    UploadFilesAdded => "upload.files_added" {
+        ja: "{count}件のファイルをアップロードキューに追加しました",
+        en: "Added {count} files to upload queue" },
+    UploadFilesAddedWithWarning => "upload.files_added_with_warning" {
+        ja: "{count}件のファイルをアップロードキューに追加しました。{warning}",
+        en: "Added {count} files to upload queue. {warning}" },
+    UploadPathsSkipped => "upload.paths_skipped" {
+        ja: "（リンク・重複のため{count}件をスキップしました: {paths}）",
+        en: " ({count} skipped as links or duplicates: {paths})" },
+    UploadFilesInUse => "upload.files_in_use" {
+        ja: "（{count}件は他のアプリが書き込み中のため、書き込みが終わるまで待機します: {paths}）",
+        en: " ({count} are being written by another application and will start once released: {paths})" },
+    UploadFilesFailedToAdd => "upload.files_failed_to_add" {
+        ja: "（{count}件は追加できませんでした: {paths}）",
+        en: " ({count} could not be added: {paths})" },
+    UploadItemsRemoved => "upload.items_removed" {
+        ja: "{count}件のアップロードアイテムを削除しました",
+        en: "Removed {count} upload item(s)" },
+    UploadAlreadyRunning => "upload.already_running" {
+        ja: "アップロード処理はすでに実行中です",
+        en: "Upload processing is already running" },
+    UploadProcessingStarted => "upload.processing_started" {
+        ja: "アップロード処理を開始しました",
+        en: "Upload processing started" },
+    UploadProcessingStopped => "upload.processing_stopped" {
+        ja: "アップロード処理を停止しました",
+        en: "Upload processing stopped" },
+    UploadQueuePaused => "upload.queue_paused" {
+        ja: "アップロードキューを一時停止しました",
+        en: "Upload queue paused" },
+    UploadQueueResumed => "upload.queue_resumed" {
+        ja: "アップロードキューを再開しました",
+        en: "Upload queue resumed" },
+    UploadRetryQueued => "upload.retry_queued" {
+        ja: "アップロードアイテムを再試行待ちにしました",
+        en: "Upload item queued for retry" },
+    UploadQueueCleared => "upload.queue_cleared" {
+        ja: "アップロードキューをクリアしました",
+        en: "Upload queue cleared" },
+    UploadFailedNotification => "upload.failed_notification" {
+        ja: "アップロードに失敗しました",
+        en: "Upload failed" },
+
+    // ティア・ライセンス
+    TierFreeConcurrentLimit => "tier.free_concurrent_limit" {
+        ja: "無料版では同時アップロードは1つまでです。現在アクティブ: {active}",
+        en: "The free tier allows only one upload at a time. Currently active: {active}" },
+    TierConcurrentLimit => "tier.concurrent_limit" {
+        ja: "同時アップロード数の上限に達しています: {active}/{limit}",
+        en: "Concurrent upload limit reached: {active}/{limit}" },
+    TierFreeFileLimit => "tier.free_file_limit" {
+        ja: "無料版では最大{max_files}ファイルまでアップロードできます",
+        en: "The free tier allows up to {max_files} files per upload" },
+    TierFreeSizeLimit => "tier.free_size_limit" {
+        ja: "無料版では最大{max_size_mb}MBまでアップロードできます",
+        en: "The free tier allows up to {max_size_mb}MB per upload" },
+    LicenseExpiredFallback => "license.expired_fallback" {
+        ja: "ライセンスの有効期限が切れたため無料版に切り替えました",
+        en: "The license has expired; switched to the free tier" },
+    LicenseNotActive => "license.not_active" {
+        ja: "ライセンスキーが有効ではありません",
+        en: "License key is not active" },
+
+    // 認証
+    AuthAccessKeyRequired => "auth.access_key_required" {
+        ja: "Access Key IDが入力されていません",
+        en: "Access Key ID is required" },
+    AuthSecretKeyRequired => "auth.secret_key_required" {
+        ja: "Secret Access Keyが入力されていません",
+        en: "Secret Access Key is required" },
+    AuthRegionRequired => "auth.region_required" {
+        ja: "リージョンが入力されていません",
+        en: "Region is required" },
+    AuthSucceeded => "auth.succeeded" {
+        ja: "AWS認証に成功しました",
+        en: "AWS authentication successful" },
+    AuthFailed => "auth.failed" {
+        ja: "認証に失敗しました: {error}",
+        en: "Authentication failed: {error}" },
+    AuthSimulated => "auth.simulated" {
+        ja: "認証をシミュレートしました（AWSへのリクエストは送信していません）",
+        en: "Authentication simulated; no request was sent to AWS" },
+    AuthCredentialsSaved => "auth.credentials_saved" {
+        ja: "認証情報を安全に保存しました",
+        en: "Credentials saved securely" },
+    AuthCredentialsSavedWithBiometry => "auth.credentials_saved_with_biometry" {
+        ja: "認証情報をTouch ID/Face IDで保護して保存しました",
+        en: "Credentials saved with Touch ID/Face ID" },
+    AuthCredentialsNotFound => "auth.credentials_not_found" {
+        ja: "保存された認証情報が見つかりません",
+        en: "No saved credentials were found" },
+    AuthBiometryCancelled => "auth.biometry_cancelled" {
+        ja: "Touch ID/Face ID認証がキャンセルされました",
+        en: "Touch ID/Face ID authentication was cancelled" },
+    AuthBiometryFailed => "auth.biometry_failed" {
+        ja: "Touch ID/Face ID認証に失敗しました: {error}",
+        en: "Touch ID/Face ID authentication failed: {error}" },
+    AuthBiometryUnavailable => "auth.biometry_unavailable" {
+        ja: "Touch ID/Face IDはmacOSでのみ利用できます",
+        en: "Touch ID/Face ID is only available on macOS" },
+    AuthCredentialsLocked => "auth.credentials_locked" {
+        ja: "認証情報がロックされています。ロックを解除してから再度お試しください",
+        en: "Credentials are locked. Unlock them and try again" },
+    AuthCredentialsExpired => "auth.credentials_expired" {
+        ja: "認証情報のキャッシュが期限切れになりました。再度ロックを解除してください",
+        en: "The cached credentials have expired. Unlock them again" },
+
+    // ライフサイクル
+    LifecycleEnabled => "lifecycle.enabled" {
+        ja: "ReelVaultライフサイクルポリシーを有効化しました: {days}日後 -> {storage_class}",
+        en: "ReelVault lifecycle policy enabled: {days} day(s) -> {storage_class}" },
+    LifecycleDisabled => "lifecycle.disabled" {
+        ja: "ReelVaultライフサイクルポリシーを無効化しました",
+        en: "ReelVault lifecycle policy disabled" },
+    LifecycleRuleNotFound => "lifecycle.rule_not_found" {
+        ja: "ReelVaultのライフサイクルルールが見つかりません",
+        en: "ReelVault lifecycle rule not found" },
+    LifecycleBucketAccessFailed => "lifecycle.bucket_access_failed" {
+        ja: "バケットアクセスエラー: {error}",
+        en: "Bucket access error: {error}" },
+    LifecycleFetchFailed => "lifecycle.fetch_failed" {
+        ja: "ライフサイクル設定取得エラー: {error}",
+        en: "Failed to get lifecycle configuration: {error}" },
+    LifecycleSetupFailed => "lifecycle.setup_failed" {
+        ja: "ライフサイクル設定に失敗しました: {error}",
+        en: "Failed to set up the lifecycle policy: {error}" },
+    LifecycleVerifyFailed => "lifecycle.verify_failed" {
+        ja: "ライフサイクル設定の確認に失敗しました: {error}",
+        en: "Failed to verify the lifecycle policy: {error}" },
+    LifecyclePrefixUncovered => "lifecycle.prefix_uncovered" {
+        ja: "{total}件中{uncovered}件のS3キーがライフサイクルルールの対象外です。このままだとアーカイブ移行されず、STANDARDのまま課金され続けます。対象キーの例: {examples}（有効なルールのプレフィックス: {prefixes}）",
+        en: "{uncovered} of {total} S3 keys are not covered by any lifecycle rule. They will stay in STANDARD and keep being billed instead of being archived. Example keys: {examples} (active rule prefixes: {prefixes})" },
+    LifecyclePrefixNone => "lifecycle.prefix_none" { ja: "なし", en: "none" },
+    LifecyclePrefixWholeBucket => "lifecycle.prefix_whole_bucket" { ja: "（バケット全体）", en: "(entire bucket)" },
+    LifecyclePrefixQuoted => "lifecycle.prefix_quoted" { ja: "「{prefix}」", en: "\"{prefix}\"" },
+    ReadinessBucketMissing => "readiness.bucket_missing" {
+        ja: "S3バケット名が設定されていません",
+        en: "No S3 bucket name is configured" },
+    ReadinessCredentialsIncomplete => "readiness.credentials_incomplete" {
+        ja: "AWS認証情報が不完全です",
+        en: "AWS credentials are incomplete" },
+    ReadinessBucketInaccessible => "readiness.bucket_inaccessible" {
+        ja: "バケット「{bucket}」にアクセスできません: {error}",
+        en: "Cannot access bucket \"{bucket}\": {error}" },
+    ReadinessReady => "readiness.ready" {
+        ja: "アップロード準備完了。ライフサイクル設定も正常です。",
+        en: "Ready to upload. The lifecycle policy is configured correctly." },
+    ReadinessReadyWithWarning => "readiness.ready_with_warning" {
+        ja: "ライフサイクル設定は正常ですが、{warning}",
+        en: "The lifecycle policy is configured, but {warning}" },
+    ReadinessLifecycleMissing => "readiness.lifecycle_missing" {
+        ja: "ライフサイクル設定に問題があります。バケット「{bucket}」のライフサイクルポリシーが見つかりません。AWS認証タブで「🔄 ライフサイクル再設定」を実行してください。",
+        en: "There is a problem with the lifecycle configuration. No lifecycle policy was found for bucket \"{bucket}\". Run \"🔄 Reconfigure lifecycle\" in the AWS authentication tab." },
+    CrossRegionTransfer => "region.cross_region_transfer" {
+        ja: "バケット「{bucket}」は{bucket_region}にあり、設定リージョン{client_region}と異なるためクロスリージョン転送が発生します（約{price}USD/GB、今回の転送で約{cost}USDの追加）",
+        en: "Bucket \"{bucket}\" is in {bucket_region}, which differs from the configured region {client_region}, so cross-region transfer will occur (about {price} USD/GB, roughly {cost} USD extra for this transfer)" },
+    CrossRegionTransferUnsized => "region.cross_region_transfer_unsized" {
+        ja: "バケット「{bucket}」は{bucket_region}にあり、設定リージョン{client_region}と異なるためクロスリージョン転送が発生します（約{price}USD/GBの追加）",
+        en: "Bucket \"{bucket}\" is in {bucket_region}, which differs from the configured region {client_region}, so cross-region transfer will occur (about {price} USD/GB extra)" },
+    BucketSecurityEnablePublicAccessBlock => "bucket_security.enable_public_access_block" {
+        ja: "バケット「{bucket}」のパブリックアクセスブロック（4項目）をすべて有効にしてください",
+        en: "Enable all four Block Public Access settings for bucket \"{bucket}\"" },
+    BucketSecurityPolicyPublic => "bucket_security.policy_public" {
+        ja: "バケット「{bucket}」はバケットポリシーで公開されています。公開を許可しているステートメントを削除してください",
+        en: "Bucket \"{bucket}\" is public through its bucket policy. Remove the statements that grant public access" },
+    BucketSecurityEnableEncryption => "bucket_security.enable_encryption" {
+        ja: "バケット「{bucket}」のデフォルト暗号化（SSE-S3またはSSE-KMS）を有効にしてください",
+        en: "Enable default encryption (SSE-S3 or SSE-KMS) for bucket \"{bucket}\"" },
+    BucketSecurityEnableVersioning => "bucket_security.enable_versioning" {
+        ja: "誤った上書き・削除から復旧できるよう、バケット「{bucket}」のバージョニングの有効化を検討してください",
+        en: "Consider enabling versioning for bucket \"{bucket}\" so accidental overwrites and deletions can be recovered" },
+    BucketSecurityUnknown => "bucket_security.unknown" {
+        ja: "権限不足などで確認できない項目があります。{permission}の権限を付与すると確認できます",
+        en: "Some settings could not be checked, possibly due to missing permissions. Grant {permission} to include them" },
+    VersioningEnabled => "versioning.enabled" {
+        ja: "バケット「{bucket}」のバージョニングは有効です",
+        en: "Versioning is enabled for bucket \"{bucket}\"" },
+    VersioningSuspended => "versioning.suspended" {
+        ja: "バケット「{bucket}」のバージョニングは一時停止中です。停止前に保存されたバージョンのみ取得できます",
+        en: "Versioning is suspended for bucket \"{bucket}\". Only versions stored before it was suspended are available" },
+    VersioningDisabled => "versioning.disabled" {
+        ja: "バケット「{bucket}」はバージョニングが有効になっていないため、旧バージョンは保存されていません",
+        en: "Versioning has never been enabled for bucket \"{bucket}\", so no previous versions are stored" },
+    VersionNotFound => "versioning.version_not_found" {
+        ja: "{key}のバージョン「{version_id}」が見つかりません",
+        en: "Version \"{version_id}\" of {key} was not found" },
+    VersionIsDeleteMarker => "versioning.delete_marker" {
+        ja: "{key}のバージョン「{version_id}」は削除マーカーのため、ダウンロード・復元できません",
+        en: "Version \"{version_id}\" of {key} is a delete marker and cannot be downloaded or restored" },
+    RestoreDaysOutOfRange => "restore.days_out_of_range" {
+        ja: "復元の保持日数は1〜{max}日で指定してください（指定値: {days}）",
+        en: "Restore days must be between 1 and {max} (got {days})" },
+    RestoreAlreadyInProgress => "restore.already_in_progress" {
+        ja: "{key} は復元処理中のため保持日数を変更できません。復元完了後に延長してください",
+        en: "{key} is still being restored, so its restore days cannot be changed. Extend it after the restore completes" },
+    RestoreExpeditedNotSupported => "restore.expedited_not_supported" {
+        ja: "{key} はDEEP_ARCHIVEのためExpedited復元を利用できません。StandardかBulkを選択してください",
+        en: "{key} is in DEEP_ARCHIVE, which does not support Expedited restores. Choose Standard or Bulk" },
+    RestoreExpeditedFallback => "restore.expedited_fallback" {
+        ja: "Expedited不可のためStandardで実行",
+        en: "Expedited capacity unavailable; restored with Standard" },
+    ArchiveRestoreRequired => "archive.restore_required" {
+        ja: "{key}は{storage_class}に保存されているため、取り出す前に復元が必要です。復元リクエストを送りますか？",
+        en: "{key} is stored in {storage_class} and must be restored first. Send a restore request?" },
+    ArchiveRestoreInProgress => "archive.restore_in_progress" {
+        ja: "{key}は復元中です。完了後に再度お試しください",
+        en: "{key} is being restored. Please try again once it completes" },
+    ArchiveRestoreInProgressEta => "archive.restore_in_progress_eta" {
+        ja: "{key}は復元中です（完了予定: {eta}）",
+        en: "{key} is being restored (estimated completion: {eta})" },
+    ArchiveRestoreRequested => "archive.restore_requested" {
+        ja: "{key}の復元をリクエストしました（完了予定: {eta}）",
+        en: "Restore requested for {key} (estimated completion: {eta})" },
+    QuickUploadTitle => "quick_upload.title" {
+        ja: "クイックアップロード",
+        en: "Quick Upload" },
+    QuickUploadShortcutInvalid => "quick_upload.shortcut_invalid" {
+        ja: "ショートカット「{shortcut}」は無効です: {error}",
+        en: "Shortcut \"{shortcut}\" is invalid: {error}" },
+    QuickUploadShortcutConflict => "quick_upload.shortcut_conflict" {
+        ja: "ショートカット「{shortcut}」を登録できませんでした。他のアプリで使用されている可能性があります（{error}）",
+        en: "Could not register shortcut \"{shortcut}\". It may be in use by another application ({error})" },
+    QuickUploadClipboardEmpty => "quick_upload.clipboard_empty" {
+        ja: "クリップボードにアップロードできるファイルのパスがありません",
+        en: "The clipboard does not contain any file paths to upload" },
+    DropTitle => "drop.title" {
+        ja: "ドラッグ＆ドロップ",
+        en: "Drag and Drop" },
+    DropNoFiles => "drop.no_files" {
+        ja: "ドロップされた項目にアップロードできるファイルがありません",
+        en: "The dropped items contain no files that can be uploaded" },
+    DropNothingPending => "drop.nothing_pending" {
+        ja: "確認待ちのドロップはありません",
+        en: "There is no dropped folder waiting for confirmation" },
+
+    // 命名パターン
+    NamingUnknownToken => "naming.unknown_token" {
+        ja: "命名パターン「{pattern}」に未知のトークン {{token}} が含まれています",
+        en: "Naming pattern \"{pattern}\" contains an unknown token {{token}}" },
+    NamingInvalidFormat => "naming.invalid_format" {
+        ja: "命名パターン「{pattern}」のトークン {{token}} の書式が正しくありません",
+        en: "Token {{token}} in naming pattern \"{pattern}\" has an invalid format" },
+    NamingUnclosedToken => "naming.unclosed_token" {
+        ja: "命名パターン「{pattern}」のトークン {token} が閉じられていません",
+        en: "Token {token} in naming pattern \"{pattern}\" is not closed" },
+    NamingHashTooLarge => "naming.hash_too_large" {
+        ja: "{file} は{limit_mb}MBを超えるため{hash8}を計算できません。先にメタデータを作成してください",
+        en: "{file} is larger than {limit_mb} MB, so {hash8} cannot be computed. Create its metadata first" },
+    HealthWatcherStopped => "health.watcher_stopped" {
+        ja: "自動アーカイブが有効ですが、フォルダ監視が停止しています",
+        en: "Auto-archive is enabled, but no folder watcher is running" },
+    HealthAutoArchiveDegraded => "health.auto_archive_degraded" {
+        ja: "自動アーカイブに問題があります: {reason}",
+        en: "Auto-archive has a problem: {reason}" },
+    HealthQueueFailed => "health.queue_failed" {
+        ja: "アップロードに失敗したファイルが{count}件あります",
+        en: "{count} file(s) failed to upload" },
+    HealthRestoreFailed => "health.restore_failed" {
+        ja: "復元に失敗したファイルが{count}件あります",
+        en: "{count} restore request(s) failed" },
+    HealthLifecycleDisabled => "health.lifecycle_disabled" {
+        ja: "バケット「{bucket}」のReelVaultライフサイクルルールが有効になっていません",
+        en: "The ReelVault lifecycle rule is not enabled for bucket \"{bucket}\"" },
+    HealthLifecycleCheckFailed => "health.lifecycle_check_failed" {
+        ja: "バケット「{bucket}」のライフサイクル設定を確認できませんでした: {error}",
+        en: "Could not check the lifecycle policy of bucket \"{bucket}\": {error}" },
+    HealthLicenseExpired => "health.license_expired" {
+        ja: "ライセンスの有効期限が切れています",
+        en: "The license has expired" },
+    HealthLicenseExpiring => "health.license_expiring" {
+        ja: "ライセンスの有効期限まで残り{days}日です",
+        en: "The license expires in {days} day(s)" },
+    HealthLowDiskSpace => "health.low_disk_space" {
+        ja: "{path}の空き容量が残り{available}です",
+        en: "Only {available} of free space is left on {path}" },
+    HealthArchiveTransitionDelayed => "health.archive_transition_delayed" {
+        ja: "バケット「{bucket}」で移行予定日を過ぎてもアーカイブされていないオブジェクトが{count}件あります",
+        en: "{count} object(s) in bucket \"{bucket}\" are past their scheduled archive transition" },
+    HealthArchiveTransitionUncovered => "health.archive_transition_uncovered" {
+        ja: "バケット「{bucket}」でライフサイクルルールの対象外のためアーカイブされないオブジェクトが{count}件あります",
+        en: "{count} object(s) in bucket \"{bucket}\" will not be archived because no lifecycle rule covers them" },
+
+    // 取り込みフォルダ
+    HotFolderWatchError => "hot_folder.watch_error" {
+        ja: "ファイル監視でエラーが発生しました: {error}",
+        en: "File watching failed: {error}" },
+    HotFolderWatchStopped => "hot_folder.watch_stopped" {
+        ja: "ファイル監視が停止しました",
+        en: "File watching stopped" },
+    HotFolderQueueFailed => "hot_folder.queue_failed" {
+        ja: "キューに追加できません: {error}",
+        en: "Could not add the file to the upload queue: {error}" },
+    HotFolderUploadFailed => "hot_folder.upload_failed" {
+        ja: "アップロードに失敗しました: {error}",
+        en: "Upload failed: {error}" },
+    HotFolderKeptUnverified => "hot_folder.kept_unverified" {
+        ja: "アップロードを検証できなかった（またはミラーが失敗した）ため元ファイルを残しました",
+        en: "Kept the original file because the upload could not be verified (or a mirror failed)" },
+    HotFolderKeptModified => "hot_folder.kept_modified" {
+        ja: "キュー追加後に変更されたため元ファイルを残しました",
+        en: "Kept the original file because it changed after it was queued" },
+    HotFolderAfterUploadFailed => "hot_folder.after_upload_failed" {
+        ja: "元ファイルを処理できません: {error}",
+        en: "Could not delete or move the original file: {error}" },
+    HotFolderRecordFailed => "hot_folder.record_failed" {
+        ja: "処理済みの記録に失敗しました: {error}",
+        en: "Failed to record the file as processed: {error}" },
+
+    // 転送量の月次上限
+    TransferCapReached => "transfer.cap_reached" {
+        ja: "今月の転送量が上限（{cap}）に達したため、新しい転送を停止しています",
+        en: "This month's transfer cap ({cap}) has been reached; new transfers are paused" },
+
+    // アップロード画面の警告
+    UploadWarningLifecycleNotConfigured => "upload_warning.lifecycle_not_configured" {
+        ja: "バケット「{bucket}」にReelVaultのライフサイクルルールが設定されていません",
+        en: "The ReelVault lifecycle rule is not configured for bucket \"{bucket}\"" },
+    UploadWarningCredentialsExpiring => "upload_warning.credentials_expiring" {
+        ja: "認証情報の有効期限まで残り{minutes}分です",
+        en: "The cached credentials expire in {minutes} minute(s)" },
+}
+
+/// メッセージキーとパラメータの組（フロントへはcode・key・paramsとして返す）
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalizedMessage {
+    pub key: MessageKey,
+    pub params: Vec<(&'static str, String)>,
+}
+
+impl LocalizedMessage {
+    pub fn new(key: MessageKey) -> Self {
+        Self { key, params: Vec::new() }
+    }
+
+    pub fn param(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.params.push((name, value.to_string()));
+        self
+    }
+
+    /// 現在の言語で文言を解決
+    pub fn render(&self) -> String {
+        self.render_in(current_language())
+    }
+
+    pub fn render_in(&self, language: Language) -> String {
+        self.params.iter().fold(self.key.template(language).to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+    }
+
+    pub fn params_map(&self) -> BTreeMap<String, String> {
+        self.params.iter().map(|(name, value)| (name.to_string(), value.clone())).collect()
+    }
+}
+
+impl From<MessageKey> for LocalizedMessage {
+    fn from(key: MessageKey) -> Self {
+        LocalizedMessage::new(key)
+    }
+}
+
+impl std::fmt::Display for LocalizedMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+/// パラメータなしのメッセージを現在の言語で解決
+pub fn tr(key: MessageKey) -> String {
+    LocalizedMessage::new(key).render()
+}
+
+/// 文言中の`{name}`プレースホルダー名を抽出
+fn placeholders(template: &str) -> BTreeSet<&str> {
+    template.split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+        .filter(|name| !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c == '_'))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_covers_every_key_in_every_language() {
+        let mut seen = BTreeSet::new();
+        for key in MessageKey::ALL {
+            assert!(seen.insert(key.as_str()), "duplicate message key: {}", key.as_str());
+            assert!(key.as_str().contains('.'), "key must be namespaced: {}", key.as_str());
+
+            let ja = key.template(Language::Ja);
+            let en = key.template(Language::En);
+            assert!(!ja.is_empty() && !en.is_empty(), "missing text for {}", key.as_str());
+            assert_eq!(placeholders(ja), placeholders(en), "placeholder mismatch for {}", key.as_str());
+        }
+    }
+
+    #[test]
+    fn test_render_substitutes_params() {
+        let message = LocalizedMessage::new(MessageKey::TierConcurrentLimit)
+            .param("active", 3)
+            .param("limit", 3);
+        assert_eq!(message.render_in(Language::Ja), "同時アップロード数の上限に達しています: 3/3");
+        assert_eq!(message.render_in(Language::En), "Concurrent upload limit reached: 3/3");
+        assert_eq!(message.params_map().get("limit").map(String::as_str), Some("3"));
+    }
+
+    #[test]
+    fn test_language_from_code() {
+        assert_eq!(Language::from_code("en"), Language::En);
+        assert_eq!(Language::from_code("en-US"), Language::En);
+        assert_eq!(Language::from_code("ja"), Language::Ja);
+        assert_eq!(Language::from_code("fr"), Language::Ja);
+    }
+}
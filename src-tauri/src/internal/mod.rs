@@ -1,3 +1,8 @@
 pub mod error;
+pub mod error_mapper;
+pub mod i18n;
+pub mod sanitize;
+pub mod time;
+pub mod unicode;
 
-pub use error::{InternalError, standardize_error}; 
\ No newline at end of file
+pub use error::{AppError, ErrorCode, InternalError, standardize_error}; 
\ No newline at end of file
@@ -0,0 +1,53 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use chrono::{DateTime, Utc};
+
+/// SystemTimeをRFC3339（UTC）の文字列へ変換（エポック以前など表現できない場合はNone）
+pub fn system_time_to_rfc3339(time: SystemTime) -> Option<String> {
+    let elapsed = time.duration_since(UNIX_EPOCH).ok()?;
+    let seconds = i64::try_from(elapsed.as_secs()).ok()?;
+    DateTime::<Utc>::from_timestamp(seconds, elapsed.subsec_nanos()).map(|time| time.to_rfc3339())
+}
+
+/// ファイルメタデータの日時（`modified()`・`created()`の結果）をRFC3339へ変換
+///
+/// 作成日時を持たないファイルシステムなど、取得できない場合はNone
+pub fn file_time_to_rfc3339(time: std::io::Result<SystemTime>) -> Option<String> {
+    time.ok().and_then(system_time_to_rfc3339)
+}
+
+/// 旧形式（`SystemTime { tv_sec: .., tv_nsec: .. }`のDebug出力）の日時をRFC3339へ変換
+pub fn legacy_system_time_to_rfc3339(value: &str) -> Option<String> {
+    let field = |name: &str| -> Option<i64> {
+        let start = value.find(name)? + name.len();
+        let digits: String = value[start..].chars()
+            .skip_while(|c| *c == ':' || c.is_whitespace())
+            .take_while(|c| c.is_ascii_digit() || *c == '-')
+            .collect();
+        digits.parse().ok()
+    };
+    let seconds = field("tv_sec")?;
+    let nanos = field("tv_nsec").unwrap_or(0);
+    DateTime::<Utc>::from_timestamp(seconds, u32::try_from(nanos).ok()?).map(|time| time.to_rfc3339())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_system_time_to_rfc3339() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+        assert_eq!(system_time_to_rfc3339(time).as_deref(), Some("2024-01-01T00:00:00+00:00"));
+        assert_eq!(file_time_to_rfc3339(Err(std::io::Error::other("unsupported"))), None);
+    }
+
+    #[test]
+    fn test_legacy_system_time_to_rfc3339() {
+        assert_eq!(
+            legacy_system_time_to_rfc3339("SystemTime { tv_sec: 1704067200, tv_nsec: 500000000 }").as_deref(),
+            Some("2024-01-01T00:00:00.500+00:00")
+        );
+        assert_eq!(legacy_system_time_to_rfc3339("2024-01-01T00:00:00Z"), None);
+    }
+}
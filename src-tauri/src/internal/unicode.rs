@@ -0,0 +1,52 @@
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+/// NFC形式へ正規化（macOSのファイルシステムが返すNFDの濁点・半濁点を合成済みの文字にする）
+pub fn to_nfc(value: &str) -> String {
+    if is_nfc(value) {
+        value.to_string()
+    } else {
+        value.nfc().collect()
+    }
+}
+
+/// NFD形式へ正規化
+pub fn to_nfd(value: &str) -> String {
+    value.nfd().collect()
+}
+
+/// 照合用の候補（NFC、異なる場合は続けてNFD）
+///
+/// NFC正規化を導入する前にNFDのまま保存されたキー・レコードとの互換に使う
+pub fn normalization_variants(value: &str) -> Vec<String> {
+    let nfc = to_nfc(value);
+    let nfd = to_nfd(value);
+    if nfc == nfd {
+        vec![nfc]
+    } else {
+        vec![nfc, nfd]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 「ポートフォリオ」をmacOSが返すNFD（半濁点を結合文字で分解）で表したもの
+    const PORTFOLIO_NFD: &str = "\u{30DB}\u{309A}\u{30FC}\u{30C8}\u{30D5}\u{30A9}\u{30EA}\u{30AA}.mp4";
+
+    #[test]
+    fn test_to_nfc_composes_japanese_file_name() {
+        assert_ne!(PORTFOLIO_NFD, "ポートフォリオ.mp4");
+        assert_eq!(to_nfc(PORTFOLIO_NFD), "ポートフォリオ.mp4");
+        assert_eq!(to_nfc("ポートフォリオ.mp4"), "ポートフォリオ.mp4");
+    }
+
+    #[test]
+    fn test_normalization_variants() {
+        assert_eq!(
+            normalization_variants(PORTFOLIO_NFD),
+            vec!["ポートフォリオ.mp4".to_string(), PORTFOLIO_NFD.to_string()]
+        );
+        assert_eq!(normalization_variants("clip.mov"), vec!["clip.mov".to_string()]);
+    }
+}
@@ -1,11 +1,6 @@
 // Learn more about Tauri commands at https://tauri.app/v1/guides/features/command
 use std::sync::{Arc, Mutex};
-use tauri::{
-    menu::{Menu, MenuItem, PredefinedMenuItem},
-    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager, Emitter,
-};
-use tauri_plugin_dialog::DialogExt;
+use tauri::Manager;
 
 // モジュール定義
 mod commands {
@@ -17,10 +12,81 @@ mod commands {
     pub mod metadata;
     pub mod upload_system;
     pub mod lifecycle;
+    pub mod diagnostics;
+    pub mod autostart;
+    pub mod sync;
+    pub mod queue_export;
+    pub mod compression;
+    pub mod bundle;
+    pub mod auto_archive;
+    pub mod retention;
+    pub mod audit;
+    pub mod mock_aws;
+    pub mod license;
+    pub mod metrics;
+    pub mod health_report;
+    pub mod quick_upload;
+    pub mod drag_drop;
+    pub mod remote_verify;
+    pub mod credential_cache;
+    pub mod naming;
+    pub mod read_scheduler;
+    pub mod bucket_init;
+    pub mod bucket_security;
+    pub mod archive_transition;
+    pub mod mirror_upload;
+    pub mod api_usage;
+    pub mod metadata_jobs;
+    pub mod watch_backend;
+    pub mod manifest;
+    pub mod prefix_restore;
+    pub mod upload_history;
+    pub mod operations;
+    pub mod bandwidth;
+    pub mod restored_files;
+    pub mod s3_cache;
+    pub mod s3_etag;
+    pub mod projects;
+    pub mod bucket_region;
+    pub mod file_links;
+    pub mod startup_diagnostics;
+    pub mod startup_phases;
+    pub mod auto_upload_guard;
+    pub mod restore_notifications;
+    pub mod queue_duplicates;
+    pub mod command_timing;
+    pub mod open_location;
+    pub mod part_progress;
+    pub mod object_metadata_edit;
+    pub mod metadata_db_repair;
+    pub mod transfer_cap;
+    pub mod frontend_log;
+    pub mod file_in_use;
+    pub mod s3_client_cache;
+    pub mod local_file_lock;
+    pub mod queue_watchdog;
+    pub mod upload_overrides;
+    pub mod upload_limits;
+    pub mod hot_folder;
+    pub mod upload_checksum;
+    pub mod app_data_usage;
+    #[cfg(test)]
+    mod integration_tests;
 }
 
 mod logger;
 mod internal;
+mod tray;
+mod notifications;
+mod badge;
+mod single_instance;
+mod window_state;
+mod shutdown;
+mod power;
+mod lock_monitor;
+mod network;
+mod finder_tags;
+pub mod cli;
 
 // コマンドをインポート
 use commands::file_operations::*;
@@ -31,107 +97,141 @@ use commands::aws_auth::*;
 use commands::metadata::*;
 use commands::upload_system::*;
 use commands::lifecycle::*;
-
-// システムトレイのセットアップ関数
-fn setup_system_tray(app: &tauri::App) -> tauri::Result<()> {
-    let settings_item = MenuItem::with_id(app, "settings", "設定", true, Some("Cmd+,"))?;
-    let version_item = MenuItem::with_id(app, "version", "ReelVaultのバージョン情報", true, None::<&str>)?;
-    let separator = PredefinedMenuItem::separator(app)?;
-    let quit_item = MenuItem::with_id(app, "quit", "終了", true, Some("Cmd+Q"))?;
-    
-    let menu = Menu::with_items(app, &[&settings_item, &version_item, &separator, &quit_item])?;
-    
-    let _tray = TrayIconBuilder::new()
-        .icon(app.default_window_icon().unwrap().clone())
-        .menu(&menu)
-        .show_menu_on_left_click(false)  // 左クリックでメニューを無効化
-        .on_tray_icon_event(|tray, event| match event {
-            // 左クリックでメインウィンドウを開く
-            TrayIconEvent::Click {
-                button: MouseButton::Left,
-                button_state: MouseButtonState::Up,
-                ..
-            } => {
-                let app = tray.app_handle();
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                }
-            }
-            _ => {}
-        })
-        .on_menu_event(|app, event| {
-            match event.id.as_ref() {
-                "settings" => {
-                    // 設定画面を開く
-                    if let Some(window) = app.get_webview_window("main") {
-                        let _ = window.show();
-                        let _ = window.set_focus();
-                        let _ = app.emit("open-settings", ());
-                    }
-                }
-                "version" => {
-                    // バージョン情報を表示
-                    tracing::info!("Version menu item clicked!");
-                    let app = app.clone();
-                    tauri::async_runtime::spawn(async move {
-                        let version = env!("CARGO_PKG_VERSION");
-                        tracing::info!("Showing version dialog: ReelVault v{}", version);
-                        let shown = app.dialog().message(&format!("ReelVault v{}", version)).blocking_show();
-                        if shown {
-                            tracing::info!("Dialog shown successfully");
-                        } else {
-                            tracing::warn!("Dialog was not shown, possibly closed by user.");
-                        }
-                    });
-                }
-                "quit" => app.exit(0),
-                _ => {}
-            }
-        })
-        .build(app)?;
-    
-    Ok(())
-}
+use commands::diagnostics::*;
+use commands::autostart::*;
+use commands::sync::*;
+use commands::queue_export::*;
+use commands::bundle::*;
+use commands::auto_archive::*;
+use commands::retention::*;
+use commands::audit::*;
+use commands::mock_aws::*;
+use commands::license::*;
+use commands::metrics::*;
+use commands::health_report::*;
+use commands::quick_upload::*;
+use commands::drag_drop::*;
+use commands::remote_verify::*;
+use commands::credential_cache::*;
+use commands::naming::*;
+use commands::bucket_init::*;
+use commands::bucket_security::*;
+use commands::archive_transition::*;
+use commands::mirror_upload::*;
+use commands::api_usage::*;
+use commands::metadata_jobs::*;
+use commands::prefix_restore::*;
+use commands::upload_history::*;
+use commands::operations::*;
+use commands::restored_files::*;
+use commands::s3_cache::*;
+use commands::projects::*;
+use commands::startup_diagnostics::*;
+use commands::startup_phases::*;
+use commands::restore_notifications::*;
+use commands::auto_upload_guard::*;
+use commands::queue_duplicates::*;
+use commands::command_timing::*;
+use commands::open_location::*;
+use commands::object_metadata_edit::*;
+use commands::metadata_db_repair::*;
+use commands::transfer_cap::*;
+use commands::frontend_log::*;
+use commands::local_file_lock::*;
+use commands::hot_folder::*;
+use commands::app_data_usage::*;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
   // アプリケーション状態の初期化
   let app_state = Arc::new(Mutex::new(commands::state_management::AppState::default()));
   let upload_queue = Arc::new(Mutex::new(commands::upload_system::UploadQueue::new()));
+  let launch_context = commands::autostart::LaunchContext::from_args(std::env::args());
 
   tauri::Builder::default()
+    // 多重起動防止（他のプラグインより先に登録する必要がある）
+    .plugin(tauri_plugin_single_instance::init(|app, argv, cwd| {
+        single_instance::handle_second_instance(app, argv, cwd);
+    }))
     .plugin(tauri_plugin_shell::init())
     .plugin(tauri_plugin_dialog::init())
+    .plugin(tauri_plugin_notification::init())
+    .plugin(tauri_plugin_clipboard_manager::init())
+    .plugin(
+        tauri_plugin_global_shortcut::Builder::new()
+            .with_handler(commands::quick_upload::handle_shortcut)
+            .build(),
+    )
+    .plugin(tauri_plugin_autostart::init(
+        tauri_plugin_autostart::MacosLauncher::LaunchAgent,
+        Some(vec![commands::autostart::HIDDEN_FLAG]),
+    ))
     .manage(app_state)
     .manage(upload_queue)
-    .invoke_handler(tauri::generate_handler![
+    .manage(notifications::PendingNotificationTarget::default())
+    .manage(launch_context.clone())
+    .manage(window_state::WindowStateManager::default())
+    .manage(commands::auto_archive::AutoArchiveState::default())
+    .manage(commands::hot_folder::HotFolderState::default())
+    .manage(commands::retention::RetentionState::default())
+    .manage(commands::drag_drop::PendingDropState::default())
+    .manage(commands::credential_cache::CredentialCacheState::default())
+    .manage(commands::operations::OperationRegistry::default())
+    // 各コマンドの所要時間を計測し、スローコマンドを警告する
+    .invoke_handler(commands::command_timing::timed_invoke_handler(tauri::generate_handler![
 
         // ファイル操作API
         list_files,
         get_file_info,
         select_directory,
+        reveal_in_finder,
+        open_in_finder,
+        open_s3_console_url,
+        update_s3_objects_metadata,
         watch_directory,
         test_watch_system,
-        get_sample_watch_configs,
+        get_watch_config_presets,
+        get_default_exclude_patterns,
+        get_watcher_statuses,
         // AWS操作API
         test_aws_connection,
         list_s3_objects,
         restore_file,
+        restore_files_bulk,
+        restore_prefix,
+        list_prefix_restore_jobs,
+        cancel_prefix_restore,
         check_restore_status,
         get_restore_notifications,
+        mark_notification_read,
+        mark_all_notifications_read,
+        delete_notification,
         download_s3_file,
+        get_bucket_versioning_status,
+        list_object_versions,
+        check_archive_access,
     download_restored_file,
         list_restore_jobs,
         cancel_restore_job,
         clear_restore_history,
+        list_restored_files,
+        browse_s3_cached,
+        refresh_s3_cache,
+        extend_restored_file_retention,
+        // 長時間操作の中断API
+        cancel_operation,
+        list_operations,
         // AWS認証API
         authenticate_aws,
         test_s3_bucket_access,
         save_aws_credentials_secure,
         load_aws_credentials_secure,
+        get_cached_credentials_status,
+        unlock_credentials,
+        lock_credentials,
         // 設定管理API
         get_config,
+        get_config_recovery,
         set_config,
         update_config,
         reset_config,
@@ -157,35 +257,189 @@ pub fn run() {
         update_file_metadata,
         delete_file_metadata,
         get_all_tags,
+        find_file_in_bundles,
+        get_metadata_job_status,
         // アップロードシステムAPI
         initialize_upload_queue,
         open_file_dialog,
         add_files_to_upload_queue,
         remove_upload_item,
+        remove_upload_items,
         start_upload_processing,
         stop_upload_processing,
         get_upload_queue_status,
         get_upload_queue_items,
+        set_upload_item_note,
         retry_upload_item,
+        retry_upload_items,
+        analyze_queue_duplicates,
+        exclude_queue_duplicates,
+        update_upload_item,
+        retry_mirror,
         clear_upload_queue,
         test_upload_config,
+        get_naming_pattern_presets,
+        pause_upload_queue,
+        resume_upload_queue,
+        export_upload_queue,
+        import_upload_queue,
+        // アップロード履歴API
+        archive_upload_history,
+        get_upload_history,
+        // プロジェクト（案件）API
+        create_project,
+        list_projects,
+        assign_files_to_project,
+        get_project_summary,
+        delete_project,
+        // 自動アーカイブAPI
+        enable_auto_archive,
+        disable_auto_archive,
+        get_auto_archive_status,
+        // ローカル原本のリテンションAPI
+        get_pending_retention_cleanup,
+        confirm_retention_cleanup,
+        dismiss_retention_cleanup,
+        // 操作の監査ログAPI
+        get_audit_log,
+        export_audit_log,
+        // モックAWSモードAPI
+        get_mock_aws_status,
+        // ライセンスAPI
+        activate_license,
+        get_license_status,
+        // メトリクスAPI
+        get_metrics,
+        reset_metrics,
+        get_command_performance_stats,
+        // S3リクエスト数・概算料金API
+        get_api_usage_summary,
+        // 転送量の月次上限API
+        get_transfer_usage,
+        override_transfer_cap,
+        log_frontend_event,
+        unlock_local_file,
+        configure_hot_folder,
+        disable_hot_folder,
+        get_hot_folder_status,
+        get_app_data_usage,
+        cleanup_app_data,
+        // ヘルスレポートAPI
+        get_health_reports,
+        // クイックアップロードAPI
+        set_quick_upload_settings,
+        get_quick_upload_status,
+        // ドラッグ＆ドロップAPI
+        confirm_dropped_files,
+        // 自動アップロードの承認待ちAPI
+        list_pending_auto_uploads,
+        approve_pending_auto_uploads,
+        discard_pending_auto_uploads,
+        // 整合性検証API
+        verify_remote_integrity,
+        // 差分同期API
+        compare_local_with_s3,
+        sync_directory_to_s3,
         // ライフサイクル管理API
         enable_reelvault_lifecycle,
         get_lifecycle_status,
         disable_lifecycle_policy,
         list_lifecycle_rules,
         validate_lifecycle_config,
-        check_upload_readiness
-    ])
-    .setup(|app| {
+        check_upload_readiness,
+        // バケット初期化API
+        initialize_bucket_for_reelvault,
+        // バケット安全性チェックAPI
+        check_bucket_security,
+        // アーカイブ移行状況API
+        check_archive_transition_status,
+        // ログ管理API
+        logger::get_log_disk_usage,
+        logger::get_recent_logs,
+        // 診断API
+        export_diagnostics,
+        get_startup_diagnostics,
+        get_startup_progress,
+        restore_metadata_db_backup,
+        get_metadata_db_recovery,
+        // 自動起動API
+        set_autostart,
+        get_autostart_status,
+        get_launch_context,
+        // 終了API
+        shutdown::confirm_quit,
+        shutdown::force_quit
+    ]))
+    .setup(move |app| {
         // ロガーを初期化
-        if let Err(e) = logger::init_logger(app.handle()) {
+        let logger_result = logger::init_logger(app.handle()).map_err(|e| e.to_string());
+        if let Err(e) = &logger_result {
             eprintln!("Failed to initialize logger: {}", e);
         }
-        tracing::info!("Logger initialized, setting up system tray...");
+        tracing::info!("Logger initialized, running startup diagnostics...");
+
+        // 設定・メタデータDBなどを読む前にセルフチェック（壊れた設定はここで作り直す）
+        commands::startup_diagnostics::run_startup_diagnostics(app.handle(), logger_result);
 
         // システムトレイを初期化
-        setup_system_tray(app)?;
+        tray::setup_system_tray(app)?;
+
+        // バックエンドのメッセージ言語を設定に合わせる
+        if let Ok(config) = commands::config::load_config(app.handle()) {
+            commands::config::apply_language_setting(&config);
+        }
+
+        // Dock・トレイのバッジを初期化
+        let show_badge = commands::config::load_config(app.handle())
+            .map(|config| config.notification_settings.show_badge)
+            .unwrap_or(true);
+        app.manage(badge::BadgeCache::new(show_badge));
+
+        // 転送中のスリープ抑止を初期化
+        let prevent_sleep = commands::config::load_config(app.handle())
+            .map(|config| config.app_settings.prevent_sleep_during_transfer)
+            .unwrap_or(true);
+        app.manage(power::PowerManager::new(prevent_sleep));
+
+        // 画面ロック・スリープ時に認証情報キャッシュを破棄する
+        lock_monitor::start_lock_monitor(app.handle());
+
+        // 設定で有効な場合はKeychainから認証情報を読み込んでおく（自動起動時は監視・アップロードの再開時に読み込む）
+        if !launch_context.launched_hidden {
+            commands::credential_cache::unlock_credentials_on_startup(app.handle());
+        }
+
+        // 時間帯別の帯域制限スケジュールを読み込む
+        commands::bandwidth::refresh_bandwidth_schedule(app.handle());
+
+        // 今月の転送量と月次上限を読み込む
+        commands::transfer_cap::initialize_transfer_cap(app.handle());
+
+        // メタデータ作成ジョブの同時実行数を設定に合わせる
+        commands::metadata_jobs::refresh_metadata_job_settings(app.handle());
+
+        // モックAWSモードを反映（有効時はトレイにも表示）
+        commands::mock_aws::refresh_mock_aws_mode(app.handle());
+
+        // ログレベルに応じて詳細ヒストグラム・command-timingイベントを切り替え
+        commands::command_timing::init_command_timing(app.handle());
+        commands::metrics::refresh_metrics_settings(app.handle());
+
+        // メインウィンドウは非表示で作成されるため、前回のジオメトリを復元してから表示する
+        // （トレイから開き直す場合も同じウィンドウを再表示するため、復元済みのジオメトリが維持される）
+        if let Some(window) = app.get_webview_window("main") {
+            window_state::restore_window_geometry(&window);
+            if launch_context.launched_hidden {
+                tracing::info!("Launched with {}, staying in system tray", commands::autostart::HIDDEN_FLAG);
+            } else {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }
+
+        // 状態の復元・ライセンス検証・ショートカット登録・定期タスクはウィンドウ表示後に段階的に実行
+        // （進行はstartup-phase-completedイベントとget_startup_progressで取得できる）
+        commands::startup_phases::start_deferred_startup(app.handle().clone());
       
         Ok(())
     })
@@ -196,9 +450,29 @@ pub fn run() {
           window.hide().unwrap();
           api.prevent_close();
         }
+        tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_) => {
+          // ウィンドウの位置・サイズをデバウンスして保存
+          window_state::schedule_save(window);
+        }
+        tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) => {
+          // ウィンドウへドロップされたファイルをアップロードキューへ追加
+          commands::drag_drop::handle_dropped_paths(window.app_handle(), paths.clone());
+        }
+        tauri::WindowEvent::Focused(true) => {
+          // 通知クリックでアクティブになった場合は該当タブを開く
+          notifications::open_pending_notification_target(window.app_handle());
+          // ウィンドウ復帰時にバッジの表示を整合させる
+          badge::refresh_badge(window.app_handle());
+        }
         _ => {}
       }
     })
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
+    .build(tauri::generate_context!())
+    .expect("error while building tauri application")
+    .run(|app, event| {
+      // Cmd+QやOSのシャットダウンでも、状態を保存してから終了する
+      if let tauri::RunEvent::ExitRequested { api, code, .. } = &event {
+        shutdown::handle_exit_requested(app, api, *code);
+      }
+    });
 }
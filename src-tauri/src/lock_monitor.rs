@@ -0,0 +1,177 @@
+use std::sync::OnceLock;
+use tauri::AppHandle;
+
+use crate::commands::credential_cache::clear_credential_cache;
+
+/// kIOMessageCanSystemSleep（アイドルスリープしてよいかの問い合わせ）
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+const MESSAGE_CAN_SYSTEM_SLEEP: u32 = 0xE000_0270;
+/// kIOMessageSystemWillSleep（スリープ直前の通知）
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+const MESSAGE_SYSTEM_WILL_SLEEP: u32 = 0xE000_0280;
+
+/// OSのコールバックは引数でアプリを受け取れないため、監視開始時に保持する
+static MONITOR_APP: OnceLock<AppHandle> = OnceLock::new();
+
+/// 電源管理メッセージへの対応（macOS以外では監視しないためテストからのみ使う）
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PowerMessageAction {
+    /// 応答不要
+    Ignore,
+    /// スリープを許可する（応答しないとスリープが30秒遅れる）
+    Allow,
+    /// 認証情報を破棄してからスリープを許可する
+    ClearAndAllow,
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn power_message_action(message_type: u32) -> PowerMessageAction {
+    match message_type {
+        MESSAGE_SYSTEM_WILL_SLEEP => PowerMessageAction::ClearAndAllow,
+        MESSAGE_CAN_SYSTEM_SLEEP => PowerMessageAction::Allow,
+        _ => PowerMessageAction::Ignore,
+    }
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn on_session_locked(reason: &str) {
+    if let Some(app) = MONITOR_APP.get() {
+        log::info!("{}, clearing credential cache", reason);
+        clear_credential_cache(app);
+    }
+}
+
+/// macOSの画面ロック（分散通知）とスリープ（IOKit）の監視
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::ffi::c_void;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use core_foundation::base::TCFType;
+    use core_foundation::runloop::{kCFRunLoopCommonModes, CFRunLoop, CFRunLoopSource, CFRunLoopSourceRef};
+    use core_foundation::string::{CFString, CFStringRef};
+
+    use super::{on_session_locked, power_message_action, PowerMessageAction};
+
+    /// 画面ロック時にloginwindowが送る分散通知
+    const SCREEN_LOCKED_NOTIFICATION: &str = "com.apple.screenIsLocked";
+    /// CFNotificationSuspensionBehaviorDeliverImmediately
+    const DELIVER_IMMEDIATELY: isize = 4;
+
+    type CFNotificationCenterRef = *mut c_void;
+    type IONotificationPortRef = *mut c_void;
+    type IOObject = u32;
+    type IOConnect = u32;
+    type IOReturn = i32;
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFNotificationCenterGetDistributedCenter() -> CFNotificationCenterRef;
+        fn CFNotificationCenterAddObserver(
+            center: CFNotificationCenterRef,
+            observer: *const c_void,
+            callback: extern "C" fn(CFNotificationCenterRef, *mut c_void, CFStringRef, *const c_void, *const c_void),
+            name: CFStringRef,
+            object: *const c_void,
+            suspension_behavior: isize,
+        );
+    }
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        fn IORegisterForSystemPower(
+            refcon: *mut c_void,
+            notify_port: *mut IONotificationPortRef,
+            callback: extern "C" fn(*mut c_void, IOObject, u32, *mut c_void),
+            notifier: *mut IOObject,
+        ) -> IOConnect;
+        fn IONotificationPortGetRunLoopSource(notify_port: IONotificationPortRef) -> CFRunLoopSourceRef;
+        fn IOAllowPowerChange(root_port: IOConnect, notification_id: isize) -> IOReturn;
+    }
+
+    /// スリープ通知への応答先（ルートパワードメインへの接続）
+    static ROOT_PORT: AtomicU32 = AtomicU32::new(0);
+
+    extern "C" fn screen_locked(
+        _center: CFNotificationCenterRef,
+        _observer: *mut c_void,
+        _name: CFStringRef,
+        _object: *const c_void,
+        _user_info: *const c_void,
+    ) {
+        on_session_locked("Screen locked");
+    }
+
+    extern "C" fn system_power(_refcon: *mut c_void, _service: IOObject, message_type: u32, message_argument: *mut c_void) {
+        let action = power_message_action(message_type);
+        if action == PowerMessageAction::ClearAndAllow {
+            on_session_locked("System will sleep");
+        }
+        if action != PowerMessageAction::Ignore {
+            unsafe {
+                IOAllowPowerChange(ROOT_PORT.load(Ordering::SeqCst), message_argument as isize);
+            }
+        }
+    }
+
+    /// メインスレッドのランループで通知を受け取る（setupから呼び出す）
+    pub fn start() -> Result<(), String> {
+        let name = CFString::new(SCREEN_LOCKED_NOTIFICATION);
+        unsafe {
+            CFNotificationCenterAddObserver(
+                CFNotificationCenterGetDistributedCenter(),
+                std::ptr::addr_of!(ROOT_PORT).cast(),
+                screen_locked,
+                name.as_concrete_TypeRef(),
+                std::ptr::null(),
+                DELIVER_IMMEDIATELY,
+            );
+        }
+
+        let mut notify_port: IONotificationPortRef = std::ptr::null_mut();
+        let mut notifier: IOObject = 0;
+        let root_port = unsafe {
+            IORegisterForSystemPower(std::ptr::null_mut(), &mut notify_port, system_power, &mut notifier)
+        };
+        if root_port == 0 {
+            return Err("IORegisterForSystemPower failed".to_string());
+        }
+        ROOT_PORT.store(root_port, Ordering::SeqCst);
+        unsafe {
+            let source = CFRunLoopSource::wrap_under_get_rule(IONotificationPortGetRunLoopSource(notify_port));
+            CFRunLoop::get_main().add_source(&source, kCFRunLoopCommonModes);
+        }
+        Ok(())
+    }
+}
+
+/// macOS以外では画面ロック・スリープを監視しない
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    pub fn start() -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// 画面ロック・スリープ時に認証情報キャッシュを破棄する監視を開始
+pub fn start_lock_monitor(app: &AppHandle) {
+    if MONITOR_APP.set(app.clone()).is_err() {
+        return;
+    }
+    if let Err(e) = platform::start() {
+        log::warn!("Failed to start screen lock monitor: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_power_message_action() {
+        assert_eq!(power_message_action(MESSAGE_SYSTEM_WILL_SLEEP), PowerMessageAction::ClearAndAllow);
+        assert_eq!(power_message_action(MESSAGE_CAN_SYSTEM_SLEEP), PowerMessageAction::Allow);
+        // kIOMessageSystemHasPoweredOn
+        assert_eq!(power_message_action(0xE000_0300), PowerMessageAction::Ignore);
+    }
+}
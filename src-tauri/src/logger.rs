@@ -1,8 +1,30 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use serde::Serialize;
 use tauri::{AppHandle, Manager};
-use tracing_subscriber::{fmt, prelude::*, EnvFilter, Layer};
+use tracing_subscriber::{fmt, fmt::format::FmtSpan, prelude::*, EnvFilter, Layer};
 use anyhow::{Context, Result};
 
-const LOG_FILE_NAME: &str = "ReelVault.log";
+use crate::commands::command_timing::{CommandTimingLayer, IPC_SPAN_TARGET};
+use crate::commands::config::{load_config, DEFAULT_LOG_RETENTION_DAYS};
+use crate::commands::frontend_log::TimelineLayer;
+use crate::internal::{InternalError, standardize_error};
+use crate::internal::sanitize::SanitizingMakeWriter;
+
+pub(crate) const LOG_FILE_NAME: &str = "ReelVault.log";
+const LOG_CLEANUP_INTERVAL_SECS: u64 = 24 * 60 * 60;
+const DEFAULT_RECENT_LOG_LINES: usize = 200;
+const MAX_RECENT_LOG_LINES: usize = 5000;
+
+/// ログディレクトリのディスク使用量
+#[derive(Debug, Serialize)]
+pub struct LogDiskUsage {
+    pub log_dir: String,
+    pub total_bytes: u64,
+    pub file_count: usize,
+    pub oldest_log_date: Option<String>,
+    pub retention_days: u32,
+}
 
 pub fn init_logger(app: &AppHandle) -> Result<()> {
     let log_dir = app
@@ -13,7 +35,9 @@ pub fn init_logger(app: &AppHandle) -> Result<()> {
     let log_path = log_dir.join(LOG_FILE_NAME);
 
     let env_filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new("info,reel_vault=debug,tauri=info,aws=warn"));
+        .unwrap_or_else(|_| EnvFilter::new("info,reel_vault=debug,frontend=debug,tauri=info,aws=warn"))
+        // コマンドの所要時間はTauriのIPCのspanから計測するため、RUST_LOGの指定によらず有効にする
+        .add_directive(format!("{}=trace", IPC_SPAN_TARGET).parse().expect("valid directive"));
 
     let file_appender = tracing_appender::rolling::daily(&log_dir, LOG_FILE_NAME);
     let file_layer = fmt::layer()
@@ -21,11 +45,13 @@ pub fn init_logger(app: &AppHandle) -> Result<()> {
         .with_thread_names(true)
         .with_target(true)
         .with_ansi(false)
+        // span終了時にtime.busy/time.idleを出力し、アイテムごとの所要時間をログから追えるようにする
+        .with_span_events(FmtSpan::CLOSE)
         .with_timer(fmt::time::ChronoUtc::new(
             "%Y-%m-%dT%H:%M:%S%.3f%z".to_string(),
         ))
-        .with_writer(file_appender)
-        .with_filter(EnvFilter::new("info,reel_vault=trace"));
+        .with_writer(SanitizingMakeWriter::new(file_appender))
+        .with_filter(EnvFilter::new("info,reel_vault=trace,frontend=trace"));
 
     let stdout_layer = fmt::layer()
         .with_thread_ids(true)
@@ -33,11 +59,14 @@ pub fn init_logger(app: &AppHandle) -> Result<()> {
         .with_target(true)
         .with_ansi(true)
         .with_timer(fmt::time::ChronoUtc::new("%H:%M:%S%.3f".to_string()))
-        .with_writer(std::io::stdout);
+        .with_writer(SanitizingMakeWriter::new(std::io::stdout));
 
     let subscriber = tracing_subscriber::registry()
         .with(env_filter)
-        .with(file_layer);
+        .with(file_layer)
+        // フロントエンドのログも含めた統合ログを診断情報のエクスポート用に保持する
+        .with(TimelineLayer)
+        .with(CommandTimingLayer);
 
     if cfg!(debug_assertions) {
         subscriber
@@ -55,5 +84,315 @@ pub fn init_logger(app: &AppHandle) -> Result<()> {
     tracing::info!("Log level: RUST_LOG or default (info, reel_vault=debug)");
     tracing::info!("==================================================");
 
+    // 保持期間を過ぎたログを削除し、以降は日次でクリーンアップ
+    run_log_cleanup(app, &log_dir);
+    spawn_log_cleanup_task(app.clone(), log_dir);
+
     Ok(())
+}
+
+/// CLIモード用のロガーを初期化（標準出力は結果表示に使うため、ログは標準エラーへ出す）
+pub fn init_cli_logger(verbose: bool) {
+    let default_filter = if verbose { "info,reel_vault=debug,aws=warn" } else { "warn" };
+    let env_filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(default_filter));
+
+    let stderr_layer = fmt::layer()
+        .with_target(verbose)
+        .with_ansi(false)
+        .with_timer(fmt::time::ChronoUtc::new("%H:%M:%S%.3f".to_string()))
+        .with_writer(SanitizingMakeWriter::new(std::io::stderr));
+
+    tracing_subscriber::registry()
+        .with(env_filter)
+        .with(stderr_layer)
+        .init();
+}
+
+fn get_log_dir(app: &AppHandle) -> Result<PathBuf, InternalError> {
+    app.path()
+        .app_log_dir()
+        .map_err(|e| InternalError::File(format!("Failed to get app log directory: {}", e)))
+}
+
+fn get_retention_days(app: &AppHandle) -> u32 {
+    load_config(app)
+        .map(|config| config.app_settings.log_retention_days)
+        .unwrap_or(DEFAULT_LOG_RETENTION_DAYS)
+}
+
+fn run_log_cleanup(app: &AppHandle, log_dir: &Path) {
+    let retention_days = get_retention_days(app);
+    let today = chrono::Local::now().date_naive();
+    match cleanup_old_logs(log_dir, retention_days, today) {
+        Ok(LogCleanupResult { removed_files: 0, .. }) => {}
+        Ok(result) => tracing::info!(
+            "Removed {} expired log file(s), freed {} bytes (retention: {} days)",
+            result.removed_files,
+            result.freed_bytes,
+            retention_days
+        ),
+        Err(e) => tracing::warn!("Log cleanup failed: {}", e),
+    }
+}
+
+/// 日次でログクリーンアップを実行するバックグラウンドタスクを起動
+fn spawn_log_cleanup_task(app: AppHandle, log_dir: PathBuf) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(LOG_CLEANUP_INTERVAL_SECS));
+        // 初回tickは即時に発火するため読み捨てる（起動時のクリーンアップは実行済み）
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            run_log_cleanup(&app, &log_dir);
+        }
+    });
+}
+
+/// ローテート済みログのファイル名から日付を取り出す（例: ReelVault.log.2024-01-31）
+fn rotated_log_date(file_name: &str) -> Option<chrono::NaiveDate> {
+    let suffix = file_name.strip_prefix(LOG_FILE_NAME)?.strip_prefix('.')?;
+    chrono::NaiveDate::parse_from_str(suffix, "%Y-%m-%d").ok()
+}
+
+/// ログクリーンアップの結果
+#[derive(Debug, Default, PartialEq)]
+struct LogCleanupResult {
+    removed_files: usize,
+    freed_bytes: u64,
+}
+
+/// 保持期間を過ぎたReelVault.log.*を削除し、削除件数と解放したバイト数を返す
+/// retention_daysが0の場合は無制限として何も削除しない
+/// 個々のファイルの削除に失敗しても警告を出して残りの処理を続ける
+fn cleanup_old_logs(log_dir: &Path, retention_days: u32, today: chrono::NaiveDate) -> std::io::Result<LogCleanupResult> {
+    let mut result = LogCleanupResult::default();
+    if retention_days == 0 || !log_dir.exists() {
+        return Ok(result);
+    }
+
+    let cutoff = today - chrono::Duration::days(retention_days as i64);
+
+    for entry in std::fs::read_dir(log_dir)? {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                tracing::warn!("Failed to read log directory entry: {}", e);
+                continue;
+            }
+        };
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(date) = rotated_log_date(&file_name) else {
+            continue;
+        };
+        if date >= cutoff {
+            continue;
+        }
+
+        let size = entry.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+        match std::fs::remove_file(entry.path()) {
+            Ok(()) => {
+                result.removed_files += 1;
+                result.freed_bytes += size;
+            }
+            Err(e) => tracing::warn!("Failed to remove expired log file {}: {}", file_name, e),
+        }
+    }
+
+    Ok(result)
+}
+
+fn collect_log_disk_usage(log_dir: &Path, retention_days: u32) -> std::io::Result<LogDiskUsage> {
+    let mut total_bytes = 0;
+    let mut file_count = 0;
+    let mut oldest: Option<chrono::NaiveDate> = None;
+
+    if log_dir.exists() {
+        for entry in std::fs::read_dir(log_dir)? {
+            let entry = entry?;
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if !file_name.starts_with(LOG_FILE_NAME) {
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+            total_bytes += metadata.len();
+            file_count += 1;
+            if let Some(date) = rotated_log_date(&file_name) {
+                oldest = Some(oldest.map_or(date, |current| current.min(date)));
+            }
+        }
+    }
+
+    Ok(LogDiskUsage {
+        log_dir: log_dir.to_string_lossy().to_string(),
+        total_bytes,
+        file_count,
+        oldest_log_date: oldest.map(|d| d.format("%Y-%m-%d").to_string()),
+        retention_days,
+    })
+}
+
+/// spanのフィールドに`item_id=<id>`として含まれる行か（別IDの前方一致は除く）
+fn line_mentions_item(line: &str, item_id: &str) -> bool {
+    let needle = format!("item_id={}", item_id);
+    line.match_indices(&needle).any(|(start, _)| {
+        matches!(line[start + needle.len()..].chars().next(), None | Some(' ' | '}' | ',' | ':'))
+    })
+}
+
+/// 新しいログファイルから遡って最大`limit`行を古い順で返す
+fn collect_recent_logs(log_dir: &Path, limit: usize, item_id: Option<&str>) -> std::io::Result<Vec<String>> {
+    if !log_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files: Vec<PathBuf> = std::fs::read_dir(log_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(LOG_FILE_NAME))
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    // ReelVault.log.YYYY-MM-DDは名前順が日付順
+    files.sort();
+
+    let mut lines = Vec::new();
+    for path in files.iter().rev() {
+        let content = std::fs::read_to_string(path)?;
+        let mut matched: Vec<String> = content.lines()
+            .filter(|line| match item_id {
+                Some(id) => line_mentions_item(line, id),
+                None => true,
+            })
+            .map(str::to_string)
+            .collect();
+        let needed = limit - lines.len();
+        if matched.len() > needed {
+            matched.drain(..matched.len() - needed);
+        }
+        matched.append(&mut lines);
+        lines = matched;
+        if lines.len() >= limit {
+            break;
+        }
+    }
+    Ok(lines)
+}
+
+/// 直近のログを取得（`item_id`指定時はそのアップロードアイテムのspan内のログのみ）
+#[tauri::command]
+pub async fn get_recent_logs(app: AppHandle, limit: Option<usize>, item_id: Option<String>) -> Result<Vec<String>, String> {
+    let log_dir = get_log_dir(&app).map_err(standardize_error)?;
+    let limit = limit.unwrap_or(DEFAULT_RECENT_LOG_LINES).clamp(1, MAX_RECENT_LOG_LINES);
+    let item_id = item_id.filter(|id| !id.is_empty());
+    collect_recent_logs(&log_dir, limit, item_id.as_deref())
+        .map_err(|e| standardize_error(InternalError::File(format!("Failed to read log files: {}", e))))
+}
+
+/// ログファイルのディスク使用量を取得
+#[tauri::command]
+pub async fn get_log_disk_usage(app: AppHandle) -> Result<LogDiskUsage, String> {
+    let log_dir = get_log_dir(&app).map_err(standardize_error)?;
+    collect_log_disk_usage(&log_dir, get_retention_days(&app))
+        .map_err(|e| standardize_error(InternalError::File(format!("Failed to read log directory: {}", e))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_log(dir: &Path, name: &str, content: &str) {
+        std::fs::write(dir.join(name), content).unwrap();
+    }
+
+    #[test]
+    fn test_rotated_log_date() {
+        assert_eq!(
+            rotated_log_date("ReelVault.log.2024-01-31"),
+            chrono::NaiveDate::from_ymd_opt(2024, 1, 31)
+        );
+        assert_eq!(rotated_log_date("ReelVault.log"), None);
+        assert_eq!(rotated_log_date("other.log.2024-01-31"), None);
+        assert_eq!(rotated_log_date("ReelVault.log.backup"), None);
+    }
+
+    #[test]
+    fn test_cleanup_old_logs_removes_expired_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        write_log(dir, "ReelVault.log.2024-01-01", "old");
+        write_log(dir, "ReelVault.log.2024-01-20", "recent");
+        write_log(dir, "ReelVault.log", "current");
+        write_log(dir, "unrelated.txt", "keep");
+
+        let today = chrono::NaiveDate::from_ymd_opt(2024, 1, 25).unwrap();
+        let result = cleanup_old_logs(dir, 14, today).unwrap();
+
+        assert_eq!(result, LogCleanupResult { removed_files: 1, freed_bytes: 3 });
+        assert!(!dir.join("ReelVault.log.2024-01-01").exists());
+        assert!(dir.join("ReelVault.log.2024-01-20").exists());
+        assert!(dir.join("ReelVault.log").exists());
+        assert!(dir.join("unrelated.txt").exists());
+    }
+
+    #[test]
+    fn test_cleanup_old_logs_zero_retention_is_unlimited() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        write_log(dir, "ReelVault.log.2000-01-01", "very old");
+
+        let today = chrono::NaiveDate::from_ymd_opt(2024, 1, 25).unwrap();
+        assert_eq!(cleanup_old_logs(dir, 0, today).unwrap(), LogCleanupResult::default());
+        assert!(dir.join("ReelVault.log.2000-01-01").exists());
+    }
+
+    #[test]
+    fn test_cleanup_old_logs_continues_after_failed_removal() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        // ディレクトリはremove_fileで削除できないため、削除失敗のケースとして使う
+        std::fs::create_dir(dir.join("ReelVault.log.2024-01-01")).unwrap();
+        write_log(dir, "ReelVault.log.2024-01-02", "expired");
+
+        let today = chrono::NaiveDate::from_ymd_opt(2024, 1, 25).unwrap();
+        let result = cleanup_old_logs(dir, 14, today).unwrap();
+
+        assert_eq!(result, LogCleanupResult { removed_files: 1, freed_bytes: 7 });
+        assert!(dir.join("ReelVault.log.2024-01-01").exists());
+        assert!(!dir.join("ReelVault.log.2024-01-02").exists());
+    }
+
+    #[test]
+    fn test_collect_log_disk_usage() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        write_log(dir, "ReelVault.log.2024-01-10", "12345");
+        write_log(dir, "ReelVault.log.2024-01-05", "123");
+        write_log(dir, "unrelated.txt", "ignored");
+
+        let usage = collect_log_disk_usage(dir, 14).unwrap();
+        assert_eq!(usage.file_count, 2);
+        assert_eq!(usage.total_bytes, 8);
+        assert_eq!(usage.oldest_log_date, Some("2024-01-05".to_string()));
+        assert_eq!(usage.retention_days, 14);
+    }
+
+    #[test]
+    fn test_collect_recent_logs_filters_by_item_id() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        write_log(dir, "ReelVault.log.2024-01-09", "upload_item{item_id=a1 size=3}: start\nupload_item{item_id=a10 size=3}: other\n");
+        write_log(dir, "ReelVault.log.2024-01-10", "idle\nupload_item{item_id=a1 size=3}:upload_file{item_id=a1:mirror:backup}: mirrored\nupload_item{item_id=a1 size=3}: close time.busy=1ms\n");
+
+        let lines = collect_recent_logs(dir, 10, Some("a1")).unwrap();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].ends_with("start"));
+        assert!(lines[2].contains("time.busy"));
+
+        let lines = collect_recent_logs(dir, 2, None).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].contains("close"));
+    }
 }
\ No newline at end of file
@@ -2,5 +2,9 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+  // サブコマンド付きで起動された場合はGUIを起動せずCLIとして実行する
+  if let Some(exit_code) = app_lib::cli::run_from_args() {
+    std::process::exit(exit_code);
+  }
   app_lib::run();
 }
@@ -0,0 +1,129 @@
+use std::time::Duration;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::net::TcpStream;
+
+use crate::commands::aws_operations::s3_endpoint_override;
+use crate::commands::upload_system::{spawn_abort_multipart_uploads, UploadQueueState};
+
+/// ネットワーク状態の変化をフロントエンドへ通知するイベント名
+pub const NETWORK_STATUS_CHANGED_EVENT: &str = "network-status-changed";
+
+/// オンライン時の到達性チェック間隔
+pub const ONLINE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// オフライン時の復帰チェック間隔
+pub const OFFLINE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+/// 接続確認のタイムアウト
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// network-status-changedイベントのペイロード
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkStatusChange {
+    pub online: bool,
+    /// オフライン検知で一時停止したアイテム数
+    pub paused_count: usize,
+    /// オンライン復帰で再開したアイテム数
+    pub resumed_count: usize,
+}
+
+/// 到達性を確認する接続先（カスタムエンドポイント設定時はそのホスト）
+pub fn connectivity_target(region: &str, endpoint: Option<&str>) -> (String, u16) {
+    if let Some(endpoint) = endpoint {
+        let (default_port, rest) = if let Some(rest) = endpoint.strip_prefix("https://") {
+            (443, rest)
+        } else if let Some(rest) = endpoint.strip_prefix("http://") {
+            (80, rest)
+        } else {
+            (443, endpoint)
+        };
+        let authority = rest.split('/').next().unwrap_or(rest);
+        if let Some((host, port)) = authority.rsplit_once(':') {
+            if let Ok(port) = port.parse() {
+                return (host.to_string(), port);
+            }
+        }
+        return (authority.to_string(), default_port);
+    }
+    (format!("s3.{}.amazonaws.com", region), 443)
+}
+
+/// S3エンドポイントへTCP接続できるかで到達性を判定
+pub async fn check_connectivity(region: &str) -> bool {
+    let (host, port) = connectivity_target(region, s3_endpoint_override().as_deref());
+    match tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect((host.as_str(), port))).await {
+        Ok(Ok(_)) => true,
+        Ok(Err(e)) => {
+            log::debug!("Connectivity check to {}:{} failed: {}", host, port, e);
+            false
+        }
+        Err(_) => {
+            log::debug!("Connectivity check to {}:{} timed out", host, port);
+            false
+        }
+    }
+}
+
+/// 到達性の判定結果をキューへ反映し、状態が変化した場合はイベントで通知
+pub fn apply_network_status(app: &AppHandle, queue_state: &UploadQueueState, online: bool) {
+    let (change, stale_uploads) = {
+        let Ok(mut queue) = queue_state.lock() else {
+            return;
+        };
+        queue.network_check_requested = false;
+        let change = match (online, queue.network_offline) {
+            (false, false) => {
+                let paused_count = queue.go_offline();
+                log::warn!("📴 Network offline detected, paused {} upload(s)", paused_count);
+                Some(NetworkStatusChange { online: false, paused_count, resumed_count: 0 })
+            }
+            (true, true) => {
+                let resumed_count = queue.resume_from_network();
+                log::info!("📶 Network back online, resumed {} upload(s)", resumed_count);
+                Some(NetworkStatusChange { online: true, paused_count: 0, resumed_count })
+            }
+            (true, false) => {
+                // 一時的な失敗で停止したアイテムは、到達性が確認できればそのまま再開する
+                if !queue.network_paused_items.is_empty() {
+                    let resumed_count = queue.resume_from_network();
+                    log::info!("Network reachable, resumed {} upload(s) paused by transient errors", resumed_count);
+                }
+                None
+            }
+            (false, true) => None,
+        };
+        // 中断したマルチパートアップロードは、S3へ到達できるときに未完了パーツを破棄する
+        let stale_uploads = if online { queue.take_stale_multipart_uploads() } else { None };
+        (change, stale_uploads)
+    };
+
+    if let Some((uploads, region)) = stale_uploads {
+        spawn_abort_multipart_uploads(app, uploads, region);
+    }
+
+    if let Some(change) = change {
+        if let Err(e) = app.emit(NETWORK_STATUS_CHANGED_EVENT, &change) {
+            log::error!("Failed to emit network status change: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connectivity_target() {
+        assert_eq!(
+            connectivity_target("ap-northeast-1", None),
+            ("s3.ap-northeast-1.amazonaws.com".to_string(), 443)
+        );
+        assert_eq!(
+            connectivity_target("us-east-1", Some("http://localhost:9000")),
+            ("localhost".to_string(), 9000)
+        );
+        assert_eq!(
+            connectivity_target("us-east-1", Some("https://minio.example.com/")),
+            ("minio.example.com".to_string(), 443)
+        );
+    }
+}
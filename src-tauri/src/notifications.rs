@@ -0,0 +1,265 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::commands::config::{load_config, NotificationSettings};
+use crate::commands::file_operations::reveal_path_in_finder;
+
+/// 通知の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    /// キュー内の全ファイルのアップロード完了
+    QueueCompleted,
+    /// 閾値以上の大きなファイルのアップロード完了
+    LargeFileCompleted,
+    /// 復元完了
+    RestoreCompleted,
+    /// ダウンロード完了
+    DownloadCompleted,
+    /// エラー発生
+    Error,
+    /// 日次ヘルスレポートで問題を検出
+    HealthIssue,
+}
+
+impl NotificationKind {
+    /// 通知クリック時に開くタブ
+    pub fn target_tab(&self) -> &'static str {
+        match self {
+            NotificationKind::QueueCompleted
+            | NotificationKind::LargeFileCompleted
+            | NotificationKind::Error
+            | NotificationKind::HealthIssue => "upload",
+            NotificationKind::RestoreCompleted
+            | NotificationKind::DownloadCompleted => "restore",
+        }
+    }
+}
+
+/// 通知クリック時の遷移先
+struct NotificationTarget {
+    tab: &'static str,
+    /// Finderで表示するファイル（ダウンロード完了通知）
+    reveal_path: Option<PathBuf>,
+    notified_at: Instant,
+}
+
+/// 通知クリックでアプリがアクティブになったときに開くタブ
+/// （デスクトップ版プラグインはクリックイベントを返さないため、直後のアクティブ化で判定する）
+#[derive(Default)]
+pub struct PendingNotificationTarget(Mutex<Option<NotificationTarget>>);
+
+/// 通知後この時間を過ぎたアクティブ化は通知クリックとみなさない
+const PENDING_TARGET_TTL: Duration = Duration::from_secs(600);
+
+/// 設定と現在のフォーカス状態から通知を出すべきか判定
+pub fn should_notify(settings: &NotificationSettings, kind: NotificationKind, app_focused: bool) -> bool {
+    if !settings.enabled {
+        return false;
+    }
+    if settings.suppress_when_focused && app_focused {
+        return false;
+    }
+    match kind {
+        NotificationKind::QueueCompleted => settings.notify_queue_completed,
+        NotificationKind::LargeFileCompleted => settings.notify_large_file_completed,
+        NotificationKind::RestoreCompleted => settings.notify_restore_completed,
+        NotificationKind::DownloadCompleted => settings.notify_download_completed,
+        NotificationKind::Error => settings.notify_errors,
+        NotificationKind::HealthIssue => settings.notify_health_issues,
+    }
+}
+
+/// バイト数を通知表示用の文字列に変換
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+fn is_main_window_focused(app: &AppHandle) -> bool {
+    app.get_webview_window("main")
+        .map(|window| {
+            window.is_visible().unwrap_or(false) && window.is_focused().unwrap_or(false)
+        })
+        .unwrap_or(false)
+}
+
+fn load_notification_settings(app: &AppHandle) -> NotificationSettings {
+    match load_config(app) {
+        Ok(config) => config.notification_settings,
+        Err(e) => {
+            log::warn!("Failed to load notification settings, using defaults: {}", e);
+            NotificationSettings::default()
+        }
+    }
+}
+
+/// 設定に従ってOSネイティブ通知を送信
+pub fn notify(app: &AppHandle, kind: NotificationKind, title: &str, body: &str) {
+    notify_with_target(app, kind, title, body, None);
+}
+
+/// 通知を送信し、クリック時にタブを開いたうえで指定ファイルをFinderで表示する
+fn notify_with_target(app: &AppHandle, kind: NotificationKind, title: &str, body: &str, reveal_path: Option<PathBuf>) {
+    let settings = load_notification_settings(app);
+    if !should_notify(&settings, kind, is_main_window_focused(app)) {
+        log::debug!("Notification suppressed: {:?}", kind);
+        return;
+    }
+
+    if let Some(pending) = app.try_state::<PendingNotificationTarget>() {
+        if let Ok(mut target) = pending.0.lock() {
+            *target = Some(NotificationTarget {
+                tab: kind.target_tab(),
+                reveal_path,
+                notified_at: Instant::now(),
+            });
+        }
+    }
+
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        log::error!("Failed to show notification: {}", e);
+    }
+}
+
+/// キュー内の全ファイルのアップロード完了を通知
+pub fn notify_queue_completed(app: &AppHandle, succeeded: usize, failed: usize, total_bytes: u64) {
+    let body = format!(
+        "成功 {}件 / 失敗 {}件（合計 {}）",
+        succeeded,
+        failed,
+        format_bytes(total_bytes)
+    );
+    notify(app, NotificationKind::QueueCompleted, "アップロードが完了しました", &body);
+}
+
+/// 大きなファイルのアップロード完了を通知（閾値未満の場合は何もしない）
+pub fn notify_large_file_completed(app: &AppHandle, file_name: &str, file_size: u64) {
+    let settings = load_notification_settings(app);
+    if file_size < settings.large_file_threshold_mb.saturating_mul(1024 * 1024) {
+        return;
+    }
+    let body = format!("{}（{}）", file_name, format_bytes(file_size));
+    notify(app, NotificationKind::LargeFileCompleted, "ファイルのアップロードが完了しました", &body);
+}
+
+/// 復元完了を通知
+pub fn notify_restore_completed(app: &AppHandle, s3_key: &str) {
+    let body = format!("{} をダウンロードできます", s3_key);
+    notify(app, NotificationKind::RestoreCompleted, "復元が完了しました", &body);
+}
+
+/// ダウンロード完了を通知（クリックで保存先をFinderで表示）
+pub fn notify_download_completed(app: &AppHandle, local_path: &Path) {
+    let file_name = local_path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| local_path.display().to_string());
+    let body = format!("{} を保存しました（クリックでFinderに表示）", file_name);
+    notify_with_target(
+        app,
+        NotificationKind::DownloadCompleted,
+        "ダウンロードが完了しました",
+        &body,
+        Some(local_path.to_path_buf()),
+    );
+}
+
+/// 日次ヘルスレポートで検出した問題を通知
+pub fn notify_health_issues(app: &AppHandle, issues: &[String]) {
+    let body = match issues {
+        [issue] => issue.clone(),
+        [first, rest @ ..] => format!("{}（他{}件）", first, rest.len()),
+        [] => return,
+    };
+    notify(app, NotificationKind::HealthIssue, "ヘルスチェックで問題が見つかりました", &body);
+}
+
+/// S3との整合性検証で見つかった不一致・欠落を通知
+pub fn notify_integrity_problems(app: &AppHandle, mismatch_count: usize, missing_count: usize) {
+    let body = format!("不一致 {}件 / S3に存在しない {}件", mismatch_count, missing_count);
+    notify(app, NotificationKind::HealthIssue, "アーカイブの整合性に問題があります", &body);
+}
+
+/// エラー発生を通知
+pub fn notify_error(app: &AppHandle, title: &str, message: &str) {
+    notify(app, NotificationKind::Error, title, message);
+}
+
+/// 通知クリック後のアクティブ化時に該当タブを開く
+pub fn open_pending_notification_target(app: &AppHandle) {
+    let Some(pending) = app.try_state::<PendingNotificationTarget>() else {
+        return;
+    };
+    let target = match pending.0.lock() {
+        Ok(mut target) => target.take(),
+        Err(_) => None,
+    };
+    if let Some(target) = target {
+        if target.notified_at.elapsed() > PENDING_TARGET_TTL {
+            return;
+        }
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.show();
+            let _ = window.set_focus();
+            let _ = app.emit("open-tab", target.tab);
+        }
+        if let Some(path) = target.reveal_path {
+            if let Err(e) = reveal_path_in_finder(&path) {
+                log::warn!("Failed to reveal downloaded file: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_notify_respects_settings() {
+        let mut settings = NotificationSettings::default();
+        assert!(should_notify(&settings, NotificationKind::QueueCompleted, false));
+
+        settings.notify_errors = false;
+        assert!(!should_notify(&settings, NotificationKind::Error, false));
+        assert!(should_notify(&settings, NotificationKind::RestoreCompleted, false));
+
+        settings.enabled = false;
+        assert!(!should_notify(&settings, NotificationKind::QueueCompleted, false));
+    }
+
+    #[test]
+    fn test_should_notify_suppressed_when_focused() {
+        let mut settings = NotificationSettings::default();
+        assert!(!should_notify(&settings, NotificationKind::QueueCompleted, true));
+
+        settings.suppress_when_focused = false;
+        assert!(should_notify(&settings, NotificationKind::QueueCompleted, true));
+    }
+
+    #[test]
+    fn test_target_tab() {
+        assert_eq!(NotificationKind::QueueCompleted.target_tab(), "upload");
+        assert_eq!(NotificationKind::RestoreCompleted.target_tab(), "restore");
+        assert_eq!(NotificationKind::DownloadCompleted.target_tab(), "restore");
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.0 GB");
+    }
+}
@@ -0,0 +1,198 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::config::load_config;
+use crate::commands::state_management::AppStateManager;
+
+/// スリープ抑止の理由（アクティビティモニタや`pmset -g assertions`に表示される）
+const ASSERTION_REASON: &str = "ReelVault: ファイル転送中";
+
+/// macOSの電源管理アサーション（IOKit）
+#[cfg(target_os = "macos")]
+mod platform {
+    use core_foundation::base::TCFType;
+    use core_foundation::string::{CFString, CFStringRef};
+
+    type IOPMAssertionID = u32;
+    type IOReturn = i32;
+
+    /// kIOPMAssertionLevelOn
+    const ASSERTION_LEVEL_ON: u32 = 255;
+    const IO_RETURN_SUCCESS: IOReturn = 0;
+    /// kIOPMAssertionTypePreventUserIdleSystemSleep（ディスプレイスリープは妨げない）
+    const ASSERTION_TYPE: &str = "PreventUserIdleSystemSleep";
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        fn IOPMAssertionCreateWithName(
+            assertion_type: CFStringRef,
+            assertion_level: u32,
+            assertion_name: CFStringRef,
+            assertion_id: *mut IOPMAssertionID,
+        ) -> IOReturn;
+        fn IOPMAssertionRelease(assertion_id: IOPMAssertionID) -> IOReturn;
+    }
+
+    pub fn create_assertion(reason: &str) -> Result<u32, String> {
+        let assertion_type = CFString::new(ASSERTION_TYPE);
+        let assertion_name = CFString::new(reason);
+        let mut assertion_id: IOPMAssertionID = 0;
+        let result = unsafe {
+            IOPMAssertionCreateWithName(
+                assertion_type.as_concrete_TypeRef(),
+                ASSERTION_LEVEL_ON,
+                assertion_name.as_concrete_TypeRef(),
+                &mut assertion_id,
+            )
+        };
+        if result == IO_RETURN_SUCCESS {
+            Ok(assertion_id)
+        } else {
+            Err(format!("IOPMAssertionCreateWithName failed: {:#x}", result))
+        }
+    }
+
+    pub fn release_assertion(assertion_id: u32) -> Result<(), String> {
+        let result = unsafe { IOPMAssertionRelease(assertion_id) };
+        if result == IO_RETURN_SUCCESS {
+            Ok(())
+        } else {
+            Err(format!("IOPMAssertionRelease failed: {:#x}", result))
+        }
+    }
+}
+
+/// macOS以外ではスリープ抑止を行わない
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    pub fn create_assertion(_reason: &str) -> Result<u32, String> {
+        Err("Sleep prevention is only supported on macOS".to_string())
+    }
+
+    pub fn release_assertion(_assertion_id: u32) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// 転送中のスリープ抑止状態（Tauriのmanage対象）
+pub struct PowerManager {
+    enabled: AtomicBool,
+    upload_active: AtomicBool,
+    active_downloads: AtomicUsize,
+    assertion: Mutex<Option<u32>>,
+}
+
+impl PowerManager {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled: AtomicBool::new(enabled),
+            upload_active: AtomicBool::new(false),
+            active_downloads: AtomicUsize::new(0),
+            assertion: Mutex::new(None),
+        }
+    }
+}
+
+/// 設定と転送状況からスリープを抑止すべきか判定
+pub fn should_prevent_sleep(enabled: bool, upload_active: bool, active_downloads: usize) -> bool {
+    enabled && (upload_active || active_downloads > 0)
+}
+
+/// アップロードの進行状況を反映（アップロードキューの処理ループから呼び出す）
+pub fn set_upload_active(app: &AppHandle, active: bool) {
+    let Some(manager) = app.try_state::<PowerManager>() else {
+        return;
+    };
+    if manager.upload_active.swap(active, Ordering::SeqCst) != active {
+        sync_assertion(app, &manager);
+    }
+}
+
+/// ダウンロード中であることを示すガード（破棄時に自動で解除）
+pub struct DownloadActivity {
+    app: AppHandle,
+}
+
+impl DownloadActivity {
+    pub fn begin(app: &AppHandle) -> Self {
+        if let Some(manager) = app.try_state::<PowerManager>() {
+            manager.active_downloads.fetch_add(1, Ordering::SeqCst);
+            sync_assertion(app, &manager);
+        }
+        Self { app: app.clone() }
+    }
+}
+
+impl Drop for DownloadActivity {
+    fn drop(&mut self) {
+        if let Some(manager) = self.app.try_state::<PowerManager>() {
+            let _ = manager.active_downloads.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                Some(count.saturating_sub(1))
+            });
+            sync_assertion(&self.app, &manager);
+        }
+    }
+}
+
+/// 設定を読み直して抑止状態を再評価（設定変更時）
+pub fn refresh_power_settings(app: &AppHandle) {
+    let Some(manager) = app.try_state::<PowerManager>() else {
+        return;
+    };
+    if let Ok(config) = load_config(app) {
+        manager.enabled.store(config.app_settings.prevent_sleep_during_transfer, Ordering::SeqCst);
+    }
+    sync_assertion(app, &manager);
+}
+
+fn sync_assertion(app: &AppHandle, manager: &PowerManager) {
+    let should_prevent = should_prevent_sleep(
+        manager.enabled.load(Ordering::SeqCst),
+        manager.upload_active.load(Ordering::SeqCst),
+        manager.active_downloads.load(Ordering::SeqCst),
+    );
+
+    let prevented = {
+        let Ok(mut assertion) = manager.assertion.lock() else {
+            return;
+        };
+        match (should_prevent, *assertion) {
+            (true, None) => match platform::create_assertion(ASSERTION_REASON) {
+                Ok(assertion_id) => {
+                    log::info!("System sleep prevention enabled (assertion id: {})", assertion_id);
+                    *assertion = Some(assertion_id);
+                }
+                Err(e) => log::warn!("Failed to prevent system sleep: {}", e),
+            },
+            (false, Some(assertion_id)) => {
+                if let Err(e) = platform::release_assertion(assertion_id) {
+                    log::warn!("Failed to release sleep assertion: {}", e);
+                }
+                log::info!("System sleep prevention released");
+                *assertion = None;
+            }
+            _ => {}
+        }
+        assertion.is_some()
+    };
+
+    if let Some(app_state) = app.try_state::<AppStateManager>() {
+        if let Ok(mut state) = app_state.lock() {
+            state.system_status.sleep_prevented = prevented;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_prevent_sleep() {
+        assert!(!should_prevent_sleep(true, false, 0));
+        assert!(should_prevent_sleep(true, true, 0));
+        assert!(should_prevent_sleep(true, false, 2));
+        assert!(!should_prevent_sleep(false, true, 1));
+    }
+}
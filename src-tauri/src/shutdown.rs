@@ -0,0 +1,254 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use serde::Serialize;
+use tauri::{command, AppHandle, Emitter, ExitRequestApi, Manager};
+
+use crate::commands::api_usage::persist_api_usage;
+use crate::commands::auto_archive::release_auto_archive_watcher;
+use crate::commands::autostart::{capture_resume_state, persist_resume_state, RESUME_STATE_FILE};
+use crate::commands::hot_folder::release_hot_folder_watcher;
+use crate::commands::aws_operations::{persist_restore_tracker, restore_persisted_restore_tracker, RESTORE_TRACKER_FILE};
+use crate::commands::metadata_jobs::{persist_metadata_jobs, restore_persisted_metadata_jobs, METADATA_JOBS_FILE};
+use crate::commands::state_management::{persist_app_state, restore_persisted_app_state, AppStateManager, APP_STATE_FILE};
+use crate::commands::transfer_cap::persist_transfer_usage;
+use crate::commands::upload_system::{abort_active_uploads, persist_queue_state, UploadQueueState};
+use crate::internal::{AppError, InternalError};
+
+/// シャットダウンシーケンスの制限時間（超過時は強制終了）
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+static SHUTDOWN_STARTED: AtomicBool = AtomicBool::new(false);
+static SHUTDOWN_FINISHED: AtomicBool = AtomicBool::new(false);
+
+/// 進行中のアップロードがある状態で終了が要求されたときにフロントエンドへ送るイベント名
+pub const QUIT_REQUESTED_EVENT: &str = "quit-requested";
+
+/// quit-requestedイベントのペイロード
+#[derive(Debug, Clone, Serialize)]
+pub struct QuitRequest {
+    pub in_progress_count: usize,
+    pub message: String,
+}
+
+/// 終了確認ダイアログの文言
+pub fn quit_confirmation_message(in_progress_count: usize) -> String {
+    format!("アップロード中のファイルが{}件あります。中断して終了しますか？", in_progress_count)
+}
+
+/// 終了要求（トレイの「終了」）
+/// 進行中のアップロードがなければそのまま終了し、ある場合はフロントエンドに確認を求める
+pub fn request_quit(app: &AppHandle) {
+    let queue_state = app.state::<UploadQueueState>();
+    let in_progress_count = match queue_state.lock() {
+        Ok(queue) => queue.in_progress_count(),
+        Err(e) => {
+            log::error!("Failed to lock upload queue on quit request: {}", e);
+            0
+        }
+    };
+
+    if in_progress_count == 0 {
+        app.exit(0);
+        return;
+    }
+
+    log::info!("Quit requested with {} upload(s) in progress, asking for confirmation", in_progress_count);
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    let request = QuitRequest {
+        in_progress_count,
+        message: quit_confirmation_message(in_progress_count),
+    };
+    if let Err(e) = app.emit(QUIT_REQUESTED_EVENT, &request) {
+        // 確認できない場合でもアップロードを失わないよう、グレースフルシャットダウンで終了する
+        log::error!("Failed to emit quit request, shutting down gracefully: {}", e);
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            graceful_shutdown(&app).await;
+            app.exit(0);
+        });
+    }
+}
+
+fn app_data_file(app: &AppHandle, file_name: &str) -> Result<PathBuf, InternalError> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| InternalError::Config(format!("Failed to get app data directory: {}", e)))?;
+    std::fs::create_dir_all(&app_data_dir)?;
+    Ok(app_data_dir.join(file_name))
+}
+
+/// 終了時のシャットダウンシーケンス
+/// 1. 進行中のアップロードを中断し、未完了パーツを破棄
+/// 2. キュー・復元トラッカー・メタデータ作成ジョブ・AppState・自動起動時に再開する監視を保存
+/// 3. ウォッチャーを解放
+/// 途中で失敗しても残りの処理は続行する
+async fn run_shutdown_sequence(app: &AppHandle) {
+    let queue_state = app.state::<UploadQueueState>();
+
+    match abort_active_uploads(app, queue_state.inner()).await {
+        Ok(count) => log::info!("Aborted {} in-progress upload(s) for shutdown", count),
+        Err(e) => log::error!("Failed to abort in-progress uploads: {}", e),
+    }
+
+    if let Err(e) = persist_queue_state(app, queue_state.inner()) {
+        log::error!("Failed to persist upload queue state: {}", e);
+    }
+
+    match app_data_file(app, RESTORE_TRACKER_FILE).and_then(|path| persist_restore_tracker(&path)) {
+        Ok(count) => log::info!("Persisted {} restore job(s)", count),
+        Err(e) => log::error!("Failed to persist restore tracker: {}", e),
+    }
+
+    let app_state = app.state::<AppStateManager>();
+    let snapshot = app_state.lock().map(|state| state.clone()).map_err(|e| e.to_string());
+    match snapshot {
+        Ok(state) => {
+            if let Err(e) = app_data_file(app, APP_STATE_FILE).and_then(|path| persist_app_state(&path, &state)) {
+                log::error!("Failed to persist app state: {}", e);
+            }
+        }
+        Err(e) => log::error!("Failed to lock app state for shutdown: {}", e),
+    }
+
+    match app_data_file(app, METADATA_JOBS_FILE).and_then(|path| persist_metadata_jobs(&path)) {
+        Ok(0) => {}
+        Ok(count) => log::info!("Persisted {} pending metadata job(s)", count),
+        Err(e) => log::error!("Failed to persist metadata jobs: {}", e),
+    }
+
+    match persist_api_usage(app) {
+        Ok(count) => log::info!("Persisted {} pending API usage counter(s)", count),
+        Err(e) => log::error!("Failed to persist API usage: {}", e),
+    }
+
+    match persist_transfer_usage(app) {
+        Ok(count) => log::info!("Persisted {} pending transfer usage counter(s)", count),
+        Err(e) => log::error!("Failed to persist transfer usage: {}", e),
+    }
+
+    // ウォッチャーを解放する前に、動作中の監視とキュー設定を記録する
+    let resume_state = capture_resume_state(app);
+    if let Err(e) = app_data_file(app, RESUME_STATE_FILE).and_then(|path| persist_resume_state(&path, &resume_state)) {
+        log::error!("Failed to persist autostart resume state: {}", e);
+    }
+
+    if release_auto_archive_watcher(app) {
+        log::info!("Released auto archive watcher");
+    }
+
+    if release_hot_folder_watcher(app) {
+        log::info!("Released hot folder watcher");
+    }
+}
+
+/// 制限時間内でシャットダウンシーケンスを実行する
+/// 超過した場合は警告を残して打ち切り、呼び出し元でそのまま終了させる
+pub async fn graceful_shutdown(app: &AppHandle) {
+    SHUTDOWN_STARTED.store(true, Ordering::SeqCst);
+    if tokio::time::timeout(SHUTDOWN_TIMEOUT, run_shutdown_sequence(app)).await.is_err() {
+        log::warn!(
+            "Shutdown sequence did not finish within {}s, forcing exit",
+            SHUTDOWN_TIMEOUT.as_secs()
+        );
+    }
+    SHUTDOWN_FINISHED.store(true, Ordering::SeqCst);
+}
+
+/// RunEvent::ExitRequestedの処理（Cmd+Q・OSのシャットダウンなど）
+///
+/// シャットダウンシーケンスが終わるまで終了を保留し、完了後にあらためて終了する
+pub fn handle_exit_requested(app: &AppHandle, api: &ExitRequestApi, code: Option<i32>) {
+    if SHUTDOWN_FINISHED.load(Ordering::SeqCst) {
+        return;
+    }
+    api.prevent_exit();
+    if SHUTDOWN_STARTED.swap(true, Ordering::SeqCst) {
+        // 既にシーケンス実行中（終了確認からの終了など）
+        return;
+    }
+
+    log::info!("Exit requested, running shutdown sequence");
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        graceful_shutdown(&app).await;
+        app.exit(code.unwrap_or(0));
+    });
+}
+
+/// 前回終了時に保存した復元トラッカーとAppStateを読み込む（キューはinitialize_upload_queueで復元）
+pub fn restore_interrupted_state(app: &AppHandle) -> Result<(), String> {
+    let mut errors = Vec::new();
+    match app_data_file(app, RESTORE_TRACKER_FILE).and_then(|path| restore_persisted_restore_tracker(&path)) {
+        Ok(0) => {}
+        Ok(count) => log::info!("Restored {} restore job(s) from previous session", count),
+        Err(e) => {
+            log::warn!("Failed to restore restore tracker: {}", e);
+            errors.push(format!("restore tracker: {}", e));
+        }
+    }
+
+    match app_data_file(app, METADATA_JOBS_FILE).and_then(|path| restore_persisted_metadata_jobs(&path)) {
+        Ok(0) => {}
+        Ok(count) => log::info!("Resumed {} metadata job(s) from previous session", count),
+        Err(e) => {
+            log::warn!("Failed to restore metadata jobs: {}", e);
+            errors.push(format!("metadata jobs: {}", e));
+        }
+    }
+
+    match app_data_file(app, APP_STATE_FILE) {
+        Ok(path) => {
+            let app_state = app.state::<AppStateManager>();
+            let restored = match app_state.lock() {
+                Ok(mut state) => restore_persisted_app_state(&path, &mut state),
+                Err(_) => false,
+            };
+            if restored {
+                log::info!("Restored app statistics from previous session");
+            }
+        }
+        Err(e) => {
+            log::warn!("Failed to resolve persisted app state: {}", e);
+            errors.push(format!("app state: {}", e));
+        }
+    }
+
+    // 1つの復元に失敗しても他は続け、失敗した項目をまとめて返す
+    if errors.is_empty() { Ok(()) } else { Err(errors.join("; ")) }
+}
+
+/// 終了確認後の終了（グレースフルシャットダウン）
+#[command]
+pub async fn confirm_quit(app: AppHandle) -> Result<(), AppError> {
+    log::info!("Quit confirmed, shutting down gracefully");
+    graceful_shutdown(&app).await;
+    app.exit(0);
+    Ok(())
+}
+
+/// 確認・中断処理を行わずに即時終了
+#[command]
+pub async fn force_quit(app: AppHandle) -> Result<(), AppError> {
+    log::warn!("Force quit requested");
+    SHUTDOWN_FINISHED.store(true, Ordering::SeqCst);
+    app.exit(0);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quit_confirmation_message() {
+        assert_eq!(
+            quit_confirmation_message(2),
+            "アップロード中のファイルが2件あります。中断して終了しますか？"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::upload_system::FileSelection;
+
+/// 2つ目の起動で渡されたファイルを既存インスタンスへ転送するイベント名
+pub const OPEN_FILES_EVENT: &str = "open-files-requested";
+
+/// 起動引数からアップロード対象のファイルパスを抽出
+/// 先頭（実行ファイル）とフラグ（-で始まる引数）は除外し、相対パスは起動時のカレントディレクトリ基準で解決する
+pub fn extract_file_paths(argv: &[String], cwd: &str) -> Vec<PathBuf> {
+    argv.iter()
+        .skip(1)
+        .filter(|arg| !arg.starts_with('-'))
+        .map(|arg| {
+            let path = Path::new(arg);
+            if path.is_absolute() {
+                path.to_path_buf()
+            } else {
+                Path::new(cwd).join(path)
+            }
+        })
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// 2つ目の起動を検知したときの処理（tauri-plugin-single-instanceのコールバック）
+pub fn handle_second_instance(app: &AppHandle, argv: Vec<String>, cwd: String) {
+    log::info!("Second instance launch detected, focusing existing window");
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+
+    let file_paths = extract_file_paths(&argv, &cwd);
+    if file_paths.is_empty() {
+        return;
+    }
+
+    let total_size = file_paths.iter()
+        .filter_map(|path| std::fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+    let selection = FileSelection {
+        file_count: file_paths.len() as u32,
+        selected_files: file_paths.iter().map(|path| path.to_string_lossy().to_string()).collect(),
+        total_size,
+    };
+
+    log::info!("Forwarding {} file(s) from second instance to upload queue", selection.file_count);
+    if let Err(e) = app.emit(OPEN_FILES_EVENT, &selection) {
+        log::error!("Failed to forward files from second instance: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_extract_file_paths() {
+        let temp_dir = tempdir().unwrap();
+        let absolute = temp_dir.path().join("absolute.mov");
+        File::create(&absolute).unwrap();
+        File::create(temp_dir.path().join("relative.mov")).unwrap();
+
+        let argv = vec![
+            "/Applications/ReelVault.app/Contents/MacOS/ReelVault".to_string(),
+            "--hidden".to_string(),
+            absolute.to_string_lossy().to_string(),
+            "relative.mov".to_string(),
+            "missing.mov".to_string(),
+        ];
+        let cwd = temp_dir.path().to_string_lossy().to_string();
+
+        let paths = extract_file_paths(&argv, &cwd);
+        assert_eq!(paths, vec![absolute, temp_dir.path().join("relative.mov")]);
+    }
+
+    #[test]
+    fn test_extract_file_paths_ignores_directories() {
+        let temp_dir = tempdir().unwrap();
+        let argv = vec![
+            "ReelVault".to_string(),
+            temp_dir.path().to_string_lossy().to_string(),
+        ];
+        assert!(extract_file_paths(&argv, "/").is_empty());
+    }
+}
@@ -0,0 +1,718 @@
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use tauri::{
+    image::Image,
+    menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
+    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
+    AppHandle, Emitter, Manager, Wry,
+};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::commands::config::load_config;
+use crate::commands::file_operations::reveal_path_in_finder;
+use crate::commands::hot_folder::HotFolderStatus;
+use crate::commands::mock_aws::{label_message, MOCK_LABEL};
+use crate::commands::open_location::{build_s3_console_url, open_with_shell};
+use crate::commands::upload_system::{set_queue_paused, UploadQueue, UploadQueueState, UploadStatus};
+
+/// トレイアイコンのID
+pub const TRAY_ID: &str = "main-tray";
+
+/// 一時停止トグル項目のラベル
+const PAUSE_LABEL: &str = "アップロードを一時停止";
+const PAUSED_LABEL: &str = "アップロード一時停止中（クリックで再開）";
+/// 自動アーカイブトグル項目のラベル
+const AUTO_ARCHIVE_LABEL: &str = "自動アーカイブ";
+/// 取り込みフォルダが無効な場合の表示
+const HOT_FOLDER_DISABLED_LABEL: &str = "取り込みフォルダ: 無効";
+/// 帯域制限の一時解除項目のラベル
+const BANDWIDTH_OVERRIDE_LABEL: &str = "今だけ無制限（1時間）";
+
+/// モックAWSモード中にトレイアイコン横へ表示するタイトル
+const MOCK_AWS_TITLE: &str = "MOCK";
+
+/// 一時停止中のトレイアイコンの不透明度（%）
+const PAUSED_ICON_OPACITY_PERCENT: u32 = 40;
+
+/// ステータス項目の更新間隔（これより短い間隔の更新は間引く）
+const STATUS_UPDATE_INTERVAL: Duration = Duration::from_millis(1500);
+
+/// 「最近のアップロード」の再構築を待つ時間（この間に完了したアップロードはまとめて反映する）
+const RECENT_UPLOADS_REFRESH_DELAY: Duration = Duration::from_secs(2);
+/// 「最近のアップロード」に表示する件数の既定値
+pub const DEFAULT_TRAY_RECENT_UPLOADS: usize = 5;
+/// 「最近のアップロード」に表示できる最大件数
+pub const MAX_TRAY_RECENT_UPLOADS: usize = 20;
+/// 「最近のアップロード」の項目のメニューIDの接頭辞（後ろにアイテムIDが付く）
+const RECENT_UPLOAD_FINDER_ID: &str = "recent_upload_finder:";
+const RECENT_UPLOAD_S3_ID: &str = "recent_upload_s3:";
+
+/// 「最近のアップロード」の項目の表示内容
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayRecentUploadLabel {
+    /// ファイル名のみ
+    #[default]
+    FileName,
+    /// ファイル名とS3キー
+    WithKey,
+}
+
+/// 「最近のアップロード」に表示する完了済みアイテム
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentUpload {
+    pub item_id: String,
+    pub file_path: String,
+    pub file_name: String,
+    pub s3_key: String,
+    /// ローカルファイルが残っている（移動・削除済みならFinder表示を無効にする）
+    pub local_exists: bool,
+}
+
+impl RecentUpload {
+    fn label(&self, style: TrayRecentUploadLabel) -> String {
+        match style {
+            TrayRecentUploadLabel::FileName => self.file_name.clone(),
+            TrayRecentUploadLabel::WithKey => format!("{} — {}", self.file_name, self.s3_key),
+        }
+    }
+}
+
+/// メニューに表示中の最近のアップロードとS3コンソールを開く先（リージョン・バケット）
+#[derive(Debug, Default)]
+struct RecentUploadsMenu {
+    uploads: Vec<RecentUpload>,
+    console_target: Option<(String, String)>,
+}
+
+/// 一定間隔より短い呼び出しを間引くスロットル
+#[derive(Debug)]
+pub struct Throttle {
+    interval: Duration,
+    last_run: Option<Instant>,
+}
+
+impl Throttle {
+    pub fn new(interval: Duration) -> Self {
+        Self { interval, last_run: None }
+    }
+
+    /// 実行してよいか判定し、実行する場合は最終実行時刻を更新
+    pub fn should_run(&mut self, now: Instant) -> bool {
+        match self.last_run {
+            Some(last) if now.duration_since(last) < self.interval => false,
+            _ => {
+                self.last_run = Some(now);
+                true
+            }
+        }
+    }
+
+    /// 次回の呼び出しを必ず実行させる
+    pub fn reset(&mut self) {
+        self.last_run = None;
+    }
+}
+
+/// AppHandle経由でメニューを更新するためのトレイ状態（Tauriのmanage対象）
+pub struct TrayMenuState {
+    status_item: MenuItem<Wry>,
+    status_throttle: Mutex<Throttle>,
+    pause_item: CheckMenuItem<Wry>,
+    auto_archive_item: CheckMenuItem<Wry>,
+    hot_folder_item: MenuItem<Wry>,
+    bandwidth_override_item: CheckMenuItem<Wry>,
+    api_cost_item: MenuItem<Wry>,
+    transfer_usage_item: MenuItem<Wry>,
+    recent_uploads_menu: Submenu<Wry>,
+    recent_uploads: Mutex<RecentUploadsMenu>,
+    recent_uploads_refresh_scheduled: AtomicBool,
+    normal_icon: Image<'static>,
+    paused_icon: Image<'static>,
+}
+
+/// キューの状態からトレイのステータス文言を生成
+pub fn format_upload_status(queue: &UploadQueue) -> String {
+    let in_progress: Vec<_> = queue.items.iter()
+        .filter(|item| item.status == UploadStatus::InProgress)
+        .collect();
+    let pending_count = queue.items.iter()
+        .filter(|item| item.status == UploadStatus::Pending)
+        .count();
+
+    if in_progress.is_empty() && pending_count == 0 {
+        return "待機中".to_string();
+    }
+
+    if queue.is_paused && in_progress.is_empty() {
+        return format!("一時停止中 / 残り {}件", pending_count);
+    }
+
+    let total_bytes: u64 = in_progress.iter().map(|item| item.file_size).sum();
+    let uploaded_bytes: u64 = in_progress.iter().map(|item| item.uploaded_bytes).sum();
+    let percentage = if total_bytes > 0 {
+        (uploaded_bytes as f64 / total_bytes as f64 * 100.0).min(100.0)
+    } else {
+        0.0
+    };
+
+    format!(
+        "アップロード中: {}件 ({:.0}%) / 残り {}件",
+        in_progress.len(),
+        percentage,
+        pending_count
+    )
+}
+
+/// トレイのステータス項目を更新（スロットリングあり）
+pub fn update_upload_status(app: &AppHandle, queue_state: &UploadQueueState) {
+    let Some(tray_state) = app.try_state::<TrayMenuState>() else {
+        return;
+    };
+    let should_run = tray_state.status_throttle.lock()
+        .map(|mut throttle| throttle.should_run(Instant::now()))
+        .unwrap_or(false);
+    if should_run {
+        apply_upload_status(&tray_state, queue_state);
+    }
+}
+
+/// トレイのステータス項目を即時更新（処理完了時など最終状態を確実に反映する場合）
+pub fn refresh_upload_status(app: &AppHandle, queue_state: &UploadQueueState) {
+    let Some(tray_state) = app.try_state::<TrayMenuState>() else {
+        return;
+    };
+    if let Ok(mut throttle) = tray_state.status_throttle.lock() {
+        throttle.reset();
+        throttle.should_run(Instant::now());
+    }
+    apply_upload_status(&tray_state, queue_state);
+}
+
+fn apply_upload_status(tray_state: &TrayMenuState, queue_state: &UploadQueueState) {
+    let text = match queue_state.lock() {
+        Ok(queue) => format_upload_status(&queue),
+        Err(e) => {
+            log::error!("Failed to lock upload queue for tray status: {}", e);
+            return;
+        }
+    };
+    // モックAWSモード中は本番のアップロードと混同しないようラベルを付ける
+    if let Err(e) = tray_state.status_item.set_text(label_message(text)) {
+        log::error!("Failed to update tray status item: {}", e);
+    }
+}
+
+/// 取り込みフォルダの状態からトレイの表示文言を生成
+pub fn format_hot_folder_status(status: &HotFolderStatus) -> String {
+    if !status.enabled {
+        return HOT_FOLDER_DISABLED_LABEL.to_string();
+    }
+    let error = if status.last_error.is_some() { "（エラーあり）" } else { "" };
+    format!("取り込みフォルダ: 処理済み{}件・待機中{}件{}", status.processed_count, status.pending_count, error)
+}
+
+/// 取り込みフォルダの処理済み・待機中の件数の表示を更新
+pub fn update_hot_folder_status(app: &AppHandle, status: &HotFolderStatus) {
+    let Some(tray_state) = app.try_state::<TrayMenuState>() else {
+        return;
+    };
+    if let Err(e) = tray_state.hot_folder_item.set_text(format_hot_folder_status(status)) {
+        log::error!("Failed to update tray hot folder item: {}", e);
+    }
+}
+
+/// 今月のS3リクエスト数と概算料金の表示を更新
+pub fn update_api_cost(app: &AppHandle, text: &str) {
+    let Some(tray_state) = app.try_state::<TrayMenuState>() else {
+        return;
+    };
+    if let Err(e) = tray_state.api_cost_item.set_text(text) {
+        log::error!("Failed to update tray API cost item: {}", e);
+    }
+}
+
+/// 今月の転送量と月次上限までの残量の表示を更新
+pub fn update_transfer_usage(app: &AppHandle, text: &str) {
+    let Some(tray_state) = app.try_state::<TrayMenuState>() else {
+        return;
+    };
+    if let Err(e) = tray_state.transfer_usage_item.set_text(text) {
+        log::error!("Failed to update tray transfer usage item: {}", e);
+    }
+}
+
+/// 完了したアイテムを完了日時の新しい順に最大`limit`件取り出す（ローカルファイルの有無は未確認）
+pub fn recent_completed_uploads(queue: &UploadQueue, limit: usize) -> Vec<RecentUpload> {
+    let mut completed: Vec<_> = queue.items.iter()
+        .filter(|item| item.status.is_primary_completed())
+        .filter_map(|item| item.completed_at.as_deref().map(|completed_at| (completed_at, item)))
+        .collect();
+    completed.sort_by(|a, b| b.0.cmp(a.0));
+    completed.into_iter()
+        .take(limit)
+        .map(|(_, item)| RecentUpload {
+            item_id: item.id.clone(),
+            file_path: item.file_path.clone(),
+            file_name: item.file_name.clone(),
+            s3_key: item.s3_key.clone(),
+            local_exists: false,
+        })
+        .collect()
+}
+
+/// 「最近のアップロード」の再構築を予約する（短時間の連続した完了はまとめて1回にする）
+pub fn schedule_recent_uploads_refresh(app: &AppHandle) {
+    let Some(tray_state) = app.try_state::<TrayMenuState>() else {
+        return;
+    };
+    if tray_state.recent_uploads_refresh_scheduled.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    // トレイ操作を待たせないよう、ファイルの確認とメニューの再構築はバックグラウンドで行う
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(RECENT_UPLOADS_REFRESH_DELAY).await;
+        if let Some(tray_state) = app.try_state::<TrayMenuState>() {
+            tray_state.recent_uploads_refresh_scheduled.store(false, Ordering::SeqCst);
+        }
+        rebuild_recent_uploads(&app).await;
+    });
+}
+
+async fn rebuild_recent_uploads(app: &AppHandle) {
+    let (limit, style) = load_config(app)
+        .map(|config| (config.app_settings.tray_recent_uploads_count, config.app_settings.tray_recent_uploads_label))
+        .unwrap_or((DEFAULT_TRAY_RECENT_UPLOADS, TrayRecentUploadLabel::default()));
+    let (uploads, console_target) = {
+        let Some(queue_state) = app.try_state::<UploadQueueState>() else {
+            return;
+        };
+        let queue = match queue_state.lock() {
+            Ok(queue) => queue,
+            Err(e) => {
+                log::error!("Failed to lock upload queue for recent uploads: {}", e);
+                return;
+            }
+        };
+        let target = queue.config.as_ref()
+            .map(|config| (config.region.clone(), config.bucket_name.clone()));
+        (recent_completed_uploads(&queue, limit.min(MAX_TRAY_RECENT_UPLOADS)), target)
+    };
+    let uploads = tokio::task::spawn_blocking(move || {
+        uploads.into_iter()
+            .map(|upload| RecentUpload { local_exists: Path::new(&upload.file_path).exists(), ..upload })
+            .collect::<Vec<_>>()
+    }).await.unwrap_or_default();
+
+    let Some(tray_state) = app.try_state::<TrayMenuState>() else {
+        return;
+    };
+    if let Err(e) = apply_recent_uploads(app, &tray_state.recent_uploads_menu, &uploads, style, console_target.is_some()) {
+        log::error!("Failed to rebuild tray recent uploads menu: {}", e);
+    }
+    if let Err(e) = tray_state.recent_uploads_menu.set_enabled(limit > 0) {
+        log::error!("Failed to update tray recent uploads menu: {}", e);
+    }
+    *tray_state.recent_uploads.lock().unwrap_or_else(|e| e.into_inner()) = RecentUploadsMenu { uploads, console_target };
+}
+
+fn apply_recent_uploads(
+    app: &AppHandle,
+    menu: &Submenu<Wry>,
+    uploads: &[RecentUpload],
+    style: TrayRecentUploadLabel,
+    can_open_console: bool,
+) -> tauri::Result<()> {
+    for item in menu.items()? {
+        menu.remove(&item)?;
+    }
+    if uploads.is_empty() {
+        let empty_item = MenuItem::with_id(app, "recent_uploads_empty", "完了したアップロードはありません", false, None::<&str>)?;
+        return menu.append(&empty_item);
+    }
+    for upload in uploads {
+        let finder_label = if upload.local_exists { "Finderで表示" } else { "Finderで表示（ファイルが見つかりません）" };
+        let finder_item = MenuItem::with_id(app, format!("{}{}", RECENT_UPLOAD_FINDER_ID, upload.item_id), finder_label, upload.local_exists, None::<&str>)?;
+        let s3_item = MenuItem::with_id(app, format!("{}{}", RECENT_UPLOAD_S3_ID, upload.item_id), "S3コンソールで開く", can_open_console, None::<&str>)?;
+        let upload_menu = Submenu::with_items(app, upload.label(style), true, &[&finder_item, &s3_item])?;
+        menu.append(&upload_menu)?;
+    }
+    Ok(())
+}
+
+/// 「最近のアップロード」の項目のクリックを処理（該当しないメニューIDは何もしない）
+fn handle_recent_upload_click(app: &AppHandle, menu_id: &str) {
+    let (item_id, reveal) = match (menu_id.strip_prefix(RECENT_UPLOAD_FINDER_ID), menu_id.strip_prefix(RECENT_UPLOAD_S3_ID)) {
+        (Some(item_id), _) => (item_id, true),
+        (None, Some(item_id)) => (item_id, false),
+        (None, None) => return,
+    };
+    let Some(tray_state) = app.try_state::<TrayMenuState>() else {
+        return;
+    };
+    let (upload, console_target) = {
+        let recent = tray_state.recent_uploads.lock().unwrap_or_else(|e| e.into_inner());
+        (recent.uploads.iter().find(|upload| upload.item_id == item_id).cloned(), recent.console_target.clone())
+    };
+    let Some(upload) = upload else {
+        return;
+    };
+
+    if reveal {
+        if let Err(e) = reveal_path_in_finder(Path::new(&upload.file_path)) {
+            log::warn!("Failed to reveal recent upload {}: {}", upload.file_path, e);
+            // 表示後に移動・削除された場合はメニューへ反映する
+            schedule_recent_uploads_refresh(app);
+        }
+    } else if let Some((region, bucket)) = console_target {
+        let opened = build_s3_console_url(&region, &bucket, &upload.s3_key)
+            .and_then(|url| open_with_shell(app, &url));
+        if let Err(e) = opened {
+            log::warn!("Failed to open S3 console for {}: {}", upload.s3_key, e);
+        }
+    }
+}
+
+/// 一時停止状態をトレイのトグル項目・アイコン・ステータスに反映
+pub fn sync_pause_state(app: &AppHandle, queue_state: &UploadQueueState, paused: bool) {
+    let Some(tray_state) = app.try_state::<TrayMenuState>() else {
+        return;
+    };
+
+    let label = if paused { PAUSED_LABEL } else { PAUSE_LABEL };
+    if let Err(e) = tray_state.pause_item.set_text(label) {
+        log::error!("Failed to update tray pause item label: {}", e);
+    }
+    if let Err(e) = tray_state.pause_item.set_checked(paused) {
+        log::error!("Failed to update tray pause item check state: {}", e);
+    }
+
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let icon = if paused { &tray_state.paused_icon } else { &tray_state.normal_icon };
+        if let Err(e) = tray.set_icon(Some(icon.clone())) {
+            log::error!("Failed to update tray icon: {}", e);
+        }
+    }
+
+    refresh_upload_status(app, queue_state);
+}
+
+/// 自動アーカイブの有効状態をトレイのトグル項目に反映
+pub fn sync_auto_archive_state(app: &AppHandle, enabled: bool) {
+    let Some(tray_state) = app.try_state::<TrayMenuState>() else {
+        return;
+    };
+    if let Err(e) = tray_state.auto_archive_item.set_checked(enabled) {
+        log::error!("Failed to update tray auto archive item: {}", e);
+    }
+}
+
+/// 帯域制限の一時解除状態をトレイのトグル項目に反映
+pub fn sync_bandwidth_override(app: &AppHandle, until: Option<chrono::DateTime<chrono::Local>>) {
+    let Some(tray_state) = app.try_state::<TrayMenuState>() else {
+        return;
+    };
+    let label = match until {
+        Some(until) => format!("帯域制限を解除中（{}まで・クリックで戻す）", until.format("%H:%M")),
+        None => BANDWIDTH_OVERRIDE_LABEL.to_string(),
+    };
+    if let Err(e) = tray_state.bandwidth_override_item.set_text(label) {
+        log::error!("Failed to update tray bandwidth override label: {}", e);
+    }
+    if let Err(e) = tray_state.bandwidth_override_item.set_checked(until.is_some()) {
+        log::error!("Failed to update tray bandwidth override check state: {}", e);
+    }
+}
+
+/// モックAWSモードであることをトレイのタイトル・ツールチップに表示
+pub fn sync_mock_aws_indicator(app: &AppHandle, enabled: bool) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let (title, tooltip) = if enabled {
+        (Some(MOCK_AWS_TITLE), format!("ReelVault {}（AWSへは接続しません）", MOCK_LABEL))
+    } else {
+        (None, "ReelVault".to_string())
+    };
+    if let Err(e) = tray.set_title(title) {
+        log::error!("Failed to update tray title: {}", e);
+    }
+    if let Err(e) = tray.set_tooltip(Some(tooltip)) {
+        log::error!("Failed to update tray tooltip: {}", e);
+    }
+    if let Some(queue_state) = app.try_state::<UploadQueueState>() {
+        refresh_upload_status(app, queue_state.inner());
+    }
+}
+
+/// トレイから一時停止・再開を切り替え
+fn toggle_pause(app: &AppHandle) {
+    let queue_state = app.state::<UploadQueueState>();
+    let paused = match queue_state.lock() {
+        Ok(queue) => queue.is_paused,
+        Err(e) => {
+            log::error!("Failed to lock upload queue: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = set_queue_paused(app, queue_state.inner(), !paused) {
+        log::error!("Failed to toggle upload pause from tray: {}", e);
+    }
+}
+
+/// アイコンの不透明度を下げた一時停止中用アイコンを生成
+pub fn dimmed_icon(icon: &Image<'_>, opacity_percent: u32) -> Image<'static> {
+    let rgba: Vec<u8> = icon.rgba()
+        .chunks_exact(4)
+        .flat_map(|pixel| {
+            let alpha = (pixel[3] as u32 * opacity_percent / 100) as u8;
+            [pixel[0], pixel[1], pixel[2], alpha]
+        })
+        .collect();
+    Image::new_owned(rgba, icon.width(), icon.height())
+}
+
+/// メインウィンドウを表示して指定タブを開く
+fn show_main_window_tab(app: &AppHandle, tab: &str) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+        let _ = app.emit("open-tab", tab);
+    }
+}
+
+// システムトレイのセットアップ関数
+pub fn setup_system_tray(app: &tauri::App) -> tauri::Result<()> {
+    let status_item = MenuItem::with_id(app, "upload_status", "待機中", true, None::<&str>)?;
+    let pause_item = CheckMenuItem::with_id(app, "toggle_pause", PAUSE_LABEL, true, false, None::<&str>)?;
+    let auto_archive_item = CheckMenuItem::with_id(app, "toggle_auto_archive", AUTO_ARCHIVE_LABEL, true, false, None::<&str>)?;
+    let hot_folder_item = MenuItem::with_id(app, "hot_folder_status", HOT_FOLDER_DISABLED_LABEL, true, None::<&str>)?;
+    let bandwidth_override_item = CheckMenuItem::with_id(app, "toggle_bandwidth_override", BANDWIDTH_OVERRIDE_LABEL, true, false, None::<&str>)?;
+    // 表示専用（クリックしても何もしない）
+    let api_cost_item = MenuItem::with_id(app, "api_cost", "今月のS3リクエスト: 集計中", false, None::<&str>)?;
+    let transfer_usage_item = MenuItem::with_id(app, "transfer_usage", "今月の転送量: 集計中", false, None::<&str>)?;
+    let recent_uploads_empty_item = MenuItem::with_id(app, "recent_uploads_empty", "完了したアップロードはありません", false, None::<&str>)?;
+    let recent_uploads_menu = Submenu::with_id_and_items(app, "recent_uploads", "最近のアップロード", true, &[&recent_uploads_empty_item])?;
+    let status_separator = PredefinedMenuItem::separator(app)?;
+    let settings_item = MenuItem::with_id(app, "settings", "設定", true, Some("Cmd+,"))?;
+    let lock_item = MenuItem::with_id(app, "lock_credentials", "認証情報をロック", true, None::<&str>)?;
+    let version_item = MenuItem::with_id(app, "version", "ReelVaultのバージョン情報", true, None::<&str>)?;
+    let separator = PredefinedMenuItem::separator(app)?;
+    let quit_item = MenuItem::with_id(app, "quit", "終了", true, Some("Cmd+Q"))?;
+    let force_quit_item = MenuItem::with_id(app, "force_quit", "強制終了", true, Some("Cmd+Alt+Q"))?;
+
+    let menu = Menu::with_items(app, &[
+        &status_item,
+        &pause_item,
+        &auto_archive_item,
+        &hot_folder_item,
+        &bandwidth_override_item,
+        &api_cost_item,
+        &transfer_usage_item,
+        &recent_uploads_menu,
+        &status_separator,
+        &settings_item,
+        &lock_item,
+        &version_item,
+        &separator,
+        &quit_item,
+        &force_quit_item,
+    ])?;
+
+    let normal_icon = app.default_window_icon().unwrap().clone().to_owned();
+    let paused_icon = dimmed_icon(&normal_icon, PAUSED_ICON_OPACITY_PERCENT);
+
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
+        .icon(normal_icon.clone())
+        .menu(&menu)
+        .show_menu_on_left_click(false)  // 左クリックでメニューを無効化
+        .on_tray_icon_event(|tray, event| match event {
+            // 左クリックでメインウィンドウを開く
+            TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } => {
+                let app = tray.app_handle();
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+            }
+            _ => {}
+        })
+        .on_menu_event(|app, event| {
+            match event.id.as_ref() {
+                "upload_status" => {
+                    // アップロードタブを開く
+                    show_main_window_tab(app, "upload");
+                }
+                "toggle_pause" => toggle_pause(app),
+                "toggle_auto_archive" => crate::commands::auto_archive::toggle_auto_archive(app),
+                "hot_folder_status" => show_main_window_tab(app, "hot-folder"),
+                "toggle_bandwidth_override" => crate::commands::bandwidth::toggle_bandwidth_override(app),
+                "settings" => {
+                    // 設定画面を開く
+                    if let Some(window) = app.get_webview_window("main") {
+                        let _ = window.show();
+                        let _ = window.set_focus();
+                        let _ = app.emit("open-settings", ());
+                    }
+                }
+                // メモリに保持している認証情報を破棄
+                "lock_credentials" => crate::commands::credential_cache::clear_credential_cache(app),
+                "version" => {
+                    // バージョン情報を表示
+                    tracing::info!("Version menu item clicked!");
+                    let app = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let version = env!("CARGO_PKG_VERSION");
+                        tracing::info!("Showing version dialog: ReelVault v{}", version);
+                        let shown = app.dialog().message(&format!("ReelVault v{}", version)).blocking_show();
+                        if shown {
+                            tracing::info!("Dialog shown successfully");
+                        } else {
+                            tracing::warn!("Dialog was not shown, possibly closed by user.");
+                        }
+                    });
+                }
+                // 進行中のアップロードがあれば確認してから終了
+                "quit" => crate::shutdown::request_quit(app),
+                // 確認・中断処理なしで即時終了
+                "force_quit" => app.exit(0),
+                id => handle_recent_upload_click(app, id),
+            }
+        })
+        .build(app)?;
+
+    app.manage(TrayMenuState {
+        status_item,
+        status_throttle: Mutex::new(Throttle::new(STATUS_UPDATE_INTERVAL)),
+        pause_item,
+        auto_archive_item,
+        hot_folder_item,
+        bandwidth_override_item,
+        api_cost_item,
+        transfer_usage_item,
+        recent_uploads_menu,
+        recent_uploads: Mutex::new(RecentUploadsMenu::default()),
+        recent_uploads_refresh_scheduled: AtomicBool::new(false),
+        normal_icon,
+        paused_icon,
+    });
+    schedule_recent_uploads_refresh(app.handle());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::upload_system::UploadItem;
+
+    fn create_item(status: UploadStatus, file_size: u64, uploaded_bytes: u64) -> UploadItem {
+        UploadItem {
+            file_size,
+            uploaded_bytes,
+            ..UploadItem::for_test("/tmp/test.mov", status)
+        }
+    }
+
+    #[test]
+    fn test_format_upload_status_idle() {
+        let mut queue = UploadQueue::new();
+        assert_eq!(format_upload_status(&queue), "待機中");
+
+        queue.items.push(create_item(UploadStatus::Completed, 100, 100));
+        queue.items.push(create_item(UploadStatus::Failed, 100, 0));
+        assert_eq!(format_upload_status(&queue), "待機中");
+    }
+
+    #[test]
+    fn test_format_upload_status_in_progress() {
+        let mut queue = UploadQueue::new();
+        queue.items.push(create_item(UploadStatus::InProgress, 100, 40));
+        queue.items.push(create_item(UploadStatus::InProgress, 100, 50));
+        for _ in 0..12 {
+            queue.items.push(create_item(UploadStatus::Pending, 100, 0));
+        }
+        assert_eq!(format_upload_status(&queue), "アップロード中: 2件 (45%) / 残り 12件");
+    }
+
+    #[test]
+    fn test_format_upload_status_paused() {
+        let mut queue = UploadQueue::new();
+        queue.is_paused = true;
+        queue.items.push(create_item(UploadStatus::Pending, 100, 0));
+        queue.items.push(create_item(UploadStatus::Pending, 100, 0));
+        assert_eq!(format_upload_status(&queue), "一時停止中 / 残り 2件");
+    }
+
+    #[test]
+    fn test_format_hot_folder_status() {
+        let mut status = HotFolderStatus::default();
+        assert_eq!(format_hot_folder_status(&status), "取り込みフォルダ: 無効");
+
+        status.enabled = true;
+        status.processed_count = 12;
+        status.pending_count = 3;
+        assert_eq!(format_hot_folder_status(&status), "取り込みフォルダ: 処理済み12件・待機中3件");
+
+        status.last_error = Some(crate::commands::hot_folder::HotFolderError {
+            message: "failed".to_string(),
+            file_path: None,
+            occurred_at: "2024-05-01T10:00:00+00:00".to_string(),
+        });
+        assert_eq!(format_hot_folder_status(&status), "取り込みフォルダ: 処理済み12件・待機中3件（エラーあり）");
+    }
+
+    #[test]
+    fn test_recent_completed_uploads_newest_first() {
+        let mut queue = UploadQueue::new();
+        for (name, status, completed_at) in [
+            ("a.mov", UploadStatus::Completed, Some("2024-01-01T10:00:00+00:00")),
+            ("b.mov", UploadStatus::Warning, Some("2024-01-03T10:00:00+00:00")),
+            ("c.mov", UploadStatus::Failed, Some("2024-01-04T10:00:00+00:00")),
+            ("d.mov", UploadStatus::Completed, Some("2024-01-02T10:00:00+00:00")),
+            ("e.mov", UploadStatus::Pending, None),
+        ] {
+            let mut item = create_item(status, 100, 100);
+            item.file_name = name.to_string();
+            item.s3_key = format!("footage/{}", name);
+            item.completed_at = completed_at.map(str::to_string);
+            queue.items.push(item);
+        }
+
+        let recent = recent_completed_uploads(&queue, 2);
+        let names: Vec<&str> = recent.iter().map(|upload| upload.file_name.as_str()).collect();
+        assert_eq!(names, vec!["b.mov", "d.mov"]);
+        assert_eq!(recent[0].label(TrayRecentUploadLabel::FileName), "b.mov");
+        assert_eq!(recent[0].label(TrayRecentUploadLabel::WithKey), "b.mov — footage/b.mov");
+        assert!(recent_completed_uploads(&queue, 0).is_empty());
+    }
+
+    #[test]
+    fn test_dimmed_icon() {
+        let icon = Image::new_owned(vec![10, 20, 30, 200, 0, 0, 0, 0], 2, 1);
+        let dimmed = dimmed_icon(&icon, 50);
+        assert_eq!(dimmed.rgba(), &[10, 20, 30, 100, 0, 0, 0, 0]);
+        assert_eq!(dimmed.width(), 2);
+        assert_eq!(dimmed.height(), 1);
+    }
+
+    #[test]
+    fn test_throttle() {
+        let mut throttle = Throttle::new(Duration::from_secs(1));
+        let start = Instant::now();
+        assert!(throttle.should_run(start));
+        assert!(!throttle.should_run(start + Duration::from_millis(500)));
+        assert!(throttle.should_run(start + Duration::from_millis(1000)));
+
+        throttle.reset();
+        assert!(throttle.should_run(start + Duration::from_millis(1100)));
+    }
+}
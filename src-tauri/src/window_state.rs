@@ -0,0 +1,282 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize, Runtime, WebviewWindow, Window};
+
+use crate::internal::InternalError;
+
+/// ジオメトリ保存ファイル名（app_data_dir直下）
+const WINDOW_STATE_FILE: &str = "window_state.json";
+
+/// 移動・リサイズの連続イベントをまとめて保存するまでの待ち時間
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// 最小ウィンドウサイズ（補正時に下回らないようにする）
+const MIN_WIDTH: u32 = 400;
+const MIN_HEIGHT: u32 = 300;
+
+/// ウィンドウの位置・サイズ（物理ピクセル）とディスプレイ識別子
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub monitor_name: Option<String>,
+}
+
+/// ディスプレイの領域
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorRect {
+    pub name: Option<String>,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl MonitorRect {
+    fn from_monitor(monitor: &tauri::Monitor) -> Self {
+        Self {
+            name: monitor.name().cloned(),
+            x: monitor.position().x,
+            y: monitor.position().y,
+            width: monitor.size().width,
+            height: monitor.size().height,
+        }
+    }
+
+    /// ウィンドウのタイトルバー付近（左上）がこのディスプレイ内にあるか
+    fn contains_title_bar(&self, geometry: &WindowGeometry) -> bool {
+        let right = self.x + self.width as i32;
+        let bottom = self.y + self.height as i32;
+        geometry.x + (geometry.width as i32).min(100) > self.x
+            && geometry.x < right
+            && geometry.y >= self.y
+            && geometry.y < bottom
+    }
+}
+
+/// デバウンス保存用の状態（Tauriのmanage対象）
+#[derive(Default)]
+pub struct WindowStateManager {
+    pending: Mutex<Option<WindowGeometry>>,
+    generation: AtomicU64,
+}
+
+fn window_state_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, InternalError> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| InternalError::Config(format!("Failed to get app data directory: {}", e)))?;
+    fs::create_dir_all(&app_data_dir)?;
+    Ok(app_data_dir.join(WINDOW_STATE_FILE))
+}
+
+fn load_geometry<R: Runtime>(app: &AppHandle<R>) -> Option<WindowGeometry> {
+    let path = window_state_path(app).ok()?;
+    let content = fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(geometry) => Some(geometry),
+        Err(e) => {
+            log::warn!("Ignoring invalid window state file: {}", e);
+            None
+        }
+    }
+}
+
+fn save_geometry<R: Runtime>(app: &AppHandle<R>, geometry: &WindowGeometry) -> Result<(), InternalError> {
+    let path = window_state_path(app)?;
+    fs::write(path, serde_json::to_string_pretty(geometry)?)?;
+    Ok(())
+}
+
+/// 保存済みジオメトリを接続中のディスプレイに合わせて補正
+/// 保存時のディスプレイが見つからない・画面外にある場合はメインディスプレイ内に収める
+pub fn fit_to_monitors(geometry: &WindowGeometry, monitors: &[MonitorRect], primary: &MonitorRect) -> WindowGeometry {
+    let saved_monitor = monitors.iter().find(|monitor| {
+        monitor.name.is_some() && monitor.name == geometry.monitor_name && monitor.contains_title_bar(geometry)
+    });
+    if saved_monitor.is_some() {
+        return geometry.clone();
+    }
+
+    let width = geometry.width.clamp(MIN_WIDTH.min(primary.width), primary.width);
+    let height = geometry.height.clamp(MIN_HEIGHT.min(primary.height), primary.height);
+    let max_x = primary.x + (primary.width - width) as i32;
+    let max_y = primary.y + (primary.height - height) as i32;
+
+    WindowGeometry {
+        x: geometry.x.clamp(primary.x, max_x),
+        y: geometry.y.clamp(primary.y, max_y),
+        width,
+        height,
+        monitor_name: primary.name.clone(),
+    }
+}
+
+/// 起動時に保存済みのジオメトリを復元（ウィンドウ表示前に呼び出す）
+pub fn restore_window_geometry<R: Runtime>(window: &WebviewWindow<R>) {
+    let Some(geometry) = load_geometry(window.app_handle()) else {
+        return;
+    };
+
+    let monitors: Vec<MonitorRect> = window.available_monitors()
+        .map(|monitors| monitors.iter().map(MonitorRect::from_monitor).collect())
+        .unwrap_or_default();
+    let primary = match window.primary_monitor() {
+        Ok(Some(monitor)) => MonitorRect::from_monitor(&monitor),
+        _ => match monitors.first() {
+            Some(monitor) => monitor.clone(),
+            None => return,
+        },
+    };
+
+    let fitted = fit_to_monitors(&geometry, &monitors, &primary);
+    if fitted != geometry {
+        log::info!("Adjusted saved window geometry to fit the current displays");
+    }
+
+    if let Err(e) = window.set_size(PhysicalSize::new(fitted.width, fitted.height)) {
+        log::warn!("Failed to restore window size: {}", e);
+    }
+    if let Err(e) = window.set_position(PhysicalPosition::new(fitted.x, fitted.y)) {
+        log::warn!("Failed to restore window position: {}", e);
+    }
+}
+
+fn current_geometry<R: Runtime>(window: &Window<R>) -> Option<WindowGeometry> {
+    if window.is_minimized().unwrap_or(false) || window.is_maximized().unwrap_or(false) {
+        return None;
+    }
+    let position = window.outer_position().ok()?;
+    let size = window.inner_size().ok()?;
+    let monitor_name = window.current_monitor().ok().flatten()
+        .and_then(|monitor| monitor.name().cloned());
+
+    Some(WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        monitor_name,
+    })
+}
+
+/// 移動・リサイズ時に呼び出し、最後のイベントから一定時間後に保存する
+pub fn schedule_save<R: Runtime>(window: &Window<R>) {
+    let Some(geometry) = current_geometry(window) else {
+        return;
+    };
+    let app = window.app_handle().clone();
+    let Some(manager) = app.try_state::<WindowStateManager>() else {
+        return;
+    };
+
+    if let Ok(mut pending) = manager.pending.lock() {
+        *pending = Some(geometry);
+    }
+    let generation = manager.generation.fetch_add(1, Ordering::SeqCst) + 1;
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SAVE_DEBOUNCE).await;
+        let Some(manager) = app.try_state::<WindowStateManager>() else {
+            return;
+        };
+        // 待機中に新しいイベントが来ていれば、そちらの保存に任せる
+        if manager.generation.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        let geometry = manager.pending.lock().ok().and_then(|mut pending| pending.take());
+        if let Some(geometry) = geometry {
+            if let Err(e) = save_geometry(&app, &geometry) {
+                log::warn!("Failed to save window geometry: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn primary() -> MonitorRect {
+        MonitorRect {
+            name: Some("Built-in Retina Display".to_string()),
+            x: 0,
+            y: 0,
+            width: 2880,
+            height: 1800,
+        }
+    }
+
+    fn external() -> MonitorRect {
+        MonitorRect {
+            name: Some("DELL U2720Q".to_string()),
+            x: 2880,
+            y: 0,
+            width: 3840,
+            height: 2160,
+        }
+    }
+
+    #[test]
+    fn test_geometry_kept_when_saved_monitor_connected() {
+        let geometry = WindowGeometry {
+            x: 3000,
+            y: 100,
+            width: 1200,
+            height: 1000,
+            monitor_name: Some("DELL U2720Q".to_string()),
+        };
+        let fitted = fit_to_monitors(&geometry, &[primary(), external()], &primary());
+        assert_eq!(fitted, geometry);
+    }
+
+    #[test]
+    fn test_geometry_moved_to_primary_when_monitor_disconnected() {
+        let geometry = WindowGeometry {
+            x: 3000,
+            y: 100,
+            width: 1200,
+            height: 1000,
+            monitor_name: Some("DELL U2720Q".to_string()),
+        };
+        let fitted = fit_to_monitors(&geometry, &[primary()], &primary());
+        assert_eq!(fitted.x, 2880 - 1200);
+        assert_eq!(fitted.y, 100);
+        assert_eq!(fitted.width, 1200);
+        assert_eq!(fitted.monitor_name, primary().name);
+    }
+
+    #[test]
+    fn test_oversized_geometry_shrinks_to_primary() {
+        let geometry = WindowGeometry {
+            x: -500,
+            y: -200,
+            width: 5000,
+            height: 4000,
+            monitor_name: None,
+        };
+        let fitted = fit_to_monitors(&geometry, &[primary()], &primary());
+        assert_eq!((fitted.x, fitted.y), (0, 0));
+        assert_eq!((fitted.width, fitted.height), (2880, 1800));
+    }
+
+    #[test]
+    fn test_geometry_serialization_roundtrip() {
+        let geometry = WindowGeometry {
+            x: 10,
+            y: 20,
+            width: 1200,
+            height: 900,
+            monitor_name: Some("Built-in Retina Display".to_string()),
+        };
+        let json = serde_json::to_string(&geometry).unwrap();
+        let restored: WindowGeometry = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, geometry);
+    }
+}
@@ -1,7 +1,7 @@
 import React, { useState, useEffect, useRef } from "react";
 import { listen } from '@tauri-apps/api/event';
-import { TauriCommands, AppConfig, AppState } from "./services/tauriCommands";
-import { ConfigManager, ConfigManagerRef } from "./components/ConfigManager";
+import { TauriCommands, EventListeners, AppConfig, AppState } from "./services/tauriCommands";
+import { ConfigManager, ConfigManagerRef, ActiveTab } from "./components/ConfigManager";
 import "./App.css";
 
 function App() {
@@ -41,6 +41,8 @@ function App() {
   // システムトレイからのイベントリスナー
   useEffect(() => {
     let unlisten: (() => void) | undefined;
+    let unlistenOpenTab: (() => void) | undefined;
+    let unlistenQuitRequested: (() => void) | undefined;
 
     const setupEventListener = async () => {
       try {
@@ -50,6 +52,18 @@ function App() {
             configManagerRef.current.openSettingsTab();
           }
         });
+        unlistenOpenTab = await listen<ActiveTab>('open-tab', (event) => {
+          console.log('システムトレイからタブを開く要求を受信:', event.payload);
+          if (configManagerRef.current) {
+            configManagerRef.current.openTab(event.payload);
+          }
+        });
+        unlistenQuitRequested = await EventListeners.listenToQuitRequested(async (request) => {
+          console.log('アップロード中の終了要求を受信:', request);
+          if (confirm(request.message)) {
+            await TauriCommands.confirmQuit();
+          }
+        });
       } catch (error) {
         console.error('イベントリスナーの設定に失敗:', error);
       }
@@ -61,6 +75,12 @@ function App() {
       if (unlisten) {
         unlisten();
       }
+      if (unlistenOpenTab) {
+        unlistenOpenTab();
+      }
+      if (unlistenQuitRequested) {
+        unlistenQuitRequested();
+      }
     };
   }, []);
 
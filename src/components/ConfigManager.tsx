@@ -28,9 +28,10 @@ interface ConfigManagerProps {
 
 export interface ConfigManagerRef {
   openSettingsTab: () => void;
+  openTab: (tab: ActiveTab) => void;
 }
 
-type ActiveTab = 'status' | 'api_test' | 'auth' | 'restore' | 'upload';
+export type ActiveTab = 'status' | 'api_test' | 'auth' | 'restore' | 'upload';
 
 export const ConfigManager = forwardRef<ConfigManagerRef, ConfigManagerProps>(({ 
   initialConfig,
@@ -108,6 +109,10 @@ export const ConfigManager = forwardRef<ConfigManagerRef, ConfigManagerProps>(({
     openSettingsTab: () => {
       console.log('設定タブを開きます');
       setActiveTab('auth');
+    },
+    openTab: (tab: ActiveTab) => {
+      console.log(`${tab}タブを開きます`);
+      setActiveTab(tab);
     }
   }));
 
@@ -498,10 +503,8 @@ export const ConfigManager = forwardRef<ConfigManagerRef, ConfigManagerProps>(({
   const testLifecycleOperations = async () => {
     addTestResult('=== ライフサイクル管理テスト開始 ===');
     
-    // デモ用の認証情報とバケット設定
+    // デモ用のバケット設定（認証情報はアプリ側のキャッシュから取得）
     const testConfig: AwsConfig = {
-      access_key_id: credentials.access_key_id || 'DEMO_ACCESS_KEY',
-      secret_access_key: credentials.secret_access_key || 'DEMO_SECRET_KEY',
       region: credentials.region || 'ap-northeast-1',
       bucket_name: bucketName || config.user_preferences.default_bucket_name || 'test-bucket'
     };
@@ -715,8 +718,6 @@ export const ConfigManager = forwardRef<ConfigManagerRef, ConfigManagerProps>(({
   // --- Lifecycle Management Functions ---
   
   const getAwsConfigFromCredentials = (): AwsConfig => ({
-    access_key_id: credentials.access_key_id,
-    secret_access_key: credentials.secret_access_key,
     region: credentials.region,
     bucket_name: bucketName || config.user_preferences.default_bucket_name || '',
   });
@@ -853,8 +854,6 @@ export const ConfigManager = forwardRef<ConfigManagerRef, ConfigManagerProps>(({
         try {
                      // 復元状況を確認
            const response = await TauriCommands.checkRestoreStatus(fileKey, {
-             access_key_id: credentials.access_key_id,
-             secret_access_key: credentials.secret_access_key,
              region: credentials.region,
              bucket_name: config.user_preferences.default_bucket_name
            });
@@ -43,6 +43,7 @@ export const UploadManager: React.FC<UploadManagerProps> = ({
   const [uploadQueue, setUploadQueue] = useState<UploadItem[]>([]);
   const [_uploadStats, setUploadStats] = useState<UploadStatistics | null>(null);
   const [isUploading, setIsUploading] = useState(false);
+  const [isQueuePaused, setIsQueuePaused] = useState(false);
   const [selectedFiles, setSelectedFiles] = useState<FileSelection | null>(null);
   const [uploadConfig, setUploadConfig] = useState<UploadConfig | null>(null);
   const [error, setError] = useState<string | null>(null);
@@ -75,7 +76,7 @@ export const UploadManager: React.FC<UploadManagerProps> = ({
 
       try {
         // 🎯 デフォルトはプレミアム版設定
-        const defaultConfig = createConfig(awsCredentials, bucketName, 'Premium');
+        const defaultConfig = createConfig(bucketName, 'Premium');
         
         // 🔍 設定内容をデバッグ出力
         debugLog('🔧 生成された設定:', {
@@ -92,6 +93,12 @@ export const UploadManager: React.FC<UploadManagerProps> = ({
         debugInfo('🗑️ 古いキューをクリア中...');
         await TauriCommands.clearUploadQueue();
         
+        // 認証情報はバックエンドにキャッシュし、ロック中の場合のみKeychainから読み込む
+        const credentialStatus = await TauriCommands.getCachedCredentialsStatus();
+        if (!credentialStatus.unlocked) {
+          await TauriCommands.unlockCredentials();
+        }
+
         debugInfo('🔄 新しい設定で初期化中...');
         await TauriCommands.initializeUploadQueue(defaultConfig);
         
@@ -120,6 +127,30 @@ export const UploadManager: React.FC<UploadManagerProps> = ({
     setForceUpdate(prev => prev + 1);
   }, [uploadQueue]);
 
+  // キュー全体の一時停止状態のリスナー（トレイからの操作と同期）
+  useEffect(() => {
+    const unlisten = listen<{ is_paused: boolean }>('upload-queue-pause-changed', (event) => {
+      setIsQueuePaused(event.payload.is_paused);
+    });
+
+    return () => {
+      if (unlisten && typeof unlisten.then === 'function') unlisten.then(f => f && typeof f === 'function' && f());
+    };
+  }, []);
+
+  // 2つ目の起動で渡されたファイルを選択状態にする（シングルインスタンス）
+  useEffect(() => {
+    const unlisten = listen<FileSelection>('open-files-requested', (event) => {
+      debugInfo('📂 別プロセスから転送されたファイル:', event.payload);
+      setSelectedFiles(event.payload);
+      setError(null);
+    });
+
+    return () => {
+      if (unlisten && typeof unlisten.then === 'function') unlisten.then(f => f && typeof f === 'function' && f());
+    };
+  }, []);
+
   // 進捗更新のリスナー
   useEffect(() => {
     if (!uploadConfig) return;
@@ -150,6 +181,7 @@ export const UploadManager: React.FC<UploadManagerProps> = ({
       const progressTextElement = document.querySelector(`[data-item-id="${progress.item_id}"] .progress-text`);
       const progressBytesElement = document.querySelector(`[data-item-id="${progress.item_id}"] .progress-bytes`);
       const speedElement = document.querySelector(`[data-item-id="${progress.item_id}"] .upload-speed`);
+      const partElement = document.querySelector(`[data-item-id="${progress.item_id}"] .part-progress`);
       
       if (progressBarElement) {
         (progressBarElement as HTMLElement).style.width = `${Math.max(0, Math.min(100, progress.percentage))}%`;
@@ -177,6 +209,16 @@ export const UploadManager: React.FC<UploadManagerProps> = ({
         speedElement.textContent = `⚡ ${progress.speed_mbps.toFixed(2)} MB/s`;
       }
       
+      // マルチパートの場合は「パート 12/160 の 45%」のようにパート内の進捗を表示
+      if (partElement) {
+        if (progress.current_part && progress.total_parts && progress.part_total_bytes) {
+          const partPercent = Math.floor(((progress.part_uploaded_bytes ?? 0) / progress.part_total_bytes) * 100);
+          partElement.textContent = `パート ${progress.current_part}/${progress.total_parts} の ${partPercent}%`;
+        } else {
+          partElement.textContent = '';
+        }
+      }
+      
       // DOM直接操作は削除 - React状態更新に一本化
 
       // React状態更新を強制的に即時実行（flushSyncで自動バッチングを無効化）
@@ -257,7 +299,8 @@ export const UploadManager: React.FC<UploadManagerProps> = ({
             const allCompleted = queueItems.every(item => 
               item.status === UploadStatus.Completed || 
               item.status === UploadStatus.Failed || 
-              item.status === UploadStatus.Cancelled
+              item.status === UploadStatus.Cancelled ||
+              item.status === UploadStatus.Warning
             );
             
             if (allCompleted) {
@@ -315,7 +358,8 @@ export const UploadManager: React.FC<UploadManagerProps> = ({
         const allCompleted = queueItems.length > 0 && queueItems.every(item => 
           item.status === UploadStatus.Completed || 
           item.status === UploadStatus.Failed || 
-          item.status === UploadStatus.Cancelled
+          item.status === UploadStatus.Cancelled ||
+          item.status === UploadStatus.Warning
         );
         
         if (allCompleted && !inProgress) {
@@ -494,6 +538,19 @@ export const UploadManager: React.FC<UploadManagerProps> = ({
     }
   }, []);
 
+  // キュー全体の一時停止・再開（状態はイベント経由で反映）
+  const handleTogglePause = useCallback(async () => {
+    try {
+      if (isQueuePaused) {
+        await TauriCommands.resumeUploadQueue();
+      } else {
+        await TauriCommands.pauseUploadQueue();
+      }
+    } catch (err) {
+      console.error('一時停止状態の切り替えに失敗:', err);
+    }
+  }, [isQueuePaused]);
+
   // キューのクリア
   const handleClearQueue = useCallback(async () => {
     try {
@@ -641,10 +698,9 @@ export const UploadManager: React.FC<UploadManagerProps> = ({
   };
 
   // 🎯 統一された設定生成関数
-  const createConfig = (credentials: AwsCredentials, bucket: string, tier: 'Free' | 'Premium'): UploadConfig => {
+  const createConfig = (bucket: string, tier: 'Free' | 'Premium'): UploadConfig => {
     // 基本設定（共通）
     const baseConfig = {
-      aws_credentials: credentials,
       bucket_name: bucket,
       auto_create_metadata: true,
       s3_key_prefix: 'uploads',
@@ -688,7 +744,7 @@ export const UploadManager: React.FC<UploadManagerProps> = ({
   const handleTierChange = (tier: 'Free' | 'Premium') => {
     if (!awsCredentials || !bucketName) return;
     
-    const newConfig = createConfig(awsCredentials, bucketName, tier);
+    const newConfig = createConfig(bucketName, tier);
     setTempConfig(newConfig);
     setCurrentTier(tier);
   };
@@ -939,6 +995,12 @@ export const UploadManager: React.FC<UploadManagerProps> = ({
                 ⏸️ 停止
               </button>
             )}
+            <button 
+              onClick={handleTogglePause}
+              className="btn-secondary"
+            >
+              {isQueuePaused ? '▶️ 再開' : '⏯️ 一時停止'}
+            </button>
             <button 
               onClick={handleClearQueue}
               className="btn-danger"
@@ -1002,6 +1064,8 @@ export const UploadManager: React.FC<UploadManagerProps> = ({
                       {item.status === UploadStatus.Failed && '❌ 失敗'}
                       {item.status === UploadStatus.Paused && '⏸️ 一時停止'}
                       {item.status === UploadStatus.Cancelled && '🚫 キャンセル'}
+                      {item.status === UploadStatus.Warning && '⚠️ ミラー失敗'}
+                      {item.status === UploadStatus.Skipped && '⏭️ 重複のため除外'}
                     </span>
                   </div>
                 </div>
@@ -1014,6 +1078,7 @@ export const UploadManager: React.FC<UploadManagerProps> = ({
                         {formatFileSize(item.uploaded_bytes)} / {formatFileSize(item.file_size)}
                       </span>
                       <span className="upload-speed">⚡ {item.speed_mbps.toFixed(2)} MB/s</span>
+                      {item.status === UploadStatus.InProgress && <span className="part-progress"></span>}
                       {item.status === UploadStatus.InProgress && item.eta_seconds && item.eta_seconds > 0 && (
                         <span className="eta-time">⏱️ 残り {Math.round(item.eta_seconds)}秒</span>
                       )}
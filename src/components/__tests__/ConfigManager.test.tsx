@@ -181,8 +181,6 @@ describe('ConfigManager', () => {
 
     // getLifecycleStatusが正しい引数で呼ばれたことを確認
     expect(vi.mocked(TauriCommands.TauriCommands.getLifecycleStatus)).toHaveBeenCalledWith({
-      access_key_id: 'test-access-key',
-      secret_access_key: 'test-secret-key',
       region: 'ap-northeast-1',
       bucket_name: 'test-bucket'
     });
@@ -190,8 +188,6 @@ describe('ConfigManager', () => {
     // 返り値が期待通りであることを確認
     const lastCall = vi.mocked(TauriCommands.TauriCommands.getLifecycleStatus).mock.calls[vi.mocked(TauriCommands.TauriCommands.getLifecycleStatus).mock.calls.length - 1];
     expect(lastCall[0]).toEqual({
-      access_key_id: 'test-access-key',
-      secret_access_key: 'test-secret-key',
       region: 'ap-northeast-1',
       bucket_name: 'test-bucket'
     });
@@ -20,8 +20,6 @@ const mockInvoke = vi.mocked(invoke);
 const mockAwsConfig: AwsConfig = {
   region: 'us-east-1',
   bucket_name: 'test-bucket',
-  access_key_id: 'test-access-key',
-  secret_access_key: 'test-secret-key',
 };
 
 const mockRestoreInfo: RestoreInfo = {
@@ -48,10 +46,12 @@ const mockDownloadProgress: DownloadProgress = {
 };
 
 const mockRestoreNotification: RestoreNotification = {
+  id: 'completed:test-file.mp4:2024-01-01T00:00:00Z',
   key: 'test-file.mp4',
   message: '復元が完了しました',
   timestamp: '2024-01-01T03:00:00Z',
   status: 'completed',
+  read: false,
 };
 
 describe('RestoreService', () => {
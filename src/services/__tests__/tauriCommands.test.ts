@@ -42,8 +42,6 @@ const mockAwsCredentials = {
 };
 
 const mockAwsConfig = {
-  access_key_id: 'test-access-key',
-  secret_access_key: 'test-secret-key',
   region: 'ap-northeast-1',
   bucket_name: 'test-bucket',
 };
@@ -113,7 +111,6 @@ const mockAppState = {
 };
 
 const uploadConfig = {
-  aws_credentials: mockAwsCredentials,
   bucket_name: 'test-bucket',
   max_concurrent_uploads: 3,
   chunk_size_mb: 5,
@@ -253,7 +250,7 @@ describe('tauriCommands', () => {
     it('should get upload queue items successfully', async () => {
       vi.mocked(invoke).mockResolvedValue([mockUploadItem]);
       const result = await UploadOperations.getUploadQueueItems();
-      expect(invoke).toHaveBeenCalledWith('get_upload_queue_items');
+      expect(invoke).toHaveBeenCalledWith('get_upload_queue_items', { projectId: undefined });
       expect(result).toEqual([mockUploadItem]);
     });
 
@@ -327,7 +324,7 @@ describe('tauriCommands', () => {
     it('should get restore notifications successfully', async () => {
       vi.mocked(invoke).mockResolvedValue([{ key: 'test-key', message: 'Restore completed' }]);
       const result = await RestoreOperations.getRestoreNotifications();
-      expect(invoke).toHaveBeenCalledWith('get_restore_notifications');
+      expect(invoke).toHaveBeenCalledWith('get_restore_notifications', { unreadOnly: undefined });
       expect(result).toEqual([{ key: 'test-key', message: 'Restore completed' }]);
     });
 
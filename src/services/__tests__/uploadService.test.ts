@@ -37,15 +37,7 @@ import { listen } from '@tauri-apps/api/event';
 import { TauriCommands } from '../../types/tauri-commands';
 
 // モックデータ
-const mockAwsCredentials = {
-  access_key_id: 'test-access-key',
-  secret_access_key: 'test-secret-key',
-  region: 'ap-northeast-1',
-  session_token: 'test-session-token',
-};
-
 const mockUploadConfig = {
-  aws_credentials: mockAwsCredentials,
   bucket_name: 'test-bucket',
   max_concurrent_uploads: 3,
   chunk_size_mb: 5,
@@ -346,9 +338,9 @@ describe('UploadService', () => {
   describe('ユーティリティ関数', () => {
     describe('createDefaultConfig', () => {
       it('should create default config for Free tier', () => {
-        const config = UploadService.createDefaultConfig(mockAwsCredentials, 'test-bucket', 'Free');
+        const config = UploadService.createDefaultConfig('test-bucket', 'Free');
         
-        expect(config.aws_credentials).toEqual(mockAwsCredentials);
+        expect(config).not.toHaveProperty('aws_credentials');
         expect(config.bucket_name).toBe('test-bucket');
         expect(config.tier).toBe('Free');
         expect(config.max_concurrent_parts).toBe(1);
@@ -357,7 +349,7 @@ describe('UploadService', () => {
       });
 
       it('should create default config for Premium tier', () => {
-        const config = UploadService.createDefaultConfig(mockAwsCredentials, 'test-bucket', 'Premium');
+        const config = UploadService.createDefaultConfig('test-bucket', 'Premium');
         
         expect(config.tier).toBe('Premium');
         expect(config.max_concurrent_parts).toBe(8);
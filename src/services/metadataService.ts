@@ -5,6 +5,7 @@ import type {
   CreateMetadataRequest, 
   UpdateMetadataRequest 
 } from '../types/metadata';
+import type { MetadataDbInitResult } from '../types/tauri-commands';
 
 class MetadataService {
   private dbPath: string;
@@ -14,11 +15,11 @@ class MetadataService {
   }
 
   /**
-   * メタデータデータベースを初期化
+   * メタデータデータベースを初期化（破損していた場合は修復結果がrecoveryに入る）
    */
-  async initializeDatabase(): Promise<string> {
+  async initializeDatabase(): Promise<MetadataDbInitResult> {
     try {
-      const result = await invoke<string>('initialize_metadata_db', {
+      const result = await invoke<MetadataDbInitResult>('initialize_metadata_db', {
         dbPath: this.dbPath
       });
       return result;
@@ -6,14 +6,29 @@ import { listen } from '@tauri-apps/api/event';
 import type {
   // ファイル操作API関連
   FileInfo,
+  OpenInFinderResult,
   WatchConfig,
   
   // AWS操作API関連
   AwsConfig,
   ConnectionTestResult,
+  FieldChanges,
+  ObjectMetadataUpdateReport,
+  ObjectMetadataProgress,
   S3Object,
+  S3ObjectVersion,
+  BucketVersioningStatus,
+  ArchiveGuardOptions,
+  ArchiveAccessCheck,
   UploadProgress,
   RestoreInfo,
+  BulkRestoreResult,
+  PrefixRestoreEstimate,
+  PrefixRestoreJob,
+  PrefixRestoreResponse,
+  RestoredFile,
+  OperationInfo,
+  OperationFinished,
   RestoreStatusResult,
   DownloadProgress,
   RestoreNotification,
@@ -21,11 +36,17 @@ import type {
   // ライフサイクル管理API関連
   LifecyclePolicyResult,
   LifecyclePolicyStatus,
+  BucketInitReport,
+  BucketSecurityReport,
+  ArchiveTransitionStatus,
   LifecycleRule,
   LifecycleTransition,
+  LifecyclePrefixWarning,
+  UploadReadinessResult,
   
   // AWS認証API関連
   AwsCredentials,
+  CredentialCacheStatus,
   AwsAuthResult,
   AwsUserIdentity,
   PermissionCheck,
@@ -41,15 +62,118 @@ import type {
   // 状態管理API関連
   AppState,
   UploadItem,
+  BatchOperationResult,
+  UploadItemChanges,
+  UploadItemUpdateResult,
+  UploadQueueChanged,
   UploadStatistics,
   FileSelection,
   UploadConfig,
   UploadProgressInfo,
   UploadStatistics as UploadStats,
+  UploadQueuePauseState,
   S3KeyConfig,
+  NamingPatternPreset,
   AppStatistics,
   SystemStatus,
-  StateUpdate
+  StateUpdate,
+  
+  // ローカル原本のリテンション関連
+  RetentionMode,
+  RetentionAction,
+  RetentionSettings,
+  UploadRecord,
+  RetentionCleanupPending,
+  RetentionCleanupEntry,
+  RetentionCleanupResult,
+  
+  // 操作の監査ログ関連
+  AuditOperation,
+  AuditExportFormat,
+  OperationAuditEntry,
+  AuditLogFilter,
+  
+  // 自動アーカイブ関連
+  AutoArchiveHealth,
+  AutoArchiveUploadSettings,
+  AutoArchiveStatus,
+  
+  // 小ファイルのバンドル関連
+  BundleOptions,
+  BundleLookupResult,
+  MetadataJobStatus,
+  
+  // キューのエクスポート・インポート関連
+  QueueImportMode,
+  QueueImportResult,
+  UploadHistory,
+  UploadHistoryArchiveResult,
+  UploadHistoryEntry,
+  Project,
+  NewProject,
+  ProjectSummary,
+  ProjectAssignmentResult,
+  StorageClassUsage,
+  CrossRegionWarning,
+  StartupCheck,
+  StartupDiagnostics,
+  ConfigRecovery,
+  MetadataDbRecovery,
+  StartupProgress,
+  StartupPhaseCompleted,
+  QueueAddProgress,
+  WatcherRecovered,
+  WatcherFailed,
+  QueueDuplicateProgress,
+  QueueDuplicateReport,
+  
+  // ネットワーク状態関連
+  NetworkStatusChange,
+  
+  // モックAWSモード関連
+  MockAwsStatus,
+  
+  // ライセンスAPI関連
+  LicenseStatus,
+  TierPolicy,
+  MetricsSnapshot,
+  ChecksumMetricsSnapshot,
+  ChecksumAlgorithm,
+  CommandPerformance,
+  CommandTiming,
+  ApiUsagePeriod,
+  ApiUsageSummary,
+  TransferUsage,
+  TransferCapAlert,
+  UploadStalledEvent,
+  HotFolderAfterUpload,
+  HotFolderSettings,
+  HotFolderError,
+  HotFolderStatus,
+  AppDataCategory,
+  AppDataCleanupSettings,
+  AppDataCategoryUsage,
+  AppDataUsage,
+  AppDataCleanupCategoryResult,
+  AppDataCleanupResult,
+  FrontendLogLevel,
+  FrontendLogResult,
+  HealthReport,
+  QuickUploadSettings,
+  QuickUploadStatus,
+  DroppedFiles,
+  PendingAutoUpload,
+  AutoUploadThrottled,
+  VerifyScope,
+  RemoteVerifyReport,
+  
+  // 差分同期API関連
+  SyncOptions,
+  FileComparison,
+  SyncSummary,
+  
+  // 終了API関連
+  QuitRequest
 } from '../types/tauri-commands';
 
 // UploadStatusをenumとして再export
@@ -60,6 +184,7 @@ export enum UploadStatus {
   Failed = "Failed",
   Paused = "Paused",
   Cancelled = "Cancelled",
+  Warning = "Warning",
 }
 
 // ===== ファイル操作API =====
@@ -75,6 +200,22 @@ export const FileOperations = {
 
   async openFileDialog(multiple: boolean, filters?: string[]): Promise<FileSelection> {
     return invoke('open_file_dialog', { multiple, filters });
+  },
+
+  async revealInFinder(path: string): Promise<void> {
+    return invoke('reveal_in_finder', { path });
+  },
+
+  async openInFinder(path: string): Promise<OpenInFinderResult> {
+    return invoke('open_in_finder', { path });
+  },
+
+  async unlockLocalFile(path: string): Promise<void> {
+    return invoke('unlock_local_file', { path });
+  },
+
+  async openS3ConsoleUrl(config: AwsConfig, key: string): Promise<string> {
+    return invoke('open_s3_console_url', { config, key });
   }
 };
 
@@ -89,12 +230,28 @@ export const AwsOperations = {
     return invoke('list_s3_objects', { config, prefix });
   },
 
+  async updateS3ObjectsMetadata(config: AwsConfig, keys: string[], metadataChanges?: FieldChanges, tagChanges?: FieldChanges): Promise<ObjectMetadataUpdateReport> {
+    return invoke('update_s3_objects_metadata', { config, keys, metadataChanges, tagChanges });
+  },
+
   async getS3Object(bucketName: string, key: string): Promise<S3Object> {
     return invoke('get_s3_object', { bucketName, key });
   },
 
-  async downloadS3File(key: string, localPath: string, config: AwsConfig): Promise<void> {
-    return invoke('download_s3_file', { key, localPath, config });
+  async downloadS3File(key: string, localPath: string, config: AwsConfig, autoDecompress?: boolean, versionId?: string, archiveGuard?: ArchiveGuardOptions): Promise<void> {
+    return invoke('download_s3_file', { key, localPath, config, autoDecompress, versionId, archiveGuard });
+  },
+
+  async checkArchiveAccess(config: AwsConfig, s3Key: string, versionId?: string, options?: ArchiveGuardOptions): Promise<ArchiveAccessCheck> {
+    return invoke('check_archive_access', { config, s3Key, versionId, options });
+  },
+
+  async getBucketVersioningStatus(config: AwsConfig): Promise<BucketVersioningStatus> {
+    return invoke('get_bucket_versioning_status', { config });
+  },
+
+  async listObjectVersions(config: AwsConfig, key: string): Promise<S3ObjectVersion[]> {
+    return invoke('list_object_versions', { config, key });
   },
 
   async downloadRestoredFile(key: string, localPath: string, config: AwsConfig): Promise<void> {
@@ -109,8 +266,17 @@ export const UploadOperations = {
     return invoke('initialize_upload_queue', { config });
   },
 
-  async addFilesToUploadQueue(filePaths: string[], s3KeyConfig: S3KeyConfig): Promise<void> {
-    return invoke('add_files_to_upload_queue', { filePaths, s3KeyConfig });
+  // note・projectIdは追加する全アイテムに設定される
+  async addFilesToUploadQueue(filePaths: string[], s3KeyConfig: S3KeyConfig, bundleOptions?: BundleOptions, note?: string, projectId?: string): Promise<void> {
+    return invoke('add_files_to_upload_queue', { filePaths, s3KeyConfig, bundleOptions, note, projectId });
+  },
+
+  async findFileInBundles(query: string, dbPath: string): Promise<BundleLookupResult[]> {
+    return invoke('find_file_in_bundles', { query, dbPath });
+  },
+
+  async getMetadataJobStatus(): Promise<MetadataJobStatus> {
+    return invoke('get_metadata_job_status');
   },
 
   async startUploadProcessing(): Promise<void> {
@@ -125,8 +291,16 @@ export const UploadOperations = {
     return invoke('clear_upload_queue');
   },
 
-  async getUploadQueueItems(): Promise<UploadItem[]> {
-    return invoke('get_upload_queue_items');
+  async getNamingPatternPresets(): Promise<NamingPatternPreset[]> {
+    return invoke('get_naming_pattern_presets');
+  },
+
+  async getUploadQueueItems(projectId?: string): Promise<UploadItem[]> {
+    return invoke('get_upload_queue_items', { projectId });
+  },
+
+  async setUploadItemNote(itemId: string, note?: string): Promise<UploadItem> {
+    return invoke('set_upload_item_note', { itemId, note });
   },
 
   async getUploadQueueStatus(): Promise<UploadStatistics> {
@@ -139,14 +313,116 @@ export const UploadOperations = {
 
   async removeUploadItem(itemId: string): Promise<void> {
     return invoke('remove_upload_item', { itemId });
+  },
+
+  async removeUploadItems(itemIds: string[]): Promise<BatchOperationResult> {
+    return invoke('remove_upload_items', { itemIds });
+  },
+
+  async retryUploadItems(itemIds: string[]): Promise<BatchOperationResult> {
+    return invoke('retry_upload_items', { itemIds });
+  },
+
+  async analyzeQueueDuplicates(): Promise<QueueDuplicateReport> {
+    return invoke('analyze_queue_duplicates');
+  },
+
+  async excludeQueueDuplicates(report: QueueDuplicateReport): Promise<BatchOperationResult> {
+    return invoke('exclude_queue_duplicates', { report });
+  },
+
+  async updateUploadItem(itemId: string, changes: UploadItemChanges): Promise<UploadItemUpdateResult> {
+    return invoke('update_upload_item', { itemId, changes });
+  },
+
+  // 失敗したミラーのみ再実行（対象の件数を返す）
+  async retryMirror(itemId: string): Promise<number> {
+    return invoke('retry_mirror', { itemId });
+  },
+
+  async pauseUploadQueue(): Promise<void> {
+    return invoke('pause_upload_queue');
+  },
+
+  async resumeUploadQueue(): Promise<void> {
+    return invoke('resume_upload_queue');
+  },
+
+  async exportUploadQueue(outputPath: string): Promise<number> {
+    return invoke('export_upload_queue', { outputPath });
+  },
+
+  async importUploadQueue(inputPath: string, mode: QueueImportMode = 'append'): Promise<QueueImportResult> {
+    return invoke('import_upload_queue', { inputPath, mode });
+  },
+
+  // 保持日数を過ぎた完了・失敗アイテムをキューから履歴へ移す（未指定は設定値）
+  async archiveUploadHistory(retentionDays?: number): Promise<UploadHistoryArchiveResult> {
+    return invoke('archive_upload_history', { retentionDays });
+  },
+
+  async getUploadHistory(projectId?: string, limit?: number, offset?: number): Promise<UploadHistory> {
+    return invoke('get_upload_history', { projectId, limit, offset });
+  }
+};
+
+// ===== プロジェクト（案件）API =====
+
+export const ProjectOperations = {
+  async createProject(project: NewProject): Promise<Project> {
+    return invoke('create_project', { project });
+  },
+
+  async listProjects(): Promise<Project[]> {
+    return invoke('list_projects');
+  },
+
+  // projectId に null を渡すと割り当てを解除
+  async assignFilesToProject(projectId: string | null, filePaths: string[]): Promise<ProjectAssignmentResult> {
+    return invoke('assign_files_to_project', { projectId, filePaths });
+  },
+
+  async getProjectSummary(projectId: string): Promise<ProjectSummary> {
+    return invoke('get_project_summary', { projectId });
+  },
+
+  // 割り当てを解除するだけで、ローカルファイル・S3オブジェクトは削除しない
+  async deleteProject(projectId: string): Promise<void> {
+    return invoke('delete_project', { projectId });
   }
 };
 
 // ===== 復元API =====
 
 export const RestoreOperations = {
-  async restoreFile(key: string, config: AwsConfig, tier: string): Promise<RestoreInfo> {
-    return invoke('restore_file', { key, config, tier });
+  async restoreFile(key: string, config: AwsConfig, tier: string, versionId?: string, days?: number, fallbackToStandard?: boolean): Promise<RestoreInfo> {
+    return invoke('restore_file', { key, config, tier, versionId, days, fallbackToStandard });
+  },
+
+  async restoreFilesBulk(s3Keys: string[], config: AwsConfig, tier: string, days?: number): Promise<BulkRestoreResult> {
+    return invoke('restore_files_bulk', { s3Keys, config, tier, days });
+  },
+
+  // confirm: false で見積もりのみ、true で復元ジョブを開始
+  async restorePrefix(config: AwsConfig, prefix: string, tier: string, days: number | undefined, confirm: boolean): Promise<PrefixRestoreResponse> {
+    return invoke('restore_prefix', { config, prefix, tier, days, confirm });
+  },
+
+  async listPrefixRestoreJobs(): Promise<PrefixRestoreJob[]> {
+    return invoke('list_prefix_restore_jobs');
+  },
+
+  async cancelPrefixRestore(prefix: string): Promise<PrefixRestoreJob> {
+    return invoke('cancel_prefix_restore', { prefix });
+  },
+
+  // Restoredフォルダの作業コピー（日次タスクで期限切れのものをゴミ箱へ移す）
+  async listRestoredFiles(): Promise<RestoredFile[]> {
+    return invoke('list_restored_files');
+  },
+
+  async extendRestoredFileRetention(localPath: string, days?: number): Promise<RestoredFile> {
+    return invoke('extend_restored_file_retention', { localPath, days });
   },
 
   async checkRestoreStatus(key: string, config: AwsConfig): Promise<RestoreStatusResult> {
@@ -157,8 +433,20 @@ export const RestoreOperations = {
     return invoke('list_restore_jobs');
   },
 
-  async getRestoreNotifications(): Promise<RestoreNotification[]> {
-    return invoke('get_restore_notifications');
+  async getRestoreNotifications(unreadOnly?: boolean): Promise<RestoreNotification[]> {
+    return invoke('get_restore_notifications', { unreadOnly });
+  },
+
+  async markNotificationRead(id: string): Promise<void> {
+    return invoke('mark_notification_read', { id });
+  },
+
+  async markAllNotificationsRead(): Promise<number> {
+    return invoke('mark_all_notifications_read');
+  },
+
+  async deleteNotification(id: string): Promise<void> {
+    return invoke('delete_notification', { id });
   },
 
   async clearRestoreHistory(): Promise<void> {
@@ -183,6 +471,22 @@ export const LifecycleOperations = {
 
   async validateLifecycleConfig(config: AwsConfig): Promise<boolean> {
     return invoke('validate_lifecycle_config', { config });
+  },
+
+  async checkUploadReadiness(config: AwsConfig, s3KeyConfig?: S3KeyConfig, force?: boolean, includeSecurity?: boolean): Promise<UploadReadinessResult> {
+    return invoke('check_upload_readiness', { config, s3KeyConfig, force, includeSecurity });
+  },
+
+  async initializeBucketForReelvault(config: AwsConfig): Promise<BucketInitReport> {
+    return invoke('initialize_bucket_for_reelvault', { config });
+  },
+
+  async checkBucketSecurity(config: AwsConfig): Promise<BucketSecurityReport> {
+    return invoke('check_bucket_security', { config });
+  },
+
+  async checkArchiveTransitionStatus(config: AwsConfig, prefix?: string): Promise<ArchiveTransitionStatus> {
+    return invoke('check_archive_transition_status', { config, prefix });
   }
 };
 
@@ -201,6 +505,19 @@ export const AuthOperations = {
     return invoke('load_aws_credentials_secure', { profileName });
   },
 
+  async getCachedCredentialsStatus(): Promise<CredentialCacheStatus> {
+    return invoke('get_cached_credentials_status');
+  },
+
+  // Keychainからの読み込み（Touch ID）は有効期間内に1回だけ行われる
+  async unlockCredentials(profile?: string): Promise<CredentialCacheStatus> {
+    return invoke('unlock_credentials', { profile });
+  },
+
+  async lockCredentials(): Promise<CredentialCacheStatus> {
+    return invoke('lock_credentials');
+  },
+
   async checkBasicPermissions(credentials: AwsCredentials): Promise<PermissionCheck[]> {
     return invoke('check_basic_permissions', { credentials });
   }
@@ -213,6 +530,10 @@ export const ConfigOperations = {
     return invoke('get_config');
   },
 
+  async getConfigRecovery(): Promise<ConfigRecovery | null> {
+    return invoke('get_config_recovery');
+  },
+
   async setConfig(config: AppConfig): Promise<void> {
     return invoke('set_config', { config });
   },
@@ -247,6 +568,247 @@ export const StateOperations = {
 
   async updateSystemStats(): Promise<SystemStatus> {
     return invoke('update_system_stats');
+  },
+
+  async getStartupDiagnostics(): Promise<StartupDiagnostics | null> {
+    return invoke('get_startup_diagnostics');
+  },
+
+  async restoreMetadataDbBackup(): Promise<StartupCheck> {
+    return invoke('restore_metadata_db_backup');
+  },
+
+  async getMetadataDbRecovery(): Promise<MetadataDbRecovery | null> {
+    return invoke('get_metadata_db_recovery');
+  },
+
+  async getStartupProgress(): Promise<StartupProgress> {
+    return invoke('get_startup_progress');
+  }
+};
+
+// ===== 自動アーカイブAPI =====
+
+export const AutoArchiveOperations = {
+  async enableAutoArchive(watchConfig: WatchConfig, uploadSettings: AutoArchiveUploadSettings): Promise<AutoArchiveStatus> {
+    return invoke('enable_auto_archive', { watchConfig, uploadSettings });
+  },
+
+  async disableAutoArchive(): Promise<AutoArchiveStatus> {
+    return invoke('disable_auto_archive');
+  },
+
+  async getAutoArchiveStatus(): Promise<AutoArchiveStatus> {
+    return invoke('get_auto_archive_status');
+  }
+};
+
+// ===== 取り込みフォルダAPI =====
+
+export const HotFolderOperations = {
+  async configureHotFolder(path: string, s3Prefix: string, deleteAfterUpload: boolean, moveTo?: string): Promise<HotFolderStatus> {
+    return invoke('configure_hot_folder', { path, s3Prefix, deleteAfterUpload, moveTo });
+  },
+
+  async disableHotFolder(): Promise<HotFolderStatus> {
+    return invoke('disable_hot_folder');
+  },
+
+  async getHotFolderStatus(): Promise<HotFolderStatus> {
+    return invoke('get_hot_folder_status');
+  }
+};
+
+// ===== アプリのデータ使用量API =====
+
+export const AppDataOperations = {
+  async getAppDataUsage(): Promise<AppDataUsage> {
+    return invoke('get_app_data_usage');
+  },
+
+  async cleanupAppData(categories: AppDataCategory[], olderThanDays?: number): Promise<AppDataCleanupResult> {
+    return invoke('cleanup_app_data', { categories, olderThanDays });
+  }
+};
+
+// ===== ローカル原本のリテンションAPI =====
+
+export const RetentionOperations = {
+  async getPendingRetentionCleanup(): Promise<UploadRecord[]> {
+    return invoke('get_pending_retention_cleanup');
+  },
+
+  async confirmRetentionCleanup(filePaths: string[]): Promise<RetentionCleanupResult> {
+    return invoke('confirm_retention_cleanup', { filePaths });
+  },
+
+  async dismissRetentionCleanup(): Promise<number> {
+    return invoke('dismiss_retention_cleanup');
+  }
+};
+
+// ===== 操作の監査ログAPI =====
+
+export const AuditOperations = {
+  async getAuditLog(filter?: AuditLogFilter, limit?: number, offset?: number): Promise<OperationAuditEntry[]> {
+    return invoke('get_audit_log', { filter, limit, offset });
+  },
+
+  async exportAuditLog(format: AuditExportFormat, outputPath: string, filter?: AuditLogFilter): Promise<number> {
+    return invoke('export_audit_log', { format, outputPath, filter });
+  }
+};
+
+// ===== モックAWSモードAPI =====
+
+export const MockAwsOperations = {
+  async getMockAwsStatus(): Promise<MockAwsStatus> {
+    return invoke('get_mock_aws_status');
+  }
+};
+
+// ===== ライセンスAPI =====
+
+export const LicenseOperations = {
+  async activateLicense(key: string): Promise<LicenseStatus> {
+    return invoke('activate_license', { key });
+  },
+
+  async getLicenseStatus(): Promise<LicenseStatus> {
+    return invoke('get_license_status');
+  }
+};
+
+// ===== メトリクスAPI =====
+
+export const MetricsOperations = {
+  async getMetrics(): Promise<MetricsSnapshot> {
+    return invoke('get_metrics');
+  },
+
+  async resetMetrics(): Promise<void> {
+    return invoke('reset_metrics');
+  },
+
+  async getCommandPerformanceStats(): Promise<CommandPerformance[]> {
+    return invoke('get_command_performance_stats');
+  }
+};
+
+// ===== S3リクエスト数・概算料金API =====
+
+export const ApiUsageOperations = {
+  async getApiUsageSummary(period: ApiUsagePeriod): Promise<ApiUsageSummary> {
+    return invoke('get_api_usage_summary', { period });
+  }
+};
+
+// ===== 転送量の月次上限API =====
+
+export const TransferCapOperations = {
+  async getTransferUsage(): Promise<TransferUsage> {
+    return invoke('get_transfer_usage');
+  },
+
+  async overrideTransferCap(): Promise<TransferUsage> {
+    return invoke('override_transfer_cap');
+  }
+};
+
+// ===== フロントエンドログ（統合ログ）API =====
+
+export const FrontendLogOperations = {
+  async logFrontendEvent(level: FrontendLogLevel, message: string, context?: unknown): Promise<FrontendLogResult> {
+    return invoke('log_frontend_event', { level, message, context });
+  }
+};
+
+// ===== ヘルスレポートAPI =====
+
+export const HealthReportOperations = {
+  async getHealthReports(limit?: number): Promise<HealthReport[]> {
+    return invoke('get_health_reports', { limit });
+  }
+};
+
+// ===== クイックアップロードAPI =====
+
+export const QuickUploadOperations = {
+  async setQuickUploadSettings(settings: QuickUploadSettings): Promise<QuickUploadStatus> {
+    return invoke('set_quick_upload_settings', { settings });
+  },
+
+  async getQuickUploadStatus(): Promise<QuickUploadStatus> {
+    return invoke('get_quick_upload_status');
+  }
+};
+
+// ===== ドラッグ＆ドロップAPI =====
+
+export const DragDropOperations = {
+  async confirmDroppedFiles(accept: boolean): Promise<string | null> {
+    return invoke('confirm_dropped_files', { accept });
+  }
+};
+
+// ===== 自動アップロードの承認待ちAPI =====
+
+export const AutoUploadGuardOperations = {
+  async listPendingAutoUploads(): Promise<PendingAutoUpload[]> {
+    return invoke('list_pending_auto_uploads');
+  },
+
+  async approvePendingAutoUploads(filePaths?: string[]): Promise<string | null> {
+    return invoke('approve_pending_auto_uploads', { filePaths });
+  },
+
+  async discardPendingAutoUploads(filePaths?: string[]): Promise<number> {
+    return invoke('discard_pending_auto_uploads', { filePaths });
+  }
+};
+
+// ===== 整合性検証API =====
+
+export const RemoteVerifyOperations = {
+  async verifyRemoteIntegrity(config: AwsConfig, scope: VerifyScope = { type: 'all' }): Promise<RemoteVerifyReport> {
+    return invoke('verify_remote_integrity', { config, scope });
+  }
+};
+
+// ===== 差分同期API =====
+
+export const SyncOperations = {
+  async compareLocalWithS3(localDir: string, config: AwsConfig, s3Prefix: string): Promise<FileComparison[]> {
+    return invoke('compare_local_with_s3', { localDir, config, s3Prefix });
+  },
+
+  async syncDirectoryToS3(localDir: string, config: AwsConfig, s3Prefix: string, options: SyncOptions = {}): Promise<SyncSummary> {
+    return invoke('sync_directory_to_s3', { localDir, config, s3Prefix, options });
+  }
+};
+
+// ===== 長時間操作の中断API =====
+
+export const OperationControl = {
+  // 操作IDは operation-started イベントで通知される（未知のIDは false）
+  async cancelOperation(operationId: string): Promise<boolean> {
+    return invoke('cancel_operation', { operationId });
+  },
+
+  async listOperations(): Promise<OperationInfo[]> {
+    return invoke('list_operations');
+  }
+};
+
+// ===== 終了API =====
+
+export const QuitOperations = {
+  async confirmQuit(): Promise<void> {
+    return invoke('confirm_quit');
+  },
+
+  async forceQuit(): Promise<void> {
+    return invoke('force_quit');
   }
 };
 
@@ -259,6 +821,156 @@ export const EventListeners = {
     });
   },
 
+  async listenToDownloadProgress(callback: (progress: DownloadProgress) => void): Promise<() => void> {
+    return listen<DownloadProgress>('download-progress', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToOperationStarted(callback: (operation: OperationInfo) => void): Promise<() => void> {
+    return listen<OperationInfo>('operation-started', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToOperationFinished(callback: (finished: OperationFinished) => void): Promise<() => void> {
+    return listen<OperationFinished>('operation-finished', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToUploadQueuePauseChanged(callback: (state: UploadQueuePauseState) => void): Promise<() => void> {
+    return listen<UploadQueuePauseState>('upload-queue-pause-changed', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToQuitRequested(callback: (request: QuitRequest) => void): Promise<() => void> {
+    return listen<QuitRequest>('quit-requested', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToNetworkStatusChanged(callback: (change: NetworkStatusChange) => void): Promise<() => void> {
+    return listen<NetworkStatusChange>('network-status-changed', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToAutoArchiveStatusChanged(callback: (status: AutoArchiveStatus) => void): Promise<() => void> {
+    return listen<AutoArchiveStatus>('auto-archive-status-changed', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToRetentionCleanupPending(callback: (pending: RetentionCleanupPending) => void): Promise<() => void> {
+    return listen<RetentionCleanupPending>('retention-cleanup-pending', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToRetentionCleanupCompleted(callback: (result: RetentionCleanupResult) => void): Promise<() => void> {
+    return listen<RetentionCleanupResult>('retention-cleanup-completed', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToLifecyclePrefixWarning(callback: (warning: LifecyclePrefixWarning) => void): Promise<() => void> {
+    return listen<LifecyclePrefixWarning>('lifecycle-prefix-warning', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToStartupDiagnosticsFailed(callback: (diagnostics: StartupDiagnostics) => void): Promise<() => void> {
+    return listen<StartupDiagnostics>('startup-diagnostics-failed', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToWatcherRecovered(callback: (recovered: WatcherRecovered) => void): Promise<() => void> {
+    return listen<WatcherRecovered>('watcher-recovered', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToWatcherFailed(callback: (failed: WatcherFailed) => void): Promise<() => void> {
+    return listen<WatcherFailed>('watcher-failed', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToQueueAddProgress(callback: (progress: QueueAddProgress) => void): Promise<() => void> {
+    return listen<QueueAddProgress>('queue-add-progress', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToObjectMetadataProgress(callback: (progress: ObjectMetadataProgress) => void): Promise<() => void> {
+    return listen<ObjectMetadataProgress>('object-metadata-progress', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToQueueDuplicateProgress(callback: (progress: QueueDuplicateProgress) => void): Promise<() => void> {
+    return listen<QueueDuplicateProgress>('queue-duplicate-progress', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToCommandTiming(callback: (timing: CommandTiming) => void): Promise<() => void> {
+    return listen<CommandTiming>('command-timing', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToMetadataDbRecovered(callback: (recovery: MetadataDbRecovery) => void): Promise<() => void> {
+    return listen<MetadataDbRecovery>('metadata-db-recovered', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToTransferCapWarning(callback: (alert: TransferCapAlert) => void): Promise<() => void> {
+    return listen<TransferCapAlert>('transfer-cap-warning', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToUploadStalled(callback: (stall: UploadStalledEvent) => void): Promise<() => void> {
+    return listen<UploadStalledEvent>('upload-stalled', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToHotFolderStatus(callback: (status: HotFolderStatus) => void): Promise<() => void> {
+    return listen<HotFolderStatus>('hot-folder-status-changed', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToConfigRecovered(callback: (recovery: ConfigRecovery) => void): Promise<() => void> {
+    return listen<ConfigRecovery>('config-recovered', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToStartupPhaseCompleted(callback: (completed: StartupPhaseCompleted) => void): Promise<() => void> {
+    return listen<StartupPhaseCompleted>('startup-phase-completed', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToRestoreNotification(callback: (notification: RestoreNotification) => void): Promise<() => void> {
+    return listen<RestoreNotification>('restore-notification', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async listenToAutoUploadThrottled(callback: (throttled: AutoUploadThrottled) => void): Promise<() => void> {
+    return listen<AutoUploadThrottled>('auto-upload-throttled', (event) => {
+      callback(event.payload);
+    });
+  },
+
   async listenToTestEvent(callback: (event: any) => void): Promise<() => void> {
     return listen('test-event', callback);
   }
@@ -271,12 +983,20 @@ export const TauriCommands = {
   listFiles: FileOperations.listFiles,
   getFileInfo: FileOperations.getFileInfo,
   openFileDialog: FileOperations.openFileDialog,
+  revealInFinder: FileOperations.revealInFinder,
+  openInFinder: FileOperations.openInFinder,
+  unlockLocalFile: FileOperations.unlockLocalFile,
+  openS3ConsoleUrl: FileOperations.openS3ConsoleUrl,
 
   // AWS操作
   testS3BucketAccess: AwsOperations.testS3BucketAccess,
   listS3Objects: AwsOperations.listS3Objects,
+  updateS3ObjectsMetadata: AwsOperations.updateS3ObjectsMetadata,
   getS3Object: AwsOperations.getS3Object,
   downloadS3File: AwsOperations.downloadS3File,
+  getBucketVersioningStatus: AwsOperations.getBucketVersioningStatus,
+  listObjectVersions: AwsOperations.listObjectVersions,
+  checkArchiveAccess: AwsOperations.checkArchiveAccess,
   downloadRestoredFile: AwsOperations.downloadRestoredFile,
 
   // アップロード
@@ -285,16 +1005,52 @@ export const TauriCommands = {
   startUploadProcessing: UploadOperations.startUploadProcessing,
   stopUploadProcessing: UploadOperations.stopUploadProcessing,
   clearUploadQueue: UploadOperations.clearUploadQueue,
+  getNamingPatternPresets: UploadOperations.getNamingPatternPresets,
   getUploadQueueItems: UploadOperations.getUploadQueueItems,
   getUploadQueueStatus: UploadOperations.getUploadQueueStatus,
   retryUploadItem: UploadOperations.retryUploadItem,
   removeUploadItem: UploadOperations.removeUploadItem,
+  removeUploadItems: UploadOperations.removeUploadItems,
+  retryUploadItems: UploadOperations.retryUploadItems,
+  analyzeQueueDuplicates: UploadOperations.analyzeQueueDuplicates,
+  excludeQueueDuplicates: UploadOperations.excludeQueueDuplicates,
+  updateUploadItem: UploadOperations.updateUploadItem,
+  retryMirror: UploadOperations.retryMirror,
+  setUploadItemNote: UploadOperations.setUploadItemNote,
+  pauseUploadQueue: UploadOperations.pauseUploadQueue,
+  resumeUploadQueue: UploadOperations.resumeUploadQueue,
+  exportUploadQueue: UploadOperations.exportUploadQueue,
+  importUploadQueue: UploadOperations.importUploadQueue,
+  archiveUploadHistory: UploadOperations.archiveUploadHistory,
+  getUploadHistory: UploadOperations.getUploadHistory,
+
+  // プロジェクト（案件）
+  createProject: ProjectOperations.createProject,
+  listProjects: ProjectOperations.listProjects,
+  assignFilesToProject: ProjectOperations.assignFilesToProject,
+  getProjectSummary: ProjectOperations.getProjectSummary,
+  deleteProject: ProjectOperations.deleteProject,
+  findFileInBundles: UploadOperations.findFileInBundles,
+  getMetadataJobStatus: UploadOperations.getMetadataJobStatus,
 
   // 復元
   restoreFile: RestoreOperations.restoreFile,
+  restoreFilesBulk: RestoreOperations.restoreFilesBulk,
+  restorePrefix: RestoreOperations.restorePrefix,
+  listPrefixRestoreJobs: RestoreOperations.listPrefixRestoreJobs,
+  cancelPrefixRestore: RestoreOperations.cancelPrefixRestore,
+  listRestoredFiles: RestoreOperations.listRestoredFiles,
+  extendRestoredFileRetention: RestoreOperations.extendRestoredFileRetention,
+
+  // 長時間操作の中断
+  cancelOperation: OperationControl.cancelOperation,
+  listOperations: OperationControl.listOperations,
   checkRestoreStatus: RestoreOperations.checkRestoreStatus,
   listRestoreJobs: RestoreOperations.listRestoreJobs,
   getRestoreNotifications: RestoreOperations.getRestoreNotifications,
+  markNotificationRead: RestoreOperations.markNotificationRead,
+  markAllNotificationsRead: RestoreOperations.markAllNotificationsRead,
+  deleteNotification: RestoreOperations.deleteNotification,
   clearRestoreHistory: RestoreOperations.clearRestoreHistory,
 
   // ライフサイクル
@@ -302,15 +1058,22 @@ export const TauriCommands = {
   listLifecycleRules: LifecycleOperations.listLifecycleRules,
   enableReelvaultLifecycle: LifecycleOperations.enableReelvaultLifecycle,
   validateLifecycleConfig: LifecycleOperations.validateLifecycleConfig,
+  initializeBucketForReelvault: LifecycleOperations.initializeBucketForReelvault,
+  checkBucketSecurity: LifecycleOperations.checkBucketSecurity,
+  checkArchiveTransitionStatus: LifecycleOperations.checkArchiveTransitionStatus,
 
   // 認証
   authenticateAws: AuthOperations.authenticateAws,
   saveAwsCredentialsSecure: AuthOperations.saveAwsCredentialsSecure,
   loadAwsCredentialsSecure: AuthOperations.loadAwsCredentialsSecure,
+  getCachedCredentialsStatus: AuthOperations.getCachedCredentialsStatus,
+  unlockCredentials: AuthOperations.unlockCredentials,
+  lockCredentials: AuthOperations.lockCredentials,
   checkBasicPermissions: AuthOperations.checkBasicPermissions,
 
   // 設定
   getConfig: ConfigOperations.getConfig,
+  getConfigRecovery: ConfigOperations.getConfigRecovery,
   setConfig: ConfigOperations.setConfig,
   updateConfig: ConfigOperations.updateConfig,
   resetConfig: ConfigOperations.resetConfig,
@@ -320,26 +1083,122 @@ export const TauriCommands = {
   // 状態
   getAppState: StateOperations.getAppState,
   updateAppState: StateOperations.updateAppState,
-  updateSystemStats: StateOperations.updateSystemStats
+  updateSystemStats: StateOperations.updateSystemStats,
+  getStartupDiagnostics: StateOperations.getStartupDiagnostics,
+  restoreMetadataDbBackup: StateOperations.restoreMetadataDbBackup,
+  getMetadataDbRecovery: StateOperations.getMetadataDbRecovery,
+  getStartupProgress: StateOperations.getStartupProgress,
+
+  // 自動アーカイブ
+  enableAutoArchive: AutoArchiveOperations.enableAutoArchive,
+  disableAutoArchive: AutoArchiveOperations.disableAutoArchive,
+  getAutoArchiveStatus: AutoArchiveOperations.getAutoArchiveStatus,
+
+  // ローカル原本のリテンション
+  getPendingRetentionCleanup: RetentionOperations.getPendingRetentionCleanup,
+  confirmRetentionCleanup: RetentionOperations.confirmRetentionCleanup,
+  dismissRetentionCleanup: RetentionOperations.dismissRetentionCleanup,
+
+  // 操作の監査ログ
+  getAuditLog: AuditOperations.getAuditLog,
+  exportAuditLog: AuditOperations.exportAuditLog,
+
+  // モックAWSモード
+  getMockAwsStatus: MockAwsOperations.getMockAwsStatus,
+
+  // ライセンス
+  activateLicense: LicenseOperations.activateLicense,
+  getLicenseStatus: LicenseOperations.getLicenseStatus,
+
+  // メトリクス
+  getMetrics: MetricsOperations.getMetrics,
+  resetMetrics: MetricsOperations.resetMetrics,
+  getCommandPerformanceStats: MetricsOperations.getCommandPerformanceStats,
+
+  // S3リクエスト数・概算料金
+  getApiUsageSummary: ApiUsageOperations.getApiUsageSummary,
+
+  // 転送量の月次上限
+  getTransferUsage: TransferCapOperations.getTransferUsage,
+  overrideTransferCap: TransferCapOperations.overrideTransferCap,
+
+  // フロントエンドログ（統合ログ）
+  logFrontendEvent: FrontendLogOperations.logFrontendEvent,
+
+  // ヘルスレポート
+  getHealthReports: HealthReportOperations.getHealthReports,
+
+  // 取り込みフォルダ
+  configureHotFolder: HotFolderOperations.configureHotFolder,
+  disableHotFolder: HotFolderOperations.disableHotFolder,
+  getHotFolderStatus: HotFolderOperations.getHotFolderStatus,
+
+  // アプリのデータ使用量
+  getAppDataUsage: AppDataOperations.getAppDataUsage,
+  cleanupAppData: AppDataOperations.cleanupAppData,
+
+  // クイックアップロード
+  setQuickUploadSettings: QuickUploadOperations.setQuickUploadSettings,
+  getQuickUploadStatus: QuickUploadOperations.getQuickUploadStatus,
+
+  // ドラッグ＆ドロップ
+  confirmDroppedFiles: DragDropOperations.confirmDroppedFiles,
+
+  // 自動アップロードの承認待ち
+  listPendingAutoUploads: AutoUploadGuardOperations.listPendingAutoUploads,
+  approvePendingAutoUploads: AutoUploadGuardOperations.approvePendingAutoUploads,
+  discardPendingAutoUploads: AutoUploadGuardOperations.discardPendingAutoUploads,
+
+  // 整合性検証
+  verifyRemoteIntegrity: RemoteVerifyOperations.verifyRemoteIntegrity,
+
+  // 差分同期
+  compareLocalWithS3: SyncOperations.compareLocalWithS3,
+  syncDirectoryToS3: SyncOperations.syncDirectoryToS3,
+
+  // 終了
+  confirmQuit: QuitOperations.confirmQuit,
+  forceQuit: QuitOperations.forceQuit
 };
 
 // 型エクスポート（後方互換性のため）
 export type {
   FileInfo,
+  OpenInFinderResult,
   WatchConfig,
   AwsConfig,
   ConnectionTestResult,
+  FieldChanges,
+  ObjectMetadataUpdateReport,
+  ObjectMetadataProgress,
   S3Object,
+  S3ObjectVersion,
+  BucketVersioningStatus,
+  ArchiveGuardOptions,
+  ArchiveAccessCheck,
   UploadProgress,
   RestoreInfo,
+  BulkRestoreResult,
+  PrefixRestoreEstimate,
+  PrefixRestoreJob,
+  PrefixRestoreResponse,
+  RestoredFile,
+  OperationInfo,
+  OperationFinished,
   RestoreStatusResult,
   DownloadProgress,
   RestoreNotification,
   LifecyclePolicyResult,
   LifecyclePolicyStatus,
+  BucketInitReport,
+  BucketSecurityReport,
+  ArchiveTransitionStatus,
   LifecycleRule,
   LifecycleTransition,
+  LifecyclePrefixWarning,
+  UploadReadinessResult,
   AwsCredentials,
+  CredentialCacheStatus,
   AwsAuthResult,
   AwsUserIdentity,
   PermissionCheck,
@@ -351,12 +1210,92 @@ export type {
   ConfigUpdate,
   AppState,
   UploadItem,
+  BatchOperationResult,
+  UploadQueueChanged,
   UploadStatistics,
   FileSelection,
   UploadConfig,
   UploadProgressInfo,
   S3KeyConfig,
+  NamingPatternPreset,
   AppStatistics,
   SystemStatus,
-  StateUpdate
+  StateUpdate,
+  RetentionMode,
+  RetentionAction,
+  RetentionSettings,
+  UploadRecord,
+  RetentionCleanupPending,
+  RetentionCleanupEntry,
+  RetentionCleanupResult,
+  AuditOperation,
+  AuditExportFormat,
+  OperationAuditEntry,
+  AuditLogFilter,
+  AutoArchiveHealth,
+  AutoArchiveUploadSettings,
+  AutoArchiveStatus,
+  BundleOptions,
+  BundleLookupResult,
+  MetadataJobStatus,
+  QueueImportMode,
+  QueueImportResult,
+  UploadHistory,
+  UploadHistoryArchiveResult,
+  UploadHistoryEntry,
+  Project,
+  NewProject,
+  ProjectSummary,
+  ProjectAssignmentResult,
+  StorageClassUsage,
+  CrossRegionWarning,
+  StartupCheck,
+  StartupDiagnostics,
+  ConfigRecovery,
+  MetadataDbRecovery,
+  StartupProgress,
+  StartupPhaseCompleted,
+  QueueAddProgress,
+  WatcherRecovered,
+  WatcherFailed,
+  QueueDuplicateProgress,
+  QueueDuplicateReport,
+  NetworkStatusChange,
+  MockAwsStatus,
+  LicenseStatus,
+  TierPolicy,
+  MetricsSnapshot,
+  ChecksumMetricsSnapshot,
+  ChecksumAlgorithm,
+  CommandPerformance,
+  CommandTiming,
+  ApiUsagePeriod,
+  ApiUsageSummary,
+  TransferUsage,
+  TransferCapAlert,
+  UploadStalledEvent,
+  HotFolderAfterUpload,
+  HotFolderSettings,
+  HotFolderError,
+  HotFolderStatus,
+  AppDataCategory,
+  AppDataCleanupSettings,
+  AppDataCategoryUsage,
+  AppDataUsage,
+  AppDataCleanupCategoryResult,
+  AppDataCleanupResult,
+  FrontendLogLevel,
+  FrontendLogResult,
+  HealthReport,
+  QuickUploadSettings,
+  QuickUploadStatus,
+  DroppedFiles,
+  PendingAutoUpload,
+  AutoUploadThrottled,
+  VerifyScope,
+  RemoteVerifyReport,
+  SyncOptions,
+  FileComparison,
+  SyncSummary,
+  QuitRequest
 }; 
\ No newline at end of file
@@ -5,8 +5,7 @@ import {
   UploadStatistics, 
   FileSelection, 
   S3KeyConfig,
-  UploadProgressInfo,
-  AwsCredentials 
+  UploadProgressInfo
 } from '../types/tauri-commands';
 import { listen } from '@tauri-apps/api/event';
 
@@ -230,19 +229,16 @@ export class UploadService {
   }
 
   /**
-   * 統一されたアップロード設定を生成
-   * @param awsCredentials AWS認証情報
+   * 統一されたアップロード設定を生成（認証情報はバックエンドのキャッシュを使用）
    * @param bucketName S3バケット名
    * @param tier アップロード機能ティア（デフォルト: Premium）
    */
   public static createDefaultConfig(
-    awsCredentials: AwsCredentials,
     bucketName: string,
     tier: 'Free' | 'Premium' = 'Premium'
   ): UploadConfig {
     // 基本設定（共通）
     const baseConfig = {
-      aws_credentials: awsCredentials,
       bucket_name: bucketName,
       auto_create_metadata: true,
       s3_key_prefix: 'uploads',
@@ -22,6 +22,7 @@ export interface FileMetadata {
   video_metadata?: VideoMetadata;
   tags: string[];
   custom_fields: Record<string, string>;
+  project_id?: string; // 所属するプロジェクト
 }
 
 export interface MetadataSearchQuery {
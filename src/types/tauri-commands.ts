@@ -4,11 +4,18 @@ export interface FileInfo {
   name: string;
   path: string;
   size: number;
-  modified: string;
+  modified?: string; // RFC3339（取得できない場合は未設定）
+  created?: string; // RFC3339（取得できない場合は未設定）
   is_directory: boolean;
   extension?: string;
 }
 
+export interface OpenInFinderResult {
+  path: string;
+  opened_path?: string; // Finderで表示したパス（ファイルも親フォルダも無い場合は未設定）
+  file_missing: boolean; // ローカルの実体が無い（復元フローへ誘導する）
+}
+
 export interface WatchConfig {
   path: string;
   recursive: boolean;
@@ -18,13 +25,75 @@ export interface WatchConfig {
   exclude_patterns: string[]; // 除外パターン (例: ["*.tmp", "*/.DS_Store"])
   exclude_directories: string[]; // 除外ディレクトリ
   auto_metadata: boolean; // 自動メタデータ作成
+  rename_completion_detection?: boolean; // 一時ファイル→最終名へのリネームを待ってから処理
+  temp_extensions?: string[]; // 最終リネームを待つ一時ファイルの拡張子
+  force_polling?: boolean; // ポーリングで監視（ネットワークボリュームは自動でポーリング）
+  poll_interval_secs?: number; // ポーリング間隔（既定 5秒）
+  follow_symlinks?: boolean; // false でシンボリックリンクを無視（既定 true）
+  max_auto_uploads_per_hour?: number | null; // 直近1時間に自動で積む件数の上限（超過分は承認待ち）
+  max_auto_upload_bytes_per_day?: number | null; // 直近24時間に自動で積む合計バイト数の上限
+  use_default_excludes?: boolean; // システム定義の除外セットを exclude_patterns / exclude_directories と併せて適用（既定 true）
+}
+
+// use_default_excludes で自動的に除外されるパターン・フォルダ
+export interface DefaultExcludePatterns {
+  patterns: string[];
+  directories: string[]; // パスに含まれていれば除外
+}
+
+export type WatcherState = 'watching' | 'recovering' | 'error';
+
+export interface WatcherStatus {
+  path: string;
+  state: WatcherState; // error: 作り直しが上限回数失敗したため監視を停止（watchDirectory で再開）
+  mode?: 'native' | 'polling';
+  recovery_attempts: number;
+  last_error?: string;
+  updated_at: string;
+}
+
+// "watcher-recovered" イベントのペイロード
+export interface WatcherRecovered {
+  path: string;
+  attempts: number;
+  mode: 'native' | 'polling';
+}
+
+// "watcher-failed" イベントのペイロード
+export interface WatcherFailed {
+  path: string;
+  attempt: number;
+  error: string;
+  retry_in_secs?: number; // 次の作り直しまでの秒数（gave_up時はなし）
+  gave_up: boolean;
+}
+
+export interface WatchConfigPreset {
+  id: string; // 'video_production' | 'photo_and_video' | 'careful_all_files'
+  name: string;
+  description: string;
+  config: WatchConfig;
+}
+
+export type AutoUploadThrottleReason = 'hourly_count' | 'daily_bytes';
+
+export interface PendingAutoUpload {
+  file_path: string;
+  file_size: number;
+  watch_path: string;
+  detected_at: string;
+  reason: AutoUploadThrottleReason;
+}
+
+// "auto-upload-throttled" イベントのペイロード
+export interface AutoUploadThrottled {
+  item: PendingAutoUpload;
+  pending_count: number; // 一覧は listPendingAutoUploads で取得する
 }
 
 // ===== AWS操作API関連の型定義 =====
 
 export interface AwsConfig {
-  access_key_id: string;
-  secret_access_key: string;
   region: string;
   bucket_name: string;
 }
@@ -35,6 +104,37 @@ export interface ConnectionTestResult {
   bucket_accessible: boolean;
 }
 
+// タグ・メタデータの変更（null は削除、未指定のキーは現在の値を維持）
+export type FieldChanges = Record<string, string | null>;
+
+export type FieldUpdateOutcome =
+  | { status: 'updated' }
+  | { status: 'unchanged' }
+  | { status: 'skipped'; reason: string } // DEEP_ARCHIVE などでメタデータを自己コピーできない
+  | { status: 'failed'; error: string };
+
+export interface ObjectMetadataUpdateResult {
+  key: string;
+  metadata: FieldUpdateOutcome;
+  tags: FieldUpdateOutcome;
+}
+
+export interface ObjectMetadataUpdateReport {
+  results: ObjectMetadataUpdateResult[];
+  updated: number;
+  skipped: number;
+  failed: number;
+}
+
+// object-metadata-progress イベントのペイロード
+export interface ObjectMetadataProgress {
+  operation_id: string; // cancel_operation に渡すと中断
+  processed: number;
+  total: number;
+  failed: number;
+  key: string;
+}
+
 export interface S3Object {
   key: string;
   size: number;
@@ -43,6 +143,86 @@ export interface S3Object {
   etag: string;
 }
 
+// S3一覧のローカルキャッシュ（local_path はこのキーへアップロードしたローカルファイル）
+export interface CachedS3Object {
+  key: string;
+  size: number;
+  storage_class: string;
+  last_modified: string;
+  etag: string;
+  fetched_at: string;
+  local_path: string | null;
+  local_removed_at: string | null;
+}
+
+export interface S3CacheFilter {
+  query?: string;
+  storage_class?: string;
+  has_local_copy?: boolean;
+  limit?: number;
+}
+
+export interface S3CacheBrowseResult {
+  bucket: string;
+  prefix: string;
+  objects: CachedS3Object[];
+  last_synced_at: string | null;
+}
+
+export interface S3CacheRefreshResult {
+  bucket: string;
+  prefix: string;
+  added: number;
+  updated: number;
+  removed: number;
+  total_objects: number;
+  last_synced_at: string;
+}
+
+export interface S3ObjectVersion {
+  key: string;
+  version_id: string;
+  size: number; // 削除マーカーは0
+  last_modified: string;
+  is_latest: boolean;
+  is_delete_marker: boolean; // 削除マーカーはダウンロード・復元できない
+  storage_class?: string;
+  etag?: string;
+}
+
+export interface BucketVersioningStatus {
+  bucket: string;
+  status: 'Enabled' | 'Suspended' | 'Disabled';
+  enabled: boolean;
+  message: string;
+}
+
+// アーカイブ済み（DEEP_ARCHIVE/GLACIER）オブジェクトの事前ガード
+export interface ArchiveGuardOptions {
+  enabled?: boolean; // 既定 true
+  auto_restore?: boolean; // 未復元の場合に復元リクエストまで自動実行
+  restore_tier?: 'Standard' | 'Expedited' | 'Bulk';
+  restore_days?: number; // 自動復元の保持日数（既定7日）
+}
+
+export type ArchiveAccessCheck =
+  | { status: 'available' }
+  | { status: 'restore_required'; storage_class: string; message: string }
+  | {
+      status: 'restore_in_progress';
+      storage_class: string;
+      requested_at?: string | null;
+      estimated_completion?: string | null; // RFC3339
+      message: string;
+    }
+  | {
+      status: 'restore_requested';
+      storage_class: string;
+      restore: RestoreInfo;
+      estimated_completion?: string | null; // RFC3339
+      message: string;
+    };
+
 export interface UploadProgress {
   uploaded_bytes: number;
   total_bytes: number;
@@ -52,11 +232,97 @@ export interface UploadProgress {
 
 export interface RestoreInfo {
   key: string;
-  restore_status: string; // "in-progress", "completed", "failed", "cancelled"
+  restore_status: string; // "in-progress", "completed", "failed", "cancelled", "expired"
   expiry_date?: string;
   tier: string; // "Standard", "Expedited", "Bulk"
   request_time: string;
   completion_time?: string;
+  local_path?: string; // ダウンロード済みの保存先
+  version_id?: string; // 特定バージョンを復元した場合
+  restore_days?: number; // 復元済みコピーの保持日数
+  note?: string; // 例: Expedited不可のためStandardで実行
+}
+
+// 一括復元の結果
+export interface BulkRestoreFailure {
+  key: string;
+  error: string;
+}
+
+export interface BulkRestoreResult {
+  days: number;
+  requested: RestoreInfo[];
+  failed: BulkRestoreFailure[];
+}
+
+// プレフィックス（フォルダ）単位の復元
+export interface PrefixRestoreEstimate {
+  prefix: string;
+  tier: string;
+  days: number;
+  object_count: number;
+  total_size: number;
+  estimated_cost_usd: number; // 取り出し料金とリクエスト料金の合計
+  skipped_count: number; // 復元不要のため対象外にしたオブジェクト数
+  cross_region_warning?: CrossRegionWarning; // 復元後のダウンロードがクロスリージョンになる場合
+}
+
+export type PrefixRestoreStatus = 'requesting' | 'waiting' | 'completed';
+
+// "restore-prefix-completed" イベントのペイロードにもなる
+export interface PrefixRestoreJob {
+  prefix: string;
+  bucket: string;
+  tier: string;
+  days: number;
+  status: PrefixRestoreStatus;
+  total: number;
+  requested: string[];
+  failed: BulkRestoreFailure[];
+  completed: number;
+  not_issued: number; // キャンセルにより発行しなかったオブジェクト数
+  cancelled: boolean;
+  started_at: string;
+  completed_at?: string;
+}
+
+export interface PrefixRestoreResponse {
+  estimate: PrefixRestoreEstimate;
+  job?: PrefixRestoreJob; // confirm: true の場合のみ
+}
+
+// Restoredフォルダへダウンロードした作業コピー
+export interface RestoredFile {
+  local_path: string;
+  s3_key: string;
+  bucket: string;
+  downloaded_at: string;
+  expires_at: string; // この日時を過ぎると日次タスクでゴミ箱へ移す
+}
+
+// 中断できる長時間操作（operation-started イベントのペイロード）
+export type OperationKind = 'download' | 'list_objects' | 'hash_calculation' | 'queue_add' | 'metadata_update';
+
+export interface OperationInfo {
+  operation_id: string; // cancel_operation に渡すID
+  kind: OperationKind;
+  label: string; // S3キー・ファイルパスなど
+  started_at: string;
+}
+
+// operation-finished イベントのペイロード
+export interface OperationFinished {
+  operation_id: string;
+  kind: OperationKind;
+  cancelled: boolean;
+}
+
+// queue-add-progress イベントのペイロード（add_files_to_upload_queue のバッチごと）
+export interface QueueAddProgress {
+  operation_id: string; // cancel_operation に渡すと追加を中断
+  processed: number;
+  total: number;
+  failed: number;
 }
 
 // 復元状況監視結果
@@ -80,10 +346,12 @@ export interface DownloadProgress {
 
 // 復元通知情報
 export interface RestoreNotification {
+  id: string;
   key: string;
   status: string; // "completed", "failed", "expired"
   message: string;
   timestamp: string;
+  read: boolean;
 }
 
 // ===== ライフサイクル管理API関連の型定義 =====
@@ -105,6 +373,23 @@ export interface LifecyclePolicyStatus {
   error_message?: string;
 }
 
+// バケット初期化
+export type BucketInitStep = 'access' | 'prefixes' | 'lifecycle' | 'versioning' | 'encryption';
+export type BucketInitStatus = 'applied' | 'already_configured' | 'warning' | 'failed' | 'skipped';
+
+export interface BucketInitStepResult {
+  step: BucketInitStep;
+  status: BucketInitStatus;
+  detail?: string;
+}
+
+export interface BucketInitReport {
+  bucket: string;
+  steps: BucketInitStepResult[];
+  completed: boolean;
+  failed_step?: BucketInitStep;
+}
+
 export interface LifecycleRule {
   id: string;
   status: string; // "Enabled" or "Disabled"
@@ -117,6 +402,80 @@ export interface LifecycleTransition {
   storage_class: string;
 }
 
+export interface LifecyclePrefixWarning {
+  uncovered_count: number;
+  total_count: number;
+  example_keys: string[];
+  covered_prefixes: string[]; // 空文字はバケット全体
+  message: string;
+}
+
+export interface UploadReadinessResult {
+  safe: boolean;
+  message: string;
+  lifecycle_healthy: boolean;
+  prefix_warning?: LifecyclePrefixWarning;
+  cross_region_warning?: CrossRegionWarning;
+  bucket_security?: BucketSecurityReport; // includeSecurity 指定時のみ（safe の判定には含めない）
+}
+
+export type BucketSecurityItem = 'public_access_block' | 'bucket_policy' | 'encryption' | 'versioning';
+
+export type BucketSecurityStatus = 'pass' | 'warning' | 'fail' | 'unknown'; // unknown は権限不足などで取得できなかった項目
+
+export interface BucketSecurityCheck {
+  item: BucketSecurityItem;
+  status: BucketSecurityStatus;
+  detail: string;
+}
+
+export interface BucketSecurityReport {
+  bucket: string;
+  checks: BucketSecurityCheck[];
+  score?: number | null; // 取得できた項目のみで算出した0〜100（すべて unknown の場合は null）
+  recommendations: string[];
+}
+
+export type TransitionState = 'on_schedule' | 'possibly_delayed' | 'not_covered';
+
+export type UncoveredReason = 'prefix_mismatch' | 'below_minimum_size'; // 最小サイズは128KB
+
+export interface UntransitionedObject {
+  key: string;
+  size: number;
+  storage_class: string;
+  last_modified: string;
+  age_days?: number | null;
+  transition_days?: number | null; // 一致したルールのうち最も早い移行日数
+  state: TransitionState;
+  reason?: UncoveredReason | null;
+}
+
+export interface ArchiveTransitionStatus {
+  bucket: string;
+  prefix?: string | null;
+  total_objects: number;
+  archived_objects: number;
+  archived_ratio: number; // 0.0〜1.0
+  storage_classes: Record<string, number>;
+  on_schedule_count: number;
+  delayed_count: number;
+  not_covered_count: number;
+  untransitioned: UntransitionedObject[]; // 遅延・対象外を先に、経過日数の長い順
+  checked_at: string;
+}
+
+// 設定リージョンとバケットのリージョンが異なる場合の警告
+export interface CrossRegionWarning {
+  bucket: string;
+  client_region: string;
+  bucket_region: string;
+  price_per_gb_usd: number;
+  transfer_bytes?: number;
+  estimated_extra_cost_usd?: number; // 転送量が分かる見積もりのみ
+  message: string;
+}
+
 // ===== AWS認証API関連の型定義 =====
 
 export interface AwsCredentials {
@@ -154,17 +513,146 @@ export interface AppConfig {
   app_settings: AppSettings;
   user_preferences: UserPreferences;
   aws_settings: AwsSettings;
+  notification_settings?: NotificationSettings;
+  local_retention_days?: number | null; // アップロード検証済みのローカル原本を削除するまでの日数
+  retention_settings?: RetentionSettings;
+  mock_aws_mode?: boolean; // AWSへ一切アクセスせずダミー応答を返すデモ・オフライン用モード
+  mock_aws_speed_mbps?: number; // モックAWSモードでシミュレートする転送速度（MB/s）
+  quick_upload?: QuickUploadSettings;
+  bandwidth_schedule?: BandwidthWindow[]; // 時間帯別の帯域制限（区間外は bandwidth_limit_mbps）
+  restored_files_dir?: string | null; // 復元した作業コピーの保存先（未設定はダウンロード/ReelVault Restored）
+  restored_retention_days?: number; // 作業コピーをゴミ箱へ移すまでの日数
+  monthly_transfer_cap_gb?: number | null; // アップロード・ダウンロード合計の月次上限（GB、未設定は無制限）
+  transfer_cap_action?: TransferCapAction; // 上限到達時の挙動
+  app_data_cleanup?: AppDataCleanupSettings; // ログ・サムネイル・一時ファイルなどの定期自動クリーンアップ
+}
+
+// 時間帯別の帯域制限（end が start より前なら日付を跨ぐ）
+export interface BandwidthWindow {
+  start: string; // "HH:MM"（ローカル時刻）
+  end: string;
+  limit_mbps?: number | null; // Mbps（メガビット毎秒）、null は無制限
+}
+
+export type BandwidthProfileSource = 'default' | 'schedule' | 'override';
+
+export interface BandwidthProfile {
+  limit_mbps?: number | null;
+  source: BandwidthProfileSource;
+  window?: BandwidthWindow | null;
+  override_until?: string | null; // トレイの「今だけ無制限」の終了日時
+}
+
+// ローカル原本のリテンション設定
+export type RetentionMode = 'confirm' | 'auto';
+export type RetentionAction = 'trash' | 'move_to_archive';
+
+export interface RetentionSettings {
+  mode: RetentionMode;
+  action: RetentionAction;
+  archive_dir?: string; // move_to_archive 時の移動先
+}
+
+export interface UploadRecord {
+  file_path: string;
+  bucket: string;
+  s3_key: string;
+  file_size: number;
+  uploaded_at: string;
+  verified: boolean;
+  local_removed_at?: string;
+  project_id?: string;
+  note?: string;
+  storage_class?: string; // アップロード検証時のストレージクラス
+  source_modified_at?: string; // アップロード時のローカル原本の更新日時
+  file_hash?: string;
+}
+
+export interface RetentionCleanupPending {
+  retention_days: number;
+  action: RetentionAction;
+  candidates: UploadRecord[];
+}
+
+export interface RetentionCleanupEntry {
+  file_path: string;
+  s3_key: string;
+  destination?: string;
+  success: boolean;
+  error_message?: string;
+}
+
+export interface RetentionCleanupResult {
+  processed_count: number;
+  failed_count: number;
+  skipped_count: number; // 実行時の再確認で対象から外れた件数
+  entries: RetentionCleanupEntry[];
+}
+
+// 操作の監査ログ
+export type AuditOperation = 'upload' | 'download' | 'restore_request' | 's3_delete' | 'local_delete' | 'remote_verify' | 'bucket_initialize' | 'mirror_upload';
+export type AuditExportFormat = 'csv' | 'json';
+
+export interface OperationAuditEntry {
+  id?: number;
+  operation: AuditOperation;
+  local_path?: string;
+  bucket?: string;
+  s3_key?: string;
+  success: boolean;
+  size_bytes?: number;
+  duration_ms?: number;
+  occurred_at: string;
+  error_message?: string;
+  project_id?: string;
+}
+
+export interface AuditLogFilter {
+  operation?: AuditOperation;
+  success?: boolean;
+  path_contains?: string; // ローカルパスまたはS3キーの部分一致
+  since?: string; // RFC3339
+  until?: string; // RFC3339
+}
+
+export interface NotificationSettings {
+  enabled: boolean;
+  notify_queue_completed: boolean;
+  notify_large_file_completed: boolean;
+  large_file_threshold_mb: number;
+  notify_restore_completed: boolean;
+  notify_download_completed: boolean;
+  notify_errors: boolean;
+  suppress_when_focused: boolean;
+  show_badge: boolean;
+  notify_health_issues: boolean;
+  health_report_time: string; // HH:MM（ローカル時刻）
 }
 
 export interface AppSettings {
   log_level: string;
   theme: string;
   language: string;
+  launch_at_login?: boolean;
+  prevent_sleep_during_transfer?: boolean; // 転送中のシステムスリープ抑止
+  metadata_job_concurrency?: number; // 検知ファイルのメタデータ作成の同時実行数（既定 1）
+  upload_history_retention_days?: number; // 完了・失敗から履歴へ移すまでの日数（既定 30）
+  upload_history_archive_limit?: number; // 1回のアーカイブで履歴へ移す最大件数（既定 1000）
+  slow_command_threshold_ms?: number; // スローコマンドとして警告する所要時間（既定 1000）
+  tray_recent_uploads_count?: number; // トレイの「最近のアップロード」の表示件数（既定 5、0で非表示、最大 20）
+  tray_recent_uploads_label?: TrayRecentUploadLabel;
 }
 
+// file_name: ファイル名のみ / with_key: ファイル名とS3キー
+export type TrayRecentUploadLabel = 'file_name' | 'with_key';
+
 export interface UserPreferences {
   default_bucket_name?: string;
   default_storage_class: string;
+  default_download_dir?: string; // 相対パス指定時のダウンロード先
+  expired_restore_retention_days?: number; // 期限切れの復元ジョブを残す日数
+  restore_notification_retention_days?: number; // 復元通知を残す日数（既定90日）
+  default_restore_days?: number; // 復元時の保持日数の既定値（1〜365日）
 }
 
 export interface AwsSettings {
@@ -172,6 +660,11 @@ export interface AwsSettings {
   timeout_seconds: number;
   max_retries: number;
   profile_name?: string;
+  credential_cache_hours?: number; // 認証情報をメモリに保持する時間（既定8時間）
+  unlock_credentials_on_startup?: boolean; // 起動時にKeychainから認証情報を読み込む
+  auto_correct_region?: boolean; // バケットのリージョンに合わせてクライアントを作り直す
+  suppress_cross_region_warning?: boolean;
+  warm_up_s3_connection?: boolean; // 認証成功時にS3への接続を確立しておく
 }
 
 export interface ConfigValidationResult {
@@ -193,6 +686,89 @@ export interface AppState {
   statistics: AppStatistics;
   last_error?: string;
   system_status: SystemStatus;
+  auto_archive_enabled: boolean; // 自動アーカイブが有効
+  license?: LicenseStatus; // 検証済みのライセンス状況（バックエンドのみが更新）
+  startup_diagnostics?: StartupDiagnostics; // 起動時の自己診断の結果
+}
+
+export type StartupCheckKind = 'logger' | 'config' | 'metadata_db' | 'app_data_dir' | 'keychain';
+
+export type StartupCheckStatus = 'ok' | 'recovered' | 'failed';
+
+export type StartupRecovery =
+  | { action: 'config_restored'; backup_path: string; moved_to: string | null }
+  | { action: 'config_safe_mode'; moved_to: string | null }
+  | { action: 'restore_metadata_db_backup'; backup_path: string; backup_modified_at?: string }
+  | { action: 'metadata_db_repaired'; corrupted_path: string; lost_records: number | null };
+
+// メタデータDBの修復（metadata-db-recovered イベント）。バックアップ→レコードの救出→作り直しの順に試す
+export type MetadataDbRecoveryAction =
+  | { action: 'restored_from_backup'; backup_path: string }
+  | { action: 'salvaged_records' }
+  | { action: 'recreated' };
+
+export interface MetadataDbRecovery {
+  action: MetadataDbRecoveryAction;
+  error: string; // integrity_check の結果
+  corrupted_path: string; // 壊れたDBの退避先
+  recovered_records: number;
+  lost_records: number | null; // 失われた可能性のあるレコード数（数えられない場合はnull）
+  incomplete_tables: string[]; // 一部のレコードしか移せなかったテーブル
+  recovered_at: string;
+}
+
+export interface MetadataDbInitResult {
+  message: string;
+  recovery: MetadataDbRecovery | null;
+}
+
+// 設定ファイルの復旧（config-recovered イベント）。safe_mode 中はAWS系の自動処理を開始しない
+export type ConfigRecoveryAction =
+  | { action: 'restored_from_backup'; backup_path: string }
+  | { action: 'safe_mode' };
+
+export interface ConfigRecovery {
+  action: ConfigRecoveryAction;
+  error: string;
+  corrupted_path: string | null;
+  rejected_backups: string[];
+  recovered_at: string;
+}
+
+export interface StartupCheck {
+  kind: StartupCheckKind;
+  status: StartupCheckStatus;
+  message: string;
+  recovery?: StartupRecovery;
+}
+
+export interface StartupDiagnostics {
+  checks: StartupCheck[];
+  checked_at: string;
+}
+
+// ウィンドウ表示後に実行する遅延初期化の段階（この順に実行）
+export type StartupPhase = 'restore_state' | 'license' | 'shortcuts' | 'schedulers';
+
+export interface StartupPhaseResult {
+  phase: StartupPhase;
+  success: boolean;
+  error?: string;
+  duration_ms: number;
+  completed_at: string;
+}
+
+export interface StartupProgress {
+  phases: StartupPhaseResult[];
+  total_phases: number;
+  finished: boolean;
+  failed_phase?: StartupPhase; // 最初に失敗した段階
+}
+
+// startup-phase-completedイベントのペイロード
+export interface StartupPhaseCompleted {
+  result: StartupPhaseResult;
+  progress: StartupProgress;
 }
 
 export interface UploadItem {
@@ -211,6 +787,133 @@ export interface UploadItem {
   completed_at?: string;
   error_message?: string;
   retry_count: number;
+  note?: string;       // 任意のメモ
+  project_id?: string; // 案件ID（x-amz-meta-project-idとして付与）
+  priority?: number;   // 大きいほど先に開始（既定0）
+  mirrors?: MirrorResult[]; // ミラー先ごとの結果（ミラー未設定時は空）
+  applied_override?: AppliedUploadOverride | null; // 追加時に適用したper_type_overridesのルール
+}
+
+// ファイル名パターンごとのアップロード設定の上書き（最初にマッチしたルールを使う）
+export interface UploadTypeOverride {
+  pattern: string; // ファイル名のglob（例: "*.mov"、"*_proxy.*"。大文字小文字は区別しない）
+  chunk_size_mb?: number | null;
+  priority?: number | null;
+  storage_class?: string | null; // STANDARD / STANDARD_IA / GLACIER_IR / DEEP_ARCHIVE など
+}
+
+export interface AppliedUploadOverride {
+  rule_index: number;
+  pattern: string;
+  chunk_size_mb: number | null; // ティアの上限に合わせた後の値
+  storage_class: string | null;
+  warnings: string[]; // ティアの上限・未対応の値のため補正した内容
+}
+
+// プライマリとは別にバックアップとしてアップロードするミラー先
+export interface MirrorTarget {
+  bucket: string;
+  region: string;
+  profile?: string | null; // 未設定はプライマリと同じ認証情報
+  prefix?: string | null;  // プライマリのS3キーの前に付ける
+}
+
+export type MirrorStatus = 'in_progress' | 'completed' | 'failed';
+
+export interface MirrorResult {
+  target: MirrorTarget;
+  s3_key: string;
+  status: MirrorStatus;
+  error?: string | null;
+  attempts: number;
+  completed_at?: string | null;
+}
+
+// 待機中アイテムの編集内容（指定しない項目はそのまま、noteは空文字で削除）
+export interface UploadItemChanges {
+  s3_key?: string;
+  priority?: number;
+  note?: string;
+}
+
+export interface UploadItemUpdateResult {
+  item: UploadItem;
+  lifecycle_warning?: LifecyclePrefixWarning;
+}
+
+export type BatchItemOutcome =
+  | 'removed'
+  | 'removed_with_abort' // 進行中だったためアップロードを中断して削除
+  | 'queued'
+  | 'not_found'
+  | 'in_progress' // 進行中のためリトライ不可
+  | 'skipped' // アップロードせずに除外
+  | 'not_pending'; // 開始済み・終了済みのため除外不可
+
+export interface BatchItemResult {
+  item_id: string;
+  success: boolean;
+  outcome: BatchItemOutcome;
+}
+
+export interface BatchOperationResult {
+  results: BatchItemResult[];
+  succeeded: number;
+  failed: number;
+}
+
+// "upload-queue-changed" イベントのペイロード
+export interface UploadQueueChanged {
+  reason: 'removed' | 'retried' | 'updated' | 'mirrored' | 'skipped';
+  item_ids: string[];
+}
+
+// queue-duplicate-progress イベントのペイロード
+export interface QueueDuplicateProgress {
+  operation_id: string; // cancel_operation に渡すと中断
+  processed: number;
+  total: number;
+  file_path: string;
+}
+
+export interface QueueDuplicateGroup {
+  file_hash: string;
+  file_size: number;
+  keep_item_id: string; // 先に追加されたアイテムを残す
+  keep_file_path: string;
+  duplicate_item_ids: string[];
+}
+
+export interface UploadedCopy {
+  file_path: string;
+  bucket: string;
+  s3_key: string;
+  uploaded_at: string;
+}
+
+export interface UploadedDuplicate {
+  item_id: string;
+  file_path: string;
+  file_size: number;
+  file_hash: string;
+  uploaded: UploadedCopy;
+}
+
+export interface DuplicateHashFailure {
+  item_id: string;
+  file_path: string;
+  error: string;
+}
+
+// analyze_queue_duplicates の結果（そのまま excludeQueueDuplicates に渡す）
+export interface QueueDuplicateReport {
+  analyzed_items: number;
+  hashed_items: number; // このチェックでハッシュを計算した件数
+  queue_groups: QueueDuplicateGroup[];
+  uploaded_duplicates: UploadedDuplicate[];
+  hash_failures: DuplicateHashFailure[];
+  estimated_savings_bytes: number;
+  analyzed_at: string;
 }
 
 export enum UploadStatus {
@@ -220,6 +923,8 @@ export enum UploadStatus {
   Failed = "Failed",
   Paused = "Paused",
   Cancelled = "Cancelled",
+  Warning = "Warning", // プライマリは完了、ミラーが失敗（retryMirrorで再実行）
+  Skipped = "Skipped", // 重複のため除外（理由は error_message）
 }
 
 export interface UploadProgressInfo {
@@ -230,12 +935,25 @@ export interface UploadProgressInfo {
   speed_mbps: number;
   eta_seconds?: number;
   status: UploadStatus;
+  current_part?: number; // 送信中のパート番号（1始まり、単純アップロードでは未設定）
+  total_parts?: number;
+  part_uploaded_bytes?: number; // 送信中のパートのうち送信済みのバイト数
+  part_total_bytes?: number;
+}
+
+// メモリ内の認証情報キャッシュの状態（認証情報そのものは含まない）
+export interface CredentialCacheStatus {
+  unlocked: boolean;
+  profile?: string | null;
+  region?: string | null;
+  unlocked_at?: string | null;
+  expires_at?: string | null;
 }
 
 // 新しいアップロードシステム用の型定義
 
+// 認証情報はバックエンドのキャッシュを使うため含めない（unlockCredentialsで事前にロック解除する）
 export interface UploadConfig {
-  aws_credentials: AwsCredentials;
   bucket_name: string;
   max_concurrent_uploads: number;
   chunk_size_mb: number;
@@ -252,6 +970,32 @@ export interface UploadConfig {
   bandwidth_limit_mbps?: number;       // 帯域制限（無料版: なし, プレミアム版: 設定可能）
   enable_resume: boolean;              // 中断・再開機能（無料版: false, プレミアム版: true）
   tier: 'Free' | 'Premium';           // 機能ティア
+  compress_non_video?: boolean;       // 動画以外のファイルをzstd圧縮してからアップロード
+  compression_level?: number;         // zstdの圧縮レベル（既定: 3）
+  block_uncovered_lifecycle_prefix?: boolean; // ライフサイクルルール対象外のキーを拒否（既定: 警告のみ）
+  read_concurrency?: ReadConcurrencyLimits;   // 同一ボリュームからの同時読み込み数
+  set_finder_tag_on_complete?: string;        // 検証完了後に付与するFinderタグ（例: "アーカイブ済み:green"）
+  set_readonly_after_upload?: boolean;        // 検証完了後にローカルファイルを読み取り専用にする
+  set_immutable_flag_after_upload?: boolean;  // 読み取り専用化の際にuchgフラグも付ける（macOSのみ）
+  write_local_manifest?: boolean;             // フォルダのアップロード完了時に.reelvault-manifest.jsonを書き出す
+  upload_manifest_to_s3?: boolean;            // マニフェストをS3の.reelvault/manifests/にもアップロードする
+  follow_symlinks?: boolean;                  // リンクを実体のパス・サイズで登録（false はスキップ、既定 true）
+  requeue_after_source_change_secs?: number; // 元ファイルの変更で失敗した場合、この秒数変化がなければ自動で再キュー
+  mirror_targets?: MirrorTarget[];            // プライマリの完了後に同じファイルをアップロードするバックアップ先
+  storage_class?: string;                     // アップロード時のストレージクラス（未設定はSTANDARD）
+  per_type_overrides?: UploadTypeOverride[];  // ファイル名パターンごとのチャンクサイズ・優先度・ストレージクラス
+  checksum_algorithm?: ChecksumAlgorithm;     // S3に受信データを検証させるチェックサム（未設定はnone＝ETagのみ）
+}
+
+// CRC32CはCPUのCRC命令で計算するため、大容量ファイルではSHA-256より負荷が小さい
+export type ChecksumAlgorithm = 'none' | 'crc32c' | 'sha256';
+
+// ボリューム種別ごとの同時読み込み数（既定: HDD 1 / SSD 4 / 外部 2）
+export interface ReadConcurrencyLimits {
+  hdd: number;
+  ssd: number;
+  external: number;
+  sequential_threshold_mb: number; // HDD上のこのサイズ以上のファイルはファイル単位で直列に読む（0で無効）
 }
 
 export interface UploadStatistics {
@@ -264,6 +1008,37 @@ export interface UploadStatistics {
   uploaded_bytes: number;
   average_speed_mbps: number;
   estimated_time_remaining?: number;
+  is_paused: boolean;
+  compression_saved_bytes: number; // 事前圧縮によって削減した転送量
+  mock_aws_mode: boolean; // モックAWSモードで実行中（実際にはS3へアップロードされない）
+  bandwidth_profile?: BandwidthProfile | null; // 現在適用中の帯域制限
+  warning_files?: number;    // プライマリは完了したがミラーが失敗しているファイル数
+  mirror_completed?: number; // ミラー先ごとに数えた成功件数
+  mirror_failed?: number;
+  limits: UploadLimitInfo;           // 現在のティアの制限と残量
+  warnings?: UploadStatusWarning[];  // 現在アクティブな警告
+}
+
+// 現在のティアの制限と残量（nullは無制限）
+export interface UploadLimitInfo {
+  tier: 'Free' | 'Premium';
+  max_files: number | null;
+  remaining_files: number | null;
+  max_total_size_bytes: number | null;
+  remaining_size_bytes: number | null;
+  bandwidth_limit_mbps: number | null; // 現在適用中の帯域制限（Mbps）
+  max_concurrent_uploads: number;      // 設定値とティア上限の小さい方
+}
+
+export type UploadWarningCode = 'lifecycle_not_configured' | 'credentials_expiring_soon' | 'low_disk_space';
+
+export interface UploadStatusWarning {
+  code: UploadWarningCode;
+  message: string;
+}
+
+export interface UploadQueuePauseState {
+  is_paused: boolean;
 }
 
 export interface FileSelection {
@@ -276,25 +1051,86 @@ export interface S3KeyConfig {
   prefix?: string;
   use_date_folder: boolean;
   preserve_directory_structure: boolean;
+  // 使えるトークン: {filename} {basename} {ext} {parent} {timestamp} {date} {date:%Y-%m-%d} {uuid} {hash8} {seq}
   custom_naming_pattern?: string;
 }
 
-export interface AppStatistics {
-  total_files_uploaded: number;
-  total_bytes_uploaded: number;
-  files_in_queue: number;
-  successful_uploads: number;
-  failed_uploads: number;
-  average_upload_speed_mbps: number;
+export interface NamingPatternPreset {
+  id: string;
+  label: string;
+  pattern: string;
 }
 
-export interface SystemStatus {
-  aws_connected: boolean;
-  disk_space_gb: number;
-  memory_usage_mb: number;
-  cpu_usage_percent: number;
-  network_available: boolean;
-  last_heartbeat: string;
+// 小ファイルのtarバンドル設定
+export interface BundleOptions {
+  small_file_threshold_bytes?: number; // このサイズ以下のファイルをまとめる（既定: 1MB）
+  db_path: string; // 目録を保存するメタデータDB
+  bundle_name?: string;
+}
+
+// 自動アーカイブ関連
+export type AutoArchiveHealth = 'disabled' | 'healthy' | 'degraded';
+
+export interface AutoArchiveUploadSettings {
+  config: UploadConfig;
+  s3_key_config: S3KeyConfig;
+  ensure_lifecycle?: boolean; // 標準ライフサイクルポリシーを確認・有効化（既定: true）
+}
+
+export interface AutoArchiveStatus {
+  enabled: boolean;
+  health: AutoArchiveHealth;
+  reason?: string; // degraded の理由
+  watch_path?: string;
+  queued_count: number;
+  last_queued_at?: string;
+}
+
+export interface BundleLookupResult {
+  bundle_key: string;
+  entry_path: string; // バンドル内のパス
+  source_path: string;
+  file_size: number;
+  file_hash: string;
+  created_at: string;
+}
+
+export interface MetadataJobStatus {
+  pending: number;
+  running: string[]; // 実行中のファイルパス
+  completed: number;
+  failed: number;
+  concurrency: number;
+  last_error?: string;
+}
+
+export interface AppStatistics {
+  total_files_uploaded: number;
+  total_bytes_uploaded: number;
+  files_in_queue: number;
+  successful_uploads: number;
+  failed_uploads: number;
+  average_upload_speed_mbps: number;
+}
+
+export interface SystemStatus {
+  aws_connected: boolean;
+  disk_space_gb: number;
+  memory_usage_mb: number;
+  cpu_usage_percent: number;
+  network_available: boolean;
+  last_heartbeat: string;
+  sleep_prevented: boolean; // 転送中のためシステムスリープを抑止中
+  upload_readiness?: UploadReadinessSnapshot | null;
+}
+
+// check_upload_readiness の結果キャッシュ（悪化時は "upload-readiness-changed" イベントでも通知）
+export interface UploadReadinessSnapshot {
+  bucket: string;
+  safe: boolean;
+  lifecycle_healthy: boolean;
+  message: string;
+  checked_at: string; // RFC3339
 }
 
 export interface StateUpdate {
@@ -302,6 +1138,528 @@ export interface StateUpdate {
   value: any;
 }
 
+// ===== エラー関連の型定義 =====
+
+export type ErrorCode =
+  | 'AWS_S3_ERROR'
+  | 'AWS_STS_ERROR'
+  | 'AWS_CONFIG_ERROR'
+  | 'CONFIG_ERROR'
+  | 'FILE_ERROR'
+  | 'DATABASE_ERROR'
+  | 'AUTH_ERROR'
+  | 'ENCRYPTION_ERROR'
+  | 'METADATA_ERROR'
+  | 'OPERATION_CANCELLED'
+  | 'UNKNOWN_ERROR';
+
+// アップロード系・AWS系コマンドが返す構造化エラー
+export interface AppError {
+  code: ErrorCode;
+  message: string;
+  details?: string | null;
+  retryable: boolean;
+  message_key?: string; // メッセージカタログのキー（例: "upload.item_not_found"）
+  params?: Record<string, string>; // message_keyの文言に埋め込まれたパラメータ
+}
+
+// ===== 自動起動API関連 =====
+
+export interface AutostartStatus {
+  enabled: boolean;     // OSのログイン項目に登録されているか
+  configured: boolean;  // AppConfigに保存されている設定値
+}
+
+export interface LaunchContext {
+  launched_hidden: boolean; // ログイン時の自動起動（--hidden）で起動された
+}
+
+// ===== 終了API関連 =====
+
+export interface QuitRequest {
+  in_progress_count: number; // 進行中のアップロード件数
+  message: string;           // 確認ダイアログの文言
+}
+
+// ===== キューのエクスポート・インポート関連 =====
+
+export type QueueImportMode = 'append' | 'replace';
+
+export interface QueueConfigSummary {
+  bucket_name: string;
+  region: string;
+  tier: 'Free' | 'Premium';
+  chunk_size_mb: number;
+  max_concurrent_uploads: number;
+  s3_key_prefix?: string;
+}
+
+export interface QueueImportResult {
+  mode: QueueImportMode;
+  imported_count: number;
+  skipped_count: number;
+  skipped_files: string[]; // ファイルが存在しないためスキップしたパス
+  config_summary?: QueueConfigSummary;
+}
+
+// ===== アップロード履歴API関連 =====
+
+export interface UploadHistoryEntry {
+  item: UploadItem;
+  finished_at: string; // 完了・失敗した日時（失敗時は開始日時）
+  archived_at: string;
+}
+
+export interface UploadHistoryArchiveResult {
+  archived: number;
+  remaining_in_queue: number;
+  retention_days: number;
+}
+
+export interface UploadHistory {
+  entries: UploadHistoryEntry[];
+  total_archived: number;
+  statistics: AppStatistics;
+}
+
+// ===== プロジェクト（案件）関連 =====
+
+export interface Project {
+  id: string;
+  name: string;
+  client?: string;
+  description?: string;
+  default_s3_prefix?: string; // キュー追加時にS3キーの先頭へ付ける
+  created_at: string;
+}
+
+export interface NewProject {
+  id?: string; // 省略時はUUIDを採番
+  name: string;
+  client?: string;
+  description?: string;
+  default_s3_prefix?: string;
+}
+
+export interface StorageClassUsage {
+  storage_class: string; // アップロード検証時のクラス（不明は "UNKNOWN"）
+  file_count: number;
+  total_bytes: number;
+}
+
+export interface ProjectSummary {
+  project: Project;
+  file_count: number;
+  total_bytes: number;
+  uploaded_count: number;
+  uploaded_bytes: number;
+  uploaded_ratio: number; // 0.0〜1.0（ファイル数基準）
+  storage_classes: StorageClassUsage[];
+}
+
+export interface ProjectAssignmentResult {
+  assigned: number;
+  not_found: string[];
+}
+
+// ===== ネットワーク状態関連 =====
+
+export interface NetworkStatusChange {
+  online: boolean;       // オンラインかどうか
+  paused_count: number;  // オフライン検知で一時停止したアイテム数
+  resumed_count: number; // オンライン復帰で再開したアイテム数
+}
+
+// ===== モックAWSモード関連 =====
+
+export interface MockAwsStatus {
+  enabled: boolean;
+  simulated_speed_mbps: number;
+  label: string; // モック中のレスポンスに付与されるラベル（"[MOCK]"）
+}
+
+// ===== ライセンスAPI関連 =====
+
+export type LicenseState = 'unlicensed' | 'active' | 'expired' | 'invalid';
+
+// ティアごとの機能ゲート
+export interface TierPolicy {
+  tier: 'Free' | 'Premium';
+  max_concurrent_uploads: number;
+  max_concurrent_parts: number;
+  min_chunk_size_mb: number;
+  max_chunk_size_mb: number;
+  adaptive_chunk_size: boolean;
+  bandwidth_limit: boolean;
+  resume: boolean;
+  custom_lifecycle: boolean;
+  max_queue_files?: number; // 未設定は無制限
+  max_total_size_mb?: number; // 未設定は無制限
+}
+
+export interface LicenseClaims {
+  license_id: string;
+  licensee: string;
+  tier: 'Free' | 'Premium';
+  issued_at: string;
+  expires_at?: string;
+}
+
+export interface LicenseStatus {
+  state: LicenseState;
+  tier: 'Free' | 'Premium'; // 実際に適用されるティア（有効なライセンス以外は無料版）
+  claims?: LicenseClaims;
+  policy: TierPolicy;
+  message?: string;
+}
+
+// ===== メトリクスAPI関連 =====
+
+export interface HistogramBucket {
+  le_ms?: number; // バケット上限（未設定は上限なし）
+  count: number;
+}
+
+export interface HistogramSnapshot {
+  count: number;
+  mean_ms: number;
+  max_ms: number;
+  buckets?: HistogramBucket[]; // 詳細ヒストグラム有効時（ログレベルdebug）のみ
+}
+
+export interface MetricsSnapshot {
+  detailed_histograms: boolean;
+  part_upload_duration: HistogramSnapshot;
+  part_retries: number;
+  upload_retries: number;
+  s3_api_calls: number;
+  s3_api_failures: number;
+  queue_wait: HistogramSnapshot;
+  lock_wait: HistogramSnapshot;
+  command_duration: HistogramSnapshot;
+  slow_commands: number;
+  checksum_crc32c: ChecksumMetricsSnapshot;
+  checksum_sha256: ChecksumMetricsSnapshot;
+}
+
+// 完全性検証のためのチェックサム計算（アルゴリズムごと）
+export interface ChecksumMetricsSnapshot {
+  duration: HistogramSnapshot;
+  bytes: number;
+  throughput_mbps: number; // MB/s
+}
+
+export interface CommandPerformance {
+  command: string;
+  calls: number;
+  slow_calls: number;
+  mean_ms: number;
+  max_ms: number;
+}
+
+// command-timingイベント（ログレベルdebug時のみ）
+export interface CommandTiming {
+  command: string;
+  duration_ms: number;
+  arguments: string;
+  slow: boolean;
+}
+
+// ===== S3リクエスト数・概算料金API関連 =====
+
+export type S3ApiKind =
+  | 'put_object'
+  | 'create_multipart_upload'
+  | 'upload_part'
+  | 'complete_multipart_upload'
+  | 'abort_multipart_upload'
+  | 'list_objects'
+  | 'get_object'
+  | 'head_object'
+  | 'head_bucket'
+  | 'delete_object'
+  | 'restore_object'
+  | 'copy_object'
+  | 'get_object_tagging'
+  | 'put_object_tagging'
+  | 'bucket_configuration';
+
+export type ApiUsagePeriod =
+  | { type: 'today' }
+  | { type: 'current_month' }
+  | { type: 'last_days'; days: number };
+
+export interface ApiUsageEntry {
+  api: S3ApiKind;
+  requests: number;
+  estimated_cost_usd: number;
+}
+
+export interface ApiUsageSummary {
+  from: string; // YYYY-MM-DD
+  to: string;
+  region: string;
+  pricing_region: string; // 単価表にないリージョンは us-east-1 の単価で概算
+  total_requests: number;
+  estimated_cost_usd: number;
+  by_api: ApiUsageEntry[];
+}
+
+// ===== 転送量の月次上限API関連 =====
+
+// stop: 新しい転送を始めない（進行中は完了させる） / warn_only: 警告のみ
+export type TransferCapAction = 'stop' | 'warn_only';
+
+export type TransferCapLevel = 'warning' | 'reached'; // 上限の80%以上 / 100%以上
+
+export interface TransferUsage {
+  month: string; // YYYY-MM
+  uploaded_bytes: number;
+  downloaded_bytes: number;
+  total_bytes: number;
+  cap_bytes: number | null;
+  remaining_bytes: number | null;
+  used_ratio: number | null; // 1.0で上限
+  action: TransferCapAction;
+  overridden: boolean; // 今月分の上限を手動で解除した
+  blocked: boolean; // 上限に達して新しい転送を止めている
+}
+
+// transfer-cap-warning イベント
+export interface TransferCapAlert {
+  level: TransferCapLevel;
+  usage: TransferUsage;
+}
+
+// upload-stalled イベント（キュー処理の停滞を検出し、自動で再起動した）
+export interface UploadStalledEvent {
+  detected_at: string;
+  stalled_secs: number;
+  pending_count: number;
+  in_progress_count: number;
+  active_upload_count: number;
+  requeued_count: number; // 再起動のため待機中へ戻したアイテム数
+}
+
+// ===== 取り込みフォルダ（Hot Folder）API関連 =====
+
+// アップロード後の元ファイルの扱い
+export type HotFolderAfterUpload =
+  | { type: 'keep' }
+  | { type: 'delete' }
+  | { type: 'move'; destination: string };
+
+export interface HotFolderSettings {
+  path: string;
+  s3_prefix: string;
+  after_upload: HotFolderAfterUpload;
+}
+
+export interface HotFolderError {
+  message: string;
+  file_path?: string;
+  occurred_at: string;
+}
+
+// hot-folder-status-changed イベントのペイロードも同じ形
+export interface HotFolderStatus {
+  enabled: boolean;
+  settings?: HotFolderSettings;
+  processed_count: number; // 有効化してから処理済みにしたファイル数
+  pending_count: number; // 書き込み完了待ち・アップロード中のファイル数
+  last_error?: HotFolderError;
+}
+
+// ===== アプリのデータ使用量API関連 =====
+
+// db は集計のみで削除しない
+export type AppDataCategory = 'logs' | 'thumbnails' | 'db' | 'backups' | 'temp';
+
+export interface AppDataCleanupSettings {
+  enabled: boolean;
+  categories: AppDataCategory[];
+  older_than_days: number; // 最終更新からこの日数を過ぎたファイルを削除する
+}
+
+export interface AppDataCategoryUsage {
+  category: AppDataCategory;
+  size_bytes: number;
+  file_count: number;
+  deletable: boolean;
+  protected_bytes: number; // 削除の対象外（DB・書き込み中のログ・使用中のファイル）のサイズ
+}
+
+export interface AppDataUsage {
+  categories: AppDataCategoryUsage[];
+  total_bytes: number;
+  total_files: number;
+}
+
+export interface AppDataCleanupCategoryResult {
+  category: AppDataCategory;
+  removed_files: number;
+  freed_bytes: number;
+  skipped_files: number; // 保護対象・新しいため残したファイル数
+}
+
+export interface AppDataCleanupResult {
+  categories: AppDataCleanupCategoryResult[];
+  freed_bytes: number;
+  removed_files: number;
+  errors: string[];
+}
+
+// ===== フロントエンドログ（統合ログ）API関連 =====
+
+export type FrontendLogLevel = 'error' | 'warn' | 'info' | 'debug' | 'trace';
+
+export interface FrontendLogResult {
+  accepted: boolean; // レートリミット（毎秒100件）超過で破棄した場合false
+  truncated: boolean; // メッセージまたはcontextを上限で切り詰めた
+  dropped_total: number;
+}
+
+// ===== ヘルスレポートAPI関連 =====
+
+export interface HealthReport {
+  generated_at: string; // RFC3339
+  healthy: boolean;
+  issues: string[];
+  watchers: {
+    active_watchers: number;
+    last_event_at?: string;
+    auto_archive: AutoArchiveHealth;
+    auto_archive_reason?: string;
+  };
+  queue: {
+    total_count: number;
+    pending_count: number;
+    failed_count: number;
+  };
+  trackers: {
+    restore_in_progress: number;
+    restore_failed: number;
+  };
+  lifecycle: {
+    bucket?: string;
+    enabled?: boolean;
+    error?: string;
+  };
+  auth: {
+    credentials_configured: boolean;
+    temporary_credentials: boolean;
+    license_state: LicenseState;
+    license_expires_at?: string;
+    license_days_remaining?: number;
+  };
+  disk: {
+    path: string;
+    available_bytes?: number;
+    total_bytes?: number;
+  };
+  api_usage?: ApiUsageSummary; // 今月分
+  archive_transition?: ArchiveTransitionStatus; // 未移行オブジェクトは先頭10件のみ
+}
+
+// ===== クイックアップロードAPI関連 =====
+
+export type QuickUploadSource = 'dialog' | 'clipboard';
+
+export interface QuickUploadSettings {
+  shortcut?: string | null; // 例: "CmdOrCtrl+Shift+U"（null で無効）
+  source: QuickUploadSource;
+}
+
+export interface QuickUploadStatus {
+  settings: QuickUploadSettings;
+  registered_shortcut?: string | null;
+}
+
+// ===== ドラッグ＆ドロップ関連 =====
+
+// "files-dropped" イベントのペイロード
+export interface DroppedFiles {
+  file_count: number;
+  total_size: number;
+  folder_count: number;
+  skipped_count: number;
+  truncated: boolean; // 上限件数で展開を打ち切った
+  requires_confirmation: boolean; // true の場合は confirmDroppedFiles で確定する
+  error?: string | null;
+}
+
+// ===== 整合性検証API関連 =====
+
+export type VerifyScope =
+  | { type: 'all' }
+  | { type: 'tag'; tag: string }
+  | { type: 'period'; from?: string | null; to?: string | null } // アップロード日時（RFC3339）
+  | { type: 'manifest'; folder: string }; // フォルダの.reelvault-manifest.jsonに記録されたファイル
+
+export type VerifyOutcome = 'ok' | 'missing' | 'mismatch';
+
+export interface RemoteVerifyEntry {
+  file_path: string;
+  s3_key: string;
+  outcome: VerifyOutcome;
+  expected_size: number;
+  remote_size?: number | null;
+  hash_checked: boolean; // x-amz-meta-sha256 まで照合できたか
+  detail?: string | null;
+}
+
+export interface RemoteVerifyReport {
+  bucket: string;
+  started_at: string;
+  completed_at: string;
+  checked_count: number;
+  ok_count: number;
+  missing_count: number;
+  mismatch_count: number;
+  hash_checked_count: number;
+  problems: RemoteVerifyEntry[];
+  errors: string[];
+}
+
+// "remote-verify-progress" イベントのペイロード
+export interface RemoteVerifyProgress {
+  checked: number;
+  total: number;
+  missing_count: number;
+  mismatch_count: number;
+}
+
+// ===== 差分同期API関連 =====
+
+export interface SyncOptions {
+  delete_remote?: boolean;     // ローカルに無いS3オブジェクトを削除（デフォルトoff）
+  dry_run?: boolean;           // 差分の算出のみ行う
+  include_patterns?: string[]; // 対象パターン（例: "*.mp4"）
+  exclude_patterns?: string[]; // 除外パターン
+}
+
+export type FileSyncStatus = 'New' | 'Modified' | 'Unchanged' | 'RemoteOnly';
+
+export interface FileComparison {
+  relative_path: string;
+  local_path?: string;
+  s3_key: string;
+  local_size?: number;
+  remote_size?: number;
+  status: FileSyncStatus;
+}
+
+export interface SyncSummary {
+  dry_run: boolean;
+  queued_count: number;           // キューへ追加した（dry_runでは追加予定の）件数
+  skipped_count: number;          // 同期済みのためスキップした件数
+  delete_candidate_count: number; // ローカルに無いS3オブジェクトの件数
+  deleted_count: number;          // 実際に削除した件数
+  queued_keys: string[];
+  delete_candidates: string[];
+  errors: string[];
+}
+
 // ===== Tauri Command API関数の型定義 =====
 
 import { invoke } from '@tauri-apps/api/core';
@@ -322,8 +1680,15 @@ export const TauriCommands = {
   testWatchSystem: (config: WatchConfig): Promise<string> =>
     invoke('test_watch_system', { config }),
     
-  getSampleWatchConfigs: (): Promise<WatchConfig[]> =>
-    invoke('get_sample_watch_configs'),
+  // ホーム配下の実在ディレクトリで生成した監視設定のプリセット（config を編集して watchDirectory に渡せる）
+  getWatchConfigPresets: (): Promise<WatchConfigPreset[]> =>
+    invoke('get_watch_config_presets'),
+
+  getDefaultExcludePatterns: (): Promise<DefaultExcludePatterns> =>
+    invoke('get_default_exclude_patterns'),
+
+  getWatcherStatuses: (): Promise<WatcherStatus[]> =>
+    invoke('get_watcher_statuses'),
 
   // AWS操作API
   testAwsConnection: (config: AwsConfig): Promise<ConnectionTestResult> =>
@@ -334,9 +1699,43 @@ export const TauriCommands = {
   
   listS3Objects: (config: AwsConfig, prefix?: string): Promise<S3Object[]> =>
     invoke('list_s3_objects', { config, prefix }),
+
+  // ネットワークなしでキャッシュを閲覧・検索（last_synced_at がキャッシュの鮮度）
+  browseS3Cached: (bucket: string, prefix?: string, filter?: S3CacheFilter): Promise<S3CacheBrowseResult> =>
+    invoke('browse_s3_cached', { bucket, prefix, filter }),
+
+  refreshS3Cache: (config: AwsConfig, prefix?: string): Promise<S3CacheRefreshResult> =>
+    invoke('refresh_s3_cache', { config, prefix }),
   
-  restoreFile: (s3Key: string, config: AwsConfig, tier: string): Promise<RestoreInfo> =>
-    invoke('restore_file', { s3Key, config, tier }),
+  // fallbackToStandard: Expeditedがキャパシティ不足のときStandardで再リクエスト（既定true）
+  restoreFile: (s3Key: string, config: AwsConfig, tier: string, versionId?: string, days?: number, fallbackToStandard?: boolean): Promise<RestoreInfo> =>
+    invoke('restore_file', { s3Key, config, tier, versionId, days, fallbackToStandard }),
+
+  restoreFilesBulk: (s3Keys: string[], config: AwsConfig, tier: string, days?: number): Promise<BulkRestoreResult> =>
+    invoke('restore_files_bulk', { s3Keys, config, tier, days }),
+
+  restorePrefix: (config: AwsConfig, prefix: string, tier: string, days: number | undefined, confirm: boolean): Promise<PrefixRestoreResponse> =>
+    invoke('restore_prefix', { config, prefix, tier, days, confirm }),
+
+  listPrefixRestoreJobs: (): Promise<PrefixRestoreJob[]> =>
+    invoke('list_prefix_restore_jobs'),
+
+  cancelPrefixRestore: (prefix: string): Promise<PrefixRestoreJob> =>
+    invoke('cancel_prefix_restore', { prefix }),
+
+  // 復元した作業コピー（削除予定日の早い順）
+  listRestoredFiles: (): Promise<RestoredFile[]> =>
+    invoke('list_restored_files'),
+
+  extendRestoredFileRetention: (localPath: string, days?: number): Promise<RestoredFile> =>
+    invoke('extend_restored_file_retention', { localPath, days }),
+
+  // 長時間操作の中断API（未知のIDは false を返す）
+  cancelOperation: (operationId: string): Promise<boolean> =>
+    invoke('cancel_operation', { operationId }),
+
+  listOperations: (): Promise<OperationInfo[]> =>
+    invoke('list_operations'),
 
   // AWS認証API
   authenticateAws: (credentials: AwsCredentials): Promise<AwsAuthResult> =>
@@ -351,9 +1750,21 @@ export const TauriCommands = {
   loadAwsCredentialsSecure: (profileName: string): Promise<AwsCredentials> =>
     invoke('load_aws_credentials_secure', { profileName }),
 
+  getCachedCredentialsStatus: (): Promise<CredentialCacheStatus> =>
+    invoke('get_cached_credentials_status'),
+
+  unlockCredentials: (profile?: string): Promise<CredentialCacheStatus> =>
+    invoke('unlock_credentials', { profile }),
+
+  lockCredentials: (): Promise<CredentialCacheStatus> =>
+    invoke('lock_credentials'),
+
   // 設定管理API
   getConfig: (): Promise<AppConfig> =>
     invoke('get_config'),
+
+  getConfigRecovery: (): Promise<ConfigRecovery | null> =>
+    invoke('get_config_recovery'),
   
   setConfig: (config: AppConfig): Promise<boolean> =>
     invoke('set_config', { config }),
@@ -387,6 +1798,18 @@ export const TauriCommands = {
   // 状態管理API
   getAppState: (): Promise<AppState> =>
     invoke('get_app_state'),
+
+  getStartupDiagnostics: (): Promise<StartupDiagnostics | null> =>
+    invoke('get_startup_diagnostics'),
+
+  restoreMetadataDbBackup: (): Promise<StartupCheck> =>
+    invoke('restore_metadata_db_backup'),
+
+  getMetadataDbRecovery: (): Promise<MetadataDbRecovery | null> =>
+    invoke('get_metadata_db_recovery'),
+
+  getStartupProgress: (): Promise<StartupProgress> =>
+    invoke('get_startup_progress'),
   
   setAppState: (newState: AppState): Promise<string> =>
     invoke('set_app_state', { newState }),
@@ -399,6 +1822,9 @@ export const TauriCommands = {
   
   removeUploadItem: (itemId: string): Promise<string> =>
     invoke('remove_upload_item', { itemId }),
+
+  removeUploadItems: (itemIds: string[]): Promise<BatchOperationResult> =>
+    invoke('remove_upload_items', { itemIds }),
   
   updateSystemStats: (): Promise<SystemStatus> =>
     invoke('update_system_stats'),
@@ -407,7 +1833,7 @@ export const TauriCommands = {
     invoke('reset_app_state'),
 
   // メタデータ管理API
-  initializeMetadataDb: (): Promise<string> =>
+  initializeMetadataDb: (): Promise<MetadataDbInitResult> =>
     invoke('initialize_metadata_db'),
   
   createFileMetadata: (filePath: string): Promise<FileMetadata> =>
@@ -428,6 +1854,12 @@ export const TauriCommands = {
   getAllTags: (): Promise<string[]> =>
     invoke('get_all_tags'),
 
+  findFileInBundles: (query: string, dbPath: string): Promise<BundleLookupResult[]> =>
+    invoke('find_file_in_bundles', { query, dbPath }),
+
+  getMetadataJobStatus: (): Promise<MetadataJobStatus> =>
+    invoke('get_metadata_job_status'),
+
   // アップロードシステムAPI
   initializeUploadQueue: (config: UploadConfig): Promise<string> =>
     invoke('initialize_upload_queue', { config }),
@@ -435,8 +1867,8 @@ export const TauriCommands = {
   openFileDialog: (multiple: boolean, fileTypes?: string[]): Promise<FileSelection> =>
     invoke('open_file_dialog', { multiple, fileTypes }),
   
-  addFilesToUploadQueue: (filePaths: string[], s3KeyConfig: S3KeyConfig): Promise<string[]> =>
-    invoke('add_files_to_upload_queue', { filePaths, s3KeyConfig }),
+  addFilesToUploadQueue: (filePaths: string[], s3KeyConfig: S3KeyConfig, bundleOptions?: BundleOptions, note?: string, projectId?: string): Promise<string[]> =>
+    invoke('add_files_to_upload_queue', { filePaths, s3KeyConfig, bundleOptions, note, projectId }),
   
   startUploadProcessing: (): Promise<string> =>
     invoke('start_upload_processing'),
@@ -447,11 +1879,29 @@ export const TauriCommands = {
   getUploadQueueStatus: (): Promise<UploadStatistics> =>
     invoke('get_upload_queue_status'),
   
-  getUploadQueueItems: (): Promise<UploadItem[]> =>
-    invoke('get_upload_queue_items'),
+  getUploadQueueItems: (projectId?: string): Promise<UploadItem[]> =>
+    invoke('get_upload_queue_items', { projectId }),
+
+  setUploadItemNote: (itemId: string, note?: string): Promise<UploadItem> =>
+    invoke('set_upload_item_note', { itemId, note }),
   
   retryUploadItem: (itemId: string): Promise<string> =>
     invoke('retry_upload_item', { itemId }),
+
+  retryUploadItems: (itemIds: string[]): Promise<BatchOperationResult> =>
+    invoke('retry_upload_items', { itemIds }),
+
+  analyzeQueueDuplicates: (): Promise<QueueDuplicateReport> =>
+    invoke('analyze_queue_duplicates'),
+
+  excludeQueueDuplicates: (report: QueueDuplicateReport): Promise<BatchOperationResult> =>
+    invoke('exclude_queue_duplicates', { report }),
+
+  updateUploadItem: (itemId: string, changes: UploadItemChanges): Promise<UploadItemUpdateResult> =>
+    invoke('update_upload_item', { itemId, changes }),
+
+  retryMirror: (itemId: string): Promise<number> =>
+    invoke('retry_mirror', { itemId }),
   
   clearUploadQueue: (): Promise<string> =>
     invoke('clear_upload_queue'),
@@ -459,15 +1909,36 @@ export const TauriCommands = {
   testUploadConfig: (config: UploadConfig): Promise<string> =>
     invoke('test_upload_config', { config }),
 
+  getNamingPatternPresets: (): Promise<NamingPatternPreset[]> =>
+    invoke('get_naming_pattern_presets'),
+
   // 復元機能API
   checkRestoreStatus: (s3Key: string, config: AwsConfig): Promise<RestoreStatusResult> =>
     invoke('check_restore_status', { s3Key, config }),
   
-  getRestoreNotifications: (): Promise<RestoreNotification[]> =>
-    invoke('get_restore_notifications'),
+  getRestoreNotifications: (unreadOnly?: boolean): Promise<RestoreNotification[]> =>
+    invoke('get_restore_notifications', { unreadOnly }),
+
+  markNotificationRead: (id: string): Promise<void> =>
+    invoke('mark_notification_read', { id }),
+
+  markAllNotificationsRead: (): Promise<number> =>
+    invoke('mark_all_notifications_read'),
+
+  deleteNotification: (id: string): Promise<void> =>
+    invoke('delete_notification', { id }),
   
-  downloadS3File: (s3Key: string, localPath: string, config: AwsConfig): Promise<DownloadProgress> =>
-    invoke('download_s3_file', { s3Key, localPath, config }),
+  downloadS3File: (s3Key: string, localPath: string, config: AwsConfig, autoDecompress?: boolean, versionId?: string, archiveGuard?: ArchiveGuardOptions): Promise<DownloadProgress> =>
+    invoke('download_s3_file', { s3Key, localPath, config, autoDecompress, versionId, archiveGuard }),
+
+  checkArchiveAccess: (config: AwsConfig, s3Key: string, versionId?: string, options?: ArchiveGuardOptions): Promise<ArchiveAccessCheck> =>
+    invoke('check_archive_access', { config, s3Key, versionId, options }),
+
+  getBucketVersioningStatus: (config: AwsConfig): Promise<BucketVersioningStatus> =>
+    invoke('get_bucket_versioning_status', { config }),
+
+  listObjectVersions: (config: AwsConfig, key: string): Promise<S3ObjectVersion[]> =>
+    invoke('list_object_versions', { config, key }),
   
   downloadRestoredFile: (s3Key: string, localPath: string, config: AwsConfig): Promise<DownloadProgress> =>
     invoke('download_restored_file', { s3Key, localPath, config }),
@@ -497,7 +1968,187 @@ export const TauriCommands = {
   validateLifecycleConfig: (config: AwsConfig): Promise<boolean> =>
     invoke('validate_lifecycle_config', { config }),
   
-  checkUploadReadiness: (config: AwsConfig): Promise<{ safe: boolean; message: string; lifecycle_healthy: boolean }> =>
-    invoke('check_upload_readiness', { config }),
+  checkUploadReadiness: (config: AwsConfig, s3KeyConfig?: S3KeyConfig, force?: boolean, includeSecurity?: boolean): Promise<UploadReadinessResult> =>
+    invoke('check_upload_readiness', { config, s3KeyConfig, force, includeSecurity }),
+
+  initializeBucketForReelvault: (config: AwsConfig): Promise<BucketInitReport> =>
+    invoke('initialize_bucket_for_reelvault', { config }),
+
+  checkBucketSecurity: (config: AwsConfig): Promise<BucketSecurityReport> =>
+    invoke('check_bucket_security', { config }),
+
+  checkArchiveTransitionStatus: (config: AwsConfig, prefix?: string): Promise<ArchiveTransitionStatus> =>
+    invoke('check_archive_transition_status', { config, prefix }),
+
+  // 自動起動API
+  setAutostart: (enabled: boolean): Promise<AutostartStatus> =>
+    invoke('set_autostart', { enabled }),
+
+  getAutostartStatus: (): Promise<AutostartStatus> =>
+    invoke('get_autostart_status'),
+
+  getLaunchContext: (): Promise<LaunchContext> =>
+    invoke('get_launch_context'),
+
+  // キューのエクスポート・インポートAPI
+  exportUploadQueue: (outputPath: string): Promise<number> =>
+    invoke('export_upload_queue', { outputPath }),
+
+  importUploadQueue: (inputPath: string, mode: QueueImportMode): Promise<QueueImportResult> =>
+    invoke('import_upload_queue', { inputPath, mode }),
+
+  // アップロード履歴API
+  archiveUploadHistory: (retentionDays?: number): Promise<UploadHistoryArchiveResult> =>
+    invoke('archive_upload_history', { retentionDays }),
+
+  getUploadHistory: (projectId?: string, limit?: number, offset?: number): Promise<UploadHistory> =>
+    invoke('get_upload_history', { projectId, limit, offset }),
+
+  // プロジェクト（案件）API（削除は割り当て解除のみでファイル・S3オブジェクトは残る）
+  createProject: (project: NewProject): Promise<Project> =>
+    invoke('create_project', { project }),
+
+  listProjects: (): Promise<Project[]> =>
+    invoke('list_projects'),
+
+  assignFilesToProject: (projectId: string | null, filePaths: string[]): Promise<ProjectAssignmentResult> =>
+    invoke('assign_files_to_project', { projectId, filePaths }),
+
+  getProjectSummary: (projectId: string): Promise<ProjectSummary> =>
+    invoke('get_project_summary', { projectId }),
+
+  deleteProject: (projectId: string): Promise<void> =>
+    invoke('delete_project', { projectId }),
+
+  // 自動アーカイブAPI
+  enableAutoArchive: (watchConfig: WatchConfig, uploadSettings: AutoArchiveUploadSettings): Promise<AutoArchiveStatus> =>
+    invoke('enable_auto_archive', { watchConfig, uploadSettings }),
+
+  disableAutoArchive: (): Promise<AutoArchiveStatus> =>
+    invoke('disable_auto_archive'),
+
+  getAutoArchiveStatus: (): Promise<AutoArchiveStatus> =>
+    invoke('get_auto_archive_status'),
+
+  // ローカル原本のリテンションAPI
+  getPendingRetentionCleanup: (): Promise<UploadRecord[]> =>
+    invoke('get_pending_retention_cleanup'),
+
+  confirmRetentionCleanup: (filePaths: string[]): Promise<RetentionCleanupResult> =>
+    invoke('confirm_retention_cleanup', { filePaths }),
+
+  dismissRetentionCleanup: (): Promise<number> =>
+    invoke('dismiss_retention_cleanup'),
+
+  // 操作の監査ログAPI
+  getAuditLog: (filter?: AuditLogFilter, limit?: number, offset?: number): Promise<OperationAuditEntry[]> =>
+    invoke('get_audit_log', { filter, limit, offset }),
+
+  exportAuditLog: (format: AuditExportFormat, outputPath: string, filter?: AuditLogFilter): Promise<number> =>
+    invoke('export_audit_log', { format, outputPath, filter }),
+
+  // モックAWSモードAPI
+  getMockAwsStatus: (): Promise<MockAwsStatus> =>
+    invoke('get_mock_aws_status'),
+
+  // ライセンスAPI
+  activateLicense: (key: string): Promise<LicenseStatus> =>
+    invoke('activate_license', { key }),
+
+  getLicenseStatus: (): Promise<LicenseStatus> =>
+    invoke('get_license_status'),
+
+  // メトリクスAPI
+  getMetrics: (): Promise<MetricsSnapshot> =>
+    invoke('get_metrics'),
+
+  resetMetrics: (): Promise<void> =>
+    invoke('reset_metrics'),
+
+  getCommandPerformanceStats: (): Promise<CommandPerformance[]> =>
+    invoke('get_command_performance_stats'),
+
+  // S3リクエスト数・概算料金API
+  getApiUsageSummary: (period: ApiUsagePeriod): Promise<ApiUsageSummary> =>
+    invoke('get_api_usage_summary', { period }),
+
+  // 転送量の月次上限API
+  getTransferUsage: (): Promise<TransferUsage> =>
+    invoke('get_transfer_usage'),
+
+  overrideTransferCap: (): Promise<TransferUsage> =>
+    invoke('override_transfer_cap'),
+
+  // フロントエンドログ（統合ログ）API
+  logFrontendEvent: (level: FrontendLogLevel, message: string, context?: unknown): Promise<FrontendLogResult> =>
+    invoke('log_frontend_event', { level, message, context }),
+
+  // アップロード後に読み取り専用にした原本の解除API
+  unlockLocalFile: (path: string): Promise<void> =>
+    invoke('unlock_local_file', { path }),
+
+  // 取り込みフォルダAPI
+  configureHotFolder: (path: string, s3Prefix: string, deleteAfterUpload: boolean, moveTo?: string): Promise<HotFolderStatus> =>
+    invoke('configure_hot_folder', { path, s3Prefix, deleteAfterUpload, moveTo }),
+
+  disableHotFolder: (): Promise<HotFolderStatus> =>
+    invoke('disable_hot_folder'),
+
+  getHotFolderStatus: (): Promise<HotFolderStatus> =>
+    invoke('get_hot_folder_status'),
+
+  // アプリのデータ使用量API
+  getAppDataUsage: (): Promise<AppDataUsage> =>
+    invoke('get_app_data_usage'),
+
+  cleanupAppData: (categories: AppDataCategory[], olderThanDays?: number): Promise<AppDataCleanupResult> =>
+    invoke('cleanup_app_data', { categories, olderThanDays }),
+
+  // ヘルスレポートAPI
+  getHealthReports: (limit?: number): Promise<HealthReport[]> =>
+    invoke('get_health_reports', { limit }),
+
+  // クイックアップロードAPI
+  setQuickUploadSettings: (settings: QuickUploadSettings): Promise<QuickUploadStatus> =>
+    invoke('set_quick_upload_settings', { settings }),
+
+  getQuickUploadStatus: (): Promise<QuickUploadStatus> =>
+    invoke('get_quick_upload_status'),
+
+  // ドラッグ＆ドロップAPI
+  confirmDroppedFiles: (accept: boolean): Promise<string | null> =>
+    invoke('confirm_dropped_files', { accept }),
+
+  // 自動アップロードの承認待ちAPI（filePaths 省略時はすべて）
+  listPendingAutoUploads: (): Promise<PendingAutoUpload[]> =>
+    invoke('list_pending_auto_uploads'),
+
+  approvePendingAutoUploads: (filePaths?: string[]): Promise<string | null> =>
+    invoke('approve_pending_auto_uploads', { filePaths }),
+
+  discardPendingAutoUploads: (filePaths?: string[]): Promise<number> =>
+    invoke('discard_pending_auto_uploads', { filePaths }),
+
+  // 整合性検証API
+  verifyRemoteIntegrity: (config: AwsConfig, scope: VerifyScope): Promise<RemoteVerifyReport> =>
+    invoke('verify_remote_integrity', { config, scope }),
+
+  // 差分同期API
+  compareLocalWithS3: (localDir: string, config: AwsConfig, s3Prefix: string): Promise<FileComparison[]> =>
+    invoke('compare_local_with_s3', { localDir, config, s3Prefix }),
+
+  syncDirectoryToS3: (localDir: string, config: AwsConfig, s3Prefix: string, options: SyncOptions): Promise<SyncSummary> =>
+    invoke('sync_directory_to_s3', { localDir, config, s3Prefix, options }),
+
+  // ログAPI（itemId 指定時はそのアップロードアイテムのログのみ、古い順）
+  getRecentLogs: (limit?: number, itemId?: string): Promise<string[]> =>
+    invoke('get_recent_logs', { limit, itemId }),
+
+  // 終了API
+  confirmQuit: (): Promise<void> =>
+    invoke('confirm_quit'),
+
+  forceQuit: (): Promise<void> =>
+    invoke('force_quit'),
 
 }; 
\ No newline at end of file
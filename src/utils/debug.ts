@@ -1,8 +1,11 @@
 /**
  * デバッグ用ヘルパー関数
  * 開発環境でのみconsole出力を行う
+ * 出力したログはバックエンドのログファイル（統合ログ）にも転送する
  */
 
+import { invoke } from '@tauri-apps/api/core';
+
 // シンプルな環境判定
 const isDevelopment = (import.meta as any).env?.DEV === true;
 
@@ -54,20 +57,59 @@ const checkLogLevelChange = (): LogLevel => {
   return currentLevel;
 };
 
+type BackendLogLevel = 'error' | 'warn' | 'info' | 'debug';
+
+// 引数をJSONにできる形へ変換（Errorや循環参照を含んでも失敗しない）
+const toLogContext = (args: any[]): unknown => {
+  if (args.length === 0) {
+    return null;
+  }
+  const seen = new WeakSet<object>();
+  try {
+    return JSON.parse(JSON.stringify(args, (_key, value) => {
+      if (value instanceof Error) {
+        return { name: value.name, message: value.message, stack: value.stack };
+      }
+      if (typeof value === 'object' && value !== null) {
+        if (seen.has(value)) {
+          return '[Circular]';
+        }
+        seen.add(value);
+      }
+      return value;
+    }));
+  } catch {
+    return args.map(arg => String(arg));
+  }
+};
+
+// バックエンドのログへ転送（Tauri外やレートリミット超過時は黙って捨てる）
+const forwardToBackend = (level: BackendLogLevel, message: any, args: any[]) => {
+  const text = message instanceof Error ? message.message : String(message);
+  try {
+    invoke('log_frontend_event', { level, message: text, context: toLogContext(args) }).catch(() => {});
+  } catch {
+    // Tauriのランタイムが無い環境（テスト・ブラウザ）では何もしない
+  }
+};
+
 // ログ出力制御関数
 export const logInfo = (message: any, ...args: any[]) => {
   // Info レベルは常に出力（標準的な動作ログ）
   console.log(message, ...args);
+  forwardToBackend('info', message, args);
 };
 
 export const logError = (message: any, ...args: any[]) => {
   // エラーは常に出力
   console.error(message, ...args);
+  forwardToBackend('error', message, args);
 };
 
 export const logWarn = (message: any, ...args: any[]) => {
   // 警告は常に出力
   console.warn(message, ...args);
+  forwardToBackend('warn', message, args);
 };
 
 export const logDebug = (message: any, ...args: any[]) => {
@@ -75,6 +117,7 @@ export const logDebug = (message: any, ...args: any[]) => {
   const currentLevel = checkLogLevelChange(); // 変更検知も実行
   if (currentLevel === 'debug') {
     console.log('[DEBUG]', message, ...args);
+    forwardToBackend('debug', message, args);
   }
 };
 